@@ -1,6 +1,8 @@
+use std::str::FromStr;
+
 use axum::http::{header, HeaderValue};
 use axum::{
-    body::Body,
+    body::{to_bytes, Body},
     extract::State,
     http::{Request, Response, StatusCode, Uri},
     response::{IntoResponse, Response as AxumResponse},
@@ -15,7 +17,7 @@ use sphare_core_user::session::ssr::LEPTOS_ENV;
 
 use sphare_cmp_utils::errors::ErrorTemplate;
 
-use sphare_app::app::{AppMeta, I18nProvider};
+use sphare_app::app::I18nProvider;
 
 pub async fn file_and_error_handler(
     uri: Uri,
@@ -25,11 +27,13 @@ pub async fn file_and_error_handler(
     let root = options.site_root.clone();
     let res = get_static_file(uri.clone(), &root).await.unwrap();
 
-    if res.status() == StatusCode::OK {
+    if res.status() != StatusCode::NOT_FOUND {
         res.into_response()
     } else {
+        let error = AppError::NotFound;
+        let status = error.http_status();
         let mut errors = Errors::default();
-        errors.insert_with_default_key(AppError::NotFound);
+        errors.insert_with_default_key(error);
         let handler = leptos_axum::render_app_to_stream(
             move || {
                 let errors = errors.clone();
@@ -40,7 +44,6 @@ pub async fn file_and_error_handler(
                         <head>
                             <meta charset="utf-8"/>
                             <meta name="viewport" content="width=device-width, initial-scale=1"/>
-                            <AppMeta/>
                             <AutoReload options=options.clone() />
                             // id=leptos means cargo-leptos will hot-reload this stylesheet
                             <HashedStylesheet id="leptos" options/>
@@ -55,7 +58,30 @@ pub async fn file_and_error_handler(
                 }
             },
         );
-        handler(req).await.into_response()
+        let mut response = handler(req).await.into_response();
+        *response.status_mut() = status;
+        response
+    }
+}
+
+/// Server functions returning an error are sent with a 500 status code. If the body of such a response is a serialized
+/// [`AppError`], replaces the status code with the one of the error.
+pub async fn set_server_fn_error_status(response: AxumResponse) -> AxumResponse {
+    if response.status() != StatusCode::INTERNAL_SERVER_ERROR {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => {
+            if let Some(error) = std::str::from_utf8(&bytes).ok().and_then(|body| AppError::from_str(body).ok()) {
+                parts.status = error.http_status();
+            }
+            AxumResponse::from_parts(parts, Body::from(bytes))
+        },
+        Err(e) => {
+            log::error!("Failed to read server function error response: {e}");
+            AxumResponse::from_parts(parts, Body::empty())
+        }
     }
 }
 
@@ -79,3 +105,52 @@ async fn get_static_file(uri: Uri, root: &str) -> Result<Response<Body>, (Status
     
     Ok(response)
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::body::{to_bytes, Body};
+    use axum::http::StatusCode;
+    use axum::response::Response;
+
+    use sphare_core_common::errors::AppError;
+
+    use crate::fallback::set_server_fn_error_status;
+
+    async fn get_remapped_status(status: StatusCode, body: String) -> (StatusCode, String) {
+        let response = Response::builder().status(status).body(Body::from(body)).unwrap();
+        let response = set_server_fn_error_status(response).await;
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.expect("Should read body.");
+        (status, String::from_utf8(body.to_vec()).expect("Body should be utf8."))
+    }
+
+    #[tokio::test]
+    async fn test_set_server_fn_error_status() {
+        let test_string = String::from("test");
+        for (error, expected_status) in [
+            (AppError::NotFound, StatusCode::NOT_FOUND),
+            (AppError::InsufficientPrivileges, StatusCode::FORBIDDEN),
+            (AppError::ValidationError(test_string.clone()), StatusCode::BAD_REQUEST),
+            (AppError::Conflict, StatusCode::CONFLICT),
+            (AppError::RateLimited(None), StatusCode::TOO_MANY_REQUESTS),
+            (AppError::InternalServerError(test_string.clone()), StatusCode::INTERNAL_SERVER_ERROR),
+        ] {
+            let error_string = error.to_string();
+            assert_eq!(
+                get_remapped_status(StatusCode::INTERNAL_SERVER_ERROR, error_string.clone()).await,
+                (expected_status, error_string),
+            );
+        }
+
+        // responses that are not server function errors are left unchanged
+        let not_found_string = AppError::NotFound.to_string();
+        assert_eq!(
+            get_remapped_status(StatusCode::OK, not_found_string.clone()).await,
+            (StatusCode::OK, not_found_string),
+        );
+        assert_eq!(
+            get_remapped_status(StatusCode::INTERNAL_SERVER_ERROR, test_string.clone()).await,
+            (StatusCode::INTERNAL_SERVER_ERROR, test_string),
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Instant;
 
-use axum::body::Body;
 use axum::http::HeaderValue;
 use axum::{body::Body as AxumBody, extract::{Path, State}, http::Request, response::{IntoResponse, Response}, routing::get, Router};
 use axum_session::{Key, SessionConfig, SessionLayer, SessionStore};
@@ -11,30 +11,45 @@ use axum_session_sqlx::SessionPgPool;
 use backoff::ExponentialBackoff;
 use base64::{engine::general_purpose, Engine};
 use leptos::prelude::*;
+use leptos::nonce::Nonce;
 use leptos_axum::{generate_route_list, handle_server_fns_with_context, LeptosRoutes};
 use sqlx::PgPool;
 use tokio_cron_scheduler::{Job, JobScheduler};
 
+use sphare_core_common::constants::POST_SCORE_UPDATE_BATCH_SIZE;
 use sphare_core_common::db_utils::ssr::create_db_pool;
 use sphare_core_common::errors::AppError;
-use sphare_core_content::post::ssr::update_post_scores;
+use sphare_core_common::routes::{POST_API_ROUTE, SPHERE_FEED_ROUTE};
+use sphare_core_content::api::ssr::post_json_handler;
+use sphare_core_content::feed::ssr::sphere_feed_handler;
+use sphare_core_content::post::ssr::{publish_scheduled_posts, recompute_post_comment_counts, update_post_scores};
+use sphare_core_content::search::ssr::run_saved_searches;
 use sphare_core_user::notification::ssr::delete_stale_notifications;
+use sphare_core_user::notification::NOTIF_RETENTION_DAYS;
 use sphare_core_user::session::ssr::{AuthSession, LEPTOS_ENV};
 use sphare_core_user::user::ssr::UserLockCache;
 use sphare_core_user::user::User;
 
 use sphare_app::app::*;
 
-use crate::fallback::file_and_error_handler;
+use crate::fallback::{file_and_error_handler, set_server_fn_error_status};
+use crate::health::{health_routes, JobHealthMonitor, DELETE_STALE_NOTIF_JOB, DELETE_STALE_NOTIF_MAX_DELAY, RECOMPUTE_COMMENT_COUNTS_JOB, RECOMPUTE_COMMENT_COUNTS_MAX_DELAY, RUN_SAVED_SEARCHES_JOB, RUN_SAVED_SEARCHES_MAX_DELAY, UPDATE_POST_SCORES_JOB, UPDATE_POST_SCORES_MAX_DELAY};
+use crate::security::{add_security_headers, SecurityHeadersConfig};
 use crate::state::AppState;
+use crate::telemetry::{get_metrics_addr, install_metrics_recorder, record_server_fn_call, serve_metrics};
 
 mod fallback;
+mod health;
+mod security;
 mod state;
+mod telemetry;
 
 pub const SESSION_KEY_ENV : &str = "SESSION_KEY";
 pub const SESSION_DB_KEY_ENV : &str = "SESSION_DB_KEY";
 pub const USER_LOCK_CACHE_SIZE_ENV : &str = "USER_LOCK_CACHE_SIZE";
 pub const POST_SCORE_UPDATE_INTERVAL_S_ENV : &str = "POST_SCORE_UPDATE_INTERVAL_S";
+pub const POST_SCORE_UPDATE_BATCH_SIZE_ENV : &str = "POST_SCORE_UPDATE_BATCH_SIZE";
+pub const NOTIFICATION_RETENTION_DAYS_ENV : &str = "NOTIFICATION_RETENTION_DAYS";
 
 pub fn get_session_key() -> Key {
     match std::env::var(SESSION_KEY_ENV) {
@@ -82,6 +97,44 @@ pub fn get_user_lock_cache_size() -> NonZeroUsize {
     }
 }
 
+pub fn get_post_score_update_batch_size() -> i64 {
+    match std::env::var(POST_SCORE_UPDATE_BATCH_SIZE_ENV) {
+        Ok(value) => {
+            log::debug!("Got post score update batch size from env variable.");
+            match i64::from_str(&value) {
+                Ok(value) if value > 0 => value,
+                _ => {
+                    log::error!("Could not parse post score update batch size as positive integer.");
+                    POST_SCORE_UPDATE_BATCH_SIZE
+                }
+            }
+        },
+        Err(_) => {
+            log::debug!("Could not find post score update batch size in env variable, take default value.");
+            POST_SCORE_UPDATE_BATCH_SIZE
+        }
+    }
+}
+
+pub fn get_notification_retention_days() -> i64 {
+    match std::env::var(NOTIFICATION_RETENTION_DAYS_ENV) {
+        Ok(value) => {
+            log::debug!("Got notification retention days from env variable.");
+            match i64::from_str(&value) {
+                Ok(value) if value > 0 => value,
+                _ => {
+                    log::error!("Could not parse notification retention days as positive integer.");
+                    NOTIF_RETENTION_DAYS
+                }
+            }
+        },
+        Err(_) => {
+            log::debug!("Could not find notification retention days in env variable, take default value.");
+            NOTIF_RETENTION_DAYS
+        }
+    }
+}
+
 async fn server_fn_handler(
     State(app_state): State<AppState>,
     auth_session: AuthSession,
@@ -90,14 +143,19 @@ async fn server_fn_handler(
 ) -> impl IntoResponse {
     log::debug!("{path:?}");
 
-    handle_server_fns_with_context(
+    let request_path = request.uri().path().to_string();
+    let start = Instant::now();
+    let response = handle_server_fns_with_context(
         move || {
             provide_context(auth_session.clone());
             provide_context(app_state.db_pool.clone());
             provide_context(app_state.user_lock_cache.clone());
         },
         request,
-    ).await
+    ).await.into_response();
+    let response = set_server_fn_error_status(response).await;
+    record_server_fn_call(&request_path, response.status(), start.elapsed());
+    response
 }
 
 async fn leptos_routes_handler(
@@ -108,10 +166,11 @@ async fn leptos_routes_handler(
     let leptos_options = app_state.leptos_options.clone();
     let db_pool = app_state.db_pool.clone();
     let user_lock_cache = app_state.user_lock_cache.clone();
+    let security_headers_config = app_state.security_headers_config.clone();
+    let nonce = Nonce::new();
+    let nonce_str = nonce.to_string();
 
-    let user_agent= UserAgentHeader {
-        value: req.headers().get("User-Agent").map(|value: &HeaderValue| value.to_str().unwrap_or_default().to_string())
-    };
+    let user_agent = req.headers().get("User-Agent").map(|value: &HeaderValue| value.to_str().unwrap_or_default().to_string());
 
     let handler = leptos_axum::render_route_with_context(
         app_state.routes.clone(),
@@ -119,33 +178,18 @@ async fn leptos_routes_handler(
             provide_context(auth_session.clone());
             provide_context(db_pool.clone());
             provide_context(user_lock_cache.clone());
-            provide_context(user_agent.clone());
+            provide_context(nonce.clone());
         },
         move || shell(leptos_options.clone()),
     );
     let mut response = handler(app_state, req).await.into_response();
-    add_security_headers(&mut response);
+    add_security_headers(&mut response, &security_headers_config, &nonce_str, user_agent.as_deref());
     response
 }
 
-fn add_security_headers(response: &mut Response<Body>) {
-    let headers = response.headers_mut();
-
-    headers.insert("X-Content-Type-Options", HeaderValue::from_static("nosniff"));
-    headers.insert("X-Frame-Options", HeaderValue::from_static("DENY"));
-    headers.insert("Referrer-Policy", HeaderValue::from_static("strict-origin-when-cross-origin"));
-    headers.insert("Permissions-Policy", HeaderValue::from_static("geolocation=(), microphone=(), camera=(), fullscreen=(self)"));
-    headers.insert("Cross-Origin-Opener-Policy", HeaderValue::from_static("same-origin"));
-    headers.insert("Cross-Origin-Resource-Policy", HeaderValue::from_static("same-origin"));
-    headers.insert("X-XSS-Protection", HeaderValue::from_static("0")); // legacy, but harmless
-    headers.insert(
-        "Strict-Transport-Security",
-        HeaderValue::from_static("max-age=31536000; includeSubDomains; preload"),
-    );
-}
-
 async fn update_post_scores_with_backoff(
     retry_duration: std::time::Duration,
+    batch_size: i64,
     db_pool: PgPool,
 ) -> Result<(), AppError> {
     let backoff_params = ExponentialBackoff {
@@ -153,12 +197,17 @@ async fn update_post_scores_with_backoff(
         ..Default::default()
     };
     backoff::future::retry(backoff_params, || async {
-        Ok(update_post_scores(&db_pool).await?)
+        let num_published_posts = publish_scheduled_posts(&db_pool).await?;
+        log::debug!("Published {num_published_posts} scheduled posts");
+        let num_updated_posts = update_post_scores(batch_size, &db_pool).await?;
+        log::debug!("Updated scores of {num_updated_posts} posts");
+        Ok(())
     }).await
 }
 
 async fn delete_stale_notifications_with_backoff(
     retry_duration: std::time::Duration,
+    retention_days: i64,
     db_pool: PgPool
 ) -> Result<(), AppError> {
     let backoff_params = ExponentialBackoff {
@@ -166,18 +215,54 @@ async fn delete_stale_notifications_with_backoff(
         ..Default::default()
     };
     backoff::future::retry(backoff_params, || async {
-        Ok(delete_stale_notifications(&db_pool).await?)
+        Ok(delete_stale_notifications(retention_days, &db_pool).await?)
     }).await
 }
 
-async fn schedule_update_post_score_job(scheduler: &mut JobScheduler, db_pool: PgPool) {
+async fn recompute_post_comment_counts_with_backoff(
+    retry_duration: std::time::Duration,
+    db_pool: PgPool
+) -> Result<(), AppError> {
+    let backoff_params = ExponentialBackoff {
+        max_elapsed_time: Some(retry_duration),
+        ..Default::default()
+    };
+    backoff::future::retry(backoff_params, || async {
+        let num_corrected_posts = recompute_post_comment_counts(&db_pool).await?;
+        log::debug!("Corrected comment count of {num_corrected_posts} posts");
+        Ok(())
+    }).await
+}
+
+async fn run_saved_searches_with_backoff(
+    retry_duration: std::time::Duration,
+    db_pool: PgPool
+) -> Result<(), AppError> {
+    let backoff_params = ExponentialBackoff {
+        max_elapsed_time: Some(retry_duration),
+        ..Default::default()
+    };
+    backoff::future::retry(backoff_params, || async {
+        let num_notifications = run_saved_searches(&db_pool).await?;
+        log::debug!("Created {num_notifications} saved search notifications");
+        Ok(())
+    }).await
+}
+
+async fn schedule_update_post_score_job(scheduler: &mut JobScheduler, db_pool: PgPool, job_health_monitor: Arc<JobHealthMonitor>) {
+    job_health_monitor.register_job(UPDATE_POST_SCORES_JOB, UPDATE_POST_SCORES_MAX_DELAY);
+    let batch_size = get_post_score_update_batch_size();
     scheduler.add(
         Job::new_async("0 */5 * * * *", move |_uuid, _l| {
             let pool = db_pool.clone();
+            let job_health_monitor = job_health_monitor.clone();
             let retry_duration = std::time::Duration::from_mins(3);
             Box::pin(async move {
-                match update_post_scores_with_backoff(retry_duration, pool).await {
-                    Ok(()) => log::debug!("Successfully updated posts' ranking timestamps"),
+                match update_post_scores_with_backoff(retry_duration, batch_size, pool).await {
+                    Ok(()) => {
+                        job_health_monitor.record_run(UPDATE_POST_SCORES_JOB);
+                        log::debug!("Successfully updated posts' ranking timestamps")
+                    },
                     Err(e) => log::error!("Failed to update posts' ranking timestamps after {} seconds with error: {e}", retry_duration.as_secs()),
                 }
             })
@@ -185,14 +270,20 @@ async fn schedule_update_post_score_job(scheduler: &mut JobScheduler, db_pool: P
     ).await.expect("Should schedule post scores update job");
 }
 
-async fn schedule_delete_stale_notif_job(scheduler: &mut JobScheduler, db_pool: PgPool) {
+async fn schedule_delete_stale_notif_job(scheduler: &mut JobScheduler, db_pool: PgPool, job_health_monitor: Arc<JobHealthMonitor>) {
+    job_health_monitor.register_job(DELETE_STALE_NOTIF_JOB, DELETE_STALE_NOTIF_MAX_DELAY);
+    let retention_days = get_notification_retention_days();
     scheduler.add(
         Job::new_async("0 0 0 * * *", move |_uuid, _l| {
             let retry_duration = std::time::Duration::from_mins(15);
             let pool = db_pool.clone();
+            let job_health_monitor = job_health_monitor.clone();
             Box::pin(async move {
-                match delete_stale_notifications_with_backoff(retry_duration, pool).await {
-                    Ok(()) => log::debug!("Successfully deleted stale notifications"),
+                match delete_stale_notifications_with_backoff(retry_duration, retention_days, pool).await {
+                    Ok(()) => {
+                        job_health_monitor.record_run(DELETE_STALE_NOTIF_JOB);
+                        log::debug!("Successfully deleted stale notifications")
+                    },
                     Err(e) => log::error!("Failed to deleted stale notifications after {} seconds with error: {e}", retry_duration.as_secs()),
                 }
             })
@@ -200,6 +291,46 @@ async fn schedule_delete_stale_notif_job(scheduler: &mut JobScheduler, db_pool:
     ).await.expect("Should schedule delete stale notification job");
 }
 
+async fn schedule_recompute_comment_counts_job(scheduler: &mut JobScheduler, db_pool: PgPool, job_health_monitor: Arc<JobHealthMonitor>) {
+    job_health_monitor.register_job(RECOMPUTE_COMMENT_COUNTS_JOB, RECOMPUTE_COMMENT_COUNTS_MAX_DELAY);
+    scheduler.add(
+        Job::new_async("0 0 3 * * *", move |_uuid, _l| {
+            let retry_duration = std::time::Duration::from_mins(15);
+            let pool = db_pool.clone();
+            let job_health_monitor = job_health_monitor.clone();
+            Box::pin(async move {
+                match recompute_post_comment_counts_with_backoff(retry_duration, pool).await {
+                    Ok(()) => {
+                        job_health_monitor.record_run(RECOMPUTE_COMMENT_COUNTS_JOB);
+                        log::debug!("Successfully recomputed posts' comment counts")
+                    },
+                    Err(e) => log::error!("Failed to recompute posts' comment counts after {} seconds with error: {e}", retry_duration.as_secs()),
+                }
+            })
+        }).expect("Should create recompute comment counts job")
+    ).await.expect("Should schedule recompute comment counts job");
+}
+
+async fn schedule_run_saved_searches_job(scheduler: &mut JobScheduler, db_pool: PgPool, job_health_monitor: Arc<JobHealthMonitor>) {
+    job_health_monitor.register_job(RUN_SAVED_SEARCHES_JOB, RUN_SAVED_SEARCHES_MAX_DELAY);
+    scheduler.add(
+        Job::new_async("0 0 * * * *", move |_uuid, _l| {
+            let retry_duration = std::time::Duration::from_mins(15);
+            let pool = db_pool.clone();
+            let job_health_monitor = job_health_monitor.clone();
+            Box::pin(async move {
+                match run_saved_searches_with_backoff(retry_duration, pool).await {
+                    Ok(()) => {
+                        job_health_monitor.record_run(RUN_SAVED_SEARCHES_JOB);
+                        log::debug!("Successfully ran saved searches")
+                    },
+                    Err(e) => log::error!("Failed to run saved searches after {} seconds with error: {e}", retry_duration.as_secs()),
+                }
+            })
+        }).expect("Should create run saved searches job")
+    ).await.expect("Should schedule run saved searches job");
+}
+
 #[tokio::main]
 async fn main() {
     simple_logger::init_with_level(log::Level::Info).expect("Should be able to initialize logging.");
@@ -207,6 +338,11 @@ async fn main() {
     let subscriber = tracing_subscriber::fmt().with_max_level(tracing::Level::ERROR).finish();
     tracing::subscriber::set_global_default(subscriber).expect("setting tracing default failed");
 
+    let metrics_handle = install_metrics_recorder();
+    if let Some(metrics_addr) = get_metrics_addr() {
+        tokio::spawn(serve_metrics(metrics_addr, metrics_handle));
+    }
+
     let pool = create_db_pool().await.expect("Failed to create db pool");
 
     sqlx::migrate!("../../migrations/")
@@ -214,9 +350,12 @@ async fn main() {
         .await
         .expect("Should be able to run SQLx migrations.");
 
+    let job_health_monitor = Arc::new(JobHealthMonitor::default());
     let mut scheduler = JobScheduler::new().await.expect("Should create Job Scheduler.");
-    schedule_update_post_score_job(&mut scheduler, pool.clone()).await;
-    schedule_delete_stale_notif_job(&mut scheduler, pool.clone()).await;
+    schedule_update_post_score_job(&mut scheduler, pool.clone(), job_health_monitor.clone()).await;
+    schedule_delete_stale_notif_job(&mut scheduler, pool.clone(), job_health_monitor.clone()).await;
+    schedule_recompute_comment_counts_job(&mut scheduler, pool.clone(), job_health_monitor.clone()).await;
+    schedule_run_saved_searches_job(&mut scheduler, pool.clone(), job_health_monitor.clone()).await;
     scheduler.start().await.expect("Scheduler should start");
 
     let session_config = SessionConfig::default()
@@ -250,6 +389,8 @@ async fn main() {
         db_pool: pool.clone(),
         user_lock_cache: Arc::new(UserLockCache::new(get_user_lock_cache_size())),
         routes: routes.clone(),
+        job_health_monitor,
+        security_headers_config: SecurityHeadersConfig::from_env(),
     };
 
     // build our application with a route
@@ -258,6 +399,8 @@ async fn main() {
             "/api/{id}",
             get(server_fn_handler).post(server_fn_handler)
         )
+        .route(SPHERE_FEED_ROUTE, get(sphere_feed_handler))
+        .route(POST_API_ROUTE, get(post_json_handler))
         .leptos_routes_with_handler(routes, get(leptos_routes_handler))
         .fallback(file_and_error_handler)
         .layer(
@@ -266,6 +409,7 @@ async fn main() {
             ).with_config(auth_config)
         )
         .layer(SessionLayer::new(session_store))
+        .merge(health_routes())
         .with_state(app_state);
 
     // run our app with hyper
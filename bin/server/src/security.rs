@@ -0,0 +1,285 @@
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use axum::body::Body;
+use axum::http::HeaderValue;
+use axum::response::Response;
+use regex::Regex;
+
+use sphare_app::app::{OEMBED_CONNECT_SRC, OEMBED_FRAME_SRC};
+
+pub const HSTS_ENABLED_ENV: &str = "HSTS_ENABLED";
+pub const PERMISSIONS_POLICY_ENV: &str = "PERMISSIONS_POLICY";
+pub const CSP_REPORT_ONLY_ENV: &str = "CSP_REPORT_ONLY";
+pub const CSP_ASSET_ORIGINS_ENV: &str = "CSP_ASSET_ORIGINS";
+
+pub const HSTS_HEADER_VALUE: &str = "max-age=31536000; includeSubDomains; preload";
+pub const DEFAULT_PERMISSIONS_POLICY: &str = "geolocation=(), microphone=(), camera=(), fullscreen=(self)";
+pub const CSP_HEADER: &str = "Content-Security-Policy";
+pub const CSP_REPORT_ONLY_HEADER: &str = "Content-Security-Policy-Report-Only";
+/// Origins of the development server's websocket used to hot-reload the site
+pub const DEV_CONNECT_SRC: &str = "https: ws://localhost:3001/ ws://127.0.0.1:3001/";
+
+static IOS_WEBKIT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new("(iPhone|iPad|iPod|iOS).*AppleWebKit").expect("iOS regex should be valid"));
+
+/// Builder of the Content-Security-Policy header. Scripts and styles are restricted to the nonce of the Leptos hydration scripts
+/// and the wasm module, except for inline style attributes. Images and media can come from any https origin because of link
+/// thumbnails, data and iframes are restricted to the oEmbed providers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentSecurityPolicy {
+    /// Additional origins serving scripts, styles, images, fonts or data (e.g. object storage or CDN)
+    pub asset_origins: Vec<String>,
+    /// Violations are only reported by browsers instead of being blocked
+    pub report_only: bool,
+}
+
+impl ContentSecurityPolicy {
+    pub fn header_name(&self) -> &'static str {
+        match self.report_only {
+            true => CSP_REPORT_ONLY_HEADER,
+            false => CSP_HEADER,
+        }
+    }
+
+    /// Returns the policy allowing the inline scripts and styles with the given `nonce`. iOS WebKit needs `'unsafe-eval'`
+    /// to run the wasm module, so it is allowed when `user_agent` is an iOS WebKit browser.
+    pub fn build(&self, nonce: &str, user_agent: Option<&str>) -> String {
+        let asset_sources: String = self.asset_origins.iter().map(|origin| format!(" {origin}")).collect();
+        let unsafe_eval = match user_agent.is_some_and(|user_agent| IOS_WEBKIT_REGEX.is_match(user_agent)) {
+            true => " 'unsafe-eval'",
+            false => "",
+        };
+        let dev_sources = match cfg!(debug_assertions) {
+            true => format!(" {DEV_CONNECT_SRC}"),
+            false => String::new(),
+        };
+        [
+            String::from("default-src 'none'"),
+            format!("script-src 'self' 'strict-dynamic' 'nonce-{nonce}' 'wasm-unsafe-eval'{unsafe_eval}{asset_sources}"),
+            format!("style-src 'self' 'nonce-{nonce}'{asset_sources}"),
+            // style attributes are rendered by components, e.g. for sphere accent colors and poll results
+            String::from("style-src-attr 'unsafe-inline'"),
+            format!("img-src 'self' data: blob: https:{asset_sources}"),
+            format!("font-src 'self'{asset_sources}"),
+            format!("connect-src 'self'{dev_sources} {OEMBED_CONNECT_SRC}{asset_sources}"),
+            String::from("media-src 'self' https:"),
+            format!("frame-src 'self' {OEMBED_FRAME_SRC}"),
+            String::from("object-src 'none'"),
+            String::from("base-uri 'self'"),
+            String::from("frame-ancestors 'none'"),
+        ].join("; ")
+    }
+}
+
+/// Configuration of the security headers added to the responses of the Leptos routes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecurityHeadersConfig {
+    /// Whether to add the Strict-Transport-Security header, should only be disabled when not served over HTTPS
+    pub enable_hsts: bool,
+    pub permissions_policy: HeaderValue,
+    pub content_security_policy: ContentSecurityPolicy,
+}
+
+impl Default for SecurityHeadersConfig {
+    fn default() -> Self {
+        Self {
+            enable_hsts: true,
+            permissions_policy: HeaderValue::from_static(DEFAULT_PERMISSIONS_POLICY),
+            content_security_policy: ContentSecurityPolicy::default(),
+        }
+    }
+}
+
+impl SecurityHeadersConfig {
+    pub fn from_env() -> Self {
+        Self::from_values(
+            std::env::var(HSTS_ENABLED_ENV).ok().as_deref(),
+            std::env::var(PERMISSIONS_POLICY_ENV).ok().as_deref(),
+            std::env::var(CSP_REPORT_ONLY_ENV).ok().as_deref(),
+            std::env::var(CSP_ASSET_ORIGINS_ENV).ok().as_deref(),
+        )
+    }
+
+    /// Builds the configuration from the given values, invalid or missing values are replaced by their defaults.
+    /// `csp_asset_origins` is a comma-separated list of origins, origins that could alter the policy are ignored.
+    pub fn from_values(
+        enable_hsts: Option<&str>,
+        permissions_policy: Option<&str>,
+        csp_report_only: Option<&str>,
+        csp_asset_origins: Option<&str>,
+    ) -> Self {
+        let default_config = Self::default();
+        let enable_hsts = parse_bool_or_default(enable_hsts, "HSTS flag", default_config.enable_hsts);
+        let permissions_policy = match permissions_policy.map(HeaderValue::from_str) {
+            Some(Ok(permissions_policy)) => permissions_policy,
+            Some(Err(_)) => {
+                log::error!("Could not parse permissions policy as header value.");
+                default_config.permissions_policy
+            },
+            None => default_config.permissions_policy,
+        };
+        let report_only = parse_bool_or_default(csp_report_only, "CSP report only flag", default_config.content_security_policy.report_only);
+        let asset_origins = csp_asset_origins
+            .map(|origins| origins
+                .split(',')
+                .map(str::trim)
+                .filter(|origin| !origin.is_empty())
+                .filter(|origin| match origin.contains([';', ',', '\'', '"']) || origin.contains(char::is_whitespace) {
+                    true => {
+                        log::error!("Ignore invalid CSP asset origin {origin}.");
+                        false
+                    },
+                    false => true,
+                })
+                .map(String::from)
+                .collect()
+            )
+            .unwrap_or(default_config.content_security_policy.asset_origins);
+        Self {
+            enable_hsts,
+            permissions_policy,
+            content_security_policy: ContentSecurityPolicy {
+                asset_origins,
+                report_only,
+            },
+        }
+    }
+}
+
+fn parse_bool_or_default(value: Option<&str>, name: &str, default_value: bool) -> bool {
+    match value.map(bool::from_str) {
+        Some(Ok(value)) => value,
+        Some(Err(_)) => {
+            log::error!("Could not parse {name} as boolean.");
+            default_value
+        },
+        None => default_value,
+    }
+}
+
+/// Adds the security headers to `response`, the Content-Security-Policy allows the inline scripts and styles with the given `nonce`
+pub fn add_security_headers(response: &mut Response<Body>, config: &SecurityHeadersConfig, nonce: &str, user_agent: Option<&str>) {
+    let headers = response.headers_mut();
+
+    headers.insert("X-Content-Type-Options", HeaderValue::from_static("nosniff"));
+    headers.insert("X-Frame-Options", HeaderValue::from_static("DENY"));
+    headers.insert("Referrer-Policy", HeaderValue::from_static("strict-origin-when-cross-origin"));
+    headers.insert("Permissions-Policy", config.permissions_policy.clone());
+    headers.insert("Cross-Origin-Opener-Policy", HeaderValue::from_static("same-origin"));
+    headers.insert("Cross-Origin-Resource-Policy", HeaderValue::from_static("same-origin"));
+    headers.insert("X-XSS-Protection", HeaderValue::from_static("0")); // legacy, but harmless
+    if config.enable_hsts {
+        headers.insert("Strict-Transport-Security", HeaderValue::from_static(HSTS_HEADER_VALUE));
+    }
+    let content_security_policy = &config.content_security_policy;
+    match HeaderValue::from_str(&content_security_policy.build(nonce, user_agent)) {
+        Ok(policy) => {
+            headers.insert(content_security_policy.header_name(), policy);
+        },
+        Err(e) => log::error!("Could not build Content-Security-Policy header: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::HeaderValue;
+    use axum::response::Response;
+
+    use sphare_app::app::{OEMBED_CONNECT_SRC, OEMBED_FRAME_SRC};
+
+    use crate::security::{add_security_headers, ContentSecurityPolicy, SecurityHeadersConfig, CSP_HEADER, CSP_REPORT_ONLY_HEADER, DEFAULT_PERMISSIONS_POLICY, DEV_CONNECT_SRC, HSTS_HEADER_VALUE};
+
+    #[test]
+    fn test_security_headers_config_from_values() {
+        assert_eq!(SecurityHeadersConfig::from_values(None, None, None, None), SecurityHeadersConfig::default());
+        assert_eq!(
+            SecurityHeadersConfig::from_values(Some("false"), Some("camera=(self)"), Some("true"), Some("https://cdn.test, ,https://assets.test")),
+            SecurityHeadersConfig {
+                enable_hsts: false,
+                permissions_policy: HeaderValue::from_static("camera=(self)"),
+                content_security_policy: ContentSecurityPolicy {
+                    asset_origins: vec![String::from("https://cdn.test"), String::from("https://assets.test")],
+                    report_only: true,
+                },
+            }
+        );
+        assert_eq!(
+            SecurityHeadersConfig::from_values(Some("no"), Some("invalid\npolicy"), Some("maybe"), Some("https://a.test; script-src *,'unsafe-inline'")),
+            SecurityHeadersConfig::default(),
+        );
+    }
+
+    #[test]
+    fn test_content_security_policy() {
+        let mut content_security_policy = ContentSecurityPolicy::default();
+        assert_eq!(content_security_policy.header_name(), CSP_HEADER);
+        content_security_policy.report_only = true;
+        assert_eq!(content_security_policy.header_name(), CSP_REPORT_ONLY_HEADER);
+
+        let policy = content_security_policy.build("abc", None);
+        let directive_vec: Vec<&str> = policy.split("; ").collect();
+        assert!(directive_vec.contains(&"default-src 'none'"));
+        assert!(directive_vec.contains(&"script-src 'self' 'strict-dynamic' 'nonce-abc' 'wasm-unsafe-eval'"));
+        assert!(directive_vec.contains(&"style-src 'self' 'nonce-abc'"));
+        assert!(directive_vec.contains(&"style-src-attr 'unsafe-inline'"));
+        assert!(directive_vec.contains(&"img-src 'self' data: blob: https:"));
+        assert!(directive_vec.contains(&"media-src 'self' https:"));
+        assert!(directive_vec.contains(&format!("frame-src 'self' {OEMBED_FRAME_SRC}").as_str()));
+        assert!(directive_vec.contains(&format!("connect-src 'self' {DEV_CONNECT_SRC} {OEMBED_CONNECT_SRC}").as_str()));
+        assert!(directive_vec.contains(&"object-src 'none'"));
+        assert!(directive_vec.contains(&"frame-ancestors 'none'"));
+        assert!(!policy.contains("'unsafe-eval'"));
+        assert!(!policy.contains("'unsafe-inline'"));
+
+        // iOS WebKit browsers need 'unsafe-eval' to run the wasm module
+        let ios_user_agent = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko)";
+        let policy = content_security_policy.build("abc", Some(ios_user_agent));
+        assert!(policy.contains("script-src 'self' 'strict-dynamic' 'nonce-abc' 'wasm-unsafe-eval' 'unsafe-eval';"));
+        let desktop_user_agent = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36";
+        assert!(!content_security_policy.build("abc", Some(desktop_user_agent)).contains("'unsafe-eval'"));
+
+        content_security_policy.asset_origins = vec![String::from("https://cdn.test")];
+        let policy = content_security_policy.build("abc", None);
+        assert!(policy.contains("script-src 'self' 'strict-dynamic' 'nonce-abc' 'wasm-unsafe-eval' https://cdn.test;"));
+        assert!(policy.contains("style-src 'self' 'nonce-abc' https://cdn.test;"));
+        assert!(policy.contains(&format!("connect-src 'self' {DEV_CONNECT_SRC} {OEMBED_CONNECT_SRC} https://cdn.test;")));
+    }
+
+    #[test]
+    fn test_add_security_headers() {
+        let mut response = Response::new(Body::empty());
+        add_security_headers(&mut response, &SecurityHeadersConfig::default(), "abc", None);
+        let headers = response.headers();
+        assert_eq!(headers.get("X-Content-Type-Options"), Some(&HeaderValue::from_static("nosniff")));
+        assert_eq!(headers.get("X-Frame-Options"), Some(&HeaderValue::from_static("DENY")));
+        assert_eq!(headers.get("Referrer-Policy"), Some(&HeaderValue::from_static("strict-origin-when-cross-origin")));
+        assert_eq!(headers.get("Permissions-Policy"), Some(&HeaderValue::from_static(DEFAULT_PERMISSIONS_POLICY)));
+        assert_eq!(headers.get("Cross-Origin-Opener-Policy"), Some(&HeaderValue::from_static("same-origin")));
+        assert_eq!(headers.get("Cross-Origin-Resource-Policy"), Some(&HeaderValue::from_static("same-origin")));
+        assert_eq!(headers.get("X-XSS-Protection"), Some(&HeaderValue::from_static("0")));
+        assert_eq!(headers.get("Strict-Transport-Security"), Some(&HeaderValue::from_static(HSTS_HEADER_VALUE)));
+        assert_eq!(headers.get(CSP_REPORT_ONLY_HEADER), None);
+        let policy = headers.get(CSP_HEADER).expect("Should have CSP header").to_str().expect("Should get CSP as str");
+        assert!(policy.contains("script-src 'self' 'strict-dynamic' 'nonce-abc' 'wasm-unsafe-eval'"));
+        assert!(policy.contains("frame-ancestors 'none'"));
+
+        let config = SecurityHeadersConfig {
+            enable_hsts: false,
+            permissions_policy: HeaderValue::from_static("camera=(self)"),
+            content_security_policy: ContentSecurityPolicy {
+                asset_origins: vec![String::from("https://cdn.test")],
+                report_only: true,
+            },
+        };
+        let mut response = Response::new(Body::empty());
+        add_security_headers(&mut response, &config, "def", None);
+        let headers = response.headers();
+        assert_eq!(headers.get("Permissions-Policy"), Some(&HeaderValue::from_static("camera=(self)")));
+        assert_eq!(headers.get("Strict-Transport-Security"), None);
+        assert_eq!(headers.get("X-Frame-Options"), Some(&HeaderValue::from_static("DENY")));
+        assert_eq!(headers.get(CSP_HEADER), None);
+        let policy = headers.get(CSP_REPORT_ONLY_HEADER).expect("Should have report only CSP header").to_str().expect("Should get CSP as str");
+        assert!(policy.contains("script-src 'self' 'strict-dynamic' 'nonce-def' 'wasm-unsafe-eval' https://cdn.test;"));
+    }
+}
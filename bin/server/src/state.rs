@@ -7,6 +7,9 @@ use sqlx::PgPool;
 
 use sphare_core_user::user::ssr::UserLockCache;
 
+use crate::health::JobHealthMonitor;
+use crate::security::SecurityHeadersConfig;
+
 /// This takes advantage of Axum's SubStates feature by deriving FromRef. This is the only way to have more than one
 /// item in Axum's State. Leptos requires you to have leptos Options in your State struct for the leptos route handlers
 #[derive(FromRef, Debug, Clone)]
@@ -15,4 +18,6 @@ pub struct AppState {
     pub db_pool: PgPool,
     pub user_lock_cache: Arc<UserLockCache>,
     pub routes: Vec<AxumRouteListing>,
+    pub job_health_monitor: Arc<JobHealthMonitor>,
+    pub security_headers_config: SecurityHeadersConfig,
 }
\ No newline at end of file
@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use leptos::server_fn::axum::server_fn_paths;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+use sphare_core_common::metrics::{SERVER_FN_DURATION_METRIC, SERVER_FN_PATH_LABEL, SERVER_FN_REQUESTS_METRIC, STATUS_LABEL};
+
+pub const METRICS_ROUTE: &str = "/metrics";
+pub const METRICS_ADDR_ENV: &str = "METRICS_ADDR";
+pub const UNKNOWN_SERVER_FN_LABEL: &str = "unknown";
+
+static REGISTERED_SERVER_FN_PATHS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    server_fn_paths().map(|(path, _)| path).collect()
+});
+
+/// Installs the global metrics recorder, the returned handle renders the recorded metrics.
+pub fn install_metrics_recorder() -> PrometheusHandle {
+    PrometheusBuilder::new()
+        .install_recorder()
+        .expect("Should install metrics recorder.")
+}
+
+/// Exposes the recorded metrics in the Prometheus text format.
+pub async fn metrics_handler(
+    State(metrics_handle): State<PrometheusHandle>,
+) -> impl IntoResponse {
+    metrics_handle.render()
+}
+
+/// Returns the address on which metrics are exposed, if any. Metrics are not exposed on the public address of the app,
+/// so that they are only reachable from the internal network.
+pub fn get_metrics_addr() -> Option<SocketAddr> {
+    let metrics_addr = std::env::var(METRICS_ADDR_ENV).ok()?;
+    match metrics_addr.parse() {
+        Ok(metrics_addr) => Some(metrics_addr),
+        Err(e) => {
+            log::error!("Could not parse metrics address {metrics_addr}: {e}");
+            None
+        }
+    }
+}
+
+/// Serves the recorded metrics on `metrics_addr`
+pub async fn serve_metrics(metrics_addr: SocketAddr, metrics_handle: PrometheusHandle) {
+    let metrics_app = Router::new()
+        .route(METRICS_ROUTE, get(metrics_handler))
+        .with_state(metrics_handle);
+    log::info!("metrics exposed on http://{metrics_addr}{METRICS_ROUTE}");
+    let listener = tokio::net::TcpListener::bind(metrics_addr).await.expect("Should bind metrics address.");
+    axum::serve(listener, metrics_app.into_make_service())
+        .await
+        .expect("Should serve metrics.");
+}
+
+/// Returns the label of the server function at `path`, or [UNKNOWN_SERVER_FN_LABEL] if no server function is registered at `path`,
+/// so that requests to arbitrary paths cannot create new metric series.
+pub fn get_server_fn_label<'a>(path: &'a str, registered_path_set: &HashSet<&str>) -> &'a str {
+    match registered_path_set.contains(path) {
+        true => path,
+        false => UNKNOWN_SERVER_FN_LABEL,
+    }
+}
+
+/// Records the call of the server function at `path`, with its response status and duration.
+/// Paths without a registered server function are recorded as [UNKNOWN_SERVER_FN_LABEL].
+pub fn record_server_fn_call(path: &str, status: StatusCode, duration: Duration) {
+    let labels = [
+        (SERVER_FN_PATH_LABEL, get_server_fn_label(path, &REGISTERED_SERVER_FN_PATHS).to_string()),
+        (STATUS_LABEL, status.as_u16().to_string()),
+    ];
+    metrics::counter!(SERVER_FN_REQUESTS_METRIC, &labels).increment(1);
+    metrics::histogram!(SERVER_FN_DURATION_METRIC, &labels).record(duration.as_secs_f64());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    use axum::http::StatusCode;
+    use metrics_exporter_prometheus::PrometheusBuilder;
+
+    use sphare_core_common::metrics::{SERVER_FN_DURATION_METRIC, SERVER_FN_REQUESTS_METRIC};
+
+    use crate::telemetry::{get_server_fn_label, record_server_fn_call, UNKNOWN_SERVER_FN_LABEL};
+
+    #[test]
+    fn test_get_server_fn_label() {
+        let registered_path_set = HashSet::from(["/api/get_post", "/api/get_comment"]);
+        assert_eq!(get_server_fn_label("/api/get_post", &registered_path_set), "/api/get_post");
+        assert_eq!(get_server_fn_label("/api/get_comment", &registered_path_set), "/api/get_comment");
+        assert_eq!(get_server_fn_label("/api/random_1234", &registered_path_set), UNKNOWN_SERVER_FN_LABEL);
+        assert_eq!(get_server_fn_label("", &registered_path_set), UNKNOWN_SERVER_FN_LABEL);
+    }
+
+    #[test]
+    fn test_record_server_fn_call() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let metrics_handle = recorder.handle();
+        metrics::with_local_recorder(&recorder, || {
+            record_server_fn_call("/api/random_1", StatusCode::OK, Duration::from_millis(10));
+            record_server_fn_call("/api/random_2", StatusCode::OK, Duration::from_millis(20));
+            record_server_fn_call("/api/random_3", StatusCode::NOT_FOUND, Duration::from_millis(5));
+        });
+
+        // paths without registered server function share a single label
+        let rendered_metrics = metrics_handle.render();
+        assert!(rendered_metrics.contains(&format!("{SERVER_FN_REQUESTS_METRIC}{{path=\"unknown\",status=\"200\"}} 2")));
+        assert!(rendered_metrics.contains(&format!("{SERVER_FN_REQUESTS_METRIC}{{path=\"unknown\",status=\"404\"}} 1")));
+        assert!(rendered_metrics.contains(&format!("{SERVER_FN_DURATION_METRIC}_count{{path=\"unknown\",status=\"200\"}} 2")));
+        assert!(!rendered_metrics.contains("random"));
+    }
+}
@@ -1,77 +1,40 @@
 use leptos::ev::TouchEvent;
 use leptos::prelude::*;
-use const_format::formatcp;
 use leptos_fluent::leptos_fluent;
-use leptos_meta::{provide_meta_context, HashedStylesheet, Link, Meta, MetaTags, Title};
+use leptos_meta::{provide_meta_context, HashedStylesheet, Link, MetaTags, Title};
 use leptos_router::{components::{ParentRoute, Route, Router, Routes}, ParamSegment, StaticSegment};
-use regex::Regex;
 
 use sphare_core_common::constants::{SITE_NAME};
 use sphare_core_common::errors::AppError;
-use sphare_core_common::routes::{ABOUT_SPHARE_ROUTE, AUTH_CALLBACK_ROUTE, CONTENT_POLICY_ROUTE, CREATE_POST_SUFFIX, CREATE_SPHERE_SUFFIX, FAQ_ROUTE, NOTIFICATION_ROUTE, POPULAR_ROUTE, POST_ROUTE_PARAM_NAME, POST_ROUTE_PREFIX, PRIVACY_POLICY_ROUTE, PUBLISH_ROUTE, RULES_ROUTE, SATELLITE_ROUTE_PARAM_NAME, SATELLITE_ROUTE_PREFIX, SEARCH_ROUTE, SPHERE_ROUTE_PARAM_NAME, SPHERE_ROUTE_PREFIX, TERMS_AND_CONDITIONS_ROUTE, USER_ROUTE_PARAM_NAME, USER_ROUTE_PREFIX};
+use sphare_core_content::ranking::SortType;
+use sphare_core_user::user::get_default_post_sort;
+use sphare_core_common::routes::{ABOUT_SPHARE_ROUTE, AUTH_CALLBACK_ROUTE, CONTENT_POLICY_ROUTE, CREATE_POLL_SUFFIX, CREATE_POST_SUFFIX, CREATE_SPHERE_SUFFIX, FAQ_ROUTE, NOTIFICATION_ROUTE, POPULAR_ROUTE, POST_ROUTE_PARAM_NAME, POST_ROUTE_PREFIX, PRIVACY_POLICY_ROUTE, PUBLISH_ROUTE, REPORT_INBOX_ROUTE, RULES_ROUTE, SATELLITE_ROUTE_PARAM_NAME, SATELLITE_ROUTE_PREFIX, SEARCH_ROUTE, SPHERE_ROUTE_PARAM_NAME, SPHERE_ROUTE_PREFIX, TERMS_AND_CONDITIONS_ROUTE, USER_ROUTE_PARAM_NAME, USER_ROUTE_PREFIX, WIKI_ROUTE_PARAM_NAME, WIKI_ROUTE_PREFIX};
 
 use sphare_iface_sphere::sphere::CreateSphere;
 use sphare_iface_user::auth::{get_user, EndSession};
-use sphare_iface_user::user::{DeleteUser, SetUserSettings};
+use sphare_iface_user::user::{DeleteUser, SetDefaultPostSort, SetUserSettings};
 
 use sphare_cmp_common::auth_widget::AuthCallback;
 use sphare_cmp_common::state::GlobalState;
+use sphare_cmp_content::poll::CreatePoll;
 use sphare_cmp_content::post::{CreatePost, Post};
 use sphare_cmp_sphere::satellite::{CreateSatellitePost, SatelliteBanner, SatelliteContent};
 use sphare_cmp_sphere::sphere::{CreateSphere, SphereContents};
 use sphare_cmp_sphere::sphere_management::{SphereCockpit, SphereCockpitGuard, MANAGE_SPHERE_ROUTE};
+use sphare_cmp_sphere::wiki::SphereWikiPage;
 use sphare_cmp_ui::navigation_bar::NavigationBar;
 use sphare_cmp_ui::policy::{AboutSphare, ContentPolicy, Faq, PrivacyPolicy, Rules, TermsAndConditions};
 use sphare_cmp_ui::search::{Search, SphereSearch};
 use sphare_cmp_ui::sidebar::LeftSidebar;
 use sphare_cmp_utils::errors::ErrorTemplate;
 
-use crate::home::{HomePage, HotPage, LoginGuard, LoginGuardHome, NotificationHome, ProfileHome, SphereHome};
+use crate::home::{HomePage, HotPage, LoginGuard, LoginGuardHome, NotificationHome, ProfileHome, ReportInboxHome, SphereHome};
 
 const IS_TEST_SITE_ENV: &str = "IS_TEST_SITE";
-const OEMBED_CONNECT_SRC: &str = env!("OEMBED_CONNECT_SRC");
-const OEMBED_FRAME_SRC: &str = env!("OEMBED_FRAME_SRC");
-
-#[derive(Clone, Debug)]
-pub struct UserAgentHeader {
-    pub value: Option<String>,
-}
-
-#[component]
-pub fn AppMeta() -> impl IntoView {
-    let connect_src_csp = match cfg!(debug_assertions) {
-        true => formatcp!("connect-src 'self' https: ws://localhost:3001/ ws://127.0.0.1:3001/ {OEMBED_CONNECT_SRC};"),
-        false => formatcp!("connect-src 'self' {OEMBED_CONNECT_SRC};"),
-    };
-    let frame_src_csp = formatcp!("frame-src 'self' {OEMBED_FRAME_SRC};");
-    let ios_user_agent_regex = Regex::new("(iPhone|iPad|iPod|iOS).*AppleWebKit").expect("iOS regex should be valid");
-
-    view! {
-        <Meta
-            http_equiv="Content-Security-Policy"
-            content=move || {
-                // this will insert the CSP with nonce on the server, be empty on client
-                use_nonce().map(|nonce| {
-                    let script_src_csp = match use_context::<UserAgentHeader>().map(|header| header.value) {
-                        Some(Some(user_agent)) if ios_user_agent_regex.captures(user_agent.as_str()).is_some() => {
-                            format!("script-src 'strict-dynamic' 'nonce-{nonce}' 'wasm-unsafe-eval' 'unsafe-eval';")
-                        },
-                        _ => format!("script-src 'strict-dynamic' 'nonce-{nonce}' 'wasm-unsafe-eval';")
-                    };
-                    format!(
-                        "default-src 'none';
-                        {script_src_csp}
-                        img-src 'self' https: data:;
-                        media-src 'self' https:;
-                        {frame_src_csp}
-                        style-src 'self' 'nonce-{nonce}';
-                        {connect_src_csp}"
-                    )
-                }).unwrap_or_default()
-            }
-        />
-    }
-}
+/// Origins of the oEmbed endpoints, the Content-Security-Policy must allow fetching from them
+pub const OEMBED_CONNECT_SRC: &str = env!("OEMBED_CONNECT_SRC");
+/// Origins of the embedded oEmbed iframes, the Content-Security-Policy must allow framing them
+pub const OEMBED_FRAME_SRC: &str = env!("OEMBED_FRAME_SRC");
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
     let is_test_site = std::env::var(IS_TEST_SITE_ENV).is_ok_and(|is_test_site_str| is_test_site_str.to_lowercase() == "true");
@@ -85,7 +48,6 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
                     true => Some(view! { <meta name="robots" content="noindex, nofollow"/> }),
                     false => None,
                 }}
-                <AppMeta/>
                 <AutoReload options=options.clone() />
                 <HydrationScripts options=options.clone() />
                 // id=leptos means cargo-leptos will hot-reload this stylesheet
@@ -110,6 +72,7 @@ pub fn App() -> impl IntoView {
     let delete_user_action = ServerAction::<DeleteUser>::new();
     let create_sphere_action = ServerAction::<CreateSphere>::new();
     let set_settings_action = ServerAction::<SetUserSettings>::new();
+    let set_default_post_sort_action = ServerAction::<SetDefaultPostSort>::new();
     let user = Resource::new(
         move || {
             (
@@ -117,6 +80,7 @@ pub fn App() -> impl IntoView {
                 delete_user_action.version().get(),
                 create_sphere_action.version().get(),
                 set_settings_action.version().get(),
+                set_default_post_sort_action.version().get(),
             )
         },
         move |_| get_user(),
@@ -127,9 +91,18 @@ pub fn App() -> impl IntoView {
         delete_user_action,
         create_sphere_action,
         set_settings_action,
+        set_default_post_sort_action,
     );
     provide_context(state);
 
+    // feeds are sorted with the user's default post sort, anonymous users get the default sort
+    // the sort is only applied when the user is first loaded, to keep the sort picked by the user when the user is reloaded
+    let is_default_post_sort_applied = StoredValue::new(false);
+    Effect::new(move || if !is_default_post_sort_applied.get_value() && let Some(Ok(user)) = &*user.read() {
+        state.post_sort_type.set(SortType::Post(get_default_post_sort(user.as_ref())));
+        is_default_post_sort_applied.set_value(true);
+    });
+
     let swipe_start_x = RwSignal::new(None);
     let swipe_start_y = RwSignal::new(None);
     let swipe_id = RwSignal::new(None);
@@ -202,6 +175,7 @@ pub fn App() -> impl IntoView {
                                         <Route path=StaticSegment("") view=SphereCockpit/>
                                     </ParentRoute>
                                     <Route path=StaticSegment(SEARCH_ROUTE) view=SphereSearch/>
+                                    <Route path=(StaticSegment(WIKI_ROUTE_PREFIX), ParamSegment(WIKI_ROUTE_PARAM_NAME)) view=SphereWikiPage/>
                                     <Route path=StaticSegment("") view=SphereContents/>
                                 </ParentRoute>
                                 <Route path=(StaticSegment(USER_ROUTE_PREFIX), ParamSegment(USER_ROUTE_PARAM_NAME)) view=ProfileHome/>
@@ -209,8 +183,10 @@ pub fn App() -> impl IntoView {
                                 <ParentRoute path=StaticSegment(PUBLISH_ROUTE) view=LoginGuardHome>
                                     <Route path=StaticSegment(CREATE_SPHERE_SUFFIX) view=CreateSphere/>
                                     <Route path=StaticSegment(CREATE_POST_SUFFIX) view=CreatePost/>
+                                    <Route path=StaticSegment(CREATE_POLL_SUFFIX) view=CreatePoll/>
                                 </ParentRoute>
                                 <Route path=StaticSegment(NOTIFICATION_ROUTE) view=NotificationHome/>
+                                <Route path=StaticSegment(REPORT_INBOX_ROUTE) view=ReportInboxHome/>
                                 <Route path=StaticSegment(SEARCH_ROUTE) view=Search/>
                                 <Route path=StaticSegment(ABOUT_SPHARE_ROUTE) view=AboutSphare/>
                                 <Route path=StaticSegment(TERMS_AND_CONDITIONS_ROUTE) view=TermsAndConditions/>
@@ -15,6 +15,7 @@ use sphare_cmp_base::ranking::PostSortWidget;
 use sphare_cmp_common::auth_widget::LoginWindow;
 use sphare_cmp_common::notification::NotificationList;
 use sphare_cmp_common::state::GlobalState;
+use sphare_cmp_content::moderation::GlobalReportInbox;
 use sphare_cmp_content::profile::UserProfile;
 use sphare_cmp_sphere::sphere::SphereBanner;
 use sphare_cmp_ui::sidebar::{HomeSidebar, SphereSidebar};
@@ -211,6 +212,23 @@ pub fn NotificationHome() -> impl IntoView {
     }
 }
 
+/// Displays the reports of all spheres, only available to admins
+#[component]
+pub fn ReportInboxHome() -> impl IntoView {
+    let state = expect_context::<GlobalState>();
+    view! {
+        <SuspenseUnpack resource=state.user let:user>
+        {
+            match user {
+                Some(_) => view! { <GlobalReportInbox/> }.into_any(),
+                None => view! { <LoginWindow/> }.into_any(),
+            }
+        }
+        </SuspenseUnpack>
+        <HomeSidebar/>
+    }
+}
+
 /// Displays a user's profile
 #[component]
 pub fn SphereHome() -> impl IntoView {
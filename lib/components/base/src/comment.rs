@@ -79,6 +79,7 @@ pub fn CommentWithContext(
     let score = comment.comment.score;
     let author_id = comment.comment.creator_id;
     let author = comment.comment.creator_name.clone();
+    let avatar_url = comment.comment.creator_avatar_url.clone();
     let is_moderator = comment.comment.is_creator_moderator;
     let timestamp = comment.comment.create_timestamp;
     let is_pinned = comment.comment.is_pinned;
@@ -97,7 +98,7 @@ pub fn CommentWithContext(
             <div class="flex gap-1">
                 <SphereHeader sphere_header=comment.sphere_header/>
                 <ScoreIndicator score/>
-                <AuthorWidget author_id author is_moderator/>
+                <AuthorWidget author_id author is_moderator avatar_url/>
                 <TimeSinceWidget timestamp/>
             </div>
         </a>
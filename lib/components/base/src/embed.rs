@@ -6,8 +6,9 @@ use url::Url;
 use sphare_core_content::embed::{select_embed_type, verify_link_and_get_embed, EmbedType, Link, LinkType};
 
 use sphare_cmp_utils::errors::ErrorDetail;
-use sphare_cmp_utils::icons::LinkIcon;
+use sphare_cmp_utils::icons::{ArrowUpIcon, LinkIcon};
 use sphare_core_common::errors::AppError;
+use sphare_iface_content::post::get_post_image_vec;
 
 const DEFAULT_MEDIA_CLASS: &str = "h-fit w-fit max-h-160 max-w-full object-contain";
 const THUMBNAIL_CLASS: &str = "h-16 w-16 object-contain";
@@ -24,12 +25,14 @@ pub fn EmbedPreview(
     #[prop(into)]
     select_trigger: Signal<usize>,
     title_input: RwSignal<String>,
+    #[prop(into, optional)]
+    alt_text_input: Signal<String>,
     select_ref: NodeRef<html::Select>,
 ) -> impl IntoView {
     let link_resource = Resource::new(
         move || (select_trigger.get(), link_input.get()),
         move |(_, url)| async move {
-            verify_link_and_get_embed(embed_type_input.get_untracked(), &url).await
+            verify_link_and_get_embed(embed_type_input.get_untracked(), &url, Some(alt_text_input.get_untracked())).await
         },
     );
 
@@ -47,25 +50,66 @@ pub fn EmbedPreview(
     }
 }
 
-/// Component to safely embed external content
+/// Component to safely embed external content.
+/// Galleries are only displayed as a carousel when `post_id` is given, otherwise their first image is displayed.
+/// When `is_blurred` is true, the content is blurred until it is clicked. The images of galleries are blurred individually,
+/// so that the carousel can still be browsed.
 #[component]
 pub fn Embed(
     link: Link,
+    #[prop(default = None)]
+    post_id: Option<i64>,
     #[prop(default = false)]
     align_center: bool,
+    #[prop(default = false)]
+    is_blurred: bool,
 ) -> impl IntoView {
-    match (link.link_type, link.link_url, link.link_embed, link.link_thumbnail_url) {
+    let alt_text = link.alt_text;
+    let embed_view = match (link.link_type, link.link_url, link.link_embed, link.link_thumbnail_url) {
         (LinkType::None, _, _, _) => None,
+        (LinkType::Gallery, _, _, _) if post_id.is_some() => return post_id.map(|post_id| view! {
+            <GalleryEmbed post_id align_center is_blurred/>
+        }.into_any()),
         (_, None, _, _) => None,
         (LinkType::Link, Some(link_url), None, thumbnail_url) => Url::parse(&link_url).ok().map(|url| view! {
             <LinkEmbed url thumbnail_url align_center/>
         }.into_any()),
         (link_type, Some(link_url), None, _) => Some(view! {
-            <NaiveEmbed link_input=link_url link_type align_center/>
+            <NaiveEmbed link_input=link_url link_type alt_text align_center/>
         }.into_any()),
         (_, Some(_), Some(link_embed), _) => Some(view! {
             <HtmlEmbed html=link_embed align_center/>
         }.into_any()),
+    };
+    match is_blurred {
+        true => embed_view.map(|embed_view| view! { <NsfwBlurOverlay>{embed_view}</NsfwBlurOverlay> }.into_any()),
+        false => embed_view,
+    }
+}
+
+/// Component to blur NSFW content until the user clicks on it
+#[component]
+pub fn NsfwBlurOverlay(
+    children: Children,
+) -> impl IntoView {
+    let is_revealed = RwSignal::new(false);
+    view! {
+        <div class="relative w-full">
+            <div class=move || match is_revealed.get() {
+                true => "",
+                false => "blur-xl pointer-events-none select-none",
+            }>
+                {children()}
+            </div>
+            <Show when=move || !is_revealed.get()>
+                <button
+                    class="absolute inset-0 flex justify-center items-center font-semibold"
+                    on:click=move |_| is_revealed.set(true)
+                >
+                    {move_tr!("reveal-nsfw")}
+                </button>
+            </Show>
+        </div>
     }
 }
 
@@ -75,6 +119,8 @@ pub fn NaiveEmbed(
     #[prop(into)]
     link_input: Signal<String>,
     link_type: LinkType,
+    #[prop(default = None)]
+    alt_text: Option<String>,
     #[prop(default = false)]
     align_center: bool,
 ) -> impl IntoView {
@@ -84,7 +130,7 @@ pub fn NaiveEmbed(
                 (LinkType::None, _) => None,
                 (_, Err(e)) => Some(view! { <ErrorDetail error=AppError::new(format!("{}: {e}", tr!("invalid-link")))/> }.into_any()),
                 (LinkType::Link, Ok(url)) => Some(view! { <LinkEmbed url align_center/> }.into_any()),
-                (LinkType::Image, Ok(url)) => Some(view! { <ImageEmbed url=url.to_string() align_center/> }.into_any()),
+                (LinkType::Image | LinkType::Gallery, Ok(url)) => Some(view! { <ImageEmbed url=url.to_string() alt_text=alt_text.clone() align_center/> }.into_any()),
                 (LinkType::Video, Ok(url)) => Some(view! { <VideoEmbed url=url.to_string() align_center/> }.into_any()),
                 (LinkType::Rich, Ok(url)) => Some(view! { <LinkEmbed url align_center/> }.into_any()),
             }
@@ -131,6 +177,8 @@ pub fn LinkEmbed(
 #[component]
 pub fn ImageEmbed(
     url: String,
+    #[prop(default = None)]
+    alt_text: Option<String>,
     #[prop(default = false)]
     align_center: bool,
 ) -> impl IntoView {
@@ -140,11 +188,78 @@ pub fn ImageEmbed(
     };
     view! {
         <div class=class>
-            <img src=url class=DEFAULT_MEDIA_CLASS/>
+            <img src=url alt=alt_text class=DEFAULT_MEDIA_CLASS/>
         </div>
     }
 }
 
+/// Component to embed the images of the gallery of post `post_id` as a carousel.
+/// When `is_blurred` is true, the images are blurred until they are clicked.
+#[component]
+pub fn GalleryEmbed(
+    post_id: i64,
+    #[prop(default = false)]
+    align_center: bool,
+    #[prop(default = false)]
+    is_blurred: bool,
+) -> impl IntoView {
+    let image_vec_resource = Resource::new(
+        move || post_id,
+        get_post_image_vec,
+    );
+    let current_index = RwSignal::new(0usize);
+    let class = match align_center {
+        true => "flex justify-center items-center gap-2 h-fit w-full",
+        false => "flex justify-center lg:justify-start items-center gap-2 h-fit w-full",
+    };
+    view! {
+        <Suspense>
+        { move || image_vec_resource.get().map(|image_vec| match image_vec {
+            Ok(image_vec) if !image_vec.is_empty() => {
+                let image_count = image_vec.len();
+                let image_vec = StoredValue::new(image_vec);
+                view! {
+                    <div class="flex flex-col items-center gap-1 w-full">
+                        <div class=class>
+                            <button
+                                class="button-ghost"
+                                aria-label=move_tr!("previous-image")
+                                on:click=move |_| current_index.update(|index| *index = (*index + image_count - 1) % image_count)
+                            >
+                                <ArrowUpIcon class="h-5 w-5 -rotate-90"/>
+                            </button>
+                            {
+                                let image_view = view! {
+                                    <img
+                                        src=move || image_vec.with_value(|image_vec| image_vec[current_index.get() % image_count].url.clone())
+                                        alt=move || image_vec.with_value(|image_vec| image_vec[current_index.get() % image_count].alt_text.clone())
+                                        class=DEFAULT_MEDIA_CLASS
+                                    />
+                                };
+                                match is_blurred {
+                                    true => view! { <NsfwBlurOverlay>{image_view}</NsfwBlurOverlay> }.into_any(),
+                                    false => image_view.into_any(),
+                                }
+                            }
+                            <button
+                                class="button-ghost"
+                                aria-label=move_tr!("next-image")
+                                on:click=move |_| current_index.update(|index| *index = (*index + 1) % image_count)
+                            >
+                                <ArrowUpIcon class="h-5 w-5 rotate-90"/>
+                            </button>
+                        </div>
+                        <div class="text-sm">{move || format!("{}/{image_count}", current_index.get() % image_count + 1)}</div>
+                    </div>
+                }.into_any()
+            },
+            Ok(_) => ().into_any(),
+            Err(e) => view! { <ErrorDetail error=e/> }.into_any(),
+        })}
+        </Suspense>
+    }
+}
+
 /// Component to embed a video
 #[component]
 pub fn VideoEmbed(
@@ -1,3 +1,4 @@
+use leptos::ev;
 use leptos::html;
 use leptos::prelude::*;
 use leptos::server_fn::const_format::concatcp;
@@ -5,7 +6,7 @@ use leptos_fluent::move_tr;
 use strum::IntoEnumIterator;
 
 use sphare_core_common::common::{SphereCategoryHeader, SphereHeader};
-use sphare_core_common::constants::{MAX_CONTENT_LENGTH, MAX_LINK_LENGTH, MAX_TITLE_LENGTH};
+use sphare_core_common::constants::{MAX_ALT_TEXT_LENGTH, MAX_CONTENT_LENGTH, MAX_LINK_LENGTH, MAX_TITLE_LENGTH};
 use sphare_core_common::editor::TextareaData;
 use sphare_core_common::errors::AppError;
 use sphare_core_common::routes::get_post_path;
@@ -16,16 +17,24 @@ use sphare_core_sphere::sphere_category::SphereCategory;
 use sphare_cmp_common::auth_widget::AuthorWidget;
 use sphare_cmp_common::role::IsPinnedCheckbox;
 use sphare_cmp_common::sphere::SphereHeaderLink;
+use sphare_cmp_common::user::MentionSuggestions;
 use sphare_cmp_utils::editor::{FormMarkdownEditor, LengthLimitedInput};
 use sphare_cmp_utils::form::LabeledFormCheckbox;
 use sphare_cmp_utils::icons::NsfwIcon;
-use sphare_cmp_utils::node_utils::has_reached_scroll_load_threshold;
+use sphare_cmp_utils::node_utils::{has_reached_scroll_load_threshold, is_editable_element_focused};
 use sphare_cmp_utils::unpack::SuspenseUnpack;
 use sphare_cmp_utils::widget::{CommentCountWidget, HelpButton, LoadIndicators, ScoreIndicator, SpoilerBadge, TagsWidget, TimeSinceWidget};
 
 use crate::embed::EmbedPreview;
 use crate::sphere_category::{SphereCategoryBadge, SphereCategoryDropdown};
 
+/// Action to perform following a key press in a post feed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostFeedKeyAction {
+    Focus(usize),
+    Ignore,
+}
+
 /// Component to initially load on the server a vector of post and load additional post on the client upon scrolling
 #[component]
 pub fn PostListWithInitLoad(
@@ -56,6 +65,21 @@ pub fn PostListWithInitLoad(
         true => concatcp!(BASE_LIST_CLASS, "overflow-y-auto"),
         false => BASE_LIST_CLASS,
     };
+    let focused_post_id = use_post_feed_navigation(Signal::derive(move || {
+        let mut post_vec = match post_vec_resource.get() {
+            Some(Ok(post_vec)) => post_vec,
+            _ => Vec::new(),
+        };
+        post_vec.extend(additional_post_vec.get());
+        post_vec
+    }));
+    let loaded_count = Signal::derive(move || {
+        let initial_count = match &*post_vec_resource.read() {
+            Some(Ok(post_vec)) => post_vec.len(),
+            _ => 0,
+        };
+        initial_count + additional_post_vec.read().len()
+    });
     view! {
         <ul class=list_class
             on:scroll=move |_| if has_reached_scroll_load_threshold(list_ref) && !is_loading.get_untracked() {
@@ -64,11 +88,11 @@ pub fn PostListWithInitLoad(
             node_ref=list_ref
         >
             <SuspenseUnpack resource=post_vec_resource fallback= move || ().into_any() let:post_vec>
-                <PostMiniatureList post_vec=post_vec.clone() show_sphere_header/>
+                <PostMiniatureList post_vec=post_vec.clone() show_sphere_header focused_post_id/>
             </SuspenseUnpack>
-            <PostMiniatureList post_vec=additional_post_vec show_sphere_header/>
+            <PostMiniatureList post_vec=additional_post_vec show_sphere_header focused_post_id/>
         </ul>
-        <LoadIndicators load_error is_loading/>
+        <LoadIndicators load_error is_loading loaded_count/>
     }
 }
 
@@ -91,6 +115,7 @@ pub fn PostListWithIndicators(
     #[prop(default = true)]
     show_sphere_header: bool,
 ) -> impl IntoView {
+    let focused_post_id = use_post_feed_navigation(post_vec);
     view! {
         <ul class="flex flex-col overflow-y-auto w-full pr-2 divide-y divide-base-content/20"
             on:scroll=move |_| if has_reached_scroll_load_threshold(list_ref) && !is_loading.get_untracked() {
@@ -98,9 +123,9 @@ pub fn PostListWithIndicators(
             }
             node_ref=list_ref
         >
-            <PostMiniatureList post_vec show_sphere_header/>
+            <PostMiniatureList post_vec show_sphere_header focused_post_id/>
         </ul>
-        <LoadIndicators load_error is_loading/>
+        <LoadIndicators load_error is_loading loaded_count=Signal::derive(move || post_vec.read().len())/>
     }
 }
 
@@ -112,6 +137,9 @@ pub fn PostMiniatureList(
     post_vec: Signal<Vec<PostWithSphereInfo>>,
     #[prop(default = true)]
     show_sphere_header: bool,
+    /// id of the post focused with the keyboard
+    #[prop(optional, into)]
+    focused_post_id: Signal<Option<i64>>,
 ) -> impl IntoView {
     view! {
         <For
@@ -124,10 +152,21 @@ pub fn PostMiniatureList(
                     false => None,
                 };
                 let post_path = get_post_path(&post_info.sphere_name, post.satellite_id, post.post_id);
+                let post_id = post.post_id;
+                let is_focused = move || focused_post_id.get() == Some(post_id);
+                let link_ref = NodeRef::<html::A>::new();
+                // move the keyboard focus to the link of the focused post, so that enter opens it
+                Effect::new(move || if is_focused() && let Some(link) = link_ref.get() {
+                    link.scroll_into_view();
+                    let _ = link.focus();
+                });
                 view! {
                     <li>
-                        <a href=post_path>
-                            <div class="flex flex-col gap-1 pl-1 pt-1 pb-2 my-1 rounded-sm hover:bg-base-200">
+                        <a href=post_path node_ref=link_ref>
+                            <div
+                                class="flex flex-col gap-1 pl-1 pt-1 pb-2 my-1 rounded-sm hover:bg-base-200"
+                                class=("bg-base-200", is_focused)
+                            >
                                 <h2 class="card-title pl-1 w-full whitespace-pre-wrap text-wrap wrap-anywhere">{post.title.clone()}</h2>
                                 <PostBadgeList
                                     sphere_header
@@ -143,6 +182,7 @@ pub fn PostMiniatureList(
                                         author_id=post.creator_id
                                         author=post.creator_name.clone()
                                         is_moderator=post.is_creator_moderator
+                                        avatar_url=post.creator_avatar_url.clone()
                                     />
                                     <TimeSinceWidget timestamp=post.create_timestamp/>
                                 </div>
@@ -155,6 +195,51 @@ pub fn PostMiniatureList(
     }
 }
 
+/// Enables browsing the posts of `post_vec` with the j/k keys. The link of the focused post receives the keyboard focus,
+/// so that enter opens it without affecting other focused elements. Returns a signal with the id of the focused post.
+pub fn use_post_feed_navigation(
+    post_vec: Signal<Vec<PostWithSphereInfo>>,
+) -> Signal<Option<i64>> {
+    let focused_index = RwSignal::new(None::<usize>);
+    // register the listener in an effect so that it is only added in the browser
+    Effect::new(move || {
+        let handle = window_event_listener(ev::keydown, move |ev| {
+            if ev.ctrl_key() || ev.meta_key() || ev.alt_key() {
+                return;
+            }
+            let post_count = post_vec.with_untracked(|post_vec| post_vec.len());
+            match get_post_feed_key_action(&ev.key(), focused_index.get_untracked(), post_count, is_editable_element_focused()) {
+                PostFeedKeyAction::Focus(index) => focused_index.set(Some(index)),
+                PostFeedKeyAction::Ignore => (),
+            }
+        });
+        on_cleanup(move || handle.remove());
+    });
+    Signal::derive(move || focused_index.get().and_then(
+        |index| post_vec.with(|post_vec| post_vec.get(index).map(|post_info| post_info.post.post_id))
+    ))
+}
+
+/// Returns the action to perform when `key` is pressed in a feed of `post_count` posts, in which the post at `focused_index` has the focus.
+/// Keys are ignored when an input is focused to avoid hijacking them while the user is typing.
+pub fn get_post_feed_key_action(
+    key: &str,
+    focused_index: Option<usize>,
+    post_count: usize,
+    is_input_focused: bool,
+) -> PostFeedKeyAction {
+    if is_input_focused || post_count == 0 {
+        return PostFeedKeyAction::Ignore;
+    }
+    match (key, focused_index.filter(|index| *index < post_count)) {
+        ("j", Some(index)) => PostFeedKeyAction::Focus((index + 1) % post_count),
+        ("j", None) => PostFeedKeyAction::Focus(0),
+        ("k", Some(index)) => PostFeedKeyAction::Focus((index + post_count - 1) % post_count),
+        ("k", None) => PostFeedKeyAction::Focus(post_count - 1),
+        _ => PostFeedKeyAction::Ignore,
+    }
+}
+
 /// Component to display a post's sphere, its category and whether it's a spoiler/NSFW
 #[component]
 pub fn PostBadgeList(
@@ -203,11 +288,11 @@ pub fn PostForm(
     #[prop(optional)]
     link_textarea_ref: NodeRef<html::Textarea>,
 ) -> impl IntoView {
-    let (is_markdown, is_spoiler, is_nsfw, is_pinned, category_id) = match current_post {
+    let (is_markdown, is_spoiler, is_nsfw, is_pinned, category_id, alt_text) = match current_post {
         Some(post) => post.with_value(|post| {
-            (post.markdown_body.is_some(), post.is_spoiler, post.is_nsfw, post.is_pinned, post.category_id)
+            (post.markdown_body.is_some(), post.is_spoiler, post.is_nsfw, post.is_pinned, post.category_id, post.link.alt_text.clone())
         }),
-        None => (false, false, false, false, None),
+        None => (false, false, false, false, None, None),
     };
 
     view! {
@@ -229,7 +314,8 @@ pub fn PostForm(
             maxlength=Some(MAX_CONTENT_LENGTH as usize)
             is_empty_ok=Signal::derive(move || embed_type_input.read() != EmbedType::None)
         />
-        <LinkForm link_input embed_type_input title_input textarea_ref=link_textarea_ref/>
+        <MentionSuggestions data=body_data/>
+        <LinkForm link_input embed_type_input title_input alt_text textarea_ref=link_textarea_ref/>
         { move || {
             match is_parent_spoiler.get() {
                 true => view! {
@@ -283,11 +369,15 @@ pub fn LinkForm(
     embed_type_input: RwSignal<EmbedType>,
     link_input: RwSignal<String>,
     title_input: RwSignal<String>,
+    /// initial alternative text of the link
+    #[prop(default = None)]
+    alt_text: Option<String>,
     /// reference to the textarea node
     #[prop(optional)]
     textarea_ref: NodeRef<html::Textarea>,
 ) -> impl IntoView {
     let select_trigger = RwSignal::new(0);
+    let alt_text_input = RwSignal::new(alt_text.unwrap_or_default());
     let select_ref = NodeRef::<html::Select>::new();
     view! {
         <div class="flex flex-col gap-2">
@@ -325,7 +415,15 @@ pub fn LinkForm(
                     textarea_ref
                 />
             </div>
-            <EmbedPreview embed_type_input link_input select_trigger title_input select_ref/>
+            <Show when=move || embed_type_input.read() != EmbedType::None>
+                <LengthLimitedInput
+                    name="post_inputs[alt_text]"
+                    placeholder=move_tr!("alt-text")
+                    content=alt_text_input
+                    maxlength=Some(MAX_ALT_TEXT_LENGTH as usize)
+                />
+            </Show>
+            <EmbedPreview embed_type_input link_input select_trigger title_input alt_text_input select_ref/>
         </div>
     }
 }
@@ -343,4 +441,35 @@ pub fn LinkHelpButton() -> impl IntoView {
             </div>
         </HelpButton>
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::post::{get_post_feed_key_action, PostFeedKeyAction};
+
+    #[test]
+    fn test_get_post_feed_key_action() {
+        assert_eq!(get_post_feed_key_action("j", None, 3, false), PostFeedKeyAction::Focus(0));
+        assert_eq!(get_post_feed_key_action("j", Some(0), 3, false), PostFeedKeyAction::Focus(1));
+        assert_eq!(get_post_feed_key_action("j", Some(2), 3, false), PostFeedKeyAction::Focus(0));
+        assert_eq!(get_post_feed_key_action("k", None, 3, false), PostFeedKeyAction::Focus(2));
+        assert_eq!(get_post_feed_key_action("k", Some(1), 3, false), PostFeedKeyAction::Focus(0));
+        assert_eq!(get_post_feed_key_action("k", Some(0), 3, false), PostFeedKeyAction::Focus(2));
+        // enter is handled by the focused post link
+        assert_eq!(get_post_feed_key_action("Enter", Some(1), 3, false), PostFeedKeyAction::Ignore);
+        assert_eq!(get_post_feed_key_action("Enter", None, 3, false), PostFeedKeyAction::Ignore);
+        assert_eq!(get_post_feed_key_action("a", Some(1), 3, false), PostFeedKeyAction::Ignore);
+        // focused index out of range after the feed changed
+        assert_eq!(get_post_feed_key_action("j", Some(5), 3, false), PostFeedKeyAction::Focus(0));
+        assert_eq!(get_post_feed_key_action("j", None, 0, false), PostFeedKeyAction::Ignore);
+        assert_eq!(get_post_feed_key_action("k", Some(0), 0, false), PostFeedKeyAction::Ignore);
+    }
+
+    #[test]
+    fn test_get_post_feed_key_action_input_focused() {
+        assert_eq!(get_post_feed_key_action("j", None, 3, true), PostFeedKeyAction::Ignore);
+        assert_eq!(get_post_feed_key_action("j", Some(1), 3, true), PostFeedKeyAction::Ignore);
+        assert_eq!(get_post_feed_key_action("k", Some(1), 3, true), PostFeedKeyAction::Ignore);
+        assert_eq!(get_post_feed_key_action("Enter", Some(1), 3, true), PostFeedKeyAction::Ignore);
+    }
+}
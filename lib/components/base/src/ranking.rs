@@ -6,7 +6,7 @@ use leptos_fluent::move_tr;
 
 use sphare_core_content::ranking::{CommentSortType, PostSortType, SortType};
 
-use sphare_cmp_utils::icons::{FlameIcon, GraphIcon, HourglassIcon, PodiumIcon};
+use sphare_cmp_utils::icons::{FlameIcon, GraphIcon, HourglassIcon, PodiumIcon, ScoreIcon};
 
 /// Component to show a sorting option
 #[component]
@@ -65,6 +65,9 @@ pub fn PostSortWidget(
             <SortWidgetOption sort_type=SortType::Post(PostSortType::Best) sort_signal datatip=move_tr!("best") is_tooltip_bottom>
                 <PodiumIcon/>
             </SortWidgetOption>
+            <SortWidgetOption sort_type=SortType::Post(PostSortType::BestRatio) sort_signal datatip=move_tr!("best-ratio") is_tooltip_bottom>
+                <ScoreIcon/>
+            </SortWidgetOption>
             <SortWidgetOption sort_type=SortType::Post(PostSortType::Recent) sort_signal datatip=move_tr!("recent") is_tooltip_bottom>
                 <HourglassIcon/>
             </SortWidgetOption>
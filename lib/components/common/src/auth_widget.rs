@@ -201,6 +201,8 @@ pub fn AuthorWidget(
     author_id: i64,
     author: String,
     is_moderator: bool,
+    #[prop(default = None)]
+    avatar_url: Option<String>,
     #[prop(into, optional)]
     is_grayed_out: Signal<bool>,
 ) -> impl IntoView {
@@ -220,14 +222,21 @@ pub fn AuthorWidget(
         >
             <Transition fallback=move || view! { <LoadingIcon class="content-toolbar-icon-size"/> }>
             {
-                move || Suspend::new(async move {
-                    match (&state.user.await, is_moderator) {
-                        (Ok(Some(user)), true) if author_id == user.user_id => view! { <SelfModeratorIcon/> }.into_any(),
-                        (Ok(Some(user)), false) if author_id == user.user_id => view! { <SelfAuthorIcon/> }.into_any(),
-                        (_, true) => view! { <ModeratorIcon is_grayed_out/> }.into_any(),
-                        (_, false) => view! { <AuthorIcon is_grayed_out/> }.into_any(),
-                    }
-                })
+                let avatar_url = avatar_url.clone();
+                move || {
+                    let avatar_url = avatar_url.clone();
+                    Suspend::new(async move {
+                        match (&state.user.await, is_moderator, avatar_url) {
+                            (_, false, Some(avatar_url)) => view! {
+                                <img src=avatar_url alt="" class="h-4 w-4 lg:h-5 lg:w-5 rounded-full object-cover"/>
+                            }.into_any(),
+                            (Ok(Some(user)), true, _) if author_id == user.user_id => view! { <SelfModeratorIcon/> }.into_any(),
+                            (Ok(Some(user)), false, _) if author_id == user.user_id => view! { <SelfAuthorIcon/> }.into_any(),
+                            (_, true, _) => view! { <ModeratorIcon is_grayed_out/> }.into_any(),
+                            (_, false, _) => view! { <AuthorIcon is_grayed_out/> }.into_any(),
+                        }
+                    })
+                }
             }
             </Transition>
             <span
@@ -330,7 +339,7 @@ pub fn NsfwCheckbox(
         {
             move || Suspend::new(async move {
                 match state.user.await {
-                    Ok(Some(user)) if user.show_nsfw => Some(view! {
+                    Ok(Some(user)) if user.nsfw_mode.includes_nsfw() => Some(view! {
                         <LabeledSignalCheckbox label value=show_nsfw class=class/>
                     }),
                     _ => {
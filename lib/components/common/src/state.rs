@@ -4,6 +4,7 @@ use leptos::prelude::*;
 
 use sphare_core_common::common::Rule;
 use sphare_core_common::errors::AppError;
+use sphare_core_content::comment::CollapsedComments;
 use sphare_core_content::filter::SphereCategoryFilter;
 use sphare_core_content::ranking::{CommentSortType, PostSortType, SortType};
 use sphare_core_sphere::satellite::Satellite;
@@ -14,15 +15,16 @@ use sphare_core_user::role::{PermissionLevel, UserSphereRole};
 use sphare_core_user::user::User;
 
 use sphare_iface_content::moderation::ModeratePost;
-use sphare_iface_content::post::{DeletePost, EditPost};
+use sphare_iface_content::post::{DeletePost, EditPost, SetAcceptedAnswer};
 use sphare_iface_sphere::rule::{get_rule_vec, AddRule, RemoveRule, UpdateRule};
 use sphare_iface_sphere::satellite::{get_satellite_vec_by_sphere_name, ActivateSatellite, CreateSatellite, DeactivateSatellite, UpdateSatellite};
 use sphare_iface_sphere::sphere::{get_sphere_with_user_info, CreateSphere, Subscribe, Unsubscribe, UpdateSphereDescription};
 use sphare_iface_sphere::sphere_category::{get_sphere_category_vec, DeleteSphereCategory, SetSphereCategory};
+use sphare_iface_sphere::sphere_management::{SetSphereColor, SetSphereSidebar};
 use sphare_iface_user::auth::{EndSession, Login};
 use sphare_iface_user::notification::get_notifications;
 use sphare_iface_user::role::{get_sphere_role_vec, SetUserSphereRole};
-use sphare_iface_user::user::{DeleteUser, SetUserSettings};
+use sphare_iface_user::user::{DeleteUser, SetDefaultPostSort, SetUserSettings};
 
 #[derive(Copy, Clone)]
 pub struct GlobalState {
@@ -30,6 +32,7 @@ pub struct GlobalState {
     pub logout_action: ServerAction<EndSession>,
     pub delete_user_action: ServerAction<DeleteUser>,
     pub set_settings_action: ServerAction<SetUserSettings>,
+    pub set_default_post_sort_action: ServerAction<SetDefaultPostSort>,
     pub subscribe_action: ServerAction<Subscribe>,
     pub unsubscribe_action: ServerAction<Unsubscribe>,
     pub edit_post_action: ServerAction<EditPost>,
@@ -40,6 +43,8 @@ pub struct GlobalState {
     pub activate_satellite_action: ServerAction<ActivateSatellite>,
     pub deactivate_satellite_action: ServerAction<DeactivateSatellite>,
     pub update_sphere_desc_action: ServerAction<UpdateSphereDescription>,
+    pub set_sphere_sidebar_action: ServerAction<SetSphereSidebar>,
+    pub set_sphere_color_action: ServerAction<SetSphereColor>,
     pub set_sphere_category_action: ServerAction<SetSphereCategory>,
     pub delete_sphere_category_action: ServerAction<DeleteSphereCategory>,
     pub set_sphere_role_action: ServerAction<SetUserSphereRole>,
@@ -47,7 +52,9 @@ pub struct GlobalState {
     pub update_rule_action: ServerAction<UpdateRule>,
     pub remove_rule_action: ServerAction<RemoveRule>,
     pub moderate_post_action: ServerAction<ModeratePost>,
+    pub set_accepted_answer_action: ServerAction<SetAcceptedAnswer>,
     pub sphere_reload_signal: RwSignal<usize>,
+    pub post_reload_signal: RwSignal<usize>,
     pub post_sort_type: RwSignal<SortType>,
     pub comment_sort_type: RwSignal<SortType>,
     pub show_left_sidebar: RwSignal<bool>,
@@ -72,6 +79,20 @@ pub struct SphereState {
     pub sphere_rules_resource: Resource<Result<Vec<Rule>, AppError>>,
 }
 
+/// State of the displayed post, used by its comments
+#[derive(Copy, Clone)]
+pub struct PostState {
+    pub creator_id: Signal<Option<i64>>,
+    pub accepted_comment_id: Signal<Option<i64>>,
+}
+
+/// Comments collapsed by the user, read from the local storage once for the whole comment section
+#[derive(Copy, Clone)]
+pub struct CollapsedCommentsState {
+    pub collapsed_comments: Signal<CollapsedComments>,
+    pub set_collapsed_comments: WriteSignal<CollapsedComments>,
+}
+
 #[derive(Copy, Clone)]
 pub struct SatelliteState {
     pub satellite_id: Memo<i64>,
@@ -87,6 +108,7 @@ impl GlobalState {
         delete_user_action: ServerAction<DeleteUser>,
         create_sphere_action: ServerAction<CreateSphere>,
         set_settings_action: ServerAction<SetUserSettings>,
+        set_default_post_sort_action: ServerAction<SetDefaultPostSort>,
     ) -> Self {
         let is_notif_read_map = StoredValue::new(HashMap::new());
         Self {
@@ -94,6 +116,7 @@ impl GlobalState {
             logout_action,
             delete_user_action,
             set_settings_action,
+            set_default_post_sort_action,
             subscribe_action: ServerAction::<Subscribe>::new(),
             unsubscribe_action: ServerAction::<Unsubscribe>::new(),
             edit_post_action: ServerAction::<EditPost>::new(),
@@ -104,6 +127,8 @@ impl GlobalState {
             activate_satellite_action: ServerAction::<ActivateSatellite>::new(),
             deactivate_satellite_action: ServerAction::<DeactivateSatellite>::new(),
             update_sphere_desc_action: ServerAction::<UpdateSphereDescription>::new(),
+            set_sphere_sidebar_action: ServerAction::<SetSphereSidebar>::new(),
+            set_sphere_color_action: ServerAction::<SetSphereColor>::new(),
             set_sphere_category_action: ServerAction::<SetSphereCategory>::new(),
             delete_sphere_category_action: ServerAction::<DeleteSphereCategory>::new(),
             set_sphere_role_action: ServerAction::<SetUserSphereRole>::new(),
@@ -111,7 +136,9 @@ impl GlobalState {
             update_rule_action: ServerAction::<UpdateRule>::new(),
             remove_rule_action: ServerAction::<RemoveRule>::new(),
             moderate_post_action: ServerAction::<ModeratePost>::new(),
+            set_accepted_answer_action: ServerAction::<SetAcceptedAnswer>::new(),
             sphere_reload_signal: RwSignal::new(0),
+            post_reload_signal: RwSignal::new(0),
             post_sort_type: RwSignal::new(SortType::Post(PostSortType::Hot)),
             comment_sort_type: RwSignal::new(SortType::Comment(CommentSortType::Best)),
             show_left_sidebar: RwSignal::new(false),
@@ -152,9 +179,11 @@ impl SphereState {
                 move || (
                     sphere_name.get(),
                     state.update_sphere_desc_action.version().get(),
+                    state.set_sphere_sidebar_action.version().get(),
+                    state.set_sphere_color_action.version().get(),
                     state.sphere_reload_signal.get(),
                 ),
-                move |(sphere_name, _, _)| get_sphere_with_user_info(sphere_name)
+                move |(sphere_name, _, _, _, _)| get_sphere_with_user_info(sphere_name)
             ),
             satellite_vec_resource: Resource::new(
                 move || (
@@ -171,7 +200,7 @@ impl SphereState {
                     state.set_sphere_category_action.version().get(),
                     state.delete_sphere_category_action.version().get()
                 ),
-                move |(sphere_name, _, _)| get_sphere_category_vec(sphere_name)
+                move |(sphere_name, _, _)| get_sphere_category_vec(sphere_name, true)
             ),
             sphere_roles_resource: Resource::new(
                 move || (sphere_name.get(), state.set_sphere_role_action.version().get()),
@@ -1,9 +1,14 @@
 use leptos::prelude::*;
+use leptos_use::signal_debounced;
 
+use sphare_core_common::editor::{adjust_textarea_height, get_mention_prefix, insert_mention, TextareaData};
 use sphare_core_common::routes::get_profile_path;
 use sphare_core_user::user::UserHeader;
 
+use sphare_iface_user::user::get_matching_username_vec;
+
 use sphare_cmp_utils::icons::{NsfwIcon, UserIcon};
+use sphare_cmp_utils::unpack::TransitionUnpack;
 
 /// Component to display a user header
 #[component]
@@ -37,4 +42,73 @@ pub fn UserHeaderLink(
             </div>
         </a>
     }.into_any()
-}
\ No newline at end of file
+}
+
+/// Component suggesting matching usernames when typing a `@mention` in the textarea of `data`
+#[component]
+pub fn MentionSuggestions(
+    /// Signals and node ref to control textarea content
+    data: TextareaData,
+) -> impl IntoView {
+    let mention = Memo::new(move |_| {
+        let content = data.content.read();
+        let cursor = data.textarea_ref.get_untracked()
+            .and_then(|textarea_ref| textarea_ref.selection_end().ok().flatten())
+            .map(|cursor| cursor as usize)
+            .unwrap_or(content.len());
+        get_mention_prefix(&content, cursor).map(|(mention_start, prefix)| (mention_start, cursor, prefix.to_string()))
+    });
+    let username_prefix = Signal::derive(move || mention.read().as_ref().map(|(_, _, prefix)| prefix.clone()).unwrap_or_default());
+    let username_prefix_debounced: Signal<String> = signal_debounced(username_prefix, 250.0);
+    let matching_username_resource = Resource::new(
+        move || username_prefix_debounced.get(),
+        move |username_prefix| async {
+            if username_prefix.is_empty() {
+                Ok(Vec::new())
+            } else {
+                get_matching_username_vec(username_prefix).await
+            }
+        },
+    );
+
+    let select_username = move |username: &str| {
+        if let (Some((mention_start, cursor, _)), Some(textarea_ref)) = (mention.get_untracked(), data.textarea_ref.get_untracked()) {
+            let cursor_position = insert_mention(&mut data.content.write(), mention_start, cursor, username);
+            textarea_ref.set_value(&data.content.read_untracked());
+            adjust_textarea_height(data.textarea_ref);
+            let _ = textarea_ref.focus();
+            let _ = textarea_ref.set_selection_start(Some(cursor_position as u32));
+            let _ = textarea_ref.set_selection_end(Some(cursor_position as u32));
+        }
+    };
+
+    view! {
+        <Show when=move || !username_prefix.read().is_empty()>
+            <TransitionUnpack resource=matching_username_resource let:username_vec>
+            {
+                let username_vec = username_vec.clone();
+                (!username_vec.is_empty()).then(|| view! {
+                    <ul class="menu z-1 p-2 shadow-sm bg-base-300 rounded-box w-full">
+                        <For
+                            each=move || username_vec.clone().into_iter()
+                            key=|username| username.clone()
+                            let(username)
+                        >
+                            <li>
+                                <button
+                                    type="button"
+                                    class="flex gap-1.5 items-center text-sm"
+                                    on:click=move |_| select_username(&username)
+                                >
+                                    <UserIcon/>
+                                    {username.clone()}
+                                </button>
+                            </li>
+                        </For>
+                    </ul>
+                })
+            }
+            </TransitionUnpack>
+        </Show>
+    }.into_any()
+}
@@ -1,36 +1,39 @@
+use codee::string::JsonSerdeCodec;
 use leptos::either::Either;
 use leptos::html;
 use leptos::prelude::*;
 use leptos_fluent::move_tr;
 use leptos_router::hooks::use_query_map;
 use leptos_use::BreakpointsTailwind::{Lg, Xxl};
-use leptos_use::{breakpoints_tailwind, signal_throttled_with_options, use_breakpoints, ThrottleOptions};
+use leptos_use::{breakpoints_tailwind, signal_throttled_with_options, storage::use_local_storage, use_breakpoints, ThrottleOptions};
 
 use sphare_core_common::colors::Color;
-use sphare_core_common::constants::{MAX_CONTENT_LENGTH, SCROLL_LOAD_THROTTLE_DELAY};
-use sphare_core_common::editor::TextareaData;
-use sphare_core_common::routes::{get_comment_link, COMMENT_ID_QUERY_PARAM};
+use sphare_core_common::constants::{CHILD_COMMENT_BATCH_SIZE, MAX_CONTENT_LENGTH, SCROLL_LOAD_THROTTLE_DELAY};
+use sphare_core_common::editor::{unescape_plain_text, TextareaData};
+use sphare_core_common::routes::{get_comment_anchor, get_comment_link, COMMENT_ID_QUERY_PARAM};
 use sphare_core_common::unpack::{handle_additional_load, handle_dialog_action_result, handle_initial_load};
-use sphare_core_content::comment::{Comment, CommentWithChildren};
+use sphare_core_content::comment::{CollapsedComments, Comment, CommentWithChildren, COLLAPSED_COMMENTS_STORAGE};
 use sphare_core_content::moderation::Content;
-use sphare_core_content::ranking::Vote;
+use sphare_core_content::ranking::{ReactionCount, Vote};
 
-use sphare_iface_content::comment::{get_comment_tree_by_id, get_post_comment_tree, CreateComment, DeleteComment, EditComment};
+use sphare_iface_content::comment::{get_child_comments, get_comment_subtree, get_comment_with_ancestors, get_post_comment_tree, CreateComment, DeleteComment, EditComment};
+use sphare_iface_content::post::SetAcceptedAnswer;
 
 use sphare_cmp_base::comment::{CommentBody, COMMENT_MAX_DEPTH, COMMENT_MAX_DEPTH_MOBILE, COMMENT_MAX_DEPTH_SMALL_SCREEN};
 use sphare_cmp_base::ranking::CommentSortWidget;
 use sphare_cmp_common::auth_widget::{AuthorWidget, DeleteButton, LoginGuardedOpenModalButton};
 use sphare_cmp_common::role::IsPinnedCheckbox;
-use sphare_cmp_common::state::{GlobalState, SatelliteState, SphereState};
+use sphare_cmp_common::state::{CollapsedCommentsState, GlobalState, PostState, SatelliteState, SphereState};
+use sphare_cmp_common::user::MentionSuggestions;
 use sphare_cmp_utils::colors::ColorIndicator;
 use sphare_cmp_utils::editor::FormMarkdownEditor;
 use sphare_cmp_utils::errors::ErrorDisplay;
-use sphare_cmp_utils::icons::{AddCommentIcon, EditIcon, LoadingIcon};
+use sphare_cmp_utils::icons::{AcceptedAnswerIcon, AddCommentIcon, EditIcon, LoadingIcon};
 use sphare_cmp_utils::unpack::{ActionError, SuspenseUnpack};
 use sphare_cmp_utils::widget::{Badge, DotMenu, IsPinnedWidget, LoadIndicators, MinimizeMaximizeWidget, ModalDialog, ModalFormButtons, ModeratorWidget, ScoreIndicator, ShareButton, TimeSinceEditWidget, TimeSinceWidget};
 
-use crate::moderation::{ModerateCommentButton, ModerationInfoButton};
-use crate::ranking::VotePanel;
+use crate::moderation::{ModerateCommentButton, ModerationInfoButton, ReportCommentButton};
+use crate::ranking::{ReactionPanel, VotePanel};
 
 const DEPTH_TO_COLOR_MAPPING_SIZE: usize = 6;
 const DEPTH_TO_COLOR_MAPPING: [&str; DEPTH_TO_COLOR_MAPPING_SIZE] = [
@@ -52,6 +55,8 @@ pub fn CommentSection(
     additional_load_count: RwSignal<i32>,
 ) -> impl IntoView {
     let state = expect_context::<GlobalState>();
+    let (collapsed_comments, set_collapsed_comments, _) = use_local_storage::<CollapsedComments, JsonSerdeCodec>(COLLAPSED_COMMENTS_STORAGE);
+    provide_context(CollapsedCommentsState { collapsed_comments, set_collapsed_comments });
     let query_comment_id = move || match use_query_map().read().get(COMMENT_ID_QUERY_PARAM) {
         Some(comment_id_string) => comment_id_string.parse::<i64>().ok(),
         None => None,
@@ -84,6 +89,8 @@ pub fn CommentTreeVec(
 
     let _initial_comments_resource = LocalResource::new(
         move || async move {
+            // reload the comments when the accepted answer changes as it is displayed first
+            state.set_accepted_answer_action.version().track();
             is_loading.set(true);
             let initial_load = get_post_comment_tree(
                 post_id.get(),
@@ -167,12 +174,14 @@ pub fn CommentTree(
     let _comment_resource = LocalResource::new(
         move || async move {
             is_loading.set(true);
-            let comment_tree = get_comment_tree_by_id(
+            let comment_with_ancestors = get_comment_with_ancestors(
                 comment_id,
                 state.comment_sort_type.get(),
                 Some(get_max_comment_depth(is_mobile.get_untracked(), is_small_screen.get_untracked())),
             ).await;
-            handle_initial_load(comment_tree.map(|comment| vec![comment]), comment_vec, load_error, None);
+            // the comment is displayed below the chain of its ancestors to give it context
+            let comment_tree = comment_with_ancestors.map(|comment_with_ancestors| vec![comment_with_ancestors.into_comment_tree()]);
+            handle_initial_load(comment_tree, comment_vec, load_error, None);
             is_loading.set(false);
         }
     );
@@ -204,9 +213,24 @@ pub fn CommentBox(
     depth: usize,
     ranking: usize,
 ) -> impl IntoView {
+    let comment_id = comment_with_children.comment.comment_id;
     let comment = RwSignal::new(comment_with_children.comment);
     let child_comments = RwSignal::new(comment_with_children.child_comments);
     let maximize = RwSignal::new(true);
+    let collapsed_comments_state = expect_context::<CollapsedCommentsState>();
+    // restore the collapsed state after hydration, the local storage is not available on the server
+    Effect::new(move || maximize.set(!collapsed_comments_state.collapsed_comments.read().is_collapsed(comment_id)));
+    let comment_ref = NodeRef::<html::Div>::new();
+    let query = use_query_map();
+    // scroll to the comment targeted by a permalink once it is rendered
+    Effect::new(move || {
+        let is_query_comment = query.read().get(COMMENT_ID_QUERY_PARAM).is_some_and(
+            |query_comment_id| query_comment_id.parse::<i64>().is_ok_and(|query_comment_id| query_comment_id == comment_id)
+        );
+        if is_query_comment && let Some(comment_elem) = comment_ref.get() {
+            comment_elem.scroll_into_view();
+        }
+    });
     let sidebar_css = move || {
         if *maximize.read() {
             "p-0.5 rounded-sm hover:bg-base-200 flex flex-col justify-start items-center gap-1"
@@ -219,15 +243,69 @@ pub fn CommentBox(
         DEPTH_TO_COLOR_MAPPING[(depth + ranking) % DEPTH_TO_COLOR_MAPPING.len()]
     );
 
+    let state = expect_context::<GlobalState>();
     let is_small_screen = use_breakpoints(breakpoints_tailwind()).lt(Xxl);
     let is_mobile = use_breakpoints(breakpoints_tailwind()).lt(Lg);
-    let collapse_children = Memo::new(move |_| depth >= get_max_comment_depth(is_mobile.get(), is_small_screen.get()) && !child_comments.read().is_empty());
+    // once a thread is continued, its subtree is rendered from depth 0 to avoid collapsing it again right away
+    let is_thread_continued = RwSignal::new(false);
+    let child_depth = move || match is_thread_continued.get() {
+        true => 0,
+        false => depth + 1,
+    };
+    let collapse_children = Memo::new(move |_| {
+        !is_thread_continued.get() &&
+        depth >= get_max_comment_depth(is_mobile.get(), is_small_screen.get()) &&
+        !child_comments.read().is_empty()
+    });
+    let load_subtree_action = Action::new(move |_: &()| {
+        let comment_id = comment.read_untracked().comment_id;
+        let sort_type = state.comment_sort_type.get_untracked();
+        let max_depth = get_max_comment_depth(is_mobile.get_untracked(), is_small_screen.get_untracked());
+        async move {
+            get_comment_subtree(comment_id, sort_type, Some(max_depth)).await
+        }
+    });
+    Effect::new(move || match &*load_subtree_action.value().read() {
+        Some(Ok(subtree)) => {
+            child_comments.set(subtree.clone());
+            is_thread_continued.set(true);
+        },
+        Some(Err(e)) => log::error!("Failed to load comment subtree: {e}"),
+        None => (),
+    });
+    // the comment trees only contain the first batch of replies of each comment, more might be available if the batch is full
+    let has_more_replies = RwSignal::new(child_comments.read_untracked().len() >= CHILD_COMMENT_BATCH_SIZE as usize);
+    let load_replies_action = Action::new(move |_: &()| {
+        let comment_id = comment.read_untracked().comment_id;
+        let sort_type = state.comment_sort_type.get_untracked();
+        let max_depth = get_max_comment_depth(is_mobile.get_untracked(), is_small_screen.get_untracked());
+        let num_already_loaded = child_comments.read_untracked().len();
+        async move {
+            get_child_comments(comment_id, sort_type, Some(max_depth), num_already_loaded).await
+        }
+    });
+    Effect::new(move || match &*load_replies_action.value().read() {
+        Some(Ok(reply_batch)) => {
+            has_more_replies.set(reply_batch.len() >= CHILD_COMMENT_BATCH_SIZE as usize);
+            child_comments.update(|child_comments| for reply in reply_batch {
+                if !child_comments.iter().any(|child| child.comment.comment_id == reply.comment.comment_id) {
+                    child_comments.push(reply.clone());
+                }
+            });
+        },
+        Some(Err(e)) => log::error!("Failed to load replies: {e}"),
+        None => (),
+    });
 
     view! {
-        <div class="w-full flex lg:gap-1 pt-4">
+        <div class="w-full flex lg:gap-1 pt-4" id=get_comment_anchor(comment_id) node_ref=comment_ref>
             <div
                 class=sidebar_css
-                on:click=move |_| maximize.update(|value: &mut bool| *value = !*value)
+                on:click=move |_| {
+                    let is_collapsed = maximize.get_untracked();
+                    maximize.set(!is_collapsed);
+                    collapsed_comments_state.set_collapsed_comments.update(|collapsed_comments| collapsed_comments.set_collapsed(comment_id, is_collapsed));
+                }
             >
                 <MinimizeMaximizeWidget is_maximized=maximize/>
                 <Show when=maximize>
@@ -242,6 +320,7 @@ pub fn CommentBox(
                 <CommentBottomWidgetBar
                     comment=comment
                     vote=comment_with_children.vote
+                    reactions=comment_with_children.reactions
                     child_comments
                 />
                 <div
@@ -251,12 +330,13 @@ pub fn CommentBox(
                 { move || match collapse_children.get() {
                     true => {
                         Either::Left(view! {
-                            <a
-                                href=format!("?{COMMENT_ID_QUERY_PARAM}={}", comment.read_untracked().comment_id)
+                            <button
                                 class="w-fit mx-auto button-neutral p-2"
+                                disabled=load_subtree_action.pending()
+                                on:click=move |_| { load_subtree_action.dispatch(()); }
                             >
-                                {move_tr!("load-replies")}
-                            </a>
+                                {move_tr!("continue-thread")}
+                            </button>
                         })
                     },
                     false => {
@@ -268,12 +348,21 @@ pub fn CommentBox(
                                     view! {
                                         <CommentBox
                                             comment_with_children
-                                            depth=depth+1
+                                            depth=child_depth()
                                             ranking=ranking+index
                                         />
                                     }.into_any()
                                 }
                             />
+                            <Show when=has_more_replies>
+                                <button
+                                    class="w-fit mx-auto button-neutral p-2 mt-2"
+                                    disabled=load_replies_action.pending()
+                                    on:click=move |_| { load_replies_action.dispatch(()); }
+                                >
+                                    {move_tr!("load-more-replies")}
+                                </button>
+                            </Show>
                         })
                     },
                 }}
@@ -290,8 +379,10 @@ pub fn CommentTopWidgetBar(
 ) -> impl IntoView {
     let author_id = comment.read_untracked().creator_id;
     let author = comment.read_untracked().creator_name.clone();
+    let avatar_url = comment.read_untracked().creator_avatar_url.clone();
     let timestamp = Signal::derive(move || comment.read().create_timestamp);
     let edit_timestamp = Signal::derive(move || comment.read().edit_timestamp);
+    let edit_count = Signal::derive(move || comment.read().edit_count);
     let moderator = Signal::derive(move || comment.read().moderator_name.clone());
     let is_active = Signal::derive(move || comment.read().is_active());
     let is_moderator_comment = comment.read_untracked().is_creator_moderator;
@@ -308,13 +399,15 @@ pub fn CommentTopWidgetBar(
                         author_id
                         author=author.clone()
                         is_moderator=is_moderator_comment
+                        avatar_url=avatar_url.clone()
                     />
                 })
             }
             <ModeratorWidget moderator/>
             <IsPinnedWidget is_pinned/>
+            <AcceptedAnswerWidget comment_id=comment.read_untracked().comment_id/>
             <TimeSinceWidget timestamp/>
-            <TimeSinceEditWidget edit_timestamp/>
+            <TimeSinceEditWidget edit_timestamp edit_count/>
             <Show when=is_query_comment>
                 <ColorIndicator color=Color::Red class="w-3 h-3 rounded-full"/>
             </Show>
@@ -327,18 +420,20 @@ pub fn CommentTopWidgetBar(
 pub fn CommentBottomWidgetBar(
     comment: RwSignal<Comment>,
     vote: Option<Vote>,
+    reactions: Vec<ReactionCount>,
     child_comments: RwSignal<Vec<CommentWithChildren>>,
 ) -> impl IntoView {
     let state = expect_context::<GlobalState>();
     let sphere_state = expect_context::<SphereState>();
     let satellite_state = use_context::<SatelliteState>();
     let vote = vote;
-    let (comment_id, post_id, score, author_id) =
+    let (comment_id, post_id, score, score_minus, author_id) =
         comment.with_untracked(|comment| {
             (
                 comment.comment_id,
                 comment.post_id,
                 comment.score,
+                comment.score_minus,
                 comment.creator_id,
             )
         });
@@ -358,6 +453,7 @@ pub fn CommentBottomWidgetBar(
                         post_id
                         comment_id=Some(comment_id)
                         score
+                        score_minus
                         vote=vote.clone()
                     />
                 }),
@@ -370,6 +466,9 @@ pub fn CommentBottomWidgetBar(
                 comment_vec=child_comments
                 parent_comment_id=Some(comment_id)
             />
+            { move || is_active.get().then(|| view! {
+                <ReactionPanel post_id comment_id=Some(comment_id) reactions=reactions.clone()/>
+            })}
             <DotMenu>
                 { move || is_active.get().then_some(view!{
                     <EditCommentButton
@@ -377,6 +476,7 @@ pub fn CommentBottomWidgetBar(
                         author_id
                         comment
                     />
+                    <AcceptAnswerButton comment/>
                     <SuspenseUnpack resource=state.user let:user>
                     {
                         match user.as_ref().is_some_and(|user| user.user_id == author_id) {
@@ -386,6 +486,7 @@ pub fn CommentBottomWidgetBar(
                                     comment_id
                                     comment
                                 />
+                                <ReportCommentButton comment_id/>
                             })
                         }
                     }
@@ -545,6 +646,7 @@ pub fn CommentForm(
                         data=comment_data
                         maxlength=Some(MAX_CONTENT_LENGTH as usize)
                     />
+                    <MentionSuggestions data=comment_data/>
                     <IsPinnedCheckbox sphere_name=sphere_name/>
                     <ModalFormButtons
                         disable_publish=is_comment_empty
@@ -598,6 +700,66 @@ pub fn EditCommentButton(
     }
 }
 
+/// Component to display a badge on the accepted answer of a post
+#[component]
+pub fn AcceptedAnswerWidget(
+    comment_id: i64,
+) -> impl IntoView {
+    let post_state = use_context::<PostState>();
+    let is_accepted = move || post_state.is_some_and(|post_state| post_state.accepted_comment_id.get() == Some(comment_id));
+    view! {
+        <Show when=is_accepted>
+            <Badge text=move_tr!("accepted-answer")>
+                <AcceptedAnswerIcon/>
+            </Badge>
+        </Show>
+    }
+}
+
+/// Component for the author of a post to mark a top-level comment as the accepted answer, or to clear it
+#[component]
+pub fn AcceptAnswerButton(
+    comment: RwSignal<Comment>,
+) -> impl IntoView {
+    let state = expect_context::<GlobalState>();
+    let post_state = use_context::<PostState>();
+    let (comment_id, post_id, is_top_level) = comment.with_untracked(|comment| (comment.comment_id, comment.post_id, comment.parent_id.is_none()));
+    let is_accepted = move || post_state.is_some_and(|post_state| post_state.accepted_comment_id.get() == Some(comment_id));
+    let show_button = move || match (&(*state.user.read()), post_state) {
+        (Some(Ok(Some(user))), Some(post_state)) => is_top_level && post_state.creator_id.get() == Some(user.user_id),
+        _ => false,
+    };
+    let button_class = move || match is_accepted() {
+        true => "button-rounded-primary",
+        false => "button-rounded-neutral",
+    };
+    let accept_title = move_tr!("accept-answer");
+    let remove_title = move_tr!("remove-accepted-answer");
+
+    view! {
+        <Suspense>
+            <Show when=show_button>
+                <button
+                    class=button_class
+                    title=move || match is_accepted() {
+                        true => remove_title.get(),
+                        false => accept_title.get(),
+                    }
+                    disabled=state.set_accepted_answer_action.pending()
+                    on:click=move |_| {
+                        state.set_accepted_answer_action.dispatch(SetAcceptedAnswer {
+                            post_id,
+                            comment_id: (!is_accepted()).then_some(comment_id),
+                        });
+                    }
+                >
+                    <AcceptedAnswerIcon/>
+                </button>
+            </Show>
+        </Suspense>
+    }
+}
+
 /// Component to delete a comment
 #[component]
 pub fn DeleteCommentButton(
@@ -658,7 +820,7 @@ pub fn EditCommentForm(
     let (current_body, is_markdown) =
         comment.with_untracked(|comment| match &comment.markdown_body {
             Some(body) => (body.clone(), true),
-            None => (comment.body.clone(), false),
+            None => (unescape_plain_text(&comment.body), false),
         });
     let textarea_ref = NodeRef::<html::Textarea>::new();
     let comment_data = TextareaData {
@@ -693,6 +855,7 @@ pub fn EditCommentForm(
                         is_markdown
                         maxlength=Some(MAX_CONTENT_LENGTH as usize)
                     />
+                    <MentionSuggestions data=comment_data/>
                     <IsPinnedCheckbox sphere_name value=comment.read_untracked().is_pinned/>
                     <ModalFormButtons
                         disable_publish=is_comment_empty
@@ -1,6 +1,7 @@
 #![recursion_limit = "256"]
 pub mod comment;
 pub mod moderation;
+pub mod poll;
 pub mod post;
 pub mod profile;
 pub mod ranking;
\ No newline at end of file
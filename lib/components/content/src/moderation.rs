@@ -3,25 +3,28 @@ use leptos::prelude::*;
 use leptos_fluent::move_tr;
 
 use sphare_core_common::checks::check_string_length;
-use sphare_core_common::constants::MAX_MOD_MESSAGE_LENGTH;
+use sphare_core_common::common::Rule;
+use sphare_core_common::constants::{MAX_MOD_MESSAGE_LENGTH, MAX_REPORT_REASON_LENGTH};
 use sphare_core_common::editor::TextareaData;
 use sphare_core_common::errors::AppError;
+use sphare_core_common::routes::{get_comment_path, get_post_path, get_profile_path};
 use sphare_core_common::unpack::handle_dialog_action_result;
 use sphare_core_content::comment::Comment;
-use sphare_core_content::moderation::{Content, ModerationInfo};
+use sphare_core_content::moderation::{Content, ContentReport, ModerationInfo, REPORT_FETCH_LIMIT};
 use sphare_core_sphere::rule::get_rule_title;
 use sphare_core_user::role::PermissionLevel;
 
-use sphare_iface_content::moderation::ModerateComment;
-use sphare_iface_sphere::rule::get_rule_by_id;
+use sphare_iface_content::moderation::{get_global_reports, ModerateComment, ModerateReportedContent, ReportComment, ReportPost};
+use sphare_iface_sphere::rule::{get_rule_by_id, get_rule_vec};
 
 use sphare_cmp_base::moderation::ModerationInfoDialog;
+use sphare_cmp_common::auth_widget::LoginGuardedButton;
 use sphare_cmp_common::role::AuthorizedShow;
 use sphare_cmp_common::state::{GlobalState, SphereState};
 use sphare_cmp_utils::editor::FormTextEditor;
-use sphare_cmp_utils::icons::{HammerIcon, MagnifierIcon};
+use sphare_cmp_utils::icons::{FlagIcon, HammerIcon, MagnifierIcon};
 use sphare_cmp_utils::unpack::{ActionError, SuspenseUnpack, TransitionUnpack};
-use sphare_cmp_utils::widget::{ModalDialog, ModalFormButtons};
+use sphare_cmp_utils::widget::{ModalDialog, ModalFormButtons, TimeSinceWidget};
 
 /// Component to moderate a post
 #[component]
@@ -186,6 +189,225 @@ pub fn ModerateCommentDialog(
     }.into_any()
 }
 
+/// Button opening a dialog to report content
+#[component]
+pub fn ReportButton(show_dialog: RwSignal<bool>) -> impl IntoView {
+    let button_class = Signal::derive(move || match show_dialog.get() {
+        true => "button-rounded-primary",
+        false => "button-rounded-neutral",
+    });
+    view! {
+        <LoginGuardedButton
+            button_class
+            button_action=move |_| show_dialog.set(true)
+        >
+            <FlagIcon/>
+        </LoginGuardedButton>
+    }.into_any()
+}
+
+/// Component to report a post to the moderators of its sphere
+#[component]
+pub fn ReportPostButton(post_id: i64) -> impl IntoView {
+    let show_dialog = RwSignal::new(false);
+    let report_post_action = ServerAction::<ReportPost>::new();
+    Effect::new(move |_| if let Some(Ok(_)) = report_post_action.value().get() {
+        show_dialog.set(false);
+    });
+    view! {
+        <div>
+            <ReportButton show_dialog/>
+            <ModalDialog
+                class="w-full max-w-xl"
+                show_dialog
+            >
+                <div class="bg-base-100 shadow-xl p-3 rounded-xs flex flex-col gap-3">
+                    <div class="text-center font-bold text-2xl">{move_tr!("report-post")}</div>
+                    <ActionForm action=report_post_action>
+                        <div class="flex flex-col gap-3 w-full">
+                            <input
+                                type="text"
+                                name="post_id"
+                                class="hidden"
+                                value=post_id
+                            />
+                            <ReportFormInputs show_dialog/>
+                        </div>
+                    </ActionForm>
+                    <ActionError action=report_post_action.into()/>
+                </div>
+            </ModalDialog>
+        </div>
+    }.into_any()
+}
+
+/// Component to report a comment to the moderators of its sphere
+#[component]
+pub fn ReportCommentButton(comment_id: i64) -> impl IntoView {
+    let show_dialog = RwSignal::new(false);
+    let report_comment_action = ServerAction::<ReportComment>::new();
+    Effect::new(move |_| if let Some(Ok(_)) = report_comment_action.value().get() {
+        show_dialog.set(false);
+    });
+    view! {
+        <div>
+            <ReportButton show_dialog/>
+            <ModalDialog
+                class="w-full max-w-xl"
+                show_dialog
+            >
+                <div class="bg-base-100 shadow-xl p-3 rounded-xs flex flex-col gap-3">
+                    <div class="text-center font-bold text-2xl">{move_tr!("report-comment")}</div>
+                    <ActionForm action=report_comment_action>
+                        <div class="flex flex-col gap-3 w-full">
+                            <input
+                                type="text"
+                                name="comment_id"
+                                class="hidden"
+                                value=comment_id
+                            />
+                            <ReportFormInputs show_dialog/>
+                        </div>
+                    </ActionForm>
+                    <ActionError action=report_comment_action.into()/>
+                </div>
+            </ModalDialog>
+        </div>
+    }.into_any()
+}
+
+/// Inputs of the forms to report a post or a comment
+#[component]
+pub fn ReportFormInputs(show_dialog: RwSignal<bool>) -> impl IntoView {
+    let textarea_ref = NodeRef::<html::Textarea>::new();
+    let reason_data = TextareaData {
+        content: RwSignal::new(String::new()),
+        textarea_ref,
+    };
+    let is_form_invalid = Signal::derive(move || {
+        check_string_length(&reason_data.content.read(), "Report reason", MAX_REPORT_REASON_LENGTH, true).is_err()
+    });
+    view! {
+        <RuleSelect name="rule_id"/>
+        <FormTextEditor
+            name="reason"
+            placeholder=move_tr!("report-reason")
+            data=reason_data
+            maxlength=Some(MAX_REPORT_REASON_LENGTH)
+        />
+        <ModalFormButtons
+            disable_publish=is_form_invalid
+            show_form=show_dialog
+        />
+    }.into_any()
+}
+
+/// Displays the reports of all spheres to admins, along with a form to moderate the reported content
+#[component]
+pub fn GlobalReportInbox() -> impl IntoView {
+    let moderate_action = ServerAction::<ModerateReportedContent>::new();
+    let report_resource = Resource::new(
+        move || moderate_action.version().get(),
+        |_| get_global_reports(REPORT_FETCH_LIMIT, 0),
+    );
+    let global_rules_resource = Resource::new(
+        || (),
+        |_| get_rule_vec(None),
+    );
+
+    view! {
+        <div class="w-full xl:w-3/5 4xl:w-2/5 p-2 xl:px-4 mx-auto flex flex-col gap-2">
+            <h2 class="py-4 text-4xl text-center">{move_tr!("report-inbox")}</h2>
+            <ActionError action=moderate_action.into()/>
+            <ul class="flex flex-col flex-1 w-full overflow-x-hidden overflow-y-auto divide-y divide-base-content/20">
+            <SuspenseUnpack resource=report_resource let:report_vec>
+            {
+                report_vec.iter().map(|report| view! {
+                    <li><ContentReportItem report=report.clone() global_rules_resource moderate_action/></li>
+                }).collect_view()
+            }
+            </SuspenseUnpack>
+            </ul>
+        </div>
+    }
+}
+
+/// Displays a content report with a form to moderate the reported content for a site-wide rule
+#[component]
+fn ContentReportItem(
+    report: ContentReport,
+    global_rules_resource: Resource<Result<Vec<Rule>, AppError>>,
+    moderate_action: ServerAction<ModerateReportedContent>,
+) -> impl IntoView {
+    let content_path = match report.comment_id {
+        Some(comment_id) => get_comment_path(&report.sphere_name, None, report.post_id, comment_id),
+        None => get_post_path(&report.sphere_name, None, report.post_id),
+    };
+    let content_label = match report.comment_id {
+        Some(_) => move_tr!("reported-comment"),
+        None => move_tr!("reported-post"),
+    };
+    let reported_rule = report.rule_title.map(|rule_title| view! {
+        <div class="text-sm">{move_tr!("infringed-rule")}": "{rule_title}</div>
+    });
+    let textarea_ref = NodeRef::<html::Textarea>::new();
+    let message_data = TextareaData {
+        content: RwSignal::new(String::new()),
+        textarea_ref,
+    };
+    let is_message_empty = move || message_data.content.read().is_empty();
+
+    view! {
+        <div class="flex flex-col gap-2 p-2">
+            <div class="flex flex-wrap gap-2 items-center">
+                <a href=content_path class="link font-semibold">{content_label}</a>
+                <span class="text-sm">{report.sphere_name}</span>
+                <a href=get_profile_path(&report.reporter_name) class="text-sm link">{report.reporter_name.clone()}</a>
+                <TimeSinceWidget timestamp=report.create_timestamp/>
+            </div>
+            {reported_rule}
+            <div class="text-sm">{report.reason}</div>
+            <ActionForm action=moderate_action>
+                <div class="flex flex-col gap-2 w-full">
+                    <input
+                        type="text"
+                        name="report_id"
+                        class="hidden"
+                        value=report.report_id
+                    />
+                    <select
+                        class="select_input max-w-full"
+                        name="rule_id"
+                    >
+                        <TransitionUnpack resource=global_rules_resource let:rules_vec>
+                        {
+                            rules_vec.iter().map(|rule| view! {
+                                <option value=rule.rule_id>
+                                    {get_rule_title(&rule.title, false)}
+                                </option>
+                            }).collect_view()
+                        }
+                        </TransitionUnpack>
+                    </select>
+                    <FormTextEditor
+                        name="moderator_message"
+                        placeholder=move_tr!("moderator-message")
+                        data=message_data
+                        maxlength=Some(MAX_MOD_MESSAGE_LENGTH)
+                    />
+                    <button
+                        type="submit"
+                        class="button-error self-end"
+                        disabled=is_message_empty
+                    >
+                        {move_tr!("moderate")}
+                    </button>
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
+
 /// Dialog to select infringed rule
 #[component]
 pub fn RuleSelect(
@@ -0,0 +1,210 @@
+use leptos::prelude::*;
+use leptos_fluent::move_tr;
+use leptos_router::hooks::use_query_map;
+
+use sphare_core_common::constants::{MAX_POLL_DURATION_HOURS, MAX_POLL_OPTIONS, MAX_POLL_OPTION_LENGTH, MAX_SPHERE_NAME_LENGTH, MAX_TITLE_LENGTH, MIN_POLL_OPTIONS};
+use sphare_core_common::routes::CREATE_POST_SPHERE_QUERY_PARAM;
+use sphare_core_content::poll::{PollOption, PollWithResults};
+
+use sphare_iface_content::poll::{get_poll_by_post_id, CreatePoll, VotePoll};
+
+use sphare_cmp_utils::editor::LengthLimitedInput;
+use sphare_cmp_utils::unpack::{ActionError, TransitionUnpack};
+
+/// Durations in hours that can be selected when creating a poll
+pub const POLL_DURATION_HOURS_OPTIONS: [u32; 4] = [24, 3 * 24, 7 * 24, MAX_POLL_DURATION_HOURS];
+
+/// Form to create a post with a poll
+#[component]
+pub fn CreatePoll() -> impl IntoView {
+    let create_poll_action = ServerAction::<CreatePoll>::new();
+
+    let query = use_query_map();
+    let sphere_name_input = RwSignal::new(
+        query.read_untracked().get(CREATE_POST_SPHERE_QUERY_PARAM).unwrap_or_default()
+    );
+    let title_input = RwSignal::new(String::default());
+    let option_vec_input = RwSignal::new(vec![String::default(); MIN_POLL_OPTIONS]);
+
+    view! {
+        <div class="w-full xl:w-3/5 4xl:w-2/5 p-2 mx-auto flex flex-col gap-2 overflow-auto">
+            <ActionForm action=create_poll_action>
+                <div class="flex flex-col gap-2 w-full">
+                    <h2 class="py-4 text-4xl text-center">{move_tr!("create-poll")}</h2>
+                    <input
+                        type="text"
+                        name="sphere_name"
+                        placeholder=move_tr!("sphere")
+                        autocomplete="off"
+                        class="input_primary"
+                        class=("input_error", move || sphere_name_input.read().is_empty())
+                        maxlength=MAX_SPHERE_NAME_LENGTH
+                        on:input=move |ev| sphere_name_input.set(event_target_value(&ev).to_lowercase())
+                        prop:value=sphere_name_input
+                    />
+                    <LengthLimitedInput
+                        name="title"
+                        placeholder=move_tr!("title")
+                        content=title_input
+                        autofocus=true
+                        minlength=Some(1)
+                        maxlength=Some(MAX_TITLE_LENGTH as usize)
+                    />
+                    <For
+                        each=move || 0..option_vec_input.read().len()
+                        key=|index| *index
+                        let:index
+                    >
+                        <input
+                            type="text"
+                            name=format!("option_vec[{index}]")
+                            placeholder=move_tr!("poll-option", {"index" => index + 1})
+                            autocomplete="off"
+                            class="input_primary"
+                            maxlength=MAX_POLL_OPTION_LENGTH
+                            on:input=move |ev| option_vec_input.write()[index] = event_target_value(&ev)
+                        />
+                    </For>
+                    <button
+                        type="button"
+                        class="button-neutral self-start"
+                        disabled=move || { option_vec_input.read().len() >= MAX_POLL_OPTIONS }
+                        on:click=move |_| option_vec_input.write().push(String::default())
+                    >
+                        {move_tr!("add-poll-option")}
+                    </button>
+                    <div class="flex justify-between items-center">
+                        {move_tr!("poll-duration")}
+                        <select name="duration_hours" class="select_input w-fit">
+                        {
+                            POLL_DURATION_HOURS_OPTIONS.into_iter().map(|duration_hours| view! {
+                                <option value=duration_hours selected=duration_hours == POLL_DURATION_HOURS_OPTIONS[0]>
+                                    {move_tr!("poll-duration-days", {"count" => duration_hours / 24})}
+                                </option>
+                            }).collect_view()
+                        }
+                        </select>
+                    </div>
+                    <button type="submit" class="button-secondary" disabled=move || {
+                        sphere_name_input.read().is_empty() ||
+                        title_input.read().is_empty() ||
+                        option_vec_input.read().iter().any(|option| option.trim().is_empty()) ||
+                        create_poll_action.pending().get()
+                    }>
+                        {move_tr!("publish")}
+                    </button>
+                </div>
+            </ActionForm>
+            <ActionError action=create_poll_action.into()/>
+        </div>
+    }
+}
+
+/// Displays the poll of a post, if any. Results are shown once the user has voted or the poll is closed
+#[component]
+pub fn PollPanel(
+    post_id: i64,
+) -> impl IntoView {
+    let vote_poll_action = ServerAction::<VotePoll>::new();
+    let poll_resource = Resource::new(
+        move || vote_poll_action.version().get(),
+        move |_| get_poll_by_post_id(post_id),
+    );
+
+    view! {
+        <TransitionUnpack resource=poll_resource let:poll>
+        {
+            poll.clone().map(|poll| view! {
+                <div class="flex flex-col gap-1 lg:w-19/20 xl:w-9/10 2xl:w-17/20 3xl:w-4/5 4xl:w-3/4 5xl:w-7/10">
+                {
+                    match poll.show_results() {
+                        true => view! { <PollResults poll/> }.into_any(),
+                        false => view! { <PollVoteForm poll vote_poll_action/> }.into_any(),
+                    }
+                }
+                </div>
+            })
+        }
+        </TransitionUnpack>
+        <ActionError action=vote_poll_action.into()/>
+    }.into_any()
+}
+
+/// Buttons to vote for one of the options of a poll
+#[component]
+fn PollVoteForm(
+    poll: PollWithResults,
+    vote_poll_action: ServerAction<VotePoll>,
+) -> impl IntoView {
+    let poll_id = poll.poll.poll_id;
+    view! {
+        {
+            poll.option_vec.into_iter().map(|option| view! {
+                <ActionForm action=vote_poll_action>
+                    <input type="text" name="poll_id" class="hidden" value=poll_id/>
+                    <input type="text" name="option_id" class="hidden" value=option.option_id/>
+                    <button
+                        type="submit"
+                        class="button-neutral w-full text-left"
+                        disabled=move || vote_poll_action.pending().get()
+                    >
+                        {option.option_text}
+                    </button>
+                </ActionForm>
+            }).collect_view()
+        }
+        <div class="text-sm text-gray-400">{move_tr!("poll-ends", {"date" => poll.poll.ends_at.format("%Y-%m-%d %H:%M").to_string()})}</div>
+    }
+}
+
+/// Vote counts of the options of a poll
+#[component]
+fn PollResults(
+    poll: PollWithResults,
+) -> impl IntoView {
+    let total_votes = poll.total_votes();
+    let user_option_id = poll.user_option_id;
+    let is_closed = poll.poll.is_closed();
+    view! {
+        {
+            poll.option_vec.into_iter().map(|option| {
+                let is_user_option = user_option_id == Some(option.option_id);
+                view! { <PollOptionResult option total_votes is_user_option/> }
+            }).collect_view()
+        }
+        <div class="text-sm text-gray-400">
+        {
+            match is_closed {
+                true => move_tr!("poll-closed", {"count" => total_votes}),
+                false => move_tr!("poll-votes", {"count" => total_votes}),
+            }
+        }
+        </div>
+    }
+}
+
+/// Vote count of an option of a poll, with a bar proportional to its share of votes
+#[component]
+fn PollOptionResult(
+    option: PollOption,
+    total_votes: i64,
+    is_user_option: bool,
+) -> impl IntoView {
+    let percentage = match total_votes {
+        0 => 0,
+        _ => option.num_votes * 100 / total_votes,
+    };
+    let bar_class = match is_user_option {
+        true => "absolute inset-y-0 left-0 rounded-sm bg-primary/40",
+        false => "absolute inset-y-0 left-0 rounded-sm bg-base-content/20",
+    };
+    view! {
+        <div class="relative w-full rounded-sm bg-base-200">
+            <div class=bar_class style=format!("width: {percentage}%")/>
+            <div class="relative flex justify-between gap-2 px-2 py-1">
+                <span class="wrap-anywhere">{option.option_text}</span>
+                <span>{format!("{percentage}% ({})", option.num_votes)}</span>
+            </div>
+        </div>
+    }
+}
@@ -1,38 +1,46 @@
 use leptos::either::Either;
+use leptos::ev::SubmitEvent;
 use leptos::html;
 use leptos::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+use leptos::web_sys::{FormData, HtmlFormElement};
 use leptos_fluent::move_tr;
 use leptos_router::hooks::{use_params_map, use_query_map};
 use leptos_use::signal_debounced;
 use url::Url;
 
 use sphare_core_common::checks::{check_sphere_name, check_sphere_name_with_options};
-use sphare_core_common::constants::{COMMENT_BATCH_SIZE, MAX_CONTENT_LENGTH};
-use sphare_core_common::editor::{adjust_textarea_height, TextareaData};
-use sphare_core_common::routes::{get_post_id_memo, get_post_link, CREATE_POST_SPHERE_QUERY_PARAM};
+use sphare_core_common::constants::{COMMENT_BATCH_SIZE, DRAFT_AUTOSAVE_DELAY, MAX_CONTENT_LENGTH, MAX_POST_SCHEDULE_HOURS, POST_ID_PARAM};
+use sphare_core_common::editor::{adjust_textarea_height, unescape_plain_text, TextareaData};
+use sphare_core_common::routes::{get_post_id_memo, get_post_link, get_post_path, CREATE_POLL_ROUTE, CREATE_POST_SPHERE_QUERY_PARAM};
 use sphare_core_content::comment::CommentWithChildren;
 use sphare_core_content::embed::{EmbedType, LinkType};
 use sphare_core_content::moderation::Content;
-use sphare_core_content::post::{Post, PostWithInfo};
+use sphare_core_content::post::{get_reading_time_minutes, reading_time, CrosspostHeader, Post, PostWithInfo};
 
-use sphare_iface_content::post::{get_post_inherited_attributes, get_post_with_info_by_id, CreatePost};
+use sphare_iface_content::post::{add_post_images, get_duplicate_link_post_id, get_post_inherited_attributes, get_post_with_info_by_id, get_similar_posts, load_draft, CreatePost, Crosspost, SaveDraft};
 use sphare_iface_content::search::get_matching_sphere_header_vec;
 use sphare_iface_sphere::sphere_category::get_sphere_category_vec;
 
 use sphare_cmp_base::embed::Embed;
 use sphare_cmp_base::moderation::ModeratedBody;
-use sphare_cmp_base::post::{PostBadgeList, PostForm};
-use sphare_cmp_common::auth_widget::{AuthorWidget, DeleteButton};
+use sphare_cmp_base::post::{PostBadgeList, PostForm, PostMiniatureList};
+use sphare_cmp_common::auth_widget::{AuthorWidget, DeleteButton, LoginGuardedButton};
 use sphare_cmp_common::sphere::SphereHeader;
-use sphare_cmp_common::state::{GlobalState, SphereState};
-use sphare_cmp_utils::icons::EditIcon;
+use sphare_cmp_common::state::{GlobalState, PostState, SphereState};
+use sphare_cmp_utils::form::IdempotencyKeyInput;
+use sphare_cmp_utils::icons::{EditIcon, ImageIcon, RefreshIcon};
 use sphare_cmp_utils::node_utils::has_reached_scroll_load_threshold;
 use sphare_cmp_utils::unpack::{ActionError, SuspenseUnpack, TransitionUnpack};
-use sphare_cmp_utils::widget::{ContentBody, DotMenu, ModalDialog, ModalFormButtons, ModeratorWidget, ScoreIndicator, ShareButton, TimeSinceEditWidget, TimeSinceWidget};
+use sphare_cmp_utils::widget::{ContentBody, DotMenu, ModalDialog, ModalFormButtons, ModeratorWidget, ScoreIndicator, ShareButton, TimeSinceEditWidget, TimeSinceWidget, IMAGE_FILE_PARAM};
 
 use crate::comment::{CommentButtonWithCount, CommentSection};
-use crate::moderation::{ModeratePostButton, ModerationInfoButton};
-use crate::ranking::VotePanel;
+use crate::moderation::{ModeratePostButton, ModerationInfoButton, ReportPostButton};
+use crate::poll::PollPanel;
+use crate::ranking::{ReactionPanel, VotePanel};
+
+/// Publication delays in hours that can be selected when creating a post, 0 publishes it immediately
+pub const POST_SCHEDULE_HOURS_OPTIONS: [u32; 6] = [0, 1, 6, 24, 7 * 24, MAX_POST_SCHEDULE_HOURS];
 
 /// Component to display a post
 #[component]
@@ -46,14 +54,21 @@ pub fn Post() -> impl IntoView {
             post_id.get(),
             state.edit_post_action.version().get(),
             state.delete_post_action.version().get(),
-            state.moderate_post_action.version().get()
+            state.moderate_post_action.version().get(),
+            state.set_accepted_answer_action.version().get(),
+            state.post_reload_signal.get(),
         ),
-        move |(post_id, _, _, _)| {
+        move |(post_id, _, _, _, _, _)| {
             log::debug!("Load data for post: {post_id}");
             get_post_with_info_by_id(post_id)
         },
     );
 
+    provide_context(PostState {
+        creator_id: Signal::derive(move || post_resource.get().and_then(|post| post.ok()).map(|post| post.post.creator_id)),
+        accepted_comment_id: Signal::derive(move || post_resource.get().and_then(|post| post.ok()).and_then(|post| post.post.accepted_comment_id)),
+    });
+
     let comment_vec = RwSignal::new(Vec::<CommentWithChildren>::with_capacity(
         COMMENT_BATCH_SIZE as usize,
     ));
@@ -76,10 +91,13 @@ pub fn Post() -> impl IntoView {
                             <PostTopWidgetBar
                                 creator_id=post_with_info.post.creator_id
                                 creator_name=post_with_info.post.creator_name.clone()
+                                creator_avatar_url=post_with_info.post.creator_avatar_url.clone()
                                 moderator_name=post_with_info.post.moderator_name.clone()
                                 is_creator_moderator=post_with_info.post.is_creator_moderator
                                 create_timestamp=post_with_info.post.create_timestamp
                                 edit_timestamp=post_with_info.post.edit_timestamp
+                                edit_count=post_with_info.post.edit_count
+                                reading_time_minutes=get_reading_time_minutes(reading_time(&post_with_info.post))
                                 is_active=post_with_info.post.is_active()
                             />
                             <h2 class="card-title text-wrap wrap-anywhere">
@@ -88,6 +106,7 @@ pub fn Post() -> impl IntoView {
                                 false => move_tr!("deleted")
                             }}
                             </h2>
+                            { post_with_info.crosspost_header.clone().map(|crosspost_header| view! { <CrosspostBadge crosspost_header/> }) }
                             <PostBody
                                 body=post_with_info.post.body.clone()
                                 markdown_body=post_with_info.post.markdown_body.clone()
@@ -96,7 +115,8 @@ pub fn Post() -> impl IntoView {
                                 is_sphere_rule=post_with_info.post.is_sphere_rule
                                 delete_timestamp=post_with_info.post.delete_timestamp
                             />
-                            <Embed link=post_with_info.post.link.clone()/>
+                            <PollPanel post_id=post_with_info.post.post_id/>
+                            <Embed link=post_with_info.post.link.clone() post_id=Some(post_with_info.post.post_id) is_blurred=post_with_info.post.is_blurred/>
                             <PostBadgeList
                                 sphere_header=None
                                 sphere_category=post_with_info.sphere_category.clone()
@@ -109,11 +129,42 @@ pub fn Post() -> impl IntoView {
                     </div>
                 </div>
             </TransitionUnpack>
+            <SimilarPosts post_id/>
             <CommentSection post_id comment_vec is_loading additional_load_count/>
+            <ActionError action=state.set_accepted_answer_action.into()/>
         </div>
     }.into_any()
 }
 
+/// Displays popular posts of the same sphere and category as the post `post_id`
+#[component]
+pub fn SimilarPosts(
+    #[prop(into)]
+    post_id: Signal<i64>,
+) -> impl IntoView {
+    let similar_post_resource = Resource::new(
+        move || post_id.get(),
+        get_similar_posts,
+    );
+    view! {
+        <TransitionUnpack resource=similar_post_resource let:post_vec>
+        {
+            match post_vec.is_empty() {
+                true => None,
+                false => Some(view! {
+                    <div class="flex flex-col gap-1 px-2">
+                        <h3 class="text-lg font-semibold">{move_tr!("similar-posts")}</h3>
+                        <ul class="flex flex-col divide-y divide-base-content/20">
+                            <PostMiniatureList post_vec=post_vec.clone() show_sphere_header=false/>
+                        </ul>
+                    </div>
+                }),
+            }
+        }
+        </TransitionUnpack>
+    }
+}
+
 /// Displays the body of a post
 #[component]
 pub fn PostBody(
@@ -159,10 +210,13 @@ pub fn PostBody(
 fn PostTopWidgetBar(
     creator_id: i64,
     creator_name: String,
+    creator_avatar_url: Option<String>,
     moderator_name: Option<String>,
     is_creator_moderator: bool,
     create_timestamp: chrono::DateTime<chrono::Utc>,
     edit_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    edit_count: i32,
+    reading_time_minutes: u64,
     is_active: bool,
 ) -> impl IntoView {
     view! {
@@ -173,12 +227,47 @@ fn PostTopWidgetBar(
                         author_id=creator_id
                         author=creator_name
                         is_moderator=is_creator_moderator
+                        avatar_url=creator_avatar_url
                     />
                 })
             }
             <ModeratorWidget moderator=moderator_name/>
             <TimeSinceWidget timestamp=create_timestamp/>
-            <TimeSinceEditWidget edit_timestamp=edit_timestamp/>
+            <TimeSinceEditWidget edit_timestamp=edit_timestamp edit_count/>
+            <ReadingTimeWidget reading_time_minutes/>
+        </div>
+    }
+}
+
+/// Component to display the estimated reading time of a post
+#[component]
+fn ReadingTimeWidget(reading_time_minutes: u64) -> impl IntoView {
+    view! {
+        <div class="flex items-center text-xs lg:text-sm px-1 text-gray-400">
+            {move_tr!("reading-time", {"count" => reading_time_minutes})}
+        </div>
+    }
+}
+
+/// Component to display the original location of a crosspost
+#[component]
+fn CrosspostBadge(crosspost_header: CrosspostHeader) -> impl IntoView {
+    let sphere_name = crosspost_header.sphere_name.clone();
+    view! {
+        <div class="flex items-center text-xs lg:text-sm text-gray-400">
+        { match crosspost_header.is_active {
+            true => Either::Left(view! {
+                <a
+                    href=get_post_path(&crosspost_header.sphere_name, crosspost_header.satellite_id, crosspost_header.post_id)
+                    class="hover:underline"
+                >
+                    {move_tr!("crossposted-from", {"sphere" => sphere_name.clone()})}
+                </a>
+            }),
+            false => Either::Right(view! {
+                <span>{move_tr!("crossposted-from-deleted", {"sphere" => sphere_name.clone()})}</span>
+            }),
+        }}
         </div>
     }
 }
@@ -204,6 +293,7 @@ fn PostBottomWidgetBar(
                         post_id=post.post.post_id
                         comment_id=None
                         score=post.post.score
+                        score_minus=post.post.downvotes()
                         vote=post.vote.clone()
                     />
                 }),
@@ -212,19 +302,25 @@ fn PostBottomWidgetBar(
                 }),
             }}
             <CommentButtonWithCount post_id comment_vec count=post.post.num_comments/>
+            { is_active.then_some(view! {
+                <ReactionPanel post_id comment_id=None reactions=post.reactions.clone()/>
+            })}
             <DotMenu>
                 { is_active.then_some(view! {
                     <EditPostButton author_id post=stored_post/>
+                    <AddPostImagesButton post_id author_id/>
                     <SuspenseUnpack resource=state.user let:user>
                     {
                         match user.as_ref().is_some_and(|user| user.user_id == author_id) {
                             true => None,
                             false => Some(view! {
                                 <ModeratePostButton post_id/>
+                                <ReportPostButton post_id/>
                             })
                         }
                     }
                     </SuspenseUnpack>
+                    <CrosspostButton post_id/>
                     <DeletePostButton post_id author_id/>
                 })}
                 <ModerationInfoButton content=Content::Post(stored_post.get_value())/>
@@ -278,6 +374,133 @@ pub fn EditPostButton(
     }
 }
 
+/// Component to add images to the gallery of a post
+#[component]
+pub fn AddPostImagesButton(
+    post_id: i64,
+    author_id: i64,
+) -> impl IntoView {
+    let state = expect_context::<GlobalState>();
+    let show_dialog = RwSignal::new(false);
+    let show_button = move || match &(*state.user.read()) {
+        Some(Ok(Some(user))) => user.user_id == author_id,
+        _ => false,
+    };
+    let button_class = move || match show_dialog.get() {
+        true => "button-rounded-primary",
+        false => "button-rounded-neutral",
+    };
+    let add_images_action = Action::new_local(|data: &FormData| {
+        add_post_images(data.clone().into())
+    });
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        if cfg!(feature = "hydrate") {
+            let target = ev.target().unwrap().unchecked_into::<HtmlFormElement>();
+            let form_data = FormData::new_with_form(&target).unwrap();
+            add_images_action.dispatch_local(form_data);
+        }
+    };
+
+    Effect::new(move || {
+        if let Some(Ok(_)) = &*add_images_action.value().read() {
+            show_dialog.set(false);
+            state.post_reload_signal.update(|value| *value += 1);
+        }
+    });
+
+    view! {
+        <Show when=show_button>
+            <div>
+                <button
+                    class=button_class
+                    aria-label=move_tr!("add-images")
+                    aria-expanded=move || show_dialog.get().to_string()
+                    aria-haspopup="dialog"
+                    on:click=move |_| show_dialog.update(|show: &mut bool| *show = !*show)
+                >
+                    <ImageIcon class="content-toolbar-icon-size"/>
+                </button>
+                <ModalDialog
+                    class="w-full flex justify-center"
+                    show_dialog
+                >
+                    <div class="bg-base-100 shadow-xl p-3 rounded-xs flex flex-col gap-3 w-full xl:w-3/5 4xl:w-2/5">
+                        <div class="text-center font-bold text-2xl">{move_tr!("add-images")}</div>
+                        <form on:submit=on_submit class="flex flex-col gap-3 w-full">
+                            <input type="text" name=POST_ID_PARAM class="hidden" value=post_id/>
+                            <input
+                                type="file"
+                                name=IMAGE_FILE_PARAM
+                                accept="image/*"
+                                multiple
+                                class="file-input file-input-primary !outline-offset-0 w-full"
+                            />
+                            <ModalFormButtons
+                                disable_publish=add_images_action.pending()
+                                show_form=show_dialog
+                            />
+                        </form>
+                        <ActionError action=add_images_action/>
+                    </div>
+                </ModalDialog>
+            </div>
+        </Show>
+    }
+}
+
+/// Component to crosspost a post in another sphere
+#[component]
+pub fn CrosspostButton(
+    post_id: i64,
+) -> impl IntoView {
+    let crosspost_action = ServerAction::<Crosspost>::new();
+    let show_dialog = RwSignal::new(false);
+    let sphere_name_input = RwSignal::new(String::default());
+    let disable_publish = Signal::derive(move || check_sphere_name(&sphere_name_input.read()).is_err());
+    let button_class = Signal::derive(move || match show_dialog.get() {
+        true => "button-rounded-primary",
+        false => "button-rounded-neutral",
+    });
+    view! {
+        <div>
+            <LoginGuardedButton
+                button_class
+                button_action=move |_| show_dialog.update(|show: &mut bool| *show = !*show)
+            >
+                <RefreshIcon class="content-toolbar-icon-size"/>
+            </LoginGuardedButton>
+            <ModalDialog
+                class="w-full flex justify-center"
+                show_dialog
+            >
+                <div class="bg-base-100 shadow-xl p-3 rounded-xs flex flex-col gap-3 w-full xl:w-3/5 4xl:w-2/5">
+                    <div class="text-center font-bold text-2xl">{move_tr!("crosspost")}</div>
+                    <ActionForm action=crosspost_action>
+                        <div class="flex flex-col gap-3 w-full">
+                            <input type="text" name="post_id" class="hidden" value=post_id/>
+                            <input
+                                type="text"
+                                name="sphere_name"
+                                placeholder=move_tr!("sphere")
+                                autocomplete="off"
+                                class="input_primary w-full"
+                                on:input=move |ev| sphere_name_input.set(event_target_value(&ev).to_lowercase())
+                                prop:value=sphere_name_input
+                            />
+                            <ModalFormButtons
+                                disable_publish
+                                show_form=show_dialog
+                            />
+                        </div>
+                    </ActionForm>
+                    <ActionError action=crosspost_action.into()/>
+                </div>
+            </ModalDialog>
+        </div>
+    }
+}
+
 /// Component to delete a post
 #[component]
 pub fn DeletePostButton(
@@ -320,6 +543,33 @@ pub fn CreatePost() -> impl IntoView {
     let link_input = RwSignal::new(String::default());
     let embed_type_input = RwSignal::new(EmbedType::None);
 
+    // Restore the draft of the user, then save it automatically while the post is being written, until it is published
+    let save_draft_action = ServerAction::<SaveDraft>::new();
+    let is_draft_loaded = RwSignal::new(false);
+    let _draft_resource = LocalResource::new(move || async move {
+        if let Ok(Some(draft)) = load_draft().await &&
+            title_input.read_untracked().is_empty() &&
+            body_data.content.read_untracked().is_empty()
+        {
+            title_input.set(draft.title);
+            body_data.content.set(draft.body);
+            link_input.set(draft.link.unwrap_or_default());
+        }
+        is_draft_loaded.set(true);
+    });
+    let draft_debounced: Signal<(String, String, String)> = signal_debounced(
+        Signal::derive(move || (title_input.get(), body_data.content.get(), link_input.get())),
+        DRAFT_AUTOSAVE_DELAY,
+    );
+    Effect::new(move || {
+        let (title, body, link) = draft_debounced.get();
+        let is_submitted = create_post_action.pending().get_untracked() ||
+            create_post_action.value().read_untracked().as_ref().is_some_and(|result| result.is_ok());
+        if is_draft_loaded.get_untracked() && !is_submitted && !(title.is_empty() && body.is_empty() && link.is_empty()) {
+            save_draft_action.dispatch(SaveDraft { title, body, link: (!link.is_empty()).then_some(link) });
+        }
+    });
+
     let matching_spheres_resource = Resource::new(
         move || sphere_name_debounced.get(),
         move |sphere_prefix| async move {
@@ -334,12 +584,24 @@ pub fn CreatePost() -> impl IntoView {
         move || sphere_name_debounced.get(),
         move |sphere_name| async move {
             match check_sphere_name(&sphere_name) {
-                Ok(()) => get_sphere_category_vec(sphere_name).await,
+                Ok(()) => get_sphere_category_vec(sphere_name, true).await,
                 Err(_) => Ok(Vec::new())
             }
         }
     );
 
+    // Warn the user if the link was already posted recently in the sphere, spheres that reject duplicates refuse the post
+    let link_debounced: Signal<String> = signal_debounced(link_input, 250.0);
+    let duplicate_link_resource = Resource::new(
+        move || (sphere_name_debounced.get(), link_debounced.get(), embed_type_input.get()),
+        move |(sphere_name, link, embed_type)| async move {
+            match embed_type != EmbedType::None && Url::parse(&link).is_ok() && check_sphere_name(&sphere_name).is_ok() {
+                true => Ok(get_duplicate_link_post_id(sphere_name, link).await.unwrap_or(None)),
+                false => Ok(None),
+            }
+        }
+    );
+
     // TODO: make sphere input into a component with a callback argument when clicking?
 
     view! {
@@ -347,6 +609,13 @@ pub fn CreatePost() -> impl IntoView {
             <ActionForm action=create_post_action>
                 <div class="flex flex-col gap-2 w-full">
                     <h2 class="py-4 text-4xl text-center">{move_tr!("share-post")}</h2>
+                    <a
+                        href=move || format!("{CREATE_POLL_ROUTE}?{CREATE_POST_SPHERE_QUERY_PARAM}={}", sphere_name_input.read())
+                        class="link text-primary self-end text-sm"
+                    >
+                        {move_tr!("create-poll")}
+                    </a>
+                    <IdempotencyKeyInput name="idempotency_key"/>
                     <div
                         class="dropdown dropdown-end input_outline_primary"
                         class=("input_outline_error", move || !is_sphere_selected.get())
@@ -413,6 +682,36 @@ pub fn CreatePost() -> impl IntoView {
                         is_parent_nsfw=is_sphere_nsfw
                         category_vec_resource
                     />
+                    <TransitionUnpack resource=duplicate_link_resource fallback=|| () let:duplicate_post_id>
+                    {
+                        (*duplicate_post_id).map(|post_id| view! {
+                            <a
+                                href=get_post_path(&sphere_name_input.get_untracked(), None, post_id)
+                                class="alert alert-warning link text-sm"
+                            >
+                                {move_tr!("duplicate-link-message")}
+                            </a>
+                        })
+                    }
+                    </TransitionUnpack>
+                    <div class="flex justify-between items-center">
+                        {move_tr!("publish-delay")}
+                        <select name="publish_delay_hours" class="select_input w-fit">
+                        {
+                            POST_SCHEDULE_HOURS_OPTIONS.into_iter().map(|delay_hours| view! {
+                                <option value=delay_hours selected=delay_hours == 0>
+                                {
+                                    match delay_hours {
+                                        0 => move_tr!("publish-now"),
+                                        delay_hours if delay_hours < 24 => move_tr!("time-hours", {"count" => delay_hours}),
+                                        delay_hours => move_tr!("time-days", {"count" => delay_hours / 24}),
+                                    }
+                                }
+                                </option>
+                            }).collect_view()
+                        }
+                        </select>
+                    </div>
                     <button type="submit" class="button-secondary" disabled=move || {
                         !is_sphere_selected.get() ||
                         title_input.read().is_empty() ||
@@ -467,7 +766,7 @@ pub fn EditPostForm(
         post.title.clone(),
         match &post.markdown_body {
             Some(body) => body.clone(),
-            None => post.body.clone(),
+            None => unescape_plain_text(&post.body),
         },
         post.link.link_type,
         post.link.link_url.clone(),
@@ -1,5 +1,11 @@
+use std::future::pending;
+
+use leptos::either::Either;
+use leptos::ev::SubmitEvent;
 use leptos::html;
 use leptos::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+use leptos::web_sys::{FormData, HtmlFormElement};
 use leptos_fluent::move_tr;
 use leptos_router::hooks::use_params_map;
 use leptos_use::{signal_throttled_with_options, ThrottleOptions};
@@ -7,23 +13,29 @@ use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
 
-use sphare_core_common::constants::{POST_BATCH_SIZE, SCROLL_LOAD_THROTTLE_DELAY};
+use sphare_core_common::constants::{MAX_BIO_LENGTH, MAX_USERNAME_LENGTH, POST_BATCH_SIZE, SCROLL_LOAD_THROTTLE_DELAY};
+use sphare_core_common::editor::TextareaData;
 use sphare_core_common::routes::get_username_memo;
+use sphare_core_common::traits::ToLocalizedStr;
 use sphare_core_common::unpack::{handle_additional_load, handle_initial_load, reset_additional_load};
 use sphare_core_content::ranking::{CommentSortType, PostSortType, SortType};
+use sphare_core_user::user::NsfwMode;
 
-use sphare_iface_content::profile::{get_user_comment_vec, get_user_post_vec};
+use sphare_iface_content::profile::{get_user_comment_vec, get_user_post_vec, set_user_avatar};
+use sphare_iface_content::search::{get_saved_search_vec, DeleteSavedSearch};
 use sphare_iface_user::auth::NavigateToUserAccount;
+use sphare_iface_user::user::{get_user_profile_header, ChangeUsername, SetPublicProfileVisible, SetUserBio};
 
 use sphare_cmp_base::comment::CommentMiniatureList;
 use sphare_cmp_base::post::PostListWithInitLoad;
 use sphare_cmp_base::ranking::{CommentSortWidget, PostSortWidget};
 use sphare_cmp_common::state::GlobalState;
+use sphare_cmp_utils::editor::FormMarkdownEditor;
 use sphare_cmp_utils::form::LabeledFormCheckbox;
-use sphare_cmp_utils::icons::{LoadingIcon, UserIcon, UserSettingsIcon};
-use sphare_cmp_utils::unpack::ActionError;
+use sphare_cmp_utils::icons::{CrossIcon, LoadingIcon, ScoreIcon, UserIcon, UserSettingsIcon};
+use sphare_cmp_utils::unpack::{ActionError, TransitionUnpack};
 use sphare_cmp_utils::view::ToView;
-use sphare_cmp_utils::widget::{EnumQueryTabs, ModalDialog, ModalFormButtons};
+use sphare_cmp_utils::widget::{AccountAgeWidget, ContentBody, EnumQueryTabs, ModalDialog, ModalFormButtons, IMAGE_FILE_PARAM};
 
 pub const PROFILE_TAB_QUERY_PARAM: &str = "tab";
 
@@ -86,6 +98,10 @@ pub fn UserProfile() -> impl IntoView {
     let state = expect_context::<GlobalState>();
     let params = use_params_map();
     let query_username = get_username_memo(params);
+    let profile_header_resource = Resource::new(
+        move || query_username.get(),
+        get_user_profile_header,
+    );
     view! {
         <div class="w-full flex justify-center">
             <div class="w-full 2xl:w-4/5 4xl:w-2/3 flex flex-col max-lg:items-center">
@@ -93,6 +109,26 @@ pub fn UserProfile() -> impl IntoView {
                     <UserIcon/>
                     {move || query_username.get()}
                 </div>
+                <Transition>
+                {
+                    move || Suspend::new(async move {
+                        profile_header_resource.await.ok().map(|profile_header| view! {
+                            <div class="px-2 pb-2 flex flex-col gap-1">
+                                <AccountAgeWidget timestamp=profile_header.timestamp/>
+                                <div class="flex gap-1.5 items-center text-sm px-1">
+                                    <ScoreIcon/>
+                                    {move_tr!("post-karma", {"count" => profile_header.karma.post_karma})}
+                                    " · "
+                                    {move_tr!("comment-karma", {"count" => profile_header.karma.comment_karma})}
+                                </div>
+                                {
+                                    profile_header.bio.map(|bio| view! { <ContentBody body=bio is_markdown=true/> })
+                                }
+                            </div>
+                        })
+                    })
+                }
+                </Transition>
                 <Transition fallback=move || view! {  <LoadingIcon/> }>
                 {
                     move || Suspend::new(async move {
@@ -130,7 +166,7 @@ pub fn UserPosts() -> impl IntoView {
     let load_error = RwSignal::new(None);
     let list_ref = NodeRef::<html::Ul>::new();
 
-    let post_vec_resource = Resource::new(
+    let profile_post_resource = Resource::new(
         move || (username.get(), sort_signal.get()),
         move |(username, sort_type)| async move {
             #[cfg(feature = "hydrate")]
@@ -142,6 +178,16 @@ pub fn UserPosts() -> impl IntoView {
             result
         }
     );
+    // the post list needs the posts alone, wait until the profile content is loaded to extract them
+    let post_vec_resource = Resource::new(
+        move || profile_post_resource.get(),
+        move |profile_content| async move {
+            match profile_content {
+                Some(profile_content) => profile_content.map(|profile_content| profile_content.content_vec),
+                None => pending().await,
+            }
+        }
+    );
 
     let additional_load_count_throttled: Signal<i32> = signal_throttled_with_options(
         additional_load_count,
@@ -154,7 +200,11 @@ pub fn UserPosts() -> impl IntoView {
             if additional_load_count_throttled.get() > 0 {
                 is_loading.set(true);
                 let num_post = (POST_BATCH_SIZE as usize) + additional_post_vec.read_untracked().len();
-                let additional_load = get_user_post_vec(username.get_untracked(), sort_signal.get_untracked(), num_post).await;
+                let additional_load = get_user_post_vec(
+                    username.get_untracked(),
+                    sort_signal.get_untracked(),
+                    num_post
+                ).await.map(|profile_content| profile_content.content_vec);
                 handle_additional_load(additional_load, additional_post_vec, load_error);
                 is_loading.set(false);
             }
@@ -163,14 +213,25 @@ pub fn UserPosts() -> impl IntoView {
 
     view! {
         <PostSortWidget sort_signal/>
-        <PostListWithInitLoad
-            post_vec_resource
-            additional_post_vec
-            is_loading
-            load_error
-            additional_load_count
-            list_ref
-        />
+        <Transition fallback=move || view! {  <LoadingIcon/> }>
+        {
+            move || Suspend::new(async move {
+                match profile_post_resource.await {
+                    Ok(profile_content) if profile_content.is_hidden => view! { <HiddenProfileMessage/> }.into_any(),
+                    _ => view! {
+                        <PostListWithInitLoad
+                            post_vec_resource
+                            additional_post_vec
+                            is_loading
+                            load_error
+                            additional_load_count
+                            list_ref
+                        />
+                    }.into_any(),
+                }
+            })
+        }
+        </Transition>
     }
 }
 
@@ -184,12 +245,20 @@ pub fn UserComments() -> impl IntoView {
     let additional_load_count = RwSignal::new(0);
     let is_loading = RwSignal::new(false);
     let load_error = RwSignal::new(None);
+    let is_hidden = RwSignal::new(false);
     let list_ref = NodeRef::<html::Ul>::new();
 
     let _initial_comment_resource = LocalResource::new(
         move || async move {
             is_loading.set(true);
-            let initial_load = get_user_comment_vec(username.get(), sort_signal.get(), 0).await;
+            let initial_load = get_user_comment_vec(
+                username.get(),
+                sort_signal.get(),
+                0
+            ).await.map(|profile_content| {
+                is_hidden.set(profile_content.is_hidden);
+                profile_content.content_vec
+            });
             handle_initial_load(initial_load, comment_vec, load_error, Some(list_ref));
             is_loading.set(false);
         }
@@ -209,7 +278,7 @@ pub fn UserComments() -> impl IntoView {
                     username.get_untracked(),
                     sort_signal.get_untracked(),
                     comment_vec.read_untracked().len(),
-                ).await;
+                ).await.map(|profile_content| profile_content.content_vec);
                 handle_additional_load(additional_load, comment_vec, load_error);
                 is_loading.set(false);
             }
@@ -218,13 +287,26 @@ pub fn UserComments() -> impl IntoView {
 
     view! {
         <CommentSortWidget sort_signal/>
-        <CommentMiniatureList
-            comment_vec
-            is_loading
-            load_error
-            additional_load_count
-            list_ref
-        />
+        <Show
+            when=move || !is_hidden.get()
+            fallback=move || view! { <HiddenProfileMessage/> }
+        >
+            <CommentMiniatureList
+                comment_vec
+                is_loading
+                load_error
+                additional_load_count
+                list_ref
+            />
+        </Show>
+    }
+}
+
+/// Displays a message indicating the user hid their posts and comments
+#[component]
+pub fn HiddenProfileMessage() -> impl IntoView {
+    view! {
+        <div class="p-4 text-center text-base-content/70">{move_tr!("profile-hidden")}</div>
     }
 }
 
@@ -232,20 +314,41 @@ pub fn UserComments() -> impl IntoView {
 #[component]
 pub fn UserSettings() -> impl IntoView {
     let state = expect_context::<GlobalState>();
+    let set_profile_visible_action = ServerAction::<SetPublicProfileVisible>::new();
+    let change_username_action = ServerAction::<ChangeUsername>::new();
+    let set_bio_action = ServerAction::<SetUserBio>::new();
 
     view! {
         <div class="self-center flex flex-col gap-3 w-4/5 lg:w-full xl:w-4/5 4xl:3/5">
             <Suspense fallback=move || view! {  <LoadingIcon/> }>
             {
                 move || Suspend::new(async move {
-                    let (is_nsfw, show_nsfw, days_hide_spoiler) = match state.user.await {
-                        Ok(Some(user)) => (user.is_nsfw, user.show_nsfw, user.days_hide_spoiler.unwrap_or_default()),
-                        _ => (false, false, 0),
+                    let (username, is_nsfw, nsfw_mode, days_hide_spoiler, public_profile_visible, default_post_sort, markdown_bio) = match state.user.await {
+                        Ok(Some(user)) => (user.username, user.is_nsfw, user.nsfw_mode, user.days_hide_spoiler.unwrap_or_default(), user.public_profile_visible, user.default_post_sort, user.markdown_bio.unwrap_or_default()),
+                        _ => (String::default(), false, NsfwMode::default(), 0, true, PostSortType::default(), String::default()),
+                    };
+                    let bio_data = TextareaData {
+                        content: RwSignal::new(markdown_bio),
+                        textarea_ref: NodeRef::<html::Textarea>::new(),
                     };
                     view! {
                         <ActionForm action=state.set_settings_action attr:class="flex flex-col gap-3">
                             <LabeledFormCheckbox name="is_nsfw" label=move_tr!("nsfw-profile") value=is_nsfw/>
-                            <LabeledFormCheckbox name="show_nsfw" label=move_tr!("show-nsfw") value=show_nsfw/>
+                            <div class="flex justify-between items-center">
+                                {move_tr!("nsfw-mode")}
+                                <select name="nsfw_mode" class="select_input w-fit">
+                                {
+                                    NsfwMode::iter().map(|mode| {
+                                        let value: &'static str = mode.into();
+                                        view! {
+                                            <option value=value selected=mode == nsfw_mode>
+                                                {mode.to_localized_str()}
+                                            </option>
+                                        }
+                                    }).collect_view()
+                                }
+                                </select>
+                            </div>
                             <div class="flex justify-between items-center">
                                 {move_tr!("hide-spoiler-duration")}
                                 <input
@@ -263,10 +366,72 @@ pub fn UserSettings() -> impl IntoView {
                             </button>
                         </ActionForm>
                         <ActionError action=state.set_settings_action.into()/>
+                        <ActionForm action=set_profile_visible_action attr:class="flex flex-col gap-3">
+                            <LabeledFormCheckbox name="is_visible" label=move_tr!("public-profile-visible") value=public_profile_visible/>
+                            <button type="submit" class="button-secondary">
+                                {move_tr!("save")}
+                            </button>
+                        </ActionForm>
+                        <ActionError action=set_profile_visible_action.into()/>
+                        <ActionForm action=state.set_default_post_sort_action attr:class="flex flex-col gap-3">
+                            <div class="flex justify-between items-center">
+                                {move_tr!("default-post-sort")}
+                                <select name="post_sort_type" class="select_input w-fit">
+                                {
+                                    PostSortType::iter().map(|post_sort_type| {
+                                        let value: &'static str = post_sort_type.into();
+                                        view! {
+                                            <option value=value selected=post_sort_type == default_post_sort>
+                                                {post_sort_type.to_localized_str()}
+                                            </option>
+                                        }
+                                    }).collect_view()
+                                }
+                                </select>
+                            </div>
+                            <button type="submit" class="button-secondary">
+                                {move_tr!("save")}
+                            </button>
+                        </ActionForm>
+                        <ActionError action=state.set_default_post_sort_action.into()/>
+                        <ActionForm action=change_username_action attr:class="flex flex-col gap-3">
+                            <div class="flex justify-between items-center gap-2">
+                                {move_tr!("change-username")}
+                                <input
+                                    type="text"
+                                    name="new_username"
+                                    class="input input-primary w-1/2"
+                                    autocomplete="off"
+                                    maxlength=MAX_USERNAME_LENGTH
+                                    placeholder=username
+                                />
+                            </div>
+                            <button type="submit" class="button-secondary">
+                                {move_tr!("save")}
+                            </button>
+                        </ActionForm>
+                        <ActionError action=change_username_action.into()/>
+                        <ActionForm action=set_bio_action attr:class="flex flex-col gap-3">
+                            <FormMarkdownEditor
+                                name="bio"
+                                is_markdown_name="is_markdown"
+                                placeholder=move_tr!("bio")
+                                data=bio_data
+                                is_markdown=true
+                                maxlength=Some(MAX_BIO_LENGTH)
+                                is_empty_ok=true
+                            />
+                            <button type="submit" class="button-secondary">
+                                {move_tr!("save")}
+                            </button>
+                        </ActionForm>
+                        <ActionError action=set_bio_action.into()/>
                     }
                 })
             }
             </Suspense>
+            <SavedSearchList/>
+            <UserAvatarForm/>
             <div class="flex justify-between items-center">
                 <UserAccountButton/>
                 <DeleteUserButton/>
@@ -275,6 +440,100 @@ pub fn UserSettings() -> impl IntoView {
     }
 }
 
+/// Displays the user's saved searches with a button to delete each of them
+#[component]
+pub fn SavedSearchList() -> impl IntoView {
+    let delete_saved_search_action = ServerAction::<DeleteSavedSearch>::new();
+    let saved_search_vec_resource = Resource::new(
+        move || delete_saved_search_action.version().get(),
+        move |_| get_saved_search_vec(),
+    );
+
+    view! {
+        <div class="flex flex-col gap-1">
+            {move_tr!("saved-searches")}
+            <TransitionUnpack resource=saved_search_vec_resource let:saved_search_vec>
+            { match saved_search_vec.is_empty() {
+                true => Either::Left(view! {
+                    <div class="text-sm text-base-content/70">{move_tr!("no-saved-search")}</div>
+                }),
+                false => Either::Right(view! {
+                    <ul class="flex flex-col divide-y divide-base-content/20">
+                    {
+                        saved_search_vec.iter().map(|saved_search| {
+                            let saved_search_id = saved_search.saved_search_id;
+                            view! {
+                                <li class="flex justify-between items-center gap-2 py-1">
+                                    <div class="flex flex-col">
+                                        <span>{saved_search.search_query.clone()}</span>
+                                        { saved_search.sphere_name.clone().map(|sphere_name| view! {
+                                            <span class="text-sm text-base-content/70">{sphere_name}</span>
+                                        })}
+                                    </div>
+                                    <ActionForm action=delete_saved_search_action attr:class="h-fit flex justify-center">
+                                        <input
+                                            name="saved_search_id"
+                                            class="hidden"
+                                            value=saved_search_id
+                                        />
+                                        <button class="button-error">
+                                            <CrossIcon/>
+                                        </button>
+                                    </ActionForm>
+                                </li>
+                            }
+                        }).collect_view()
+                    }
+                    </ul>
+                }),
+            }}
+            </TransitionUnpack>
+            <ActionError action=delete_saved_search_action.into()/>
+        </div>
+    }
+}
+
+/// Form to upload or clear the user's avatar, submitting an empty file clears it
+#[component]
+pub fn UserAvatarForm() -> impl IntoView {
+    let state = expect_context::<GlobalState>();
+    let set_avatar_action = Action::new_local(|data: &FormData| {
+        set_user_avatar(data.clone().into())
+    });
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        if cfg!(feature = "hydrate") {
+            let target = ev.target().unwrap().unchecked_into::<HtmlFormElement>();
+            let form_data = FormData::new_with_form(&target).unwrap();
+            set_avatar_action.dispatch_local(form_data);
+        }
+    };
+
+    Effect::new(move || {
+        if let Some(Ok(())) = *set_avatar_action.value().read() {
+            state.user.refetch();
+        }
+    });
+
+    view! {
+        <form on:submit=on_submit class="flex flex-col gap-3">
+            <div class="flex justify-between items-center gap-2">
+                {move_tr!("avatar")}
+                <input
+                    type="file"
+                    name=IMAGE_FILE_PARAM
+                    accept="image/png,image/jpeg"
+                    class="file-input file-input-primary !outline-offset-0 w-1/2"
+                />
+            </div>
+            <button type="submit" class="button-secondary" disabled=set_avatar_action.pending()>
+                {move_tr!("save")}
+            </button>
+        </form>
+        <ActionError action=set_avatar_action/>
+    }
+}
+
 /// Button to delete one's account
 #[component]
 pub fn DeleteUserButton() -> impl IntoView {
@@ -1,11 +1,15 @@
 use leptos::prelude::*;
+use leptos_fluent::move_tr;
 
-use sphare_core_content::ranking::{update_vote_value, Vote, VoteValue};
+use sphare_core_common::constants::ALLOWED_REACTIONS;
+use sphare_core_common::errors::AppError;
+use sphare_core_content::ranking::{update_vote_value, ReactionCount, Vote, VoteValue};
 
-use sphare_iface_content::ranking::VoteOnContent;
+use sphare_iface_content::ranking::{AddReaction, RemoveReaction, VoteOnContent};
 
 use sphare_cmp_common::auth_widget::LoginGuardedButton;
 use sphare_cmp_utils::icons::{MinusIcon, PlusIcon};
+use sphare_cmp_utils::unpack::ActionError;
 
 /// Dynamic score indicator, that can be updated through the given signal
 #[component]
@@ -22,71 +26,122 @@ pub fn DynScoreIndicator(
     }.into_any()
 }
 
+/// Local state of a user's vote on a content, updated optimistically and reconciled with the server's responses
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OptimisticVote {
+    /// vote value displayed to the user
+    pub value: VoteValue,
+    /// last vote value confirmed by the server
+    pub confirmed_value: VoteValue,
+    /// id of the last vote confirmed by the server
+    pub confirmed_vote_id: Option<i64>,
+    /// whether a vote request is waiting for the server's response
+    pub is_pending: bool,
+}
+
+/// Vote request to send to the server
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VoteRequest {
+    pub vote_value: VoteValue,
+    pub vote_id: Option<i64>,
+}
+
+impl OptimisticVote {
+    pub fn new(vote: Option<&Vote>) -> Self {
+        let value = vote.map(|vote| vote.value).unwrap_or(VoteValue::None);
+        OptimisticVote {
+            value,
+            confirmed_value: value,
+            confirmed_vote_id: vote.map(|vote| vote.vote_id),
+            is_pending: false,
+        }
+    }
+
+    /// Optimistically applies a click on a vote button. Returns the request to send to the server, unless one is
+    /// already pending, in which case the latest value is sent once the pending request is answered.
+    pub fn on_click(&mut self, is_upvote: bool) -> Option<VoteRequest> {
+        update_vote_value(&mut self.value, is_upvote);
+        match self.is_pending {
+            true => None,
+            false => self.get_request(),
+        }
+    }
+
+    /// Reconciles the state with the server's response to the pending request. On error, the displayed value is rolled back
+    /// to the last confirmed one. Returns a follow-up request if the displayed value changed while the request was pending.
+    pub fn on_response(&mut self, result: &Result<Option<Vote>, AppError>) -> Option<VoteRequest> {
+        self.is_pending = false;
+        match result {
+            Ok(vote) => {
+                self.confirmed_value = vote.as_ref().map(|vote| vote.value).unwrap_or(VoteValue::None);
+                self.confirmed_vote_id = vote.as_ref().map(|vote| vote.vote_id);
+                self.get_request()
+            },
+            Err(_) => {
+                self.value = self.confirmed_value;
+                None
+            },
+        }
+    }
+
+    fn get_request(&mut self) -> Option<VoteRequest> {
+        if self.value == self.confirmed_value {
+            return None;
+        }
+        self.is_pending = true;
+        Some(VoteRequest {
+            vote_value: self.value,
+            vote_id: self.confirmed_vote_id,
+        })
+    }
+}
+
 /// Component to display and modify a content's score
 #[component]
 pub fn VotePanel(
     post_id: i64,
     comment_id: Option<i64>,
     score: i32,
+    score_minus: i32,
     vote: Option<Vote>,
 ) -> impl IntoView {
-    let (vote_id, vote_value, initial_score) = match vote {
-        Some(vote) => (
-            Some(vote.vote_id),
-            Some(vote.value),
-            score - (vote.value as i32),
-        ),
-        None => (None, None, score),
-    };
+    let vote_value = vote.as_ref().map(|vote| vote.value).unwrap_or(VoteValue::None);
+    // score and downvotes without the user's vote
+    let initial_score = score - (vote_value as i32);
+    let initial_score_minus = score_minus - i32::from(vote_value == VoteValue::Down);
 
-    let score = RwSignal::new(score);
-    let vote = RwSignal::new(vote_value.unwrap_or(VoteValue::None));
+    let vote_state = RwSignal::new(OptimisticVote::new(vote.as_ref()));
+    let vote = Signal::derive(move || vote_state.read().value);
+    let score = Signal::derive(move || initial_score + (vote.get() as i32));
+    let downvotes = Signal::derive(move || initial_score_minus + i32::from(vote.get() == VoteValue::Down));
+    let upvotes = Signal::derive(move || score.get() + downvotes.get());
 
     let vote_action = ServerAction::<VoteOnContent>::new();
 
-    let vote_id = Memo::new(move |current_vote_id| {
-        match &(*vote_action.value().read()) {
-            Some(Ok(Some(vote))) => Some(vote.vote_id),
-            Some(Ok(None)) => None,
-            Some(Err(_)) if current_vote_id.is_some() => *current_vote_id.unwrap(),
-            _ => vote_id,
-        }
+    // reconcile the local vote with the server's response and send the latest value if it changed in the meantime
+    Effect::new(move || if let Some(result) = vote_action.value().get() &&
+        let Some(vote_request) = vote_state.write().on_response(&result)
+    {
+        dispatch_vote_request(vote_request, post_id, comment_id, vote_action);
     });
 
     view! {
         <div class="flex items-center gap-1">
             <LoginGuardedButton
                 button_class=get_vote_button_css(vote, true)
-                button_action=move |_| {
-                    on_content_vote(
-                        vote,
-                        vote_id,
-                        score,
-                        post_id,
-                        comment_id,
-                        initial_score,
-                        vote_action,
-                        true
-                    );
-                }
+                button_action=move |_| on_content_vote(vote_state, post_id, comment_id, vote_action, true)
             >
                 <PlusIcon/>
             </LoginGuardedButton>
-            <DynScoreIndicator score=score/>
+            <div
+                class="tooltip"
+                data-tip=move_tr!("vote-breakdown", {"upvotes" => upvotes.get(), "downvotes" => downvotes.get()})
+            >
+                <DynScoreIndicator score=score/>
+            </div>
             <LoginGuardedButton
                 button_class=get_vote_button_css(vote, false)
-                button_action=move |_| {
-                    on_content_vote(
-                        vote,
-                        vote_id,
-                        score,
-                        post_id,
-                        comment_id,
-                        initial_score,
-                        vote_action,
-                        false
-                    );
-                }
+                button_action=move |_| on_content_vote(vote_state, post_id, comment_id, vote_action, false)
             >
                 <MinusIcon/>
             </LoginGuardedButton>
@@ -96,30 +151,38 @@ pub fn VotePanel(
 
 // Function to react to a post's upvote or downvote button being clicked.
 pub fn on_content_vote(
-    vote: RwSignal<VoteValue>,
-    vote_id: Memo<Option<i64>>,
-    score: RwSignal<i32>,
+    vote_state: RwSignal<OptimisticVote>,
     post_id: i64,
     comment_id: Option<i64>,
-    initial_score: i32,
     vote_action: ServerAction<VoteOnContent>,
     is_upvote: bool,
 ) {
-    update_vote_value(&mut vote.write(), is_upvote);
+    let vote_request = vote_state.write().on_click(is_upvote);
+
+    log::trace!("Content vote state {:?}", vote_state.get_untracked());
 
-    log::trace!("Content vote value {:?}", vote.get_untracked());
+    if let Some(vote_request) = vote_request {
+        dispatch_vote_request(vote_request, post_id, comment_id, vote_action);
+    }
+}
 
+// Function to send a vote request to the server
+fn dispatch_vote_request(
+    vote_request: VoteRequest,
+    post_id: i64,
+    comment_id: Option<i64>,
+    vote_action: ServerAction<VoteOnContent>,
+) {
     vote_action.dispatch(VoteOnContent {
-        vote_value: vote.get_untracked(),
+        vote_value: vote_request.vote_value,
         post_id,
         comment_id,
-        vote_id: vote_id.get_untracked(),
+        vote_id: vote_request.vote_id,
     });
-    score.set(initial_score + (vote.get_untracked() as i32));
 }
 
 // Function to obtain the css classes of a vote button
-pub fn get_vote_button_css(vote: RwSignal<VoteValue>, is_upvote: bool) -> Signal<&'static str> {
+pub fn get_vote_button_css(vote: Signal<VoteValue>, is_upvote: bool) -> Signal<&'static str> {
     let activated_value = match is_upvote {
         true => VoteValue::Up,
         false => VoteValue::Down,
@@ -133,18 +196,146 @@ pub fn get_vote_button_css(vote: RwSignal<VoteValue>, is_upvote: bool) -> Signal
     })
 }
 
+/// Reactions of a post or comment, clicking on a reaction toggles the user's reaction
+#[component]
+pub fn ReactionPanel(
+    post_id: i64,
+    comment_id: Option<i64>,
+    reactions: Vec<ReactionCount>,
+) -> impl IntoView {
+    let reaction_vec = RwSignal::new(reactions);
+    let show_picker = RwSignal::new(false);
+    let add_reaction_action = ServerAction::<AddReaction>::new();
+    let remove_reaction_action = ServerAction::<RemoveReaction>::new();
+    let on_reaction = move |reaction: String| {
+        let is_added = toggle_reaction(&mut reaction_vec.write(), post_id, comment_id, &reaction);
+        match is_added {
+            true => add_reaction_action.dispatch(AddReaction { post_id, comment_id, reaction }),
+            false => remove_reaction_action.dispatch(RemoveReaction { post_id, comment_id, reaction }),
+        };
+    };
+
+    view! {
+        <div class="flex flex-wrap items-center gap-1">
+            <For
+                each=move || reaction_vec.get()
+                key=|reaction_count| (reaction_count.reaction.clone(), reaction_count.count, reaction_count.has_reacted)
+                let:reaction_count
+            >
+                <LoginGuardedButton
+                    button_class=get_reaction_button_css(reaction_count.has_reacted)
+                    button_action={
+                        let reaction = reaction_count.reaction.clone();
+                        move |_| on_reaction(reaction.clone())
+                    }
+                >
+                    <span class="text-sm">{format!("{} {}", reaction_count.reaction, reaction_count.count)}</span>
+                </LoginGuardedButton>
+            </For>
+            <Show
+                when=show_picker
+                fallback=move || view! {
+                    <LoginGuardedButton
+                        button_class="px-1 rounded-sm hover:bg-base-200"
+                        button_action=move |_| show_picker.set(true)
+                    >
+                        <span class="text-sm">{move_tr!("add-reaction")}</span>
+                    </LoginGuardedButton>
+                }
+            >
+                {
+                    ALLOWED_REACTIONS.iter().map(|reaction| view! {
+                        <button
+                            type="button"
+                            class="px-1 rounded-sm hover:bg-base-200"
+                            on:click=move |_| {
+                                let has_reacted = reaction_vec.read_untracked().iter().any(
+                                    |reaction_count| reaction_count.reaction == *reaction && reaction_count.has_reacted
+                                );
+                                if !has_reacted {
+                                    on_reaction(reaction.to_string());
+                                }
+                                show_picker.set(false);
+                            }
+                        >
+                            <span class="text-sm">{*reaction}</span>
+                        </button>
+                    }).collect_view()
+                }
+            </Show>
+            <ActionError action=add_reaction_action.into()/>
+            <ActionError action=remove_reaction_action.into()/>
+        </div>
+    }.into_any()
+}
+
+/// Toggles the user's `reaction` in `reaction_vec`, returns true if the reaction was added and false if it was removed
+pub fn toggle_reaction(
+    reaction_vec: &mut Vec<ReactionCount>,
+    post_id: i64,
+    comment_id: Option<i64>,
+    reaction: &str,
+) -> bool {
+    match reaction_vec.iter().position(|reaction_count| reaction_count.reaction == reaction) {
+        Some(index) => {
+            let reaction_count = &mut reaction_vec[index];
+            reaction_count.has_reacted = !reaction_count.has_reacted;
+            let is_added = reaction_count.has_reacted;
+            match is_added {
+                true => reaction_count.count += 1,
+                false => reaction_count.count -= 1,
+            };
+            if reaction_count.count <= 0 {
+                reaction_vec.remove(index);
+            }
+            is_added
+        },
+        None => {
+            reaction_vec.push(ReactionCount {
+                post_id,
+                comment_id,
+                reaction: reaction.to_string(),
+                count: 1,
+                has_reacted: true,
+            });
+            true
+        },
+    }
+}
+
+// Function to obtain the css classes of a reaction button
+fn get_reaction_button_css(has_reacted: bool) -> &'static str {
+    match has_reacted {
+        true => "px-1 rounded-sm bg-primary/40",
+        false => "px-1 rounded-sm bg-base-200 hover:bg-primary/40",
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::ranking::{get_vote_button_css, VoteValue};
+    use crate::ranking::{get_vote_button_css, toggle_reaction, OptimisticVote, VoteRequest, VoteValue};
     use leptos::prelude::*;
+    use sphare_core_common::errors::AppError;
+    use sphare_core_content::ranking::{ReactionCount, Vote};
+
+    fn create_vote(vote_id: i64, value: VoteValue) -> Vote {
+        Vote {
+            vote_id,
+            user_id: 1,
+            comment_id: None,
+            post_id: 1,
+            value,
+            timestamp: chrono::Utc::now(),
+        }
+    }
 
     #[test]
     fn test_get_vote_button_css() {
         let owner = Owner::new();
         owner.set();
         let vote_signal = RwSignal::new(VoteValue::None);
-        let upvote_css = get_vote_button_css(vote_signal, true);
-        let downvote_css = get_vote_button_css(vote_signal, false);
+        let upvote_css = get_vote_button_css(vote_signal.into(), true);
+        let downvote_css = get_vote_button_css(vote_signal.into(), false);
 
         assert_eq!(upvote_css(), String::from("p-1 rounded-full bg-success/20 shadow-md/30 hover:bg-success hover:shadow-none"));
         assert_eq!(downvote_css(), String::from("p-1 rounded-full bg-error/20 shadow-md/30 hover:bg-error hover:shadow-none"));
@@ -157,4 +348,139 @@ mod tests {
         assert_eq!(upvote_css(), String::from("p-1 rounded-full bg-success/20 shadow-md/30 hover:bg-success hover:shadow-none"));
         assert_eq!(downvote_css(), String::from("p-1 rounded-full bg-error"));
     }
+
+    #[test]
+    fn test_optimistic_vote_new() {
+        let vote = create_vote(1, VoteValue::Down);
+        assert_eq!(
+            OptimisticVote::new(Some(&vote)),
+            OptimisticVote { value: VoteValue::Down, confirmed_value: VoteValue::Down, confirmed_vote_id: Some(1), is_pending: false },
+        );
+        assert_eq!(
+            OptimisticVote::new(None),
+            OptimisticVote { value: VoteValue::None, confirmed_value: VoteValue::None, confirmed_vote_id: None, is_pending: false },
+        );
+    }
+
+    #[test]
+    fn test_optimistic_vote_reconcile() {
+        let mut vote_state = OptimisticVote::new(None);
+
+        assert_eq!(vote_state.on_click(true), Some(VoteRequest { vote_value: VoteValue::Up, vote_id: None }));
+        assert_eq!(vote_state.value, VoteValue::Up);
+        assert_eq!(vote_state.confirmed_value, VoteValue::None);
+        assert!(vote_state.is_pending);
+
+        let vote = create_vote(1, VoteValue::Up);
+        assert_eq!(vote_state.on_response(&Ok(Some(vote))), None);
+        assert_eq!(
+            vote_state,
+            OptimisticVote { value: VoteValue::Up, confirmed_value: VoteValue::Up, confirmed_vote_id: Some(1), is_pending: false },
+        );
+
+        assert_eq!(vote_state.on_click(false), Some(VoteRequest { vote_value: VoteValue::Down, vote_id: Some(1) }));
+        assert_eq!(vote_state.value, VoteValue::Down);
+        let vote = create_vote(1, VoteValue::Down);
+        assert_eq!(vote_state.on_response(&Ok(Some(vote))), None);
+        assert_eq!(
+            vote_state,
+            OptimisticVote { value: VoteValue::Down, confirmed_value: VoteValue::Down, confirmed_vote_id: Some(1), is_pending: false },
+        );
+
+        assert_eq!(vote_state.on_click(false), Some(VoteRequest { vote_value: VoteValue::None, vote_id: Some(1) }));
+        assert_eq!(vote_state.on_response(&Ok(None)), None);
+        assert_eq!(
+            vote_state,
+            OptimisticVote { value: VoteValue::None, confirmed_value: VoteValue::None, confirmed_vote_id: None, is_pending: false },
+        );
+    }
+
+    #[test]
+    fn test_optimistic_vote_rollback() {
+        let vote = create_vote(1, VoteValue::Up);
+        let mut vote_state = OptimisticVote::new(Some(&vote));
+
+        assert_eq!(vote_state.on_click(false), Some(VoteRequest { vote_value: VoteValue::Down, vote_id: Some(1) }));
+        assert_eq!(vote_state.value, VoteValue::Down);
+        assert_eq!(vote_state.on_response(&Err(AppError::InsufficientPrivileges)), None);
+        assert_eq!(
+            vote_state,
+            OptimisticVote { value: VoteValue::Up, confirmed_value: VoteValue::Up, confirmed_vote_id: Some(1), is_pending: false },
+        );
+    }
+
+    #[test]
+    fn test_optimistic_vote_double_click() {
+        let mut vote_state = OptimisticVote::new(None);
+
+        // clicks while a request is pending are only applied locally
+        assert_eq!(vote_state.on_click(true), Some(VoteRequest { vote_value: VoteValue::Up, vote_id: None }));
+        assert_eq!(vote_state.on_click(true), None);
+        assert_eq!(vote_state.value, VoteValue::None);
+        assert!(vote_state.is_pending);
+
+        // the server created the vote, a follow-up request deletes it to match the displayed value
+        let vote = create_vote(1, VoteValue::Up);
+        assert_eq!(vote_state.on_response(&Ok(Some(vote))), Some(VoteRequest { vote_value: VoteValue::None, vote_id: Some(1) }));
+        assert_eq!(vote_state.confirmed_value, VoteValue::Up);
+        assert!(vote_state.is_pending);
+
+        assert_eq!(vote_state.on_response(&Ok(None)), None);
+        assert_eq!(
+            vote_state,
+            OptimisticVote { value: VoteValue::None, confirmed_value: VoteValue::None, confirmed_vote_id: None, is_pending: false },
+        );
+
+        // a click changing the value back to the pending one doesn't trigger a follow-up request
+        assert_eq!(vote_state.on_click(false), Some(VoteRequest { vote_value: VoteValue::Down, vote_id: None }));
+        assert_eq!(vote_state.on_click(true), None);
+        assert_eq!(vote_state.on_click(false), None);
+        let vote = create_vote(2, VoteValue::Down);
+        assert_eq!(vote_state.on_response(&Ok(Some(vote))), None);
+        assert_eq!(
+            vote_state,
+            OptimisticVote { value: VoteValue::Down, confirmed_value: VoteValue::Down, confirmed_vote_id: Some(2), is_pending: false },
+        );
+
+        // the displayed value is rolled back to the confirmed one if the pending request fails
+        assert_eq!(vote_state.on_click(true), Some(VoteRequest { vote_value: VoteValue::Up, vote_id: Some(2) }));
+        assert_eq!(vote_state.on_click(true), None);
+        assert_eq!(vote_state.on_response(&Err(AppError::InsufficientPrivileges)), None);
+        assert_eq!(
+            vote_state,
+            OptimisticVote { value: VoteValue::Down, confirmed_value: VoteValue::Down, confirmed_vote_id: Some(2), is_pending: false },
+        );
+    }
+
+    #[test]
+    fn test_toggle_reaction() {
+        let mut reaction_vec = vec![ReactionCount {
+            post_id: 1,
+            comment_id: Some(2),
+            reaction: String::from("👍"),
+            count: 2,
+            has_reacted: false,
+        }];
+
+        assert!(toggle_reaction(&mut reaction_vec, 1, Some(2), "👍"));
+        assert_eq!(reaction_vec[0].count, 3);
+        assert!(reaction_vec[0].has_reacted);
+
+        assert!(toggle_reaction(&mut reaction_vec, 1, Some(2), "🎉"));
+        assert_eq!(reaction_vec.len(), 2);
+        assert_eq!(reaction_vec[1], ReactionCount {
+            post_id: 1,
+            comment_id: Some(2),
+            reaction: String::from("🎉"),
+            count: 1,
+            has_reacted: true,
+        });
+
+        assert!(!toggle_reaction(&mut reaction_vec, 1, Some(2), "👍"));
+        assert_eq!(reaction_vec[0].count, 2);
+        assert!(!reaction_vec[0].has_reacted);
+
+        assert!(!toggle_reaction(&mut reaction_vec, 1, Some(2), "🎉"));
+        assert_eq!(reaction_vec.len(), 1);
+    }
 }
@@ -3,4 +3,5 @@ pub mod rule;
 pub mod satellite;
 pub mod sphere;
 pub mod sphere_category;
-pub mod sphere_management;
\ No newline at end of file
+pub mod sphere_management;
+pub mod wiki;
\ No newline at end of file
@@ -5,7 +5,7 @@ use leptos_fluent::move_tr;
 
 use sphare_core_common::common::Rule;
 use sphare_core_common::constants::{MAX_MOD_MESSAGE_LENGTH, MAX_TITLE_LENGTH};
-use sphare_core_common::editor::TextareaData;
+use sphare_core_common::editor::{unescape_plain_text, TextareaData};
 use sphare_core_user::role::PermissionLevel;
 
 use sphare_cmp_common::role::AuthorizedShow;
@@ -118,7 +118,7 @@ pub fn EditRuleForm(
         rule.title.clone(),
         match &rule.markdown_description {
             Some(description) => description.clone(),
-            None => rule.description.clone(),
+            None => unescape_plain_text(&rule.description),
         },
         rule.markdown_description.is_some()
     ));
@@ -10,7 +10,7 @@ use url::Url;
 
 use sphare_core_common::checks::{check_satellite_name, check_string_length};
 use sphare_core_common::constants::{MAX_CONTENT_LENGTH, MAX_SATELLITE_NAME_LENGTH, POST_BATCH_SIZE, SCROLL_LOAD_THROTTLE_DELAY};
-use sphare_core_common::editor::TextareaData;
+use sphare_core_common::editor::{unescape_plain_text, TextareaData};
 use sphare_core_common::routes::{get_satellite_id_memo, get_satellite_path};
 use sphare_core_common::unpack::{handle_additional_load, reset_additional_load};
 use sphare_core_content::embed::EmbedType;
@@ -20,16 +20,17 @@ use sphare_core_sphere::satellite::Satellite;
 use sphare_core_user::role::PermissionLevel;
 
 use sphare_iface_content::post::{get_post_vec_by_satellite_id, CreatePost};
-use sphare_iface_sphere::satellite::{get_satellite_by_id, get_satellite_vec_by_sphere_name};
+use sphare_iface_sphere::satellite::{get_satellite_by_id, get_satellite_vec_by_sphere_name, is_subscribed_to_satellite, SubscribeSatellite, UnsubscribeSatellite};
 use sphare_iface_sphere::sphere::get_sphere_with_user_info;
 use sphare_iface_sphere::sphere_category::get_sphere_category_vec;
 
 use sphare_cmp_base::post::{PostForm, PostListWithInitLoad};
+use sphare_cmp_common::auth_widget::LoginGuardedButton;
 use sphare_cmp_common::role::AuthorizedShow;
 use sphare_cmp_common::state::{GlobalState, SatelliteState, SphereState};
 use sphare_cmp_utils::editor::{FormMarkdownEditor, FormTextEditor};
-use sphare_cmp_utils::form::LabeledFormCheckbox;
-use sphare_cmp_utils::icons::{EditIcon, LinkIcon, NsfwIcon, PauseIcon, PlayIcon, PlusIcon};
+use sphare_cmp_utils::form::{IdempotencyKeyInput, LabeledFormCheckbox};
+use sphare_cmp_utils::icons::{EditIcon, LinkIcon, NsfwIcon, PauseIcon, PlayIcon, PlusIcon, StarIcon};
 use sphare_cmp_utils::unpack::{ActionError, SuspenseUnpack, TransitionUnpack};
 use sphare_cmp_utils::widget::{ContentBody, ModalDialog, ModalFormButtons, SpoilerBadge, TagsWidget};
 
@@ -88,6 +89,46 @@ pub fn SatelliteHeader(
     }
 }
 
+/// Button to follow a satellite, so that its posts are included in the user's home feed
+#[component]
+pub fn SatelliteSubscribeButton() -> impl IntoView {
+    let satellite_state = expect_context::<SatelliteState>();
+    let subscribe_action = ServerAction::<SubscribeSatellite>::new();
+    let unsubscribe_action = ServerAction::<UnsubscribeSatellite>::new();
+    let is_subscribed_resource = Resource::new(
+        move || satellite_state.satellite_id.get(),
+        is_subscribed_to_satellite,
+    );
+
+    view! {
+        <SuspenseUnpack resource=is_subscribed_resource let:is_subscribed>
+        {
+            let is_subscribed = RwSignal::new(*is_subscribed);
+            view! {
+                <div class="tooltip" data-tip=move_tr!("follow-satellite")>
+                    <LoginGuardedButton
+                        button_class="button-rounded-ghost"
+                        button_action=move |_| {
+                            let satellite_id = satellite_state.satellite_id.get_untracked();
+                            is_subscribed.update(|value| {
+                                *value = !*value;
+                                if *value {
+                                    subscribe_action.dispatch(SubscribeSatellite { satellite_id });
+                                } else {
+                                    unsubscribe_action.dispatch(UnsubscribeSatellite { satellite_id });
+                                }
+                            })
+                        }
+                    >
+                        <StarIcon class="sphere-toolbar-icon-size" show_color=is_subscribed/>
+                    </LoginGuardedButton>
+                </div>
+            }
+        }
+        </SuspenseUnpack>
+    }
+}
+
 /// Component to display a satellite's content
 #[component]
 pub fn SatelliteContent() -> impl IntoView {
@@ -222,7 +263,7 @@ pub fn CreateSatellitePost() -> impl IntoView {
 
     let category_vec_resource = Resource::new(
         move || sphere_state.sphere_name.get(),
-        move |sphere_name| get_sphere_category_vec(sphere_name)
+        move |sphere_name| get_sphere_category_vec(sphere_name, true)
     );
 
     view! {
@@ -230,6 +271,7 @@ pub fn CreateSatellitePost() -> impl IntoView {
             <ActionForm action=create_post_action>
                 <div class="flex flex-col gap-2 w-full">
                     <h2 class="py-4 text-4xl text-center">"Share a post!"</h2>
+                    <IdempotencyKeyInput name="idempotency_key"/>
                     <input
                         type="text"
                         name="post_location[sphere]"
@@ -454,7 +496,7 @@ pub fn EditSatelliteForm(
     let body_ref = NodeRef::<html::Textarea>::new();
     let (body, is_markdown_body) = match satellite.markdown_body {
         Some(markdown_body) => (markdown_body, true),
-        None => (satellite.body, false),
+        None => (unescape_plain_text(&satellite.body), false),
     };
     let body_data = TextareaData {
         content: RwSignal::new(body),
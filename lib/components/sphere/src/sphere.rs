@@ -19,7 +19,7 @@ use sphare_core_content::ranking::SortType;
 use sphare_core_user::role::PermissionLevel;
 
 use sphare_iface_content::post::get_post_vec_by_sphere_name;
-use sphare_iface_sphere::sphere::{is_sphere_available, Subscribe, Unsubscribe};
+use sphare_iface_sphere::sphere::{has_confirmed_nsfw, is_sphere_available, ConfirmNsfw, Subscribe, Unsubscribe};
 
 use sphare_cmp_base::filter::PostFiltersButton;
 use sphare_cmp_base::post::PostListWithInitLoad;
@@ -34,7 +34,7 @@ use sphare_cmp_utils::icons::{LoadingIcon, MagnifierIcon, NsfwIcon, PlusIcon, Re
 use sphare_cmp_utils::unpack::{ActionError, SuspenseUnpack, TransitionUnpack};
 use sphare_cmp_utils::widget::{BannerContent, RefreshButton};
 
-use crate::satellite::ActiveSatelliteList;
+use crate::satellite::{ActiveSatelliteList, SatelliteSubscribeButton};
 use crate::sphere_category::get_sphere_category_header_map;
 use crate::sphere_management::MANAGE_SPHERE_ROUTE;
 
@@ -54,19 +54,29 @@ pub fn SphereBanner() -> impl IntoView {
         path.matches("/").count() > 2
     };
 
+    // accent color picked by the sphere's leader, exposed as a css variable to the sphere's layout
+    let accent_color = move || match &*sphere_state.sphere_with_user_info_resource.read() {
+        Some(Ok(sphere_with_user_info)) => sphere_with_user_info.sphere.accent_color.to_css_value().unwrap_or("transparent"),
+        _ => "transparent",
+    };
+
     Effect::new(move || {
         sphere_name.read();
         sphere_state.sphere_category_filter.set(SphereCategoryFilter::All);
     });
 
     view! {
-        <div class="flex flex-col flex-1 w-full overflow-y-auto pt-2 px-2 xl:px-4 gap-2 overflow-hidden">
+        <div
+            class="flex flex-col flex-1 w-full overflow-y-auto pt-2 px-2 xl:px-4 gap-2 overflow-hidden"
+            style=("--sphere-accent-color", accent_color)
+        >
             <TransitionUnpack resource=sphere_state.sphere_with_user_info_resource let:sphere_with_user_info>
             {
                 view! {
                     <a
                         href=sphere_path()
                         class="relative flex-none rounded-sm w-full h-16 2xl:h-24 4xl:h-32 flex items-center justify-center max-w-full overflow-hidden"
+                        style="border-bottom: 4px solid var(--sphere-accent-color, transparent)"
                         node_ref=link_ref
                     >
                         <Show when=is_sphere_sub_page>
@@ -188,6 +198,7 @@ pub fn SphereContents() -> impl IntoView {
                 subscription_id=sphere.subscription_id
                 sort_signal=state.post_sort_type
             />
+            { sphere.sphere.is_nsfw.then(|| view! { <NsfwSphereGuard/> }) }
         </SuspenseUnpack>
         <PostListWithInitLoad
             post_vec_resource
@@ -279,11 +290,59 @@ pub fn SphereToolbar(
                         <SubscribedIcon class="sphere-toolbar-icon-size" show_color=is_subscribed/>
                     </LoginGuardedButton>
                 </div>
+                { satellite_state.map(|_| view! { <SatelliteSubscribeButton/> }) }
             </div>
         </div>
     }.into_any()
 }
 
+/// Prompt blocking the posts of a NSFW sphere until the user confirms they want to see them.
+/// Anonymous users are asked to log in instead.
+#[component]
+pub fn NsfwSphereGuard() -> impl IntoView {
+    let sphere_state = expect_context::<SphereState>();
+    let confirm_nsfw_action = ServerAction::<ConfirmNsfw>::new();
+    let has_confirmed_resource = Resource::new(
+        move || (sphere_state.sphere_name.get(), confirm_nsfw_action.version().get()),
+        move |(sphere_name, _)| has_confirmed_nsfw(sphere_name),
+    );
+
+    Effect::new(move || {
+        if let Some(Ok(())) = &*confirm_nsfw_action.value().read() {
+            sphere_state.post_refresh_count.update(|count| *count += 1);
+        }
+    });
+
+    view! {
+        <SuspenseUnpack resource=has_confirmed_resource let:has_confirmed>
+        {
+            (!*has_confirmed).then(|| view! {
+                <div class="flex flex-col items-center gap-2 p-4 bg-base-200 rounded-sm">
+                    <div class="flex items-center gap-2">
+                        <NsfwIcon/>
+                        {move_tr!("nsfw-sphere-warning")}
+                    </div>
+                    <LoginGuardButton
+                        login_button_class="button-primary"
+                        login_button_content=move || view! { {move_tr!("confirm-nsfw")} }.into_any()
+                        let:_user
+                    >
+                        <button
+                            class="button-primary"
+                            on:click=move |_| {
+                                confirm_nsfw_action.dispatch(ConfirmNsfw { sphere_name: sphere_state.sphere_name.get_untracked() });
+                            }
+                        >
+                            {move_tr!("confirm-nsfw")}
+                        </button>
+                    </LoginGuardButton>
+                </div>
+            })
+        }
+        </SuspenseUnpack>
+    }
+}
+
 /// Button to navigate to the search page of a sphere
 #[component]
 pub fn SphereSearchButton() -> impl IntoView
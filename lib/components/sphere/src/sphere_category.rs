@@ -18,11 +18,12 @@ use sphare_cmp_utils::colors::{ColorIndicator, ColorSelect};
 use sphare_cmp_utils::editor::{FormTextEditor, LengthLimitedInput};
 use sphare_cmp_utils::form::FormCheckbox;
 use sphare_cmp_utils::icons::{CrossIcon, PauseIcon, PlayIcon, SaveIcon};
-use sphare_cmp_utils::unpack::TransitionUnpack;
+use sphare_cmp_utils::unpack::{ActionError, TransitionUnpack};
 
 /// Component to manage sphere categories
 #[component]
 pub fn SphereCategoriesDialog() -> impl IntoView {
+    let state = expect_context::<GlobalState>();
     let sphere_state = expect_context::<SphereState>();
     let sphere_name = sphere_state.sphere_name;
 
@@ -101,6 +102,7 @@ pub fn SphereCategoriesDialog() -> impl IntoView {
                         </TransitionUnpack>
                     </div>
                     <SetCategoryForm category_input color_input activated_input description_data name_textarea_ref/>
+                    <ActionError action=state.delete_sphere_category_action.into()/>
                 </div>
             </div>
         </AuthorizedShow>
@@ -193,6 +195,11 @@ pub fn DeleteCategoryButton(
                     class="hidden"
                     value=category_name.get_value()
                 />
+                <input
+                    name="reassign_posts"
+                    class="hidden"
+                    value="false"
+                />
                 <button class="button-error">
                     <CrossIcon/>
                 </button>
@@ -230,6 +237,7 @@ mod tests {
             category_color: Color::None,
             description: "".to_string(),
             is_active: false,
+            display_order: 0,
             creator_id: 0,
             timestamp: Default::default(),
             delete_timestamp: None,
@@ -241,6 +249,7 @@ mod tests {
             category_color: Color::None,
             description: "".to_string(),
             is_active: false,
+            display_order: 0,
             creator_id: 0,
             timestamp: Default::default(),
             delete_timestamp: None,
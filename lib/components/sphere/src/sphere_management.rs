@@ -1,3 +1,4 @@
+use leptos::either::Either;
 use leptos::ev::{Event, SubmitEvent};
 use leptos::html;
 use leptos::prelude::*;
@@ -10,14 +11,17 @@ use leptos_use::signal_debounced;
 use strum::IntoEnumIterator;
 
 use sphare_core_common::checks::check_username;
-use sphare_core_common::constants::{MAX_SPHERE_DESCRIPTION_LENGTH, MAX_USERNAME_LENGTH};
+use sphare_core_common::constants::{MAX_SPHERE_DESCRIPTION_LENGTH, MAX_SPHERE_SIDEBAR_LENGTH, MAX_USERNAME_LENGTH};
 use sphare_core_common::editor::TextareaData;
 use sphare_core_common::errors::AppError;
 use sphare_core_common::traits::ToLocalizedStr;
+use sphare_core_user::mod_log::MOD_LOG_FETCH_LIMIT;
 use sphare_core_user::role::PermissionLevel;
 
 use sphare_iface_content::moderation::get_moderation_info;
+use sphare_iface_sphere::sphere::{SetSphereRejectDuplicateLinks, SetSphereRequireRuleAcceptance};
 use sphare_iface_sphere::sphere_management::{get_sphere_ban_vec, set_sphere_banner, set_sphere_icon, RemoveUserBan};
+use sphare_iface_user::mod_log::get_mod_log;
 use sphare_iface_user::role::SetUserSphereRole;
 use sphare_iface_user::user::get_matching_user_header_vec;
 
@@ -25,10 +29,12 @@ use sphare_cmp_base::moderation::ModerationInfoDialog;
 use sphare_cmp_common::auth_widget::LoginWindow;
 use sphare_cmp_common::role::AuthorizedShow;
 use sphare_cmp_common::state::{GlobalState, SphereState};
-use sphare_cmp_utils::editor::{FormTextEditor, LengthLimitedInput};
+use sphare_cmp_utils::colors::ColorSelect;
+use sphare_cmp_utils::editor::{FormMarkdownEditor, FormTextEditor, LengthLimitedInput};
 use sphare_cmp_utils::errors::ErrorDisplay;
+use sphare_cmp_utils::form::LabeledFormCheckbox;
 use sphare_cmp_utils::icons::{CrossIcon, LoadingIcon, MagnifierIcon, SaveIcon};
-use sphare_cmp_utils::unpack::{SuspenseUnpack, TransitionUnpack};
+use sphare_cmp_utils::unpack::{ActionError, SuspenseUnpack, TransitionUnpack};
 use sphare_cmp_utils::widget::{LocalizedEnumDropdown, ModalDialog, IMAGE_FILE_PARAM, SPHERE_NAME_PARAM};
 
 use crate::rule::SphereRulesPanel;
@@ -72,13 +78,17 @@ pub fn SphereCockpit() -> impl IntoView {
         <div class="flex flex-col gap-5 overflow-y-auto w-full 2xl:w-4/5 4xl:w-2/3 mx-auto pb-5">
             <div class="text-2xl text-center">{move_tr!("sphere-cockpit")}</div>
             <SphereDescriptionDialog/>
+            <SphereSidebarDialog/>
+            <SphereColorDialog/>
             <SphereIconDialog/>
             <SphereBannerDialog/>
+            <SphereSettingsPanel/>
             <SatellitePanel/>
             <SphereCategoriesDialog/>
             <ModeratorPanel/>
             <SphereRulesPanel/>
             <BanPanel/>
+            <ModLogPanel/>
         </div>
     }
 }
@@ -141,6 +151,102 @@ pub fn SphereDescriptionForm(
     }
 }
 
+/// Component to edit the content of a sphere's sidebar
+#[component]
+pub fn SphereSidebarDialog() -> impl IntoView {
+    let sphere_state = expect_context::<SphereState>();
+    let sphere_name = expect_context::<SphereState>().sphere_name;
+    view! {
+        <AuthorizedShow sphere_name permission_level=PermissionLevel::Moderate>
+            <div class="flex flex-col gap-1 items-center w-full h-fit bg-base-200 p-2 rounded-sm">
+                <div class="text-xl text-center">{move_tr!("sphere-sidebar")}</div>
+                <SuspenseUnpack resource=sphere_state.sphere_with_user_info_resource let:sphere_with_user_info>
+                    <SphereSidebarForm sidebar_markdown=sphere_with_user_info.sphere.sidebar_markdown.clone().unwrap_or_default()/>
+                </SuspenseUnpack>
+            </div>
+        </AuthorizedShow>
+    }
+}
+
+/// Form to edit the content of a sphere's sidebar
+#[component]
+pub fn SphereSidebarForm(
+    sidebar_markdown: String,
+) -> impl IntoView {
+    let state = expect_context::<GlobalState>();
+    let sphere_state = expect_context::<SphereState>();
+    let textarea_ref = NodeRef::<html::Textarea>::new();
+    let sidebar_data = TextareaData {
+        content: RwSignal::new(sidebar_markdown),
+        textarea_ref
+    };
+    let disable_submit = move || sidebar_data.content.read().len() > MAX_SPHERE_SIDEBAR_LENGTH;
+    view! {
+        <ActionForm
+            action=state.set_sphere_sidebar_action
+            attr:class="w-full flex flex-col gap-1"
+        >
+            <input
+                name="sphere_name"
+                class="hidden"
+                value=sphere_state.sphere_name
+            />
+            <FormMarkdownEditor
+                name="markdown"
+                is_markdown_name="is_markdown"
+                placeholder=move_tr!("content")
+                data=sidebar_data
+                is_markdown=true
+                maxlength=Some(MAX_SPHERE_SIDEBAR_LENGTH)
+                is_empty_ok=true
+            />
+            <button
+                type="submit"
+                class="button-secondary self-end"
+                disabled=disable_submit
+            >
+                <SaveIcon/>
+            </button>
+        </ActionForm>
+    }
+}
+
+/// Component to edit a sphere's accent color
+#[component]
+pub fn SphereColorDialog() -> impl IntoView {
+    let state = expect_context::<GlobalState>();
+    let sphere_state = expect_context::<SphereState>();
+    let sphere_name = sphere_state.sphere_name;
+    view! {
+        <AuthorizedShow sphere_name permission_level=PermissionLevel::Lead>
+            <div class="flex flex-col gap-1 items-center w-full h-fit bg-base-200 p-2 rounded-sm">
+                <div class="text-xl text-center">{move_tr!("sphere-accent-color")}</div>
+                <SuspenseUnpack resource=sphere_state.sphere_with_user_info_resource let:sphere_with_user_info>
+                {
+                    let color_input = RwSignal::new(sphere_with_user_info.sphere.accent_color);
+                    view! {
+                        <ActionForm
+                            action=state.set_sphere_color_action
+                            attr:class="w-full flex gap-1 justify-between items-center"
+                        >
+                            <input
+                                name="sphere_name"
+                                class="hidden"
+                                value=sphere_name
+                            />
+                            <ColorSelect name="color" color_input class="h-full w-12 lg:w-20 flex justify-center"/>
+                            <button type="submit" class="button-secondary">
+                                <SaveIcon/>
+                            </button>
+                        </ActionForm>
+                    }
+                }
+                </SuspenseUnpack>
+            </div>
+        </AuthorizedShow>
+    }
+}
+
 /// Component to edit a sphere's icon
 #[component]
 pub fn SphereIconDialog() -> impl IntoView {
@@ -289,6 +395,101 @@ pub fn SphereImageForm(
     }
 }
 
+/// Component to edit the settings restricting publication in a sphere
+#[component]
+pub fn SphereSettingsPanel() -> impl IntoView {
+    let sphere_state = expect_context::<SphereState>();
+    view! {
+        <AuthorizedShow sphere_name=sphere_state.sphere_name permission_level=PermissionLevel::Manage>
+            <div class="flex flex-col gap-1 items-center w-full h-fit bg-base-200 p-2 rounded-sm">
+                <div class="text-xl text-center">{move_tr!("sphere-settings")}</div>
+                <SuspenseUnpack resource=sphere_state.sphere_with_user_info_resource let:sphere_with_user_info>
+                    <RuleAcceptanceForm require_rule_acceptance=sphere_with_user_info.sphere.require_rule_acceptance/>
+                    <DuplicateLinkForm reject_duplicate_links=sphere_with_user_info.sphere.reject_duplicate_links/>
+                </SuspenseUnpack>
+            </div>
+        </AuthorizedShow>
+    }
+}
+
+/// Form to set whether users must accept a sphere's rules before posting
+#[component]
+pub fn RuleAcceptanceForm(
+    require_rule_acceptance: bool,
+) -> impl IntoView {
+    let state = expect_context::<GlobalState>();
+    let sphere_state = expect_context::<SphereState>();
+    let set_require_rule_acceptance_action = ServerAction::<SetSphereRequireRuleAcceptance>::new();
+
+    Effect::new(move || {
+        if let Some(Ok(())) = &*set_require_rule_acceptance_action.value().read() {
+            state.sphere_reload_signal.update(|value| *value += 1);
+        }
+    });
+
+    view! {
+        <ActionForm
+            action=set_require_rule_acceptance_action
+            attr:class="w-full flex justify-between items-center gap-2"
+        >
+            <input
+                name="sphere_name"
+                class="hidden"
+                value=sphere_state.sphere_name
+            />
+            <LabeledFormCheckbox
+                name="require_rule_acceptance"
+                label=move_tr!("require-rule-acceptance")
+                value=require_rule_acceptance
+                class="grow"
+            />
+            <button type="submit" class="button-secondary">
+                <SaveIcon/>
+            </button>
+        </ActionForm>
+        <ActionError action=set_require_rule_acceptance_action.into()/>
+    }
+}
+
+/// Form to set whether posts with a link recently posted in a sphere are rejected
+#[component]
+pub fn DuplicateLinkForm(
+    reject_duplicate_links: bool,
+) -> impl IntoView {
+    let state = expect_context::<GlobalState>();
+    let sphere_state = expect_context::<SphereState>();
+    let set_reject_duplicate_links_action = ServerAction::<SetSphereRejectDuplicateLinks>::new();
+
+    Effect::new(move || {
+        if let Some(Ok(())) = &*set_reject_duplicate_links_action.value().read() {
+            state.sphere_reload_signal.update(|value| *value += 1);
+        }
+    });
+
+    view! {
+        <ActionForm
+            action=set_reject_duplicate_links_action
+            attr:class="w-full flex justify-between items-center gap-2"
+        >
+            <input
+                name="sphere_name"
+                class="hidden"
+                value=sphere_state.sphere_name
+            />
+            <LabeledFormCheckbox
+                name="reject_duplicate_links"
+                label=move_tr!("reject-duplicate-links")
+                value=reject_duplicate_links
+                class="grow"
+            />
+            <button type="submit" class="button-secondary">
+                <SaveIcon/>
+            </button>
+        </ActionForm>
+        <ActionError action=set_reject_duplicate_links_action.into()/>
+    }
+}
+
 /// Component to manage moderators
 #[component]
 pub fn ModeratorPanel() -> impl IntoView {
@@ -473,10 +674,23 @@ pub fn BanPanel() -> impl IntoView {
                                 <div class="w-2/5 px-2 text-sm">{user_ban.username.clone()}</div>
                                 <div class="w-2/5 text-sm">{duration_string}</div>
                                 <div class="flex-grow flex justify-end items-center gap-1">
-                                    <BanInfoButton
-                                        post_id=user_ban.post_id
-                                        comment_id=user_ban.comment_id
-                                    />
+                                    {
+                                        match user_ban.post_id {
+                                            Some(post_id) => Either::Left(view! {
+                                                <BanInfoButton
+                                                    post_id
+                                                    comment_id=user_ban.comment_id
+                                                />
+                                            }),
+                                            None => Either::Right(view! {
+                                                <div class="tooltip tooltip-left" data-tip=user_ban.reason.clone().unwrap_or_default()>
+                                                    <div class="button-secondary">
+                                                        <MagnifierIcon class="content-toolbar-icon-size"/>
+                                                    </div>
+                                                </div>
+                                            }),
+                                        }
+                                    }
                                     <AuthorizedShow sphere_name permission_level=PermissionLevel::Ban>
                                         <ActionForm action=unban_action attr:class="flex justify-center items-center">
                                             <input
@@ -500,6 +714,42 @@ pub fn BanPanel() -> impl IntoView {
     }
 }
 
+/// Component to display the most recent moderation actions of a sphere
+#[component]
+pub fn ModLogPanel() -> impl IntoView {
+    let sphere_name = expect_context::<SphereState>().sphere_name;
+    let mod_log_resource = Resource::new(
+        move || sphere_name.get(),
+        move |sphere_name| get_mod_log(sphere_name, MOD_LOG_FETCH_LIMIT, 0),
+    );
+
+    view! {
+        <div class="flex flex-col gap-1 items-center w-full bg-base-200 p-2 rounded-sm">
+            <div class="text-xl text-center">{move_tr!("mod-log")}</div>
+            <div class="w-full flex flex-col gap-1">
+                <div class="flex gap-4 items-center border-b border-base-content/20 py-2 font-bold">
+                    <div class="w-1/4 px-2">{move_tr!("date")}</div>
+                    <div class="w-1/4">{move_tr!("moderator")}</div>
+                    <div class="w-1/4">{move_tr!("action")}</div>
+                    <div class="w-1/4">{move_tr!("target")}</div>
+                </div>
+                <TransitionUnpack resource=mod_log_resource show_error_detail=true let:mod_log_vec>
+                {
+                    mod_log_vec.iter().map(|mod_log_entry| view! {
+                        <div class="flex gap-4 items-center text-sm">
+                            <div class="w-1/4 px-2">{mod_log_entry.create_timestamp.format("%Y-%m-%d %H:%M UTC").to_string()}</div>
+                            <div class="w-1/4">{mod_log_entry.actor_name.clone()}</div>
+                            <div class="w-1/4">{mod_log_entry.action.to_localized_str()}</div>
+                            <div class="w-1/4">{mod_log_entry.target_username.clone()}</div>
+                        </div>
+                    }).collect_view()
+                }
+                </TransitionUnpack>
+            </div>
+        </div>
+    }
+}
+
 /// Component to display a button opening a modal dialog with a ban's details
 #[component]
 pub fn BanInfoButton(
@@ -0,0 +1,249 @@
+use leptos::either::EitherOf3;
+use leptos::form::ActionForm;
+use leptos::html;
+use leptos::prelude::*;
+use leptos_fluent::move_tr;
+use leptos_router::hooks::use_params_map;
+
+use sphare_core_common::checks::check_wiki_slug;
+use sphare_core_common::constants::{MAX_CONTENT_LENGTH, MAX_TITLE_LENGTH, MAX_WIKI_SLUG_LENGTH};
+use sphare_core_common::editor::TextareaData;
+use sphare_core_common::errors::AppError;
+use sphare_core_common::routes::get_wiki_slug_memo;
+use sphare_core_sphere::wiki::SpherePage;
+use sphare_core_user::role::PermissionLevel;
+
+use sphare_iface_sphere::wiki::{get_sphere_page, CreateSpherePage, UpdateSpherePage};
+
+use sphare_cmp_common::role::AuthorizedShow;
+use sphare_cmp_common::state::SphereState;
+use sphare_cmp_utils::editor::{FormMarkdownEditor, FormTextEditor};
+use sphare_cmp_utils::errors::ErrorDisplay;
+use sphare_cmp_utils::icons::{EditIcon, LoadingIcon, PlusIcon};
+use sphare_cmp_utils::unpack::ActionError;
+use sphare_cmp_utils::widget::{ContentBody, ModalDialog, ModalFormButtons};
+
+/// Component to display a wiki page of a sphere. Moderators can create the page if it doesn't exist yet or edit it.
+#[component]
+pub fn SphereWikiPage() -> impl IntoView {
+    let sphere_state = expect_context::<SphereState>();
+    let params = use_params_map();
+    let slug = get_wiki_slug_memo(params);
+    let create_page_action = ServerAction::<CreateSpherePage>::new();
+    let update_page_action = ServerAction::<UpdateSpherePage>::new();
+    let page_resource = Resource::new(
+        move || (
+            sphere_state.sphere_name.get(),
+            slug.get(),
+            create_page_action.version().get(),
+            update_page_action.version().get(),
+        ),
+        move |(sphere_name, slug, _, _)| get_sphere_page(sphere_name, slug),
+    );
+
+    view! {
+        <div class="w-full flex flex-col gap-2 p-2">
+            <Suspense fallback=move || view! { <LoadingIcon/> }>
+            {
+                move || Suspend::new(async move {
+                    match page_resource.await {
+                        Ok(page) => EitherOf3::A(view! { <SpherePageContent page update_page_action/> }),
+                        Err(AppError::NotFound) => EitherOf3::B(view! {
+                            <div class="text-center">{move_tr!("wiki-page-not-found")}</div>
+                            <AuthorizedShow sphere_name=sphere_state.sphere_name permission_level=PermissionLevel::Moderate>
+                                <CreateSpherePageForm slug=slug.get_untracked() create_page_action/>
+                            </AuthorizedShow>
+                        }),
+                        Err(e) => EitherOf3::C(view! { <ErrorDisplay error=e.clone()/> }),
+                    }
+                })
+            }
+            </Suspense>
+            <ActionError action=create_page_action.into()/>
+            <ActionError action=update_page_action.into()/>
+        </div>
+    }
+}
+
+/// Component to display the content of a wiki page with a button to edit it for moderators
+#[component]
+pub fn SpherePageContent(
+    page: SpherePage,
+    update_page_action: ServerAction<UpdateSpherePage>,
+) -> impl IntoView {
+    let sphere_state = expect_context::<SphereState>();
+    let page = StoredValue::new(page);
+    let show_edit_form = RwSignal::new(false);
+    view! {
+        <div class="flex justify-between items-center gap-2">
+            <h1 class="text-2xl font-bold">{page.read_value().title.clone()}</h1>
+            <AuthorizedShow sphere_name=sphere_state.sphere_name permission_level=PermissionLevel::Moderate>
+                <button
+                    class="button-secondary"
+                    on:click=move |_| show_edit_form.update(|value| *value = !*value)
+                >
+                    <EditIcon/>
+                </button>
+            </AuthorizedShow>
+        </div>
+        <ContentBody body=page.read_value().html_body.clone() is_markdown=page.read_value().is_markdown/>
+        <ModalDialog
+            class="w-full max-w-xl"
+            show_dialog=show_edit_form
+        >
+            <EditSpherePageForm page update_page_action show_form=show_edit_form/>
+        </ModalDialog>
+    }
+}
+
+/// Component to edit a wiki page
+#[component]
+pub fn EditSpherePageForm(
+    page: StoredValue<SpherePage>,
+    update_page_action: ServerAction<UpdateSpherePage>,
+    show_form: RwSignal<bool>,
+) -> impl IntoView {
+    let sphere_state = expect_context::<SphereState>();
+    let title_ref = NodeRef::<html::Textarea>::new();
+    let title_data = TextareaData {
+        content: RwSignal::new(page.read_value().title.clone()),
+        textarea_ref: title_ref,
+    };
+    let body_ref = NodeRef::<html::Textarea>::new();
+    let body_data = TextareaData {
+        content: RwSignal::new(page.read_value().markdown_body.clone()),
+        textarea_ref: body_ref,
+    };
+    let slug = RwSignal::new(page.read_value().slug.clone());
+    let invalid_inputs = Signal::derive(move || is_invalid_sphere_page_inputs(slug, title_data.content, body_data.content));
+
+    view! {
+        <div class="bg-base-100 shadow-xl p-3 rounded-xs flex flex-col gap-3">
+            <div class="text-center font-bold text-2xl">{move_tr!("edit-wiki-page")}</div>
+            <ActionForm
+                action=update_page_action
+                on:submit=move |_| show_form.set(false)
+            >
+                <input
+                    name="sphere_name"
+                    class="hidden"
+                    value=sphere_state.sphere_name
+                />
+                <input
+                    name="slug"
+                    class="hidden"
+                    value=slug
+                />
+                <div class="flex flex-col gap-3 w-full">
+                    <SpherePageInputs title_data body_data is_markdown=page.read_value().is_markdown/>
+                    <ModalFormButtons
+                        disable_publish=invalid_inputs
+                        show_form
+                    />
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
+
+/// Component to create a wiki page with the given `slug`
+#[component]
+pub fn CreateSpherePageForm(
+    slug: String,
+    create_page_action: ServerAction<CreateSpherePage>,
+) -> impl IntoView {
+    let sphere_state = expect_context::<SphereState>();
+    let show_dialog = RwSignal::new(false);
+    let slug = RwSignal::new(slug);
+    let title_ref = NodeRef::<html::Textarea>::new();
+    let title_data = TextareaData {
+        content: RwSignal::new(String::new()),
+        textarea_ref: title_ref,
+    };
+    let body_ref = NodeRef::<html::Textarea>::new();
+    let body_data = TextareaData {
+        content: RwSignal::new(String::new()),
+        textarea_ref: body_ref,
+    };
+    let invalid_inputs = Signal::derive(move || is_invalid_sphere_page_inputs(slug, title_data.content, body_data.content));
+
+    view! {
+        <button
+            class="self-center button-secondary"
+            on:click=move |_| show_dialog.update(|value| *value = !*value)
+        >
+            <PlusIcon/>
+        </button>
+        <ModalDialog
+            class="w-full max-w-xl"
+            show_dialog
+        >
+            <div class="bg-base-100 shadow-xl p-3 rounded-xs flex flex-col gap-3">
+                <div class="text-center font-bold text-2xl">{move_tr!("create-wiki-page")}</div>
+                <ActionForm
+                    action=create_page_action
+                    on:submit=move |_| show_dialog.set(false)
+                >
+                    <input
+                        name="sphere_name"
+                        class="hidden"
+                        value=sphere_state.sphere_name
+                    />
+                    <div class="flex flex-col gap-3 w-full">
+                        <input
+                            tabindex="0"
+                            type="text"
+                            name="slug"
+                            placeholder=move_tr!("wiki-slug")
+                            autocomplete="off"
+                            maxlength=MAX_WIKI_SLUG_LENGTH
+                            class="input_primary px-1"
+                            class=("input_error", move || check_wiki_slug(&slug.read()).is_err())
+                            bind:value=slug
+                        />
+                        <SpherePageInputs title_data body_data is_markdown=true/>
+                        <ModalFormButtons
+                            disable_publish=invalid_inputs
+                            show_form=show_dialog
+                        />
+                    </div>
+                </ActionForm>
+            </div>
+        </ModalDialog>
+    }
+}
+
+/// Components with inputs to create or edit a wiki page
+#[component]
+pub fn SpherePageInputs(
+    title_data: TextareaData,
+    body_data: TextareaData,
+    is_markdown: bool,
+) -> impl IntoView {
+    view! {
+        <FormTextEditor
+            name="title"
+            placeholder=move_tr!("title")
+            data=title_data
+            maxlength=Some(MAX_TITLE_LENGTH as usize)
+        />
+        <FormMarkdownEditor
+            name="markdown_body"
+            is_markdown_name="is_markdown"
+            placeholder=move_tr!("content")
+            data=body_data
+            is_markdown
+            maxlength=Some(MAX_CONTENT_LENGTH as usize)
+        />
+    }
+}
+
+fn is_invalid_sphere_page_inputs(
+    slug: RwSignal<String>,
+    title: RwSignal<String>,
+    body: RwSignal<String>,
+) -> bool {
+    check_wiki_slug(&slug.read()).is_err() ||
+        title.with(|title| title.is_empty() || title.len() > MAX_TITLE_LENGTH as usize) ||
+        body.with(|body| body.is_empty() || body.len() > MAX_CONTENT_LENGTH as usize)
+}
@@ -2,12 +2,16 @@ use leptos::prelude::*;
 use leptos_fluent::move_tr;
 use leptos_router::components::Form;
 
-use sphare_core_common::routes::{get_create_post_path, get_current_url, get_profile_path, get_sphere_name, CREATE_POST_ROUTE, CREATE_POST_SPHERE_QUERY_PARAM, CREATE_SPHERE_ROUTE};
+use sphare_core_common::routes::{get_create_post_path, get_current_url, get_profile_path, get_sphere_name, CREATE_POST_ROUTE, CREATE_POST_SPHERE_QUERY_PARAM, CREATE_SPHERE_ROUTE, REPORT_INBOX_ROUTE};
+use sphare_core_user::role::AdminRole;
+
+use sphare_iface_user::announcement::{get_active_announcements, DismissAnnouncement};
 
 use sphare_cmp_common::auth_widget::LoginGuardButton;
 use sphare_cmp_common::notification::NotificationButton;
 use sphare_cmp_common::state::GlobalState;
 use sphare_cmp_utils::icons::*;
+use sphare_cmp_utils::unpack::TransitionUnpack;
 use sphare_cmp_utils::widget::DropdownButton;
 
 use crate::search::SearchButton;
@@ -39,6 +43,50 @@ pub fn NavigationBar() -> impl IntoView
                 <UserMenu/>
             </div>
         </div>
+        <AnnouncementBanner/>
+    }
+}
+
+/// Banner displaying the active site-wide announcements, logged-in users can dismiss them
+#[component]
+pub fn AnnouncementBanner() -> impl IntoView {
+    let state = expect_context::<GlobalState>();
+    let dismiss_action = ServerAction::<DismissAnnouncement>::new();
+    let announcement_resource = Resource::new(
+        move || dismiss_action.version().get(),
+        |_| get_active_announcements(),
+    );
+
+    view! {
+        <TransitionUnpack resource=announcement_resource fallback=|| () let:announcement_vec>
+        {
+            announcement_vec.iter().map(|announcement| {
+                let announcement_id = announcement.announcement_id;
+                view! {
+                    <div class="flex justify-between items-start gap-2 w-full px-2 py-1 bg-warning text-warning-content text-sm">
+                        <div class="flex flex-col">
+                            <span class="font-semibold">{announcement.title.clone()}</span>
+                            <div inner_html=announcement.body.clone()/>
+                        </div>
+                        <Transition>
+                        {
+                            move || Suspend::new(async move {
+                                matches!(state.user.await, Ok(Some(_))).then(|| view! {
+                                    <ActionForm action=dismiss_action attr:class="flex">
+                                        <input type="text" name="announcement_id" class="hidden" value=announcement_id/>
+                                        <button type="submit" class="button-rounded-ghost" aria-label=move_tr!("close")>
+                                            <CrossIcon/>
+                                        </button>
+                                    </ActionForm>
+                                })
+                            })
+                        }
+                        </Transition>
+                    </div>
+                }
+            }).collect_view()
+        }
+        </TransitionUnpack>
     }
 }
 
@@ -50,7 +98,7 @@ pub fn UserMenu() -> impl IntoView {
             login_button_content=move || view! { <UserIcon/> }
             let:user
         >
-            <LoggedInMenu username=user.username.clone()/>
+            <LoggedInMenu username=user.username.clone() is_admin=user.check_admin_role(AdminRole::Admin).is_ok()/>
         </LoginGuardButton>
     }
 }
@@ -58,6 +106,7 @@ pub fn UserMenu() -> impl IntoView {
 #[component]
 pub fn LoggedInMenu(
     username: String,
+    is_admin: bool,
 ) -> impl IntoView {
     let state = expect_context::<GlobalState>();
 
@@ -72,6 +121,11 @@ pub fn LoggedInMenu(
                 <li>
                     <a href=get_profile_path(&username) class="button-ghost-sm block w-full">{move_tr!("profile")}</a>
                 </li>
+                <Show when=move || is_admin>
+                    <li>
+                        <a href=REPORT_INBOX_ROUTE class="button-ghost-sm block w-full">{move_tr!("report-inbox")}</a>
+                    </li>
+                </Show>
                 <li>
                     <ActionForm action=state.logout_action attr:class="flex">
                         <input type="text" name="redirect_url" class="hidden" value=get_current_url()/>
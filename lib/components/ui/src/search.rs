@@ -14,16 +14,17 @@ use sphare_core_common::routes::{SEARCH_ROUTE, SEARCH_TAB_QUERY_PARAM};
 use sphare_core_common::unpack::{handle_additional_load, handle_initial_load};
 use sphare_core_content::search::{is_content_search_valid, SearchState};
 
-use sphare_iface_content::search::{search_comments, search_posts};
+use sphare_iface_content::search::{search_comments, search_posts, CreateSavedSearch};
 use sphare_iface_user::user::get_matching_user_header_vec;
 
 use sphare_cmp_base::comment::CommentMiniatureList;
 use sphare_cmp_base::post::PostListWithIndicators;
 use sphare_cmp_base::search::{SearchForm, SearchSpheres};
+use sphare_cmp_common::auth_widget::LoginGuardButton;
 use sphare_cmp_common::state::SphereState;
 use sphare_cmp_common::user::UserHeaderLink;
 use sphare_cmp_utils::icons::MagnifierIcon;
-use sphare_cmp_utils::unpack::TransitionUnpack;
+use sphare_cmp_utils::unpack::{ActionError, TransitionUnpack};
 use sphare_cmp_utils::view::ToView;
 use sphare_cmp_utils::widget::{EnumQueryTabs, NotFoundWidget};
 
@@ -208,6 +209,7 @@ pub fn SearchPosts() -> impl IntoView
             maxlength=Some(MAX_SEARCH_QUERY_LENGTH)
             input_error
         />
+        <SaveSearchButton/>
         { move || match (post_vec.read().is_empty(), search_state.search_input_debounced.get_untracked().is_empty()) {
             (true, true) => None,
             (true, false) => Some(Either::Left(view! { <NotFoundWidget message=move_tr!("search-no-post-found")/> })),
@@ -226,6 +228,53 @@ pub fn SearchPosts() -> impl IntoView
     }
 }
 
+/// Button to save the current post search, the user is then notified of new posts matching it
+#[component]
+pub fn SaveSearchButton() -> impl IntoView
+{
+    let search_state = expect_context::<SearchState>();
+    let sphere_state = use_context::<SphereState>();
+    let create_saved_search_action = ServerAction::<CreateSavedSearch>::new();
+
+    let input_error = is_content_search_valid(search_state.search_input_debounced);
+    let is_search_saved = move || matches!(
+        &*create_saved_search_action.value().read(),
+        Some(Ok(saved_search)) if saved_search.search_query == *search_state.search_input_debounced.read()
+    );
+
+    view! {
+        <div class="flex flex-col gap-1 self-center items-center">
+            <LoginGuardButton
+                login_button_class="button-secondary"
+                login_button_content=move || view! { {move_tr!("save-search")} }.into_any()
+                let:_user
+            >
+                <button
+                    class="button-secondary"
+                    disabled=move || {
+                        search_state.search_input_debounced.read().is_empty() ||
+                        input_error.read().is_some() ||
+                        create_saved_search_action.pending().get() ||
+                        is_search_saved()
+                    }
+                    on:click=move |_| {
+                        create_saved_search_action.dispatch(CreateSavedSearch {
+                            search_query: search_state.search_input_debounced.get_untracked(),
+                            sphere_name: sphere_state.map(|sphere_state| sphere_state.sphere_name.get_untracked()),
+                        });
+                    }
+                >
+                    {move_tr!("save-search")}
+                </button>
+            </LoginGuardButton>
+            <Show when=is_search_saved>
+                <div class="text-sm text-base-content/70">{move_tr!("search-saved")}</div>
+            </Show>
+            <ActionError action=create_saved_search_action.into()/>
+        </div>
+    }
+}
+
 #[component]
 pub fn SearchComments() -> impl IntoView
 {
@@ -6,21 +6,24 @@ use leptos_use::on_click_outside;
 
 use sphare_core_common::common::{Rule, SphereHeader};
 use sphare_core_common::errors::AppError;
-use sphare_core_common::routes::{ABOUT_SPHARE_ROUTE, CONTENT_POLICY_ROUTE, FAQ_ROUTE, GITHUB_REPO_URL, POPULAR_ROUTE, PRIVACY_POLICY_ROUTE, RULES_ROUTE, TERMS_AND_CONDITIONS_ROUTE};
+use sphare_core_common::routes::{get_sphere_page_path, ABOUT_SPHARE_ROUTE, CONTENT_POLICY_ROUTE, FAQ_ROUTE, GITHUB_REPO_URL, POPULAR_ROUTE, PRIVACY_POLICY_ROUTE, RULES_ROUTE, TERMS_AND_CONDITIONS_ROUTE};
 use sphare_core_common::traits::ToLocalizedStr;
 use sphare_core_content::search::SearchState;
 
+use sphare_iface_sphere::rule::{has_accepted_rules, AcceptRules};
 use sphare_iface_sphere::sphere::{get_popular_sphere_headers, get_subscribed_sphere_headers};
+use sphare_iface_sphere::wiki::get_sphere_page_vec;
 
 use sphare_cmp_base::filter::{AllCategoriesToggle, OnlyCategoriesToggle};
 use sphare_cmp_base::rule::{BaseRuleList, RuleList};
 use sphare_cmp_base::search::SearchSpheres;
 use sphare_cmp_base::sphere::SphereLinkList;
 use sphare_cmp_base::sphere_category::SphereCategoryCollapseWithFilter;
+use sphare_cmp_common::auth_widget::LoginGuardButton;
 use sphare_cmp_common::state::{GlobalState, SphereState};
 use sphare_cmp_utils::icons::{GithubIcon, HomeIcon, PopularIcon};
-use sphare_cmp_utils::unpack::TransitionUnpack;
-use sphare_cmp_utils::widget::{Badge, TitleCollapse};
+use sphare_cmp_utils::unpack::{ActionError, TransitionUnpack};
+use sphare_cmp_utils::widget::{Badge, ContentBody, TitleCollapse};
 
 /// Component to display a collapsable list of sphere links
 #[component]
@@ -234,6 +237,11 @@ pub fn SphereSidebar() -> impl IntoView {
                 <div class="text-xl font-semibold text-center text-wrap wrap-anywhere">{sphere_state.sphere_name}</div>
                 <TransitionUnpack resource=sphere_state.sphere_with_user_info_resource let:sphere_with_user_info>
                     <div class="pl-4 whitespace-pre-wrap">{sphere_with_user_info.sphere.description.clone()}</div>
+                    {
+                        sphere_with_user_info.sphere.sidebar_html.clone().map(|sidebar_html| view! {
+                            <ContentBody body=sidebar_html is_markdown=true attr:class="pl-4"/>
+                        })
+                    }
                 </TransitionUnpack>
             </div>
             <div class="border-b border-primary/80"/>
@@ -241,6 +249,8 @@ pub fn SphereSidebar() -> impl IntoView {
             <div class="border-b border-primary/80"/>
             <SphereRuleList rule_resource=sphere_state.sphere_rules_resource/>
             <div class="border-b border-primary/80"/>
+            <SpherePageList/>
+            <div class="border-b border-primary/80"/>
             <ModeratorList/>
         </div>
         <Show when=state.show_right_sidebar>
@@ -259,10 +269,57 @@ fn SphereRuleList(
             <TransitionUnpack resource=rule_resource let:rule_vec>
                 <RuleList rule_vec=rule_vec.clone()/>
             </TransitionUnpack>
+            <SphereRuleAcceptance/>
         </TitleCollapse>
     }
 }
 
+/// Button to accept the rules of a sphere requiring it before posting
+#[component]
+fn SphereRuleAcceptance() -> impl IntoView {
+    let sphere_state = expect_context::<SphereState>();
+    let accept_rules_action = ServerAction::<AcceptRules>::new();
+    let has_accepted_resource = Resource::new(
+        move || (sphere_state.sphere_name.get(), accept_rules_action.version().get()),
+        move |(sphere_name, _)| has_accepted_rules(sphere_name),
+    );
+
+    view! {
+        <TransitionUnpack resource=sphere_state.sphere_with_user_info_resource let:sphere_with_user_info>
+        {
+            sphere_with_user_info.sphere.require_rule_acceptance.then(|| view! {
+                <div class="flex flex-col items-center gap-1 pt-2">
+                    <TransitionUnpack resource=has_accepted_resource let:has_accepted>
+                    {
+                        match *has_accepted {
+                            true => view! { <div class="text-sm">{move_tr!("rules-accepted")}</div> }.into_any(),
+                            false => view! {
+                                <LoginGuardButton
+                                    login_button_class="button-primary"
+                                    login_button_content=move || view! { {move_tr!("accept-rules")} }.into_any()
+                                    let:_user
+                                >
+                                    <button
+                                        class="button-primary"
+                                        on:click=move |_| {
+                                            accept_rules_action.dispatch(AcceptRules { sphere_name: sphere_state.sphere_name.get_untracked() });
+                                        }
+                                    >
+                                        {move_tr!("accept-rules")}
+                                    </button>
+                                </LoginGuardButton>
+                            }.into_any(),
+                        }
+                    }
+                    </TransitionUnpack>
+                    <ActionError action=accept_rules_action.into()/>
+                </div>
+            })
+        }
+        </TransitionUnpack>
+    }
+}
+
 /// List of categories for a sphere
 #[component]
 pub fn SphereCategoryList() -> impl IntoView {
@@ -287,6 +344,43 @@ pub fn SphereCategoryList() -> impl IntoView {
     }
 }
 
+/// List of links to the wiki pages of a sphere
+#[component]
+pub fn SpherePageList() -> impl IntoView {
+    let sphere_state = expect_context::<SphereState>();
+    let sphere_page_resource = Resource::new(
+        move || sphere_state.sphere_name.get(),
+        get_sphere_page_vec,
+    );
+    view! {
+        <TitleCollapse title=move_tr!("wiki")>
+            <TransitionUnpack resource=sphere_page_resource let:sphere_page_vec>
+            {
+                match sphere_page_vec.is_empty() {
+                    true => view! { <div class="pl-4 text-sm">{move_tr!("no-wiki-page")}</div> }.into_any(),
+                    false => view! {
+                        <ul class="flex flex-col gap-1 pl-4">
+                        {
+                            sphere_page_vec.iter().map(|sphere_page| view! {
+                                <li>
+                                    <a
+                                        href=get_sphere_page_path(&sphere_state.sphere_name.get_untracked(), &sphere_page.slug)
+                                        class="link text-primary"
+                                    >
+                                        {sphere_page.title.clone()}
+                                    </a>
+                                </li>
+                            }).collect_view()
+                        }
+                        </ul>
+                    }.into_any(),
+                }
+            }
+            </TransitionUnpack>
+        </TitleCollapse>
+    }
+}
+
 /// List of moderators for a sphere
 #[component]
 pub fn ModeratorList() -> impl IntoView {
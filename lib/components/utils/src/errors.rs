@@ -13,8 +13,8 @@ pub fn AppErrorIcon(
     match app_error {
         AppError::AuthenticationError(_) => view! { <AuthErrorIcon/> }.into_any(),
         AppError::NotAuthenticated => view! { <AuthErrorIcon/> }.into_any(),
-        AppError::InsufficientPrivileges => view! { <NotAuthorizedIcon/> }.into_any(),
-        AppError::SphereBanUntil(_) | AppError::PermanentSphereBan | AppError::GlobalBanUntil(_) | AppError::PermanentGlobalBan => view! { <BannedIcon/> }.into_any(),
+        AppError::InsufficientPrivileges | AppError::RulesNotAccepted | AppError::SphereArchived | AppError::AccountTooNew(_) | AppError::InsufficientKarma(_) | AppError::PostTypeNotAllowed | AppError::NsfwNotConfirmed | AppError::RateLimited(_) => view! { <NotAuthorizedIcon/> }.into_any(),
+        AppError::SphereBanUntil(_) | AppError::PermanentSphereBan | AppError::GlobalBanUntil(_) | AppError::PermanentGlobalBan | AppError::SphereMute => view! { <BannedIcon/> }.into_any(),
         AppError::CommunicationError(error) => match error {
             ServerFnErrorErr::Args(_) | ServerFnErrorErr::MissingArg(_) => view! { <InvalidRequestIcon/> }.into_any(),
             ServerFnErrorErr::Registration(_) | ServerFnErrorErr::Request(_) | ServerFnErrorErr::Response(_) => view! { <NetworkErrorIcon/> }.into_any(),
@@ -24,6 +24,8 @@ pub fn AppErrorIcon(
         AppError::InternalServerError(_) => view! { <InternalErrorIcon/> }.into_any(),
         AppError::NotFound => view! { <NotFoundIcon/> }.into_any(),
         AppError::PayloadTooLarge(_) => view! { <TooHeavyIcon/> }.into_any(),
+        AppError::DuplicateLink(_) | AppError::SphereNameTaken | AppError::CategoryInUse | AppError::ReservedName | AppError::UsernameTaken | AppError::Conflict |
+        AppError::TitleTooShort(_) | AppError::TitleTooLong(_) | AppError::TitleFormatMismatch(_) | AppError::CategoryRequired | AppError::ValidationError(_) => view! { <InvalidRequestIcon/> }.into_any(),
     }
 }
 
@@ -33,7 +35,7 @@ pub fn ErrorDisplay(
     error: AppError
 ) -> impl IntoView {
     let error_string = error.to_string();
-    let status_code =  error.status_code().as_u16();
+    let status_code =  error.http_status().as_u16();
     let user_message = error.user_message();
 
     log::error!("Caught error, status_code: {status_code}, error message: {error_string}");
@@ -54,7 +56,7 @@ pub fn ErrorDetail(
     error: AppError
 ) -> impl IntoView {
     let error_string = error.to_string();
-    let status_code = error.status_code().as_u16();
+    let status_code = error.http_status().as_u16();
     let error_detail = error.error_detail();
 
     log::error!("Caught error, status_code: {status_code}, error message: {error_string}");
@@ -98,7 +100,7 @@ pub fn ErrorTemplate(
         let response = use_context::<ResponseOptions>();
         if let Some(response) = response {
             let status_code = match errors.first() {
-                Some(error) => error.status_code(),
+                Some(error) => error.http_status(),
                 None => StatusCode::INTERNAL_SERVER_ERROR,
             };
             response.set_status(status_code);
@@ -85,3 +85,17 @@ pub fn LabeledSignalCheckbox(
         </div>
     }
 }
+
+/// Hidden input with a key generated once per form, allowing the server to process repeated submissions of the form only once
+#[component]
+pub fn IdempotencyKeyInput(
+    /// Name of the input in the form that contains this component, must correspond to the parameter of the associated server function
+    name: &'static str,
+) -> impl IntoView {
+    let idempotency_key = RwSignal::new(String::default());
+    // Generated in an effect so that the key is only created in the browser
+    Effect::new(move || idempotency_key.set(chrono::Utc::now().timestamp_micros().to_string()));
+    view! {
+        <input type="hidden" name=name prop:value=idempotency_key/>
+    }
+}
@@ -2,6 +2,13 @@ use leptos::prelude::*;
 
 use sphare_core_common::constants::{LOGO_ICON_PATH, POPULAR_ICON_PATH};
 
+#[component]
+pub fn AcceptedAnswerIcon(#[prop(default = "content-toolbar-icon-size")] class: &'static str) -> impl IntoView {
+    view! {
+        <img src="/svg/toolbar/star.svg" class=class/>
+    }
+}
+
 #[component]
 pub fn AddCommentIcon(#[prop(default = "content-toolbar-icon-size")] class: &'static str) -> impl IntoView {
     view! {
@@ -137,6 +144,13 @@ pub fn FiltersIcon(#[prop(default = "filter-icon-size")] class: &'static str) ->
     }
 }
 
+#[component]
+pub fn FlagIcon(#[prop(default = "content-toolbar-icon-size")] class: &'static str) -> impl IntoView {
+    view! {
+        <img src="/svg/toolbar/flag.svg" class=class/>
+    }
+}
+
 #[component]
 pub fn FlameIcon(#[prop(default = "filter-icon-size")] class: &'static str) -> impl IntoView {
     view! {
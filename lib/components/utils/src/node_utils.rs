@@ -26,4 +26,11 @@ pub fn set_checkbox(is_checked: bool, input_ref: NodeRef<html::Input>) {
     if let Some(input_ref) = input_ref.get() {
         input_ref.set_checked(is_checked);
     }
-}
\ No newline at end of file
+}
+/// Returns true if the focused element of the document accepts text input, in which case keyboard shortcuts should be ignored.
+pub fn is_editable_element_focused() -> bool {
+    document().active_element().is_some_and(|element| {
+        matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT") ||
+            element.dyn_ref::<HtmlElement>().is_some_and(|element| element.is_content_editable())
+    })
+}
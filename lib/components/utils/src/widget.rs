@@ -6,7 +6,7 @@ use const_format::concatcp;
 use leptos::html;
 use leptos::prelude::codee::{Decoder, Encoder};
 use leptos::prelude::*;
-use leptos_fluent::move_tr;
+use leptos_fluent::{move_tr, tr};
 use leptos_router::components::Form;
 use leptos_router::hooks::use_query_map;
 use leptos_use::BreakpointsTailwind::Lg;
@@ -17,7 +17,7 @@ use strum::IntoEnumIterator;
 use leptos_use::on_click_outside;
 
 use sphare_core_common::errors::AppError;
-use sphare_core_common::time::get_elapsed_time_string;
+use sphare_core_common::time::{get_duration_string, get_elapsed_time_string};
 use sphare_core_common::traits::ToLocalizedStr;
 use crate::errors::ErrorDisplay;
 use crate::icons::{ArrowUpIcon, ClockIcon, CommentIcon, DotMenuIcon, EditTimeIcon, HelpIcon, LoadingIcon, MaximizeIcon, MinimizeIcon, ModeratorIcon, NotFoundIcon, NsfwIcon, PinnedIcon, RefreshIcon, ScoreIcon, ShareIcon, SphereIcon, SpoilerIcon};
@@ -418,16 +418,21 @@ pub fn TimeSinceWidget(
     }.into_any()
 }
 
-/// Component to display the edit time of a post or comment
+/// Component to display the edit time of a post or comment, with the number of edits in a tooltip
 #[component]
 pub fn TimeSinceEditWidget(
     #[prop(into)]
-    edit_timestamp: Signal<Option<chrono::DateTime<chrono::Utc>>>
+    edit_timestamp: Signal<Option<chrono::DateTime<chrono::Utc>>>,
+    #[prop(into)]
+    edit_count: Signal<i32>,
 ) -> impl IntoView {
     let use_fullname = use_breakpoints(breakpoints_tailwind()).ge(Lg);
     view! {
         <Show when=move || edit_timestamp.read().is_some()>
-            <div class="flex gap-1.5 items-center text-xs lg:text-sm px-1">
+            <div
+                class="flex gap-1.5 items-center text-xs lg:text-sm px-1 tooltip"
+                data-tip=move_tr!("edited-times", {"count" => edit_count.get()})
+            >
                 <EditTimeIcon/>
                 {
                     move || get_elapsed_time_string(edit_timestamp.get().unwrap(), use_fullname.get())
@@ -437,6 +442,22 @@ pub fn TimeSinceEditWidget(
     }
 }
 
+/// Component to display the join date of a user and the age of their account, formatted like `TimeSinceWidget`
+#[component]
+pub fn AccountAgeWidget(
+    timestamp: chrono::DateTime<chrono::Utc>,
+) -> impl IntoView {
+    let account_age = chrono::Utc::now().signed_duration_since(timestamp);
+    view! {
+        <div class="flex gap-1.5 items-center text-sm px-1">
+            <ClockIcon/>
+            {move_tr!("joined")}
+            <span>{timestamp.format("%Y-%m-%d").to_string()}</span>
+            <span>"("{get_duration_string(account_age, true)}")"</span>
+        </div>
+    }
+}
+
 /// Button to share content that copies the input `link` to the clipboard
 #[component]
 pub fn ShareButton(
@@ -788,16 +809,50 @@ pub fn NotFoundWidget(
     }
 }
 
-/// Component to display a loading indicator or error depending on the input signals
+/// Load state of a list to announce to screen readers
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum LoadAnnouncement {
+    #[default]
+    None,
+    Loading,
+    Loaded(usize),
+    Error(AppError),
+}
+
+/// Component to display a loading indicator or error depending on the input signals.
+/// The load state is also announced to screen readers through a live region.
 #[component]
 pub fn LoadIndicators(
     #[prop(into)]
     is_loading: Signal<bool>,
     #[prop(into)]
     load_error: Signal<Option<AppError>>,
+    /// number of loaded items, the ones added by a load are announced once it is done
+    #[prop(optional, into)]
+    loaded_count: Option<Signal<usize>>,
     #[prop(default = "w-full min-h-9 lg:min-h-17")]
     load_div_class: &'static str,
 ) -> impl IntoView {
+    let announcement = RwSignal::new(LoadAnnouncement::None);
+    // number of items before the ongoing load, items removed while loading (e.g. when the list is reset) are not counted
+    let count_before_load = StoredValue::new(None::<usize>);
+    // only update the live region on the client to avoid hydration mismatches
+    Effect::new(move || {
+        let is_loading = is_loading.get();
+        let loaded_count = loaded_count.map(|loaded_count| loaded_count.get());
+        if is_loading {
+            count_before_load.update_value(|count_before_load| *count_before_load = count_before_load.iter().copied().chain(loaded_count).min());
+        }
+        announcement.set(get_load_announcement(
+            is_loading,
+            load_error.get(),
+            loaded_count,
+            count_before_load.get_value().unwrap_or_default(),
+        ));
+        if !is_loading {
+            count_before_load.set_value(None);
+        }
+    });
     view! {
         <Show when=move || load_error.read().is_some()>
         {
@@ -814,6 +869,33 @@ pub fn LoadIndicators(
                 <LoadingIcon/>
             </Show>
         </div>
+        <div class="sr-only" role="status" aria-live="polite">
+        {
+            move || match announcement.get() {
+                LoadAnnouncement::None => String::new(),
+                LoadAnnouncement::Loading => tr!("loading-more-content"),
+                LoadAnnouncement::Loaded(count) => tr!("content-loaded", {"count" => count}),
+                LoadAnnouncement::Error(error) => error.user_message().get(),
+            }
+        }
+        </div>
+    }
+}
+
+/// Returns the load state to announce given the loading and error states and the optional number of loaded items.
+/// Only the items loaded in addition to the `count_before_load` previous ones are announced.
+/// Errors take precedence over the loading state.
+pub fn get_load_announcement(
+    is_loading: bool,
+    load_error: Option<AppError>,
+    loaded_count: Option<usize>,
+    count_before_load: usize,
+) -> LoadAnnouncement {
+    match (load_error, is_loading, loaded_count) {
+        (Some(error), _, _) => LoadAnnouncement::Error(error),
+        (None, true, _) => LoadAnnouncement::Loading,
+        (None, false, Some(loaded_count)) => LoadAnnouncement::Loaded(loaded_count.saturating_sub(count_before_load)),
+        (None, false, None) => LoadAnnouncement::None,
     }
 }
 
@@ -841,4 +923,57 @@ pub fn BannerContent(
             </div>
         </div>
     }.into_any()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos::prelude::*;
+
+    use sphare_core_common::errors::AppError;
+
+    use crate::widget::{get_load_announcement, LoadAnnouncement};
+
+    #[test]
+    fn test_get_load_announcement() {
+        let owner = Owner::new();
+        owner.set();
+
+        let is_loading = RwSignal::new(false);
+        let load_error = RwSignal::new(None::<AppError>);
+        let loaded_count = RwSignal::new(None::<usize>);
+        let count_before_load = RwSignal::new(0);
+        let announcement = Memo::new(move |_| get_load_announcement(is_loading.get(), load_error.get(), loaded_count.get(), count_before_load.get()));
+
+        assert_eq!(announcement.get(), LoadAnnouncement::None);
+
+        is_loading.set(true);
+        assert_eq!(announcement.get(), LoadAnnouncement::Loading);
+
+        loaded_count.set(Some(20));
+        assert_eq!(announcement.get(), LoadAnnouncement::Loading);
+
+        is_loading.set(false);
+        assert_eq!(announcement.get(), LoadAnnouncement::Loaded(20));
+
+        load_error.set(Some(AppError::NotFound));
+        assert_eq!(announcement.get(), LoadAnnouncement::Error(AppError::NotFound));
+
+        is_loading.set(true);
+        assert_eq!(announcement.get(), LoadAnnouncement::Error(AppError::NotFound));
+
+        load_error.set(None);
+        assert_eq!(announcement.get(), LoadAnnouncement::Loading);
+
+        // only the additionally loaded items are announced
+        is_loading.set(false);
+        count_before_load.set(20);
+        loaded_count.set(Some(35));
+        assert_eq!(announcement.get(), LoadAnnouncement::Loaded(15));
+
+        loaded_count.set(Some(10));
+        assert_eq!(announcement.get(), LoadAnnouncement::Loaded(0));
+
+        loaded_count.set(None);
+        assert_eq!(announcement.get(), LoadAnnouncement::None);
+    }
+}
@@ -2,9 +2,9 @@ use const_format::formatcp;
 use url::Url;
 use validator::ValidationError;
 
-use crate::constants::{MAX_SATELLITE_NAME_LENGTH, MAX_SPHERE_NAME_LENGTH, MAX_TITLE_LENGTH, MAX_USERNAME_LENGTH};
+use crate::constants::{MAX_SATELLITE_NAME_LENGTH, MAX_SPHERE_NAME_LENGTH, MAX_TITLE_LENGTH, MAX_USERNAME_LENGTH, MAX_WIKI_SLUG_LENGTH, RESERVED_NAMES};
 use crate::errors::AppError;
-use crate::routes::get_app_origin;
+use crate::routes::{get_app_origin, TOP_LEVEL_ROUTES};
 
 /// # Returns whether the given string `input` is shorter or equal than the given max length and, if not `is_empty_ok` than it's not empty
 ///
@@ -13,8 +13,8 @@ use crate::routes::get_app_origin;
 /// use sphare_core_common::errors::AppError;
 ///
 /// assert!(check_string_length("hello", "input", 5, false).is_ok());
-/// assert_eq!(check_string_length("hello", "input", 4, false), Err(AppError::new("input exceeds the maximum length: 4.")));
-/// assert_eq!(check_string_length("", "input", 4, false), Err(AppError::new("input cannot be empty.")));
+/// assert_eq!(check_string_length("hello", "input", 4, false), Err(AppError::ValidationError(String::from("input exceeds the maximum length: 4."))));
+/// assert_eq!(check_string_length("", "input", 4, false), Err(AppError::ValidationError(String::from("input cannot be empty."))));
 /// ```
 pub fn check_string_length(
     input: &str,
@@ -23,8 +23,8 @@ pub fn check_string_length(
     is_empty_ok: bool,
 ) -> Result<(), AppError> {
     match (input.len() > max_length, !is_empty_ok && input.is_empty()) {
-        (true, _) => Err(AppError::new(format!("{input_name} exceeds the maximum length: {max_length}."))),
-        (_, true) => Err(AppError::new(format!("{input_name} cannot be empty."))),
+        (true, _) => Err(AppError::ValidationError(format!("{input_name} exceeds the maximum length: {max_length}."))),
+        (_, true) => Err(AppError::ValidationError(format!("{input_name} cannot be empty."))),
         (false, false) => Ok(()),
     }
 }
@@ -106,6 +106,34 @@ pub fn check_satellite_name(name: &str) -> Result<(), ValidationError> {
     }
 }
 
+/// # Returns whether a wiki page slug is valid.
+///
+/// # Valid slugs contain only lowercase ascii alphanumeric characters and '-' and have a maximum length of `MAX_WIKI_SLUG_LENGTH`
+///
+/// ```
+/// use sphare_core_common::checks::{check_wiki_slug};
+/// use sphare_core_common::constants::{MAX_WIKI_SLUG_LENGTH};
+///
+/// assert!(check_wiki_slug("getting-started-2").is_ok());
+/// assert!(check_wiki_slug("").is_err());
+/// assert!(check_wiki_slug("Getting-Started").is_err());
+/// assert!(check_wiki_slug("getting_started").is_err());
+/// assert!(check_wiki_slug("getting/started").is_err());
+/// assert!(check_wiki_slug(&"a".repeat(MAX_WIKI_SLUG_LENGTH)).is_ok());
+/// assert!(check_wiki_slug(&"a".repeat(MAX_WIKI_SLUG_LENGTH + 1)).is_err());
+/// ```
+pub fn check_wiki_slug(slug: &str) -> Result<(), ValidationError> {
+    if slug.is_empty() {
+        Err(ValidationError::new("Wiki page slug cannot be empty."))
+    } else if !slug.chars().all(move |c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+        Err(ValidationError::new("Wiki page slug can only contain lowercase alphanumeric characters and dashes."))
+    } else if slug.len() > MAX_WIKI_SLUG_LENGTH {
+        Err(ValidationError::new(formatcp!("Wiki page slug cannot exceed {MAX_WIKI_SLUG_LENGTH} characters.")))
+    } else {
+        Ok(())
+    }
+}
+
 /// # Returns whether a post's title is valid.
 ///
 /// ```
@@ -151,12 +179,48 @@ pub fn check_post_title(title: &str) -> Result<(), ValidationError> {
 /// ```
 pub fn check_username(name: &str, is_empty_ok: bool) -> Result<(), AppError> {
     if !name.chars().all(move |c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
-        Err(AppError::new("Username can only contain alphanumeric characters, dashes and underscores."))
+        Err(AppError::ValidationError(String::from("Username can only contain alphanumeric characters, dashes and underscores.")))
     } else {
         check_string_length(name, "Username", MAX_USERNAME_LENGTH, is_empty_ok)
     }
 }
 
+/// # Returns an error if `name` is one of the `RESERVED_NAMES` or the name of one of the `TOP_LEVEL_ROUTES`,
+/// ignoring case and treating dashes and underscores as equivalent.
+///
+/// ```
+/// use sphare_core_common::checks::{check_reserved_name};
+/// use sphare_core_common::errors::AppError;
+/// use sphare_core_common::routes::{POST_ROUTE_PREFIX, SPHERE_ROUTE_PREFIX, USER_ROUTE_PREFIX, WIKI_ROUTE_PREFIX};
+///
+/// assert_eq!(check_reserved_name("admin"), Err(AppError::ReservedName));
+/// assert_eq!(check_reserved_name("Admin"), Err(AppError::ReservedName));
+/// assert_eq!(check_reserved_name("API"), Err(AppError::ReservedName));
+/// assert_eq!(check_reserved_name(&POST_ROUTE_PREFIX[1..]), Err(AppError::ReservedName));
+/// assert_eq!(check_reserved_name(&SPHERE_ROUTE_PREFIX[1..]), Err(AppError::ReservedName));
+/// assert_eq!(check_reserved_name(&USER_ROUTE_PREFIX[1..]), Err(AppError::ReservedName));
+/// assert_eq!(check_reserved_name(&WIKI_ROUTE_PREFIX[1..]), Err(AppError::ReservedName));
+/// assert_eq!(check_reserved_name("about-sphare"), Err(AppError::ReservedName));
+/// assert_eq!(check_reserved_name("Terms-And_Conditions"), Err(AppError::ReservedName));
+/// assert!(check_reserved_name("admins").is_ok());
+/// assert!(check_reserved_name("my_posts").is_ok());
+/// ```
+pub fn check_reserved_name(name: &str) -> Result<(), AppError> {
+    let name = normalize_reserved_name(name);
+    let is_reserved = RESERVED_NAMES.iter().copied()
+        .chain(TOP_LEVEL_ROUTES.iter().map(|route| route.trim_start_matches('/')))
+        .any(|reserved_name| normalize_reserved_name(reserved_name) == name);
+    match is_reserved {
+        true => Err(AppError::ReservedName),
+        false => Ok(()),
+    }
+}
+
+/// Returns `name` in lowercase with dashes replaced by underscores, so that names which look alike are compared as equal
+fn normalize_reserved_name(name: &str) -> String {
+    name.to_ascii_lowercase().replace('-', "_")
+}
+
 pub fn validate_redirect_url(redirect_url: &str) -> Result<(), AppError> {
     let app_origin_str = get_app_origin()?;
     let app_origin = Url::parse(&app_origin_str).map_err(AppError::new)?;
@@ -1,6 +1,26 @@
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumIter};
 
+/// Minimum contrast ratio between a text and its background, following the WCAG AA level for normal text
+pub const MIN_CONTRAST_RATIO: f64 = 4.5;
+/// Rgb components of the tailwind colors used in `Color::to_bg_class`
+const TAILWIND_RGB_COLORS: [(&str, (u8, u8, u8)); 14] = [
+    ("blue-600", (37, 99, 235)),
+    ("purple-600", (147, 51, 234)),
+    ("pink-400", (244, 114, 182)),
+    ("red-800", (153, 27, 27)),
+    ("red-600", (220, 38, 38)),
+    ("orange-600", (234, 88, 12)),
+    ("yellow-600", (202, 138, 4)),
+    ("green-600", (22, 163, 74)),
+    ("cyan-600", (8, 145, 178)),
+    ("gray-400", (156, 163, 175)),
+    ("gray-600", (75, 85, 99)),
+    ("amber-800", (146, 64, 14)),
+    ("black", (0, 0, 0)),
+    ("white", (255, 255, 255)),
+];
+
 #[repr(i16)]
 #[derive(Clone, Copy, Debug, Display, EnumIter, Eq, Hash, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(feature = "ssr", derive(sqlx::Type))]
@@ -52,11 +72,11 @@ impl Color {
             Color::Purple => "bg-purple-600 text-white font-semibold",
             Color::Pink => "bg-pink-400 text-black font-semibold",
             Color::Bordeaux => "bg-red-800 text-white font-semibold",
-            Color::Red => "bg-red-600 text-black font-semibold",
+            Color::Red => "bg-red-600 text-white font-semibold",
             Color::Orange => "bg-orange-600 text-black font-semibold",
             Color::Yellow => "bg-yellow-600 text-black font-semibold",
             Color::Green => "bg-green-600 text-black font-semibold",
-            Color::Cyan => "bg-cyan-600 text-white font-semibold",
+            Color::Cyan => "bg-cyan-600 text-black font-semibold",
             Color::LightGray => "bg-gray-400 text-black font-semibold",
             Color::DarkGray => "bg-gray-600 text-white font-semibold",
             Color::Brown => "bg-amber-800 text-white font-semibold",
@@ -64,13 +84,102 @@ impl Color {
             Color::White => "bg-white text-black font-semibold",
         }
     }
+
+    /// Returns the rgb components of the background color of `to_bg_class`. Returns `None` for `Color::None`.
+    pub fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        get_class_rgb(self.to_bg_class(), "bg-")
+    }
+
+    /// Returns the WCAG contrast ratio between this color and `background`, ranging from 1 to 21.
+    /// Returns `None` when one of the colors is `Color::None`.
+    pub fn contrast_ratio_against(&self, background: Color) -> Option<f64> {
+        Some(get_contrast_ratio(self.to_rgb()?, background.to_rgb()?))
+    }
+
+    /// Returns whether the text of badges with this background color is readable, i.e. whether the text and background colors
+    /// of `to_bg_class` have a contrast ratio of at least `MIN_CONTRAST_RATIO`.
+    /// `Color::None` uses the theme's colors and is always readable.
+    pub fn has_readable_text(&self) -> bool {
+        has_readable_text_classes(self.to_bg_class())
+    }
+
+    /// Returns the css value of the color, based on the variables of the theme. Returns `None` for `Color::None`.
+    pub fn to_css_value(&self) -> Option<&'static str> {
+        match self {
+            Color::None => None,
+            Color::Blue => Some("var(--color-blue-600)"),
+            Color::Purple => Some("var(--color-purple-600)"),
+            Color::Pink => Some("var(--color-pink-400)"),
+            Color::Bordeaux => Some("var(--color-red-800)"),
+            Color::Red => Some("var(--color-red-600)"),
+            Color::Orange => Some("var(--color-orange-600)"),
+            Color::Yellow => Some("var(--color-yellow-600)"),
+            Color::Green => Some("var(--color-green-600)"),
+            Color::Cyan => Some("var(--color-cyan-600)"),
+            Color::LightGray => Some("var(--color-gray-400)"),
+            Color::DarkGray => Some("var(--color-gray-600)"),
+            Color::Brown => Some("var(--color-amber-800)"),
+            Color::Black => Some("var(--color-black)"),
+            Color::White => Some("var(--color-white)"),
+        }
+    }
+}
+
+/// Returns the rgb components of the color of the first class of `classes` with the given `prefix`, e.g. `bg-` or `text-`.
+/// Returns `None` if there is no such class or if its color is not in `TAILWIND_RGB_COLORS`.
+fn get_class_rgb(classes: &str, prefix: &str) -> Option<(u8, u8, u8)> {
+    let color_name = classes.split_whitespace().find_map(|class| class.strip_prefix(prefix))?;
+    TAILWIND_RGB_COLORS.iter().find(|(name, _)| *name == color_name).map(|(_, rgb)| *rgb)
+}
+
+/// Returns the relative luminance of the `rgb` color as defined by the WCAG
+fn get_relative_luminance((red, green, blue): (u8, u8, u8)) -> f64 {
+    let to_linear = |component: u8| {
+        let component = component as f64 / 255.0;
+        match component <= 0.04045 {
+            true => component / 12.92,
+            false => ((component + 0.055) / 1.055).powf(2.4),
+        }
+    };
+    0.2126 * to_linear(red) + 0.7152 * to_linear(green) + 0.0722 * to_linear(blue)
+}
+
+/// Returns the WCAG contrast ratio between the `rgb` and `other_rgb` colors, ranging from 1 to 21.
+fn get_contrast_ratio(rgb: (u8, u8, u8), other_rgb: (u8, u8, u8)) -> f64 {
+    let luminance = get_relative_luminance(rgb);
+    let other_luminance = get_relative_luminance(other_rgb);
+    let (lighter, darker) = match luminance > other_luminance {
+        true => (luminance, other_luminance),
+        false => (other_luminance, luminance),
+    };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Returns whether the text and background colors set by `classes` have a contrast ratio of at least `MIN_CONTRAST_RATIO`.
+/// Classes without text nor background color use the theme's colors and are readable, classes with only one of them
+/// or with colors missing from `TAILWIND_RGB_COLORS` cannot be checked and are considered unreadable.
+fn has_readable_text_classes(classes: &str) -> bool {
+    match (get_class_rgb(classes, "text-"), get_class_rgb(classes, "bg-")) {
+        (Some(text_rgb), Some(bg_rgb)) => get_contrast_ratio(text_rgb, bg_rgb) >= MIN_CONTRAST_RATIO,
+        (None, None) => true,
+        _ => false,
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::colors::Color;
+    use crate::colors::{has_readable_text_classes, Color, MIN_CONTRAST_RATIO};
     use strum::IntoEnumIterator;
 
+    fn assert_contrast_ratio(color: Color, background: Color, expected_ratio: f64) {
+        let contrast_ratio = color.contrast_ratio_against(background).expect("Should compute contrast ratio");
+        assert!(
+            (contrast_ratio - expected_ratio).abs() < 0.01,
+            "Contrast ratio of {color} against {background} should be {expected_ratio}, got {contrast_ratio}"
+        );
+        assert_eq!(background.contrast_ratio_against(color), Some(contrast_ratio));
+    }
+
     #[test]
     fn test_color_from_i16() {
         for color in Color::iter() {
@@ -79,4 +188,60 @@ mod tests {
         assert_eq!(Color::from(-2), Color::None);
         assert_eq!(Color::from(100), Color::None);
     }
+
+    #[test]
+    fn test_color_to_css_value() {
+        for color in Color::iter() {
+            match color {
+                Color::None => assert_eq!(color.to_css_value(), None),
+                _ => {
+                    let css_value = color.to_css_value().expect("Color should have a css value");
+                    let bg_class = color.to_bg_class().split_whitespace().next().expect("Color should have a background class");
+                    assert_eq!(css_value, format!("var(--color-{})", bg_class.trim_start_matches("bg-")));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_color_contrast_ratio_against() {
+        assert_contrast_ratio(Color::Black, Color::White, 21.0);
+        assert_contrast_ratio(Color::White, Color::White, 1.0);
+        assert_contrast_ratio(Color::White, Color::Blue, 5.17);
+        assert_contrast_ratio(Color::Black, Color::Pink, 7.93);
+        assert_contrast_ratio(Color::White, Color::DarkGray, 7.56);
+        assert_contrast_ratio(Color::White, Color::Cyan, 3.68);
+        assert_contrast_ratio(Color::Black, Color::Red, 4.35);
+
+        assert_eq!(Color::None.contrast_ratio_against(Color::White), None);
+        assert_eq!(Color::Black.contrast_ratio_against(Color::None), None);
+    }
+
+    #[test]
+    fn test_color_has_readable_text() {
+        for color in Color::iter() {
+            assert!(color.has_readable_text(), "Text on {color} should be readable");
+            if color != Color::None {
+                assert!(color.to_rgb().is_some(), "Background color of {color} should be known");
+            }
+        }
+        // white on cyan or black on red would fall below the threshold
+        assert!(Color::White.contrast_ratio_against(Color::Cyan).unwrap() < MIN_CONTRAST_RATIO);
+        assert!(Color::Black.contrast_ratio_against(Color::Red).unwrap() < MIN_CONTRAST_RATIO);
+        assert!(Color::Black.contrast_ratio_against(Color::Cyan).unwrap() >= MIN_CONTRAST_RATIO);
+        assert!(Color::White.contrast_ratio_against(Color::Red).unwrap() >= MIN_CONTRAST_RATIO);
+    }
+
+    #[test]
+    fn test_has_readable_text_classes() {
+        assert!(has_readable_text_classes("bg-cyan-600 text-black font-semibold"));
+        assert!(has_readable_text_classes("bg-red-600 text-white font-semibold"));
+        assert!(!has_readable_text_classes("bg-cyan-600 text-white font-semibold"));
+        assert!(!has_readable_text_classes("bg-red-600 text-black font-semibold"));
+        assert!(!has_readable_text_classes("bg-white text-white"));
+        assert!(has_readable_text_classes("border border-base-content/20 font-semibold"));
+        // colors that cannot be checked are not readable
+        assert!(!has_readable_text_classes("bg-blue-600 font-semibold"));
+        assert!(!has_readable_text_classes("bg-lime-300 text-black"));
+    }
 }
\ No newline at end of file
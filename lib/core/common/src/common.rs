@@ -1,6 +1,24 @@
+use std::str::FromStr;
+
+use leptos::prelude::Signal;
+use leptos_fluent::move_tr;
 use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
 
 use crate::colors::Color;
+use crate::constants::{BEST_POST_ORDER_BY_COLUMN, BEST_RATIO_POST_ORDER_BY_COLUMN, HOT_ORDER_BY_COLUMN, RECENT_ORDER_BY_COLUMN, TRENDING_ORDER_BY_COLUMN};
+use crate::traits::ToLocalizedStr;
+
+#[derive(Clone, Copy, Debug, Default, Display, EnumIter, EnumString, Eq, Hash, IntoStaticStr, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+pub enum PostSortType {
+    #[default]
+    Hot,
+    Trending,
+    Best,
+    BestRatio,
+    Recent,
+}
 
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
 #[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -32,6 +50,36 @@ pub struct Rule {
     pub delete_timestamp: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+impl PostSortType {
+    pub fn to_order_by_code(self) -> &'static str {
+        match self {
+            PostSortType::Hot => HOT_ORDER_BY_COLUMN,
+            PostSortType::Trending => TRENDING_ORDER_BY_COLUMN,
+            PostSortType::Best => BEST_POST_ORDER_BY_COLUMN,
+            PostSortType::BestRatio => BEST_RATIO_POST_ORDER_BY_COLUMN,
+            PostSortType::Recent => RECENT_ORDER_BY_COLUMN,
+        }
+    }
+}
+
+impl ToLocalizedStr for PostSortType {
+    fn to_localized_str(&self) -> Signal<String> {
+        match self {
+            PostSortType::Hot => move_tr!("hot"),
+            PostSortType::Trending => move_tr!("trending"),
+            PostSortType::Best => move_tr!("best"),
+            PostSortType::BestRatio => move_tr!("best-ratio"),
+            PostSortType::Recent => move_tr!("recent"),
+        }
+    }
+}
+
+impl From<String> for PostSortType {
+    fn from(value: String) -> PostSortType {
+        PostSortType::from_str(&value).unwrap_or_default()
+    }
+}
+
 impl SphereHeader {
     pub fn new(sphere_name: String, icon_url: Option<String>, is_nsfw: bool) -> Self {
         Self {
@@ -1,3 +1,5 @@
+use const_format::formatcp;
+
 pub const SITE_NAME: &str = "Sphare";
 
 pub const SECONDS_IN_MINUTE: i64 = 60;
@@ -15,17 +17,39 @@ pub const SECONDS_IN_YEAR: i64 = DAYS_IN_YEAR*SECONDS_IN_DAY;
 
 pub const SPOILER_TAG: &str = "||";
 pub const MULTI_LINE_SPOILER_TAG: &str = "|||";
+pub const INLINE_SPOILER_START_TAG: &str = ">!";
+pub const INLINE_SPOILER_END_TAG: &str = "!<";
+pub const ESCAPED_PIPE: &str = "\\|";
 
 
 pub const HOT_ORDER_BY_COLUMN: &str = "recommended_score";
 pub const TRENDING_ORDER_BY_COLUMN: &str = "trending_score";
 pub const BEST_ORDER_BY_COLUMN: &str = "score";
+// Posts with fewer votes than this are ranked after well-voted posts when sorting by best
+pub const BEST_SORT_MIN_VOTE_COUNT: i32 = 5;
+// score_minus holds the number of downvotes, so the vote count is score + 2*score_minus. Must match the vote floor of the best post indexes, checked by test_best_post_index_vote_floor.
+pub const BEST_POST_ORDER_BY_COLUMN: &str = formatcp!("(score + 2 * score_minus >= {BEST_SORT_MIN_VOTE_COUNT}) DESC, {BEST_ORDER_BY_COLUMN}");
+// Z-score of the 95% confidence level used to compute the Wilson score lower bound of the upvote ratio, in hundredths
+// so that the sql constants can be derived from it
+pub const WILSON_SCORE_Z_HUNDREDTHS: i32 = 196;
+pub const WILSON_SCORE_Z: f64 = WILSON_SCORE_Z_HUNDREDTHS as f64 / 100.0;
+// Wilson score lower bound with n = score + 2*score_minus votes, of which score + score_minus are upvotes.
+// The constants are WILSON_SCORE_Z, WILSON_SCORE_Z^2/2, WILSON_SCORE_Z^2/4 and WILSON_SCORE_Z^2, ties are broken by score.
+pub const BEST_RATIO_POST_ORDER_BY_COLUMN: &str = formatcp!(
+    "CASE WHEN score + 2 * score_minus > 0 THEN \
+    (((score + score_minus) + {z} * {z} / 20000.0) / (score + 2 * score_minus) - \
+    {z} / 100.0 * SQRT(((score + score_minus) * score_minus)::FLOAT8 / (score + 2 * score_minus) + {z} * {z} / 40000.0) / (score + 2 * score_minus)) / \
+    (1 + {z} * {z} / 10000.0 / (score + 2 * score_minus)) \
+    ELSE 0 END DESC, score",
+    z = WILSON_SCORE_Z_HUNDREDTHS,
+);
 pub const RECENT_ORDER_BY_COLUMN: &str = "create_timestamp";
 
 
 pub const SITE_ROOT: &str = "/";
 pub const IMAGE_TYPE: &str = "image/";
 pub const SCROLL_LOAD_THROTTLE_DELAY: f64 = 3000.0;
+pub const DRAFT_AUTOSAVE_DELAY: f64 = 2000.0;
 
 
 pub const LOGO_ICON_PATH: &str = "/svg/planet.svg";
@@ -34,23 +58,62 @@ pub const POPULAR_ICON_PATH: &str = "/svg/shooting_star.svg";
 
 pub const MAX_SPHERE_NAME_LENGTH: usize = 20;
 pub const MAX_SPHERE_DESCRIPTION_LENGTH: usize = 1000;
+pub const MAX_SPHERE_SIDEBAR_LENGTH: usize = 5000;
 pub const MAX_SATELLITE_NAME_LENGTH: usize = 50;
+pub const MAX_WIKI_SLUG_LENGTH: usize = 50;
 pub const MAX_USERNAME_LENGTH: usize = 30;
+pub const MAX_BIO_LENGTH: usize = 500;
+// Names that cannot be used for spheres and users to avoid impersonation and collisions with server routes,
+// in addition to the names of the app routes in `routes::TOP_LEVEL_ROUTES`
+pub const RESERVED_NAMES: &[&str] = &[
+    "admin", "administrator", "api", "sphare", "pkg", "svg", "healthz", "readyz", "metrics",
+];
+// Reactions that can be added to posts and comments
+pub const ALLOWED_REACTIONS: &[&str] = &[
+    "👍", "❤️", "😂", "🎉", "👀", "🤔", "😢", "🔥",
+];
 pub const MAX_TITLE_LENGTH: u64 = 250;
 pub const MAX_CONTENT_LENGTH: u64 = 20000;
 pub const MAX_LINK_LENGTH: u64 = 500;
+pub const MAX_ALT_TEXT_LENGTH: u64 = 500;
+pub const MAX_TITLE_REGEX_LENGTH: usize = 200;
+pub const MAX_MIN_ACCOUNT_AGE_DAYS: i32 = 365;
 pub const MAX_MOD_MESSAGE_LENGTH: usize = 500;
+pub const MAX_MOD_REASON_TITLE_LENGTH: usize = 100;
+pub const MAX_AUTOMOD_PATTERN_LENGTH: usize = 100;
+pub const MAX_REPORT_REASON_LENGTH: usize = 500;
 pub const MAX_SEARCH_QUERY_LENGTH: usize = 200;
+pub const MAX_SAVED_SEARCHES: usize = 20;
+pub const SPHERE_NAME_SIMILARITY_THRESHOLD: f32 = 0.4;
 pub const MAX_CATEGORY_NAME_LENGTH: usize = 50;
 pub const MAX_CATEGORY_DESCRIPTION_LENGTH: usize = 500;
+pub const MIN_POLL_OPTIONS: usize = 2;
+pub const MAX_POLL_OPTIONS: usize = 10;
+pub const MAX_POLL_OPTION_LENGTH: usize = 100;
+pub const MAX_POLL_DURATION_HOURS: u32 = 24 * 30;
+pub const MAX_POST_SCHEDULE_HOURS: u32 = 24 * 30;
+pub const MAX_ANNOUNCEMENT_HOURS: u32 = 24 * 365;
+pub const MAX_BAN_DURATION_HOURS: u32 = 24 * 365 * 10;
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 64;
 
 
 pub const SPHERE_NAME_PARAM: &str = "sphere_name";
 pub const IMAGE_FILE_PARAM: &str = "image";
+pub const POST_ID_PARAM: &str = "post_id";
+pub const ALT_TEXT_PARAM: &str = "alt_text";
 
 
 pub const USER_FETCH_LIMIT: i64 = 100;
+pub const MENTION_SUGGESTION_LIMIT: i64 = 5;
+pub const SIMILAR_POST_LIMIT: i64 = 5;
 pub const SPHERE_FETCH_LIMIT: usize = 100;
 pub const SPHERE_HEADER_FETCH_LIMIT: usize = 10;
+pub const TRENDING_SPHERE_DAYS: i64 = 7;
+pub const USERNAME_CHANGE_COOLDOWN_DAYS: i64 = 30;
+pub const MAX_POST_IMAGES: usize = 20;
 pub const POST_BATCH_SIZE: i64 = 50;
-pub const COMMENT_BATCH_SIZE: i64 = 50;
\ No newline at end of file
+pub const COMMENT_BATCH_SIZE: i64 = 50;
+pub const SAVED_SEARCH_NOTIF_LIMIT: i64 = 10;
+pub const CHILD_COMMENT_BATCH_SIZE: i64 = 20;
+pub const MAX_COLLAPSED_COMMENTS: usize = 500;
+pub const POST_SCORE_UPDATE_BATCH_SIZE: i64 = 1000;
\ No newline at end of file
@@ -1,5 +1,8 @@
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use std::str::FromStr;
+    use std::time::Duration;
+
     use anyhow::Context;
     use leptos::prelude::use_context;
     use sqlx::{postgres::PgPoolOptions, PgPool};
@@ -7,22 +10,72 @@ pub mod ssr {
     use crate::errors::AppError;
 
     pub const DB_URL_ENV: &str = "DATABASE_URL";
+    pub const DB_MAX_CONNECTIONS_ENV: &str = "DB_MAX_CONNECTIONS";
+    // Deprecated name of DB_MAX_CONNECTIONS_ENV, still read if the latter is missing
     pub const DB_POOL_SIZE_ENV: &str = "DATABASE_POOL_SIZE";
-    pub const DB_POOL_SIZE_DEFAULT: u32 = 6;
+    pub const DB_ACQUIRE_TIMEOUT_S_ENV: &str = "DB_ACQUIRE_TIMEOUT_S";
+    pub const DB_IDLE_TIMEOUT_S_ENV: &str = "DB_IDLE_TIMEOUT_S";
+    pub const DB_MAX_CONNECTIONS_DEFAULT: u32 = 6;
+    pub const DB_ACQUIRE_TIMEOUT_S_DEFAULT: u64 = 30;
+    pub const DB_IDLE_TIMEOUT_S_DEFAULT: u64 = 600;
+
     pub fn get_db_pool() -> Result<PgPool, AppError> {
         use_context::<PgPool>().ok_or_else(|| AppError::new("DB pool missing."))
     }
 
     pub async fn create_db_pool() -> anyhow::Result<sqlx::Pool<sqlx::Postgres>> {
-        let db_pool_size = std::env::var(DB_POOL_SIZE_ENV)
-            .ok()
-            .and_then(|v| v.parse::<u32>().ok())
-            .unwrap_or(DB_POOL_SIZE_DEFAULT);
-        
+        let max_connections = parse_positive_value(
+            std::env::var(DB_MAX_CONNECTIONS_ENV).or_else(|_| std::env::var(DB_POOL_SIZE_ENV)).ok().as_deref(),
+            DB_MAX_CONNECTIONS_ENV,
+            DB_MAX_CONNECTIONS_DEFAULT,
+        );
+        let acquire_timeout_s = parse_positive_value(
+            std::env::var(DB_ACQUIRE_TIMEOUT_S_ENV).ok().as_deref(),
+            DB_ACQUIRE_TIMEOUT_S_ENV,
+            DB_ACQUIRE_TIMEOUT_S_DEFAULT,
+        );
+        let idle_timeout_s = parse_positive_value(
+            std::env::var(DB_IDLE_TIMEOUT_S_ENV).ok().as_deref(),
+            DB_IDLE_TIMEOUT_S_ENV,
+            DB_IDLE_TIMEOUT_S_DEFAULT,
+        );
+
         PgPoolOptions::new()
-            .max_connections(db_pool_size)
+            .max_connections(max_connections)
+            .acquire_timeout(Duration::from_secs(acquire_timeout_s))
+            .idle_timeout(Duration::from_secs(idle_timeout_s))
             .connect(&std::env::var(DB_URL_ENV)?)
             .await
             .with_context(|| "Failed to connect to DB")
     }
-}
\ No newline at end of file
+
+    /// Parses `value` of the env variable `name` as a positive number, returns `default_value` if it is missing or invalid.
+    pub fn parse_positive_value<T: FromStr + PartialOrd + Default>(value: Option<&str>, name: &str, default_value: T) -> T {
+        match value.map(|value| value.trim().parse::<T>()) {
+            Some(Ok(value)) if value > T::default() => value,
+            Some(_) => {
+                log::error!("Could not parse {name} as positive integer, take default value.");
+                default_value
+            },
+            None => default_value,
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::db_utils::ssr::{parse_positive_value, DB_ACQUIRE_TIMEOUT_S_ENV, DB_MAX_CONNECTIONS_DEFAULT, DB_MAX_CONNECTIONS_ENV};
+
+        #[test]
+        fn test_parse_positive_value() {
+            assert_eq!(parse_positive_value(Some("20"), DB_MAX_CONNECTIONS_ENV, DB_MAX_CONNECTIONS_DEFAULT), 20);
+            assert_eq!(parse_positive_value(Some(" 12 "), DB_MAX_CONNECTIONS_ENV, DB_MAX_CONNECTIONS_DEFAULT), 12);
+            assert_eq!(parse_positive_value(Some("120"), DB_ACQUIRE_TIMEOUT_S_ENV, 30u64), 120);
+            assert_eq!(parse_positive_value(None, DB_MAX_CONNECTIONS_ENV, DB_MAX_CONNECTIONS_DEFAULT), DB_MAX_CONNECTIONS_DEFAULT);
+            assert_eq!(parse_positive_value(Some(""), DB_MAX_CONNECTIONS_ENV, DB_MAX_CONNECTIONS_DEFAULT), DB_MAX_CONNECTIONS_DEFAULT);
+            assert_eq!(parse_positive_value(Some("abc"), DB_MAX_CONNECTIONS_ENV, DB_MAX_CONNECTIONS_DEFAULT), DB_MAX_CONNECTIONS_DEFAULT);
+            assert_eq!(parse_positive_value(Some("0"), DB_MAX_CONNECTIONS_ENV, DB_MAX_CONNECTIONS_DEFAULT), DB_MAX_CONNECTIONS_DEFAULT);
+            assert_eq!(parse_positive_value(Some("-5"), DB_MAX_CONNECTIONS_ENV, DB_MAX_CONNECTIONS_DEFAULT), DB_MAX_CONNECTIONS_DEFAULT);
+            assert_eq!(parse_positive_value(Some("1.5"), DB_ACQUIRE_TIMEOUT_S_ENV, 30u64), 30);
+        }
+    }
+}
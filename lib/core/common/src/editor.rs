@@ -7,7 +7,8 @@ use markdown::Options;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::{Reader, Writer};
 
-use crate::constants::SPOILER_TAG;
+use crate::checks::check_username;
+use crate::constants::{ESCAPED_PIPE, INLINE_SPOILER_END_TAG, INLINE_SPOILER_START_TAG, SPOILER_TAG};
 use crate::errors::AppError;
 use crate::traits::ToLocalizedStr;
 
@@ -54,26 +55,63 @@ impl ToLocalizedStr for FormatType {
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use ammonia::Builder;
+    use quick_xml::escape::partial_escape;
+
     use crate::editor::get_styled_html_from_markdown;
     use crate::errors::AppError;
 
+    /// Returns the html to display for `body` and its markdown source if `is_markdown` is true.
+    /// Plain text bodies are html-escaped so that they are displayed as is, see [crate::editor::unescape_plain_text] to edit them.
     pub fn get_html_and_markdown_strings(body: &str, is_markdown: bool) -> Result<(String, Option<&str>), AppError> {
         match is_markdown {
             true => Ok((
-                get_styled_html_from_markdown(body)?,
+                sanitize_html_user_content(&get_styled_html_from_markdown(body)?),
                 Some(body),
             )),
-            false => Ok((String::from(body), None)),
+            false => Ok((partial_escape(body).into_owned(), None)),
         }
     }
+
+    /// Removes every tag, attribute and url scheme of `html` that is not explicitly allowed in user content.
+    /// The allowlist covers the html generated from markdown (including tables, task lists, footnotes and spoilers) and its styling,
+    /// scripts, event handlers and `javascript:` links are dropped.
+    pub fn sanitize_html_user_content(html: &str) -> String {
+        Builder::default()
+            .add_tags(["input", "label", "section"])
+            .add_generic_attributes(["class"])
+            .add_tag_attributes("a", ["id", "aria-describedby", "aria-label", "data-footnote-ref", "data-footnote-backref"])
+            .add_tag_attributes("h2", ["id"])
+            .add_tag_attributes("li", ["id"])
+            .add_tag_attributes("section", ["data-footnotes"])
+            .add_tag_attributes("th", ["align"])
+            .add_tag_attributes("td", ["align"])
+            .add_tag_attributes("input", ["disabled", "checked"])
+            .add_tag_attribute_values("input", "type", ["checkbox"])
+            .clean(html)
+            .to_string()
+    }
+}
+
+/// Returns the markdown options used for user content: GitHub flavored markdown, including tables, task lists and footnotes.
+/// Raw html and dangerous protocols in links are not allowed.
+pub fn get_markdown_options() -> Options {
+    let mut options = Options::gfm();
+    options.parse.constructs.gfm_table = true;
+    options.parse.constructs.gfm_task_list_item = true;
+    options.parse.constructs.gfm_footnote_definition = true;
+    options.parse.constructs.gfm_label_start_footnote = true;
+    options.compile.allow_dangerous_html = false;
+    options.compile.allow_dangerous_protocol = false;
+    options
 }
 
 pub fn get_styled_html_from_markdown(
     markdown_input: &str,
 ) -> Result<String, AppError> {
     let html_from_markdown = markdown::to_html_with_options(
-        markdown_input,
-        &Options::gfm()
+        &convert_inline_spoilers(markdown_input),
+        &get_markdown_options()
     ).map_err(AppError::new)?;
     log::debug!("Markdown as html: {html_from_markdown}");
 
@@ -83,6 +121,154 @@ pub fn get_styled_html_from_markdown(
     Ok(styled_html_output)
 }
 
+/// Converts the inline spoilers `>!spoiler!<` of the markdown `input` to the spoiler syntax handled by [style_html_user_content].
+///
+/// Inline spoilers cannot span multiple lines and are ignored in inline code and fenced code blocks. Nested spoilers are merged
+/// into the outermost one, unclosed spoilers and unmatched closing tags are kept as is. Pipes inside spoilers are escaped so that
+/// they cannot close them early.
+///
+/// ```
+/// use sphare_core_common::editor::convert_inline_spoilers;
+///
+/// assert_eq!(convert_inline_spoilers("a >!b!< c"), String::from("a ||b|| c"));
+/// assert_eq!(convert_inline_spoilers(">!a >!b!< c!<"), String::from("||a b c||"));
+/// assert_eq!(convert_inline_spoilers(">!a || b!<"), String::from(r"||a \\|\\| b||"));
+/// assert_eq!(convert_inline_spoilers(">!a\nb!<"), String::from(">!a\nb!<"));
+/// assert_eq!(convert_inline_spoilers("a!< `>!b!<`"), String::from("a!< `>!b!<`"));
+/// assert_eq!(convert_inline_spoilers("```\n>!a!<\n```"), String::from("```\n>!a!<\n```"));
+/// ```
+pub fn convert_inline_spoilers(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    // character and length of the fence of the current code block
+    let mut code_fence: Option<(char, usize)> = None;
+    for (line_index, line) in input.split('\n').enumerate() {
+        if line_index > 0 {
+            output.push('\n');
+        }
+        let line_fence = get_code_fence(line);
+        match (code_fence, line_fence) {
+            (Some((fence_char, fence_length)), Some((line_fence_char, line_fence_length, info_string))) => {
+                if line_fence_char == fence_char && line_fence_length >= fence_length && info_string.trim().is_empty() {
+                    code_fence = None;
+                }
+                output.push_str(line);
+                continue;
+            },
+            (Some(_), None) => {
+                output.push_str(line);
+                continue;
+            },
+            (None, Some((line_fence_char, line_fence_length, _))) => {
+                code_fence = Some((line_fence_char, line_fence_length));
+                output.push_str(line);
+                continue;
+            },
+            (None, None) => (),
+        }
+        let mut is_in_code = false;
+        let mut spoiler_depth = 0usize;
+        // byte indexes of the outermost opened spoiler in `line` and `output`, to restore it if it is never closed
+        let mut spoiler_start = (0, 0);
+        let mut index = 0;
+        while let Some(c) = line[index..].chars().next() {
+            let remaining = &line[index..];
+            if c == '`' {
+                is_in_code = !is_in_code;
+            } else if !is_in_code && remaining.starts_with(INLINE_SPOILER_START_TAG) {
+                if spoiler_depth == 0 {
+                    spoiler_start = (index, output.len());
+                    output.push_str(SPOILER_TAG);
+                }
+                spoiler_depth += 1;
+                index += INLINE_SPOILER_START_TAG.len();
+                continue;
+            } else if !is_in_code && spoiler_depth > 0 && remaining.starts_with(INLINE_SPOILER_END_TAG) {
+                spoiler_depth -= 1;
+                if spoiler_depth == 0 {
+                    output.push_str(SPOILER_TAG);
+                }
+                index += INLINE_SPOILER_END_TAG.len();
+                continue;
+            }
+            if c == '|' && spoiler_depth > 0 {
+                // escape pipes so that they cannot close the spoiler, the backslash is itself escaped outside of code
+                if !is_in_code {
+                    output.push('\\');
+                }
+                output.push_str(ESCAPED_PIPE);
+            } else {
+                output.push(c);
+            }
+            index += c.len_utf8();
+        }
+        if spoiler_depth > 0 {
+            let (spoiler_line_index, spoiler_output_index) = spoiler_start;
+            output.truncate(spoiler_output_index);
+            output.push_str(&line[spoiler_line_index..]);
+        }
+    }
+    output
+}
+
+/// Returns the fence character, the fence length and the info string if `line` opens or closes a fenced code block
+fn get_code_fence(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed_line = line.trim_start_matches(' ');
+    if line.len() - trimmed_line.len() > 3 {
+        return None
+    }
+    let fence_char = trimmed_line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence_length = trimmed_line.chars().take_while(|c| *c == fence_char).count();
+    match fence_length >= 3 {
+        true => Some((fence_char, fence_length, &trimmed_line[fence_length..])),
+        false => None,
+    }
+}
+
+/// Adds `class` to the classes of `elem`, merging it with its existing class attribute if needed
+fn push_class(elem: &mut BytesStart, class: &str) -> Result<(), AppError> {
+    let existing_class = match elem.try_get_attribute("class").map_err(quick_xml::Error::from)? {
+        Some(attribute) => Some(attribute.unescape_value().map_err(AppError::new)?.into_owned()),
+        None => None,
+    };
+    match existing_class {
+        Some(existing_class) => {
+            let other_attribute_vec: Vec<(Vec<u8>, Vec<u8>)> = elem.attributes()
+                .filter_map(Result::ok)
+                .filter(|attribute| attribute.key.as_ref() != b"class")
+                .map(|attribute| (attribute.key.as_ref().to_vec(), attribute.value.to_vec()))
+                .collect();
+            elem.clear_attributes();
+            for (key, value) in &other_attribute_vec {
+                elem.push_attribute((key.as_slice(), value.as_slice()));
+            }
+            elem.push_attribute(("class", format!("{existing_class} {class}").as_str()));
+        },
+        None => elem.push_attribute(("class", class)),
+    }
+    Ok(())
+}
+
+/// Splits `text` on the spoiler tags, unescaping the pipes escaped with a backslash so that they cannot close a spoiler.
+/// The segments at odd indexes of the result are spoilers.
+fn split_spoiler_text(text: &str) -> Vec<String> {
+    let mut segment_vec = vec![String::new()];
+    let mut index = 0;
+    while let Some(c) = text[index..].chars().next() {
+        let remaining = &text[index..];
+        if remaining.starts_with(ESCAPED_PIPE) {
+            segment_vec.last_mut().unwrap().push('|');
+            index += ESCAPED_PIPE.len();
+        } else if remaining.starts_with(SPOILER_TAG) {
+            segment_vec.push(String::new());
+            index += SPOILER_TAG.len();
+        } else {
+            segment_vec.last_mut().unwrap().push(c);
+            index += c.len_utf8();
+        }
+    }
+    segment_vec
+}
+
 pub fn style_html_user_content(user_content: &str) -> Result<String, AppError> {
     let mut reader = Reader::from_str(user_content);
     let mut writer = Writer::new(Cursor::new(Vec::new()));
@@ -93,23 +279,19 @@ pub fn style_html_user_content(user_content: &str) -> Result<String, AppError> {
             Ok(Event::Start(e)) => {
                 let mut elem = e.into_owned();
                 match elem.name().as_ref() {
-                    b"h1" => elem.push_attribute(("class", "text-4xl mb-3")),
-                    b"h2" => elem.push_attribute(("class", "text-2xl mb-3")),
-                    b"h3" => elem.push_attribute(("class", "text-xl mb-2.5")),
-                    b"p" if !is_in_block => elem.push_attribute(("class", "mb-2.5")),
-                    b"a" => elem.push_attribute(("class", "link text-primary")),
-                    b"ul" => elem.push_attribute(("class", "list-inside list-disc mb-2.5")),
-                    b"ol" => elem.push_attribute(("class", "list-inside list-decimal mb-2.5")),
-                    b"code" => {
-                        elem.push_attribute(("class", "block w-fit rounded-md bg-black px-1 py-0.5 mx-0.5 mb-2.5"))
-                    }
-                    b"table" => elem.push_attribute(("class", "table mb-2.5")),
+                    b"h1" => push_class(&mut elem, "text-4xl mb-3")?,
+                    b"h2" => push_class(&mut elem, "text-2xl mb-3")?,
+                    b"h3" => push_class(&mut elem, "text-xl mb-2.5")?,
+                    b"p" if !is_in_block => push_class(&mut elem, "mb-2.5")?,
+                    b"a" => push_class(&mut elem, "link text-primary")?,
+                    b"ul" => push_class(&mut elem, "list-inside list-disc mb-2.5")?,
+                    b"ol" => push_class(&mut elem, "list-inside list-decimal mb-2.5")?,
+                    b"code" => push_class(&mut elem, "block w-fit rounded-md bg-black px-1 py-0.5 mx-0.5 mb-2.5")?,
+                    b"table" => push_class(&mut elem, "table mb-2.5")?,
+                    b"section" => push_class(&mut elem, "text-sm border-t border-base-content/20 pt-1")?,
                     b"blockquote" => {
                         is_in_block = true;
-                        elem.push_attribute((
-                            "class",
-                            "w-fit p-1 mb-2.5 border-s-4 rounded-sm border-slate-400 bg-slate-600",
-                        ))
+                        push_class(&mut elem, "w-fit p-1 mb-2.5 border-s-4 rounded-sm border-slate-400 bg-slate-600")?
                     },
                     _ => (),
                 }
@@ -126,15 +308,17 @@ pub fn style_html_user_content(user_content: &str) -> Result<String, AppError> {
             Ok(Event::Empty(e)) => {
                 let mut elem = e.into_owned();
 
-                if elem.name().as_ref() == b"hr" {
-                    elem.push_attribute(("class", "my-2"))
+                match elem.name().as_ref() {
+                    b"hr" => push_class(&mut elem, "my-2")?,
+                    b"input" => push_class(&mut elem, "checkbox checkbox-xs align-middle mr-1")?,
+                    _ => (),
                 }
                 // writes the event to the writer
                 writer.write_event(Event::Empty(elem))?;
             }
             Ok(Event::Text(e)) => {
                 let text = e.decode().map_err(|e| AppError::new(format!("Error while decoding text: {e}")))?.into_owned();
-                let spoiler_split_text = text.split(SPOILER_TAG);
+                let spoiler_split_text = split_spoiler_text(&text);
                 let mut is_current_text_spoiler = None;
                 for text in spoiler_split_text {
                     let is_spoiler_text = is_current_text_spoiler.unwrap_or_default();
@@ -161,7 +345,7 @@ pub fn style_html_user_content(user_content: &str) -> Result<String, AppError> {
                             let label_end = BytesEnd::new("label");
                             writer.write_event(Event::End(label_end))?;
                         } else {
-                            writer.write_event(Event::Text(BytesText::new(text)))?;
+                            writer.write_event(Event::Text(BytesText::new(&text)))?;
                         }
                     }
                     is_current_text_spoiler = Some(!is_spoiler_text);
@@ -320,6 +504,108 @@ pub fn clear_newlines(string: String, add_whitespace: bool) -> String {
     )
 }
 
+/// Returns the deduplicated usernames mentioned with `@username` in the markdown `input`, in order of first appearance.
+///
+/// Mentions must not directly follow a word character (to ignore e-mail addresses) and are ignored in inline code and code blocks.
+///
+/// ```
+/// use sphare_core_common::editor::get_mentioned_usernames;
+///
+/// assert_eq!(get_mentioned_usernames("hello @alice and @bob-2, @alice again"), vec![String::from("alice"), String::from("bob-2")]);
+/// assert!(get_mentioned_usernames("mail@example.com `@code` @").is_empty());
+/// ```
+pub fn get_mentioned_usernames(input: &str) -> Vec<String> {
+    let mut username_vec = Vec::<String>::new();
+    let mut is_in_code = false;
+    let mut previous_char = None;
+    let mut char_iter = input.char_indices().peekable();
+    while let Some((index, c)) = char_iter.next() {
+        match c {
+            '`' => is_in_code = !is_in_code,
+            '@' if !is_in_code && !previous_char.is_some_and(|p: char| p.is_ascii_alphanumeric() || p == '_' || p == '-' || p == '@') => {
+                let start = index + 1;
+                let mut end = start;
+                while let Some((next_index, next_char)) = char_iter.peek() {
+                    if next_char.is_ascii_alphanumeric() || *next_char == '_' || *next_char == '-' {
+                        end = next_index + 1;
+                        previous_char = Some(*next_char);
+                        char_iter.next();
+                    } else {
+                        break;
+                    }
+                }
+                let username = &input[start..end];
+                if check_username(username, false).is_ok() && !username_vec.iter().any(|name| name == username) {
+                    username_vec.push(username.to_string());
+                }
+                if end > start {
+                    continue;
+                }
+            },
+            _ => (),
+        }
+        previous_char = Some(c);
+    }
+    username_vec
+}
+
+fn is_username_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Returns the byte index of the `@` and the username prefix of the mention being typed before the byte index `cursor`, if any.
+///
+/// ```
+/// use sphare_core_common::editor::get_mention_prefix;
+///
+/// assert_eq!(get_mention_prefix("hello @ali", 10), Some((6, "ali")));
+/// assert_eq!(get_mention_prefix("hello @", 7), Some((6, "")));
+/// assert_eq!(get_mention_prefix("hello @ali and", 14), None);
+/// assert_eq!(get_mention_prefix("mail@exa", 8), None);
+/// ```
+pub fn get_mention_prefix(content: &str, cursor: usize) -> Option<(usize, &str)> {
+    if cursor > content.len() || !content.is_char_boundary(cursor) {
+        return None;
+    }
+    let before_cursor = &content[..cursor];
+    let prefix_start = before_cursor
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_username_char(*c))
+        .last()
+        .map(|(index, _)| index)
+        .unwrap_or(cursor);
+    let mention_start = prefix_start.checked_sub(1)?;
+    if !before_cursor[mention_start..].starts_with('@') {
+        return None;
+    }
+    match before_cursor[..mention_start].chars().next_back() {
+        Some(c) if is_username_char(c) || c == '@' => None,
+        _ => Some((mention_start, &before_cursor[prefix_start..])),
+    }
+}
+
+/// Replaces the mention being typed between the byte indexes `mention_start` and `cursor` with `@username `,
+/// returns the byte index to set the cursor at.
+pub fn insert_mention(
+    content: &mut String,
+    mention_start: usize,
+    cursor: usize,
+    username: &str,
+) -> usize {
+    let mention = format!("@{username} ");
+    content.replace_range(mention_start..cursor, &mention);
+    mention_start + mention.len()
+}
+
+/// Returns the original text of a plain text `body` stored as escaped html, so that it can be edited again.
+pub fn unescape_plain_text(body: &str) -> String {
+    match quick_xml::escape::unescape(body) {
+        Ok(text) => text.into_owned(),
+        Err(_) => body.to_string(),
+    }
+}
+
 /// Adjust the height of `textarea_ref` so that all its content is displayed without a scrollbar.
 pub fn adjust_textarea_height(textarea_ref: NodeRef<Textarea>) {
     if let Some(textarea_ref) = textarea_ref.get() {
@@ -336,8 +622,9 @@ mod tests {
     use indoc::indoc;
     use leptos::prelude::ServerFnError;
 
-    use crate::editor::ssr::get_html_and_markdown_strings;
-    use crate::editor::{format_textarea_content, get_styled_html_from_markdown, style_html_user_content, FormatType};
+    use crate::constants::MAX_USERNAME_LENGTH;
+    use crate::editor::ssr::{get_html_and_markdown_strings, sanitize_html_user_content};
+    use crate::editor::{convert_inline_spoilers, format_textarea_content, get_mention_prefix, get_mentioned_usernames, get_styled_html_from_markdown, insert_mention, style_html_user_content, unescape_plain_text, FormatType};
 
     #[test]
     fn test_get_html_and_markdown_strings() -> Result<(), ServerFnError> {
@@ -361,6 +648,75 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_get_html_and_markdown_strings_malicious_markdown() -> Result<(), ServerFnError> {
+        let (html_body, _) = get_html_and_markdown_strings("[x](javascript:alert(1))", true)?;
+        assert!(!html_body.contains("javascript"));
+        assert!(html_body.contains(">x</a>"));
+
+        let (html_body, _) = get_html_and_markdown_strings(r#"<img src="x" onerror="alert(1)">"#, true)?;
+        assert!(!html_body.contains("<img"));
+
+        let (html_body, _) = get_html_and_markdown_strings("<script>alert(1)</script>", true)?;
+        assert!(!html_body.contains("<script"));
+
+        let (html_body, _) = get_html_and_markdown_strings(r#"<a href="https://example.com" onclick="alert(1)">x</a>"#, true)?;
+        assert!(!html_body.contains("<a"));
+
+        // plain text bodies are escaped rather than rendered as html
+        let (html_body, markdown_body) = get_html_and_markdown_strings("[x](javascript:alert(1))", false)?;
+        assert_eq!(html_body, "[x](javascript:alert(1))");
+        assert_eq!(markdown_body, None);
+
+        let (html_body, _) = get_html_and_markdown_strings(r#"<img src="x" onerror="alert(1)">"#, false)?;
+        assert_eq!(html_body, r#"&lt;img src="x" onerror="alert(1)"&gt;"#);
+
+        let (html_body, _) = get_html_and_markdown_strings("<script>alert(1)</script>", false)?;
+        assert_eq!(html_body, "&lt;script&gt;alert(1)&lt;/script&gt;");
+
+        let (html_body, _) = get_html_and_markdown_strings(r#"<a href="https://example.com" onclick="alert(1)">x</a>"#, false)?;
+        assert!(!html_body.contains("<a"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sanitize_html_user_content() -> Result<(), ServerFnError> {
+        assert_eq!(sanitize_html_user_content("<p>test</p><script>alert(1)</script>"), "<p>test</p>");
+        assert_eq!(sanitize_html_user_content(r#"<img src="x" onerror="alert(1)">"#), r#"<img src="x">"#);
+        assert_eq!(
+            sanitize_html_user_content(r#"<a href="javascript:alert(1)" onclick="alert(1)" class="link">x</a>"#),
+            r#"<a class="link" rel="noopener noreferrer">x</a>"#
+        );
+        assert_eq!(sanitize_html_user_content(r#"<input type="text" onfocus="alert(1)">"#), "<input>");
+        assert_eq!(sanitize_html_user_content(r#"<iframe src="https://example.com"></iframe>"#), "");
+
+        let markdown = indoc! {r#"
+            | Left | Right |
+            | :--- | ----: |
+            | a    | b     |
+
+            - [x] done
+
+            Text with a footnote[^1] and a || spoiler ||.
+
+            [^1]: The footnote.
+        "#};
+        let styled_html = get_styled_html_from_markdown(markdown)?;
+        let sanitized_html = sanitize_html_user_content(&styled_html);
+        assert!(sanitized_html.contains(r#"<table class="table mb-2.5">"#));
+        assert!(sanitized_html.contains(r#"<th align="left">Left</th>"#));
+        assert!(sanitized_html.contains(r#"<td align="right">b</td>"#));
+        assert!(sanitized_html.contains(r#"<input type="checkbox" disabled="" checked="" class="checkbox checkbox-xs align-middle mr-1">"#));
+        assert!(sanitized_html.contains(r##"<a href="#user-content-fn-1" id="user-content-fnref-1" data-footnote-ref="" aria-describedby="footnote-label" class="link text-primary" rel="noopener noreferrer">1</a>"##));
+        assert!(sanitized_html.contains(r#"<section data-footnotes="" class="footnotes text-sm border-t border-base-content/20 pt-1">"#));
+        assert!(sanitized_html.contains(r#"<h2 id="footnote-label" class="sr-only text-2xl mb-3">Footnotes</h2>"#));
+        assert!(sanitized_html.contains(r#"<li id="user-content-fn-1">"#));
+        assert!(sanitized_html.contains(r#"<label><input type="checkbox" class="spoiler-checkbox hidden">"#));
+
+        Ok(())
+    }
+
     #[test]
     fn test_style_html_user_content() -> Result<(), ServerFnError> {
         assert_eq!(
@@ -400,6 +756,18 @@ mod tests {
             r#"<blockquote class="w-fit p-1 mb-2.5 border-s-4 rounded-sm border-slate-400 bg-slate-600"></blockquote>"#
         );
         assert_eq!(style_html_user_content("<hr/>")?, r#"<hr class="my-2"/>"#);
+        assert_eq!(
+            style_html_user_content(r#"<input type="checkbox" disabled=""/>"#)?,
+            r#"<input type="checkbox" disabled="" class="checkbox checkbox-xs align-middle mr-1"/>"#
+        );
+        assert_eq!(
+            style_html_user_content(r#"<section data-footnotes="" class="footnotes"></section>"#)?,
+            r#"<section data-footnotes="" class="footnotes text-sm border-t border-base-content/20 pt-1"></section>"#
+        );
+        assert_eq!(
+            style_html_user_content(r##"<a href="#fn" class="data-footnote-backref" aria-label="Back">↩</a>"##)?,
+            r##"<a href="#fn" aria-label="Back" class="data-footnote-backref link text-primary">↩</a>"##
+        );
         assert_eq!(
             style_html_user_content("<p>Test, || This is a spoiler || this is not a spoiler</p>")?,
             r#"<p class="mb-2.5">Test, <label><input type="checkbox" class="spoiler-checkbox hidden"/><span class="transition-all duration-300 ease-in-out rounded-md bg-white p-0.5 px-1 mx-0.5 text-white spoiler-text">This is a spoiler</span></label> this is not a spoiler</p>"#
@@ -576,6 +944,112 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_get_styled_html_from_markdown_gfm() -> Result<(), ServerFnError> {
+        let table_markdown = indoc! {r#"
+            | Left | Right |
+            | :--- | ----: |
+            | a    | b     |
+        "#};
+        let table_html = get_styled_html_from_markdown(table_markdown)?;
+        assert!(table_html.starts_with(r#"<table class="table mb-2.5">"#));
+        assert!(table_html.contains(r#"<th align="left">Left</th>"#));
+        assert!(table_html.contains(r#"<th align="right">Right</th>"#));
+        assert!(table_html.contains(r#"<td align="left">a</td>"#));
+        assert!(table_html.contains(r#"<td align="right">b</td>"#));
+
+        let task_list_markdown = indoc! {r#"
+            - [x] done
+            - [ ] todo
+        "#};
+        let task_list_html = get_styled_html_from_markdown(task_list_markdown)?;
+        assert!(task_list_html.contains(r#"<input type="checkbox" disabled="" checked=""  class="checkbox checkbox-xs align-middle mr-1"/>"#));
+        assert!(task_list_html.contains(r#"<input type="checkbox" disabled=""  class="checkbox checkbox-xs align-middle mr-1"/>"#));
+
+        let footnote_markdown = indoc! {r#"
+            Text with a footnote[^1].
+
+            [^1]: The footnote.
+        "#};
+        let footnote_html = get_styled_html_from_markdown(footnote_markdown)?;
+        assert!(footnote_html.contains(r##"<sup><a href="#user-content-fn-1" id="user-content-fnref-1" data-footnote-ref="" aria-describedby="footnote-label" class="link text-primary">1</a></sup>"##));
+        assert!(footnote_html.contains(r#"<section data-footnotes="" class="footnotes text-sm border-t border-base-content/20 pt-1">"#));
+        assert!(footnote_html.contains(r#"<h2 id="footnote-label" class="sr-only text-2xl mb-3">Footnotes</h2>"#));
+        assert!(footnote_html.contains(r#"<li id="user-content-fn-1">"#));
+        assert!(footnote_html.contains(r#"class="data-footnote-backref link text-primary""#));
+        assert!(footnote_html.split('<').all(|tag| tag.matches("class=").count() <= 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_styled_html_from_markdown_inline_spoiler() -> Result<(), ServerFnError> {
+        let spoiler_html = r#"<label><input type="checkbox" class="spoiler-checkbox hidden"/><span class="transition-all duration-300 ease-in-out rounded-md bg-white p-0.5 px-1 mx-0.5 text-white spoiler-text">This is a spoiler</span></label>"#;
+        assert_eq!(
+            get_styled_html_from_markdown("Test, >!This is a spoiler!< this is not a spoiler")?,
+            format!("<p class=\"mb-2.5\">Test, {spoiler_html} this is not a spoiler</p>")
+        );
+        // inline spoilers at the start of a line are not parsed as block quotes
+        assert_eq!(
+            get_styled_html_from_markdown(">!This is a spoiler!<")?,
+            format!("<p class=\"mb-2.5\">{spoiler_html}</p>")
+        );
+        // nested spoilers are merged into the outermost one
+        assert_eq!(
+            get_styled_html_from_markdown(">!This >!is!< a spoiler!<")?,
+            format!("<p class=\"mb-2.5\">{spoiler_html}</p>")
+        );
+        // pipes inside spoilers do not close them
+        let pipe_spoiler_html = spoiler_html.replace("This is a spoiler", "This || is a spoiler");
+        assert_eq!(
+            get_styled_html_from_markdown(">!This || is a spoiler!<")?,
+            format!("<p class=\"mb-2.5\">{pipe_spoiler_html}</p>")
+        );
+        // unclosed spoilers and unmatched closing tags are kept as text
+        assert_eq!(
+            get_styled_html_from_markdown("a >!b")?,
+            "<p class=\"mb-2.5\">a &gt;!b</p>"
+        );
+        assert_eq!(
+            get_styled_html_from_markdown("a!< b")?,
+            "<p class=\"mb-2.5\">a!&lt; b</p>"
+        );
+        assert_eq!(
+            get_styled_html_from_markdown("a >!b\nc!<")?,
+            "<p class=\"mb-2.5\">a &gt;!b\nc!&lt;</p>"
+        );
+
+        let (sanitized_html, _) = get_html_and_markdown_strings("Test, >!This is a spoiler!<", true)?;
+        assert!(sanitized_html.contains(r#"<label><input type="checkbox" class="spoiler-checkbox hidden">"#));
+        assert!(sanitized_html.contains(r#"class="transition-all duration-300 ease-in-out rounded-md bg-white p-0.5 px-1 mx-0.5 text-white spoiler-text">This is a spoiler</span>"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_inline_spoilers() {
+        assert_eq!(convert_inline_spoilers(""), "");
+        assert_eq!(convert_inline_spoilers("no spoiler"), "no spoiler");
+        assert_eq!(convert_inline_spoilers(">!a!< b >!c!<"), "||a|| b ||c||");
+        assert_eq!(convert_inline_spoilers(">!a >!b >!c!< d!< e!<"), "||a b c d e||");
+        assert_eq!(convert_inline_spoilers(">!a!< >!b"), "||a|| >!b");
+        assert_eq!(convert_inline_spoilers(">!a >!b!<"), ">!a >!b!<");
+        assert_eq!(convert_inline_spoilers("a!< b!<"), "a!< b!<");
+        assert_eq!(convert_inline_spoilers(">!a\n>!b!<"), ">!a\n||b||");
+        assert_eq!(convert_inline_spoilers("`>!a!<` >!b!<"), "`>!a!<` ||b||");
+        assert_eq!(convert_inline_spoilers(">!été!<"), "||été||");
+        assert_eq!(convert_inline_spoilers(">!a || b!< c || d"), r"||a \\|\\| b|| c || d");
+        assert_eq!(convert_inline_spoilers(">!a `||` b!<"), r"||a `\|\|` b||");
+        // fenced code blocks are kept as is
+        assert_eq!(
+            convert_inline_spoilers(">!a!<\n```rust\n>!b!<\n`\n>!c!<\n```\n>!d!<"),
+            "||a||\n```rust\n>!b!<\n`\n>!c!<\n```\n||d||"
+        );
+        assert_eq!(convert_inline_spoilers("~~~~\n>!a!<\n~~~\n```\n>!b!<\n~~~~\n>!c!<"), "~~~~\n>!a!<\n~~~\n```\n>!b!<\n~~~~\n||c||");
+        assert_eq!(convert_inline_spoilers("```\n>!a!<\n``` b\n>!c!<"), "```\n>!a!<\n``` b\n>!c!<");
+        assert_eq!(convert_inline_spoilers("    ```\n>!a!<"), "    ```\n||a||");
+    }
+
     #[test]
     fn test_format_textarea_content() {
         // Bold
@@ -710,4 +1184,69 @@ mod tests {
         assert_eq!(cursor_position, Some(10));
         assert_eq!(content, "This is ![](some) user text ");
     }
+
+    #[test]
+    fn test_get_mentioned_usernames() {
+        assert!(get_mentioned_usernames("").is_empty());
+        assert!(get_mentioned_usernames("no mention here").is_empty());
+        assert_eq!(get_mentioned_usernames("@alice"), vec![String::from("alice")]);
+        assert_eq!(
+            get_mentioned_usernames("Hi @alice, @Bob_1 and (@carol-2)!"),
+            vec![String::from("alice"), String::from("Bob_1"), String::from("carol-2")]
+        );
+        // duplicates are removed, order of first appearance is kept
+        assert_eq!(
+            get_mentioned_usernames("@bob @alice\n@bob **@alice**"),
+            vec![String::from("bob"), String::from("alice")]
+        );
+        // e-mail addresses, lone or double @ are ignored
+        assert!(get_mentioned_usernames("contact me at alice@example.com").is_empty());
+        assert!(get_mentioned_usernames("@ @@alice @%").is_empty());
+        // mentions in code are ignored
+        assert_eq!(
+            get_mentioned_usernames("`@alice` @bob\n```\n@carol\n```"),
+            vec![String::from("bob")]
+        );
+        // too long usernames are ignored
+        assert!(get_mentioned_usernames(&format!("@{}", "a".repeat(MAX_USERNAME_LENGTH + 1))).is_empty());
+    }
+
+    #[test]
+    fn test_get_mention_prefix() {
+        assert_eq!(get_mention_prefix("@", 1), Some((0, "")));
+        assert_eq!(get_mention_prefix("@bob", 4), Some((0, "bob")));
+        assert_eq!(get_mention_prefix("@bob", 2), Some((0, "b")));
+        assert_eq!(get_mention_prefix("hi\n@bob-2_x", 11), Some((3, "bob-2_x")));
+        assert_eq!(get_mention_prefix("(@bob", 5), Some((1, "bob")));
+        assert_eq!(get_mention_prefix("é @bob", 7), Some((3, "bob")));
+        assert_eq!(get_mention_prefix("@bob ", 5), None);
+        assert_eq!(get_mention_prefix("bob", 3), None);
+        assert_eq!(get_mention_prefix("a@bob", 5), None);
+        assert_eq!(get_mention_prefix("@@bob", 5), None);
+        assert_eq!(get_mention_prefix("", 0), None);
+        assert_eq!(get_mention_prefix("@bob", 5), None);
+        assert_eq!(get_mention_prefix("é", 1), None);
+    }
+
+    #[test]
+    fn test_unescape_plain_text() -> Result<(), ServerFnError> {
+        for text in ["hello world", "a < b && c > d", "<script>alert(1)</script>", "&lt; stays escaped", "it's \"quoted\""] {
+            let (html_body, _) = get_html_and_markdown_strings(text, false)?;
+            assert_eq!(unescape_plain_text(&html_body), text);
+        }
+        assert_eq!(unescape_plain_text("a & b"), "a & b");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_mention() {
+        let mut content = String::from("hello @al");
+        assert_eq!(insert_mention(&mut content, 6, 9, "alice"), 13);
+        assert_eq!(content, "hello @alice ");
+
+        let mut content = String::from("@ and more");
+        assert_eq!(insert_mention(&mut content, 0, 1, "bob"), 5);
+        assert_eq!(content, "@bob  and more");
+    }
 }
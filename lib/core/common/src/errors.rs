@@ -19,19 +19,41 @@ pub enum AppError {
     PermanentSphereBan,
     GlobalBanUntil(chrono::DateTime<chrono::Utc>),
     PermanentGlobalBan,
+    RulesNotAccepted,
+    SphereMute,
+    SphereArchived,
+    AccountTooNew(i32),
+    InsufficientKarma(i32),
+    PostTypeNotAllowed,
+    CategoryRequired,
+    TitleTooShort(i32),
+    TitleTooLong(i32),
+    TitleFormatMismatch(String),
+    NsfwNotConfirmed,
+    ValidationError(String),
     CommunicationError(ServerFnErrorErr),
     DatabaseError(String),
     InternalServerError(String),
     NotFound,
     PayloadTooLarge(usize),
+    DuplicateLink(i64),
+    SphereNameTaken,
+    CategoryInUse,
+    ReservedName,
+    UsernameTaken,
+    Conflict,
+    RateLimited(Option<chrono::DateTime<chrono::Utc>>),
 }
 
 impl AppError {
-    pub fn status_code(&self) -> StatusCode {
+    /// Returns the HTTP status code of responses failing with this error.
+    pub fn http_status(&self) -> StatusCode {
         match self {
             AppError::AuthenticationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::NotAuthenticated | AppError::InsufficientPrivileges | AppError::SphereBanUntil(_) |
-            AppError::PermanentSphereBan | AppError::GlobalBanUntil(_) | AppError::PermanentGlobalBan => StatusCode::FORBIDDEN,
+            AppError::PermanentSphereBan | AppError::GlobalBanUntil(_) | AppError::PermanentGlobalBan |
+            AppError::RulesNotAccepted | AppError::SphereMute | AppError::SphereArchived | AppError::AccountTooNew(_) | AppError::InsufficientKarma(_) |
+            AppError::PostTypeNotAllowed | AppError::NsfwNotConfirmed => StatusCode::FORBIDDEN,
             AppError::CommunicationError(error) => match error {
                 ServerFnErrorErr::Args(_) | ServerFnErrorErr::MissingArg(_) | ServerFnErrorErr::Serialization(_) | ServerFnErrorErr::Deserialization(_) => StatusCode::BAD_REQUEST,
                 ServerFnErrorErr::Registration(_) | ServerFnErrorErr::Request(_) | ServerFnErrorErr::Response(_) => StatusCode::SERVICE_UNAVAILABLE,
@@ -41,6 +63,11 @@ impl AppError {
             AppError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::NotFound => StatusCode::NOT_FOUND,
             AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::DuplicateLink(_) | AppError::SphereNameTaken | AppError::CategoryInUse | AppError::ReservedName |
+            AppError::UsernameTaken | AppError::Conflict => StatusCode::CONFLICT,
+            AppError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::TitleTooShort(_) | AppError::TitleTooLong(_) | AppError::TitleFormatMismatch(_) |
+            AppError::CategoryRequired | AppError::ValidationError(_) => StatusCode::BAD_REQUEST,
         }
     }
 
@@ -59,6 +86,33 @@ impl AppError {
                 move_tr!("global-ban-until-message", {"timestamp" => timestamp_str.clone()})
             },
             AppError::PermanentGlobalBan => move_tr!("permanent-global-ban-message"),
+            AppError::RulesNotAccepted => move_tr!("rules-not-accepted-message"),
+            AppError::SphereMute => move_tr!("sphere-mute-message"),
+            AppError::SphereArchived => move_tr!("sphere-archived-message"),
+            AppError::AccountTooNew(min_account_age_days) => {
+                let min_account_age_days = *min_account_age_days;
+                move_tr!("account-too-new-message", {"days" => min_account_age_days})
+            },
+            AppError::InsufficientKarma(min_karma) => {
+                let min_karma = *min_karma;
+                move_tr!("insufficient-karma-message", {"karma" => min_karma})
+            },
+            AppError::PostTypeNotAllowed => move_tr!("post-type-not-allowed-message"),
+            AppError::CategoryRequired => move_tr!("category-required-message"),
+            AppError::TitleTooShort(min_title_length) => {
+                let min_title_length = *min_title_length;
+                move_tr!("title-too-short-message", {"count" => min_title_length})
+            },
+            AppError::TitleTooLong(max_title_length) => {
+                let max_title_length = *max_title_length;
+                move_tr!("title-too-long-message", {"count" => max_title_length})
+            },
+            AppError::TitleFormatMismatch(title_regex) => {
+                let title_regex = title_regex.clone();
+                move_tr!("title-format-mismatch-message", {"regex" => title_regex.clone()})
+            },
+            AppError::NsfwNotConfirmed => move_tr!("nsfw-not-confirmed-message"),
+            AppError::ValidationError(_) => move_tr!("invalid-input-message"),
             AppError::CommunicationError(error) => match error {
                 ServerFnErrorErr::Args(_) | ServerFnErrorErr::MissingArg(_) |
                 ServerFnErrorErr::Serialization(_) | ServerFnErrorErr::Deserialization(_) => move_tr!("bad-request-message"),
@@ -72,6 +126,17 @@ impl AppError {
                 let byte_limit = *byte_limit as f64 / 1024.0 / 1024.0;
                 move_tr!("payload-too-large-message", {"mb_limit" => byte_limit})
             },
+            AppError::DuplicateLink(_) => move_tr!("duplicate-link-message"),
+            AppError::SphereNameTaken => move_tr!("sphere-name-taken-message"),
+            AppError::CategoryInUse => move_tr!("category-in-use-message"),
+            AppError::ReservedName => move_tr!("reserved-name-message"),
+            AppError::UsernameTaken => move_tr!("username-taken-message"),
+            AppError::Conflict => move_tr!("conflict-message"),
+            AppError::RateLimited(None) => move_tr!("rate-limited-message"),
+            AppError::RateLimited(Some(timestamp)) => {
+                let timestamp_str = timestamp.to_string();
+                move_tr!("rate-limited-until-message", {"timestamp" => timestamp_str.clone()})
+            },
         }
     }
 
@@ -84,7 +149,7 @@ impl AppError {
                 ServerFnErrorErr::Registration(e) | ServerFnErrorErr::Request(e) | ServerFnErrorErr::Response(e) => e.clone().into(),
                 _ => self.user_message(),
             },
-            AppError::InternalServerError(e) => e.clone().into(),
+            AppError::ValidationError(e) | AppError::InternalServerError(e) => e.clone().into(),
             _ => self.user_message()
         }
     }
@@ -122,13 +187,13 @@ impl FromServerFnError for AppError {
 
 impl From<ValidationError> for AppError {
     fn from(error: ValidationError) -> Self {
-        AppError::new(error)
+        AppError::ValidationError(error.to_string())
     }
 }
 
 impl From<ValidationErrors> for AppError {
     fn from(errors: ValidationErrors) -> Self {
-        AppError::new(errors)
+        AppError::ValidationError(errors.to_string())
     }
 }
 
@@ -157,15 +222,55 @@ impl From<std::io::Error> for AppError {
 }
 
 #[cfg(feature = "ssr")]
-mod ssr {
+pub mod ssr {
+    use std::future::Future;
+    use std::time::Duration;
     use crate::errors::AppError;
     use openidconnect::SignatureVerificationError;
     use sqlx;
 
+    pub const DB_RETRY_MAX_ATTEMPTS: usize = 3;
+    pub const DB_RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+
+    /// Returns true if the error is caused by a temporary loss of connectivity to the database
+    /// and the same query is likely to succeed when retried.
+    pub fn is_transient_db_error(error: &sqlx::Error) -> bool {
+        matches!(error, sqlx::Error::PoolTimedOut | sqlx::Error::Io(_) | sqlx::Error::WorkerCrashed)
+    }
+
+    /// Runs `operation` until it succeeds, fails with a non-transient error or was attempted `max_attempts` times.
+    /// The delay between attempts starts at `base_delay` and doubles after each failure.
+    pub async fn retry_transient_db_errors<T, F, Fut>(
+        max_attempts: usize,
+        base_delay: Duration,
+        mut operation: F,
+    ) -> Result<T, sqlx::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        let mut attempt = 1;
+        let mut delay = base_delay;
+        loop {
+            match operation().await {
+                Err(error) if attempt < max_attempts && is_transient_db_error(&error) => {
+                    log::warn!("Transient database error on attempt {attempt}/{max_attempts}, retrying in {delay:?}: {error}");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    delay *= 2;
+                },
+                result => return result,
+            }
+        }
+    }
+
+    /// Missing rows are converted to [AppError::NotFound] and violations of any unique constraint to [AppError::Conflict].
+    /// Callers that need a more specific error for a given constraint, like [AppError::UsernameTaken], must map the conflict themselves.
     impl From<sqlx::Error> for AppError {
         fn from(error: sqlx::Error) -> Self {
             match error {
                 sqlx::Error::RowNotFound => AppError::NotFound,
+                sqlx::Error::Database(ref database_error) if database_error.is_unique_violation() => AppError::Conflict,
                 _ => AppError::DatabaseError(error.to_string()),
             }
         }
@@ -218,6 +323,68 @@ mod ssr {
             AppError::InternalServerError(value.to_string())
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::time::Duration;
+
+        use crate::errors::ssr::{is_transient_db_error, retry_transient_db_errors};
+
+        fn fail_n_times_then_succeed(
+            failure_count: usize,
+            error: fn() -> sqlx::Error,
+            call_count: &AtomicUsize,
+        ) -> impl FnMut() -> std::future::Ready<Result<usize, sqlx::Error>> + '_ {
+            move || {
+                let call_index = call_count.fetch_add(1, Ordering::SeqCst);
+                std::future::ready(match call_index < failure_count {
+                    true => Err(error()),
+                    false => Ok(call_index),
+                })
+            }
+        }
+
+        #[test]
+        fn test_is_transient_db_error() {
+            assert!(is_transient_db_error(&sqlx::Error::PoolTimedOut));
+            assert!(is_transient_db_error(&sqlx::Error::WorkerCrashed));
+            assert!(is_transient_db_error(&sqlx::Error::Io(std::io::Error::from(std::io::ErrorKind::ConnectionReset))));
+            assert!(!is_transient_db_error(&sqlx::Error::RowNotFound));
+            assert!(!is_transient_db_error(&sqlx::Error::PoolClosed));
+            assert!(!is_transient_db_error(&sqlx::Error::Protocol(String::from("test"))));
+        }
+
+        #[tokio::test]
+        async fn test_retry_transient_db_errors() {
+            let call_count = AtomicUsize::new(0);
+            let result = retry_transient_db_errors(
+                3,
+                Duration::ZERO,
+                fail_n_times_then_succeed(2, || sqlx::Error::PoolTimedOut, &call_count),
+            ).await;
+            assert_eq!(result.expect("Should succeed after retries."), 2);
+            assert_eq!(call_count.load(Ordering::SeqCst), 3);
+
+            let call_count = AtomicUsize::new(0);
+            let result = retry_transient_db_errors(
+                3,
+                Duration::ZERO,
+                fail_n_times_then_succeed(3, || sqlx::Error::PoolTimedOut, &call_count),
+            ).await;
+            assert!(matches!(result, Err(sqlx::Error::PoolTimedOut)));
+            assert_eq!(call_count.load(Ordering::SeqCst), 3);
+
+            let call_count = AtomicUsize::new(0);
+            let result = retry_transient_db_errors(
+                3,
+                Duration::ZERO,
+                fail_n_times_then_succeed(1, || sqlx::Error::RowNotFound, &call_count),
+            ).await;
+            assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+            assert_eq!(call_count.load(Ordering::SeqCst), 1);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -253,7 +420,7 @@ mod tests {
     ];
 
     #[test]
-    fn test_app_error_status_code() {
+    fn test_app_error_http_status() {
         let test_string = String::from("test");
         let test_timestamp = chrono::DateTime::from_timestamp_nanos(0);
         let server_fn_error = ServerFnErrorErr::ServerError(String::from("test"));
@@ -264,24 +431,45 @@ mod tests {
         let registration_error = ServerFnErrorErr::Registration(String::from("test"));
         let serialization_error = ServerFnErrorErr::Serialization(String::from("test"));
         let deserialization_error = ServerFnErrorErr::Deserialization(String::from("test"));
-        assert_eq!(AppError::AuthenticationError(test_string.clone()).status_code(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(AppError::NotAuthenticated.status_code(), StatusCode::FORBIDDEN);
-        assert_eq!(AppError::InsufficientPrivileges.status_code(), StatusCode::FORBIDDEN);
-        assert_eq!(AppError::SphereBanUntil(test_timestamp).status_code(), StatusCode::FORBIDDEN);
-        assert_eq!(AppError::PermanentSphereBan.status_code(), StatusCode::FORBIDDEN);
-        assert_eq!(AppError::GlobalBanUntil(test_timestamp).status_code(), StatusCode::FORBIDDEN);
-        assert_eq!(AppError::PermanentGlobalBan.status_code(), StatusCode::FORBIDDEN);
-        assert_eq!(AppError::CommunicationError(server_fn_error).status_code(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(AppError::CommunicationError(args_error).status_code(), StatusCode::BAD_REQUEST);
-        assert_eq!(AppError::CommunicationError(missing_arg_error).status_code(), StatusCode::BAD_REQUEST);
-        assert_eq!(AppError::CommunicationError(serialization_error).status_code(), StatusCode::BAD_REQUEST);
-        assert_eq!(AppError::CommunicationError(deserialization_error).status_code(), StatusCode::BAD_REQUEST);
-        assert_eq!(AppError::CommunicationError(request_error).status_code(), StatusCode::SERVICE_UNAVAILABLE);
-        assert_eq!(AppError::CommunicationError(response_error).status_code(), StatusCode::SERVICE_UNAVAILABLE);
-        assert_eq!(AppError::CommunicationError(registration_error).status_code(), StatusCode::SERVICE_UNAVAILABLE);
-        assert_eq!(AppError::DatabaseError(test_string.clone()).status_code(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(AppError::InternalServerError(test_string.clone()).status_code(), StatusCode::INTERNAL_SERVER_ERROR);
-        assert_eq!(AppError::NotFound.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(AppError::AuthenticationError(test_string.clone()).http_status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(AppError::NotAuthenticated.http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::InsufficientPrivileges.http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::SphereBanUntil(test_timestamp).http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::PermanentSphereBan.http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::GlobalBanUntil(test_timestamp).http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::PermanentGlobalBan.http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::RulesNotAccepted.http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::SphereMute.http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::SphereArchived.http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::AccountTooNew(7).http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::InsufficientKarma(10).http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::PostTypeNotAllowed.http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::NsfwNotConfirmed.http_status(), StatusCode::FORBIDDEN);
+        assert_eq!(AppError::ValidationError(test_string.clone()).http_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(AppError::CommunicationError(server_fn_error).http_status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(AppError::CommunicationError(args_error).http_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(AppError::CommunicationError(missing_arg_error).http_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(AppError::CommunicationError(serialization_error).http_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(AppError::CommunicationError(deserialization_error).http_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(AppError::CommunicationError(request_error).http_status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(AppError::CommunicationError(response_error).http_status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(AppError::CommunicationError(registration_error).http_status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(AppError::DatabaseError(test_string.clone()).http_status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(AppError::InternalServerError(test_string.clone()).http_status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(AppError::NotFound.http_status(), StatusCode::NOT_FOUND);
+        assert_eq!(AppError::DuplicateLink(1).http_status(), StatusCode::CONFLICT);
+        assert_eq!(AppError::SphereNameTaken.http_status(), StatusCode::CONFLICT);
+        assert_eq!(AppError::CategoryInUse.http_status(), StatusCode::CONFLICT);
+        assert_eq!(AppError::ReservedName.http_status(), StatusCode::CONFLICT);
+        assert_eq!(AppError::UsernameTaken.http_status(), StatusCode::CONFLICT);
+        assert_eq!(AppError::Conflict.http_status(), StatusCode::CONFLICT);
+        assert_eq!(AppError::RateLimited(None).http_status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(AppError::RateLimited(Some(test_timestamp)).http_status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(AppError::PayloadTooLarge(1024).http_status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(AppError::TitleTooShort(5).http_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(AppError::TitleTooLong(50).http_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(AppError::TitleFormatMismatch(test_string.clone()).http_status(), StatusCode::BAD_REQUEST);
+        assert_eq!(AppError::CategoryRequired.http_status(), StatusCode::BAD_REQUEST);
     }
 
     #[test]
@@ -320,6 +508,21 @@ mod tests {
         assert_eq!(AppError::PermanentSphereBan.user_message().get_untracked(), tr!("permanent-sphere-ban-message"));
         assert_eq!(AppError::GlobalBanUntil(test_timestamp).user_message().get_untracked(), tr!("global-ban-until-message", {"timestamp" => test_timestamp.to_string()}));
         assert_eq!(AppError::PermanentGlobalBan.user_message().get_untracked(), tr!("permanent-global-ban-message"));
+        assert_eq!(AppError::RulesNotAccepted.user_message().get_untracked(), tr!("rules-not-accepted-message"));
+        assert_eq!(AppError::SphereMute.user_message().get_untracked(), tr!("sphere-mute-message"));
+        assert_eq!(AppError::SphereArchived.user_message().get_untracked(), tr!("sphere-archived-message"));
+        assert_eq!(AppError::AccountTooNew(7).user_message().get_untracked(), tr!("account-too-new-message", {"days" => 7}));
+        assert_eq!(AppError::InsufficientKarma(10).user_message().get_untracked(), tr!("insufficient-karma-message", {"karma" => 10}));
+        assert_eq!(AppError::PostTypeNotAllowed.user_message().get_untracked(), tr!("post-type-not-allowed-message"));
+        assert_eq!(AppError::CategoryRequired.user_message().get_untracked(), tr!("category-required-message"));
+        assert_eq!(AppError::TitleTooShort(5).user_message().get_untracked(), tr!("title-too-short-message", {"count" => 5}));
+        assert_eq!(AppError::TitleTooLong(50).user_message().get_untracked(), tr!("title-too-long-message", {"count" => 50}));
+        assert_eq!(
+            AppError::TitleFormatMismatch(test_string.clone()).user_message().get_untracked(),
+            tr!("title-format-mismatch-message", {"regex" => test_string.clone()})
+        );
+        assert_eq!(AppError::NsfwNotConfirmed.user_message().get_untracked(), tr!("nsfw-not-confirmed-message"));
+        assert_eq!(AppError::ValidationError(test_string.clone()).user_message().get_untracked(), tr!("invalid-input-message"));
         assert_eq!(AppError::CommunicationError(server_fn_error).user_message().get_untracked(), tr!("internal-error-message"));
         assert_eq!(AppError::CommunicationError(args_error).user_message().get_untracked(), tr!("bad-request-message"));
         assert_eq!(AppError::CommunicationError(missing_arg_error).user_message().get_untracked(), tr!("bad-request-message"));
@@ -331,6 +534,17 @@ mod tests {
         assert_eq!(AppError::DatabaseError(test_string.clone()).user_message().get_untracked(), tr!("internal-error-message"));
         assert_eq!(AppError::InternalServerError(test_string.clone()).user_message().get_untracked(), tr!("internal-error-message"));
         assert_eq!(AppError::NotFound.user_message().get_untracked(), tr!("not-found-message"));
+        assert_eq!(AppError::DuplicateLink(1).user_message().get_untracked(), tr!("duplicate-link-message"));
+        assert_eq!(AppError::SphereNameTaken.user_message().get_untracked(), tr!("sphere-name-taken-message"));
+        assert_eq!(AppError::CategoryInUse.user_message().get_untracked(), tr!("category-in-use-message"));
+        assert_eq!(AppError::ReservedName.user_message().get_untracked(), tr!("reserved-name-message"));
+        assert_eq!(AppError::UsernameTaken.user_message().get_untracked(), tr!("username-taken-message"));
+        assert_eq!(AppError::Conflict.user_message().get_untracked(), tr!("conflict-message"));
+        assert_eq!(AppError::RateLimited(None).user_message().get_untracked(), tr!("rate-limited-message"));
+        assert_eq!(
+            AppError::RateLimited(Some(test_timestamp)).user_message().get_untracked(),
+            tr!("rate-limited-until-message", {"timestamp" => test_timestamp.to_string()})
+        );
     }
 
     #[test]
@@ -373,6 +587,50 @@ mod tests {
             AppError::from_str(AppError::PermanentGlobalBan.to_string().as_str()).expect("AppError should be convert to string and back"),
             AppError::PermanentGlobalBan
         );
+        assert_eq!(
+            AppError::from_str(AppError::RulesNotAccepted.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::RulesNotAccepted
+        );
+        assert_eq!(
+            AppError::from_str(AppError::SphereMute.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::SphereMute
+        );
+        assert_eq!(
+            AppError::from_str(AppError::SphereArchived.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::SphereArchived
+        );
+        assert_eq!(
+            AppError::from_str(AppError::AccountTooNew(7).to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::AccountTooNew(7)
+        );
+        assert_eq!(
+            AppError::from_str(AppError::InsufficientKarma(10).to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::InsufficientKarma(10)
+        );
+        assert_eq!(
+            AppError::from_str(AppError::PostTypeNotAllowed.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::PostTypeNotAllowed
+        );
+        assert_eq!(
+            AppError::from_str(AppError::CategoryRequired.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::CategoryRequired
+        );
+        assert_eq!(
+            AppError::from_str(AppError::TitleTooShort(5).to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::TitleTooShort(5)
+        );
+        assert_eq!(
+            AppError::from_str(AppError::TitleTooLong(50).to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::TitleTooLong(50)
+        );
+        assert_eq!(
+            AppError::from_str(AppError::TitleFormatMismatch(test_string.clone()).to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::TitleFormatMismatch(test_string.clone())
+        );
+        assert_eq!(
+            AppError::from_str(AppError::NsfwNotConfirmed.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::NsfwNotConfirmed
+        );
         assert_eq!(
             AppError::from_str(AppError::CommunicationError(server_fn_error.clone()).to_string().as_str()).expect("AppError should be convert to string and back"),
             AppError::CommunicationError(server_fn_error)
@@ -393,6 +651,30 @@ mod tests {
             AppError::from_str(AppError::NotFound.to_string().as_str()).expect("AppError should be convert to string and back"),
             AppError::NotFound
         );
+        assert_eq!(
+            AppError::from_str(AppError::DuplicateLink(1).to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::DuplicateLink(1)
+        );
+        assert_eq!(
+            AppError::from_str(AppError::SphereNameTaken.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::SphereNameTaken
+        );
+        assert_eq!(
+            AppError::from_str(AppError::CategoryInUse.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::CategoryInUse
+        );
+        assert_eq!(
+            AppError::from_str(AppError::ReservedName.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::ReservedName
+        );
+        assert_eq!(
+            AppError::from_str(AppError::UsernameTaken.to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::UsernameTaken
+        );
+        assert_eq!(
+            AppError::from_str(AppError::RateLimited(Some(test_timestamp)).to_string().as_str()).expect("AppError should be convert to string and back"),
+            AppError::RateLimited(Some(test_timestamp))
+        );
         assert!(AppError::from_str("invalid").is_err());
     }
 
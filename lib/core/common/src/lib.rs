@@ -5,6 +5,7 @@ pub mod constants;
 pub mod db_utils;
 pub mod editor;
 pub mod errors;
+pub mod metrics;
 pub mod routes;
 pub mod time;
 pub mod traits;
@@ -0,0 +1,10 @@
+// Names of the metrics exported in the Prometheus text format on the metrics route of the server
+pub const POSTS_CREATED_METRIC: &str = "sphare_posts_created_total";
+pub const COMMENTS_CREATED_METRIC: &str = "sphare_comments_created_total";
+pub const VOTES_METRIC: &str = "sphare_votes_total";
+pub const SERVER_FN_REQUESTS_METRIC: &str = "sphare_server_fn_requests_total";
+pub const SERVER_FN_DURATION_METRIC: &str = "sphare_server_fn_duration_seconds";
+
+pub const VOTE_VALUE_LABEL: &str = "value";
+pub const SERVER_FN_PATH_LABEL: &str = "path";
+pub const STATUS_LABEL: &str = "status";
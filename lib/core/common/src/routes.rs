@@ -13,18 +13,28 @@ pub const USER_ROUTE_PREFIX: &str = "/users";
 pub const USER_ROUTE_PARAM_NAME: &str = "username";
 pub const SPHERE_ROUTE_PREFIX: &str = "/spheres";
 pub const SPHERE_ROUTE_PARAM_NAME: &str = "sphere_name";
+pub const SPHERE_FEED_SUFFIX: &str = "/feed.xml";
+pub const SPHERE_FEED_ROUTE: &str = concatcp!(SPHERE_ROUTE_PREFIX, "/{", SPHERE_ROUTE_PARAM_NAME, "}", SPHERE_FEED_SUFFIX);
 pub const CREATE_SPHERE_SUFFIX: &str = "/sphere";
 pub const CREATE_SPHERE_ROUTE: &str = concatcp!(PUBLISH_ROUTE, CREATE_SPHERE_SUFFIX);
 pub const SATELLITE_ROUTE_PREFIX: &str = "/satellites";
 pub const SATELLITE_ROUTE_PARAM_NAME: &str = "satellite_id";
+pub const WIKI_ROUTE_PREFIX: &str = "/wiki";
+pub const WIKI_ROUTE_PARAM_NAME: &str = "wiki_slug";
 pub const CREATE_POST_SUFFIX: &str = "/post";
 pub const CREATE_POST_ROUTE: &str = concatcp!(PUBLISH_ROUTE, CREATE_POST_SUFFIX);
+pub const CREATE_POLL_SUFFIX: &str = "/poll";
+pub const CREATE_POLL_ROUTE: &str = concatcp!(PUBLISH_ROUTE, CREATE_POLL_SUFFIX);
 pub const CREATE_POST_SPHERE_QUERY_PARAM: &str = "sphere";
 pub const POST_ROUTE_PREFIX: &str = "/posts";
 pub const POST_ROUTE_PARAM_NAME: &str = "post_name";
+pub const API_V1_ROUTE_PREFIX: &str = "/api/v1";
+pub const POST_API_ROUTE: &str = concatcp!(API_V1_ROUTE_PREFIX, POST_ROUTE_PREFIX, "/{post_id}");
 pub const COMMENT_ID_QUERY_PARAM: &str = "comment_id";
+pub const COMMENT_ANCHOR_PREFIX: &str = "comment-";
 pub const SEARCH_ROUTE: &str = "/search";
 pub const NOTIFICATION_ROUTE: &str = "/notification";
+pub const REPORT_INBOX_ROUTE: &str = "/reports";
 pub const SEARCH_TAB_QUERY_PARAM: &str = "type";
 pub const ABOUT_SPHARE_ROUTE: &str = "/about_sphare";
 pub const TERMS_AND_CONDITIONS_ROUTE: &str = "/terms_and_conditions";
@@ -32,6 +42,12 @@ pub const PRIVACY_POLICY_ROUTE: &str = "/privacy_policy";
 pub const CONTENT_POLICY_ROUTE: &str = "/content_policy";
 pub const RULES_ROUTE: &str = "/rules";
 pub const FAQ_ROUTE: &str = "/faq";
+/// Top-level routes of the app, whose names cannot be used for spheres and users
+pub const TOP_LEVEL_ROUTES: [&str; 17] = [
+    AUTH_CALLBACK_ROUTE, POPULAR_ROUTE, PUBLISH_ROUTE, USER_ROUTE_PREFIX, SPHERE_ROUTE_PREFIX, SATELLITE_ROUTE_PREFIX,
+    WIKI_ROUTE_PREFIX, POST_ROUTE_PREFIX, SEARCH_ROUTE, NOTIFICATION_ROUTE, ABOUT_SPHARE_ROUTE, TERMS_AND_CONDITIONS_ROUTE,
+    PRIVACY_POLICY_ROUTE, CONTENT_POLICY_ROUTE, RULES_ROUTE, FAQ_ROUTE, REPORT_INBOX_ROUTE,
+];
 pub const GITHUB_REPO_URL: &str = "https://github.com/winteler/sphare";
 
 #[cfg(feature = "ssr")]
@@ -81,6 +97,19 @@ pub fn get_sphere_path(
     format!("{SPHERE_ROUTE_PREFIX}/{sphere_name}")
 }
 
+/// # Returns the path to the Atom feed of a sphere given its name
+///
+/// ```
+/// use sphare_core_common::routes::get_sphere_feed_path;
+///
+/// assert_eq!(get_sphere_feed_path("test"), "/spheres/test/feed.xml");
+/// ```
+pub fn get_sphere_feed_path(
+    sphere_name: &str,
+) -> String {
+    format!("{SPHERE_ROUTE_PREFIX}/{sphere_name}{SPHERE_FEED_SUFFIX}")
+}
+
 /// # Extract the sphere name from the current path, if it exists
 ///
 /// ```
@@ -161,6 +190,33 @@ pub fn get_create_post_path() -> Signal<String> {
     })
 }
 
+/// # Returns the path to a wiki page of a sphere given its slug
+///
+/// ```
+/// use sphare_core_common::routes::get_sphere_page_path;
+///
+/// assert_eq!(get_sphere_page_path("test", "faq"), "/spheres/test/wiki/faq");
+/// ```
+pub fn get_sphere_page_path(
+    sphere_name: &str,
+    slug: &str,
+) -> String {
+    format!("{SPHERE_ROUTE_PREFIX}/{sphere_name}{WIKI_ROUTE_PREFIX}/{slug}")
+}
+
+/// Get a memo returning the last valid wiki page slug from the url. Used to avoid triggering resources when leaving pages
+pub fn get_wiki_slug_memo(params: Memo<ParamsMap>) -> Memo<String> {
+    Memo::new(move |current_slug: Option<&String>| {
+        if let Some(new_slug) = params.read().get_str(WIKI_ROUTE_PARAM_NAME) {
+            log::trace!("Current wiki slug: {current_slug:?}, new wiki slug: {new_slug}");
+            new_slug.to_string()
+        } else {
+            log::trace!("Could not find new wiki slug, reuse current wiki slug: {current_slug:?}");
+            current_slug.cloned().unwrap_or_default()
+        }
+    })
+}
+
 /// # Returns the path to a post given its id, sphere and optional satellite
 ///
 /// ```
@@ -183,6 +239,17 @@ pub fn get_post_path(
     }
 }
 
+/// # Returns the path to the JSON api of a post given its id
+///
+/// ```
+/// use sphare_core_common::routes::get_post_api_path;
+///
+/// assert_eq!(get_post_api_path(1), "/api/v1/posts/1");
+/// ```
+pub fn get_post_api_path(post_id: i64) -> String {
+    format!("{API_V1_ROUTE_PREFIX}{POST_ROUTE_PREFIX}/{post_id}")
+}
+
 /// Returns the url to a post given its id, sphere and optional satellite
 pub fn get_post_link(
     sphere_name: &str,
@@ -217,6 +284,17 @@ pub fn get_comment_path(
     }
 }
 
+/// # Returns the id of the html anchor of a comment, used to scroll to comments targeted by a permalink
+///
+/// ```
+/// use sphare_core_common::routes::get_comment_anchor;
+///
+/// assert_eq!(get_comment_anchor(1), "comment-1");
+/// ```
+pub fn get_comment_anchor(comment_id: i64) -> String {
+    format!("{COMMENT_ANCHOR_PREFIX}{comment_id}")
+}
+
 /// # Returns the url to a comment given its id, post_id, sphere and optional satellite
 pub fn get_comment_link(
     sphere_name: &str,
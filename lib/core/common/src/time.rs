@@ -1,3 +1,5 @@
+use std::cmp::max;
+
 use leptos::prelude::*;
 use leptos_fluent::move_tr;
 
@@ -5,6 +7,7 @@ use crate::constants::{
     SECONDS_IN_DAY, SECONDS_IN_HOUR, SECONDS_IN_MINUTE, SECONDS_IN_MONTH, SECONDS_IN_YEAR,
 };
 
+#[derive(Debug, PartialEq)]
 enum TimeUnit {
     Seconds,
     Minutes,
@@ -33,18 +36,47 @@ impl TimeUnit {
     }
 }
 
+/// Returns the largest time unit fitting in `duration` and how many of them it contains. Negative durations count as 0 seconds.
+fn get_time_unit_and_count(duration: chrono::TimeDelta) -> (TimeUnit, i64) {
+    let seconds = max(duration.num_seconds(), 0);
+    match seconds {
+        seconds if seconds < SECONDS_IN_MINUTE => (TimeUnit::Seconds, seconds),
+        seconds if seconds < SECONDS_IN_HOUR => (TimeUnit::Minutes, seconds / SECONDS_IN_MINUTE),
+        seconds if seconds < SECONDS_IN_DAY => (TimeUnit::Hours, seconds / SECONDS_IN_HOUR),
+        seconds if seconds < SECONDS_IN_MONTH => (TimeUnit::Days, seconds / SECONDS_IN_DAY),
+        seconds if seconds < SECONDS_IN_YEAR => (TimeUnit::Months, seconds / SECONDS_IN_MONTH),
+        _ => (TimeUnit::Years, seconds / SECONDS_IN_YEAR),
+    }
+}
+
+/// Returns a localized string describing `duration` in its largest fitting time unit
+pub fn get_duration_string(
+    duration: chrono::TimeDelta,
+    use_fullname: bool,
+) -> Signal<String> {
+    let (time_unit, count) = get_time_unit_and_count(duration);
+    time_unit.to_localized_string(count, use_fullname)
+}
+
 pub fn get_elapsed_time_string(
     timestamp: chrono::DateTime<chrono::Utc>,
     use_fullname: bool,
 ) -> Signal<String> {
-    let elapsed_time = chrono::Utc::now().signed_duration_since(timestamp);
-    let seconds = elapsed_time.num_seconds();
-    match seconds {
-        seconds if seconds < SECONDS_IN_MINUTE => TimeUnit::Seconds.to_localized_string(seconds, use_fullname),
-        seconds if seconds < SECONDS_IN_HOUR => TimeUnit::Minutes.to_localized_string(seconds / SECONDS_IN_MINUTE, use_fullname),
-        seconds if seconds < SECONDS_IN_DAY => TimeUnit::Hours.to_localized_string(seconds / SECONDS_IN_HOUR, use_fullname),
-        seconds if seconds < SECONDS_IN_MONTH => TimeUnit::Days.to_localized_string(seconds / SECONDS_IN_DAY, use_fullname),
-        seconds if seconds < SECONDS_IN_YEAR => TimeUnit::Months.to_localized_string(seconds / SECONDS_IN_MONTH, use_fullname),
-        _ => TimeUnit::Years.to_localized_string(seconds / SECONDS_IN_YEAR, use_fullname),
+    get_duration_string(chrono::Utc::now().signed_duration_since(timestamp), use_fullname)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_time_unit_and_count() {
+        assert_eq!(get_time_unit_and_count(chrono::TimeDelta::seconds(-10)), (TimeUnit::Seconds, 0));
+        assert_eq!(get_time_unit_and_count(chrono::TimeDelta::seconds(59)), (TimeUnit::Seconds, 59));
+        assert_eq!(get_time_unit_and_count(chrono::TimeDelta::minutes(1)), (TimeUnit::Minutes, 1));
+        assert_eq!(get_time_unit_and_count(chrono::TimeDelta::hours(5)), (TimeUnit::Hours, 5));
+        assert_eq!(get_time_unit_and_count(chrono::TimeDelta::days(1)), (TimeUnit::Days, 1));
+        assert_eq!(get_time_unit_and_count(chrono::TimeDelta::days(45)), (TimeUnit::Months, 1));
+        assert_eq!(get_time_unit_and_count(chrono::TimeDelta::days(800)), (TimeUnit::Years, 2));
     }
-}
\ No newline at end of file
+}
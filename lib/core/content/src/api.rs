@@ -0,0 +1,22 @@
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use axum::extract::{Path, State};
+    use axum::response::{IntoResponse, Json, Response};
+    use sqlx::PgPool;
+
+    use crate::post::ssr::get_post_with_info_by_id;
+
+    /// Axum handler returning a post and its info as JSON. The API is anonymous, so the returned vote is always None.
+    pub async fn post_json_handler(
+        Path(post_id): Path<i64>,
+        State(db_pool): State<PgPool>,
+    ) -> Response {
+        match get_post_with_info_by_id(post_id, None, &db_pool).await {
+            Ok(post) => Json(post).into_response(),
+            Err(e) => {
+                log::debug!("Failed to get post {post_id} for JSON api: {e}");
+                (e.http_status(), e.to_string()).into_response()
+            }
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, IntoStaticStr};
+
+/// Action applied to a post or comment matching an automod rule, ordered by severity
+#[repr(i16)]
+#[derive(Clone, Copy, Debug, Default, Display, EnumString, Eq, IntoStaticStr, Hash, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+pub enum AutomodAction {
+    /// Reports the content to the moderators of the sphere
+    #[default]
+    Flag = 0,
+    /// Moderates the content, hiding it until a moderator reverts the moderation
+    Moderate = 1,
+}
+
+/// Keyword rule of a sphere, automatically applied to new posts and comments
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct AutomodRule {
+    pub automod_rule_id: i64,
+    pub sphere_id: i64,
+    pub pattern: String,
+    pub action: AutomodAction,
+    /// Rule set as infringed on the content moderated by this automod rule
+    pub rule_id: Option<i64>,
+    pub creator_id: i64,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl AutomodRule {
+    /// Returns whether `text` contains the pattern of the rule, ignoring case
+    pub fn is_match(&self, text: &str) -> bool {
+        text.to_lowercase().contains(&self.pattern.to_lowercase())
+    }
+}
+
+/// Returns the most severe rule of `automod_rule_vec` matched by any of the strings in `text_vec`
+pub fn find_matching_automod_rule<'a>(
+    text_vec: &[&str],
+    automod_rule_vec: &'a [AutomodRule],
+) -> Option<&'a AutomodRule> {
+    automod_rule_vec
+        .iter()
+        .filter(|rule| text_vec.iter().any(|text| rule.is_match(text)))
+        .max_by_key(|rule| rule.action)
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use sqlx::PgPool;
+    use sphare_core_common::checks::{check_sphere_name, check_string_length};
+    use sphare_core_common::constants::MAX_AUTOMOD_PATTERN_LENGTH;
+    use sphare_core_common::errors::AppError;
+    use sphare_core_sphere::rule::ssr::load_rule_by_id;
+    use sphare_core_sphere::sphere::ssr::get_sphere_by_name;
+    use sphare_core_user::mod_log::ModAction;
+    use sphare_core_user::mod_log::ssr::insert_mod_log;
+    use sphare_core_user::role::PermissionLevel;
+    use sphare_core_user::user::User;
+
+    use crate::automod::{find_matching_automod_rule, AutomodAction, AutomodRule};
+    use crate::comment::Comment;
+    use crate::comment::ssr::get_comment_by_id;
+    use crate::post::Post;
+    use crate::post::ssr::get_post_by_id;
+
+    pub async fn get_automod_rule_by_id(
+        automod_rule_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<AutomodRule, AppError> {
+        let automod_rule = sqlx::query_as::<_, AutomodRule>(
+            "SELECT * FROM automod_rules WHERE automod_rule_id = $1"
+        )
+            .bind(automod_rule_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(automod_rule)
+    }
+
+    /// Returns the automod rules of the sphere `sphere_name` ordered by pattern, only to its moderators.
+    pub async fn get_automod_rule_vec(
+        sphere_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<AutomodRule>, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+
+        let automod_rule_vec = sqlx::query_as::<_, AutomodRule>(
+            "SELECT a.* FROM automod_rules a
+            JOIN spheres s ON s.sphere_id = a.sphere_id
+            WHERE s.sphere_name = $1
+            ORDER BY a.pattern, a.automod_rule_id"
+        )
+            .bind(sphere_name)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(automod_rule_vec)
+    }
+
+    async fn get_sphere_automod_rule_vec(
+        sphere_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<AutomodRule>, AppError> {
+        let automod_rule_vec = sqlx::query_as::<_, AutomodRule>(
+            "SELECT * FROM automod_rules WHERE sphere_id = $1"
+        )
+            .bind(sphere_id)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(automod_rule_vec)
+    }
+
+    /// Creates an automod rule in the sphere `sphere_name`. Rules moderating content must reference the infringed rule `rule_id`,
+    /// which must be a rule of the sphere or a site-wide rule.
+    pub async fn create_automod_rule(
+        sphere_name: &str,
+        pattern: &str,
+        action: AutomodAction,
+        rule_id: Option<i64>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<AutomodRule, AppError> {
+        check_sphere_name(sphere_name)?;
+        let pattern = pattern.trim();
+        check_string_length(pattern, "Pattern", MAX_AUTOMOD_PATTERN_LENGTH, false)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+        let sphere = get_sphere_by_name(sphere_name, db_pool).await?;
+        match rule_id {
+            Some(rule_id) => {
+                let rule = load_rule_by_id(rule_id, db_pool).await?;
+                if rule.delete_timestamp.is_some() || rule.sphere_id.is_some_and(|rule_sphere_id| rule_sphere_id != sphere.sphere_id) {
+                    return Err(AppError::ValidationError(String::from("Automod rules must reference an active rule of their sphere.")));
+                }
+            },
+            None if action == AutomodAction::Moderate => {
+                return Err(AppError::ValidationError(String::from("Automod rules moderating content must reference the infringed rule.")));
+            },
+            None => (),
+        }
+
+        let automod_rule = sqlx::query_as::<_, AutomodRule>(
+            "INSERT INTO automod_rules (sphere_id, pattern, action, rule_id, creator_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *"
+        )
+            .bind(sphere.sphere_id)
+            .bind(pattern)
+            .bind(action as i16)
+            .bind(rule_id)
+            .bind(user.user_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(automod_rule)
+    }
+
+    pub async fn delete_automod_rule(
+        automod_rule_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        let automod_rule = get_automod_rule_by_id(automod_rule_id, db_pool).await?;
+        user.check_sphere_permissions_by_id(automod_rule.sphere_id, PermissionLevel::Moderate)?;
+
+        sqlx::query("DELETE FROM automod_rules WHERE automod_rule_id = $1")
+            .bind(automod_rule_id)
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Applies the automod rules of its sphere to the newly created or edited `post`. Posts of moderators are left untouched.
+    /// Returns the post, reloaded if it was moderated.
+    pub async fn apply_automod_rules_to_post(
+        post: Post,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        if post.is_creator_moderator {
+            return Ok(post);
+        }
+        let automod_rule_vec = get_sphere_automod_rule_vec(post.sphere_id, db_pool).await?;
+        let text_vec = [post.title.as_str(), post.markdown_body.as_deref().unwrap_or(&post.body)];
+        let Some(automod_rule) = find_matching_automod_rule(&text_vec, &automod_rule_vec) else {
+            return Ok(post);
+        };
+        log::debug!("Post {} matched automod rule {}.", post.post_id, automod_rule.automod_rule_id);
+
+        match automod_rule.action {
+            AutomodAction::Flag => {
+                insert_automod_report(post.sphere_id, post.post_id, None, automod_rule, db_pool).await?;
+                Ok(post)
+            },
+            AutomodAction::Moderate => {
+                sqlx::query(
+                    "UPDATE posts SET
+                        moderator_message = $1,
+                        infringed_rule_id = $2,
+                        moderator_id = $3
+                    WHERE post_id = $4"
+                )
+                    .bind(get_automod_message(automod_rule))
+                    .bind(automod_rule.rule_id)
+                    .bind(automod_rule.creator_id)
+                    .bind(post.post_id)
+                    .execute(db_pool)
+                    .await?;
+                insert_mod_log(
+                    post.sphere_id,
+                    ModAction::ModeratePost,
+                    automod_rule.creator_id,
+                    Some(post.creator_id),
+                    Some(post.post_id),
+                    None,
+                    db_pool,
+                ).await?;
+                get_post_by_id(post.post_id, db_pool).await
+            },
+        }
+    }
+
+    /// Applies the automod rules of the sphere `sphere_id` to the newly created or edited `comment`. Comments of moderators are left untouched.
+    /// Returns the comment, reloaded if it was moderated.
+    pub async fn apply_automod_rules_to_comment(
+        comment: Comment,
+        sphere_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<Comment, AppError> {
+        if comment.is_creator_moderator {
+            return Ok(comment);
+        }
+        let automod_rule_vec = get_sphere_automod_rule_vec(sphere_id, db_pool).await?;
+        let text_vec = [comment.markdown_body.as_deref().unwrap_or(&comment.body)];
+        let Some(automod_rule) = find_matching_automod_rule(&text_vec, &automod_rule_vec) else {
+            return Ok(comment);
+        };
+        log::debug!("Comment {} matched automod rule {}.", comment.comment_id, automod_rule.automod_rule_id);
+
+        match automod_rule.action {
+            AutomodAction::Flag => {
+                insert_automod_report(sphere_id, comment.post_id, Some(comment.comment_id), automod_rule, db_pool).await?;
+                Ok(comment)
+            },
+            AutomodAction::Moderate => {
+                sqlx::query(
+                    "UPDATE comments SET
+                        moderator_message = $1,
+                        infringed_rule_id = $2,
+                        moderator_id = $3
+                    WHERE comment_id = $4"
+                )
+                    .bind(get_automod_message(automod_rule))
+                    .bind(automod_rule.rule_id)
+                    .bind(automod_rule.creator_id)
+                    .bind(comment.comment_id)
+                    .execute(db_pool)
+                    .await?;
+                insert_mod_log(
+                    sphere_id,
+                    ModAction::ModerateComment,
+                    automod_rule.creator_id,
+                    Some(comment.creator_id),
+                    Some(comment.post_id),
+                    Some(comment.comment_id),
+                    db_pool,
+                ).await?;
+                get_comment_by_id(comment.comment_id, db_pool).await
+            },
+        }
+    }
+
+    /// Reports content matching `automod_rule` on behalf of the moderator who created the rule
+    async fn insert_automod_report(
+        sphere_id: i64,
+        post_id: i64,
+        comment_id: Option<i64>,
+        automod_rule: &AutomodRule,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO content_reports (sphere_id, post_id, comment_id, reason, reporter_id)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING"
+        )
+            .bind(sphere_id)
+            .bind(post_id)
+            .bind(comment_id)
+            .bind(get_automod_message(automod_rule))
+            .bind(automod_rule.creator_id)
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    fn get_automod_message(automod_rule: &AutomodRule) -> String {
+        format!("Automod: contains \"{}\".", automod_rule.pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::automod::{find_matching_automod_rule, AutomodAction, AutomodRule};
+
+    fn get_automod_rule(automod_rule_id: i64, pattern: &str, action: AutomodAction) -> AutomodRule {
+        AutomodRule {
+            automod_rule_id,
+            sphere_id: 1,
+            pattern: pattern.to_string(),
+            action,
+            rule_id: None,
+            creator_id: 1,
+            create_timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_automod_rule_is_match() {
+        let automod_rule = get_automod_rule(1, "Spam", AutomodAction::Flag);
+        assert!(automod_rule.is_match("spam"));
+        assert!(automod_rule.is_match("Buy cheap SPAM now"));
+        assert!(automod_rule.is_match("antispammer"));
+        assert!(!automod_rule.is_match("sp am"));
+        assert!(!automod_rule.is_match(""));
+    }
+
+    #[test]
+    fn test_find_matching_automod_rule() {
+        let flag_rule = get_automod_rule(1, "scam", AutomodAction::Flag);
+        let moderate_rule = get_automod_rule(2, "slur", AutomodAction::Moderate);
+        let automod_rule_vec = vec![flag_rule.clone(), moderate_rule.clone()];
+
+        assert_eq!(find_matching_automod_rule(&["hello", "world"], &automod_rule_vec), None);
+        assert_eq!(find_matching_automod_rule(&[], &automod_rule_vec), None);
+        assert_eq!(find_matching_automod_rule(&["hello", "a SCAM"], &[]), None);
+        assert_eq!(find_matching_automod_rule(&["hello", "a SCAM"], &automod_rule_vec), Some(&flag_rule));
+        assert_eq!(find_matching_automod_rule(&["Slur", "world"], &automod_rule_vec), Some(&moderate_rule));
+        assert_eq!(find_matching_automod_rule(&["scam and slur"], &automod_rule_vec), Some(&moderate_rule));
+    }
+}
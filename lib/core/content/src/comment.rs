@@ -1,9 +1,14 @@
+use std::collections::VecDeque;
+
 use serde::{Deserialize, Serialize};
 
 use sphare_core_common::common::SphereHeader;
+use sphare_core_common::constants::MAX_COLLAPSED_COMMENTS;
 
 use crate::post::Post;
-use crate::ranking::Vote;
+use crate::ranking::{ReactionCount, Vote};
+
+pub const COLLAPSED_COMMENTS_STORAGE: &str = "collapsed_comments";
 
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
 #[derive(Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
@@ -22,6 +27,8 @@ pub struct Comment {
     pub post_id: i64,
     pub creator_id: i64,
     pub creator_name: String,
+    #[cfg_attr(feature = "ssr", sqlx(default))]
+    pub creator_avatar_url: Option<String>,
     pub is_creator_moderator: bool,
     pub moderator_id: Option<i64>,
     #[cfg_attr(feature = "ssr", sqlx(default))]
@@ -31,6 +38,7 @@ pub struct Comment {
     pub score_minus: i32,
     pub create_timestamp: chrono::DateTime<chrono::Utc>,
     pub edit_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub edit_count: i32,
     pub delete_timestamp: Option<chrono::DateTime<chrono::Utc>>,
 }
 
@@ -38,9 +46,17 @@ pub struct Comment {
 pub struct CommentWithChildren {
     pub comment: Comment,
     pub vote: Option<Vote>,
+    pub reactions: Vec<ReactionCount>,
     pub child_comments: Vec<CommentWithChildren>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentWithAncestors {
+    /// Ancestors ordered from the root comment down to the parent, without their child comments
+    pub ancestors: Vec<CommentWithChildren>,
+    pub comment_tree: CommentWithChildren,
+}
+
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
 #[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct CommentWithContext {
@@ -52,10 +68,27 @@ pub struct CommentWithContext {
     pub post_title: String,
 }
 
+/// Set of comments collapsed by the user, persisted in the local storage to keep them collapsed on revisit.
+/// Only the `MAX_COLLAPSED_COMMENTS` most recently collapsed comments are kept, ordered from oldest to newest.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollapsedComments {
+    comment_id_set: VecDeque<i64>,
+}
+
 impl Comment {
     pub fn is_active(&self) -> bool {
         self.delete_timestamp.is_none() && self.moderator_id.is_none()
     }
+
+    /// Returns the number of upvotes, `score` being the difference between upvotes and downvotes
+    pub fn upvotes(&self) -> i32 {
+        self.score + self.score_minus
+    }
+
+    /// Returns the number of downvotes, stored in `score_minus`
+    pub fn downvotes(&self) -> i32 {
+        self.score_minus
+    }
 }
 
 impl CommentWithContext {
@@ -73,25 +106,58 @@ impl CommentWithContext {
     }
 }
 
+impl CommentWithAncestors {
+    /// Nests the focused comment tree under its ancestors, each ancestor having the next one as single child
+    pub fn into_comment_tree(self) -> CommentWithChildren {
+        self.ancestors.into_iter().rev().fold(self.comment_tree, |comment_tree, mut ancestor| {
+            ancestor.child_comments = vec![comment_tree];
+            ancestor
+        })
+    }
+}
+
+impl CollapsedComments {
+    pub fn is_collapsed(&self, comment_id: i64) -> bool {
+        self.comment_id_set.contains(&comment_id)
+    }
+
+    pub fn set_collapsed(&mut self, comment_id: i64, is_collapsed: bool) {
+        self.comment_id_set.retain(|collapsed_comment_id| *collapsed_comment_id != comment_id);
+        if is_collapsed {
+            self.comment_id_set.push_back(comment_id);
+            while self.comment_id_set.len() > MAX_COLLAPSED_COMMENTS {
+                self.comment_id_set.pop_front();
+            }
+        }
+    }
+}
+
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use std::collections::HashMap;
+
     use sqlx::PgPool;
     use sphare_core_common::checks::check_string_length;
-    use sphare_core_common::constants::{COMMENT_BATCH_SIZE, MAX_CONTENT_LENGTH};
+    use sphare_core_common::constants::{CHILD_COMMENT_BATCH_SIZE, COMMENT_BATCH_SIZE, MAX_CONTENT_LENGTH};
+    use sphare_core_common::editor::get_mentioned_usernames;
     use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
     use sphare_core_common::errors::AppError;
+    use sphare_core_common::metrics::COMMENTS_CREATED_METRIC;
     use sphare_core_sphere::sphere::ssr::get_post_sphere;
     use sphare_core_sphere::sphere::Sphere;
-    use sphare_core_user::notification::NotificationType;
-    use sphare_core_user::notification::ssr::create_notification;
+    use sphare_core_user::notification::ssr::{create_mention_notifications, create_reply_notification};
     use sphare_core_user::role::PermissionLevel;
     use sphare_core_user::user::User;
 
-    use crate::post::ssr::increment_post_comment_count;
+    use crate::automod::ssr::apply_automod_rules_to_comment;
+    use crate::moderation::ssr::is_user_muted;
+    use crate::post::ssr::{check_account_gates, decrement_post_comment_count, get_post_by_id, increment_post_comment_count};
     use crate::ranking::{SortType, VoteValue};
-    use crate::ranking::ssr::vote_on_content;
+    use crate::ranking::ssr::{get_comment_reaction_counts, vote_on_content};
     use super::*;
 
+    pub const PENDING_POST_COMMENT_STR: &str = "Cannot comment on a post pending approval.";
+
     #[derive(Clone, Debug, PartialEq, Eq, sqlx::FromRow, Ord, PartialOrd, Serialize, Deserialize)]
     pub struct CommentWithVote {
         #[sqlx(flatten)]
@@ -122,6 +188,7 @@ pub mod ssr {
             CommentWithChildren {
                 comment: self.comment,
                 vote: comment_vote,
+                reactions: Vec::new(),
                 child_comments: Vec::<CommentWithChildren>::new(),
             }
         }
@@ -135,6 +202,7 @@ pub mod ssr {
             "SELECT
                 c.*,
                 COALESCE(u.username, '') as creator_name,
+                u.avatar_url as creator_avatar_url,
                 m.username as moderator_name,
                 r.title as infringed_rule_title,
                 r.sphere_id IS NOT NULL AS is_sphere_rule
@@ -169,6 +237,45 @@ pub mod ssr {
         Ok(sphere)
     }
 
+    /// Retrieves the immediate parent of `comment_id` with its context, or None if `comment_id` is a root comment.
+    /// A deleted parent is returned as a placeholder, without its body and author.
+    pub async fn get_parent_comment(
+        comment_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<Option<CommentWithContext>, AppError> {
+        let comment = get_comment_by_id(comment_id, db_pool).await?;
+        let Some(parent_id) = comment.parent_id else {
+            return Ok(None)
+        };
+
+        let parent_comment = sqlx::query_as::<_, CommentWithContext>(
+            "SELECT
+                c.*,
+                COALESCE(u.username, '') as creator_name,
+                u.avatar_url as creator_avatar_url,
+                m.username as moderator_name,
+                r.title as infringed_rule_title,
+                r.sphere_id IS NOT NULL AS is_sphere_rule,
+                p.satellite_id,
+                p.title as post_title,
+                s.sphere_name,
+                s.icon_url,
+                s.is_nsfw
+            FROM comments c
+            JOIN posts p ON p.post_id = c.post_id
+            JOIN spheres s ON s.sphere_id = p.sphere_id
+            LEFT JOIN users u ON u.user_id = c.creator_id AND c.delete_timestamp IS NULL
+            LEFT JOIN users m ON m.user_id = c.moderator_id AND c.delete_timestamp IS NULL
+            LEFT JOIN rules r ON r.rule_id = c.infringed_rule_id AND c.delete_timestamp IS NULL
+            WHERE c.comment_id = $1"
+        )
+            .bind(parent_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(Some(parent_comment))
+    }
+
     fn process_comment_tree(
         comment_with_vote_vec: Vec<CommentWithVote>,
         allow_partial_tree: bool,
@@ -214,6 +321,66 @@ pub mod ssr {
         comment_tree
     }
 
+    /// Loads the reaction counts of the comments in `comment_tree`, including their children
+    async fn load_comment_tree_reactions(
+        mut comment_tree: Vec<CommentWithChildren>,
+        user_id: Option<i64>,
+        db_pool: &PgPool,
+    ) -> Result<Vec<CommentWithChildren>, AppError> {
+        let Some(post_id) = comment_tree.first().map(|comment| comment.comment.post_id) else {
+            return Ok(comment_tree);
+        };
+        let mut comment_id_vec = Vec::new();
+        collect_comment_ids(&comment_tree, &mut comment_id_vec);
+
+        let mut reaction_map = HashMap::<i64, Vec<ReactionCount>>::new();
+        for reaction_count in get_comment_reaction_counts(post_id, &comment_id_vec, user_id, db_pool).await? {
+            if let Some(comment_id) = reaction_count.comment_id {
+                reaction_map.entry(comment_id).or_default().push(reaction_count);
+            }
+        }
+        set_comment_tree_reactions(&mut comment_tree, &mut reaction_map);
+
+        Ok(comment_tree)
+    }
+
+    fn collect_comment_ids(comment_tree: &[CommentWithChildren], comment_id_vec: &mut Vec<i64>) {
+        for comment in comment_tree {
+            comment_id_vec.push(comment.comment.comment_id);
+            collect_comment_ids(&comment.child_comments, comment_id_vec);
+        }
+    }
+
+    fn set_comment_tree_reactions(comment_tree: &mut [CommentWithChildren], reaction_map: &mut HashMap<i64, Vec<ReactionCount>>) {
+        for comment in comment_tree {
+            comment.reactions = reaction_map.remove(&comment.comment.comment_id).unwrap_or_default();
+            set_comment_tree_reactions(&mut comment.child_comments, reaction_map);
+        }
+    }
+
+    /// Returns the recursive term of the comment tree queries, selecting the replies of the comments in `comment_tree`.
+    /// Only the first `CHILD_COMMENT_BATCH_SIZE` replies of each comment are selected, pinned replies first and then sorted by `sort_column`.
+    /// The max depth of the tree must be bound to `$3`.
+    fn get_child_comment_tree_term(sort_column: &str) -> String {
+        format!(
+            "SELECT * FROM (
+                SELECT
+                    n.*,
+                    r.depth + 1 AS depth,
+                    r.path || (n.is_pinned::INT, n.{sort_column}, n.comment_id) AS path,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY n.parent_id
+                        ORDER BY n.is_pinned DESC, n.{sort_column} DESC, n.comment_id DESC
+                    ) AS sibling_rank
+                FROM comment_tree r
+                JOIN comments n ON n.parent_id = r.comment_id
+                WHERE ($3 IS NULL OR r.depth <= $3)
+            ) AS child_comments
+            WHERE sibling_rank <= {CHILD_COMMENT_BATCH_SIZE}"
+        )
+    }
+
+    /// Retrieves the comment trees of the post `post_id`. Its accepted answer comes first, then pinned comments and the others sorted by `sort_type`.
     pub async fn get_post_comment_tree(
         post_id: i64,
         sort_type: SortType,
@@ -228,6 +395,7 @@ pub mod ssr {
         }
 
         let sort_column = sort_type.to_order_by_code();
+        let child_comment_tree_term = get_child_comment_tree_term(sort_column);
 
         let comment_with_vote_vec = sqlx::query_as::<_, CommentWithVote>(
             format!(
@@ -236,28 +404,29 @@ pub mod ssr {
                         SELECT
                             c.*,
                             1 AS depth,
-                            ARRAY[(c.is_pinned, c.{sort_column}, c.comment_id)] AS path
+                            ARRAY[(
+                                CASE WHEN c.comment_id = p.accepted_comment_id THEN 2 ELSE c.is_pinned::INT END,
+                                c.{sort_column},
+                                c.comment_id
+                            )] AS path,
+                            1::BIGINT AS sibling_rank
                         FROM comments c
+                        JOIN posts p ON p.post_id = c.post_id
                         WHERE
                             c.post_id = $2 AND
                             c.parent_id IS NULL
-                        ORDER BY c.is_pinned DESC, c.{sort_column} DESC
+                        ORDER BY (c.comment_id = p.accepted_comment_id) IS TRUE DESC, c.is_pinned DESC, c.{sort_column} DESC
                         LIMIT $4
                         OFFSET $5
                     )
                     UNION ALL (
-                        SELECT
-                            n.*,
-                            r.depth + 1,
-                            r.path || (n.is_pinned, n.{sort_column}, n.comment_id)
-                        FROM comment_tree r
-                        JOIN comments n ON n.parent_id = r.comment_id
-                        WHERE ($3 IS NULL OR r.depth <= $3)
+                        {child_comment_tree_term}
                     )
                 )
                 SELECT
                     c.*,
                     COALESCE(u.username, '') as creator_name,
+                    u.avatar_url as creator_avatar_url,
                     m.username as moderator_name,
                     r.title as infringed_rule_title,
                     r.sphere_id IS NOT NULL AS is_sphere_rule,
@@ -278,7 +447,7 @@ pub mod ssr {
         )
             .bind(user_id)
             .bind(post_id)
-            .bind(max_depth.map(|max_depth| (max_depth+ 1) as i64))
+            .bind(max_depth.map(|max_depth| (max_depth + 1) as i64))
             .bind(limit)
             .bind(offset)
             .fetch_all(db_pool)
@@ -286,7 +455,7 @@ pub mod ssr {
 
         let comment_tree = process_comment_tree(comment_with_vote_vec, false);
 
-        Ok(comment_tree)
+        load_comment_tree_reactions(comment_tree, user_id, db_pool).await
     }
 
     /// Retrieves the comment tree of `comment_id`'s parent, itself and its children
@@ -302,6 +471,7 @@ pub mod ssr {
         }
 
         let sort_column = sort_type.to_order_by_code();
+        let child_comment_tree_term = get_child_comment_tree_term(sort_column);
 
         let comment_with_vote_vec = sqlx::query_as::<_, CommentWithVote>(
             format!(
@@ -310,7 +480,8 @@ pub mod ssr {
                         SELECT
                             c.*,
                             1 AS depth,
-                            ARRAY[(c.is_pinned, c.{sort_column}, c.comment_id)] AS path
+                            ARRAY[(c.is_pinned::INT, c.{sort_column}, c.comment_id)] AS path,
+                            1::BIGINT AS sibling_rank
                         FROM comments c
                         WHERE
                             c.comment_id = $2
@@ -318,13 +489,7 @@ pub mod ssr {
                         LIMIT $4
                     )
                     UNION ALL (
-                        SELECT
-                            n.*,
-                            r.depth + 1,
-                            r.path || (n.is_pinned, n.{sort_column}, n.comment_id)
-                        FROM comment_tree r
-                        JOIN comments n ON n.parent_id = r.comment_id
-                        WHERE ($3 IS NULL OR r.depth <= $3)
+                        {child_comment_tree_term}
                     )
                 ),
                 selected_comments AS (
@@ -335,7 +500,8 @@ pub mod ssr {
                         SELECT
                             c1.*,
                             0 as depth,
-                            ARRAY[(c1.is_pinned, c1.{sort_column}, c1.comment_id)] AS path
+                            ARRAY[(c1.is_pinned::INT, c1.{sort_column}, c1.comment_id)] AS path,
+                            1::BIGINT AS sibling_rank
                         FROM comments c1
                         WHERE c1.comment_id = (
                             SELECT c2.parent_id
@@ -347,6 +513,7 @@ pub mod ssr {
                 SELECT
                     c.*,
                     COALESCE(u.username, '') as creator_name,
+                    u.avatar_url as creator_avatar_url,
                     m.username as moderator_name,
                     r.title as infringed_rule_title,
                     r.sphere_id IS NOT NULL AS is_sphere_rule,
@@ -366,12 +533,13 @@ pub mod ssr {
         )
             .bind(user_id)
             .bind(comment_id)
-            .bind(max_depth.map(|max_depth| (max_depth+ 1) as i64))
+            .bind(max_depth.map(|max_depth| (max_depth + 1) as i64))
             .bind(COMMENT_BATCH_SIZE)
             .fetch_all(db_pool)
             .await?;
 
         let comment_tree = process_comment_tree(comment_with_vote_vec, true);
+        let comment_tree = load_comment_tree_reactions(comment_tree, user_id, db_pool).await?;
 
         if comment_tree.len() > 1 {
             return Err(AppError::new(format!("Comment tree for comment {comment_id} should have a single root element.")));
@@ -380,6 +548,151 @@ pub mod ssr {
         comment_tree.into_iter().next().ok_or(AppError::new(format!("No comment tree found for comment {comment_id}")))
     }
 
+    /// Retrieves `comment_id` with its replies and the chain of its ancestors, ordered from the root comment down to its parent.
+    /// The replies are limited to `max_depth` levels below the direct replies, as in `get_comment_subtree`.
+    pub async fn get_comment_with_ancestors(
+        comment_id: i64,
+        sort_type: SortType,
+        max_depth: Option<usize>,
+        user_id: Option<i64>,
+        db_pool: &PgPool,
+    ) -> Result<CommentWithAncestors, AppError> {
+        if comment_id < 1 {
+            return Err(AppError::new("Invalid comment id."));
+        }
+
+        let comment_with_vote_vec = sqlx::query_as::<_, CommentWithVote>(
+            "WITH RECURSIVE ancestors AS (
+                SELECT c.*, 0 AS depth
+                FROM comments c
+                WHERE c.comment_id = $2
+                UNION ALL
+                SELECT p.*, a.depth + 1 AS depth
+                FROM ancestors a
+                JOIN comments p ON p.comment_id = a.parent_id
+            )
+            SELECT
+                c.*,
+                COALESCE(u.username, '') as creator_name,
+                u.avatar_url as creator_avatar_url,
+                m.username as moderator_name,
+                r.title as infringed_rule_title,
+                r.sphere_id IS NOT NULL AS is_sphere_rule,
+                v.vote_id,
+                v.user_id as vote_user_id,
+                v.post_id as vote_post_id,
+                v.comment_id as vote_comment_id,
+                v.value,
+                v.timestamp as vote_timestamp
+            FROM ancestors c
+            LEFT JOIN users u ON u.user_id = c.creator_id AND c.delete_timestamp IS NULL
+            LEFT JOIN users m ON m.user_id = c.moderator_id AND c.delete_timestamp IS NULL
+            LEFT JOIN rules r ON r.rule_id = c.infringed_rule_id AND c.delete_timestamp IS NULL
+            LEFT JOIN votes v ON v.comment_id = c.comment_id AND v.user_id = $1
+            ORDER BY c.depth DESC"
+        )
+            .bind(user_id)
+            .bind(comment_id)
+            .fetch_all(db_pool)
+            .await?;
+
+        let mut ancestors = load_comment_tree_reactions(
+            comment_with_vote_vec.into_iter().map(|comment_with_vote| comment_with_vote.into_comment_with_children()).collect(),
+            user_id,
+            db_pool,
+        ).await?;
+        let mut comment_tree = ancestors.pop().ok_or(
+            AppError::new(format!("No comment found for comment {comment_id}"))
+        )?;
+        comment_tree.child_comments = get_comment_subtree(comment_id, sort_type, max_depth, user_id, db_pool).await?;
+
+        Ok(CommentWithAncestors {
+            ancestors,
+            comment_tree,
+        })
+    }
+
+    /// Retrieves the descendants of `comment_id` as a vector of comment trees, sorted by `sort_type` with pinned comments first.
+    /// The comment itself is not included and the trees are limited to `max_depth` levels below its direct replies.
+    pub async fn get_comment_subtree(
+        comment_id: i64,
+        sort_type: SortType,
+        max_depth: Option<usize>,
+        user_id: Option<i64>,
+        db_pool: &PgPool,
+    ) -> Result<Vec<CommentWithChildren>, AppError> {
+        get_child_comments(comment_id, sort_type, max_depth, user_id, CHILD_COMMENT_BATCH_SIZE, 0, db_pool).await
+    }
+
+    /// Retrieves a batch of direct replies to `parent_id` with their own replies, sorted by `sort_type` with pinned comments first.
+    /// The batch is selected with `limit` and `offset` and the trees are limited to `max_depth` levels below the direct replies.
+    pub async fn get_child_comments(
+        parent_id: i64,
+        sort_type: SortType,
+        max_depth: Option<usize>,
+        user_id: Option<i64>,
+        limit: i64,
+        offset: i64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<CommentWithChildren>, AppError> {
+        if parent_id < 1 {
+            return Err(AppError::new("Invalid comment id."));
+        }
+
+        let sort_column = sort_type.to_order_by_code();
+        let child_comment_tree_term = get_child_comment_tree_term(sort_column);
+
+        let comment_with_vote_vec = sqlx::query_as::<_, CommentWithVote>(
+            format!(
+                "WITH RECURSIVE comment_tree AS (
+                    (
+                        SELECT
+                            c.*,
+                            1 AS depth,
+                            ARRAY[(c.is_pinned::INT, c.{sort_column}, c.comment_id)] AS path,
+                            1::BIGINT AS sibling_rank
+                        FROM comments c
+                        WHERE c.parent_id = $2
+                        ORDER BY c.is_pinned DESC, c.{sort_column} DESC, c.comment_id DESC
+                        LIMIT $4
+                        OFFSET $5
+                    )
+                    UNION ALL (
+                        {child_comment_tree_term}
+                    )
+                )
+                SELECT
+                    c.*,
+                    COALESCE(u.username, '') as creator_name,
+                    u.avatar_url as creator_avatar_url,
+                    m.username as moderator_name,
+                    r.title as infringed_rule_title,
+                    r.sphere_id IS NOT NULL AS is_sphere_rule,
+                    v.vote_id,
+                    v.user_id as vote_user_id,
+                    v.post_id as vote_post_id,
+                    v.comment_id as vote_comment_id,
+                    v.value,
+                    v.timestamp as vote_timestamp
+                FROM comment_tree c
+                LEFT JOIN users u ON u.user_id = c.creator_id AND c.delete_timestamp IS NULL
+                LEFT JOIN users m ON m.user_id = c.moderator_id AND c.delete_timestamp IS NULL
+                LEFT JOIN rules r ON r.rule_id = c.infringed_rule_id AND c.delete_timestamp IS NULL
+                LEFT JOIN votes v ON v.comment_id = c.comment_id AND v.user_id = $1
+                ORDER BY c.path DESC"
+            ).as_str(),
+        )
+            .bind(user_id)
+            .bind(parent_id)
+            .bind(max_depth.map(|max_depth| (max_depth + 1) as i64))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(db_pool)
+            .await?;
+
+        load_comment_tree_reactions(process_comment_tree(comment_with_vote_vec, true), user_id, db_pool).await
+    }
+
     pub async fn search_comments(
         search_query: &str,
         sphere_name: Option<&str>,
@@ -391,6 +704,7 @@ pub mod ssr {
             "SELECT
                 c.*,
                 u.username as creator_name,
+                u.avatar_url as creator_avatar_url,
                 p.sphere_id,
                 p.satellite_id,
                 p.title as post_title,
@@ -433,6 +747,7 @@ pub mod ssr {
     ) -> Result<CommentWithChildren, AppError> {
         log::trace!("Create comment for post {post_id}");
         check_string_length(comment, "Comment", MAX_CONTENT_LENGTH as usize, false)?;
+        let mentioned_username_vec = get_mentioned_usernames(comment);
         let (comment, markdown_comment) = get_html_and_markdown_strings(comment, is_markdown)?;
 
         let mut comment = create_comment(
@@ -457,15 +772,13 @@ pub mod ssr {
 
         comment.score = 1;
 
-        let notif_type = match parent_comment_id {
-            Some(_) => NotificationType::CommentReply,
-            None => NotificationType::PostReply,
-        };
-        create_notification(post_id, comment.parent_id, Some(comment.comment_id), user.user_id, notif_type, &db_pool).await?;
+        create_reply_notification(post_id, comment.parent_id, comment.comment_id, user.user_id, db_pool).await?;
+        create_mention_notifications(post_id, Some(comment.comment_id), &mentioned_username_vec, user.user_id, db_pool).await?;
 
         Ok(CommentWithChildren {
             comment,
             vote,
+            reactions: Vec::new(),
             child_comments: Vec::<CommentWithChildren>::default(),
         })
     }
@@ -481,9 +794,19 @@ pub mod ssr {
     ) -> Result<Comment, AppError> {
         let sphere = get_post_sphere(post_id, &db_pool).await?;
         user.check_can_publish_on_sphere(&sphere.sphere_name)?;
+        if sphere.is_archived {
+            return Err(AppError::SphereArchived);
+        }
+        if is_user_muted(user.user_id, sphere.sphere_id, db_pool).await? {
+            return Err(AppError::SphereMute);
+        }
+        check_account_gates(&sphere, user, db_pool).await?;
         if comment.is_empty() {
             return Err(AppError::new("Cannot create empty comment."));
         }
+        if !get_post_by_id(post_id, db_pool).await?.is_approved {
+            return Err(AppError::new(PENDING_POST_COMMENT_STR));
+        }
         if is_pinned {
             user.check_sphere_permissions_by_name(&sphere.sphere_name, PermissionLevel::Moderate)?;
         }
@@ -510,7 +833,13 @@ pub mod ssr {
             .fetch_one(db_pool)
             .await?;
 
-        increment_post_comment_count(post_id, &db_pool).await?;
+        let comment = apply_automod_rules_to_comment(comment, sphere.sphere_id, db_pool).await?;
+
+        if comment.moderator_id.is_none() {
+            increment_post_comment_count(post_id, db_pool).await?;
+        }
+
+        metrics::counter!(COMMENTS_CREATED_METRIC).increment(1);
 
         Ok(comment)
     }
@@ -548,8 +877,8 @@ pub mod ssr {
         user: &User,
         db_pool: &PgPool,
     ) -> Result<Comment, AppError> {
+        let sphere = get_comment_sphere(comment_id, db_pool).await?;
         if is_pinned {
-            let sphere = get_comment_sphere(comment_id, &db_pool).await?;
             user.check_sphere_permissions_by_name(&sphere.sphere_name, PermissionLevel::Moderate)?;
         }
         let comment = sqlx::query_as::<_, Comment>(
@@ -558,7 +887,8 @@ pub mod ssr {
                     body = $1,
                     markdown_body = $2,
                     is_pinned = $3,
-                    edit_timestamp = NOW()
+                    edit_timestamp = NOW(),
+                    edit_count = edit_count + 1
                 WHERE
                     comment_id = $4 AND
                     creator_id = $5 AND
@@ -577,6 +907,12 @@ pub mod ssr {
             .fetch_one(db_pool)
             .await?;
 
+        let comment = apply_automod_rules_to_comment(comment, sphere.sphere_id, db_pool).await?;
+
+        if comment.moderator_id.is_some() {
+            decrement_post_comment_count(comment.post_id, db_pool).await?;
+        }
+
         Ok(comment)
     }
 
@@ -590,13 +926,16 @@ pub mod ssr {
                 UPDATE comments SET
                     body = '',
                     markdown_body = NULL,
+                    deleted_body = body,
+                    deleted_markdown_body = markdown_body,
                     is_pinned = false,
                     edit_timestamp = NOW(),
                     delete_timestamp = NOW()
                 WHERE
                     comment_id = $1 AND
                     creator_id = $2 AND
-                    moderator_id IS NULL
+                    moderator_id IS NULL AND
+                    delete_timestamp IS NULL
                 RETURNING *
             )
             SELECT *, '' as creator_name FROM deleted_comment",
@@ -606,9 +945,46 @@ pub mod ssr {
             .fetch_one(db_pool)
             .await?;
 
+        decrement_post_comment_count(deleted_comment.post_id, db_pool).await?;
+
         Ok(deleted_comment)
     }
 
+    /// Restores a comment deleted by its author along with the content it had before its deletion. Moderated comments cannot be restored.
+    pub async fn restore_comment(
+        comment_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Comment, AppError> {
+        let restored_comment = sqlx::query_as::<_, Comment>(
+            "WITH restored_comment AS (
+                UPDATE comments SET
+                    body = COALESCE(deleted_body, body),
+                    markdown_body = CASE WHEN deleted_body IS NULL THEN markdown_body ELSE deleted_markdown_body END,
+                    deleted_body = NULL,
+                    deleted_markdown_body = NULL,
+                    edit_timestamp = NOW(),
+                    delete_timestamp = NULL
+                WHERE
+                    comment_id = $1 AND
+                    creator_id = $2 AND
+                    moderator_id IS NULL AND
+                    delete_timestamp IS NOT NULL
+                RETURNING *
+            )
+            SELECT *, $3 as creator_name FROM restored_comment",
+        )
+            .bind(comment_id)
+            .bind(user.user_id)
+            .bind(user.username.clone())
+            .fetch_one(db_pool)
+            .await?;
+
+        increment_post_comment_count(restored_comment.post_id, db_pool).await?;
+
+        Ok(restored_comment)
+    }
+
     #[cfg(test)]
     mod tests {
         use crate::comment::ssr::CommentWithVote;
@@ -655,4 +1031,65 @@ pub mod ssr {
             assert_eq!(comment_with_vote.child_comments.is_empty(), true);
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use sphare_core_common::constants::MAX_COLLAPSED_COMMENTS;
+
+    use crate::comment::CollapsedComments;
+
+    #[test]
+    fn test_collapsed_comments_set_collapsed() {
+        let mut collapsed_comments = CollapsedComments::default();
+        assert!(!collapsed_comments.is_collapsed(1));
+
+        collapsed_comments.set_collapsed(1, true);
+        collapsed_comments.set_collapsed(2, true);
+        assert!(collapsed_comments.is_collapsed(1));
+        assert!(collapsed_comments.is_collapsed(2));
+        assert!(!collapsed_comments.is_collapsed(3));
+
+        collapsed_comments.set_collapsed(1, false);
+        collapsed_comments.set_collapsed(3, false);
+        assert!(!collapsed_comments.is_collapsed(1));
+        assert!(collapsed_comments.is_collapsed(2));
+        assert!(!collapsed_comments.is_collapsed(3));
+    }
+
+    #[test]
+    fn test_collapsed_comments_capacity() {
+        let mut collapsed_comments = CollapsedComments::default();
+        for comment_id in 0..MAX_COLLAPSED_COMMENTS as i64 {
+            collapsed_comments.set_collapsed(comment_id, true);
+        }
+        // collapsing a comment again moves it to the most recent ones
+        collapsed_comments.set_collapsed(0, true);
+        collapsed_comments.set_collapsed(MAX_COLLAPSED_COMMENTS as i64, true);
+        assert!(collapsed_comments.is_collapsed(0));
+        assert!(!collapsed_comments.is_collapsed(1));
+        assert!(collapsed_comments.is_collapsed(2));
+        assert!(collapsed_comments.is_collapsed(MAX_COLLAPSED_COMMENTS as i64));
+
+        let serialized = serde_json::to_string(&collapsed_comments).expect("CollapsedComments should be serialized");
+        let deserialized: CollapsedComments = serde_json::from_str(&serialized).expect("CollapsedComments should be deserialized");
+        assert_eq!(deserialized, collapsed_comments);
+    }
+
+    #[test]
+    fn test_collapsed_comments_serialization() {
+        let mut collapsed_comments = CollapsedComments::default();
+        collapsed_comments.set_collapsed(1, true);
+        collapsed_comments.set_collapsed(42, true);
+
+        let serialized = serde_json::to_string(&collapsed_comments).expect("CollapsedComments should be serialized");
+        let deserialized: CollapsedComments = serde_json::from_str(&serialized).expect("CollapsedComments should be deserialized");
+        assert_eq!(deserialized, collapsed_comments);
+        assert!(deserialized.is_collapsed(1));
+        assert!(deserialized.is_collapsed(42));
+
+        let empty: CollapsedComments = serde_json::from_str(r#"{"comment_id_set":[]}"#).expect("Empty CollapsedComments should be deserialized");
+        assert_eq!(empty, CollapsedComments::default());
+        assert!(serde_json::from_str::<CollapsedComments>("invalid").is_err());
+    }
+}
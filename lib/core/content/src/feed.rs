@@ -0,0 +1,109 @@
+pub const ATOM_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+pub const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+pub const SPHERE_FEED_NUM_POSTS: i64 = 50;
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use std::io::Cursor;
+
+    use axum::extract::{Path, State};
+    use axum::http::header;
+    use axum::response::{IntoResponse, Response};
+    use quick_xml::events::{BytesDecl, BytesText, Event};
+    use quick_xml::Writer;
+    use sqlx::PgPool;
+
+    use sphare_core_common::errors::AppError;
+    use sphare_core_common::routes::{get_app_origin, get_post_path, get_sphere_feed_path, get_sphere_path};
+    use sphare_core_sphere::sphere::ssr::get_sphere_by_name;
+    use sphare_core_sphere::sphere::Sphere;
+
+    use crate::feed::{ATOM_CONTENT_TYPE, ATOM_NAMESPACE, SPHERE_FEED_NUM_POSTS};
+    use crate::filter::SphereCategoryFilter;
+    use crate::post::ssr::get_post_vec_by_sphere_name;
+    use crate::post::Post;
+    use crate::ranking::{PostSortType, SortType};
+
+    /// Axum handler returning the Atom feed with the most recent posts of a sphere
+    pub async fn sphere_feed_handler(
+        Path(sphere_name): Path<String>,
+        State(db_pool): State<PgPool>,
+    ) -> Response {
+        match get_sphere_atom_feed(&sphere_name, &db_pool).await {
+            Ok(feed) => ([(header::CONTENT_TYPE, ATOM_CONTENT_TYPE)], feed).into_response(),
+            Err(e) => {
+                log::debug!("Failed to get feed of sphere {sphere_name}: {e}");
+                (e.http_status(), e.to_string()).into_response()
+            }
+        }
+    }
+
+    /// Returns the Atom feed with the most recent posts of the sphere `sphere_name`
+    pub async fn get_sphere_atom_feed(
+        sphere_name: &str,
+        db_pool: &PgPool,
+    ) -> Result<String, AppError> {
+        let sphere = get_sphere_by_name(sphere_name, db_pool).await?;
+        let post_vec = get_post_vec_by_sphere_name(
+            sphere_name,
+            SphereCategoryFilter::All,
+            SortType::Post(PostSortType::Recent),
+            SPHERE_FEED_NUM_POSTS,
+            0,
+            None,
+            db_pool,
+        ).await?;
+        let base_url = get_app_origin().unwrap_or_default();
+        write_sphere_atom_feed(&sphere, &post_vec, &base_url)
+    }
+
+    /// Serializes the given sphere and posts into an Atom feed, all links are prefixed with `base_url`
+    pub fn write_sphere_atom_feed(
+        sphere: &Sphere,
+        post_vec: &[Post],
+        base_url: &str,
+    ) -> Result<String, AppError> {
+        let sphere_url = format!("{base_url}{}", get_sphere_path(&sphere.sphere_name));
+        let feed_url = format!("{base_url}{}", get_sphere_feed_path(&sphere.sphere_name));
+        let feed_update_timestamp = post_vec
+            .iter()
+            .map(|post| post.edit_timestamp.unwrap_or(post.create_timestamp))
+            .max()
+            .unwrap_or(sphere.create_timestamp);
+
+        let mut writer = Writer::new(Cursor::new(Vec::new()));
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))?;
+        writer.create_element("feed")
+            .with_attribute(("xmlns", ATOM_NAMESPACE))
+            .write_inner_content(|writer| {
+                writer.create_element("id").write_text_content(BytesText::new(&sphere_url))?;
+                writer.create_element("title").write_text_content(BytesText::new(&sphere.sphere_name))?;
+                writer.create_element("subtitle").write_text_content(BytesText::new(&sphere.description))?;
+                writer.create_element("updated").write_text_content(BytesText::new(&feed_update_timestamp.to_rfc3339()))?;
+                writer.create_element("link").with_attribute(("href", sphere_url.as_str())).write_empty()?;
+                writer.create_element("link")
+                    .with_attribute(("rel", "self"))
+                    .with_attribute(("href", feed_url.as_str()))
+                    .write_empty()?;
+                for post in post_vec {
+                    let post_url = format!("{base_url}{}", get_post_path(&sphere.sphere_name, post.satellite_id, post.post_id));
+                    let post_update_timestamp = post.edit_timestamp.unwrap_or(post.create_timestamp);
+                    writer.create_element("entry").write_inner_content(|writer| {
+                        writer.create_element("id").write_text_content(BytesText::new(&post_url))?;
+                        writer.create_element("title").write_text_content(BytesText::new(&post.title))?;
+                        writer.create_element("link").with_attribute(("href", post_url.as_str())).write_empty()?;
+                        writer.create_element("author").write_inner_content(|writer| {
+                            writer.create_element("name").write_text_content(BytesText::new(&post.creator_name))?;
+                            Ok(())
+                        })?;
+                        writer.create_element("published").write_text_content(BytesText::new(&post.create_timestamp.to_rfc3339()))?;
+                        writer.create_element("updated").write_text_content(BytesText::new(&post_update_timestamp.to_rfc3339()))?;
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+
+        Ok(String::from_utf8(writer.into_inner().into_inner())?)
+    }
+}
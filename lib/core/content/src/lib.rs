@@ -1,7 +1,11 @@
+pub mod api;
+pub mod automod;
 pub mod comment;
 pub mod embed;
+pub mod feed;
 pub mod filter;
 pub mod moderation;
+pub mod poll;
 pub mod post;
 pub mod profile;
 pub mod ranking;
@@ -5,36 +5,110 @@ use sphare_core_common::common::Rule;
 use crate::comment::Comment;
 use crate::post::Post;
 
+pub const REPORT_FETCH_LIMIT: i64 = 50;
+pub const CONTROVERSIAL_FETCH_LIMIT: i64 = 50;
+pub const MAX_CONTROVERSIAL_SINCE_HOURS: u64 = 24 * 365;
+
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum Content {
     Post(Post),
     Comment(Comment),
 }
 
+impl Content {
+    /// Returns the number of downvotes of the content
+    pub fn downvotes(&self) -> i32 {
+        match self {
+            Content::Post(post) => post.downvotes(),
+            Content::Comment(comment) => comment.downvotes(),
+        }
+    }
+
+    pub fn create_timestamp(&self) -> chrono::DateTime<chrono::Utc> {
+        match self {
+            Content::Post(post) => post.create_timestamp,
+            Content::Comment(comment) => comment.create_timestamp,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct ModerationInfo {
     pub rule: Rule,
     pub content: Content,
 }
 
+/// Report of a post or comment submitted by a user to the moderators of its sphere
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct ContentReport {
+    pub report_id: i64,
+    pub sphere_id: i64,
+    pub sphere_name: String,
+    pub post_id: i64,
+    pub comment_id: Option<i64>,
+    pub rule_id: Option<i64>,
+    pub rule_title: Option<String>,
+    pub reason: String,
+    pub reporter_id: i64,
+    pub reporter_name: String,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Restriction preventing a user from commenting in a sphere, while still allowing them to post
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct UserMute {
+    pub mute_id: i64,
+    pub user_id: i64,
+    pub username: String,
+    pub sphere_id: i64,
+    pub sphere_name: String,
+    pub moderator_id: i64,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+    pub delete_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Reusable moderation reason of a sphere, to avoid typing the same message when moderating content
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct ModReasonTemplate {
+    pub template_id: i64,
+    pub sphere_id: i64,
+    pub title: String,
+    pub reason: String,
+    pub creator_id: i64,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+    pub edit_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[cfg(feature = "ssr")]
 pub mod ssr {
-    use sqlx::PgPool;
-    use sphare_core_common::checks::check_string_length;
-    use sphare_core_common::constants::MAX_MOD_MESSAGE_LENGTH;
+    use std::cmp::{max, min, Reverse};
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    use sqlx::{PgExecutor, PgPool};
+    use sphare_core_common::checks::{check_sphere_name, check_string_length};
+    use sphare_core_common::editor::get_mentioned_usernames;
+    use sphare_core_common::constants::{MAX_BAN_DURATION_HOURS, MAX_MOD_MESSAGE_LENGTH, MAX_MOD_REASON_TITLE_LENGTH, MAX_REPORT_REASON_LENGTH};
     use sphare_core_common::errors::AppError;
     use sphare_core_sphere::rule::ssr::load_rule_by_id;
+    use sphare_core_sphere::satellite::ssr::get_satellite_by_id;
+    use sphare_core_sphere::sphere::ssr::get_sphere_by_name;
+    use sphare_core_user::mod_log::ModAction;
+    use sphare_core_user::mod_log::ssr::insert_mod_log;
     use sphare_core_user::notification::{Notification, NotificationType};
-    use sphare_core_user::notification::ssr::create_notification;
+    use sphare_core_user::notification::ssr::{create_mention_notifications, create_notification};
     use sphare_core_user::role::{AdminRole, PermissionLevel};
     use sphare_core_user::role::ssr::is_user_sphere_moderator;
     use sphare_core_user::user::{User, UserBan};
 
     use crate::comment::Comment;
     use crate::comment::ssr::{get_comment_by_id, get_comment_sphere};
-    use crate::moderation::{Content, ModerationInfo};
+    use crate::moderation::{Content, ContentReport, ModReasonTemplate, ModerationInfo, UserMute, CONTROVERSIAL_FETCH_LIMIT, MAX_CONTROVERSIAL_SINCE_HOURS, REPORT_FETCH_LIMIT};
     use crate::post::Post;
-    use crate::post::ssr::get_post_by_id;
+    use crate::post::ssr::{decrement_post_comment_count, get_post_by_id, get_post_inherited_attributes, get_post_sphere_name, increment_post_comment_count};
 
     pub async fn get_moderation_info(
         post_id: i64,
@@ -62,6 +136,434 @@ pub mod ssr {
         })
     }
 
+    /// Returns the original content of the moderated post `post_id`, only to moderators of its sphere.
+    pub async fn get_removed_post(
+        post_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        let post = get_post_by_id(post_id, db_pool).await?;
+        user.check_sphere_permissions_by_id(post.sphere_id, PermissionLevel::Moderate)?;
+        match post.moderator_id {
+            Some(_) => Ok(post),
+            None => Err(AppError::NotFound),
+        }
+    }
+
+    /// Returns the original content of the moderated comment `comment_id`, only to moderators of its sphere.
+    pub async fn get_removed_comment(
+        comment_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Comment, AppError> {
+        let sphere = get_comment_sphere(comment_id, db_pool).await?;
+        user.check_sphere_permissions_by_id(sphere.sphere_id, PermissionLevel::Moderate)?;
+        let comment = get_comment_by_id(comment_id, db_pool).await?;
+        match comment.moderator_id {
+            Some(_) => Ok(comment),
+            None => Err(AppError::NotFound),
+        }
+    }
+
+    /// Returns the posts of `sphere_name` awaiting moderator approval, oldest first.
+    pub async fn get_pending_post_vec(
+        sphere_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<Post>, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+
+        let post_vec = sqlx::query_as::<_, Post>(
+            "SELECT p.*, u.username as creator_name
+            FROM posts p
+            JOIN users u ON u.user_id = p.creator_id
+            JOIN spheres s ON s.sphere_id = p.sphere_id
+            WHERE
+                s.sphere_name = $1 AND
+                p.is_approved = FALSE AND
+                p.moderator_id IS NULL AND
+                p.delete_timestamp IS NULL
+            ORDER BY p.create_timestamp ASC",
+        )
+            .bind(sphere_name)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(post_vec)
+    }
+
+    #[derive(sqlx::FromRow)]
+    struct ApprovedPost {
+        #[sqlx(flatten)]
+        post: Post,
+        was_approved: bool,
+    }
+
+    pub async fn approve_post(
+        post_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        let sphere_name = get_post_sphere_name(post_id, db_pool).await?;
+        user.check_sphere_permissions_by_name(&sphere_name, PermissionLevel::Moderate)?;
+
+        // the previous approval is read from the locked row, so that concurrent approvals only notify mentions once
+        let ApprovedPost { post, was_approved } = sqlx::query_as::<_, ApprovedPost>(
+            "WITH previous_post AS (
+                SELECT post_id, is_approved FROM posts
+                WHERE
+                    post_id = $1 AND
+                    moderator_id IS NULL AND
+                    delete_timestamp IS NULL
+                FOR UPDATE
+            ), approved_post AS (
+                UPDATE posts p
+                SET
+                    is_approved = TRUE,
+                    publish_timestamp = CASE WHEN p.publish_at IS NULL THEN NOW() ELSE p.publish_timestamp END
+                FROM previous_post pp
+                WHERE p.post_id = pp.post_id
+                RETURNING p.*, pp.is_approved AS was_approved
+            )
+            SELECT p.*, u.username as creator_name
+            FROM approved_post p
+            JOIN users u ON u.user_id = p.creator_id",
+        )
+            .bind(post_id)
+            .fetch_optional(db_pool)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        insert_mod_log(post.sphere_id, ModAction::ApprovePost, user.user_id, Some(post.creator_id), Some(post.post_id), None, db_pool).await?;
+
+        // mentions of scheduled posts are notified when they are published
+        if !was_approved && post.publish_at.is_none() {
+            let mentioned_username_vec = get_mentioned_usernames(post.markdown_body.as_deref().unwrap_or(&post.body));
+            create_mention_notifications(post.post_id, None, &mentioned_username_vec, post.creator_id, db_pool).await?;
+        }
+
+        Ok(post)
+    }
+
+    /// Moves the post `post_id` to the satellite `new_satellite_id`, or to the sphere itself if it is None.
+    /// The satellite must belong to the same sphere as the post. NSFW and spoiler tags inherited from the previous location are
+    /// removed and the ones of the new location are applied.
+    pub async fn move_post_to_satellite(
+        post_id: i64,
+        new_satellite_id: Option<i64>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        let sphere_name = get_post_sphere_name(post_id, db_pool).await?;
+        user.check_sphere_permissions_by_name(&sphere_name, PermissionLevel::Moderate)?;
+
+        if let Some(satellite_id) = new_satellite_id {
+            let post = get_post_by_id(post_id, db_pool).await?;
+            let satellite = get_satellite_by_id(satellite_id, db_pool).await?;
+            if satellite.sphere_id != post.sphere_id {
+                return Err(AppError::new("Cannot move post to a satellite of another sphere."));
+            }
+        }
+
+        let previous_inherited_attributes = get_post_inherited_attributes(post_id, db_pool).await?;
+
+        let post = sqlx::query_as::<_, Post>(
+            "WITH moved_post AS (
+                UPDATE posts p SET
+                    satellite_id = $2,
+                    is_nsfw = (p.is_nsfw AND NOT $3) OR s.is_nsfw OR COALESCE(sa.is_nsfw, FALSE),
+                    is_spoiler = (p.is_spoiler AND NOT $4) OR COALESCE(sa.is_spoiler, FALSE)
+                FROM spheres s
+                LEFT JOIN satellites sa ON sa.satellite_id = $2
+                WHERE
+                    p.post_id = $1 AND
+                    s.sphere_id = p.sphere_id
+                RETURNING p.*
+            )
+            SELECT p.*, u.username as creator_name
+            FROM moved_post p
+            JOIN users u ON u.user_id = p.creator_id",
+        )
+            .bind(post_id)
+            .bind(new_satellite_id)
+            .bind(previous_inherited_attributes.is_nsfw)
+            .bind(previous_inherited_attributes.is_spoiler)
+            .fetch_one(db_pool)
+            .await?;
+
+        insert_mod_log(post.sphere_id, ModAction::MovePost, user.user_id, Some(post.creator_id), Some(post.post_id), None, db_pool).await?;
+
+        Ok(post)
+    }
+
+    /// Reports the post `post_id` to the moderators of its sphere, optionally for infringing the rule `rule_id`.
+    /// A user can report a given post only once.
+    pub async fn report_post(
+        post_id: i64,
+        rule_id: Option<i64>,
+        reason: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<ContentReport, AppError> {
+        let post = get_post_by_id(post_id, db_pool).await?;
+        if !post.is_active() {
+            return Err(AppError::new("Cannot report a deleted or moderated post."));
+        }
+        insert_content_report(post.sphere_id, post.post_id, None, rule_id, reason, user, db_pool).await
+    }
+
+    /// Reports the comment `comment_id` to the moderators of its sphere, optionally for infringing the rule `rule_id`.
+    /// A user can report a given comment only once.
+    pub async fn report_comment(
+        comment_id: i64,
+        rule_id: Option<i64>,
+        reason: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<ContentReport, AppError> {
+        let comment = get_comment_by_id(comment_id, db_pool).await?;
+        if !comment.is_active() {
+            return Err(AppError::new("Cannot report a deleted or moderated comment."));
+        }
+        let sphere = get_comment_sphere(comment_id, db_pool).await?;
+        insert_content_report(sphere.sphere_id, comment.post_id, Some(comment.comment_id), rule_id, reason, user, db_pool).await
+    }
+
+    async fn insert_content_report(
+        sphere_id: i64,
+        post_id: i64,
+        comment_id: Option<i64>,
+        rule_id: Option<i64>,
+        reason: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<ContentReport, AppError> {
+        // a reason is required when the report does not refer to a rule
+        check_string_length(reason, "Report reason", MAX_REPORT_REASON_LENGTH, rule_id.is_some())?;
+        if let Some(rule_id) = rule_id {
+            let rule = load_rule_by_id(rule_id, db_pool).await?;
+            if rule.sphere_id.is_some_and(|rule_sphere_id| rule_sphere_id != sphere_id) {
+                return Err(AppError::new("Cannot report content for a rule of another sphere."));
+            }
+        }
+
+        let report = sqlx::query_as::<_, ContentReport>(
+            "WITH report AS (
+                INSERT INTO content_reports (sphere_id, post_id, comment_id, rule_id, reason, reporter_id)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT DO NOTHING
+                RETURNING *
+            )
+            SELECT r.*, s.sphere_name, ru.title as rule_title, $7 as reporter_name
+            FROM report r
+            JOIN spheres s ON s.sphere_id = r.sphere_id
+            LEFT JOIN rules ru ON ru.rule_id = r.rule_id",
+        )
+            .bind(sphere_id)
+            .bind(post_id)
+            .bind(comment_id)
+            .bind(rule_id)
+            .bind(reason)
+            .bind(user.user_id)
+            .bind(user.username.clone())
+            .fetch_optional(db_pool)
+            .await?;
+
+        report.ok_or(AppError::new("Content was already reported."))
+    }
+
+    /// Returns the unresolved reports of the sphere `sphere_name`, most recent first, only to its moderators.
+    pub async fn get_reports_for_sphere(
+        sphere_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<ContentReport>, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+
+        let report_vec = sqlx::query_as::<_, ContentReport>(
+            "SELECT r.*, s.sphere_name, ru.title as rule_title, u.username as reporter_name
+            FROM content_reports r
+            JOIN spheres s ON s.sphere_id = r.sphere_id
+            JOIN users u ON u.user_id = r.reporter_id
+            LEFT JOIN rules ru ON ru.rule_id = r.rule_id
+            WHERE s.sphere_name = $1 AND r.resolve_timestamp IS NULL
+            ORDER BY r.create_timestamp DESC, r.report_id DESC",
+        )
+            .bind(sphere_name)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(report_vec)
+    }
+
+    /// Returns the unresolved reports of all spheres, most recent first, only to admins.
+    pub async fn get_global_reports(
+        limit: i64,
+        offset: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<ContentReport>, AppError> {
+        user.check_admin_role(AdminRole::Admin)?;
+
+        let report_vec = sqlx::query_as::<_, ContentReport>(
+            "SELECT r.*, s.sphere_name, ru.title as rule_title, u.username as reporter_name
+            FROM content_reports r
+            JOIN spheres s ON s.sphere_id = r.sphere_id
+            JOIN users u ON u.user_id = r.reporter_id
+            LEFT JOIN rules ru ON ru.rule_id = r.rule_id
+            WHERE r.resolve_timestamp IS NULL
+            ORDER BY r.create_timestamp DESC, r.report_id DESC
+            LIMIT $1
+            OFFSET $2",
+        )
+            .bind(limit.clamp(0, REPORT_FETCH_LIMIT))
+            .bind(max(offset, 0))
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(report_vec)
+    }
+
+    pub async fn get_content_report_by_id(
+        report_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<ContentReport, AppError> {
+        let report = sqlx::query_as::<_, ContentReport>(
+            "SELECT r.*, s.sphere_name, ru.title as rule_title, u.username as reporter_name
+            FROM content_reports r
+            JOIN spheres s ON s.sphere_id = r.sphere_id
+            JOIN users u ON u.user_id = r.reporter_id
+            LEFT JOIN rules ru ON ru.rule_id = r.rule_id
+            WHERE r.report_id = $1",
+        )
+            .bind(report_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(report)
+    }
+
+    /// Resolves the pending reports of the posts `post_id_vec`, reports of their comments are left untouched.
+    async fn resolve_post_reports<'e, E: PgExecutor<'e>>(
+        post_id_vec: &[i64],
+        executor: E,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE content_reports SET resolve_timestamp = NOW()
+            WHERE post_id = ANY($1) AND comment_id IS NULL AND resolve_timestamp IS NULL",
+        )
+            .bind(post_id_vec)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Resolves the pending reports of the comment `comment_id`.
+    async fn resolve_comment_reports<'e, E: PgExecutor<'e>>(
+        comment_id: i64,
+        executor: E,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE content_reports SET resolve_timestamp = NOW()
+            WHERE comment_id = $1 AND resolve_timestamp IS NULL",
+        )
+            .bind(comment_id)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Moderates the post or comment of the report `report_id` for infringing the rule `rule_id`, only for admins.
+    /// The rule must be a site-wide rule or a rule of the sphere of the reported content.
+    pub async fn moderate_reported_content(
+        report_id: i64,
+        rule_id: i64,
+        moderator_message: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Content, AppError> {
+        user.check_admin_role(AdminRole::Admin)?;
+        check_string_length(moderator_message, "Moderator message", MAX_MOD_MESSAGE_LENGTH, false)?;
+        let report = get_content_report_by_id(report_id, db_pool).await?;
+        let rule = load_rule_by_id(rule_id, db_pool).await?;
+        if rule.sphere_id.is_some_and(|rule_sphere_id| rule_sphere_id != report.sphere_id) {
+            return Err(AppError::new("Cannot moderate content for a rule of another sphere."));
+        }
+
+        match report.comment_id {
+            Some(comment_id) => moderate_comment(comment_id, rule_id, moderator_message, user, db_pool).await.map(Content::Comment),
+            None => moderate_post(report.post_id, rule_id, moderator_message, user, db_pool).await.map(Content::Post),
+        }
+    }
+
+    /// Returns the most downvoted posts and comments of the sphere `sphere_name` created within `since`, most downvoted first,
+    /// only to its moderators. Content without downvotes, deleted or already moderated is excluded.
+    pub async fn get_controversial_content(
+        sphere_name: &str,
+        since: Duration,
+        limit: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<Content>, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+        let since = min(since, Duration::from_hours(MAX_CONTROVERSIAL_SINCE_HOURS));
+        let limit = limit.clamp(0, CONTROVERSIAL_FETCH_LIMIT);
+
+        let post_vec = sqlx::query_as::<_, Post>(
+            "SELECT p.*, u.username as creator_name
+            FROM posts p
+            JOIN users u ON u.user_id = p.creator_id
+            JOIN spheres s ON s.sphere_id = p.sphere_id
+            WHERE
+                s.sphere_name = $1 AND
+                p.score_minus > 0 AND
+                p.moderator_id IS NULL AND
+                p.delete_timestamp IS NULL AND
+                p.create_timestamp > NOW() - $2
+            ORDER BY p.score_minus DESC, p.create_timestamp DESC
+            LIMIT $3",
+        )
+            .bind(sphere_name)
+            .bind(since)
+            .bind(limit)
+            .fetch_all(db_pool)
+            .await?;
+
+        let comment_vec = sqlx::query_as::<_, Comment>(
+            "SELECT c.*, u.username as creator_name
+            FROM comments c
+            JOIN users u ON u.user_id = c.creator_id
+            JOIN posts p ON p.post_id = c.post_id
+            JOIN spheres s ON s.sphere_id = p.sphere_id
+            WHERE
+                s.sphere_name = $1 AND
+                c.score_minus > 0 AND
+                c.moderator_id IS NULL AND
+                c.delete_timestamp IS NULL AND
+                c.create_timestamp > NOW() - $2
+            ORDER BY c.score_minus DESC, c.create_timestamp DESC
+            LIMIT $3",
+        )
+            .bind(sphere_name)
+            .bind(since)
+            .bind(limit)
+            .fetch_all(db_pool)
+            .await?;
+
+        let mut content_vec: Vec<Content> = post_vec.into_iter().map(Content::Post)
+            .chain(comment_vec.into_iter().map(Content::Comment))
+            .collect();
+        content_vec.sort_by_key(|content| Reverse((content.downvotes(), content.create_timestamp())));
+        content_vec.truncate(usize::try_from(limit).unwrap_or_default());
+
+        Ok(content_vec)
+    }
+
     pub async fn moderate_post_and_ban_user(
         post_id: i64,
         rule_id: i64,
@@ -110,6 +612,27 @@ pub mod ssr {
         Ok((post, user_ban, notif))
     }
 
+    /// Moderates the post `post_id` with the reason of the template `template_id`, which must belong to the post's sphere.
+    pub async fn moderate_post_with_template(
+        post_id: i64,
+        rule_id: i64,
+        template_id: i64,
+        ban_duration_days: Option<usize>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(Post, Option<UserBan>, Option<Notification>), AppError> {
+        let post = get_post_by_id(post_id, db_pool).await?;
+        if user.check_admin_role(AdminRole::Moderator).is_err() {
+            user.check_sphere_permissions_by_id(post.sphere_id, PermissionLevel::Moderate)?;
+        }
+        let template = get_mod_reason_template_by_id(template_id, db_pool).await?;
+        if template.sphere_id != post.sphere_id {
+            return Err(AppError::NotFound);
+        }
+
+        moderate_post_and_ban_user(post_id, rule_id, &template.reason, ban_duration_days, user, db_pool).await
+    }
+
     pub async fn moderate_post(
         post_id: i64,
         rule_id: i64,
@@ -117,6 +640,7 @@ pub mod ssr {
         user: &User,
         db_pool: &PgPool,
     ) -> Result<Post, AppError> {
+        let mut tx = db_pool.begin().await?;
         let post = if user.check_admin_role(AdminRole::Moderator).is_ok() {
             sqlx::query_as::<_, Post>(
                 "WITH moderated_post AS (
@@ -144,7 +668,7 @@ pub mod ssr {
                 .bind(user.user_id)
                 .bind(post_id)
                 .bind(user.username.clone())
-                .fetch_one(db_pool)
+                .fetch_one(&mut *tx)
                 .await?
         } else {
             sqlx::query_as::<_, Post>(
@@ -180,13 +704,88 @@ pub mod ssr {
                 .bind(user.user_id)
                 .bind(post_id)
                 .bind(user.username.clone())
-                .fetch_one(db_pool)
+                .fetch_one(&mut *tx)
                 .await?
         };
 
+        resolve_post_reports(&[post.post_id], &mut *tx).await?;
+        insert_mod_log(post.sphere_id, ModAction::ModeratePost, user.user_id, Some(post.creator_id), Some(post.post_id), None, &mut *tx).await?;
+        tx.commit().await?;
+
         Ok(post)
     }
 
+    /// Moderates all posts of `post_id_vec` for infringing the rule `rule_id` in a single transaction.
+    /// The user's permissions are checked once per distinct sphere and no post is moderated if any of them cannot be.
+    pub async fn moderate_posts(
+        post_id_vec: Vec<i64>,
+        rule_id: i64,
+        moderator_message: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<Post>, AppError> {
+        log::trace!("Moderate posts {post_id_vec:?}");
+        check_string_length(moderator_message, "Moderation message", MAX_MOD_MESSAGE_LENGTH, false)?;
+        let post_id_set: HashSet<i64> = post_id_vec.into_iter().collect();
+        if post_id_set.is_empty() {
+            return Ok(Vec::new());
+        }
+        let post_id_vec: Vec<i64> = post_id_set.into_iter().collect();
+
+        let mut tx = db_pool.begin().await?;
+        let post_sphere_vec = sqlx::query_as::<_, (i64, i64)>(
+            "SELECT post_id, sphere_id FROM posts WHERE post_id = ANY($1) FOR UPDATE"
+        )
+            .bind(&post_id_vec)
+            .fetch_all(&mut *tx)
+            .await?;
+        if post_sphere_vec.len() != post_id_vec.len() {
+            return Err(AppError::NotFound);
+        }
+        let sphere_id_set: HashSet<i64> = post_sphere_vec.into_iter().map(|(_, sphere_id)| sphere_id).collect();
+        for sphere_id in sphere_id_set {
+            user.check_sphere_permissions_by_id(sphere_id, PermissionLevel::Moderate)?;
+        }
+
+        let post_vec = sqlx::query_as::<_, Post>(
+            "WITH moderated_post AS (
+                UPDATE posts SET
+                    moderator_message = $1,
+                    infringed_rule_id = $2,
+                    edit_timestamp = NOW(),
+                    moderator_id = $3
+                WHERE
+                    post_id = ANY($4)
+                RETURNING *
+            )
+            SELECT
+                p.*,
+                u.username as creator_name,
+                $5 as moderator_name,
+                r.title as infringed_rule_title,
+                r.sphere_id IS NOT NULL AS is_sphere_rule
+            FROM moderated_post p
+            JOIN users u ON u.user_id = p.creator_id
+            JOIN rules r ON r.rule_id = p.infringed_rule_id
+            ORDER BY p.post_id",
+        )
+            .bind(moderator_message)
+            .bind(rule_id)
+            .bind(user.user_id)
+            .bind(&post_id_vec)
+            .bind(user.username.clone())
+            .fetch_all(&mut *tx)
+            .await?;
+
+        for post in &post_vec {
+            insert_mod_log(post.sphere_id, ModAction::ModeratePost, user.user_id, Some(post.creator_id), Some(post.post_id), None, &mut *tx).await?;
+        }
+        resolve_post_reports(&post_id_vec, &mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(post_vec)
+    }
+
     pub async fn moderate_comment_and_ban_user(
         comment_id: i64,
         rule_id: i64,
@@ -244,6 +843,15 @@ pub mod ssr {
         user: &User,
         db_pool: &PgPool,
     ) -> Result<Comment, AppError> {
+        let sphere = get_comment_sphere(comment_id, db_pool).await?;
+        let mut tx = db_pool.begin().await?;
+        // lock the comment so that concurrent moderations cannot both decrement the comment count of the post
+        let was_visible = sqlx::query_scalar::<_, bool>(
+            "SELECT moderator_id IS NULL AND delete_timestamp IS NULL FROM comments WHERE comment_id = $1 FOR UPDATE"
+        )
+            .bind(comment_id)
+            .fetch_one(&mut *tx)
+            .await?;
         let comment = if user.check_admin_role(AdminRole::Moderator).is_ok() {
             sqlx::query_as::<_, Comment>(
                 "WITH moderated_comment AS (
@@ -271,7 +879,7 @@ pub mod ssr {
                 .bind(user.user_id)
                 .bind(comment_id)
                 .bind(user.username.clone())
-                .fetch_one(db_pool)
+                .fetch_one(&mut *tx)
                 .await?
         } else {
             // check if the user has at least the moderate permission for this sphere
@@ -309,13 +917,353 @@ pub mod ssr {
                 .bind(user.user_id)
                 .bind(comment_id)
                 .bind(user.username.clone())
-                .fetch_one(db_pool)
+                .fetch_one(&mut *tx)
                 .await?
         };
 
+        if was_visible {
+            decrement_post_comment_count(comment.post_id, &mut *tx).await?;
+        }
+        resolve_comment_reports(comment.comment_id, &mut *tx).await?;
+
+        insert_mod_log(
+            sphere.sphere_id,
+            ModAction::ModerateComment,
+            user.user_id,
+            Some(comment.creator_id),
+            Some(comment.post_id),
+            Some(comment.comment_id),
+            &mut *tx,
+        ).await?;
+        tx.commit().await?;
+
         Ok(comment)
     }
 
+    /// Reverts the moderation of the post `post_id`, making it visible again. Only the moderators of its sphere can do it.
+    pub async fn unmoderate_post(
+        post_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        let post = get_post_by_id(post_id, db_pool).await?;
+        user.check_sphere_permissions_by_id(post.sphere_id, PermissionLevel::Moderate)?;
+        if post.moderator_id.is_none() {
+            return Err(AppError::new("Post is not moderated."));
+        }
+
+        let mut tx = db_pool.begin().await?;
+        sqlx::query(
+            "UPDATE posts SET
+                moderator_id = NULL,
+                moderator_message = NULL,
+                infringed_rule_id = NULL,
+                edit_timestamp = NOW()
+            WHERE post_id = $1",
+        )
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await?;
+
+        insert_mod_log(post.sphere_id, ModAction::UnmoderatePost, user.user_id, Some(post.creator_id), Some(post.post_id), None, &mut *tx).await?;
+        tx.commit().await?;
+
+        get_post_by_id(post_id, db_pool).await
+    }
+
+    /// Reverts the moderation of the comment `comment_id`, making it visible again. Only the moderators of its sphere can do it.
+    pub async fn unmoderate_comment(
+        comment_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Comment, AppError> {
+        let comment = get_comment_by_id(comment_id, db_pool).await?;
+        let sphere = get_comment_sphere(comment_id, db_pool).await?;
+        user.check_sphere_permissions_by_id(sphere.sphere_id, PermissionLevel::Moderate)?;
+        if comment.moderator_id.is_none() {
+            return Err(AppError::new("Comment is not moderated."));
+        }
+
+        let mut tx = db_pool.begin().await?;
+        sqlx::query(
+            "UPDATE comments SET
+                moderator_id = NULL,
+                moderator_message = NULL,
+                infringed_rule_id = NULL,
+                edit_timestamp = NOW()
+            WHERE comment_id = $1",
+        )
+            .bind(comment_id)
+            .execute(&mut *tx)
+            .await?;
+
+        if comment.delete_timestamp.is_none() {
+            increment_post_comment_count(comment.post_id, &mut *tx).await?;
+        }
+
+        insert_mod_log(
+            sphere.sphere_id,
+            ModAction::UnmoderateComment,
+            user.user_id,
+            Some(comment.creator_id),
+            Some(comment.post_id),
+            Some(comment.comment_id),
+            &mut *tx,
+        ).await?;
+        tx.commit().await?;
+
+        get_comment_by_id(comment_id, db_pool).await
+    }
+
+    pub async fn get_mod_reason_template_by_id(
+        template_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<ModReasonTemplate, AppError> {
+        let template = sqlx::query_as::<_, ModReasonTemplate>(
+            "SELECT * FROM mod_reason_templates WHERE template_id = $1"
+        )
+            .bind(template_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(template)
+    }
+
+    /// Returns the moderation reason templates of the sphere `sphere_name` ordered by title, only to its moderators.
+    pub async fn get_mod_reason_template_vec(
+        sphere_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<ModReasonTemplate>, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+
+        let template_vec = sqlx::query_as::<_, ModReasonTemplate>(
+            "SELECT t.* FROM mod_reason_templates t
+            JOIN spheres s ON s.sphere_id = t.sphere_id
+            WHERE s.sphere_name = $1
+            ORDER BY t.title, t.template_id"
+        )
+            .bind(sphere_name)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(template_vec)
+    }
+
+    pub async fn create_mod_reason_template(
+        sphere_name: &str,
+        title: &str,
+        reason: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<ModReasonTemplate, AppError> {
+        check_sphere_name(sphere_name)?;
+        check_string_length(title, "Title", MAX_MOD_REASON_TITLE_LENGTH, false)?;
+        check_string_length(reason, "Reason", MAX_MOD_MESSAGE_LENGTH, false)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+
+        let template = sqlx::query_as::<_, ModReasonTemplate>(
+            "INSERT INTO mod_reason_templates (sphere_id, title, reason, creator_id)
+            VALUES (
+                (SELECT sphere_id FROM spheres WHERE sphere_name = $1),
+                $2, $3, $4
+            ) RETURNING *"
+        )
+            .bind(sphere_name)
+            .bind(title)
+            .bind(reason)
+            .bind(user.user_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(template)
+    }
+
+    pub async fn update_mod_reason_template(
+        template_id: i64,
+        title: &str,
+        reason: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<ModReasonTemplate, AppError> {
+        check_string_length(title, "Title", MAX_MOD_REASON_TITLE_LENGTH, false)?;
+        check_string_length(reason, "Reason", MAX_MOD_MESSAGE_LENGTH, false)?;
+        let template = get_mod_reason_template_by_id(template_id, db_pool).await?;
+        user.check_sphere_permissions_by_id(template.sphere_id, PermissionLevel::Moderate)?;
+
+        let template = sqlx::query_as::<_, ModReasonTemplate>(
+            "UPDATE mod_reason_templates SET
+                title = $1,
+                reason = $2,
+                edit_timestamp = NOW()
+            WHERE template_id = $3
+            RETURNING *"
+        )
+            .bind(title)
+            .bind(reason)
+            .bind(template_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(template)
+    }
+
+    pub async fn delete_mod_reason_template(
+        template_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        let template = get_mod_reason_template_by_id(template_id, db_pool).await?;
+        user.check_sphere_permissions_by_id(template.sphere_id, PermissionLevel::Moderate)?;
+
+        sqlx::query("DELETE FROM mod_reason_templates WHERE template_id = $1")
+            .bind(template_id)
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Bans the user `user_id` from the sphere `sphere_name` for `duration`, without referring to a moderated post or comment.
+    /// The ban stops being enforced once it expires.
+    pub async fn ban_user_temporarily(
+        user_id: i64,
+        sphere_name: &str,
+        duration: chrono::Duration,
+        reason: &str,
+        moderator: &User,
+        db_pool: &PgPool,
+    ) -> Result<UserBan, AppError> {
+        check_sphere_name(sphere_name)?;
+        check_string_length(reason, "Ban reason", MAX_MOD_MESSAGE_LENGTH, false)?;
+        moderator.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Ban)?;
+        if duration <= chrono::Duration::zero() {
+            return Err(AppError::new("Ban duration must be positive."));
+        }
+        let duration = min(duration, chrono::TimeDelta::hours(MAX_BAN_DURATION_HOURS as i64));
+        let until_timestamp = chrono::Utc::now().checked_add_signed(duration).ok_or(AppError::new("Invalid ban duration."))?;
+        let sphere = get_sphere_by_name(sphere_name, db_pool).await?;
+        if moderator.user_id == user_id || is_user_sphere_moderator(user_id, sphere.sphere_id, db_pool).await? {
+            return Err(AppError::new("Cannot ban yourself or a moderator of the sphere."));
+        }
+
+        let user_ban = sqlx::query_as!(
+            UserBan,
+            "WITH ban AS (
+                INSERT INTO user_bans (user_id, sphere_id, reason, moderator_id, until_timestamp)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING *
+            )
+            SELECT b.*, u.username, s.sphere_name FROM ban b
+            JOIN users u ON u.user_id = b.user_id
+            JOIN spheres s ON s.sphere_id = b.sphere_id",
+            user_id,
+            sphere.sphere_id,
+            reason,
+            moderator.user_id,
+            until_timestamp,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        insert_mod_log(sphere.sphere_id, ModAction::BanUser, moderator.user_id, Some(user_id), None, None, db_pool).await?;
+
+        Ok(user_ban)
+    }
+
+    /// Prevents the user `user_id` from commenting in the sphere `sphere_name` until they are unmuted, they can still create posts.
+    pub async fn mute_user(
+        user_id: i64,
+        sphere_name: &str,
+        moderator: &User,
+        db_pool: &PgPool,
+    ) -> Result<UserMute, AppError> {
+        check_sphere_name(sphere_name)?;
+        moderator.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+        let sphere = get_sphere_by_name(sphere_name, db_pool).await?;
+        if moderator.user_id == user_id || is_user_sphere_moderator(user_id, sphere.sphere_id, db_pool).await? {
+            return Err(AppError::new("Cannot mute yourself or a moderator of the sphere."));
+        }
+
+        let user_mute = sqlx::query_as::<_, UserMute>(
+            "WITH mute AS (
+                INSERT INTO user_mutes (user_id, sphere_id, moderator_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT DO NOTHING
+                RETURNING *
+            )
+            SELECT m.*, u.username, s.sphere_name FROM mute m
+            JOIN users u ON u.user_id = m.user_id
+            JOIN spheres s ON s.sphere_id = m.sphere_id",
+        )
+            .bind(user_id)
+            .bind(sphere.sphere_id)
+            .bind(moderator.user_id)
+            .fetch_optional(db_pool)
+            .await?
+            .ok_or(AppError::new("User is already muted in this sphere."))?;
+
+        insert_mod_log(sphere.sphere_id, ModAction::MuteUser, moderator.user_id, Some(user_id), None, None, db_pool).await?;
+
+        Ok(user_mute)
+    }
+
+    /// Allows the user `user_id` to comment again in the sphere `sphere_name`
+    pub async fn unmute_user(
+        user_id: i64,
+        sphere_name: &str,
+        moderator: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        check_sphere_name(sphere_name)?;
+        moderator.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+        let sphere = get_sphere_by_name(sphere_name, db_pool).await?;
+
+        let result = sqlx::query(
+            "UPDATE user_mutes SET delete_timestamp = NOW()
+            WHERE
+                user_id = $1 AND
+                sphere_id = $2 AND
+                delete_timestamp IS NULL",
+        )
+            .bind(user_id)
+            .bind(sphere.sphere_id)
+            .execute(db_pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound);
+        }
+
+        insert_mod_log(sphere.sphere_id, ModAction::UnmuteUser, moderator.user_id, Some(user_id), None, None, db_pool).await?;
+
+        Ok(())
+    }
+
+    /// Returns whether the user `user_id` is currently muted in the sphere `sphere_id`
+    pub async fn is_user_muted(
+        user_id: i64,
+        sphere_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<bool, AppError> {
+        let is_muted = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (
+                SELECT 1 FROM user_mutes
+                WHERE
+                    user_id = $1 AND
+                    sphere_id = $2 AND
+                    delete_timestamp IS NULL
+            )",
+        )
+            .bind(user_id)
+            .bind(sphere_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(is_muted)
+    }
+
     pub async fn ban_user_from_sphere(
         user_id: i64,
         sphere_id: i64,
@@ -351,13 +1299,16 @@ pub mod ssr {
                             comment_id,
                             rule_id,
                             user.user_id,
-                            ban_duration.map(|duration| duration as f64),
+                            ban_duration.map(|duration| min(duration, MAX_BAN_DURATION_HOURS as usize / 24) as f64),
                         )
                             .fetch_one(db_pool)
                             .await?
                     )
                 }
             };
+            if user_ban.is_some() {
+                insert_mod_log(sphere_id, ModAction::BanUser, user.user_id, Some(user_id), Some(post_id), comment_id, db_pool).await?;
+            }
             Ok(user_ban)
         } else {
             Err(AppError::InternalServerError(format!("Error while trying to ban user {user_id}. Insufficient permissions or user is a moderator of the sphere.")))
@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+
+/// Poll attached to a post, users can vote for one of its options until `ends_at`
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+pub struct Poll {
+    pub poll_id: i64,
+    pub post_id: i64,
+    pub ends_at: chrono::DateTime<chrono::Utc>,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+pub struct PollOption {
+    pub option_id: i64,
+    pub poll_id: i64,
+    pub option_text: String,
+    pub position: i32,
+    #[cfg_attr(feature = "ssr", sqlx(default))]
+    pub num_votes: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+pub struct PollWithResults {
+    pub poll: Poll,
+    pub option_vec: Vec<PollOption>,
+    /// Option voted for by the current user
+    pub user_option_id: Option<i64>,
+}
+
+impl Poll {
+    pub fn is_closed(&self) -> bool {
+        self.ends_at <= chrono::Utc::now()
+    }
+}
+
+impl PollWithResults {
+    /// Results are revealed once the user has voted or the poll is closed
+    pub fn show_results(&self) -> bool {
+        self.user_option_id.is_some() || self.poll.is_closed()
+    }
+
+    pub fn total_votes(&self) -> i64 {
+        self.option_vec.iter().map(|option| option.num_votes).sum()
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use std::collections::HashSet;
+
+    use sqlx::PgPool;
+
+    use sphare_core_common::checks::check_string_length;
+    use sphare_core_common::constants::{MAX_POLL_OPTIONS, MAX_POLL_OPTION_LENGTH, MIN_POLL_OPTIONS};
+    use sphare_core_common::errors::AppError;
+    use sphare_core_user::user::User;
+
+    use crate::embed::Link;
+    use crate::poll::{Poll, PollOption, PollWithResults};
+    use crate::post::ssr::{check_post_creation, insert_post_row, process_created_post, NewPost};
+    use crate::post::{Post, PostTags};
+    use crate::ranking::VoteValue;
+    use crate::ranking::ssr::vote_on_content;
+
+    pub const POLL_CLOSED_STR: &str = "This poll is closed.";
+    pub const POLL_ALREADY_VOTED_STR: &str = "You already voted in this poll.";
+
+    /// Creates a post in the sphere `sphere_name` with a poll offering the given `option_vec`, open until `ends_at`
+    pub async fn create_poll(
+        sphere_name: &str,
+        title: &str,
+        option_vec: &[String],
+        ends_at: chrono::DateTime<chrono::Utc>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(Post, Poll), AppError> {
+        let option_vec: Vec<&str> = option_vec.iter().map(|option| option.trim()).collect();
+        if option_vec.len() < MIN_POLL_OPTIONS || option_vec.len() > MAX_POLL_OPTIONS {
+            return Err(AppError::ValidationError(format!(
+                "A poll must have between {MIN_POLL_OPTIONS} and {MAX_POLL_OPTIONS} options."
+            )));
+        }
+        for option in &option_vec {
+            check_string_length(option, "Poll option", MAX_POLL_OPTION_LENGTH, false)?;
+        }
+        if option_vec.iter().collect::<HashSet<_>>().len() != option_vec.len() {
+            return Err(AppError::ValidationError(String::from("Poll options must be unique.")));
+        }
+        if ends_at <= chrono::Utc::now() {
+            return Err(AppError::new("Cannot create a poll ending in the past."));
+        }
+
+        let post_tags = PostTags::default();
+        check_post_creation(sphere_name, title, &Link::default(), &post_tags, user, db_pool).await?;
+
+        let mut tx = db_pool.begin().await?;
+
+        let new_post = NewPost {
+            sphere_name,
+            title,
+            post_tags,
+            ..Default::default()
+        };
+        let post = insert_post_row(new_post, None, None, user, &mut *tx).await?;
+
+        let poll = sqlx::query_as::<_, Poll>(
+            "INSERT INTO polls (post_id, ends_at) VALUES ($1, $2) RETURNING *"
+        )
+            .bind(post.post_id)
+            .bind(ends_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO poll_options (poll_id, option_text, position)
+            SELECT $1, option_text, position - 1
+            FROM UNNEST($2::TEXT[]) WITH ORDINALITY AS o(option_text, position)"
+        )
+            .bind(poll.poll_id)
+            .bind(&option_vec)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        let post = process_created_post(post, db_pool).await?;
+
+        vote_on_content(VoteValue::Up, post.post_id, None, None, user, db_pool).await?;
+
+        Ok((post, poll))
+    }
+
+    /// Returns the poll of the post `post_id` with its vote counts and the option voted for by `user_id`, if any
+    pub async fn get_poll_by_post_id(
+        post_id: i64,
+        user_id: Option<i64>,
+        db_pool: &PgPool,
+    ) -> Result<Option<PollWithResults>, AppError> {
+        let poll = sqlx::query_as::<_, Poll>(
+            "SELECT * FROM polls WHERE post_id = $1"
+        )
+            .bind(post_id)
+            .fetch_optional(db_pool)
+            .await?;
+
+        match poll {
+            Some(poll) => Ok(Some(get_poll_results(poll, user_id, db_pool).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Votes for the option `option_id` of the poll `poll_id`, a user can vote only once and only until the poll is closed
+    pub async fn vote_poll(
+        poll_id: i64,
+        option_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<PollWithResults, AppError> {
+        let poll = sqlx::query_as::<_, Poll>(
+            "SELECT pl.*
+            FROM polls pl
+            JOIN posts p ON p.post_id = pl.post_id
+            WHERE pl.poll_id = $1 AND p.delete_timestamp IS NULL AND p.moderator_id IS NULL"
+        )
+            .bind(poll_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        let sphere_name = sqlx::query_scalar::<_, String>(
+            "SELECT s.sphere_name FROM posts p JOIN spheres s ON s.sphere_id = p.sphere_id WHERE p.post_id = $1"
+        )
+            .bind(poll.post_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        user.check_can_publish_on_sphere(&sphere_name)?;
+        if poll.is_closed() {
+            return Err(AppError::new(POLL_CLOSED_STR));
+        }
+
+        let result = sqlx::query(
+            "INSERT INTO poll_votes (poll_id, option_id, user_id)
+            SELECT o.poll_id, o.option_id, $3
+            FROM poll_options o
+            JOIN polls pl ON pl.poll_id = o.poll_id
+            WHERE
+                o.poll_id = $1 AND
+                o.option_id = $2 AND
+                pl.ends_at > NOW()
+            ON CONFLICT ON CONSTRAINT unique_poll_vote DO NOTHING"
+        )
+            .bind(poll_id)
+            .bind(option_id)
+            .bind(user.user_id)
+            .execute(db_pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            let has_voted = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS (SELECT * FROM poll_votes WHERE poll_id = $1 AND user_id = $2)"
+            )
+                .bind(poll_id)
+                .bind(user.user_id)
+                .fetch_one(db_pool)
+                .await?;
+            return match has_voted {
+                true => Err(AppError::new(POLL_ALREADY_VOTED_STR)),
+                false => Err(AppError::NotFound),
+            };
+        }
+
+        get_poll_results(poll, Some(user.user_id), db_pool).await
+    }
+
+    async fn get_poll_results(
+        poll: Poll,
+        user_id: Option<i64>,
+        db_pool: &PgPool,
+    ) -> Result<PollWithResults, AppError> {
+        let option_vec = sqlx::query_as::<_, PollOption>(
+            "SELECT o.*, COUNT(v.poll_vote_id) AS num_votes
+            FROM poll_options o
+            LEFT JOIN poll_votes v ON v.option_id = o.option_id
+            WHERE o.poll_id = $1
+            GROUP BY o.option_id
+            ORDER BY o.position"
+        )
+            .bind(poll.poll_id)
+            .fetch_all(db_pool)
+            .await?;
+
+        let user_option_id = match user_id {
+            Some(user_id) => sqlx::query_scalar::<_, i64>(
+                "SELECT option_id FROM poll_votes WHERE poll_id = $1 AND user_id = $2"
+            )
+                .bind(poll.poll_id)
+                .bind(user_id)
+                .fetch_optional(db_pool)
+                .await?,
+            None => None,
+        };
+
+        Ok(PollWithResults {
+            poll,
+            option_vec,
+            user_option_id,
+        })
+    }
+}
@@ -1,14 +1,19 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use sphare_core_common::checks::{check_post_title, check_sphere_name};
 use sphare_core_common::common::SphereCategoryHeader;
-use sphare_core_common::constants::{MAX_CONTENT_LENGTH, MAX_LINK_LENGTH};
+use sphare_core_common::constants::{MAX_ALT_TEXT_LENGTH, MAX_CONTENT_LENGTH, MAX_LINK_LENGTH, WILSON_SCORE_Z};
+
+use sphare_core_user::user::NsfwMode;
 
 use crate::embed::{EmbedType, Link};
-use crate::ranking::Vote;
+use crate::ranking::{ReactionCount, Vote};
+
+pub const READING_WORDS_PER_MINUTE: usize = 200;
 
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
 #[derive(Clone, Debug, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -25,8 +30,11 @@ pub struct Post {
     pub is_edited: bool,
     pub sphere_id: i64,
     pub satellite_id: Option<i64>,
+    pub crosspost_of: Option<i64>,
     pub creator_id: i64,
     pub creator_name: String,
+    #[cfg_attr(feature = "ssr", sqlx(default))]
+    pub creator_avatar_url: Option<String>,
     pub is_creator_moderator: bool,
     pub moderator_message: Option<String>,
     pub infringed_rule_id: Option<i64>,
@@ -39,14 +47,28 @@ pub struct Post {
     pub moderator_name: Option<String>,
     pub num_comments: i32,
     pub is_pinned: bool,
+    pub pin_order: i32,
+    /// Top-level comment marked by the post's author as the answer to the post, displayed above the other comments
+    pub accepted_comment_id: Option<i64>,
+    pub is_approved: bool,
     pub score: i32,
     pub score_minus: i32,
     pub recommended_score: f32,
     pub trending_score: f32,
     pub create_timestamp: chrono::DateTime<chrono::Utc>,
     pub edit_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub edit_count: i32,
     pub scoring_timestamp: chrono::DateTime<chrono::Utc>,
     pub delete_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// Publication time of a scheduled post, the post is hidden from feeds until it is published
+    #[cfg_attr(feature = "ssr", sqlx(default))]
+    pub publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time at which the post became visible to all users, i.e. when it was both approved and published
+    #[cfg_attr(feature = "ssr", sqlx(default))]
+    pub publish_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether the post is NSFW and should be blurred until clicked, set according to the user's `NsfwMode`
+    #[cfg_attr(feature = "ssr", sqlx(default))]
+    pub is_blurred: bool,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, PartialOrd, Validate, Serialize, Deserialize)]
@@ -67,6 +89,9 @@ pub struct PostDataInputs {
     pub embed_type: EmbedType,
     #[validate(length(min = 1, max = MAX_LINK_LENGTH))]
     pub link: Option<String>,
+    #[serde(default)]
+    #[validate(length(max = MAX_ALT_TEXT_LENGTH))]
+    pub alt_text: Option<String>,
     #[validate(nested)]
     pub post_tags: PostTags
 }
@@ -81,11 +106,33 @@ pub struct PostTags {
     pub category_id: Option<i64>,
 }
 
+/// Unpublished post of a user, automatically saved while the post is being written
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct PostDraft {
+    pub user_id: i64,
+    pub title: String,
+    pub body: String,
+    pub link: Option<String>,
+    pub edit_timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Location of the original post of a crosspost
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+pub struct CrosspostHeader {
+    pub post_id: i64,
+    pub sphere_name: String,
+    pub satellite_id: Option<i64>,
+    pub is_active: bool,
+}
+
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct PostWithInfo {
     pub post: Post,
     pub sphere_category: Option<SphereCategoryHeader>,
     pub vote: Option<Vote>,
+    pub crosspost_header: Option<CrosspostHeader>,
+    pub reactions: Vec<ReactionCount>,
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -103,10 +150,43 @@ pub struct PostInheritedAttributes {
     pub is_nsfw: bool,
 }
 
+/// Image of a post gallery, images are displayed by increasing `position`.
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct PostImage {
+    pub image_id: i64,
+    pub post_id: i64,
+    pub position: i32,
+    pub url: String,
+    pub thumbnail_url: Option<String>,
+    pub alt_text: Option<String>,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+}
+
 impl Post {
     pub fn is_active(&self) -> bool {
         self.delete_timestamp.is_none() && self.moderator_id.is_none()
     }
+
+    /// Returns whether the post is visible to all users, i.e. it was approved and is not scheduled for later
+    pub fn is_published(&self) -> bool {
+        self.is_approved && self.publish_at.is_none()
+    }
+
+    /// Returns the number of upvotes, `score` being the difference between upvotes and downvotes
+    pub fn upvotes(&self) -> i32 {
+        self.score + self.score_minus
+    }
+
+    /// Returns the number of downvotes, stored in `score_minus`
+    pub fn downvotes(&self) -> i32 {
+        self.score_minus
+    }
+
+    /// Flags the post as blurred if it is NSFW and `nsfw_mode` is `NsfwMode::Blur`
+    pub fn apply_nsfw_mode(&mut self, nsfw_mode: NsfwMode) {
+        self.is_blurred = nsfw_mode.is_blurred(self.is_nsfw);
+    }
 }
 
 impl PostTags {
@@ -143,25 +223,83 @@ impl PostWithSphereInfo {
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use std::cmp::min;
+    use std::path::Path;
+    use std::time::Duration;
+    use leptos::server_fn::codec::MultipartData;
+    use object_store::aws::{AmazonS3, AmazonS3Builder};
+    use object_store::ObjectStoreExt;
     use serde::{Deserialize, Serialize};
-    use sqlx::PgPool;
+    use sqlx::types::Uuid;
+    use sqlx::{PgExecutor, PgPool};
     use validator::Validate;
-    use sphare_core_common::checks::check_sphere_name;
+    use sphare_core_common::checks::{check_sphere_name, check_string_length};
     use sphare_core_common::colors::Color;
     use sphare_core_common::common::SphereCategoryHeader;
-    use sphare_core_common::constants::POST_BATCH_SIZE;
-    use sphare_core_common::editor::clear_newlines;
+    use sphare_core_common::constants::{ALT_TEXT_PARAM, HOT_ORDER_BY_COLUMN, IMAGE_FILE_PARAM, MAX_ALT_TEXT_LENGTH, MAX_CONTENT_LENGTH, MAX_IDEMPOTENCY_KEY_LENGTH, MAX_LINK_LENGTH, MAX_POST_IMAGES, MAX_TITLE_LENGTH, POST_BATCH_SIZE, POST_ID_PARAM, TRENDING_SPHERE_DAYS};
+    use sphare_core_common::editor::{clear_newlines, get_mentioned_usernames};
     use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
     use sphare_core_common::errors::AppError;
+    use sphare_core_common::errors::ssr::{retry_transient_db_errors, DB_RETRY_BASE_DELAY, DB_RETRY_MAX_ATTEMPTS};
+    use sphare_core_common::metrics::POSTS_CREATED_METRIC;
     use sphare_core_common::routes::get_post_path;
+    use sphare_core_sphere::rule::ssr::check_rules_accepted;
+    use sphare_core_sphere::satellite::ssr::get_satellite_sphere;
+    use sphare_core_sphere::sphere::ssr::{check_nsfw_confirmed, check_sphere_post_title, get_post_sphere, get_sphere_by_name};
+    use sphare_core_sphere::sphere::Sphere;
+    use sphare_core_sphere::sphere_category::ssr::get_sphere_category_by_id;
+    use sphare_core_sphere::sphere_management::ssr::{decode_image, is_trusted_user, store_webp_image};
+    use sphare_core_user::notification::ssr::create_mention_notifications;
     use sphare_core_user::role::PermissionLevel;
-    use sphare_core_user::user::User;
+    use sphare_core_user::user::{NsfwMode, User};
+    use sphare_core_user::user::ssr::get_user_karma;
 
-    use crate::embed::{verify_link_and_get_embed, EmbedType, Link};
+    use crate::automod::ssr::apply_automod_rules_to_post;
+    use crate::comment::ssr::get_comment_by_id;
+    use crate::embed::{get_link_comparison_key, normalize_link, verify_link_and_get_embed, EmbedType, Link, LinkType};
     use crate::filter::SphereCategoryFilter;
-    use crate::post::{Post, PostDataInputs, PostInheritedAttributes, PostLocation, PostTags, PostWithInfo, PostWithSphereInfo};
+    use crate::post::{CrosspostHeader, Post, PostDataInputs, PostDraft, PostImage, PostInheritedAttributes, PostLocation, PostTags, PostWithInfo, PostWithSphereInfo};
     use crate::ranking::{SortType, Vote, VoteValue};
-    use crate::ranking::ssr::vote_on_content;
+    use crate::ranking::ssr::{get_post_reaction_counts, upvote_new_post, vote_on_content};
+
+    pub const POST_IMAGE_BUCKET_ENV: &str = "POST_IMAGE_BUCKET";
+    pub const MAX_POST_IMAGE_SIZE: usize = 5 * 1024 * 1024; // 5 MB in bytes
+    pub const POST_IMAGE_THUMBNAIL_SIZE: u32 = 256;
+    pub const MISSING_POST_ID_STR: &str = "Missing post id.";
+    pub const INCORRECT_POST_IMAGE_FILE_TYPE_STR: &str = "Post image files must be images.";
+    pub const POST_IMAGE_FILE_INFER_ERROR_STR: &str = "Could not infer the file type of a post image.";
+    pub const DUPLICATE_LINK_WINDOW: Duration = Duration::from_hours(24 * 30);
+    pub const POST_IDEMPOTENCY_WINDOW: Duration = Duration::from_hours(24);
+    pub const PENDING_POST_IDEMPOTENCY_WINDOW: Duration = Duration::from_mins(5);
+    pub const HOME_FEED_DISCOVERY_RATIO_ENV: &str = "HOME_FEED_DISCOVERY_RATIO";
+    const SPHERE_SUBSCRIPTION_FILTER: &str = "p.satellite_id IS NULL AND p.sphere_id IN (
+        SELECT sphere_id FROM sphere_subscriptions WHERE user_id = $1
+    )";
+    const SATELLITE_SUBSCRIPTION_FILTER: &str = "p.satellite_id IN (
+        SELECT ss.satellite_id FROM satellite_subscriptions ss
+        JOIN satellites sa ON sa.satellite_id = ss.satellite_id
+        WHERE ss.user_id = $1 AND sa.disable_timestamp IS NULL
+    )";
+
+    /// Urls and alternative text of an image to add to a post gallery
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct PostImageInput {
+        pub url: String,
+        pub thumbnail_url: Option<String>,
+        pub alt_text: Option<String>,
+    }
+
+    /// Location and content of a post to create
+    #[derive(Clone, Debug, Default)]
+    pub struct NewPost<'a> {
+        pub sphere_name: &'a str,
+        pub satellite_id: Option<i64>,
+        pub title: &'a str,
+        pub body: &'a str,
+        pub markdown_body: Option<&'a str>,
+        pub link: Link,
+        pub post_tags: PostTags,
+    }
 
     #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
     #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -186,6 +324,9 @@ pub mod ssr {
         pub vote_user_id: Option<i64>,
         pub value: Option<i16>,
         pub vote_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+        pub crosspost_sphere_name: Option<String>,
+        pub crosspost_satellite_id: Option<i64>,
+        pub is_crosspost_active: Option<bool>,
     }
 
     impl PostJoinSphereInfo {
@@ -226,11 +367,22 @@ pub mod ssr {
                 }),
                 _ => None,
             };
+            let crosspost_header = match (self.post.crosspost_of, self.crosspost_sphere_name) {
+                (Some(post_id), Some(sphere_name)) => Some(CrosspostHeader {
+                    post_id,
+                    sphere_name,
+                    satellite_id: self.crosspost_satellite_id,
+                    is_active: self.is_crosspost_active.unwrap_or(false),
+                }),
+                _ => None,
+            };
 
             PostWithInfo {
                 post: self.post,
                 sphere_category,
                 vote: post_vote,
+                crosspost_header,
+                reactions: Vec::new(),
             }
         }
     }
@@ -243,6 +395,7 @@ pub mod ssr {
             "SELECT
                 p.*,
                 COALESCE(u.username, '') as creator_name,
+                u.avatar_url as creator_avatar_url,
                 m.username as moderator_name,
                 r.title as infringed_rule_title,
                 r.sphere_id IS NOT NULL AS is_sphere_rule
@@ -259,6 +412,8 @@ pub mod ssr {
         Ok(post)
     }
 
+    /// Returns the post `post_id` with its category, crosspost header, reactions and the vote of `user`.
+    /// Posts that are pending approval or scheduled are only returned to their author and to moderators of their sphere.
     pub async fn get_post_with_info_by_id(
         post_id: i64,
         user: Option<&User>,
@@ -269,6 +424,7 @@ pub mod ssr {
         let post_join_vote = sqlx::query_as::<_, PostJoinInfo>(
             "SELECT p.*,
                 COALESCE(u.username, '') as creator_name,
+                u.avatar_url as creator_avatar_url,
                 m.username as moderator_name,
                 r.title as infringed_rule_title,
                 r.sphere_id IS NOT NULL AS is_sphere_rule,
@@ -279,12 +435,17 @@ pub mod ssr {
                 v.post_id as vote_post_id,
                 v.comment_id as vote_comment_id,
                 v.value,
-                v.timestamp as vote_timestamp
+                v.timestamp as vote_timestamp,
+                os.sphere_name as crosspost_sphere_name,
+                o.satellite_id as crosspost_satellite_id,
+                o.delete_timestamp IS NULL AND o.moderator_id IS NULL as is_crosspost_active
             FROM posts p
             LEFT JOIN users u ON u.user_id = p.creator_id AND p.delete_timestamp IS NULL
             LEFT JOIN users m ON m.user_id = p.moderator_id AND p.delete_timestamp IS NULL
             LEFT JOIN rules r ON r.rule_id = p.infringed_rule_id AND p.delete_timestamp IS NULL
             LEFT JOIN sphere_categories c on c.category_id = p.category_id
+            LEFT JOIN posts o ON o.post_id = p.crosspost_of
+            LEFT JOIN spheres os ON os.sphere_id = o.sphere_id
             LEFT JOIN votes v
             ON v.post_id = p.post_id AND
                v.comment_id IS NULL AND
@@ -296,7 +457,19 @@ pub mod ssr {
             .fetch_one(db_pool)
             .await?;
 
-        Ok(post_join_vote.into_post_with_info())
+        let mut post_with_info = post_join_vote.into_post_with_info();
+        let post = &post_with_info.post;
+        if !post.is_published() && !user.is_some_and(|user|
+            user.user_id == post.creator_id || user.check_sphere_permissions_by_id(post.sphere_id, PermissionLevel::Moderate).is_ok()
+        ) {
+            return Err(AppError::NotFound);
+        }
+        post_with_info.reactions = get_post_reaction_counts(post_id, user_id, db_pool).await?;
+        if let Some(user) = user {
+            post_with_info.post.apply_nsfw_mode(user.nsfw_mode);
+        }
+
+        Ok(post_with_info)
     }
 
     pub async fn get_post_inherited_attributes(
@@ -346,11 +519,12 @@ pub mod ssr {
         db_pool: &PgPool,
     ) -> Result<Vec<Post>, AppError> {
         check_sphere_name(sphere_name)?;
+        check_nsfw_confirmed(sphere_name, user, db_pool).await?;
         let posts_filters = user.map(|user| user.get_posts_filter()).unwrap_or_default();
-        let post_vec = sqlx::query_as::<_, Post>(
+        let mut post_vec = sqlx::query_as::<_, Post>(
             format!(
                 "WITH base_posts AS NOT MATERIALIZED (
-                    SELECT p.*, u.username as creator_name
+                    SELECT p.*, u.username as creator_name, u.avatar_url as creator_avatar_url
                     FROM posts p
                     JOIN users u ON u.user_id = p.creator_id
                     JOIN spheres s on s.sphere_id = p.sphere_id
@@ -364,6 +538,8 @@ pub mod ssr {
                             )
                         ) AND
                         p.moderator_id IS NULL AND
+                        p.is_approved AND
+                        p.publish_at IS NULL AND
                         p.delete_timestamp IS NULL AND
                         p.satellite_id IS NULL AND
                         (
@@ -374,7 +550,7 @@ pub mod ssr {
                         )
                 )
                 (
-                  SELECT * FROM base_posts WHERE is_pinned = TRUE ORDER BY {order_by} DESC
+                  SELECT * FROM base_posts WHERE is_pinned = TRUE ORDER BY pin_order ASC, {order_by} DESC
                 )
                 UNION ALL
                 (
@@ -396,12 +572,14 @@ pub mod ssr {
                 SphereCategoryFilter::CategorySet(category_filter_set) => Some(Vec::from_iter(category_filter_set.filters))
             })
             .bind(posts_filters.days_hide_spoiler)
-            .bind(posts_filters.show_nsfw)
+            .bind(posts_filters.nsfw_mode.includes_nsfw())
             .bind(limit)
             .bind(offset)
             .fetch_all(db_pool)
             .await?;
 
+        post_vec.iter_mut().for_each(|post| post.apply_nsfw_mode(posts_filters.nsfw_mode));
+
         Ok(post_vec)
     }
 
@@ -414,11 +592,13 @@ pub mod ssr {
         user: Option<&User>,
         db_pool: &PgPool,
     ) -> Result<Vec<Post>, AppError> {
+        let sphere = get_satellite_sphere(satellite_id, db_pool).await?;
+        check_nsfw_confirmed(&sphere.sphere_name, user, db_pool).await?;
         let posts_filters = user.map(|user| user.get_posts_filter()).unwrap_or_default();
-        let post_vec = sqlx::query_as::<_, Post>(
+        let mut post_vec = sqlx::query_as::<_, Post>(
             format!(
                 "WITH base_posts AS NOT MATERIALIZED (
-                    SELECT p.*, u.username as creator_name
+                    SELECT p.*, u.username as creator_name, u.avatar_url as creator_avatar_url
                     FROM posts p
                     JOIN users u ON u.user_id = p.creator_id
                     JOIN satellites s ON s.satellite_id = p.satellite_id
@@ -426,6 +606,8 @@ pub mod ssr {
                         s.satellite_id = $1 AND
                         p.category_id IS NOT DISTINCT FROM COALESCE($2, p.category_id) AND
                         p.moderator_id IS NULL AND
+                        p.is_approved AND
+                        p.publish_at IS NULL AND
                         p.delete_timestamp IS NULL AND
                         (
                             $3 IS NULL OR NOT p.is_spoiler OR p.create_timestamp < NOW() - (INTERVAL '1 day' * $3)
@@ -435,7 +617,7 @@ pub mod ssr {
                         )
                 )
                 (
-                  SELECT * FROM base_posts WHERE is_pinned = TRUE ORDER BY {order_by} DESC
+                  SELECT * FROM base_posts WHERE is_pinned = TRUE ORDER BY pin_order ASC, {order_by} DESC
                 )
                 UNION ALL
                 (
@@ -449,12 +631,14 @@ pub mod ssr {
             .bind(satellite_id)
             .bind(sphere_category_id)
             .bind(posts_filters.days_hide_spoiler)
-            .bind(posts_filters.show_nsfw)
+            .bind(posts_filters.nsfw_mode.includes_nsfw())
             .bind(limit)
             .bind(offset)
             .fetch_all(db_pool)
             .await?;
 
+        post_vec.iter_mut().for_each(|post| post.apply_nsfw_mode(posts_filters.nsfw_mode));
+
         Ok(post_vec)
     }
 
@@ -491,51 +675,119 @@ pub mod ssr {
         user: Option<&User>,
         db_pool: &PgPool,
     ) -> Result<Vec<PostWithSphereInfo>, AppError> {
-        let (days_hide_spoiler, show_nsfw) = match user {
-            Some(user) => (user.days_hide_spoiler, user.show_nsfw),
-            None => (None, false),
+        let (days_hide_spoiler, nsfw_mode) = match user {
+            Some(user) => (user.days_hide_spoiler, user.nsfw_mode),
+            None => (None, NsfwMode::Hide),
         };
+        let query = format!(
+            "SELECT
+                p.*,
+                u.username as creator_name,
+                u.avatar_url as creator_avatar_url,
+                c.category_name,
+                c.category_color,
+                s.icon_url as sphere_icon_url,
+                s.sphere_name
+            FROM posts p
+            JOIN users u ON u.user_id = p.creator_id
+            JOIN spheres s on s.sphere_id = p.sphere_id
+            LEFT JOIN sphere_categories c on c.category_id = p.category_id
+            WHERE
+                p.moderator_id IS NULL AND
+                p.is_approved AND
+                p.publish_at IS NULL AND
+                p.delete_timestamp IS NULL AND
+                p.satellite_id IS NULL AND
+                (
+                    $1 IS NULL OR NOT p.is_spoiler OR p.create_timestamp < NOW() - (INTERVAL '1 day' * $1)
+                ) AND
+                (
+                    $2 OR NOT p.is_nsfw
+                )
+            ORDER BY {} DESC
+            LIMIT $3
+            OFFSET $4",
+            sort_type.to_order_by_code(),
+        );
+        let post_vec = retry_transient_db_errors(DB_RETRY_MAX_ATTEMPTS, DB_RETRY_BASE_DELAY, || {
+            sqlx::query_as::<_, PostJoinSphereInfo>(query.as_str())
+                .bind(days_hide_spoiler)
+                .bind(nsfw_mode.includes_nsfw())
+                .bind(limit)
+                .bind(offset)
+                .fetch_all(db_pool)
+        }).await?;
+
+        let post_vec = post_vec.into_iter().map(|post| {
+            let mut post = post.into_post_with_sphere_info();
+            post.post.apply_nsfw_mode(nsfw_mode);
+            post
+        }).collect();
+
+        Ok(post_vec)
+    }
+
+    /// Returns up to `limit` active posts of the same sphere and category as the post `post_id`, excluding the post itself.
+    /// NSFW posts are only included when the post `post_id` is NSFW and the `user`'s NSFW mode does not hide them.
+    /// Posts of satellites and recent spoilers hidden by the `user`'s settings are excluded.
+    pub async fn get_similar_posts(
+        post_id: i64,
+        limit: i64,
+        user: Option<&User>,
+        db_pool: &PgPool,
+    ) -> Result<Vec<PostWithSphereInfo>, AppError> {
+        let posts_filters = user.map(|user| user.get_posts_filter()).unwrap_or_default();
         let post_vec = sqlx::query_as::<_, PostJoinSphereInfo>(
             format!(
                 "SELECT
                     p.*,
                     u.username as creator_name,
+                    u.avatar_url as creator_avatar_url,
                     c.category_name,
                     c.category_color,
                     s.icon_url as sphere_icon_url,
                     s.sphere_name
-                FROM posts p
+                FROM posts o
+                JOIN posts p ON
+                    p.sphere_id = o.sphere_id AND
+                    p.category_id IS NOT DISTINCT FROM o.category_id AND
+                    p.post_id <> o.post_id
                 JOIN users u ON u.user_id = p.creator_id
                 JOIN spheres s on s.sphere_id = p.sphere_id
                 LEFT JOIN sphere_categories c on c.category_id = p.category_id
                 WHERE
+                    o.post_id = $1 AND
                     p.moderator_id IS NULL AND
+                    p.is_approved AND
+                    p.publish_at IS NULL AND
                     p.delete_timestamp IS NULL AND
                     p.satellite_id IS NULL AND
+                    (NOT p.is_nsfw OR (o.is_nsfw AND $4)) AND
                     (
-                        $1 IS NULL OR NOT p.is_spoiler OR p.create_timestamp < NOW() - (INTERVAL '1 day' * $1)
-                    ) AND
-                    (
-                        $2 OR NOT p.is_nsfw
+                        $3 IS NULL OR NOT p.is_spoiler OR p.create_timestamp < NOW() - (INTERVAL '1 day' * $3)
                     )
-                ORDER BY {} DESC
-                LIMIT $3
-                OFFSET $4",
-                sort_type.to_order_by_code(),
+                ORDER BY p.{HOT_ORDER_BY_COLUMN} DESC, p.create_timestamp DESC
+                LIMIT $2",
             ).as_str()
         )
-            .bind(days_hide_spoiler)
-            .bind(show_nsfw)
+            .bind(post_id)
             .bind(limit)
-            .bind(offset)
+            .bind(posts_filters.days_hide_spoiler)
+            .bind(posts_filters.nsfw_mode.includes_nsfw())
             .fetch_all(db_pool)
             .await?;
 
-        let post_vec = post_vec.into_iter().map(PostJoinSphereInfo::into_post_with_sphere_info).collect();
+        let post_vec = post_vec.into_iter().map(|post| {
+            let mut post = post.into_post_with_sphere_info();
+            post.post.apply_nsfw_mode(posts_filters.nsfw_mode);
+            post
+        }).collect();
 
         Ok(post_vec)
     }
 
+    /// Returns posts of spheres or satellites the user subscribed to. Posts of a sphere's satellites are only included
+    /// when the user subscribed to the satellite, so that each post matches a single subscription filter.
     pub async fn get_subscribed_post_vec(
         sort_type: SortType,
         limit: i64,
@@ -545,42 +797,15 @@ pub mod ssr {
     ) -> Result<Vec<PostWithSphereInfo>, AppError> {
         let posts_filters = user.get_posts_filter();
         let order_by = sort_type.to_order_by_code();
-        let mut post_vec = sqlx::query_as::<_, PostJoinSphereInfo>(
-            format!(
-                "SELECT
-                    p.*,
-                    u.username AS creator_name,
-                    c.category_name,
-                    c.category_color,
-                    s.icon_url AS sphere_icon_url,
-                    s.sphere_name
-                FROM posts p
-                JOIN users u ON u.user_id = p.creator_id
-                JOIN spheres s on s.sphere_id = p.sphere_id
-                JOIN sphere_subscriptions su ON su.sphere_id = s.sphere_id AND su.user_id = $1
-                LEFT JOIN sphere_categories c on c.category_id = p.category_id
-                WHERE
-                    p.moderator_id IS NULL AND
-                    p.delete_timestamp IS NULL AND
-                    p.satellite_id IS NULL AND
-                    (
-                        $2 IS NULL OR NOT p.is_spoiler OR p.create_timestamp < NOW() - (INTERVAL '1 day' * $2)
-                    ) AND
-                    (
-                        $3 OR NOT p.is_nsfw
-                    )
-                ORDER BY {order_by} DESC
-                LIMIT $4
-                OFFSET $5"
-            ).as_str(),
-        )
-            .bind(user.user_id)
-            .bind(posts_filters.days_hide_spoiler)
-            .bind(posts_filters.show_nsfw)
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(db_pool)
-            .await?;
+        let subscription_filter = format!("(({SPHERE_SUBSCRIPTION_FILTER}) OR ({SATELLITE_SUBSCRIPTION_FILTER}))");
+        let mut post_vec = get_post_vec_by_subscription_filter(
+            &subscription_filter,
+            sort_type,
+            limit,
+            offset,
+            user,
+            db_pool,
+        ).await?;
 
         let loaded_post_count = post_vec.len();
         // If no posts are returned, fetch posts from not subscribed spheres
@@ -590,12 +815,12 @@ pub mod ssr {
                     "WITH subscribed_post_count AS (
                         SELECT COUNT(*) AS total
                         FROM posts p
-                        JOIN spheres s on s.sphere_id = p.sphere_id
-                        JOIN sphere_subscriptions su ON su.sphere_id = s.sphere_id AND su.user_id = $1
                         WHERE
                             p.moderator_id IS NULL AND
+                            p.is_approved AND
+                            p.publish_at IS NULL AND
                             p.delete_timestamp IS NULL AND
-                            p.satellite_id IS NULL AND
+                            {subscription_filter} AND
                             (
                                 $2 IS NULL OR NOT p.is_spoiler OR p.create_timestamp < NOW() - (INTERVAL '1 day' * $2)
                             ) AND
@@ -606,6 +831,7 @@ pub mod ssr {
                     SELECT
                         p.*,
                         u.username as creator_name,
+                        u.avatar_url as creator_avatar_url,
                         c.category_name,
                         c.category_color,
                         s.icon_url as sphere_icon_url,
@@ -616,6 +842,8 @@ pub mod ssr {
                     LEFT JOIN sphere_categories c on c.category_id = p.category_id
                     WHERE
                         p.moderator_id IS NULL AND
+                        p.is_approved AND
+                        p.publish_at IS NULL AND
                         p.delete_timestamp IS NULL AND
                         p.satellite_id IS NULL AND
                         (
@@ -634,11 +862,12 @@ pub mod ssr {
                 )
                     .bind(user.user_id)
                     .bind(posts_filters.days_hide_spoiler)
-                    .bind(posts_filters.show_nsfw)
+                    .bind(posts_filters.nsfw_mode.includes_nsfw())
                     .bind(limit - loaded_post_count as i64)
                     .bind(offset)
                     .fetch_all(db_pool)
                     .await?;
+            additional_posts.iter_mut().for_each(|post| post.post.apply_nsfw_mode(posts_filters.nsfw_mode));
             post_vec.append(&mut additional_posts);
         }
 
@@ -647,65 +876,522 @@ pub mod ssr {
         Ok(post_vec)
     }
 
+    /// Returns the home feed of `user`, where a `discovery_ratio` fraction of the slots, spread evenly, contains posts of trending
+    /// spheres the user does not follow and the other slots contain posts of the user's subscriptions. Both sources are sorted by `sort_type`
+    /// and the slots of an exhausted source are filled by the other one. Discovery posts are never in a subscribed sphere or satellite,
+    /// so that a post cannot appear twice in the feed.
+    pub async fn get_home_feed(
+        user: &User,
+        sort_type: SortType,
+        limit: i64,
+        offset: i64,
+        discovery_ratio: f64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<PostWithSphereInfo>, AppError> {
+        let discovery_ratio = discovery_ratio.clamp(0.0, 1.0);
+        let subscription_filter = format!("(({SPHERE_SUBSCRIPTION_FILTER}) OR ({SATELLITE_SUBSCRIPTION_FILTER}))");
+        let discovery_filter = format!(
+            "p.satellite_id IS NULL AND
+            p.sphere_id NOT IN (
+                SELECT sphere_id FROM sphere_subscriptions WHERE user_id = $1
+            ) AND
+            p.sphere_id IN (
+                SELECT sphere_id FROM sphere_subscriptions WHERE timestamp > NOW() - make_interval(days => {TRENDING_SPHERE_DAYS})
+            )"
+        );
+        let subscribed_total = get_post_count_by_subscription_filter(&subscription_filter, user, db_pool).await?;
+        let discovery_total = get_post_count_by_subscription_filter(&discovery_filter, user, db_pool).await?;
+
+        let get_source_counts = |num_slots: i64| get_home_feed_source_counts(num_slots, discovery_ratio, subscribed_total, discovery_total);
+        let (subscribed_offset, discovery_offset) = get_source_counts(offset);
+        let (subscribed_end, discovery_end) = get_source_counts(offset + limit);
+
+        let mut subscribed_post_iter = match subscribed_end > subscribed_offset {
+            true => get_post_vec_by_subscription_filter(
+                &subscription_filter, sort_type, subscribed_end - subscribed_offset, subscribed_offset, user, db_pool
+            ).await?,
+            false => Vec::new(),
+        }.into_iter();
+        let mut discovery_post_iter = match discovery_end > discovery_offset {
+            true => get_post_vec_by_subscription_filter(
+                &discovery_filter, sort_type, discovery_end - discovery_offset, discovery_offset, user, db_pool
+            ).await?,
+            false => Vec::new(),
+        }.into_iter();
+
+        let mut post_vec = Vec::new();
+        let mut num_subscribed_posts = subscribed_offset;
+        for slot in offset + 1..=offset + limit {
+            let (slot_num_subscribed_posts, _) = get_source_counts(slot);
+            let post = match slot_num_subscribed_posts > num_subscribed_posts {
+                true => subscribed_post_iter.next(),
+                false => discovery_post_iter.next(),
+            };
+            num_subscribed_posts = slot_num_subscribed_posts;
+            match post {
+                Some(post) => post_vec.push(post.into_post_with_sphere_info()),
+                None => break,
+            }
+        }
+
+        Ok(post_vec)
+    }
+
+    /// Returns how many of the first `num_slots` slots of the home feed are filled with subscribed and discovery posts,
+    /// given the total number of posts of each source.
+    pub fn get_home_feed_source_counts(
+        num_slots: i64,
+        discovery_ratio: f64,
+        subscribed_total: i64,
+        discovery_total: i64,
+    ) -> (i64, i64) {
+        let discovery_count = (num_slots as f64 * discovery_ratio).floor() as i64;
+        let subscribed_count = num_slots - discovery_count;
+        if subscribed_count > subscribed_total {
+            (subscribed_total, min(num_slots - subscribed_total, discovery_total))
+        } else if discovery_count > discovery_total {
+            (min(num_slots - discovery_total, subscribed_total), discovery_total)
+        } else {
+            (subscribed_count, discovery_count)
+        }
+    }
+
+    /// Returns posts matching `subscription_filter`, a condition on the post `p` where `$1` is the id of `user`
+    async fn get_post_vec_by_subscription_filter(
+        subscription_filter: &str,
+        sort_type: SortType,
+        limit: i64,
+        offset: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<PostJoinSphereInfo>, AppError> {
+        let posts_filters = user.get_posts_filter();
+        let order_by = sort_type.to_order_by_code();
+        let mut post_vec = sqlx::query_as::<_, PostJoinSphereInfo>(
+            format!(
+                "SELECT
+                    p.*,
+                    u.username AS creator_name,
+                    u.avatar_url as creator_avatar_url,
+                    c.category_name,
+                    c.category_color,
+                    s.icon_url AS sphere_icon_url,
+                    s.sphere_name
+                FROM posts p
+                JOIN users u ON u.user_id = p.creator_id
+                JOIN spheres s on s.sphere_id = p.sphere_id
+                LEFT JOIN sphere_categories c on c.category_id = p.category_id
+                WHERE
+                    p.moderator_id IS NULL AND
+                    p.is_approved AND
+                    p.publish_at IS NULL AND
+                    p.delete_timestamp IS NULL AND
+                    {subscription_filter} AND
+                    (
+                        $2 IS NULL OR NOT p.is_spoiler OR p.create_timestamp < NOW() - (INTERVAL '1 day' * $2)
+                    ) AND
+                    (
+                        $3 OR NOT p.is_nsfw
+                    )
+                ORDER BY {order_by} DESC
+                LIMIT $4
+                OFFSET $5"
+            ).as_str(),
+        )
+            .bind(user.user_id)
+            .bind(posts_filters.days_hide_spoiler)
+            .bind(posts_filters.nsfw_mode.includes_nsfw())
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(db_pool)
+            .await?;
+
+        post_vec.iter_mut().for_each(|post| post.post.apply_nsfw_mode(posts_filters.nsfw_mode));
+
+        Ok(post_vec)
+    }
+
+    /// Returns the number of posts visible to `user` matching `subscription_filter`, a condition on the post `p` where `$1` is the id of `user`
+    async fn get_post_count_by_subscription_filter(
+        subscription_filter: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<i64, AppError> {
+        let posts_filters = user.get_posts_filter();
+        let post_count = sqlx::query_scalar::<_, i64>(
+            format!(
+                "SELECT COUNT(*)
+                FROM posts p
+                WHERE
+                    p.moderator_id IS NULL AND
+                    p.is_approved AND
+                    p.publish_at IS NULL AND
+                    p.delete_timestamp IS NULL AND
+                    {subscription_filter} AND
+                    (
+                        $2 IS NULL OR NOT p.is_spoiler OR p.create_timestamp < NOW() - (INTERVAL '1 day' * $2)
+                    ) AND
+                    (
+                        $3 OR NOT p.is_nsfw
+                    )"
+            ).as_str(),
+        )
+            .bind(user.user_id)
+            .bind(posts_filters.days_hide_spoiler)
+            .bind(posts_filters.nsfw_mode.includes_nsfw())
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(post_count)
+    }
+
+    /// Creates a post and upvotes it. When an `idempotency_key` is provided and the user already created a post with it within
+    /// the [POST_IDEMPOTENCY_WINDOW], returns the existing post instead of creating a new one.
     pub async fn create_post_and_vote(
         post_location: PostLocation,
         post_inputs: PostDataInputs,
+        idempotency_key: Option<&str>,
         user: &User,
         db_pool: &PgPool,
     ) -> Result<(Post, Option<Vote>, String), AppError> {
         post_location.validate()?;
         post_inputs.validate()?;
 
-        let (body, markdown_body) = get_html_and_markdown_strings(&post_inputs.body, post_inputs.is_markdown)?;
+        let Some(idempotency_key) = idempotency_key else {
+            return insert_post_and_vote(post_location, post_inputs, None, None, user, db_pool).await;
+        };
+        check_string_length(idempotency_key, "Idempotency key", MAX_IDEMPOTENCY_KEY_LENGTH, false)?;
 
-        let link = process_embed_link(post_inputs.embed_type, post_inputs.link).await;
+        if let Some(post_id) = reserve_post_idempotency_key(idempotency_key, user, db_pool).await? {
+            log::debug!("Post {post_id} was already created with idempotency key {idempotency_key}.");
+            let post_with_info = get_post_with_info_by_id(post_id, Some(user), db_pool).await?;
+            let sphere_name = get_post_sphere_name(post_id, db_pool).await?;
+            let post_path = get_post_path(&sphere_name, post_with_info.post.satellite_id, post_id);
+            return Ok((post_with_info.post, post_with_info.vote, post_path));
+        }
 
-        let post = create_post(
-            post_location.sphere.as_str(),
-            post_location.satellite_id,
-            clear_newlines(post_inputs.title, true).as_str(),
-            body.as_str(),
-            markdown_body,
-            link,
-            post_inputs.post_tags,
-            user,
-            db_pool,
-        ).await?;
+        insert_post_and_vote(post_location, post_inputs, None, Some(idempotency_key), user, db_pool).await
+    }
 
-        let vote = vote_on_content(VoteValue::Up, post.post_id, None, None, user, db_pool).await?;
+    /// Reserves `idempotency_key` for a new post of `user`. If the user already used the key within the [POST_IDEMPOTENCY_WINDOW],
+    /// returns the id of the post created with it instead, or [AppError::Conflict] if the post is still being created.
+    /// Reservations without post are released after the [PENDING_POST_IDEMPOTENCY_WINDOW], in case their request was interrupted.
+    async fn reserve_post_idempotency_key(
+        idempotency_key: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Option<i64>, AppError> {
+        sqlx::query(
+            "DELETE FROM post_idempotency_keys
+            WHERE
+                user_id = $1 AND (
+                    create_timestamp < NOW() - $2 OR
+                    (post_id IS NULL AND create_timestamp < NOW() - $3)
+                )"
+        )
+            .bind(user.user_id)
+            .bind(POST_IDEMPOTENCY_WINDOW)
+            .bind(PENDING_POST_IDEMPOTENCY_WINDOW)
+            .execute(db_pool)
+            .await?;
 
-        log::trace!("Created post with id: {}", post.post_id);
-        let new_post_path = get_post_path(&post_location.sphere, post_location.satellite_id, post.post_id);
+        let is_reserved = sqlx::query(
+            "INSERT INTO post_idempotency_keys (user_id, idempotency_key) VALUES ($1, $2)
+            ON CONFLICT (user_id, idempotency_key) DO NOTHING"
+        )
+            .bind(user.user_id)
+            .bind(idempotency_key)
+            .execute(db_pool)
+            .await?
+            .rows_affected() > 0;
+
+        if is_reserved {
+            return Ok(None);
+        }
+
+        let post_id = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT post_id FROM post_idempotency_keys WHERE user_id = $1 AND idempotency_key = $2"
+        )
+            .bind(user.user_id)
+            .bind(idempotency_key)
+            .fetch_optional(db_pool)
+            .await?
+            .flatten();
+
+        match post_id {
+            Some(post_id) => Ok(Some(post_id)),
+            None => Err(AppError::Conflict),
+        }
+    }
 
-        Ok((post, vote, new_post_path))
+    /// Creates a post that stays hidden from feeds until `publish_at` and upvotes it in the name of `user`.
+    /// Returns the post, the vote and the path to the post.
+    pub async fn create_scheduled_post_and_vote(
+        post_location: PostLocation,
+        post_inputs: PostDataInputs,
+        publish_at: chrono::DateTime<chrono::Utc>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(Post, Option<Vote>, String), AppError> {
+        post_location.validate()?;
+        post_inputs.validate()?;
+        insert_post_and_vote(post_location, post_inputs, Some(publish_at), None, user, db_pool).await
     }
 
-    pub async fn create_post(
-        sphere_name: &str,
-        satellite_id: Option<i64>,
-        post_title: &str,
-        post_body: &str,
-        post_markdown_body: Option<&str>,
-        link: Link,
-        post_tags: PostTags,
+    /// Creates the post and its upvote in a single transaction, that also completes the reserved `idempotency_key` with the post.
+    /// The key is released if the post is not created, so that the user can submit the same form again.
+    async fn insert_post_and_vote(
+        post_location: PostLocation,
+        post_inputs: PostDataInputs,
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+        idempotency_key: Option<&str>,
         user: &User,
         db_pool: &PgPool,
-    ) -> Result<Post, AppError> {
-        user.check_can_publish_on_sphere(sphere_name)?;
-        if sphere_name.is_empty() || post_title.is_empty() {
-            return Err(AppError::new(
-                "Cannot create post without a valid sphere and title.",
-            ));
+    ) -> Result<(Post, Option<Vote>, String), AppError> {
+        let mentioned_username_vec = get_mentioned_usernames(&post_inputs.body);
+        let (post, vote) = match insert_post_and_vote_rows(&post_location, post_inputs, publish_at, idempotency_key, user, db_pool).await {
+            Ok(post_and_vote) => post_and_vote,
+            Err(e) => {
+                if let Some(idempotency_key) = idempotency_key {
+                    sqlx::query("DELETE FROM post_idempotency_keys WHERE user_id = $1 AND idempotency_key = $2")
+                        .bind(user.user_id)
+                        .bind(idempotency_key)
+                        .execute(db_pool)
+                        .await?;
+                }
+                return Err(e);
+            }
+        };
+        let post = process_created_post(post, db_pool).await?;
+
+        // mentions of posts pending approval or scheduled for later are notified once the post is published
+        if post.is_published() {
+            create_mention_notifications(post.post_id, None, &mentioned_username_vec, user.user_id, db_pool).await?;
         }
-        if post_tags.is_pinned {
-            user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+
+        // the draft was used to write this post, failing to delete it should not fail the post creation
+        if let Err(e) = delete_draft(user, db_pool).await {
+            log::warn!("Failed to delete draft of user {} after creating post {}: {e:?}", user.user_id, post.post_id);
         }
 
+        log::trace!("Created post with id: {}", post.post_id);
+        let new_post_path = get_post_path(&post_location.sphere, post_location.satellite_id, post.post_id);
+
+        Ok((post, Some(vote), new_post_path))
+    }
+
+    async fn insert_post_and_vote_rows(
+        post_location: &PostLocation,
+        post_inputs: PostDataInputs,
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+        idempotency_key: Option<&str>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(Post, Vote), AppError> {
+        let (body, markdown_body) = get_html_and_markdown_strings(&post_inputs.body, post_inputs.is_markdown)?;
+
+        let link = process_embed_link(post_inputs.embed_type, post_inputs.link, post_inputs.alt_text).await?;
+
+        let title = clear_newlines(post_inputs.title, true);
+        let new_post = NewPost {
+            sphere_name: post_location.sphere.as_str(),
+            satellite_id: post_location.satellite_id,
+            title: title.as_str(),
+            body: body.as_str(),
+            markdown_body,
+            link,
+            post_tags: post_inputs.post_tags,
+        };
+        if let Some(publish_at) = publish_at {
+            check_post_schedule(new_post.sphere_name, publish_at, user, db_pool).await?;
+        }
+        check_post_creation(new_post.sphere_name, new_post.title, &new_post.link, &new_post.post_tags, user, db_pool).await?;
+
+        let mut tx = db_pool.begin().await?;
+        let post = insert_post_row(new_post, publish_at, None, user, &mut *tx).await?;
+        let vote = upvote_new_post(post.post_id, user, &mut *tx).await?;
+        if let Some(idempotency_key) = idempotency_key {
+            sqlx::query(
+                "UPDATE post_idempotency_keys SET post_id = $1 WHERE user_id = $2 AND idempotency_key = $3"
+            )
+                .bind(post.post_id)
+                .bind(user.user_id)
+                .bind(idempotency_key)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok((post, vote))
+    }
+
+    pub async fn create_post(
+        sphere_name: &str,
+        satellite_id: Option<i64>,
+        post_title: &str,
+        post_body: &str,
+        post_markdown_body: Option<&str>,
+        link: Link,
+        post_tags: PostTags,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        let new_post = NewPost {
+            sphere_name,
+            satellite_id,
+            title: post_title,
+            body: post_body,
+            markdown_body: post_markdown_body,
+            link,
+            post_tags,
+        };
+        insert_post(new_post, None, user, db_pool).await
+    }
+
+    /// Creates a post that stays hidden from feeds until `publish_at`, when it is published by [publish_scheduled_posts].
+    /// Only moderators and trusted users of the sphere can schedule posts.
+    pub async fn create_scheduled_post(
+        new_post: NewPost<'_>,
+        publish_at: chrono::DateTime<chrono::Utc>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        check_post_schedule(new_post.sphere_name, publish_at, user, db_pool).await?;
+        insert_post(new_post, Some(publish_at), user, db_pool).await
+    }
+
+    /// Checks that `user` can schedule a post at `publish_at` in the sphere `sphere_name`
+    async fn check_post_schedule(
+        sphere_name: &str,
+        publish_at: chrono::DateTime<chrono::Utc>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        if publish_at <= chrono::Utc::now() {
+            return Err(AppError::new("Cannot schedule a post in the past."));
+        }
+        if user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate).is_err() {
+            let sphere = get_sphere_by_name(sphere_name, db_pool).await?;
+            if !is_trusted_user(user.user_id, sphere.sphere_id, db_pool).await? {
+                return Err(AppError::InsufficientPrivileges);
+            }
+        }
+        Ok(())
+    }
+
+    async fn insert_post(
+        new_post: NewPost<'_>,
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        check_post_creation(new_post.sphere_name, new_post.title, &new_post.link, &new_post.post_tags, user, db_pool).await?;
+        let post = insert_post_row(new_post, publish_at, None, user, db_pool).await?;
+        process_created_post(post, db_pool).await
+    }
+
+    /// Checks that `user` can create a post with the given title, link and tags in the sphere `sphere_name`
+    pub(crate) async fn check_post_creation(
+        sphere_name: &str,
+        post_title: &str,
+        link: &Link,
+        post_tags: &PostTags,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        user.check_can_publish_on_sphere(sphere_name)?;
+        if sphere_name.is_empty() || post_title.is_empty() {
+            return Err(AppError::new(
+                "Cannot create post without a valid sphere and title.",
+            ));
+        }
+        let sphere = get_sphere_by_name(sphere_name, db_pool).await?;
+        if sphere.is_archived {
+            return Err(AppError::SphereArchived);
+        }
+        if !link.link_type.is_allowed(sphere.allowed_post_types) {
+            return Err(AppError::PostTypeNotAllowed);
+        }
+        check_sphere_post_title(post_title, sphere.min_title_length, sphere.max_title_length, sphere.title_regex.as_deref())?;
+        check_post_category(&sphere, post_tags, db_pool).await?;
+        check_account_gates(&sphere, user, db_pool).await?;
+        check_rules_accepted(sphere_name, user, db_pool).await?;
+        if post_tags.is_pinned {
+            user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+        }
+        if let Some(link_url) = &link.link_url &&
+            let Some(existing_post_id) = find_recent_post_with_link(sphere.sphere_id, link_url, DUPLICATE_LINK_WINDOW, db_pool).await?
+        {
+            if sphere.reject_duplicate_links {
+                return Err(AppError::DuplicateLink(existing_post_id));
+            }
+            log::debug!("Link {link_url} was already posted in sphere {sphere_name} by post {existing_post_id}.");
+        }
+        Ok(())
+    }
+
+    /// Checks that `user` meets the minimum account age and karma required to publish in `sphere`.
+    /// Moderators and trusted users of the sphere are exempted.
+    pub(crate) async fn check_account_gates(
+        sphere: &Sphere,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        if user.check_sphere_permissions_by_name(&sphere.sphere_name, PermissionLevel::Moderate).is_ok() ||
+            is_trusted_user(user.user_id, sphere.sphere_id, db_pool).await?
+        {
+            return Ok(())
+        }
+        if user.account_age() < chrono::TimeDelta::days(sphere.min_account_age_days as i64) {
+            return Err(AppError::AccountTooNew(sphere.min_account_age_days));
+        }
+        if sphere.min_karma > 0 &&
+            get_user_karma(user.user_id, db_pool).await?.get(sphere.min_karma_type) < sphere.min_karma as i64
+        {
+            return Err(AppError::InsufficientKarma(sphere.min_karma));
+        }
+        Ok(())
+    }
+
+    /// Checks that the post has an active category of `sphere` if the sphere requires one
+    async fn check_post_category(
+        sphere: &Sphere,
+        post_tags: &PostTags,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        if !sphere.require_category {
+            return Ok(())
+        }
+        let category = match post_tags.category_id {
+            Some(category_id) => match get_sphere_category_by_id(category_id, db_pool).await {
+                Ok(category) => Some(category),
+                Err(AppError::NotFound) => None,
+                Err(e) => return Err(e),
+            },
+            None => None,
+        };
+        match category.is_some_and(|category| category.sphere_id == sphere.sphere_id && category.is_active && category.delete_timestamp.is_none()) {
+            true => Ok(()),
+            false => Err(AppError::CategoryRequired),
+        }
+    }
+
+    /// Inserts a post without any check, using `executor` so that it can be part of a larger transaction
+    pub(crate) async fn insert_post_row<'e, E: PgExecutor<'e>>(
+        new_post: NewPost<'_>,
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+        crosspost_of: Option<i64>,
+        user: &User,
+        executor: E,
+    ) -> Result<Post, AppError> {
+        let NewPost { sphere_name, satellite_id, title: post_title, body: post_body, markdown_body: post_markdown_body, link, post_tags } = new_post;
+        let link_key = link.link_url.as_deref().map(get_link_comparison_key);
         let post = sqlx::query_as::<_, Post>(
             "WITH new_post AS (
                     INSERT INTO posts (
                         title, body, markdown_body, link_type, link_url, link_embed, link_thumbnail_url, is_nsfw, is_spoiler, category_id,
-                        sphere_id, satellite_id, is_pinned, creator_id, is_creator_moderator
+                        sphere_id, satellite_id, is_pinned, creator_id, is_creator_moderator, is_approved, link_alt_text, publish_at,
+                        crosspost_of, publish_timestamp, link_key
                     )
                     VALUES (
                         $1, $2, $3, $4, $5, $6, $7,
@@ -732,7 +1418,16 @@ pub mod ssr {
                         ),
                         $10,
                         (SELECT sphere_id FROM spheres s WHERE s.sphere_name = $11),
-                        $12, $13, $14, $15
+                        $12, $13, $14, $15,
+                        $15 OR NOT (SELECT require_post_approval FROM spheres s WHERE s.sphere_name = $11),
+                        $17, $18, $19,
+                        (
+                            CASE
+                                WHEN $18::TIMESTAMPTZ IS NULL AND ($15 OR NOT (SELECT require_post_approval FROM spheres s WHERE s.sphere_name = $11))
+                                THEN NOW()
+                            END
+                        ),
+                        $20
                 ) RETURNING *
             )
             SELECT *, $16 as creator_name FROM new_post",
@@ -753,12 +1448,125 @@ pub mod ssr {
             .bind(user.user_id)
             .bind(user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate).is_ok())
             .bind(user.username.clone())
-            .fetch_one(db_pool)
+            .bind(link.alt_text)
+            .bind(publish_at)
+            .bind(crosspost_of)
+            .bind(link_key)
+            .fetch_one(executor)
             .await?;
 
         Ok(post)
     }
 
+    /// Applies the automod rules to the newly created `post`
+    pub(crate) async fn process_created_post(
+        post: Post,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        let post = apply_automod_rules_to_post(post, db_pool).await?;
+
+        metrics::counter!(POSTS_CREATED_METRIC).increment(1);
+
+        Ok(post)
+    }
+
+    /// Creates a post in the sphere `target_sphere_name` with the title and link of the post `original_post_id`, referencing it as
+    /// its origin. Crossposts of crossposts reference the original post.
+    pub async fn crosspost(
+        original_post_id: i64,
+        target_sphere_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        check_sphere_name(target_sphere_name)?;
+        let original_post = get_post_by_id(original_post_id, db_pool).await?;
+        if !original_post.is_active() || !original_post.is_published() {
+            return Err(AppError::new("Cannot crosspost a deleted, moderated or unpublished post."));
+        }
+        let target_sphere = get_sphere_by_name(target_sphere_name, db_pool).await?;
+        if target_sphere.sphere_id == original_post.sphere_id {
+            return Err(AppError::new("Cannot crosspost a post in its own sphere."));
+        }
+        let post_tags = PostTags::new(original_post.is_spoiler, original_post.is_nsfw, false, None);
+        check_post_creation(target_sphere_name, &original_post.title, &original_post.link, &post_tags, user, db_pool).await?;
+
+        let mut tx = db_pool.begin().await?;
+        let new_post = NewPost {
+            sphere_name: target_sphere_name,
+            title: &original_post.title,
+            link: original_post.link,
+            post_tags,
+            ..Default::default()
+        };
+        let post = insert_post_row(
+            new_post,
+            None,
+            Some(original_post.crosspost_of.unwrap_or(original_post.post_id)),
+            user,
+            &mut *tx,
+        ).await?;
+
+        sqlx::query(
+            "INSERT INTO post_images (post_id, position, url, thumbnail_url, alt_text)
+            SELECT $1, position, url, thumbnail_url, alt_text
+            FROM post_images
+            WHERE post_id = $2"
+        )
+            .bind(post.post_id)
+            .bind(original_post.post_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        let post = process_created_post(post, db_pool).await?;
+
+        vote_on_content(VoteValue::Up, post.post_id, None, None, user, db_pool).await?;
+
+        Ok(post)
+    }
+
+    /// Returns the id of the most recent post of the sphere `sphere_id` created within `within` with the same link as `link_url`.
+    /// Links are compared with their key computed by [`get_link_comparison_key`] and stored in `link_key`,
+    /// to ignore differences like tracking parameters or trailing slashes.
+    pub async fn find_recent_post_with_link(
+        sphere_id: i64,
+        link_url: &str,
+        within: Duration,
+        db_pool: &PgPool,
+    ) -> Result<Option<i64>, AppError> {
+        let post_id = sqlx::query_scalar::<_, i64>(
+            "SELECT post_id FROM posts
+            WHERE
+                sphere_id = $1 AND
+                link_key = $2 AND
+                moderator_id IS NULL AND
+                delete_timestamp IS NULL AND
+                create_timestamp > NOW() - $3
+            ORDER BY create_timestamp DESC
+            LIMIT 1"
+        )
+            .bind(sphere_id)
+            .bind(get_link_comparison_key(link_url))
+            .bind(within)
+            .fetch_optional(db_pool)
+            .await?;
+
+        Ok(post_id)
+    }
+
+    /// Returns the id of a post of the sphere `sphere_name` created within the [DUPLICATE_LINK_WINDOW] with the same link as `link_url`,
+    /// to warn users before they post a duplicate in spheres that do not reject them.
+    pub async fn get_duplicate_link_post_id(
+        sphere_name: &str,
+        link_url: &str,
+        db_pool: &PgPool,
+    ) -> Result<Option<i64>, AppError> {
+        check_sphere_name(sphere_name)?;
+        let link_url = normalize_link(link_url)?;
+        let sphere = get_sphere_by_name(sphere_name, db_pool).await?;
+        find_recent_post_with_link(sphere.sphere_id, &link_url, DUPLICATE_LINK_WINDOW, db_pool).await
+    }
+
     pub async fn edit_post(
         post_id: i64,
         post_inputs: PostDataInputs,
@@ -773,7 +1581,7 @@ pub mod ssr {
             post_inputs.is_markdown,
         )?;
 
-        let link = process_embed_link(post_inputs.embed_type, post_inputs.link).await;
+        let link = process_embed_link(post_inputs.embed_type, post_inputs.link, post_inputs.alt_text).await?;
 
         let post = update_post(
             post_id,
@@ -805,11 +1613,17 @@ pub mod ssr {
                 "Cannot update post without a valid title.",
             ));
         }
+        let sphere = get_post_sphere(post_id, db_pool).await?;
+        if !link.link_type.is_allowed(sphere.allowed_post_types) {
+            return Err(AppError::PostTypeNotAllowed);
+        }
+        check_sphere_post_title(post_title, sphere.min_title_length, sphere.max_title_length, sphere.title_regex.as_deref())?;
+        check_post_category(&sphere, &post_tags, db_pool).await?;
         if post_tags.is_pinned {
-            let sphere_name = get_post_sphere_name(post_id, db_pool).await?;
-            user.check_sphere_permissions_by_name(&sphere_name, PermissionLevel::Moderate)?;
+            user.check_sphere_permissions_by_name(&sphere.sphere_name, PermissionLevel::Moderate)?;
         }
 
+        let link_key = link.link_url.as_deref().map(get_link_comparison_key);
         let post = sqlx::query_as::<_, Post>(
             "WITH updated_post AS (
                 UPDATE posts SET
@@ -820,6 +1634,8 @@ pub mod ssr {
                     link_url = $5,
                     link_embed = $6,
                     link_thumbnail_url = $7,
+                    link_alt_text = $15,
+                    link_key = $16,
                     is_nsfw = (
                         CASE
                             WHEN $8 THEN TRUE
@@ -843,7 +1659,8 @@ pub mod ssr {
                     ),
                     is_pinned = $10,
                     category_id = $11,
-                    edit_timestamp = NOW()
+                    edit_timestamp = NOW(),
+                    edit_count = edit_count + 1
                 WHERE
                     post_id = $12 AND
                     creator_id = $13 AND
@@ -868,12 +1685,267 @@ pub mod ssr {
             .bind(post_id)
             .bind(user.user_id)
             .bind(user.username.clone())
+            .bind(link.alt_text)
+            .bind(link_key)
+            .fetch_one(db_pool)
+            .await?;
+
+        apply_automod_rules_to_post(post, db_pool).await
+    }
+
+    /// Sets the position of the post `post_id` among the pinned posts of its sphere or satellite, lower values come first.
+    pub async fn set_pin_order(
+        post_id: i64,
+        pin_order: i32,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        let sphere_name = get_post_sphere_name(post_id, db_pool).await?;
+        user.check_sphere_permissions_by_name(&sphere_name, PermissionLevel::Moderate)?;
+
+        let post = sqlx::query_as::<_, Post>(
+            "WITH updated_post AS (
+                UPDATE posts SET pin_order = $1
+                WHERE post_id = $2
+                RETURNING *
+            )
+            SELECT p.*, u.username as creator_name, u.avatar_url as creator_avatar_url
+            FROM updated_post p
+            JOIN users u ON u.user_id = p.creator_id",
+        )
+            .bind(pin_order)
+            .bind(post_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(post)
+    }
+
+    /// Marks the top-level comment `comment_id` as the accepted answer of the post `post_id`, or clears the accepted answer
+    /// if `comment_id` is None. Only the author of the post can do it.
+    pub async fn set_accepted_answer(
+        post_id: i64,
+        comment_id: Option<i64>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Post, AppError> {
+        let post = get_post_by_id(post_id, db_pool).await?;
+        if post.creator_id != user.user_id {
+            return Err(AppError::InsufficientPrivileges);
+        }
+        if let Some(comment_id) = comment_id {
+            let comment = get_comment_by_id(comment_id, db_pool).await?;
+            if comment.post_id != post_id || comment.parent_id.is_some() {
+                return Err(AppError::new("Only top-level comments of the post can be accepted as answer."));
+            }
+            if !comment.is_active() {
+                return Err(AppError::new("Cannot accept a deleted or moderated comment as answer."));
+            }
+        }
+
+        let post = sqlx::query_as::<_, Post>(
+            "WITH updated_post AS (
+                UPDATE posts SET accepted_comment_id = $1
+                WHERE post_id = $2
+                RETURNING *
+            )
+            SELECT p.*, u.username as creator_name, u.avatar_url as creator_avatar_url
+            FROM updated_post p
+            JOIN users u ON u.user_id = p.creator_id",
+        )
+            .bind(comment_id)
+            .bind(post_id)
             .fetch_one(db_pool)
             .await?;
 
         Ok(post)
     }
 
+    pub async fn get_post_image_vec(
+        post_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<PostImage>, AppError> {
+        let image_vec = sqlx::query_as::<_, PostImage>(
+            "SELECT * FROM post_images
+            WHERE post_id = $1
+            ORDER BY position"
+        )
+            .bind(post_id)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(image_vec)
+    }
+
+    /// Checks that `num_new_images` can be added to the gallery of the post `post_id` without exceeding [MAX_POST_IMAGES].
+    ///
+    /// Returns the number of images currently in the gallery.
+    async fn check_post_image_count(
+        post_id: i64,
+        num_new_images: usize,
+        db_pool: &PgPool,
+    ) -> Result<i64, AppError> {
+        let current_image_count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM post_images WHERE post_id = $1"
+        )
+            .bind(post_id)
+            .fetch_one(db_pool)
+            .await?;
+        if current_image_count as usize + num_new_images > MAX_POST_IMAGES {
+            return Err(AppError::ValidationError(format!("A post cannot have more than {MAX_POST_IMAGES} images.")))
+        }
+        Ok(current_image_count)
+    }
+
+    /// Appends the images in `image_input_vec` to the gallery of the post `post_id`, in the given order.
+    ///
+    /// The link of the post is set to a gallery pointing to its first image.
+    /// Returns all the images of the gallery.
+    pub async fn insert_post_images(
+        post_id: i64,
+        image_input_vec: Vec<PostImageInput>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<PostImage>, AppError> {
+        let post = get_post_by_id(post_id, db_pool).await?;
+        if post.creator_id != user.user_id {
+            return Err(AppError::InsufficientPrivileges)
+        }
+        let current_image_count = check_post_image_count(post_id, image_input_vec.len(), db_pool).await?;
+
+        let mut url_vec = Vec::with_capacity(image_input_vec.len());
+        let mut thumbnail_url_vec = Vec::with_capacity(image_input_vec.len());
+        let mut alt_text_vec = Vec::with_capacity(image_input_vec.len());
+        for image_input in image_input_vec {
+            if let Some(alt_text) = &image_input.alt_text {
+                check_string_length(alt_text, "Alt text", MAX_ALT_TEXT_LENGTH as usize, true)?;
+            }
+            url_vec.push(image_input.url);
+            thumbnail_url_vec.push(image_input.thumbnail_url);
+            alt_text_vec.push(image_input.alt_text.filter(|alt_text| !alt_text.is_empty()));
+        }
+        let mut tx = db_pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO post_images (post_id, position, url, thumbnail_url, alt_text)
+            SELECT $1, $2 + i.ordinality - 1, i.url, i.thumbnail_url, i.alt_text
+            FROM UNNEST($3::TEXT[], $4::TEXT[], $5::TEXT[]) WITH ORDINALITY AS i(url, thumbnail_url, alt_text, ordinality)"
+        )
+            .bind(post_id)
+            .bind(current_image_count as i32)
+            .bind(url_vec)
+            .bind(thumbnail_url_vec)
+            .bind(alt_text_vec)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "UPDATE posts p SET
+                link_type = $1,
+                link_url = i.url,
+                link_embed = NULL,
+                link_thumbnail_url = i.thumbnail_url,
+                link_alt_text = i.alt_text,
+                link_key = NULL
+            FROM post_images i
+            WHERE
+                p.post_id = $2 AND
+                i.post_id = p.post_id AND
+                i.position = 0"
+        )
+            .bind(LinkType::Gallery as i16)
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        get_post_image_vec(post_id, db_pool).await
+    }
+
+    pub fn get_post_image_object_store() -> Result<AmazonS3, AppError> {
+        AmazonS3Builder::from_env()
+            .with_bucket_name(std::env::var(POST_IMAGE_BUCKET_ENV)?)
+            .build()
+            .map_err(|e| AppError::new(format!("Error while building object store: {e}")))
+    }
+
+    /// Extracts the post id and the images from `data`, stores the images and their thumbnails in `object_store`
+    /// and appends them to the post's gallery, in the order of the multipart fields.
+    /// An alternative text field applies to the image field preceding it.
+    pub async fn add_post_images<T: ObjectStoreExt>(
+        data: MultipartData,
+        object_store: &T,
+        object_container_url: &str,
+        bucket_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<PostImage>, AppError> {
+        // `.into_inner()` returns the inner `multer` stream
+        // it is `None` if we call this on the client, but always `Some(_)` on the server, so is safe to unwrap
+        let mut data = data.into_inner().unwrap();
+        let mut post_id = Err(AppError::new(MISSING_POST_ID_STR));
+        let mut image_buffer_vec = Vec::<(Vec<u8>, Option<String>)>::new();
+
+        while let Ok(Some(mut field)) = data.next_field().await {
+            let name = field.name().unwrap_or_default().to_string();
+            if name == POST_ID_PARAM {
+                let post_id_str = field.text().await.map_err(|e| AppError::new(e.to_string()))?;
+                post_id = post_id_str.parse::<i64>().map_err(|e| AppError::new(format!("Invalid post id: {e}")));
+            } else if name == ALT_TEXT_PARAM {
+                let alt_text = field.text().await.map_err(|e| AppError::new(e.to_string()))?;
+                if let Some((_, image_alt_text)) = image_buffer_vec.last_mut() {
+                    *image_alt_text = Some(alt_text);
+                }
+            } else if name == IMAGE_FILE_PARAM && !field.file_name().unwrap_or_default().is_empty() {
+                if image_buffer_vec.len() >= MAX_POST_IMAGES {
+                    return Err(AppError::ValidationError(format!("A post cannot have more than {MAX_POST_IMAGES} images.")))
+                }
+                let mut image_buffer = Vec::<u8>::new();
+                while let Ok(Some(chunk)) = field.chunk().await {
+                    if image_buffer.len() + chunk.len() > MAX_POST_IMAGE_SIZE {
+                        return Err(AppError::PayloadTooLarge(MAX_POST_IMAGE_SIZE));
+                    }
+                    image_buffer.extend_from_slice(&chunk);
+                }
+                image_buffer_vec.push((image_buffer, None));
+            }
+        }
+
+        let post_id = post_id?;
+        let post = get_post_by_id(post_id, db_pool).await?;
+        if post.creator_id != user.user_id {
+            return Err(AppError::InsufficientPrivileges)
+        }
+        // check the gallery size before storing anything
+        check_post_image_count(post_id, image_buffer_vec.len(), db_pool).await?;
+
+        let get_image_url = |file_name: &str| Path::new(object_container_url)
+            .join(bucket_name)
+            .join(file_name)
+            .to_string_lossy()
+            .to_string();
+
+        let mut image_input_vec = Vec::with_capacity(image_buffer_vec.len());
+        for (image_buffer, alt_text) in image_buffer_vec {
+            let img = decode_image(image_buffer, INCORRECT_POST_IMAGE_FILE_TYPE_STR, POST_IMAGE_FILE_INFER_ERROR_STR)?;
+            let image_identifier = Uuid::new_v4();
+            let file_name = format!("post_{post_id}_{image_identifier}.webp");
+            let thumbnail_file_name = format!("post_{post_id}_{image_identifier}_thumbnail.webp");
+            store_webp_image(&img, &file_name, object_store).await?;
+            store_webp_image(
+                &img.thumbnail(POST_IMAGE_THUMBNAIL_SIZE, POST_IMAGE_THUMBNAIL_SIZE),
+                &thumbnail_file_name,
+                object_store
+            ).await?;
+            image_input_vec.push(PostImageInput {
+                url: get_image_url(&file_name),
+                thumbnail_url: Some(get_image_url(&thumbnail_file_name)),
+                alt_text,
+            });
+        }
+
+        insert_post_images(post_id, image_input_vec, user, db_pool).await
+    }
+
     pub async fn delete_post(
         post_id: i64,
         user: &User,
@@ -889,6 +1961,8 @@ pub mod ssr {
                     link_url = NULL,
                     link_embed = NULL,
                     link_thumbnail_url = NULL,
+                    link_alt_text = NULL,
+                    link_key = NULL,
                     is_nsfw = false,
                     is_spoiler = false,
                     is_pinned = false,
@@ -912,9 +1986,72 @@ pub mod ssr {
         Ok(deleted_post)
     }
 
-    pub async fn increment_post_comment_count(
-        post_id: i64,
+    /// Saves the post draft of `user`, overwriting the previous one if it exists.
+    pub async fn save_draft(
+        title: &str,
+        body: &str,
+        link: Option<&str>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<PostDraft, AppError> {
+        check_string_length(title, "Title", MAX_TITLE_LENGTH as usize, true)?;
+        check_string_length(body, "Body", MAX_CONTENT_LENGTH as usize, true)?;
+        if let Some(link) = link {
+            check_string_length(link, "Link", MAX_LINK_LENGTH as usize, true)?;
+        }
+        let draft = sqlx::query_as!(
+            PostDraft,
+            "INSERT INTO post_drafts (user_id, title, body, link)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id) DO UPDATE
+            SET title = EXCLUDED.title,
+                body = EXCLUDED.body,
+                link = EXCLUDED.link,
+                edit_timestamp = NOW()
+            RETURNING *",
+            user.user_id,
+            title,
+            body,
+            link,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(draft)
+    }
+
+    pub async fn load_draft(
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Option<PostDraft>, AppError> {
+        let draft = sqlx::query_as!(
+            PostDraft,
+            "SELECT * FROM post_drafts WHERE user_id = $1",
+            user.user_id,
+        )
+            .fetch_optional(db_pool)
+            .await?;
+
+        Ok(draft)
+    }
+
+    pub async fn delete_draft(
+        user: &User,
         db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "DELETE FROM post_drafts WHERE user_id = $1",
+            user.user_id,
+        )
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn increment_post_comment_count<'e, E: PgExecutor<'e>>(
+        post_id: i64,
+        executor: E,
     ) -> Result<(), AppError> {
         sqlx::query!(
             "UPDATE posts
@@ -922,42 +2059,175 @@ pub mod ssr {
             WHERE post_id = $1",
             post_id,
         )
-            .execute(db_pool)
+            .execute(executor)
             .await?;
 
         Ok(())
     }
 
-    pub async fn update_post_scores(db_pool: &PgPool) -> Result<(), AppError> {
+    pub async fn decrement_post_comment_count<'e, E: PgExecutor<'e>>(
+        post_id: i64,
+        executor: E,
+    ) -> Result<(), AppError> {
         sqlx::query!(
             "UPDATE posts
-            SET scoring_timestamp = NOW()
-            WHERE create_timestamp > (NOW() - INTERVAL '2 days')",
+            SET num_comments = GREATEST(num_comments - 1, 0)
+            WHERE post_id = $1",
+            post_id,
         )
-            .execute(db_pool)
+            .execute(executor)
             .await?;
 
         Ok(())
     }
 
-    pub async fn process_embed_link(embed_type: EmbedType, link: Option<String>) -> Link {
-        let (link, _) = match (embed_type, link) {
-            (embed_type, Some(link)) if embed_type != EmbedType::None => verify_link_and_get_embed(embed_type, &link).await,
-            _ => (Link::default(), None),
+    /// Recounts the comments of each post that are neither deleted nor moderated and corrects the `num_comments` of posts
+    /// whose counter drifted from this count. Returns the number of corrected posts.
+    pub async fn recompute_post_comment_counts(db_pool: &PgPool) -> Result<u64, AppError> {
+        let num_corrected_posts = sqlx::query!(
+            "UPDATE posts p
+            SET num_comments = counts.num_comments
+            FROM (
+                SELECT p.post_id, COUNT(c.comment_id)::INT AS num_comments
+                FROM posts p
+                LEFT JOIN comments c ON
+                    c.post_id = p.post_id AND
+                    c.delete_timestamp IS NULL AND
+                    c.moderator_id IS NULL
+                GROUP BY p.post_id
+            ) counts
+            WHERE
+                p.post_id = counts.post_id AND
+                p.num_comments <> counts.num_comments",
+        )
+            .execute(db_pool)
+            .await?
+            .rows_affected();
+
+        Ok(num_corrected_posts)
+    }
+
+    /// Refreshes the scores of posts created in the last 2 days by bumping their `scoring_timestamp`.
+    /// `recommended_score` and `trending_score` are stored generated columns, they are therefore recomputed
+    /// and materialized by the same update and feed queries can order by them without evaluating the formulas.
+    /// Posts are updated in batches of `batch_size` ordered by post_id, each batch being committed separately
+    /// to avoid locking all posts in a single long transaction. Returns the number of updated posts.
+    pub async fn update_post_scores(batch_size: i64, db_pool: &PgPool) -> Result<u64, AppError> {
+        let mut last_post_id = 0;
+        let mut num_updated_posts = 0;
+        loop {
+            let (batch_count, batch_max_post_id) = sqlx::query_as::<_, (i64, Option<i64>)>(
+                "WITH post_batch AS (
+                    SELECT post_id FROM posts
+                    WHERE
+                        post_id > $1 AND
+                        create_timestamp > (NOW() - INTERVAL '2 days')
+                    ORDER BY post_id
+                    LIMIT $2
+                ), updated_posts AS (
+                    UPDATE posts p
+                    SET scoring_timestamp = NOW()
+                    FROM post_batch b
+                    WHERE p.post_id = b.post_id
+                    RETURNING p.post_id
+                )
+                SELECT COUNT(*), MAX(post_id) FROM updated_posts",
+            )
+                .bind(last_post_id)
+                .bind(batch_size)
+                .fetch_one(db_pool)
+                .await?;
+
+            num_updated_posts += batch_count as u64;
+            match batch_max_post_id {
+                Some(post_id) if batch_count == batch_size => last_post_id = post_id,
+                _ => break,
+            }
+        }
+
+        Ok(num_updated_posts)
+    }
+
+    /// Publishes the scheduled posts whose `publish_at` has passed, their creation timestamp is set to their publication time
+    /// so that they are ranked as new posts. Returns the number of published posts.
+    pub async fn publish_scheduled_posts(db_pool: &PgPool) -> Result<u64, AppError> {
+        let published_post_vec = sqlx::query_as::<_, (i64, i64, String, Option<String>, bool)>(
+            "UPDATE posts
+            SET
+                create_timestamp = publish_at,
+                scoring_timestamp = NOW(),
+                publish_timestamp = CASE WHEN is_approved THEN NOW() END,
+                publish_at = NULL
+            WHERE publish_at <= NOW()
+            RETURNING post_id, creator_id, body, markdown_body, is_approved",
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        // mentions of posts pending approval are notified once the post is approved
+        for (post_id, creator_id, body, markdown_body, is_approved) in &published_post_vec {
+            if !is_approved {
+                continue;
+            }
+            let mentioned_username_vec = get_mentioned_usernames(markdown_body.as_deref().unwrap_or(body));
+            if let Err(e) = create_mention_notifications(*post_id, None, &mentioned_username_vec, *creator_id, db_pool).await {
+                log::error!("Failed to notify mentions of published post {post_id}: {e:?}");
+            }
+        }
+
+        Ok(published_post_vec.len() as u64)
+    }
+
+    /// Normalizes the input `link` with [`normalize_link`] and gets its embed, returns an error if the link is invalid.
+    pub async fn process_embed_link(embed_type: EmbedType, link: Option<String>, alt_text: Option<String>) -> Result<Link, AppError> {
+        let link = match (embed_type, link) {
+            (embed_type, Some(link)) if embed_type != EmbedType::None => {
+                let link = normalize_link(&link)?;
+                verify_link_and_get_embed(embed_type, &link, alt_text).await.0
+            },
+            _ => Link::default(),
         };
-        link
+        Ok(link)
     }
 
     #[cfg(test)]
     mod tests {
         use sphare_core_common::colors::Color;
+        use sphare_core_common::errors::AppError;
         use sphare_core_user::user::User;
 
         use crate::embed::{EmbedType, Link, LinkType};
-        use crate::post::ssr::{process_embed_link, PostJoinInfo};
+        use crate::post::ssr::{get_home_feed_source_counts, process_embed_link, PostJoinInfo};
         use crate::post::Post;
         use crate::ranking::VoteValue;
 
+        #[test]
+        fn test_get_home_feed_source_counts() {
+            assert_eq!(get_home_feed_source_counts(0, 0.25, 10, 10), (0, 0));
+            assert_eq!(get_home_feed_source_counts(3, 0.25, 10, 10), (3, 0));
+            assert_eq!(get_home_feed_source_counts(4, 0.25, 10, 10), (3, 1));
+            assert_eq!(get_home_feed_source_counts(8, 0.25, 10, 10), (6, 2));
+            assert_eq!(get_home_feed_source_counts(8, 0.0, 10, 10), (8, 0));
+            assert_eq!(get_home_feed_source_counts(8, 1.0, 10, 10), (0, 8));
+            assert_eq!(get_home_feed_source_counts(10, 0.5, 10, 10), (5, 5));
+            // Exhausted sources are replaced by the other one
+            assert_eq!(get_home_feed_source_counts(8, 0.25, 4, 10), (4, 4));
+            assert_eq!(get_home_feed_source_counts(8, 0.5, 10, 1), (7, 1));
+            assert_eq!(get_home_feed_source_counts(30, 0.5, 10, 5), (10, 5));
+
+            // Each additional slot is filled by exactly one source
+            for (subscribed_total, discovery_total) in [(10, 10), (3, 20), (20, 3), (5, 5)] {
+                let mut previous_counts = (0, 0);
+                for num_slots in 1..=20 {
+                    let counts = get_home_feed_source_counts(num_slots, 0.3, subscribed_total, discovery_total);
+                    let num_added = (counts.0 - previous_counts.0) + (counts.1 - previous_counts.1);
+                    assert_eq!(num_added, (num_slots <= subscribed_total + discovery_total) as i64);
+                    assert!(counts.0 >= previous_counts.0 && counts.1 >= previous_counts.1);
+                    previous_counts = counts;
+                }
+            }
+        }
+
         #[test]
         fn test_post_join_vote_into_post_with_info() {
             let user = User::default();
@@ -974,6 +2244,9 @@ pub mod ssr {
                 vote_user_id: None,
                 value: None,
                 vote_timestamp: None,
+                crosspost_sphere_name: None,
+                crosspost_satellite_id: None,
+                is_crosspost_active: None,
             };
             let user_post_with_info = user_post_without_vote.into_post_with_info();
             assert_eq!(user_post_with_info.post, user_post);
@@ -990,6 +2263,9 @@ pub mod ssr {
                 vote_user_id: Some(user.user_id),
                 value: Some(1),
                 vote_timestamp: Some(user_post.create_timestamp),
+                crosspost_sphere_name: None,
+                crosspost_satellite_id: None,
+                is_crosspost_active: None,
             };
             let user_post_with_info = user_post_with_vote.into_post_with_info();
             let user_vote = user_post_with_info.vote.expect("PostWithInfo should contain vote.");
@@ -1013,6 +2289,9 @@ pub mod ssr {
                 vote_user_id: Some(user.user_id),
                 value: Some(-1),
                 vote_timestamp: Some(other_post.create_timestamp),
+                crosspost_sphere_name: None,
+                crosspost_satellite_id: None,
+                is_crosspost_active: None,
             };
             let other_post_with_info = other_post_with_vote.into_post_with_info();
             let user_vote = other_post_with_info.vote.expect("PostWithInfo should contain vote.");
@@ -1027,17 +2306,108 @@ pub mod ssr {
         }
 
         #[tokio::test]
-        async fn test_process_embed_link() {
-            let default_link = process_embed_link(EmbedType::None, None).await;
+        async fn test_process_embed_link() -> Result<(), AppError> {
+            let default_link = process_embed_link(EmbedType::None, None, None).await?;
             assert_eq!(default_link, Link::default());
 
+            let alt_text = Some(String::from("alt"));
+            let no_link = process_embed_link(EmbedType::None, None, alt_text.clone()).await?;
+            assert_eq!(no_link, Link::default());
+
             let link_url = String::from("https://test.com/");
-            let simple_link = process_embed_link(EmbedType::Link, Some(link_url.clone())).await;
-            assert_eq!(simple_link, Link::new(LinkType::Link, Some(link_url), None, None));
+            let simple_link = process_embed_link(EmbedType::Link, Some(link_url.clone()), None).await?;
+            assert_eq!(simple_link, Link::new(LinkType::Link, Some(link_url.clone()), None, None));
+
+            let alt_text_link = process_embed_link(EmbedType::Link, Some(link_url.clone()), alt_text.clone()).await?;
+            assert_eq!(alt_text_link, Link::new(LinkType::Link, Some(link_url.clone()), None, None).with_alt_text(alt_text));
+            assert_eq!(alt_text_link.alt_text, Some(String::from("alt")));
+
+            let empty_alt_text_link = process_embed_link(EmbedType::Link, Some(link_url.clone()), Some(String::new())).await?;
+            assert_eq!(empty_alt_text_link.alt_text, None);
+
+            let normalized_link = process_embed_link(EmbedType::Link, Some(String::from("http://test.com/?utm_source=a")), None).await?;
+            assert_eq!(normalized_link, Link::new(LinkType::Link, Some(link_url), None, None));
+
+            assert!(process_embed_link(EmbedType::Link, Some(String::from("javascript:alert(1)")), None).await.is_err());
+            // links are ignored without embed type
+            assert_eq!(process_embed_link(EmbedType::None, Some(String::from("javascript:alert(1)")), None).await?, Link::default());
+
+            Ok(())
         }
     }
 }
 
+/// Returns the number of words of `body`. When `is_html` is true, html tags are skipped and predefined entities are decoded, so that
+/// a markdown body rendered to html has the same word count as the same text in plain form.
+/// Only tokens containing at least one alphanumeric character are counted as words, to ignore standalone punctuation or markup symbols.
+pub fn get_word_count(body: &str, is_html: bool) -> usize {
+    let text = match is_html {
+        true => {
+            let mut text = String::with_capacity(body.len());
+            let mut is_in_tag = false;
+            for c in body.chars() {
+                match (c, is_in_tag) {
+                    ('<', false) => {
+                        is_in_tag = true;
+                        text.push(' ');
+                    },
+                    ('>', true) => is_in_tag = false,
+                    (_, true) => (),
+                    (c, false) => text.push(c),
+                }
+            }
+            text.replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&#x27;", "'")
+                .replace("&amp;", "&")
+        },
+        false => body.to_string(),
+    };
+    text.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// Returns the number of words in the body of `post`
+pub fn get_post_word_count(post: &Post) -> usize {
+    get_word_count(&post.body, post.markdown_body.is_some())
+}
+
+/// Returns the estimated time needed to read the body of `post`, based on `READING_WORDS_PER_MINUTE`
+pub fn reading_time(post: &Post) -> Duration {
+    Duration::from_secs_f64(get_post_word_count(post) as f64 * 60.0 / READING_WORDS_PER_MINUTE as f64)
+}
+
+/// Returns `reading_time` rounded to the nearest minute, with a minimum of one minute
+pub fn get_reading_time_minutes(reading_time: Duration) -> u64 {
+    ((reading_time.as_secs_f64() / 60.0).round() as u64).max(1)
+}
+
+/// Returns the ratio of upvotes over the total number of votes of `post`, or 0 if it has no votes
+pub fn upvote_ratio(post: &Post) -> f32 {
+    let num_votes = post.upvotes() + post.downvotes();
+    match num_votes > 0 {
+        true => post.upvotes() as f32 / num_votes as f32,
+        false => 0.0,
+    }
+}
+
+/// Returns the lower bound of the Wilson score confidence interval of the upvote ratio, or 0 if there are no votes.
+/// Must match `BEST_RATIO_POST_ORDER_BY_COLUMN`.
+pub fn get_wilson_score_lower_bound(upvotes: i32, downvotes: i32) -> f64 {
+    let num_votes = (upvotes + downvotes) as f64;
+    if num_votes <= 0.0 {
+        return 0.0;
+    }
+    let z_squared = WILSON_SCORE_Z * WILSON_SCORE_Z;
+    let ratio = upvotes as f64 / num_votes;
+    (
+        ratio + z_squared / (2.0 * num_votes) -
+        WILSON_SCORE_Z * ((ratio * (1.0 - ratio) + z_squared / (4.0 * num_votes)) / num_votes).sqrt()
+    ) / (1.0 + z_squared / num_votes)
+}
+
 pub fn add_sphere_info_to_post_vec(
     post_vec: Vec<Post>,
     sphere_name: String,
@@ -1061,7 +2431,7 @@ mod tests {
     use sphare_core_common::common::SphereCategoryHeader;
 
     use crate::embed::Link;
-    use crate::post::{add_sphere_info_to_post_vec, Post, PostWithSphereInfo};
+    use crate::post::{add_sphere_info_to_post_vec, get_post_word_count, get_reading_time_minutes, get_wilson_score_lower_bound, get_word_count, reading_time, upvote_ratio, Post, PostWithSphereInfo};
 
     fn create_post_with_category(title: &str, category_id: Option<i64>) -> Post {
         Post {
@@ -1076,8 +2446,10 @@ mod tests {
             is_edited: false,
             sphere_id: 0,
             satellite_id: None,
+            crosspost_of: None,
             creator_id: 0,
             creator_name: String::default(),
+            creator_avatar_url: None,
             is_creator_moderator: false,
             moderator_message: None,
             infringed_rule_id: None,
@@ -1087,14 +2459,21 @@ mod tests {
             moderator_name: None,
             num_comments: 0,
             is_pinned: false,
+            pin_order: 0,
+            accepted_comment_id: None,
+            is_approved: true,
             score: 0,
             score_minus: 0,
             recommended_score: 0.0,
             trending_score: 0.0,
             create_timestamp: Default::default(),
             edit_timestamp: None,
+            edit_count: 0,
             scoring_timestamp: Default::default(),
             delete_timestamp: None,
+            publish_at: None,
+            publish_timestamp: None,
+            is_blurred: false,
         }
     }
 
@@ -1180,4 +2559,98 @@ mod tests {
         assert_eq!(post_with_sphere_info_vec[1].sphere_icon_url, Some(sphere_icon_url.clone()));
         assert_eq!(post_with_sphere_info_vec[2].sphere_icon_url, Some(sphere_icon_url));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_word_count() {
+        assert_eq!(get_word_count("", false), 0);
+        assert_eq!(get_word_count("hello world", false), 2);
+        assert_eq!(get_word_count("  hello\n\nworld  - again ", false), 3);
+        assert_eq!(get_word_count("Tom & Jerry", false), 2);
+        assert_eq!(get_word_count("<p>hello</p>", false), 1);
+        assert_eq!(get_word_count("<p>hello</p>", true), 1);
+        assert_eq!(get_word_count("<p>hello</p>\n<p>world</p>", true), 2);
+        assert_eq!(get_word_count("<h1 class=\"text-4xl mb-3\">A title</h1><p>Tom &amp; Jerry</p>", true), 4);
+        assert_eq!(get_word_count("<ul><li>one</li><li>two</li></ul><hr class=\"my-2\"/>", true), 2);
+    }
+
+    #[test]
+    fn test_reading_time() {
+        let mut post = create_post_with_category("title", None);
+        assert_eq!(get_post_word_count(&post), 0);
+        assert_eq!(reading_time(&post).as_secs(), 0);
+        assert_eq!(get_reading_time_minutes(reading_time(&post)), 1);
+
+        post.body = vec!["word"; 500].join(" ");
+        assert_eq!(get_post_word_count(&post), 500);
+        assert_eq!(reading_time(&post).as_secs(), 150);
+        assert_eq!(get_reading_time_minutes(reading_time(&post)), 3);
+
+        post.body = vec!["<p>word</p>"; 250].join("\n");
+        post.markdown_body = Some(vec!["word"; 250].join("\n\n"));
+        assert_eq!(get_post_word_count(&post), 250);
+        assert_eq!(reading_time(&post).as_secs(), 75);
+        assert_eq!(get_reading_time_minutes(reading_time(&post)), 1);
+
+        post.body = vec!["<p>word</p>"; 1000].join("\n");
+        assert_eq!(get_reading_time_minutes(reading_time(&post)), 5);
+    }
+
+    #[test]
+    fn test_post_upvotes_downvotes() {
+        let mut post = create_post_with_category("title", None);
+        for (score, score_minus, expected_upvotes, expected_downvotes) in [
+            (0, 0, 0, 0),
+            (5, 0, 5, 0),
+            (3, 2, 5, 2),
+            (0, 3, 3, 3),
+            (-4, 4, 0, 4),
+            (-2, 5, 3, 5),
+        ] {
+            post.score = score;
+            post.score_minus = score_minus;
+            assert_eq!(post.upvotes(), expected_upvotes);
+            assert_eq!(post.downvotes(), expected_downvotes);
+            assert_eq!(post.upvotes() - post.downvotes(), post.score);
+        }
+    }
+
+    #[test]
+    fn test_upvote_ratio() {
+        let mut post = create_post_with_category("title", None);
+        assert_eq!(upvote_ratio(&post), 0.0);
+        post.score = 4;
+        assert_eq!(upvote_ratio(&post), 1.0);
+        post.score_minus = 4;
+        assert_eq!(upvote_ratio(&post), 2.0 / 3.0);
+        post.score = -2;
+        post.score_minus = 2;
+        assert_eq!(upvote_ratio(&post), 0.0);
+        post.score = 0;
+        assert_eq!(upvote_ratio(&post), 0.5);
+    }
+
+    #[test]
+    fn test_get_wilson_score_lower_bound() {
+        assert_eq!(get_wilson_score_lower_bound(0, 0), 0.0);
+        assert_eq!(get_wilson_score_lower_bound(0, 10), 0.0);
+        assert!(get_wilson_score_lower_bound(1, 0) > 0.0);
+        assert!(get_wilson_score_lower_bound(10, 0) < 1.0);
+        assert!(get_wilson_score_lower_bound(10, 0) > get_wilson_score_lower_bound(1, 0));
+        assert!(get_wilson_score_lower_bound(10, 1) > get_wilson_score_lower_bound(10, 5));
+        assert!((get_wilson_score_lower_bound(6, 4) - 0.3127).abs() < 1e-4);
+
+        // The better ratio wins over the higher raw score
+        let mut high_score_post = create_post_with_category("high score", None);
+        high_score_post.score = 20;
+        high_score_post.score_minus = 40;
+        let mut high_ratio_post = create_post_with_category("high ratio", None);
+        high_ratio_post.score = 10;
+        high_ratio_post.score_minus = 0;
+        assert!(high_score_post.score > high_ratio_post.score);
+        assert!(upvote_ratio(&high_ratio_post) > upvote_ratio(&high_score_post));
+        assert!(
+            get_wilson_score_lower_bound(high_ratio_post.upvotes(), high_ratio_post.downvotes()) >
+            get_wilson_score_lower_bound(high_score_post.upvotes(), high_score_post.downvotes())
+        );
+    }
+}
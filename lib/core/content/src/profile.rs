@@ -1,27 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+/// Content displayed in a user's profile. When the user hid their profile from the viewer,
+/// `content_vec` is empty and `is_hidden` is true.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileContent<T> {
+    pub content_vec: Vec<T>,
+    pub is_hidden: bool,
+}
+
+impl<T> ProfileContent<T> {
+    pub fn hidden() -> Self {
+        ProfileContent {
+            content_vec: Vec::new(),
+            is_hidden: true,
+        }
+    }
+}
+
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use std::path::Path;
+
+    use leptos::server_fn::codec::MultipartData;
+    use object_store::aws::{AmazonS3, AmazonS3Builder};
+    use object_store::ObjectStoreExt;
+    use sqlx::types::Uuid;
     use sqlx::PgPool;
     use sphare_core_common::checks::check_username;
+    use sphare_core_common::constants::IMAGE_FILE_PARAM;
     use sphare_core_common::errors::AppError;
+    use sphare_core_sphere::sphere_management::ssr::{decode_image, store_webp_image};
+    use sphare_core_user::user::User;
 
     use crate::comment::CommentWithContext;
     use crate::post::ssr::PostJoinSphereInfo;
     use crate::post::PostWithSphereInfo;
+    use crate::profile::ProfileContent;
     use crate::ranking::SortType;
 
+    pub const AVATAR_BUCKET_ENV: &str = "AVATAR_BUCKET";
+    pub const MAX_AVATAR_SIZE: usize = 512 * 1024; // 0.5 MB in bytes
+    pub const AVATAR_SIZE: u32 = 128;
+    pub const MISSING_AVATAR_FILE_STR: &str = "Missing avatar file.";
+    pub const INVALID_AVATAR_FILE_TYPE_STR: &str = "Avatar must be a PNG or JPEG image.";
+    const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+    const JPEG_SIGNATURE: &[u8] = &[0xFF, 0xD8, 0xFF];
+
+    /// Returns whether the user `username` hid their profile content from the viewer `viewer_id`.
+    pub async fn is_profile_hidden(
+        username: &str,
+        viewer_id: Option<i64>,
+        db_pool: &PgPool,
+    ) -> Result<bool, AppError> {
+        let is_hidden = sqlx::query_scalar::<_, bool>(
+            "SELECT NOT public_profile_visible AND user_id IS DISTINCT FROM $2
+            FROM users
+            WHERE username = $1"
+        )
+            .bind(username)
+            .bind(viewer_id)
+            .fetch_optional(db_pool)
+            .await?;
+
+        Ok(is_hidden.unwrap_or(false))
+    }
+
     pub async fn get_user_post_vec(
         username: &str,
         sort_type: SortType,
+        viewer_id: Option<i64>,
         limit: i64,
         offset: i64,
         db_pool: &PgPool,
-    ) -> Result<Vec<PostWithSphereInfo>, AppError> {
+    ) -> Result<ProfileContent<PostWithSphereInfo>, AppError> {
         check_username(username, false)?;
+        if is_profile_hidden(username, viewer_id, db_pool).await? {
+            return Ok(ProfileContent::hidden());
+        }
         let post_vec = sqlx::query_as::<_, PostJoinSphereInfo>(
             format!(
                 "SELECT
                     p.*,
                     u.username as creator_name,
+                    u.avatar_url as creator_avatar_url,
                     c.category_name,
                     c.category_color,
                     s.icon_url as sphere_icon_url,
@@ -33,6 +94,8 @@ pub mod ssr {
                 WHERE
                     u.username = $1 AND
                     p.moderator_id IS NULL AND
+                    p.is_approved AND
+                    p.publish_at IS NULL AND
                     p.delete_timestamp IS NULL
                 ORDER BY {} DESC
                 LIMIT $2
@@ -48,22 +111,30 @@ pub mod ssr {
 
         let post_vec = post_vec.into_iter().map(PostJoinSphereInfo::into_post_with_sphere_info).collect();
 
-        Ok(post_vec)
+        Ok(ProfileContent {
+            content_vec: post_vec,
+            is_hidden: false,
+        })
     }
 
     pub async fn get_user_comment_vec(
         username: &str,
         sort_type: SortType,
+        viewer_id: Option<i64>,
         limit: i64,
         offset: i64,
         db_pool: &PgPool,
-    ) -> Result<Vec<CommentWithContext>, AppError> {
+    ) -> Result<ProfileContent<CommentWithContext>, AppError> {
         check_username(username, false)?;
+        if is_profile_hidden(username, viewer_id, db_pool).await? {
+            return Ok(ProfileContent::hidden());
+        }
         let comment_vec = sqlx::query_as::<_, CommentWithContext>(
             format!(
                 "SELECT
                     c.*,
                     u.username as creator_name,
+                    u.avatar_url as creator_avatar_url,
                     s.sphere_name,
                     s.icon_url,
                     s.is_nsfw,
@@ -89,6 +160,88 @@ pub mod ssr {
             .fetch_all(db_pool)
             .await?;
 
-        Ok(comment_vec)
+        Ok(ProfileContent {
+            content_vec: comment_vec,
+            is_hidden: false,
+        })
+    }
+
+    pub fn get_avatar_object_store() -> Result<AmazonS3, AppError> {
+        AmazonS3Builder::from_env()
+            .with_bucket_name(std::env::var(AVATAR_BUCKET_ENV)?)
+            .build()
+            .map_err(|e| AppError::new(format!("Error while building object store: {e}")))
+    }
+
+    /// Extracts the avatar image of `user` from `data`, stores it in `object_store` and sets it as the user's avatar.
+    /// The avatar must be a PNG or JPEG image, it is resized to `AVATAR_SIZE` and stored in the webp format.
+    /// An empty file removes the user's avatar. Returns the url of the new avatar.
+    pub async fn set_user_avatar<T: ObjectStoreExt>(
+        data: MultipartData,
+        object_store: &T,
+        object_container_url: &str,
+        bucket_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Option<String>, AppError> {
+        // `.into_inner()` returns the inner `multer` stream
+        // it is `None` if we call this on the client, but always `Some(_)` on the server, so is safe to unwrap
+        let mut data = data.into_inner().unwrap();
+        let mut image_buffer = Err(AppError::new(MISSING_AVATAR_FILE_STR));
+
+        while let Ok(Some(mut field)) = data.next_field().await {
+            if field.name().unwrap_or_default() != IMAGE_FILE_PARAM {
+                continue
+            }
+            let mut buffer = Vec::<u8>::new();
+            if !field.file_name().unwrap_or_default().is_empty() {
+                while let Ok(Some(chunk)) = field.chunk().await {
+                    if buffer.len() + chunk.len() > MAX_AVATAR_SIZE {
+                        return Err(AppError::PayloadTooLarge(MAX_AVATAR_SIZE));
+                    }
+                    buffer.extend_from_slice(&chunk);
+                }
+            }
+            image_buffer = Ok(buffer);
+        }
+        let image_buffer = image_buffer?;
+
+        let avatar_url = match image_buffer.is_empty() {
+            true => None,
+            false => {
+                if !image_buffer.starts_with(PNG_SIGNATURE) && !image_buffer.starts_with(JPEG_SIGNATURE) {
+                    return Err(AppError::new(INVALID_AVATAR_FILE_TYPE_STR));
+                }
+                let img = decode_image(image_buffer, INVALID_AVATAR_FILE_TYPE_STR, INVALID_AVATAR_FILE_TYPE_STR)?;
+                let file_name = format!("avatar_{}_{}.webp", user.user_id, Uuid::new_v4());
+                store_webp_image(&img.thumbnail(AVATAR_SIZE, AVATAR_SIZE), &file_name, object_store).await?;
+                Some(
+                    Path::new(object_container_url)
+                        .join(bucket_name)
+                        .join(&file_name)
+                        .to_string_lossy()
+                        .to_string()
+                )
+            }
+        };
+
+        let previous_avatar_url = sqlx::query_scalar::<_, Option<String>>(
+            "UPDATE users u SET avatar_url = $1
+            FROM users previous
+            WHERE u.user_id = $2 AND previous.user_id = u.user_id
+            RETURNING previous.avatar_url"
+        )
+            .bind(&avatar_url)
+            .bind(user.user_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        // Clear previous avatar if it exists
+        if let Some(previous_file_name) = previous_avatar_url.as_deref().and_then(|url| url.rsplit('/').next()) &&
+            let Err(e) = object_store.delete(&object_store::path::Path::from(previous_file_name)).await {
+            log::warn!("Failed to delete previous avatar {previous_file_name}: {e:?}");
+        }
+
+        Ok(avatar_url)
     }
-}
\ No newline at end of file
+}
@@ -1,14 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use sphare_core_common::constants::{BEST_ORDER_BY_COLUMN, HOT_ORDER_BY_COLUMN, RECENT_ORDER_BY_COLUMN, TRENDING_ORDER_BY_COLUMN};
+use sphare_core_common::constants::{BEST_ORDER_BY_COLUMN, RECENT_ORDER_BY_COLUMN};
 
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub enum PostSortType {
-    Hot,
-    Trending,
-    Best,
-    Recent,
-}
+pub use sphare_core_common::common::PostSortType;
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum CommentSortType {
@@ -42,15 +36,26 @@ pub struct Vote {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
-impl PostSortType {
-    pub fn to_order_by_code(self) -> &'static str {
-        match self {
-            PostSortType::Hot => HOT_ORDER_BY_COLUMN,
-            PostSortType::Trending => TRENDING_ORDER_BY_COLUMN,
-            PostSortType::Best => BEST_ORDER_BY_COLUMN,
-            PostSortType::Recent => RECENT_ORDER_BY_COLUMN,
-        }
-    }
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct ContentReaction {
+    pub reaction_id: i64,
+    pub post_id: i64,
+    pub comment_id: Option<i64>,
+    pub user_id: i64,
+    pub reaction: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Aggregated count of a single reaction on a post or comment
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct ReactionCount {
+    pub post_id: i64,
+    pub comment_id: Option<i64>,
+    pub reaction: String,
+    pub count: i64,
+    pub has_reacted: bool,
 }
 
 impl CommentSortType {
@@ -83,10 +88,12 @@ impl From<i16> for VoteValue {
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
-    use crate::ranking::{Vote, VoteValue};
+    use crate::ranking::{ContentReaction, ReactionCount, Vote, VoteValue};
+    use sphare_core_common::constants::ALLOWED_REACTIONS;
     use sphare_core_common::errors::AppError;
+    use sphare_core_common::metrics::{VOTES_METRIC, VOTE_VALUE_LABEL};
     use sphare_core_user::user::User;
-    use sqlx::PgPool;
+    use sqlx::{PgExecutor, PgPool};
 
     pub async fn vote_on_content(
         vote_value: VoteValue,
@@ -119,7 +126,7 @@ pub mod ssr {
                                 p.post_id = $3 AND
                                 b.user_id = $5 AND
                                 b.delete_timestamp IS NULL AND
-                                (b.until_timestamp <= NOW() OR b.until_timestamp IS NULL)
+                                (b.until_timestamp > NOW() OR b.until_timestamp IS NULL)
                         )
                     RETURNING *",
                     vote_value as i16,
@@ -159,7 +166,7 @@ pub mod ssr {
                         p.post_id = $1 AND
                         b.user_id = $3 AND
                         b.delete_timestamp IS NULL AND
-                        (b.until_timestamp <= NOW() OR b.until_timestamp IS NULL)
+                        (b.until_timestamp > NOW() OR b.until_timestamp IS NULL)
                 ) RETURNING *",
                 post_id,
                 comment_id,
@@ -179,6 +186,53 @@ pub mod ssr {
             db_pool,
         ).await?;
 
+        if vote_value != prev_vote_value {
+            metrics::counter!(VOTES_METRIC, VOTE_VALUE_LABEL => format!("{vote_value:?}").to_lowercase()).increment(1);
+        }
+
+        Ok(vote)
+    }
+
+    /// Upvotes the newly created post `post_id` in the name of its creator `user` and updates the post's score in a single
+    /// statement, so that it can be part of the transaction creating the post.
+    pub async fn upvote_new_post<'e, E: PgExecutor<'e>>(
+        post_id: i64,
+        user: &User,
+        executor: E,
+    ) -> Result<Vote, AppError> {
+        user.check_can_publish()?;
+        log::debug!("Upvote new post {post_id} of user {}", user.user_id);
+        let (score_delta, minus_delta) = get_vote_deltas(VoteValue::Up, VoteValue::None);
+        let vote = sqlx::query_as::<_, Vote>(
+            "WITH new_vote AS (
+                INSERT INTO votes (post_id, comment_id, user_id, value)
+                SELECT $1, NULL, $2, $3
+                WHERE NOT EXISTS (
+                    SELECT * FROM user_bans b
+                    JOIN posts p ON p.sphere_id = b.sphere_id
+                    WHERE
+                        p.post_id = $1 AND
+                        b.user_id = $2 AND
+                        b.delete_timestamp IS NULL AND
+                        (b.until_timestamp > NOW() OR b.until_timestamp IS NULL)
+                ) RETURNING *
+            ), scored_post AS (
+                UPDATE posts
+                SET score = score + $4, score_minus = score_minus + $5, scoring_timestamp = NOW()
+                WHERE post_id = $1 AND EXISTS (SELECT * FROM new_vote)
+            )
+            SELECT * FROM new_vote",
+        )
+            .bind(post_id)
+            .bind(user.user_id)
+            .bind(VoteValue::Up as i16)
+            .bind(score_delta)
+            .bind(minus_delta)
+            .fetch_one(executor)
+            .await?;
+
+        metrics::counter!(VOTES_METRIC, VOTE_VALUE_LABEL => format!("{:?}", VoteValue::Up).to_lowercase()).increment(1);
+
         Ok(vote)
     }
 
@@ -256,6 +310,138 @@ pub mod ssr {
         Ok(vote)
     }
 
+    pub async fn add_reaction(
+        post_id: i64,
+        comment_id: Option<i64>,
+        reaction: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<ContentReaction, AppError> {
+        user.check_can_publish()?;
+        let reaction = reaction.trim();
+        if !ALLOWED_REACTIONS.contains(&reaction) {
+            return Err(AppError::ValidationError(format!("Unknown reaction: {reaction}.")));
+        }
+        log::debug!("Add reaction {reaction} on content {post_id}, comment {comment_id:?} for user {}", user.user_id);
+        let content_reaction = sqlx::query_as::<_, ContentReaction>(
+            "INSERT INTO content_reactions (post_id, comment_id, user_id, reaction)
+            SELECT $1, $2, $3, $4
+            WHERE
+                ($2::BIGINT IS NULL OR EXISTS (
+                    SELECT * FROM comments c WHERE c.comment_id = $2 AND c.post_id = $1
+                )) AND
+                NOT EXISTS (
+                    SELECT * FROM user_bans b
+                    JOIN posts p ON p.sphere_id = b.sphere_id
+                    WHERE
+                        p.post_id = $1 AND
+                        b.user_id = $3 AND
+                        b.delete_timestamp IS NULL AND
+                        (b.until_timestamp > NOW() OR b.until_timestamp IS NULL)
+                )
+            ON CONFLICT ON CONSTRAINT unique_content_reaction DO UPDATE SET reaction = EXCLUDED.reaction
+            RETURNING *",
+        )
+            .bind(post_id)
+            .bind(comment_id)
+            .bind(user.user_id)
+            .bind(reaction)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(content_reaction)
+    }
+
+    pub async fn remove_reaction(
+        post_id: i64,
+        comment_id: Option<i64>,
+        reaction: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        log::debug!("Remove reaction {reaction} on content {post_id}, comment {comment_id:?} for user {}", user.user_id);
+        let result = sqlx::query(
+            "DELETE FROM content_reactions
+            WHERE
+                post_id = $1 AND
+                comment_id IS NOT DISTINCT FROM $2 AND
+                user_id = $3 AND
+                reaction = $4",
+        )
+            .bind(post_id)
+            .bind(comment_id)
+            .bind(user.user_id)
+            .bind(reaction.trim())
+            .execute(db_pool)
+            .await?;
+
+        match result.rows_affected() {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the aggregated reaction counts of the post `post_id` itself, excluding its comments
+    pub async fn get_post_reaction_counts(
+        post_id: i64,
+        user_id: Option<i64>,
+        db_pool: &PgPool,
+    ) -> Result<Vec<ReactionCount>, AppError> {
+        let reaction_count_vec = sqlx::query_as::<_, ReactionCount>(
+            "SELECT
+                post_id,
+                comment_id,
+                reaction,
+                COUNT(*) AS count,
+                COALESCE(BOOL_OR(user_id = $2), FALSE) AS has_reacted
+            FROM content_reactions
+            WHERE
+                post_id = $1 AND
+                comment_id IS NULL
+            GROUP BY post_id, comment_id, reaction
+            ORDER BY count DESC, MIN(timestamp), reaction",
+        )
+            .bind(post_id)
+            .bind(user_id)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(reaction_count_vec)
+    }
+
+    /// Returns the aggregated reaction counts of the comments `comment_id_vec` of the post `post_id`
+    pub async fn get_comment_reaction_counts(
+        post_id: i64,
+        comment_id_vec: &[i64],
+        user_id: Option<i64>,
+        db_pool: &PgPool,
+    ) -> Result<Vec<ReactionCount>, AppError> {
+        if comment_id_vec.is_empty() {
+            return Ok(Vec::new());
+        }
+        let reaction_count_vec = sqlx::query_as::<_, ReactionCount>(
+            "SELECT
+                post_id,
+                comment_id,
+                reaction,
+                COUNT(*) AS count,
+                COALESCE(BOOL_OR(user_id = $3), FALSE) AS has_reacted
+            FROM content_reactions
+            WHERE
+                post_id = $1 AND
+                comment_id = ANY($2)
+            GROUP BY post_id, comment_id, reaction
+            ORDER BY comment_id, count DESC, MIN(timestamp), reaction",
+        )
+            .bind(post_id)
+            .bind(comment_id_vec)
+            .bind(user_id)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(reaction_count_vec)
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -324,14 +510,17 @@ pub fn update_vote_value(vote: &mut VoteValue, is_upvote: bool) {
 #[cfg(test)]
 mod tests {
     use crate::ranking::{update_vote_value, CommentSortType, PostSortType, SortType, VoteValue};
-    use sphare_core_common::constants::{BEST_ORDER_BY_COLUMN, HOT_ORDER_BY_COLUMN, RECENT_ORDER_BY_COLUMN, TRENDING_ORDER_BY_COLUMN};
+    use sphare_core_common::constants::{BEST_ORDER_BY_COLUMN, BEST_POST_ORDER_BY_COLUMN, BEST_RATIO_POST_ORDER_BY_COLUMN, BEST_SORT_MIN_VOTE_COUNT, HOT_ORDER_BY_COLUMN, RECENT_ORDER_BY_COLUMN, TRENDING_ORDER_BY_COLUMN};
 
     #[test]
     fn test_post_sort_type_to_order_by_code() {
         assert_eq!(PostSortType::Hot.to_order_by_code(), HOT_ORDER_BY_COLUMN);
         assert_eq!(PostSortType::Trending.to_order_by_code(), TRENDING_ORDER_BY_COLUMN);
-        assert_eq!(PostSortType::Best.to_order_by_code(), BEST_ORDER_BY_COLUMN);
+        assert_eq!(PostSortType::Best.to_order_by_code(), BEST_POST_ORDER_BY_COLUMN);
+        assert_eq!(PostSortType::BestRatio.to_order_by_code(), BEST_RATIO_POST_ORDER_BY_COLUMN);
         assert_eq!(PostSortType::Recent.to_order_by_code(), RECENT_ORDER_BY_COLUMN);
+        assert!(BEST_POST_ORDER_BY_COLUMN.contains(&format!(">= {BEST_SORT_MIN_VOTE_COUNT})")));
+        assert!(BEST_POST_ORDER_BY_COLUMN.ends_with(BEST_ORDER_BY_COLUMN));
     }
 
     #[test]
@@ -344,7 +533,8 @@ mod tests {
     fn test_sort_type_to_order_by_code() {
         assert_eq!(SortType::Post(PostSortType::Hot).to_order_by_code(), HOT_ORDER_BY_COLUMN);
         assert_eq!(SortType::Post(PostSortType::Trending).to_order_by_code(), TRENDING_ORDER_BY_COLUMN);
-        assert_eq!(SortType::Post(PostSortType::Best).to_order_by_code(), BEST_ORDER_BY_COLUMN);
+        assert_eq!(SortType::Post(PostSortType::Best).to_order_by_code(), BEST_POST_ORDER_BY_COLUMN);
+        assert_eq!(SortType::Post(PostSortType::BestRatio).to_order_by_code(), BEST_RATIO_POST_ORDER_BY_COLUMN);
         assert_eq!(SortType::Post(PostSortType::Recent).to_order_by_code(), RECENT_ORDER_BY_COLUMN);
         assert_eq!(SortType::Comment(CommentSortType::Best).to_order_by_code(), BEST_ORDER_BY_COLUMN);
         assert_eq!(SortType::Comment(CommentSortType::Recent).to_order_by_code(), RECENT_ORDER_BY_COLUMN);
@@ -1,5 +1,6 @@
 use leptos::prelude::*;
 use leptos_use::signal_debounced;
+use serde::{Deserialize, Serialize};
 
 use sphare_core_common::checks::check_string_length;
 use sphare_core_common::constants::MAX_SEARCH_QUERY_LENGTH;
@@ -23,36 +24,70 @@ impl Default for SearchState {
     }
 }
 
+/// Post search query saved by a user, who is notified of new matching posts
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub saved_search_id: i64,
+    pub user_id: i64,
+    pub search_query: String,
+    pub sphere_id: Option<i64>,
+    pub sphere_name: Option<String>,
+    /// Posts created after this timestamp have not been notified yet
+    pub last_run_timestamp: chrono::DateTime<chrono::Utc>,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+}
+
 #[cfg(feature = "ssr")]
 pub mod ssr {
     use sqlx::PgPool;
     use std::cmp::min;
     use sphare_core_common::checks::{check_sphere_name, check_sphere_name_with_options, check_string_length};
     use sphare_core_common::common::SphereHeader;
-    use sphare_core_common::constants::{MAX_SEARCH_QUERY_LENGTH, SPHERE_FETCH_LIMIT};
+    use sphare_core_common::constants::{MAX_SAVED_SEARCHES, MAX_SEARCH_QUERY_LENGTH, SAVED_SEARCH_NOTIF_LIMIT, SPHERE_FETCH_LIMIT, SPHERE_NAME_SIMILARITY_THRESHOLD};
     use sphare_core_common::errors::AppError;
+    use sphare_core_sphere::sphere::ssr::get_sphere_by_name;
+    use sphare_core_user::notification::ssr::create_saved_search_notifications;
+    use sphare_core_user::user::User;
     use crate::comment::CommentWithContext;
     use crate::post::ssr::PostJoinSphereInfo;
     use crate::post::PostWithSphereInfo;
+    use crate::search::SavedSearch;
 
 
+    /// Returns the spheres whose name starts with `sphere_prefix`, followed by the spheres whose name is similar enough to it
+    /// so that typos still find the intended sphere.
     pub async fn get_matching_sphere_header_vec(
         sphere_prefix: &str,
         limit: i64,
         db_pool: &PgPool,
     ) -> Result<Vec<SphereHeader>, AppError> {
         check_sphere_name_with_options(&sphere_prefix, false)?;
+        let mut tx = db_pool.begin().await?;
+        // the `<%` operator and the prefix LIKE can use the trigram index, contrary to a comparison with `word_similarity`.
+        // `<%` uses the session's threshold, which is set for this transaction only
+        sqlx::query("SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)")
+            .bind(SPHERE_NAME_SIMILARITY_THRESHOLD.to_string())
+            .execute(&mut *tx)
+            .await?;
         let sphere_header_vec = sqlx::query_as!(
             SphereHeader,
             "SELECT sphere_name, icon_url, is_nsfw
             FROM spheres
-            WHERE normalized_sphere_name LIKE normalize_sphere_name($1)
-            ORDER BY sphere_name LIMIT $2",
-            format!("{sphere_prefix}%"),
+            WHERE
+                normalized_sphere_name LIKE REPLACE(normalize_sphere_name($1), '_', '\\_') || '%' OR
+                normalize_sphere_name($1) <% normalized_sphere_name
+            ORDER BY
+                starts_with(normalized_sphere_name, normalize_sphere_name($1)) DESC,
+                word_similarity(normalize_sphere_name($1), normalized_sphere_name) DESC,
+                sphere_name
+            LIMIT $2",
+            sphere_prefix,
             limit,
         )
-            .fetch_all(db_pool)
+            .fetch_all(&mut *tx)
             .await?;
+        tx.commit().await?;
 
         Ok(sphere_header_vec)
     }
@@ -132,6 +167,7 @@ pub mod ssr {
             "SELECT
                 p.*,
                 u.username as creator_name,
+                u.avatar_url as creator_avatar_url,
                 c.category_name,
                 c.category_color,
                 s.icon_url as sphere_icon_url,
@@ -148,6 +184,8 @@ pub mod ssr {
                 ($3 OR NOT p.is_spoiler) AND
                 ($4 OR NOT p.is_nsfw) AND
                 p.moderator_id IS NULL AND
+                p.is_approved AND
+                p.publish_at IS NULL AND
                 p.delete_timestamp IS NULL
             ORDER BY rank DESC, p.score DESC
             LIMIT $5
@@ -182,6 +220,7 @@ pub mod ssr {
             "SELECT
                 c.*,
                 u.username as creator_name,
+                u.avatar_url as creator_avatar_url,
                 p.sphere_id,
                 p.satellite_id,
                 p.title as post_title,
@@ -212,6 +251,162 @@ pub mod ssr {
 
         Ok(comment_vec)
     }
+
+    pub async fn get_saved_search_vec(
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<SavedSearch>, AppError> {
+        let saved_search_vec = sqlx::query_as::<_, SavedSearch>(
+            "SELECT ss.*, s.sphere_name
+            FROM saved_searches ss
+            LEFT JOIN spheres s ON s.sphere_id = ss.sphere_id
+            WHERE ss.user_id = $1
+            ORDER BY ss.create_timestamp"
+        )
+            .bind(user.user_id)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(saved_search_vec)
+    }
+
+    /// Saves the post search `search_query`, optionally restricted to the sphere `sphere_name`. The user is notified of matching
+    /// posts created from now on.
+    pub async fn create_saved_search(
+        search_query: &str,
+        sphere_name: Option<&str>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<SavedSearch, AppError> {
+        let search_query = search_query.trim();
+        check_string_length(search_query, "Search query", MAX_SEARCH_QUERY_LENGTH, false)?;
+        if let Some(sphere_name) = &sphere_name {
+            check_sphere_name(sphere_name)?;
+        }
+        if get_saved_search_vec(user, db_pool).await?.len() >= MAX_SAVED_SEARCHES {
+            return Err(AppError::ValidationError(format!("Cannot save more than {MAX_SAVED_SEARCHES} searches.")));
+        }
+
+        let sphere_id = match sphere_name {
+            Some(sphere_name) => Some(get_sphere_by_name(sphere_name, db_pool).await?.sphere_id),
+            None => None,
+        };
+
+        let saved_search = sqlx::query_as::<_, SavedSearch>(
+            "WITH new_saved_search AS (
+                INSERT INTO saved_searches (user_id, search_query, sphere_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT ON CONSTRAINT unique_saved_search DO UPDATE SET search_query = EXCLUDED.search_query
+                RETURNING *
+            )
+            SELECT ss.*, s.sphere_name
+            FROM new_saved_search ss
+            LEFT JOIN spheres s ON s.sphere_id = ss.sphere_id"
+        )
+            .bind(user.user_id)
+            .bind(search_query)
+            .bind(sphere_id)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(saved_search)
+    }
+
+    pub async fn delete_saved_search(
+        saved_search_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "DELETE FROM saved_searches WHERE saved_search_id = $1 AND user_id = $2"
+        )
+            .bind(saved_search_id)
+            .bind(user.user_id)
+            .execute(db_pool)
+            .await?;
+
+        match result.rows_affected() {
+            0 => Err(AppError::NotFound),
+            _ => Ok(()),
+        }
+    }
+
+    /// Runs every saved search and notifies their users of the matching posts published since the search's last run.
+    /// Returns the number of created notifications.
+    pub async fn run_saved_searches(
+        db_pool: &PgPool,
+    ) -> Result<usize, AppError> {
+        let saved_search_vec = sqlx::query_as::<_, SavedSearch>(
+            "SELECT ss.*, s.sphere_name
+            FROM saved_searches ss
+            LEFT JOIN spheres s ON s.sphere_id = ss.sphere_id
+            ORDER BY ss.saved_search_id"
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        let mut num_notifications = 0;
+        for saved_search in saved_search_vec {
+            num_notifications += run_saved_search(&saved_search, db_pool).await?;
+        }
+
+        Ok(num_notifications)
+    }
+
+    async fn run_saved_search(
+        saved_search: &SavedSearch,
+        db_pool: &PgPool,
+    ) -> Result<usize, AppError> {
+        let run_timestamp = chrono::Utc::now();
+        let matching_post_vec = sqlx::query_as::<_, (i64, chrono::DateTime<chrono::Utc>)>(
+            "SELECT p.post_id, p.publish_timestamp
+            FROM posts p
+            JOIN spheres s ON s.sphere_id = p.sphere_id
+            JOIN users u ON u.user_id = $3
+            WHERE
+                p.post_document @@ plainto_tsquery('simple', $1) AND
+                ($2::BIGINT IS NULL OR p.sphere_id = $2) AND
+                (u.nsfw_mode != 'Hide' OR NOT p.is_nsfw) AND
+                p.creator_id != $3 AND
+                p.publish_timestamp > $4 AND
+                p.publish_timestamp <= $5 AND
+                p.moderator_id IS NULL AND
+                p.is_approved AND
+                p.publish_at IS NULL AND
+                p.delete_timestamp IS NULL
+            ORDER BY p.publish_timestamp
+            LIMIT $6"
+        )
+            .bind(&saved_search.search_query)
+            .bind(saved_search.sphere_id)
+            .bind(saved_search.user_id)
+            .bind(saved_search.last_run_timestamp)
+            .bind(run_timestamp)
+            .bind(SAVED_SEARCH_NOTIF_LIMIT)
+            .fetch_all(db_pool)
+            .await?;
+
+        // When the limit is reached, only advance up to the last notified post so that the remaining ones are notified on the next runs
+        let last_run_timestamp = match matching_post_vec.last() {
+            Some((_, publish_timestamp)) if matching_post_vec.len() as i64 >= SAVED_SEARCH_NOTIF_LIMIT => *publish_timestamp,
+            _ => run_timestamp,
+        };
+        let post_id_vec = matching_post_vec.into_iter().map(|(post_id, _)| post_id).collect::<Vec<i64>>();
+
+        let mut tx = db_pool.begin().await?;
+
+        let notification_vec = create_saved_search_notifications(saved_search.user_id, &post_id_vec, &mut *tx).await?;
+
+        sqlx::query("UPDATE saved_searches SET last_run_timestamp = $1 WHERE saved_search_id = $2")
+            .bind(last_run_timestamp)
+            .bind(saved_search.saved_search_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(notification_vec.len())
+    }
 }
 
 /// # Returns whether a search for content is valid
@@ -250,7 +445,7 @@ mod tests {
         search.set(String::from(&"a".repeat(MAX_SEARCH_QUERY_LENGTH + 1)));
         assert_eq!(
             is_search_valid.get_untracked(),
-            Some(AppError::new(format!("Sphere search exceeds the maximum length: {MAX_SEARCH_QUERY_LENGTH}.")))
+            Some(AppError::ValidationError(format!("Sphere search exceeds the maximum length: {MAX_SEARCH_QUERY_LENGTH}.")))
         );
     }
 }
\ No newline at end of file
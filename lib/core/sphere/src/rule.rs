@@ -240,6 +240,79 @@ pub mod ssr {
 
         Ok(())
     }
+
+    pub async fn has_accepted_rules(
+        sphere_name: &str,
+        user_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<bool, AppError> {
+        check_sphere_name(sphere_name)?;
+        let has_accepted_rules = sqlx::query_scalar!(
+            "SELECT EXISTS (
+                SELECT 1 FROM sphere_rule_acceptances a
+                JOIN spheres s ON s.sphere_id = a.sphere_id
+                WHERE s.sphere_name = $1 AND a.user_id = $2
+            )",
+            sphere_name,
+            user_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(has_accepted_rules.unwrap_or(false))
+    }
+
+    pub async fn accept_rules(
+        sphere_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        check_sphere_name(sphere_name)?;
+        sqlx::query!(
+            "INSERT INTO sphere_rule_acceptances (user_id, sphere_id)
+            VALUES (
+                $1,
+                (SELECT sphere_id FROM spheres WHERE sphere_name = $2)
+            )
+            ON CONFLICT (sphere_id, user_id) DO NOTHING",
+            user.user_id,
+            sphere_name,
+        )
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Checks that `user` accepted the rules of `sphere_name` if the sphere requires it before publishing.
+    /// Sphere moderators are exempted.
+    pub async fn check_rules_accepted(
+        sphere_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        if user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate).is_ok() {
+            return Ok(())
+        }
+        let is_accepted = sqlx::query_scalar!(
+            "SELECT
+                NOT s.require_rule_acceptance OR EXISTS (
+                    SELECT 1 FROM sphere_rule_acceptances a
+                    WHERE a.sphere_id = s.sphere_id AND a.user_id = $2
+                )
+            FROM spheres s
+            WHERE s.sphere_name = $1",
+            sphere_name,
+            user.user_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        match is_accepted {
+            Some(true) => Ok(()),
+            _ => Err(AppError::RulesNotAccepted),
+        }
+    }
 }
 
 pub fn get_rule_title(rule_title: &str, is_sphere_rule: bool) -> Signal<String> {
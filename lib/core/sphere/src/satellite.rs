@@ -212,4 +212,47 @@ pub mod ssr {
 
         Ok(satellite)
     }
-}
\ No newline at end of file
+
+    pub async fn is_subscribed_to_satellite(satellite_id: i64, user_id: i64, db_pool: &PgPool) -> Result<bool, AppError> {
+        let is_subscribed = sqlx::query_scalar!(
+            "SELECT EXISTS (
+                SELECT 1 FROM satellite_subscriptions WHERE user_id = $1 AND satellite_id = $2
+            )",
+            user_id,
+            satellite_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(is_subscribed.unwrap_or(false))
+    }
+
+    pub async fn subscribe_satellite(satellite_id: i64, user_id: i64, db_pool: &PgPool) -> Result<(), AppError> {
+        sqlx::query!(
+            "INSERT INTO satellite_subscriptions (user_id, satellite_id) VALUES ($1, $2)",
+            user_id,
+            satellite_id
+        )
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn unsubscribe_satellite(satellite_id: i64, user_id: i64, db_pool: &PgPool) -> Result<(), AppError> {
+        let deleted_rows = sqlx::query!(
+            "DELETE FROM satellite_subscriptions WHERE user_id = $1 AND satellite_id = $2",
+            user_id,
+            satellite_id,
+        )
+            .execute(db_pool)
+            .await?
+            .rows_affected();
+
+        if deleted_rows != 1 {
+            return Err(AppError::InternalServerError(format!("Expected one satellite subscription deleted, got {deleted_rows} instead.")))
+        }
+
+        Ok(())
+    }
+}
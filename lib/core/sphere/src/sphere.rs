@@ -1,6 +1,23 @@
 use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
 
+use sphare_core_common::checks::check_sphere_name;
+use sphare_core_common::colors::Color;
 use sphare_core_common::common::SphereHeader;
+use sphare_core_common::errors::AppError;
+use sphare_core_user::user::KarmaType;
+
+/// Types of posts that can be published in a sphere
+#[repr(i16)]
+#[derive(Clone, Copy, Debug, Default, Display, EnumIter, EnumString, Eq, Hash, IntoStaticStr, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+pub enum AllowedPostTypes {
+    #[default]
+    Any = 0,
+    Text = 1,
+    Link = 2,
+    Image = 3,
+}
 
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
 #[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
@@ -17,6 +34,24 @@ pub struct Sphere {
     pub creator_id: i64,
     pub create_timestamp: chrono::DateTime<chrono::Utc>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub require_rule_acceptance: bool,
+    pub require_post_approval: bool,
+    pub reject_duplicate_links: bool,
+    pub require_category: bool,
+    /// Minimum age in days of an account to post in the sphere
+    pub min_account_age_days: i32,
+    /// Minimum karma of the type `min_karma_type` to post in the sphere
+    pub min_karma: i32,
+    pub min_karma_type: KarmaType,
+    pub allowed_post_types: AllowedPostTypes,
+    /// Title rules of the sphere's posts, enforced on top of the global title checks
+    pub min_title_length: i32,
+    pub max_title_length: Option<i32>,
+    pub title_regex: Option<String>,
+    pub is_archived: bool,
+    pub sidebar_markdown: Option<String>,
+    pub sidebar_html: Option<String>,
+    pub accent_color: Color,
 }
 
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
@@ -42,20 +77,44 @@ impl From<&Sphere> for SphereHeader {
     }
 }
 
+/// Returns the normalized version of `sphere_name`, used to enforce case-insensitive uniqueness of sphere names.
+///
+/// The name is trimmed and validated with [`check_sphere_name`], then lowercased with dashes replaced by underscores,
+/// matching the `normalize_sphere_name` SQL function.
+///
+/// ```
+/// use sphare_core_sphere::sphere::normalize_sphere_name;
+///
+/// assert_eq!(normalize_sphere_name("Foo").as_deref(), Ok("foo"));
+/// assert_eq!(normalize_sphere_name(" camelCase-snake_1 ").as_deref(), Ok("camelcase_snake_1"));
+/// assert!(normalize_sphere_name("").is_err());
+/// assert!(normalize_sphere_name("foo bar").is_err());
+/// assert!(normalize_sphere_name("foo%").is_err());
+/// ```
+pub fn normalize_sphere_name(sphere_name: &str) -> Result<String, AppError> {
+    let sphere_name = sphere_name.trim();
+    check_sphere_name(sphere_name)?;
+    Ok(sphere_name.to_lowercase().replace('-', "_"))
+}
+
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use regex::{Regex, RegexBuilder};
     use sqlx::PgPool;
 
-    use sphare_core_common::checks::{check_sphere_name, check_string_length};
-    use sphare_core_common::constants::MAX_SPHERE_DESCRIPTION_LENGTH;
+    use sphare_core_common::checks::{check_reserved_name, check_sphere_name, check_string_length};
+    use sphare_core_common::constants::{MAX_MIN_ACCOUNT_AGE_DAYS, MAX_SPHERE_DESCRIPTION_LENGTH, MAX_TITLE_LENGTH, MAX_TITLE_REGEX_LENGTH, TRENDING_SPHERE_DAYS};
     use sphare_core_common::errors::AppError;
     use sphare_core_common::errors::AppError::InternalServerError;
     use sphare_core_common::routes::get_sphere_path;
     use sphare_core_user::role::ssr::init_sphere_leader;
     use sphare_core_user::role::PermissionLevel;
-    use sphare_core_user::user::User;
+    use sphare_core_user::user::{KarmaType, User};
+
+    use crate::sphere::{normalize_sphere_name, AllowedPostTypes, Sphere, SphereHeader, SphereWithUserInfo};
 
-    use crate::sphere::{Sphere, SphereHeader, SphereWithUserInfo};
+    /// Maximum compiled size of a sphere's title regex, to limit the cost of user-provided patterns
+    const TITLE_REGEX_SIZE_LIMIT: usize = 1 << 16;
 
     pub async fn get_sphere_by_name(sphere_name: &str, db_pool: &PgPool) -> Result<Sphere, AppError> {
         check_sphere_name(sphere_name)?;
@@ -110,6 +169,9 @@ pub mod ssr {
 
     pub async fn is_sphere_available(sphere_name: &str, db_pool: &PgPool) -> Result<bool, AppError> {
         check_sphere_name(sphere_name)?;
+        if check_reserved_name(sphere_name).is_err() {
+            return Ok(false)
+        }
         let sphere_exist = sqlx::query!(
             "SELECT sphere_id FROM spheres WHERE normalized_sphere_name = normalize_sphere_name($1)",
             sphere_name,
@@ -142,6 +204,99 @@ pub mod ssr {
         Ok(sphere_header_vec)
     }
 
+    /// Returns the spheres that gained the most subscriptions in the last `TRENDING_SPHERE_DAYS` days,
+    /// regardless of their total number of members. Spheres without recent subscriptions are excluded.
+    pub async fn get_trending_sphere_vec(
+        limit: i64,
+        offset: i64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<SphereHeader>, AppError> {
+        get_unsubscribed_trending_sphere_vec(None, limit, offset, db_pool).await
+    }
+
+    /// Returns the trending spheres like [`get_trending_sphere_vec`], excluding the spheres the user `user_id` is subscribed to.
+    async fn get_unsubscribed_trending_sphere_vec(
+        user_id: Option<i64>,
+        limit: i64,
+        offset: i64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<SphereHeader>, AppError> {
+        let sphere_header_vec = sqlx::query_as!(
+            SphereHeader,
+            "SELECT s.sphere_name, s.icon_url, s.is_nsfw
+            FROM spheres s
+            JOIN (
+                SELECT sphere_id, COUNT(*) AS num_recent_subscriptions
+                FROM sphere_subscriptions
+                WHERE timestamp > NOW() - make_interval(days => $1)
+                GROUP BY sphere_id
+            ) sub ON sub.sphere_id = s.sphere_id
+            WHERE
+                NOT s.is_nsfw AND
+                NOT s.is_banned AND
+                NOT EXISTS (
+                    SELECT 1 FROM sphere_subscriptions user_sub
+                    WHERE user_sub.user_id = $4 AND user_sub.sphere_id = s.sphere_id
+                )
+            ORDER BY sub.num_recent_subscriptions DESC, s.sphere_name
+            LIMIT $2
+            OFFSET $3",
+            TRENDING_SPHERE_DAYS as i32,
+            limit,
+            offset,
+            user_id,
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(sphere_header_vec)
+    }
+
+    /// Returns spheres recommended to `user`: the spheres most subscribed to by the users sharing subscriptions with `user`,
+    /// excluding the ones `user` is already subscribed to. Falls back to trending spheres for anonymous users
+    /// and to the trending spheres `user` is not subscribed to for users without co-subscription recommendations.
+    pub async fn get_recommended_spheres(
+        user: Option<&User>,
+        limit: i64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<SphereHeader>, AppError> {
+        let Some(user) = user else {
+            return get_trending_sphere_vec(limit, 0, db_pool).await;
+        };
+        let sphere_header_vec = sqlx::query_as!(
+            SphereHeader,
+            "SELECT s.sphere_name, s.icon_url, s.is_nsfw
+            FROM sphere_subscriptions own_sub
+            JOIN sphere_subscriptions peer_sub ON
+                peer_sub.sphere_id = own_sub.sphere_id AND
+                peer_sub.user_id <> own_sub.user_id
+            JOIN sphere_subscriptions rec_sub ON rec_sub.user_id = peer_sub.user_id
+            JOIN spheres s ON s.sphere_id = rec_sub.sphere_id
+            WHERE
+                own_sub.user_id = $1 AND
+                NOT EXISTS (
+                    SELECT 1 FROM sphere_subscriptions sub
+                    WHERE sub.user_id = $1 AND sub.sphere_id = rec_sub.sphere_id
+                ) AND
+                NOT s.is_banned AND
+                NOT s.is_archived AND
+                (NOT s.is_nsfw OR $2)
+            GROUP BY s.sphere_id
+            ORDER BY COUNT(*) DESC, s.num_members DESC, s.sphere_name
+            LIMIT $3",
+            user.user_id,
+            user.nsfw_mode.includes_nsfw(),
+            limit,
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        match sphere_header_vec.is_empty() {
+            true => get_unsubscribed_trending_sphere_vec(Some(user.user_id), limit, 0, db_pool).await,
+            false => Ok(sphere_header_vec),
+        }
+    }
+
     pub async fn get_subscribed_sphere_headers(
         user_id: i64,
         db_pool: &PgPool,
@@ -170,12 +325,10 @@ pub mod ssr {
         user: &User,
         db_pool: &PgPool,
     ) -> Result<(Sphere, String), AppError> {
-        check_sphere_name(sphere_name)?;
+        normalize_sphere_name(sphere_name)?;
         check_string_length(description, "Sphere description", MAX_SPHERE_DESCRIPTION_LENGTH, false)?;
         log::trace!("Create Sphere '{sphere_name}', {description}, {is_nsfw}");
 
-        let new_sphere_path = get_sphere_path(sphere_name);
-
         let mut sphere = create_sphere(
             sphere_name,
             description,
@@ -184,6 +337,8 @@ pub mod ssr {
             db_pool,
         ).await?;
 
+        let new_sphere_path = get_sphere_path(&sphere.sphere_name);
+
         subscribe(sphere.sphere_id, user.user_id, db_pool).await?;
 
         sphere.num_members = 1;
@@ -199,17 +354,23 @@ pub mod ssr {
         db_pool: &PgPool,
     ) -> Result<Sphere, AppError> {
         user.check_can_publish()?;
-        check_sphere_name(name)?;
+        let name = name.trim();
+        normalize_sphere_name(name)?;
+        check_reserved_name(name)?;
 
+        // Names are unique once normalized, conflicts are reported as taken names instead of database errors
         let sphere = sqlx::query_as::<_, Sphere>(
-            "INSERT INTO spheres (sphere_name, description, is_nsfw, creator_id) VALUES ($1, $2, $3, $4) RETURNING *"
+            "INSERT INTO spheres (sphere_name, description, is_nsfw, creator_id) VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+            RETURNING *"
         )
             .bind(name)
             .bind(description)
             .bind(is_nsfw)
             .bind(user.user_id)
-            .fetch_one(db_pool)
-            .await?;
+            .fetch_optional(db_pool)
+            .await?
+            .ok_or(AppError::SphereNameTaken)?;
 
         init_sphere_leader(user.user_id, &sphere.sphere_name, &db_pool).await?;
 
@@ -237,6 +398,223 @@ pub mod ssr {
         Ok(sphere)
     }
 
+    pub async fn set_sphere_require_rule_acceptance(
+        sphere_name: &str,
+        require_rule_acceptance: bool,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET require_rule_acceptance = $1, timestamp = NOW() WHERE sphere_name = $2 RETURNING *"
+        )
+            .bind(require_rule_acceptance)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
+    pub async fn set_sphere_require_post_approval(
+        sphere_name: &str,
+        require_post_approval: bool,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET require_post_approval = $1, timestamp = NOW() WHERE sphere_name = $2 RETURNING *"
+        )
+            .bind(require_post_approval)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
+    pub async fn set_sphere_reject_duplicate_links(
+        sphere_name: &str,
+        reject_duplicate_links: bool,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET reject_duplicate_links = $1, timestamp = NOW() WHERE sphere_name = $2 RETURNING *"
+        )
+            .bind(reject_duplicate_links)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
+    pub async fn set_sphere_require_category(
+        sphere_name: &str,
+        require_category: bool,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET require_category = $1, timestamp = NOW() WHERE sphere_name = $2 RETURNING *"
+        )
+            .bind(require_category)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
+    pub async fn set_sphere_min_account_age_days(
+        sphere_name: &str,
+        min_account_age_days: i32,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        if !(0..=MAX_MIN_ACCOUNT_AGE_DAYS).contains(&min_account_age_days) {
+            return Err(AppError::new(format!("Minimum account age must be between 0 and {MAX_MIN_ACCOUNT_AGE_DAYS} days.")));
+        }
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET min_account_age_days = $1, timestamp = NOW() WHERE sphere_name = $2 RETURNING *"
+        )
+            .bind(min_account_age_days)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
+    pub async fn set_sphere_min_karma(
+        sphere_name: &str,
+        min_karma: i32,
+        min_karma_type: KarmaType,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        if min_karma < 0 {
+            return Err(AppError::new("Minimum karma cannot be negative."));
+        }
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET min_karma = $1, min_karma_type = $2, timestamp = NOW() WHERE sphere_name = $3 RETURNING *"
+        )
+            .bind(min_karma)
+            .bind(min_karma_type)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
+    pub async fn set_sphere_allowed_post_types(
+        sphere_name: &str,
+        allowed_post_types: AllowedPostTypes,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET allowed_post_types = $1, timestamp = NOW() WHERE sphere_name = $2 RETURNING *"
+        )
+            .bind(allowed_post_types)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
+    /// Checks that `title` satisfies the title rules of a sphere: its length in characters is between `min_title_length`
+    /// and `max_title_length` and it matches `title_regex`.
+    pub fn check_sphere_post_title(
+        title: &str,
+        min_title_length: i32,
+        max_title_length: Option<i32>,
+        title_regex: Option<&str>,
+    ) -> Result<(), AppError> {
+        let title_length = title.chars().count();
+        if title_length < min_title_length as usize {
+            return Err(AppError::TitleTooShort(min_title_length));
+        }
+        if let Some(max_title_length) = max_title_length && title_length > max_title_length as usize {
+            return Err(AppError::TitleTooLong(max_title_length));
+        }
+        if let Some(title_regex) = title_regex && !get_title_regex(title_regex)?.is_match(title) {
+            return Err(AppError::TitleFormatMismatch(title_regex.to_string()));
+        }
+        Ok(())
+    }
+
+    fn get_title_regex(title_regex: &str) -> Result<Regex, AppError> {
+        RegexBuilder::new(title_regex)
+            .size_limit(TITLE_REGEX_SIZE_LIMIT)
+            .build()
+            .map_err(|e| AppError::new(format!("Invalid title regex: {e}")))
+    }
+
+    pub async fn set_sphere_title_rules(
+        sphere_name: &str,
+        min_title_length: i32,
+        max_title_length: Option<i32>,
+        title_regex: Option<&str>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        if min_title_length < 0 || min_title_length > max_title_length.unwrap_or(MAX_TITLE_LENGTH as i32) {
+            return Err(AppError::new("Minimum title length must be positive and cannot exceed the maximum title length."));
+        }
+        if let Some(max_title_length) = max_title_length && !(1..=MAX_TITLE_LENGTH as i32).contains(&max_title_length) {
+            return Err(AppError::new(format!("Maximum title length must be between 1 and {MAX_TITLE_LENGTH}.")));
+        }
+        let title_regex = title_regex.filter(|title_regex| !title_regex.is_empty());
+        if let Some(title_regex) = title_regex {
+            check_string_length(title_regex, "Title regex", MAX_TITLE_REGEX_LENGTH, false)?;
+            get_title_regex(title_regex)?;
+        }
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET
+                min_title_length = $1,
+                max_title_length = $2,
+                title_regex = $3,
+                timestamp = NOW()
+            WHERE sphere_name = $4
+            RETURNING *"
+        )
+            .bind(min_title_length)
+            .bind(max_title_length)
+            .bind(title_regex)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
     pub async fn subscribe(sphere_id: i64, user_id: i64, db_pool: &PgPool) -> Result<(), AppError> {
         sqlx::query!(
             "INSERT INTO sphere_subscriptions (user_id, sphere_id) VALUES ($1, $2)",
@@ -256,6 +634,32 @@ pub mod ssr {
         Ok(())
     }
 
+    /// Subscribes the user to all spheres in `sphere_ids` and updates their number of members in a single statement.
+    /// Existing subscriptions and unknown spheres are skipped, returns the number of added subscriptions.
+    pub async fn subscribe_many(sphere_ids: Vec<i64>, user_id: i64, db_pool: &PgPool) -> Result<u64, AppError> {
+        let num_added_subscriptions = sqlx::query_scalar!(
+            "WITH new_subscriptions AS (
+                INSERT INTO sphere_subscriptions (user_id, sphere_id)
+                SELECT $1, sphere_id FROM spheres WHERE sphere_id = ANY($2)
+                ON CONFLICT (user_id, sphere_id) DO NOTHING
+                RETURNING sphere_id
+            ), updated_spheres AS (
+                UPDATE spheres s
+                SET num_members = num_members + 1
+                FROM new_subscriptions n
+                WHERE s.sphere_id = n.sphere_id
+                RETURNING s.sphere_id
+            )
+            SELECT COUNT(*) FROM updated_spheres",
+            user_id,
+            &sphere_ids,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(num_added_subscriptions.unwrap_or(0) as u64)
+    }
+
     pub async fn unsubscribe(sphere_id: i64, user_id: i64, db_pool: &PgPool) -> Result<(), AppError> {
         let deleted_rows = sqlx::query!(
             "DELETE FROM sphere_subscriptions WHERE user_id = $1 AND sphere_id = $2",
@@ -279,4 +683,94 @@ pub mod ssr {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    pub async fn has_confirmed_nsfw(
+        sphere_name: &str,
+        user_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<bool, AppError> {
+        check_sphere_name(sphere_name)?;
+        let has_confirmed_nsfw = sqlx::query_scalar!(
+            "SELECT EXISTS (
+                SELECT 1 FROM nsfw_confirmations c
+                JOIN spheres s ON s.sphere_id = c.sphere_id
+                WHERE s.sphere_name = $1 AND c.user_id = $2
+            )",
+            sphere_name,
+            user_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(has_confirmed_nsfw.unwrap_or(false))
+    }
+
+    pub async fn confirm_nsfw(
+        sphere_name: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        check_sphere_name(sphere_name)?;
+        let sphere = get_sphere_by_name(sphere_name, db_pool).await?;
+        sqlx::query!(
+            "INSERT INTO nsfw_confirmations (user_id, sphere_id)
+            VALUES ($1, $2)
+            ON CONFLICT (sphere_id, user_id) DO NOTHING",
+            user.user_id,
+            sphere.sphere_id,
+        )
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Checks that `user` confirmed they want to see the content of `sphere_name` if it is a NSFW sphere.
+    /// Anonymous users can never browse NSFW spheres.
+    pub async fn check_nsfw_confirmed(
+        sphere_name: &str,
+        user: Option<&User>,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        let is_confirmed = sqlx::query_scalar!(
+            "SELECT
+                NOT s.is_nsfw OR EXISTS (
+                    SELECT 1 FROM nsfw_confirmations c
+                    WHERE c.sphere_id = s.sphere_id AND c.user_id = $2
+                )
+            FROM spheres s
+            WHERE s.sphere_name = $1",
+            sphere_name,
+            user.map(|user| user.user_id),
+        )
+            .fetch_optional(db_pool)
+            .await?;
+
+        match is_confirmed {
+            Some(Some(false)) => Err(AppError::NsfwNotConfirmed),
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use sphare_core_common::errors::AppError;
+
+        use crate::sphere::ssr::check_sphere_post_title;
+
+        #[test]
+        fn test_check_sphere_post_title() {
+            assert_eq!(check_sphere_post_title("title", 0, None, None), Ok(()));
+            assert_eq!(check_sphere_post_title("title", 5, Some(5), None), Ok(()));
+            assert_eq!(check_sphere_post_title("titre éé", 8, Some(8), None), Ok(()));
+            assert_eq!(check_sphere_post_title("title", 6, None, None), Err(AppError::TitleTooShort(6)));
+            assert_eq!(check_sphere_post_title("title", 0, Some(4), None), Err(AppError::TitleTooLong(4)));
+            assert_eq!(check_sphere_post_title("[Tag] title", 0, None, Some(r"^\[\w+\] ")), Ok(()));
+            assert_eq!(
+                check_sphere_post_title("title [Tag]", 0, None, Some(r"^\[\w+\] ")),
+                Err(AppError::TitleFormatMismatch(String::from(r"^\[\w+\] "))),
+            );
+            assert!(check_sphere_post_title("title", 0, None, Some("(")).is_err());
+        }
+    }
+}
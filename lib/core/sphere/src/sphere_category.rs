@@ -12,6 +12,8 @@ pub struct SphereCategory {
     pub category_color: Color,
     pub description: String,
     pub is_active: bool,
+    /// Position of the category when displayed, categories are ordered by increasing `display_order`
+    pub display_order: i32,
     pub creator_id: i64,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub delete_timestamp: Option<chrono::DateTime<chrono::Utc>>,
@@ -37,6 +39,8 @@ impl From<&SphereCategory> for SphereCategoryHeader {
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use std::collections::HashSet;
+
     use sqlx::PgPool;
     use sphare_core_common::checks::{check_sphere_name, check_string_length};
     use sphare_core_common::colors::Color;
@@ -47,10 +51,11 @@ pub mod ssr {
 
     use crate::sphere_category::SphereCategory;
 
-    pub const CATEGORY_NOT_DELETED_STR: &str = "Category was not deleted, it either doesn't exist or is used.";
-
+    /// Returns the categories of the sphere `sphere_name` ordered by `display_order`, inactive categories are only included
+    /// if `include_inactive` is true.
     pub async fn get_sphere_category_vec(
         sphere_name: &str,
+        include_inactive: bool,
         db_pool: &PgPool,
     ) -> Result<Vec<SphereCategory>, AppError> {
         check_sphere_name(sphere_name)?;
@@ -58,9 +63,10 @@ pub mod ssr {
             SphereCategory,
             "SELECT sc.* FROM sphere_categories sc
             JOIN spheres s ON s.sphere_id = sc.sphere_id
-            WHERE s.sphere_name = $1
-            ORDER BY sc.is_active DESC, sc.category_name",
-            sphere_name
+            WHERE s.sphere_name = $1 AND ($2 OR sc.is_active)
+            ORDER BY sc.display_order, sc.category_name",
+            sphere_name,
+            include_inactive,
         )
             .fetch_all(db_pool)
             .await?;
@@ -68,6 +74,21 @@ pub mod ssr {
         Ok(sphere_category_vec)
     }
 
+    pub async fn get_sphere_category_by_id(
+        category_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<SphereCategory, AppError> {
+        let sphere_category = sqlx::query_as!(
+            SphereCategory,
+            "SELECT * FROM sphere_categories WHERE category_id = $1",
+            category_id
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere_category)
+    }
+
     pub async fn set_sphere_category(
         sphere_name: &str,
         category_name: &str,
@@ -80,15 +101,24 @@ pub mod ssr {
         check_sphere_name(sphere_name)?;
         check_string_length(category_name, "Category name", MAX_CATEGORY_NAME_LENGTH, false)?;
         check_string_length(description, "Category description", MAX_CATEGORY_DESCRIPTION_LENGTH, false)?;
+        if !category_color.has_readable_text() {
+            return Err(AppError::new(format!("Category color {category_color} does not have enough contrast with its text.")));
+        }
         user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
 
         let category = sqlx::query_as!(
             SphereCategory,
             "INSERT INTO sphere_categories
-            (sphere_id, category_name, category_color, description, is_active, creator_id)
+            (sphere_id, category_name, category_color, description, is_active, display_order, creator_id)
             VALUES (
                 (SELECT sphere_id FROM spheres WHERE sphere_name = $1),
-                $2, $3, $4, $5, $6
+                $2, $3, $4, $5,
+                (
+                    SELECT COALESCE(MAX(c.display_order) + 1, 0) FROM sphere_categories c
+                    JOIN spheres s ON s.sphere_id = c.sphere_id
+                    WHERE s.sphere_name = $1
+                ),
+                $6
             ) ON CONFLICT (sphere_id, category_name) DO UPDATE
                 SET description = EXCLUDED.description,
                     category_color = EXCLUDED.category_color,
@@ -108,9 +138,92 @@ pub mod ssr {
         Ok(category)
     }
 
+    /// Sets the display order of the categories of the sphere `sphere_name` to the order of `category_name_vec`,
+    /// which must contain the name of each category of the sphere exactly once.
+    pub async fn reorder_categories(
+        sphere_name: &str,
+        category_name_vec: &[String],
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<SphereCategory>, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let mut tx = db_pool.begin().await?;
+        let current_name_set = sqlx::query_scalar!(
+            "SELECT c.category_name FROM sphere_categories c
+            JOIN spheres s ON s.sphere_id = c.sphere_id
+            WHERE s.sphere_name = $1
+            FOR UPDATE OF c",
+            sphere_name,
+        )
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .collect::<HashSet<String>>();
+
+        let category_name_set = category_name_vec.iter().cloned().collect::<HashSet<String>>();
+        if category_name_set.len() != category_name_vec.len() || category_name_set != current_name_set {
+            return Err(AppError::new("The new category order must contain each category of the sphere exactly once."));
+        }
+
+        sqlx::query!(
+            "UPDATE sphere_categories c
+            SET display_order = o.display_order - 1, timestamp = NOW()
+            FROM spheres s, UNNEST($2::TEXT[]) WITH ORDINALITY AS o(category_name, display_order)
+            WHERE
+                s.sphere_id = c.sphere_id AND
+                s.sphere_name = $1 AND
+                c.category_name = o.category_name",
+            sphere_name,
+            category_name_vec,
+        )
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        get_sphere_category_vec(sphere_name, true, db_pool).await
+    }
+
+    /// Activates or deactivates the category `category_name` of the sphere `sphere_name`.
+    /// Inactive categories are kept on existing posts but cannot be selected for new ones.
+    pub async fn set_category_active(
+        sphere_name: &str,
+        category_name: &str,
+        is_active: bool,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<SphereCategory, AppError> {
+        check_sphere_name(sphere_name)?;
+        check_string_length(category_name, "Category name", MAX_CATEGORY_NAME_LENGTH, false)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
+
+        let category = sqlx::query_as!(
+            SphereCategory,
+            "UPDATE sphere_categories c
+            SET is_active = $1, timestamp = NOW()
+            FROM spheres s
+            WHERE
+                s.sphere_id = c.sphere_id AND
+                s.sphere_name = $2 AND
+                c.category_name = $3
+            RETURNING c.*",
+            is_active,
+            sphere_name,
+            category_name,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(category)
+    }
+
+    /// Deletes the category `category_name` of the sphere `sphere_name`. If posts use the category, returns
+    /// `AppError::CategoryInUse` unless `reassign_posts` is true, in which case the category of these posts is removed.
     pub async fn delete_sphere_category(
         sphere_name: &str,
         category_name: &str,
+        reassign_posts: bool,
         user: &User,
         db_pool: &PgPool,
     ) -> Result<(), AppError> {
@@ -118,23 +231,45 @@ pub mod ssr {
         check_string_length(category_name, "Category name", MAX_CATEGORY_NAME_LENGTH, false)?;
         user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Manage)?;
 
-        let result = sqlx::query!(
-            "DELETE FROM sphere_categories c
-             WHERE sphere_id = (
-                    SELECT sphere_id FROM spheres WHERE sphere_name = $1
-                ) AND category_name = $2 AND NOT EXISTS (
-                SELECT 1 FROM posts p WHERE p.category_id = c.category_id
-             )",
+        let mut tx = db_pool.begin().await?;
+        let category_id = sqlx::query_scalar!(
+            "SELECT c.category_id FROM sphere_categories c
+            JOIN spheres s ON s.sphere_id = c.sphere_id
+            WHERE s.sphere_name = $1 AND c.category_name = $2
+            FOR UPDATE OF c",
             sphere_name,
             category_name,
         )
-            .execute(db_pool)
+            .fetch_one(&mut *tx)
             .await?;
 
-        match result.rows_affected() {
-            0 => Err(AppError::InternalServerError(String::from(CATEGORY_NOT_DELETED_STR))),
-            1 => Ok(()),
-            count => Err(AppError::InternalServerError(format!("Expected 1 category to be deleted, got {count} instead"))),
+        let post_count = sqlx::query_scalar!(
+            "SELECT COUNT(*) AS \"count!\" FROM posts WHERE category_id = $1",
+            category_id,
+        )
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if post_count > 0 {
+            if !reassign_posts {
+                return Err(AppError::CategoryInUse);
+            }
+            sqlx::query!(
+                "UPDATE posts SET category_id = NULL WHERE category_id = $1",
+                category_id,
+            )
+                .execute(&mut *tx)
+                .await?;
         }
+
+        sqlx::query!(
+            "DELETE FROM sphere_categories WHERE category_id = $1",
+            category_id,
+        )
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,9 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// User trusted by the moderators of a sphere, trusted users bypass the sphere's publishing gates.
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct TrustedUser {
+    pub trusted_user_id: i64,
+    pub user_id: i64,
+    pub username: String,
+    pub sphere_id: i64,
+    pub sphere_name: String,
+    pub grantor_id: i64,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+    pub delete_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[cfg(feature = "ssr")]
 pub mod ssr {
     use std::io::Cursor;
     use std::path::Path;
     use http::StatusCode;
-    use image::ImageReader;
+    use image::{DynamicImage, ImageReader};
     use leptos::prelude::use_context;
     use leptos::server_fn::codec::MultipartData;
     use leptos_axum::ResponseOptions;
@@ -14,15 +30,21 @@ pub mod ssr {
     use url::Url;
     use webp::Encoder;
 
-    use sphare_core_common::checks::{check_sphere_name, check_username};
+    use sphare_core_common::checks::{check_sphere_name, check_string_length, check_username};
+    use sphare_core_common::colors::Color;
     use sphare_core_common::constants::IMAGE_TYPE;
-    use sphare_core_common::constants::{IMAGE_FILE_PARAM, SPHERE_NAME_PARAM};
+    use sphare_core_common::constants::{IMAGE_FILE_PARAM, MAX_SPHERE_SIDEBAR_LENGTH, SPHERE_NAME_PARAM};
+    use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
     use sphare_core_common::errors::AppError;
+    use sphare_core_user::mod_log::ModAction;
+    use sphare_core_user::mod_log::ssr::insert_mod_log;
     use sphare_core_user::role::{AdminRole, PermissionLevel};
+    use sphare_core_user::user::ssr::SqlUser;
     use sphare_core_user::user::{User, UserBan};
 
     use crate::sphere::ssr::get_sphere_by_name;
     use crate::sphere::Sphere;
+    use crate::sphere_management::TrustedUser;
 
     pub const OBJECT_CONTAINER_URL_ENV: &str = "OBJECT_CONTAINER_URL";
     pub const ICON_BUCKET_ENV: &str = "ICON_BUCKET";
@@ -124,9 +146,207 @@ pub mod ssr {
             .execute(db_pool)
             .await?;
 
+        if let Some(sphere_id) = user_ban.sphere_id {
+            insert_mod_log(
+                sphere_id,
+                ModAction::UnbanUser,
+                grantor.user_id,
+                Some(user_ban.user_id),
+                user_ban.post_id,
+                user_ban.comment_id,
+                db_pool,
+            ).await?;
+        }
+
         Ok(user_ban)
     }
 
+    pub async fn get_sphere_trusted_user_vec(
+        sphere_name: &str,
+        db_pool: &PgPool,
+    ) -> Result<Vec<TrustedUser>, AppError> {
+        check_sphere_name(sphere_name)?;
+        let trusted_user_vec = sqlx::query_as!(
+            TrustedUser,
+            "SELECT t.*, u.username, s.sphere_name FROM trusted_users t
+            JOIN users u ON u.user_id = t.user_id
+            JOIN spheres s ON s.sphere_id = t.sphere_id
+            WHERE s.sphere_name = $1 AND
+                  t.delete_timestamp IS NULL
+            ORDER BY u.username",
+            sphere_name,
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(trusted_user_vec)
+    }
+
+    /// Returns whether the user with id `user_id` is trusted in the sphere with id `sphere_id`.
+    /// Publishing gates of the sphere should not apply to trusted users.
+    pub async fn is_trusted_user(
+        user_id: i64,
+        sphere_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<bool, AppError> {
+        let is_trusted = sqlx::query_scalar!(
+            "SELECT EXISTS (
+                SELECT 1 FROM trusted_users
+                WHERE user_id = $1 AND
+                      sphere_id = $2 AND
+                      delete_timestamp IS NULL
+            )",
+            user_id,
+            sphere_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(is_trusted.unwrap_or(false))
+    }
+
+    pub async fn add_trusted_user(
+        username: &str,
+        sphere_name: &str,
+        grantor: &User,
+        db_pool: &PgPool,
+    ) -> Result<TrustedUser, AppError> {
+        check_username(username, false)?;
+        check_sphere_name(sphere_name)?;
+        grantor.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Ban)?;
+        let trusted_user = SqlUser::get_by_username(username, db_pool).await?;
+
+        let trusted_user = sqlx::query_as!(
+            TrustedUser,
+            "WITH new_trusted_user AS (
+                INSERT INTO trusted_users (user_id, sphere_id, grantor_id)
+                VALUES (
+                    $1,
+                    (SELECT sphere_id FROM spheres WHERE sphere_name = $2),
+                    $3
+                )
+                ON CONFLICT (sphere_id, user_id) WHERE delete_timestamp IS NULL DO UPDATE
+                    SET grantor_id = EXCLUDED.grantor_id
+                RETURNING *
+            )
+            SELECT t.*, u.username, s.sphere_name FROM new_trusted_user t
+            JOIN users u ON u.user_id = t.user_id
+            JOIN spheres s ON s.sphere_id = t.sphere_id",
+            trusted_user.user_id,
+            sphere_name,
+            grantor.user_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(trusted_user)
+    }
+
+    pub async fn remove_trusted_user(
+        trusted_user_id: i64,
+        grantor: &User,
+        db_pool: &PgPool,
+    ) -> Result<TrustedUser, AppError> {
+        let trusted_user = sqlx::query_as!(
+            TrustedUser,
+            "SELECT t.*, u.username, s.sphere_name FROM trusted_users t
+            JOIN users u ON u.user_id = t.user_id
+            JOIN spheres s ON s.sphere_id = t.sphere_id
+            WHERE trusted_user_id = $1 AND
+                  t.delete_timestamp IS NULL",
+            trusted_user_id
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        grantor.check_sphere_permissions_by_name(&trusted_user.sphere_name, PermissionLevel::Ban)?;
+
+        sqlx::query!(
+            "UPDATE trusted_users SET delete_timestamp = NOW() WHERE trusted_user_id = $1",
+            trusted_user_id
+        )
+            .execute(db_pool)
+            .await?;
+
+        Ok(trusted_user)
+    }
+
+    /// Archives or restores the sphere with the given name. Archived spheres are read-only: new posts and comments are rejected.
+    pub async fn set_sphere_archived(
+        sphere_name: &str,
+        is_archived: bool,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_is_sphere_leader(sphere_name)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET is_archived = $1, timestamp = NOW() WHERE sphere_name = $2 RETURNING *"
+        )
+            .bind(is_archived)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
+    /// Sets the accent color of the sphere with the given name. `Color::None` restores the default theme.
+    pub async fn set_sphere_color(
+        sphere_name: &str,
+        color: Color,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_is_sphere_leader(sphere_name)?;
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres SET accent_color = $1, timestamp = NOW() WHERE sphere_name = $2 RETURNING *"
+        )
+            .bind(color as i16)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
+    /// Sets the markdown content displayed in the sidebar of the sphere with the given name. An empty `markdown` removes the sidebar content.
+    pub async fn set_sphere_sidebar(
+        sphere_name: &str,
+        markdown: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Sphere, AppError> {
+        check_sphere_name(sphere_name)?;
+        check_string_length(markdown, "Sphere sidebar", MAX_SPHERE_SIDEBAR_LENGTH, true)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+
+        let (sidebar_html, sidebar_markdown) = match markdown.is_empty() {
+            true => (None, None),
+            false => {
+                let (html, markdown) = get_html_and_markdown_strings(markdown, true)?;
+                (Some(html), markdown)
+            }
+        };
+
+        let sphere = sqlx::query_as::<_, Sphere>(
+            "UPDATE spheres
+            SET sidebar_markdown = $1, sidebar_html = $2, timestamp = NOW()
+            WHERE sphere_name = $3
+            RETURNING *"
+        )
+            .bind(sidebar_markdown)
+            .bind(sidebar_html)
+            .bind(sphere_name)
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere)
+    }
+
     pub async fn set_sphere_image<T: ObjectStoreExt>(
         image_type: SphereImageType,
         data: MultipartData,
@@ -242,30 +462,52 @@ pub mod ssr {
             input_file_buffer.append(chunk.to_vec().as_mut());
         }
 
-        match infer::get(&input_file_buffer) {
+        let img = decode_image(input_file_buffer, INCORRECT_BANNER_FILE_TYPE_STR, BANNER_FILE_INFER_ERROR_STR)?;
+        let file_name = format!("{}_{}.webp", sphere_name, image_identifier);
+        store_webp_image(&img, &file_name, object_store).await?;
+
+        Ok((sphere_name, Some(file_name)))
+    }
+
+    /// Checks that `image_buffer` contains an image file and decodes it.
+    ///
+    /// Returns an error with `incorrect_file_type_str` if the file is not an image
+    /// and with `file_infer_error_str` if its type cannot be inferred.
+    pub fn decode_image(
+        image_buffer: Vec<u8>,
+        incorrect_file_type_str: &str,
+        file_infer_error_str: &str,
+    ) -> Result<DynamicImage, AppError> {
+        match infer::get(&image_buffer) {
             Some(file_type) if file_type.mime_type().starts_with(IMAGE_TYPE) => Ok(()),
             Some(file_type) => {
                 log::info!("Invalid file type: {}, extension: {}", file_type.mime_type(), file_type.extension());
-                Err(AppError::new(INCORRECT_BANNER_FILE_TYPE_STR))
+                Err(AppError::new(incorrect_file_type_str))
             },
-            None => Err(AppError::new(BANNER_FILE_INFER_ERROR_STR)),
+            None => Err(AppError::new(file_infer_error_str)),
         }?;
 
-        let img = ImageReader::new(Cursor::new(input_file_buffer))
+        ImageReader::new(Cursor::new(image_buffer))
             .with_guessed_format()?
-            .decode().map_err(|e| AppError::new(format!("Error while decoding image: {e}")))?;
+            .decode().map_err(|e| AppError::new(format!("Error while decoding image: {e}")))
+    }
+
+    /// Encodes `img` in the webp format and stores it in `object_store` under `file_name`.
+    pub async fn store_webp_image<T: ObjectStoreExt>(
+        img: &DynamicImage,
+        file_name: &str,
+        object_store: &T,
+    ) -> Result<(), AppError> {
         let rgb = img.to_rgb8();
         let encoder = Encoder::from_rgb(&rgb, img.width(), img.height());
         let webp_data = encoder.encode(75.0).to_vec(); // 75% quality
 
-        let file_name = format!("{}_{}.webp", sphere_name, image_identifier);
-
         object_store.put(
-            &object_store::path::Path::from(file_name.clone()),
+            &object_store::path::Path::from(file_name),
             PutPayload::from_bytes(webp_data.into())
         ).await.map_err(|e| AppError::new(format!("Error while uploading to object store: {e}")))?;
 
-        Ok((sphere_name, Some(file_name)))
+        Ok(())
     }
 
     pub async fn set_sphere_icon_url(
@@ -313,8 +555,10 @@ pub mod ssr {
     #[cfg(test)]
     mod tests {
         use sealed_test::prelude::*;
+        use sphare_core_common::colors::Color;
+        use sphare_core_user::user::KarmaType;
 
-        use crate::sphere::Sphere;
+        use crate::sphere::{AllowedPostTypes, Sphere};
         use crate::sphere_management::ssr::{get_file_name_from_url, SphereImageType, BANNER_BUCKET_ENV, ICON_BUCKET_ENV};
 
         #[sealed_test]
@@ -347,6 +591,21 @@ pub mod ssr {
                 creator_id: 0,
                 create_timestamp: Default::default(),
                 timestamp: Default::default(),
+                require_rule_acceptance: false,
+                require_post_approval: false,
+                reject_duplicate_links: false,
+                require_category: false,
+                min_account_age_days: 0,
+                min_karma: 0,
+                min_karma_type: KarmaType::Total,
+                allowed_post_types: AllowedPostTypes::Any,
+                min_title_length: 0,
+                max_title_length: None,
+                title_regex: None,
+                is_archived: false,
+                sidebar_markdown: None,
+                sidebar_html: None,
+                accent_color: Color::None,
             };
 
             let sphere2 = Sphere {
@@ -362,6 +621,21 @@ pub mod ssr {
                 creator_id: 0,
                 create_timestamp: Default::default(),
                 timestamp: Default::default(),
+                require_rule_acceptance: false,
+                require_post_approval: false,
+                reject_duplicate_links: false,
+                require_category: false,
+                min_account_age_days: 0,
+                min_karma: 0,
+                min_karma_type: KarmaType::Total,
+                allowed_post_types: AllowedPostTypes::Any,
+                min_title_length: 0,
+                max_title_length: None,
+                title_regex: None,
+                is_archived: false,
+                sidebar_markdown: None,
+                sidebar_html: None,
+                accent_color: Color::None,
             };
 
             assert_eq!(*icon.get_sphere_image_url(&sphere), Some(String::from("icon.png")));
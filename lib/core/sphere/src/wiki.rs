@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct SpherePage {
+    pub page_id: i64,
+    pub sphere_id: i64,
+    pub slug: String,
+    pub title: String,
+    pub markdown_body: String,
+    pub html_body: String,
+    pub is_markdown: bool,
+    pub creator_id: i64,
+    pub editor_id: i64,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+    pub edit_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use sqlx::PgPool;
+    use sphare_core_common::checks::{check_sphere_name, check_string_length, check_wiki_slug};
+    use sphare_core_common::constants::{MAX_CONTENT_LENGTH, MAX_TITLE_LENGTH};
+    use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
+    use sphare_core_common::errors::AppError;
+    use sphare_core_user::role::PermissionLevel;
+    use sphare_core_user::user::User;
+
+    use crate::wiki::SpherePage;
+
+    pub async fn get_sphere_page(
+        sphere_name: &str,
+        slug: &str,
+        db_pool: &PgPool,
+    ) -> Result<SpherePage, AppError> {
+        check_sphere_name(sphere_name)?;
+        check_wiki_slug(slug)?;
+        let sphere_page = sqlx::query_as!(
+            SpherePage,
+            "SELECT p.* FROM sphere_pages p
+            JOIN spheres s ON s.sphere_id = p.sphere_id
+            WHERE s.sphere_name = $1 AND p.slug = $2",
+            sphere_name,
+            slug,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere_page)
+    }
+
+    pub async fn get_sphere_page_vec(
+        sphere_name: &str,
+        db_pool: &PgPool,
+    ) -> Result<Vec<SpherePage>, AppError> {
+        check_sphere_name(sphere_name)?;
+        let sphere_page_vec = sqlx::query_as!(
+            SpherePage,
+            "SELECT p.* FROM sphere_pages p
+            JOIN spheres s ON s.sphere_id = p.sphere_id
+            WHERE s.sphere_name = $1
+            ORDER BY p.title, p.slug",
+            sphere_name,
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(sphere_page_vec)
+    }
+
+    pub async fn create_sphere_page(
+        sphere_name: &str,
+        slug: &str,
+        title: &str,
+        markdown_body: &str,
+        is_markdown: bool,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<SpherePage, AppError> {
+        check_sphere_name(sphere_name)?;
+        check_wiki_slug(slug)?;
+        check_string_length(title, "Title", MAX_TITLE_LENGTH as usize, false)?;
+        check_string_length(markdown_body, "Body", MAX_CONTENT_LENGTH as usize, false)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+        let (html_body, _) = get_html_and_markdown_strings(markdown_body, is_markdown)?;
+
+        let sphere_page = sqlx::query_as!(
+            SpherePage,
+            "INSERT INTO sphere_pages
+            (sphere_id, slug, title, markdown_body, html_body, is_markdown, creator_id, editor_id)
+            VALUES (
+                (SELECT sphere_id FROM spheres WHERE sphere_name = $1),
+                $2, $3, $4, $5, $6, $7, $7
+            ) RETURNING *",
+            sphere_name,
+            slug,
+            title,
+            markdown_body,
+            html_body,
+            is_markdown,
+            user.user_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere_page)
+    }
+
+    pub async fn update_sphere_page(
+        sphere_name: &str,
+        slug: &str,
+        title: &str,
+        markdown_body: &str,
+        is_markdown: bool,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<SpherePage, AppError> {
+        check_sphere_name(sphere_name)?;
+        check_wiki_slug(slug)?;
+        check_string_length(title, "Title", MAX_TITLE_LENGTH as usize, false)?;
+        check_string_length(markdown_body, "Body", MAX_CONTENT_LENGTH as usize, false)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+        let (html_body, _) = get_html_and_markdown_strings(markdown_body, is_markdown)?;
+
+        let sphere_page = sqlx::query_as!(
+            SpherePage,
+            "UPDATE sphere_pages p
+            SET
+                title = $3,
+                markdown_body = $4,
+                html_body = $5,
+                is_markdown = $6,
+                editor_id = $7,
+                edit_timestamp = NOW()
+            FROM spheres s
+            WHERE
+                s.sphere_id = p.sphere_id AND
+                s.sphere_name = $1 AND
+                p.slug = $2
+            RETURNING p.*",
+            sphere_name,
+            slug,
+            title,
+            markdown_body,
+            html_body,
+            is_markdown,
+            user.user_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(sphere_page)
+    }
+
+    pub async fn delete_sphere_page(
+        sphere_name: &str,
+        slug: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        check_sphere_name(sphere_name)?;
+        check_wiki_slug(slug)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+
+        sqlx::query!(
+            "DELETE FROM sphere_pages p
+            USING spheres s
+            WHERE
+                s.sphere_id = p.sphere_id AND
+                s.sphere_name = $1 AND
+                p.slug = $2",
+            sphere_name,
+            slug,
+        )
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+}
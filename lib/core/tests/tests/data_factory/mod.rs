@@ -339,6 +339,7 @@ pub async fn create_post_with_comment_tree(
     let mut comment_map: HashMap<i64, CommentWithChildren> = comment_vec.iter().enumerate().map(|(i, comment)| (comment.comment_id, CommentWithChildren {
         comment: comment.clone(),
         vote: vote_vec.get(i).cloned().unwrap_or(None),
+        reactions: Vec::new(),
         child_comments: Vec::new()
     })).collect();
 
@@ -435,6 +436,26 @@ pub async fn set_sphere_num_members(
     Ok(sphere)
 }
 
+pub async fn set_subscription_timestamp(
+    sphere_id: i64,
+    user_id: i64,
+    day_offset: i64,
+    db_pool: &PgPool,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE sphere_subscriptions
+        SET timestamp = timestamp + (INTERVAL '1 day' * $1)
+        WHERE sphere_id = $2 AND user_id = $3",
+    )
+        .bind(day_offset)
+        .bind(sphere_id)
+        .bind(user_id)
+        .execute(db_pool)
+        .await?;
+
+    Ok(())
+}
+
 pub async fn set_post_score(
     post_id: i64,
     score: i32,
@@ -458,6 +479,45 @@ pub async fn set_post_score(
     Ok(post)
 }
 
+pub async fn set_post_publish_at(
+    post_id: i64,
+    publish_at: chrono::DateTime<chrono::Utc>,
+    db_pool: &PgPool,
+) -> Result<(), AppError> {
+    sqlx::query("UPDATE posts SET publish_at = $1 WHERE post_id = $2")
+        .bind(publish_at)
+        .bind(post_id)
+        .execute(db_pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn set_post_vote_counts(
+    post_id: i64,
+    num_upvotes: i32,
+    num_downvotes: i32,
+    db_pool: &PgPool,
+) -> Result<Post, AppError> {
+    let post = sqlx::query_as::<_, Post>(
+        "WITH updated_post AS (
+            UPDATE posts SET score = $1, score_minus = $2, scoring_timestamp = NOW()
+            WHERE post_id = $3
+            RETURNING *
+        )
+        SELECT p.*, u.username as creator_name, NULL as moderator_name
+        FROM updated_post p
+        JOIN users u ON u.user_id = p.creator_id",
+    )
+        .bind(num_upvotes - num_downvotes)
+        .bind(num_downvotes)
+        .bind(post_id)
+        .fetch_one(db_pool)
+        .await?;
+
+    Ok(post)
+}
+
 pub async fn set_post_timestamp(
     post_id: i64,
     day_offset: i64,
@@ -507,6 +567,45 @@ pub async fn set_comment_score(
     Ok(comment)
 }
 
+pub async fn set_comment_vote_counts(
+    comment_id: i64,
+    num_upvotes: i32,
+    num_downvotes: i32,
+    db_pool: &PgPool,
+) -> Result<Comment, AppError> {
+    let comment = sqlx::query_as::<_, Comment>(
+        "WITH updated_comment AS (
+            UPDATE comments SET score = $1, score_minus = $2
+            WHERE comment_id = $3
+            RETURNING *
+        )
+        SELECT c.*, u.username as creator_name, NULL as moderator_name
+        FROM updated_comment c
+        JOIN users u ON u.user_id = c.creator_id",
+    )
+        .bind(num_upvotes - num_downvotes)
+        .bind(num_downvotes)
+        .bind(comment_id)
+        .fetch_one(db_pool)
+        .await?;
+
+    Ok(comment)
+}
+
+pub async fn set_comment_timestamp(
+    comment_id: i64,
+    day_offset: i64,
+    db_pool: &PgPool,
+) -> Result<(), AppError> {
+    sqlx::query("UPDATE comments SET create_timestamp = create_timestamp + (INTERVAL '1 day' * $1) WHERE comment_id = $2")
+        .bind(day_offset)
+        .bind(comment_id)
+        .execute(db_pool)
+        .await?;
+
+    Ok(())
+}
+
 pub async fn add_base_rule(
     priority: i16,
     title: &str,
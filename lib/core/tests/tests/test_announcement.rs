@@ -0,0 +1,128 @@
+use sphare_core_common::editor::get_styled_html_from_markdown;
+use sphare_core_common::errors::AppError;
+use sphare_core_user::announcement::ssr::{create_announcement, dismiss_announcement, get_active_announcements, get_unseen_announcements};
+use sphare_core_user::role::AdminRole;
+
+use crate::common::*;
+
+mod common;
+
+#[tokio::test]
+async fn test_create_announcement() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut admin = create_user("global_admin", &db_pool).await;
+    admin.admin_role = AdminRole::Admin;
+    let mut moderator = create_user("moderator", &db_pool).await;
+    moderator.admin_role = AdminRole::Moderator;
+    let user = create_test_user(&db_pool).await;
+
+    assert_eq!(
+        create_announcement("a", "b", false, None, None, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        create_announcement("a", "b", false, None, None, &moderator, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(create_announcement("", "b", false, None, None, &admin, &db_pool).await.is_err());
+    assert!(get_unseen_announcements(&user, &db_pool).await?.is_empty());
+
+    let announcement = create_announcement("a", "b", false, None, None, &admin, &db_pool).await?;
+    assert_eq!(announcement.title, "a");
+    assert_eq!(announcement.body, "b");
+    assert_eq!(announcement.markdown_body, None);
+    assert_eq!(announcement.creator_id, admin.user_id);
+    assert_eq!(announcement.end_timestamp, None);
+    assert_eq!(announcement.delete_timestamp, None);
+
+    let markdown_body = "# Title\n*new* feature";
+    let markdown_announcement = create_announcement("c", markdown_body, true, None, None, &admin, &db_pool).await?;
+    assert_eq!(markdown_announcement.body, get_styled_html_from_markdown(markdown_body)?);
+    assert_eq!(markdown_announcement.markdown_body.as_deref(), Some(markdown_body));
+
+    // Announcements must end in the future and after they start
+    let now = chrono::Utc::now();
+    assert!(create_announcement("a", "b", false, None, Some(now - chrono::Duration::hours(1)), &admin, &db_pool).await.is_err());
+    assert!(create_announcement("a", "b", false, Some(now + chrono::Duration::hours(2)), Some(now + chrono::Duration::hours(1)), &admin, &db_pool).await.is_err());
+    let scheduled_announcement = create_announcement(
+        "a", "b", false, Some(now + chrono::Duration::hours(1)), Some(now + chrono::Duration::hours(2)), &admin, &db_pool
+    ).await?;
+    assert!(scheduled_announcement.start_timestamp > now);
+    assert!(scheduled_announcement.end_timestamp.is_some_and(|end_timestamp| end_timestamp > scheduled_announcement.start_timestamp));
+    assert!(get_active_announcements(&db_pool).await?.iter().all(|announcement| announcement.announcement_id != scheduled_announcement.announcement_id));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_active_announcements() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut admin = create_user("global_admin", &db_pool).await;
+    admin.admin_role = AdminRole::Admin;
+    let user = create_test_user(&db_pool).await;
+    let now = chrono::Utc::now();
+
+    let permanent_announcement = create_announcement("a", "b", false, None, None, &admin, &db_pool).await?;
+    let temporary_announcement = create_announcement("c", "d", false, None, Some(now + chrono::Duration::hours(1)), &admin, &db_pool).await?;
+    let future_announcement = create_announcement("e", "f", false, Some(now + chrono::Duration::hours(1)), None, &admin, &db_pool).await?;
+    let expired_announcement = create_announcement("g", "h", false, None, Some(now + chrono::Duration::hours(1)), &admin, &db_pool).await?;
+    sqlx::query(
+        "UPDATE announcements
+        SET start_timestamp = NOW() - INTERVAL '2 hours', end_timestamp = NOW() - INTERVAL '1 hour'
+        WHERE announcement_id = $1"
+    )
+        .bind(expired_announcement.announcement_id)
+        .execute(&db_pool)
+        .await?;
+
+    // Future and expired announcements are excluded
+    let expected_announcement_vec = vec![temporary_announcement.clone(), permanent_announcement.clone()];
+    assert_eq!(get_active_announcements(&db_pool).await?, expected_announcement_vec);
+    assert_eq!(get_unseen_announcements(&user, &db_pool).await?, expected_announcement_vec);
+
+    // Announcements become active once their start timestamp is reached
+    sqlx::query("UPDATE announcements SET start_timestamp = NOW() WHERE announcement_id = $1")
+        .bind(future_announcement.announcement_id)
+        .execute(&db_pool)
+        .await?;
+    let active_announcement_vec = get_active_announcements(&db_pool).await?;
+    assert_eq!(active_announcement_vec.len(), 3);
+    assert_eq!(active_announcement_vec[0].announcement_id, future_announcement.announcement_id);
+
+    // Dismissed announcements remain active for other users
+    dismiss_announcement(permanent_announcement.announcement_id, &user, &db_pool).await?;
+    assert_eq!(get_active_announcements(&db_pool).await?.len(), 3);
+    assert_eq!(get_unseen_announcements(&user, &db_pool).await?.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_unseen_announcements() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut admin = create_user("global_admin", &db_pool).await;
+    admin.admin_role = AdminRole::Admin;
+    let user_1 = create_test_user(&db_pool).await;
+    let user_2 = create_user("2", &db_pool).await;
+
+    let announcement_1 = create_announcement("a", "b", false, None, None, &admin, &db_pool).await?;
+    let announcement_2 = create_announcement("c", "d", false, None, None, &admin, &db_pool).await?;
+
+    // New announcements are shown until they are dismissed, newest first
+    assert_eq!(get_unseen_announcements(&user_1, &db_pool).await?, vec![announcement_2.clone(), announcement_1.clone()]);
+    assert_eq!(get_unseen_announcements(&user_1, &db_pool).await?, vec![announcement_2.clone(), announcement_1.clone()]);
+
+    dismiss_announcement(announcement_2.announcement_id, &user_1, &db_pool).await?;
+    assert_eq!(get_unseen_announcements(&user_1, &db_pool).await?, vec![announcement_1.clone()]);
+    assert_eq!(get_unseen_announcements(&user_2, &db_pool).await?, vec![announcement_2.clone(), announcement_1.clone()]);
+
+    // Dismissing twice has no effect
+    dismiss_announcement(announcement_2.announcement_id, &user_1, &db_pool).await?;
+    dismiss_announcement(announcement_1.announcement_id, &user_1, &db_pool).await?;
+    assert!(get_unseen_announcements(&user_1, &db_pool).await?.is_empty());
+    assert_eq!(get_unseen_announcements(&user_2, &db_pool).await?, vec![announcement_2, announcement_1]);
+
+    assert_eq!(dismiss_announcement(-1, &user_1, &db_pool).await, Err(AppError::NotFound));
+
+    Ok(())
+}
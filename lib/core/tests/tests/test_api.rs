@@ -0,0 +1,104 @@
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use tower::ServiceExt;
+
+use sphare_core_common::errors::AppError;
+use sphare_core_common::routes::{get_post_api_path, POST_API_ROUTE};
+use sphare_core_content::api::ssr::post_json_handler;
+use sphare_core_content::embed::Link;
+use sphare_core_content::post::ssr::{create_post, create_scheduled_post, get_post_with_info_by_id, NewPost};
+use sphare_core_content::post::{PostTags, PostWithInfo};
+use sphare_core_content::ranking::ssr::vote_on_content;
+use sphare_core_content::ranking::VoteValue;
+use sphare_core_sphere::sphere::ssr::set_sphere_require_post_approval;
+
+use crate::common::*;
+use crate::data_factory::*;
+
+mod common;
+mod data_factory;
+
+#[tokio::test]
+async fn test_post_json_api() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    vote_on_content(VoteValue::Up, post.post_id, None, None, &user, &db_pool).await?;
+
+    let app = Router::new()
+        .route(POST_API_ROUTE, get(post_json_handler))
+        .with_state(db_pool.clone());
+
+    let response = app.clone().oneshot(
+        Request::get(get_post_api_path(post.post_id)).body(Body::empty()).expect("Should build request")
+    ).await.expect("Should get response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get(header::CONTENT_TYPE).map(|value| value.as_bytes()), Some("application/json".as_bytes()));
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.expect("Should read response body");
+    let json_value: serde_json::Value = serde_json::from_slice(&body).expect("Response should be valid json");
+    let json_object = json_value.as_object().expect("Response should be a json object");
+    assert_eq!(json_object.len(), 5);
+    assert_eq!(json_object["post"]["post_id"], post.post_id);
+    assert_eq!(json_object["post"]["title"], post.title.as_str());
+    assert!(json_object["sphere_category"].is_null());
+    assert!(json_object["vote"].is_null());
+    assert!(json_object["crosspost_header"].is_null());
+    assert_eq!(json_object["reactions"], serde_json::json!([]));
+
+    let post_with_info: PostWithInfo = serde_json::from_value(json_value).expect("Response should deserialize into PostWithInfo");
+    assert_eq!(post_with_info, get_post_with_info_by_id(post.post_id, None, &db_pool).await?);
+    assert_eq!(post_with_info.vote, None);
+
+    let response = app.oneshot(
+        Request::get(get_post_api_path(post.post_id + 1)).body(Body::empty()).expect("Should build request")
+    ).await.expect("Should get response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_post_json_api_hides_unpublished_posts() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    set_sphere_require_post_approval(&sphere.sphere_name, true, &lead, &db_pool).await?;
+
+    let pending_post = create_post(
+        &sphere.sphere_name, None, "pending", "body", None, Link::default(), PostTags::default(), &user, &db_pool,
+    ).await?;
+    assert!(!pending_post.is_approved);
+    let scheduled_post = create_scheduled_post(
+        NewPost { sphere_name: &sphere.sphere_name, title: "scheduled", body: "body", ..Default::default() },
+        chrono::Utc::now() + chrono::Duration::hours(1),
+        &lead,
+        &db_pool,
+    ).await?;
+
+    let app = Router::new()
+        .route(POST_API_ROUTE, get(post_json_handler))
+        .with_state(db_pool.clone());
+
+    for post_id in [pending_post.post_id, scheduled_post.post_id] {
+        let response = app.clone().oneshot(
+            Request::get(get_post_api_path(post_id)).body(Body::empty()).expect("Should build request")
+        ).await.expect("Should get response");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    // the author and moderators can still access unpublished posts
+    let other_user = create_user("other", &db_pool).await;
+    assert_eq!(get_post_with_info_by_id(pending_post.post_id, Some(&other_user), &db_pool).await, Err(AppError::NotFound));
+    assert_eq!(get_post_with_info_by_id(pending_post.post_id, Some(&user), &db_pool).await?.post.post_id, pending_post.post_id);
+    assert_eq!(get_post_with_info_by_id(pending_post.post_id, Some(&lead), &db_pool).await?.post.post_id, pending_post.post_id);
+    assert_eq!(get_post_with_info_by_id(scheduled_post.post_id, Some(&user), &db_pool).await, Err(AppError::NotFound));
+    assert_eq!(get_post_with_info_by_id(scheduled_post.post_id, Some(&lead), &db_pool).await?.post.post_id, scheduled_post.post_id);
+
+    Ok(())
+}
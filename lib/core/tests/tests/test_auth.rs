@@ -39,7 +39,7 @@ async fn test_user_get() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
     let mut creator_user = create_user("creator", &db_pool).await;
     let test_user = create_user("user", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
     admin.admin_role = AdminRole::Admin;
 
     // Create utils rule to enable bans
@@ -72,7 +72,7 @@ async fn test_user_get() -> Result<(), AppError> {
     assert_eq!(result_user.username, test_user.username);
     assert_eq!(result_user.email, test_user.email);
     assert_eq!(result_user.admin_role, test_user.admin_role);
-    assert_eq!(result_user.show_nsfw, test_user.show_nsfw);
+    assert_eq!(result_user.nsfw_mode, test_user.nsfw_mode);
     assert_eq!(result_user.days_hide_spoiler, test_user.days_hide_spoiler);
     
     assert_eq!(result_user.check_sphere_permissions_by_name(&sphere_a.sphere_name, PermissionLevel::Moderate), Ok(()));
@@ -99,7 +99,7 @@ async fn test_user_check_can_set_user_sphere_role() -> Result<(), AppError> {
     let manage_mod = create_user("elect", &db_pool).await;
     let simple_mod = create_user("mod", &db_pool).await;
     let mut global_moderator = create_user("gmod", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
     let std_user  = create_user("std", &db_pool).await;
     let test_user = create_user("test", &db_pool).await;
 
@@ -0,0 +1,158 @@
+use sphare_core_common::errors::AppError;
+use sphare_core_content::automod::AutomodAction;
+use sphare_core_content::automod::ssr::{create_automod_rule, delete_automod_rule, get_automod_rule_vec};
+use sphare_core_content::comment::ssr::{create_comment, get_comment_by_id, update_comment};
+use sphare_core_content::embed::Link;
+use sphare_core_content::moderation::ssr::get_reports_for_sphere;
+use sphare_core_content::post::ssr::{get_post_by_id, update_post};
+use sphare_core_content::post::PostTags;
+use sphare_core_sphere::rule::ssr::add_rule;
+use sphare_core_user::mod_log::ModAction;
+use sphare_core_user::mod_log::ssr::get_mod_log;
+
+use crate::common::{create_user, get_db_pool};
+use crate::data_factory::{create_simple_post, create_sphere_with_post};
+
+mod common;
+mod data_factory;
+
+#[tokio::test]
+async fn test_automod_rule_crud() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let mut other_lead = create_user("other", &db_pool).await;
+    let base_user = create_user("user", &db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let (other_sphere, _) = create_sphere_with_post("other", &mut other_lead, &db_pool).await;
+
+    assert_eq!(
+        create_automod_rule(&sphere.sphere_name, "spam", AutomodAction::Flag, None, &base_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(create_automod_rule(&sphere.sphere_name, "  ", AutomodAction::Flag, None, &lead, &db_pool).await.is_err());
+    assert!(create_automod_rule(&sphere.sphere_name, &"a".repeat(101), AutomodAction::Flag, None, &lead, &db_pool).await.is_err());
+
+    let spam_rule = create_automod_rule(&sphere.sphere_name, " spam ", AutomodAction::Flag, None, &lead, &db_pool).await?;
+    assert_eq!(spam_rule.sphere_id, sphere.sphere_id);
+    assert_eq!(spam_rule.pattern, "spam");
+    assert_eq!(spam_rule.action, AutomodAction::Flag);
+    assert_eq!(spam_rule.creator_id, lead.user_id);
+    let rule = add_rule(&sphere.sphere_name, 0, "rule", "description", false, &lead, &db_pool).await?;
+    let other_sphere_rule = add_rule(&other_sphere.sphere_name, 0, "rule", "description", false, &other_lead, &db_pool).await?;
+    // moderating automod rules must reference an infringed rule of their sphere
+    assert!(matches!(
+        create_automod_rule(&sphere.sphere_name, "scam", AutomodAction::Moderate, None, &lead, &db_pool).await,
+        Err(AppError::ValidationError(_)),
+    ));
+    assert!(matches!(
+        create_automod_rule(&sphere.sphere_name, "scam", AutomodAction::Moderate, Some(other_sphere_rule.rule_id), &lead, &db_pool).await,
+        Err(AppError::ValidationError(_)),
+    ));
+    let scam_rule = create_automod_rule(&sphere.sphere_name, "scam", AutomodAction::Moderate, Some(rule.rule_id), &lead, &db_pool).await?;
+    assert_eq!(scam_rule.rule_id, Some(rule.rule_id));
+    let other_rule = create_automod_rule(&other_sphere.sphere_name, "spam", AutomodAction::Moderate, Some(other_sphere_rule.rule_id), &other_lead, &db_pool).await?;
+    assert!(create_automod_rule(&sphere.sphere_name, "spam", AutomodAction::Moderate, Some(rule.rule_id), &lead, &db_pool).await.is_err());
+
+    assert_eq!(get_automod_rule_vec(&sphere.sphere_name, &base_user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(get_automod_rule_vec(&sphere.sphere_name, &lead, &db_pool).await?, vec![scam_rule.clone(), spam_rule.clone()]);
+    assert_eq!(get_automod_rule_vec(&other_sphere.sphere_name, &other_lead, &db_pool).await?, vec![other_rule.clone()]);
+
+    assert_eq!(delete_automod_rule(spam_rule.automod_rule_id, &base_user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(delete_automod_rule(other_rule.automod_rule_id, &lead, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    delete_automod_rule(spam_rule.automod_rule_id, &lead, &db_pool).await?;
+    assert_eq!(get_automod_rule_vec(&sphere.sphere_name, &lead, &db_pool).await?, vec![scam_rule]);
+    assert_eq!(delete_automod_rule(spam_rule.automod_rule_id, &lead, &db_pool).await, Err(AppError::NotFound));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_automod_moderate_action() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let base_user = create_user("user", &db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "rule", "description", false, &lead, &db_pool).await?;
+    create_automod_rule(&sphere.sphere_name, "Forbidden", AutomodAction::Moderate, Some(rule.rule_id), &lead, &db_pool).await?;
+
+    let clean_post = create_simple_post(&sphere.sphere_name, None, "clean", "nothing to see", None, &base_user, &db_pool).await.post;
+    assert_eq!(clean_post.moderator_id, None);
+    assert_eq!(clean_post.moderator_message, None);
+    assert!(clean_post.is_active());
+
+    let title_post = create_simple_post(&sphere.sphere_name, None, "A FORBIDDEN title", "body", None, &base_user, &db_pool).await.post;
+    assert_eq!(title_post.moderator_id, Some(lead.user_id));
+    assert_eq!(title_post.moderator_name, Some(lead.username.clone()));
+    assert!(title_post.moderator_message.as_ref().is_some_and(|message| message.contains("Forbidden")));
+    assert_eq!(title_post.infringed_rule_id, Some(rule.rule_id));
+    assert!(!title_post.is_active());
+    assert_eq!(get_post_by_id(title_post.post_id, &db_pool).await?, title_post);
+
+    let body_post = create_simple_post(&sphere.sphere_name, None, "title", "a forbidden body", None, &base_user, &db_pool).await.post;
+    assert_eq!(body_post.moderator_id, Some(lead.user_id));
+
+    // moderators are not affected by automod rules
+    let lead_post = create_simple_post(&sphere.sphere_name, None, "forbidden", "body", None, &lead, &db_pool).await.post;
+    assert_eq!(lead_post.moderator_id, None);
+
+    let clean_comment = create_comment(clean_post.post_id, None, "clean comment", None, false, &base_user, &db_pool).await?;
+    assert_eq!(clean_comment.moderator_id, None);
+
+    let comment = create_comment(clean_post.post_id, None, "it is forBIDden", None, false, &base_user, &db_pool).await?;
+    assert_eq!(comment.moderator_id, Some(lead.user_id));
+    assert_eq!(comment.moderator_name, Some(lead.username.clone()));
+    assert!(comment.moderator_message.is_some());
+    assert_eq!(comment.infringed_rule_id, Some(rule.rule_id));
+
+    assert!(get_reports_for_sphere(&sphere.sphere_name, &lead, &db_pool).await?.is_empty());
+
+    // automated removals are recorded in the moderation log
+    let mod_log = get_mod_log(&sphere.sphere_name, 10, 0, &lead, &db_pool).await?;
+    assert_eq!(mod_log.len(), 3);
+    assert!(mod_log.iter().all(|entry| entry.actor_id == lead.user_id && entry.target_user_id == Some(base_user.user_id)));
+    assert!(mod_log.iter().any(|entry| entry.action == ModAction::ModeratePost && entry.post_id == Some(title_post.post_id)));
+    assert!(mod_log.iter().any(|entry| entry.action == ModAction::ModeratePost && entry.post_id == Some(body_post.post_id)));
+    assert!(mod_log.iter().any(|entry| entry.action == ModAction::ModerateComment && entry.comment_id == Some(comment.comment_id)));
+
+    // automod rules also apply to edited content
+    let edited_post = update_post(
+        clean_post.post_id, "now forbidden", "body", None, Link::default(), PostTags::default(), &base_user, &db_pool,
+    ).await?;
+    assert_eq!(edited_post.moderator_id, Some(lead.user_id));
+    assert_eq!(edited_post.infringed_rule_id, Some(rule.rule_id));
+    let num_comments = get_post_by_id(clean_post.post_id, &db_pool).await?.num_comments;
+    let edited_comment = update_comment(clean_comment.comment_id, "forbidden edit", None, false, &base_user, &db_pool).await?;
+    assert_eq!(edited_comment.moderator_id, Some(lead.user_id));
+    assert_eq!(get_comment_by_id(clean_comment.comment_id, &db_pool).await?.infringed_rule_id, Some(rule.rule_id));
+    assert_eq!(get_post_by_id(clean_post.post_id, &db_pool).await?.num_comments, num_comments - 1);
+    assert_eq!(get_mod_log(&sphere.sphere_name, 10, 0, &lead, &db_pool).await?.len(), 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_automod_flag_action() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let base_user = create_user("user", &db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let automod_rule = create_automod_rule(&sphere.sphere_name, "spam", AutomodAction::Flag, None, &lead, &db_pool).await?;
+
+    let clean_post = create_simple_post(&sphere.sphere_name, None, "clean", "body", None, &base_user, &db_pool).await.post;
+    assert!(get_reports_for_sphere(&sphere.sphere_name, &lead, &db_pool).await?.is_empty());
+
+    let flagged_post = create_simple_post(&sphere.sphere_name, None, "title", "Buy SPAM", None, &base_user, &db_pool).await.post;
+    assert_eq!(flagged_post.moderator_id, None);
+    assert!(flagged_post.is_active());
+
+    let flagged_comment = create_comment(clean_post.post_id, None, "more spam", None, false, &base_user, &db_pool).await?;
+    assert_eq!(flagged_comment.moderator_id, None);
+
+    let report_vec = get_reports_for_sphere(&sphere.sphere_name, &lead, &db_pool).await?;
+    assert_eq!(report_vec.len(), 2);
+    assert!(report_vec.iter().all(|report| report.reporter_id == automod_rule.creator_id && report.reason.contains("spam")));
+    assert!(report_vec.iter().any(|report| report.post_id == flagged_post.post_id && report.comment_id.is_none()));
+    assert!(report_vec.iter().any(|report| report.post_id == clean_post.post_id && report.comment_id == Some(flagged_comment.comment_id)));
+
+    Ok(())
+}
@@ -1,16 +1,20 @@
 use rand::RngExt;
 
-use sphare_core_common::constants::COMMENT_BATCH_SIZE;
+use sphare_core_common::constants::{CHILD_COMMENT_BATCH_SIZE, COMMENT_BATCH_SIZE};
 use sphare_core_common::editor::get_styled_html_from_markdown;
 use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
 use sphare_core_common::errors::AppError;
-use sphare_core_content::comment::ssr::{create_comment, create_comment_with_notif, delete_comment, edit_comment, get_comment_by_id, get_comment_sphere, get_comment_tree_by_id, get_post_comment_tree, update_comment};
-use sphare_core_content::comment::CommentWithChildren;
+use sphare_core_content::comment::ssr::{create_comment, create_comment_with_notif, delete_comment, edit_comment, get_child_comments, get_comment_by_id, get_comment_sphere, get_comment_subtree, get_comment_tree_by_id, get_comment_with_ancestors, get_parent_comment, get_post_comment_tree, restore_comment, update_comment};
+use sphare_core_content::moderation::ssr::moderate_comment;
+use sphare_core_content::comment::{Comment, CommentWithChildren, CommentWithContext};
 use sphare_core_content::post::ssr::get_post_by_id;
 use sphare_core_content::ranking::{CommentSortType, SortType, VoteValue};
+use sphare_core_sphere::rule::ssr::add_rule;
+use sphare_core_sphere::sphere::ssr::{set_sphere_min_account_age_days, set_sphere_min_karma};
+use sphare_core_sphere::sphere_management::ssr::{add_trusted_user, remove_trusted_user};
 use sphare_core_user::notification::NotificationType;
 use sphare_core_user::notification::ssr::get_notifications;
-use sphare_core_user::user::User;
+use sphare_core_user::user::{KarmaType, User};
 
 use crate::common::*;
 use crate::data_factory::*;
@@ -48,6 +52,184 @@ async fn test_get_comment_sphere() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_comment_subtree() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    // reload user to refresh moderator permissions
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user.");
+
+    let comment_1 = create_comment(post.post_id, None, "1", None, false, &user, &db_pool).await?;
+    let comment_2 = create_comment(post.post_id, None, "2", None, false, &user, &db_pool).await?;
+    create_comment(post.post_id, Some(comment_2.comment_id), "2_1", None, false, &user, &db_pool).await?;
+
+    let comment_1_1 = create_comment(post.post_id, Some(comment_1.comment_id), "1_1", None, false, &user, &db_pool).await?;
+    set_comment_score(comment_1_1.comment_id, 2, &db_pool).await?;
+    let comment_1_2 = create_comment(post.post_id, Some(comment_1.comment_id), "1_2", None, false, &user, &db_pool).await?;
+    set_comment_score(comment_1_2.comment_id, 1, &db_pool).await?;
+    let comment_1_3 = create_comment(post.post_id, Some(comment_1.comment_id), "1_3", None, true, &user, &db_pool).await?;
+    let comment_1_2_1 = create_comment(post.post_id, Some(comment_1_2.comment_id), "1_2_1", None, false, &user, &db_pool).await?;
+    let comment_1_2_1_1 = create_comment(post.post_id, Some(comment_1_2_1.comment_id), "1_2_1_1", None, false, &user, &db_pool).await?;
+
+    let full_comment_tree = get_post_comment_tree(
+        post.post_id,
+        SortType::Comment(CommentSortType::Best),
+        None,
+        Some(user.user_id),
+        COMMENT_BATCH_SIZE,
+        0,
+        &db_pool
+    ).await?;
+    let mut expected_subtree = full_comment_tree.into_iter().find(
+        |comment| comment.comment.comment_id == comment_1.comment_id
+    ).expect("Should find comment 1").child_comments;
+
+    for sort_type in COMMENT_SORT_TYPE_ARRAY {
+        let subtree = get_comment_subtree(
+            comment_1.comment_id, SortType::Comment(sort_type), None, Some(user.user_id), &db_pool
+        ).await?;
+        sort_comment_tree(&mut expected_subtree, sort_type, true);
+        assert_eq!(subtree, expected_subtree);
+        assert_eq!(subtree.len(), 3);
+        assert_eq!(subtree[0].comment.comment_id, comment_1_3.comment_id);
+        let expected_second_comment_id = match sort_type {
+            CommentSortType::Best => comment_1_1.comment_id,
+            CommentSortType::Recent => comment_1_2.comment_id,
+        };
+        assert_eq!(subtree[1].comment.comment_id, expected_second_comment_id);
+    }
+
+    let limited_subtree = get_comment_subtree(
+        comment_1.comment_id, SortType::Comment(CommentSortType::Best), Some(0), Some(user.user_id), &db_pool
+    ).await?;
+    let limited_comment_1_2 = limited_subtree.iter().find(
+        |comment| comment.comment.comment_id == comment_1_2.comment_id
+    ).expect("Should find comment 1_2");
+    assert_eq!(limited_comment_1_2.child_comments.len(), 1);
+    assert_eq!(limited_comment_1_2.child_comments[0].comment, comment_1_2_1);
+    assert!(limited_comment_1_2.child_comments[0].child_comments.is_empty());
+
+    let comment_1_2_1_subtree = get_comment_subtree(
+        comment_1_2_1.comment_id, SortType::Comment(CommentSortType::Best), None, None, &db_pool
+    ).await?;
+    assert_eq!(comment_1_2_1_subtree.len(), 1);
+    assert_eq!(comment_1_2_1_subtree[0].comment, comment_1_2_1_1);
+
+    let leaf_subtree = get_comment_subtree(
+        comment_1_2_1_1.comment_id, SortType::Comment(CommentSortType::Best), None, None, &db_pool
+    ).await?;
+    assert!(leaf_subtree.is_empty());
+
+    assert!(get_comment_subtree(0, SortType::Comment(CommentSortType::Best), None, None, &db_pool).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_child_comments() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    // reload user to refresh moderator permissions
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user.");
+
+    let parent_comment = create_comment(post.post_id, None, "parent", None, false, &user, &db_pool).await?;
+
+    let num_replies = 2 * CHILD_COMMENT_BATCH_SIZE as usize + 5;
+    let mut expected_reply_vec = Vec::with_capacity(num_replies);
+    for i in 0..num_replies {
+        let reply = create_comment(post.post_id, Some(parent_comment.comment_id), &i.to_string(), None, i == num_replies - 1, &user, &db_pool).await?;
+        let reply = set_comment_score(reply.comment_id, ((i * 7) % num_replies) as i32, &db_pool).await?;
+        expected_reply_vec.push(CommentWithChildren {
+            comment: reply,
+            vote: None,
+            reactions: Vec::new(),
+            child_comments: Vec::new(),
+        });
+    }
+    let nested_reply = create_comment(post.post_id, Some(expected_reply_vec[0].comment.comment_id), "nested", None, false, &user, &db_pool).await?;
+    expected_reply_vec[0].child_comments.push(CommentWithChildren {
+        comment: nested_reply,
+        vote: None,
+        reactions: Vec::new(),
+        child_comments: Vec::new(),
+    });
+
+    for sort_type in COMMENT_SORT_TYPE_ARRAY {
+        sort_comment_tree(&mut expected_reply_vec, sort_type, true);
+        let mut loaded_reply_vec = Vec::new();
+        for offset in (0..num_replies).step_by(CHILD_COMMENT_BATCH_SIZE as usize) {
+            let reply_batch = get_child_comments(
+                parent_comment.comment_id,
+                SortType::Comment(sort_type),
+                None,
+                None,
+                CHILD_COMMENT_BATCH_SIZE,
+                offset as i64,
+                &db_pool
+            ).await?;
+            let batch_end = usize::min(offset + CHILD_COMMENT_BATCH_SIZE as usize, num_replies);
+            assert_eq!(reply_batch, expected_reply_vec[offset..batch_end]);
+            loaded_reply_vec.extend(reply_batch);
+        }
+        assert_eq!(loaded_reply_vec, expected_reply_vec);
+        assert!(loaded_reply_vec[0].comment.is_pinned);
+
+        let empty_batch = get_child_comments(
+            parent_comment.comment_id, SortType::Comment(sort_type), None, None, CHILD_COMMENT_BATCH_SIZE, num_replies as i64, &db_pool
+        ).await?;
+        assert!(empty_batch.is_empty());
+
+        // comment trees only include the first batch of replies of each comment
+        let comment_tree = get_post_comment_tree(
+            post.post_id, SortType::Comment(sort_type), None, None, COMMENT_BATCH_SIZE, 0, &db_pool
+        ).await?;
+        assert_eq!(comment_tree.len(), 1);
+        assert_eq!(comment_tree[0].child_comments, expected_reply_vec[..CHILD_COMMENT_BATCH_SIZE as usize]);
+    }
+
+    assert!(get_child_comments(0, SortType::Comment(CommentSortType::Best), None, None, CHILD_COMMENT_BATCH_SIZE, 0, &db_pool).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_parent_comment() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (sphere, post, root_comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+    let child_comment = create_comment(post.post_id, Some(root_comment.comment_id), "b", None, false, &user, &db_pool).await?;
+    let nested_comment = create_comment(post.post_id, Some(child_comment.comment_id), "c", None, false, &user, &db_pool).await?;
+
+    assert_eq!(get_parent_comment(root_comment.comment_id, &db_pool).await?, None);
+    assert_eq!(
+        get_parent_comment(child_comment.comment_id, &db_pool).await?,
+        Some(CommentWithContext::from_comment(root_comment.clone(), (&sphere).into(), &post)),
+    );
+    assert_eq!(
+        get_parent_comment(nested_comment.comment_id, &db_pool).await?,
+        Some(CommentWithContext::from_comment(child_comment.clone(), (&sphere).into(), &post)),
+    );
+
+    // deleted parent is returned as a placeholder
+    delete_comment(root_comment.comment_id, &user, &db_pool).await?;
+    let deleted_parent = get_parent_comment(child_comment.comment_id, &db_pool).await?.expect("Should get deleted parent");
+    assert_eq!(deleted_parent.comment.comment_id, root_comment.comment_id);
+    assert!(deleted_parent.comment.body.is_empty());
+    assert!(deleted_parent.comment.creator_name.is_empty());
+    assert!(deleted_parent.comment.delete_timestamp.is_some());
+    assert_eq!(deleted_parent.sphere_header.sphere_name, sphere.sphere_name);
+    assert_eq!(deleted_parent.post_title, post.title);
+
+    assert_eq!(get_parent_comment(-1, &db_pool).await, Err(AppError::NotFound));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_post_comment_tree() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -86,6 +268,7 @@ async fn test_get_post_comment_tree() -> Result<(), AppError> {
     expected_comment_tree.push(CommentWithChildren {
         comment: pinned_comment.clone(),
         vote: None,
+        reactions: Vec::new(),
         child_comments: Vec::new(),
     });
 
@@ -361,6 +544,73 @@ async fn test_get_comment_tree_by_id() {
     assert!(depth_2_comment_1_elem_1.child_comments.is_empty());
 }
 
+#[tokio::test]
+async fn test_get_comment_with_ancestors() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+
+    let comment_1 = create_comment(post.post_id, None, "1", None, false, &user, &db_pool).await?;
+    let comment_2 = create_comment(post.post_id, None, "2", None, false, &user, &db_pool).await?;
+    let comment_1_1 = create_comment(post.post_id, Some(comment_1.comment_id), "1_1", None, false, &user, &db_pool).await?;
+    let comment_1_1_1 = create_comment(post.post_id, Some(comment_1_1.comment_id), "1_1_1", None, false, &user, &db_pool).await?;
+    let comment_1_1_1_1 = create_comment(post.post_id, Some(comment_1_1_1.comment_id), "1_1_1_1", None, false, &user, &db_pool).await?;
+    let comment_1_1_1_2 = create_comment(post.post_id, Some(comment_1_1_1.comment_id), "1_1_1_2", None, false, &user, &db_pool).await?;
+    let comment_1_1_1_1_1 = create_comment(post.post_id, Some(comment_1_1_1_1.comment_id), "1_1_1_1_1", None, false, &user, &db_pool).await?;
+    create_comment(post.post_id, Some(comment_1_1.comment_id), "1_1_2", None, false, &user, &db_pool).await?;
+
+    for sort_type in COMMENT_SORT_TYPE_ARRAY {
+        let comment_with_ancestors = get_comment_with_ancestors(
+            comment_1_1_1.comment_id, SortType::Comment(sort_type), Some(0), Some(user.user_id), &db_pool
+        ).await?;
+
+        assert_eq!(
+            comment_with_ancestors.ancestors.iter().map(|ancestor| ancestor.comment.clone()).collect::<Vec<Comment>>(),
+            vec![comment_1.clone(), comment_1_1.clone()],
+        );
+        assert!(comment_with_ancestors.ancestors.iter().all(|ancestor| ancestor.child_comments.is_empty()));
+        assert_eq!(comment_with_ancestors.comment_tree.comment, comment_1_1_1);
+        assert_eq!(comment_with_ancestors.comment_tree.vote, None);
+
+        let mut child_comment_ids = comment_with_ancestors.comment_tree.child_comments.iter().map(
+            |child_comment| child_comment.comment.comment_id
+        ).collect::<Vec<i64>>();
+        child_comment_ids.sort();
+        assert_eq!(child_comment_ids, vec![comment_1_1_1_1.comment_id, comment_1_1_1_2.comment_id]);
+        // the replies are limited to one level below the direct replies
+        for child_comment in &comment_with_ancestors.comment_tree.child_comments {
+            let grandchild_comment_ids = child_comment.child_comments.iter().map(
+                |grandchild_comment| grandchild_comment.comment.comment_id
+            ).collect::<Vec<i64>>();
+            match child_comment.comment.comment_id == comment_1_1_1_1.comment_id {
+                true => assert_eq!(grandchild_comment_ids, vec![comment_1_1_1_1_1.comment_id]),
+                false => assert!(grandchild_comment_ids.is_empty()),
+            }
+        }
+
+        // the ancestors are nested into a single branch leading to the focused comment tree
+        let focused_comment_tree = comment_with_ancestors.comment_tree.clone();
+        let comment_tree = comment_with_ancestors.into_comment_tree();
+        assert_eq!(comment_tree.comment, comment_1);
+        assert_eq!(comment_tree.child_comments.len(), 1);
+        assert_eq!(comment_tree.child_comments[0].comment, comment_1_1);
+        assert_eq!(comment_tree.child_comments[0].child_comments, vec![focused_comment_tree]);
+    }
+
+    let root_with_ancestors = get_comment_with_ancestors(
+        comment_2.comment_id, SortType::Comment(CommentSortType::Best), None, None, &db_pool
+    ).await?;
+    assert!(root_with_ancestors.ancestors.is_empty());
+    assert_eq!(root_with_ancestors.comment_tree.comment, comment_2);
+    assert!(root_with_ancestors.comment_tree.child_comments.is_empty());
+
+    assert!(get_comment_with_ancestors(0, SortType::Comment(CommentSortType::Best), None, None, &db_pool).await.is_err());
+    assert!(get_comment_with_ancestors(i64::MAX, SortType::Comment(CommentSortType::Best), None, None, &db_pool).await.is_err());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_create_comment_with_notif() {
     let db_pool = get_db_pool().await;
@@ -523,6 +773,48 @@ async fn test_create_comment() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_create_comment_account_gates() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let (sphere, post) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let (other_sphere, other_post) = create_sphere_with_post("other", &mut lead, &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    set_sphere_min_account_age_days(&sphere.sphere_name, 7, &lead, &db_pool).await?;
+    set_sphere_min_account_age_days(&other_sphere.sphere_name, 7, &lead, &db_pool).await?;
+
+    assert_eq!(
+        create_comment(post.post_id, None, "comment", None, false, &user, &db_pool).await,
+        Err(AppError::AccountTooNew(7)),
+    );
+    // moderators are exempted from the requirements
+    create_comment(post.post_id, None, "comment", None, false, &lead, &db_pool).await?;
+
+    // trusted users are exempted from the requirements of their sphere only
+    let trusted_user = add_trusted_user(&user.username, &sphere.sphere_name, &lead, &db_pool).await?;
+    let comment = create_comment(post.post_id, None, "comment", None, false, &user, &db_pool).await?;
+    assert_eq!(comment.creator_id, user.user_id);
+    assert_eq!(
+        create_comment(other_post.post_id, None, "comment", None, false, &user, &db_pool).await,
+        Err(AppError::AccountTooNew(7)),
+    );
+
+    set_sphere_min_account_age_days(&other_sphere.sphere_name, 0, &lead, &db_pool).await?;
+    set_sphere_min_karma(&other_sphere.sphere_name, 5, KarmaType::Total, &lead, &db_pool).await?;
+    assert_eq!(
+        create_comment(other_post.post_id, None, "comment", None, false, &user, &db_pool).await,
+        Err(AppError::InsufficientKarma(5)),
+    );
+
+    remove_trusted_user(trusted_user.trusted_user_id, &lead, &db_pool).await?;
+    assert_eq!(
+        create_comment(post.post_id, None, "comment", None, false, &user, &db_pool).await,
+        Err(AppError::AccountTooNew(7)),
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_edit_comment() {
     let db_pool = get_db_pool().await;
@@ -633,6 +925,30 @@ async fn test_update_comment() -> Result<(), AppError> {
     Ok(())
 }
 
+
+#[tokio::test]
+async fn test_update_comment_edit_count() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, _, comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+    assert_eq!(comment.edit_count, 0);
+
+    for expected_edit_count in 1..=3 {
+        let updated_comment = update_comment(
+            comment.comment_id,
+            &format!("comment {expected_edit_count}"),
+            None,
+            false,
+            &user,
+            &db_pool
+        ).await?;
+        assert_eq!(updated_comment.edit_count, expected_edit_count);
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delete_comment() {
     let db_pool = get_db_pool().await;
@@ -708,3 +1024,84 @@ async fn test_delete_comment() {
         Err(AppError::NotFound),
     );
 }
+
+#[tokio::test]
+async fn test_delete_comment_num_comments() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, post, comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+    create_comment(post.post_id, None, "other", None, false, &user, &db_pool).await?;
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.num_comments, 2);
+
+    delete_comment(comment.comment_id, &user, &db_pool).await?;
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.num_comments, 1);
+
+    // an already deleted comment cannot be deleted again
+    assert_eq!(delete_comment(comment.comment_id, &user, &db_pool).await, Err(AppError::NotFound));
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.num_comments, 1);
+
+    restore_comment(comment.comment_id, &user, &db_pool).await?;
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.num_comments, 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_comment() {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    let (sphere, post, _) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+
+    let deleted_comment = get_deleted_comment(&post, &user, &db_pool).await;
+
+    assert_eq!(
+        restore_comment(deleted_comment.comment_id, &other_user, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+
+    let restored_comment = restore_comment(
+        deleted_comment.comment_id,
+        &user,
+        &db_pool
+    ).await.expect("Should restore comment.");
+
+    assert_eq!(restored_comment.comment_id, deleted_comment.comment_id);
+    assert_eq!(restored_comment.creator_id, user.user_id);
+    assert_eq!(restored_comment.creator_name, user.username);
+    assert_eq!(restored_comment.delete_timestamp, None);
+    assert!(
+        restored_comment.edit_timestamp.is_some() &&
+            restored_comment.edit_timestamp > deleted_comment.edit_timestamp
+    );
+    assert!(deleted_comment.body.is_empty());
+    assert_eq!(restored_comment.body, "a");
+    assert_eq!(restored_comment.markdown_body, None);
+
+    // The markdown body is restored as well
+    let markdown_comment = create_comment(post.post_id, None, "<h1>b</h1>", Some("# b"), false, &user, &db_pool).await.expect("Should create comment.");
+    let deleted_comment = delete_comment(markdown_comment.comment_id, &user, &db_pool).await.expect("Should delete comment.");
+    assert!(deleted_comment.body.is_empty());
+    assert_eq!(deleted_comment.markdown_body, None);
+    assert_eq!(get_comment_by_id(markdown_comment.comment_id, &db_pool).await.expect("Should get comment.").body, "");
+    let restored_comment = restore_comment(markdown_comment.comment_id, &user, &db_pool).await.expect("Should restore comment.");
+    assert_eq!(restored_comment.body, markdown_comment.body);
+    assert_eq!(restored_comment.markdown_body, markdown_comment.markdown_body);
+
+    // Cannot restore a comment that is not deleted
+    assert_eq!(
+        restore_comment(restored_comment.comment_id, &user, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+
+    // Cannot restore a deleted comment that was then moderated
+    let deleted_comment = get_deleted_comment(&post, &user, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "1", "2", false, &user, &db_pool).await.expect("Should add rule");
+    moderate_comment(deleted_comment.comment_id, rule.rule_id, "reason", &user, &db_pool).await.expect("Should moderate comment.");
+    assert_eq!(
+        restore_comment(deleted_comment.comment_id, &user, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+}
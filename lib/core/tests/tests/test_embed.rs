@@ -1,4 +1,71 @@
-use sphare_core_content::embed::{verify_link_and_get_embed, EmbedType, Link, LinkType};
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use url::Url;
+
+use sphare_core_common::errors::AppError;
+use sphare_core_content::embed::{discover_oembed, discover_oembed_endpoint, fetch_oembed_reply, get_oembed_discovery_client, verify_link_and_get_embed, EmbedType, Link, LinkType, OEmbedType, MAX_OEMBED_DISCOVERY_PAGE_SIZE};
+
+/// Starts a mock http server serving pages with oEmbed discovery links and the corresponding oEmbed data, returns its base url
+async fn start_oembed_mock_server() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("Should bind mock server");
+    let base_url = format!("http://{}", listener.local_addr().expect("Should get mock server address"));
+    let page = format!(
+        r#"<html><head><title>Page</title><link rel="alternate" type="application/json+oembed" href="{base_url}/oembed?url=page&amp;format=json" title="Page"></head><body></body></html>"#
+    );
+    let app = Router::new()
+        .route("/page", get(move || async move { ([(header::CONTENT_TYPE, "text/html")], page).into_response() }))
+        .route("/no_discovery", get(|| async { ([(header::CONTENT_TYPE, "text/html")], "<html><head></head></html>").into_response() }))
+        .route("/large", get(|| async { "a".repeat(MAX_OEMBED_DISCOVERY_PAGE_SIZE + 1) }))
+        .route("/oembed", get(|| async {
+            (
+                [(header::CONTENT_TYPE, "application/json")],
+                r#"{"type": "photo", "version": "1.0", "title": "Mock photo", "url": "https://example.com/photo.jpg", "width": 100, "height": 100, "thumbnail_url": "https://example.com/thumbnail.jpg"}"#,
+            ).into_response()
+        }));
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.expect("Mock server should run");
+    });
+    base_url
+}
+
+#[tokio::test]
+async fn test_discover_oembed() -> Result<(), AppError> {
+    let base_url = start_oembed_mock_server().await;
+    let client = get_oembed_discovery_client()?;
+
+    let page_url = Url::parse(&format!("{base_url}/page"))?;
+    let endpoint = discover_oembed_endpoint(&client, &page_url).await?;
+    assert_eq!(endpoint.as_str(), format!("{base_url}/oembed?url=page&format=json"));
+
+    let oembed_data = fetch_oembed_reply(&client, &endpoint).await?;
+    assert_eq!(oembed_data.title.as_deref(), Some("Mock photo"));
+    assert_eq!(oembed_data.thumbnail_url.as_deref(), Some("https://example.com/thumbnail.jpg"));
+    match oembed_data.oembed_type {
+        OEmbedType::Photo(photo) => assert_eq!(photo.url, "https://example.com/photo.jpg"),
+        oembed_type => panic!("Expected photo oEmbed type, got {oembed_type:?}"),
+    }
+
+    let no_discovery_url = Url::parse(&format!("{base_url}/no_discovery"))?;
+    assert!(discover_oembed_endpoint(&client, &no_discovery_url).await.is_err());
+
+    let large_page_url = Url::parse(&format!("{base_url}/large"))?;
+    assert_eq!(
+        discover_oembed_endpoint(&client, &large_page_url).await,
+        Err(AppError::PayloadTooLarge(MAX_OEMBED_DISCOVERY_PAGE_SIZE)),
+    );
+
+    let missing_url = Url::parse(&format!("{base_url}/missing"))?;
+    assert!(discover_oembed_endpoint(&client, &missing_url).await.is_err());
+
+    // Only public https urls are fetched
+    assert!(discover_oembed(&page_url).await.is_err());
+    assert!(discover_oembed(&Url::parse("https://127.0.0.1/page")?).await.is_err());
+    assert!(discover_oembed(&Url::parse("https://localhost/page")?).await.is_err());
+
+    Ok(())
+}
 
 #[tokio::test]
 async fn test_verify_link_and_get_embed() {
@@ -6,6 +73,7 @@ async fn test_verify_link_and_get_embed() {
         verify_link_and_get_embed(
             EmbedType::None,
             "this is not an url",
+            None,
         ).await,
         (Link::default(), None),
     );
@@ -15,6 +83,7 @@ async fn test_verify_link_and_get_embed() {
         verify_link_and_get_embed(
             EmbedType::None,
             &link_url,
+            None,
         ).await,
         (Link::new(LinkType::Link, Some(link_url.clone()), None, None), None),
     );
@@ -23,6 +92,7 @@ async fn test_verify_link_and_get_embed() {
         verify_link_and_get_embed(
             EmbedType::Embed,
             &link_url,
+            None,
         ).await,
         (Link::new(LinkType::Link, Some(link_url), None, None), None),
     );
@@ -32,6 +102,7 @@ async fn test_verify_link_and_get_embed() {
         verify_link_and_get_embed(
             EmbedType::None,
             &image_url,
+            None,
         ).await,
         (Link::new(LinkType::Image, Some(image_url.clone()), None, None), None),
     );
@@ -40,6 +111,7 @@ async fn test_verify_link_and_get_embed() {
         verify_link_and_get_embed(
             EmbedType::Link,
             &image_url,
+            None,
         ).await,
         (Link::new(LinkType::Link, Some(image_url.clone()), None, None), None),
     );
@@ -48,6 +120,7 @@ async fn test_verify_link_and_get_embed() {
         verify_link_and_get_embed(
             EmbedType::Embed,
             &image_url,
+            None,
         ).await,
         (Link::new(LinkType::Image, Some(image_url), None, None), None),
     );
@@ -57,6 +130,7 @@ async fn test_verify_link_and_get_embed() {
         verify_link_and_get_embed(
             EmbedType::None,
             &video_url,
+            None,
         ).await,
         (Link::new(LinkType::Video, Some(video_url.clone()), None, None), None),
     );
@@ -65,6 +139,7 @@ async fn test_verify_link_and_get_embed() {
         verify_link_and_get_embed(
             EmbedType::Link,
             &video_url,
+            None,
         ).await,
         (Link::new(LinkType::Link, Some(video_url.clone()), None, None), None),
     );
@@ -73,17 +148,51 @@ async fn test_verify_link_and_get_embed() {
         verify_link_and_get_embed(
             EmbedType::Embed,
             &video_url,
+            None,
         ).await,
         (Link::new(LinkType::Video, Some(video_url), None, None), None),
     );
 }
 
+#[tokio::test]
+async fn test_verify_link_and_get_embed_alt_text() {
+    let alt_text = String::from("A test image");
+    assert_eq!(
+        verify_link_and_get_embed(
+            EmbedType::None,
+            "this is not an url",
+            Some(alt_text.clone()),
+        ).await,
+        (Link::default(), None),
+    );
+
+    let image_url = String::from("https://www.test.com/image.jpg");
+    assert_eq!(
+        verify_link_and_get_embed(
+            EmbedType::Embed,
+            &image_url,
+            Some(alt_text.clone()),
+        ).await,
+        (Link::new(LinkType::Image, Some(image_url.clone()), None, None).with_alt_text(Some(alt_text)), None),
+    );
+
+    assert_eq!(
+        verify_link_and_get_embed(
+            EmbedType::Embed,
+            &image_url,
+            Some(String::new()),
+        ).await,
+        (Link::new(LinkType::Image, Some(image_url), None, None), None),
+    );
+}
+
 #[tokio::test]
 async fn test_verify_link_and_get_embed_giphy() {
     let giphy_url = String::from("https://giphy.com/gifs/justin-raccoon-pedro-tHIRLHtNwxpjIFqPdV");
     let (giphy_link, giphy_title) = verify_link_and_get_embed(
         EmbedType::None,
         &giphy_url,
+        None,
     ).await;
     assert_eq!(giphy_link.link_type, LinkType::Image);
     assert!(giphy_link.link_url.is_some());
@@ -95,6 +204,7 @@ async fn test_verify_link_and_get_embed_giphy() {
         verify_link_and_get_embed(
             EmbedType::Link,
             &giphy_url,
+            None,
         ).await,
         (Link::new(LinkType::Link, Some(giphy_url.clone()), None, None), None),
     );
@@ -103,6 +213,7 @@ async fn test_verify_link_and_get_embed_giphy() {
     let (giphy_link, giphy_title) = verify_link_and_get_embed(
         EmbedType::Embed,
         &giphy_url,
+        None,
     ).await;
     assert_eq!(giphy_link.link_type, LinkType::Image);
     assert!(giphy_link.link_url.is_some());
@@ -117,6 +228,7 @@ async fn test_verify_link_and_get_embed_youtube() {
     let (youtube_link, youtube_title) = verify_link_and_get_embed(
         EmbedType::None,
         &youtube_url,
+        None,
     ).await;
     assert_eq!(youtube_link.link_type, LinkType::Video);
     assert!(youtube_link.link_url.is_some());
@@ -128,6 +240,7 @@ async fn test_verify_link_and_get_embed_youtube() {
         verify_link_and_get_embed(
             EmbedType::Link,
             &youtube_url,
+            None,
         ).await,
         (Link::new(LinkType::Link, Some(youtube_url.clone()), None, None), None),
     );
@@ -135,6 +248,7 @@ async fn test_verify_link_and_get_embed_youtube() {
     let (youtube_link, youtube_title) = verify_link_and_get_embed(
         EmbedType::Embed,
         &youtube_url,
+        None,
     ).await;
     assert_eq!(youtube_link.link_type, LinkType::Video);
     assert!(youtube_link.link_url.is_some());
@@ -149,6 +263,7 @@ async fn test_verify_link_and_get_embed_bluesky() {
     let (bluesky_link, bluesky_title) = verify_link_and_get_embed(
         EmbedType::None,
         &bluesky_url,
+        None,
     ).await;
     assert_eq!(bluesky_link.link_type, LinkType::Rich);
     assert!(bluesky_link.link_url.is_some());
@@ -160,6 +275,7 @@ async fn test_verify_link_and_get_embed_bluesky() {
         verify_link_and_get_embed(
             EmbedType::Link,
             &bluesky_url,
+            None,
         ).await,
         (Link::new(LinkType::Link, Some(bluesky_url.clone()), None, None), None),
     );
@@ -167,6 +283,7 @@ async fn test_verify_link_and_get_embed_bluesky() {
     let (bluesky_link, bluesky_title) = verify_link_and_get_embed(
         EmbedType::Embed,
         &bluesky_url,
+        None,
     ).await;
     assert_eq!(bluesky_link.link_type, LinkType::Rich);
     assert!(bluesky_link.link_url.is_some());
@@ -0,0 +1,123 @@
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use tower::ServiceExt;
+
+use sphare_core_common::errors::AppError;
+use sphare_core_common::routes::{get_app_origin, get_post_path, get_sphere_feed_path, SPHERE_FEED_ROUTE};
+use sphare_core_content::embed::Link;
+use sphare_core_content::feed::ssr::sphere_feed_handler;
+use sphare_core_content::feed::ATOM_CONTENT_TYPE;
+use sphare_core_content::post::ssr::create_post;
+use sphare_core_content::post::PostTags;
+
+use crate::common::*;
+use crate::data_factory::*;
+
+mod common;
+mod data_factory;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct FeedEntry {
+    title: String,
+    link: String,
+    author: String,
+}
+
+/// Parses the entries of an Atom feed, fails if the xml is not well-formed
+fn parse_feed_entries(feed: &str) -> Result<Vec<FeedEntry>, AppError> {
+    let mut reader = Reader::from_str(feed);
+    let mut entry_vec = Vec::new();
+    let mut current_entry: Option<FeedEntry> = None;
+    let mut current_element = Vec::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                if e.name().as_ref() == b"entry" {
+                    current_entry = Some(FeedEntry::default());
+                }
+                current_element = e.name().as_ref().to_vec();
+            },
+            Event::Empty(e) => if let Some(entry) = current_entry.as_mut() &&
+                e.name().as_ref() == b"link" &&
+                let Some(href) = e.try_get_attribute("href").map_err(|e| AppError::new(e.to_string()))?
+            {
+                entry.link = href.unescape_value().map_err(|e| AppError::new(e.to_string()))?.to_string();
+            },
+            Event::Text(e) => if let Some(entry) = current_entry.as_mut() {
+                let text = e.decode().map_err(|e| AppError::new(e.to_string()))?.to_string();
+                match current_element.as_slice() {
+                    b"title" => entry.title.push_str(&text),
+                    b"name" => entry.author.push_str(&text),
+                    _ => (),
+                }
+            },
+            Event::GeneralRef(e) => if let Some(entry) = current_entry.as_mut() && current_element.as_slice() == b"title" {
+                let entity = e.decode().map_err(|e| AppError::new(e.to_string()))?;
+                let text = quick_xml::escape::resolve_predefined_entity(&entity).ok_or(AppError::new("Unknown entity"))?;
+                entry.title.push_str(text);
+            },
+            Event::End(e) => {
+                if e.name().as_ref() == b"entry" && let Some(entry) = current_entry.take() {
+                    entry_vec.push(entry);
+                }
+                current_element.clear();
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Ok(entry_vec)
+}
+
+#[tokio::test]
+async fn test_sphere_feed_route() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (sphere, post_1) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let post_2 = create_post(
+        &sphere.sphere_name,
+        None,
+        "Tom & Jerry <3",
+        "body",
+        None,
+        Link::default(),
+        PostTags::default(),
+        &user,
+        &db_pool,
+    ).await?;
+
+    let app = Router::new()
+        .route(SPHERE_FEED_ROUTE, get(sphere_feed_handler))
+        .with_state(db_pool.clone());
+
+    let response = app.clone().oneshot(
+        Request::get(get_sphere_feed_path(&sphere.sphere_name)).body(Body::empty()).expect("Should build request")
+    ).await.expect("Should get response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get(header::CONTENT_TYPE).map(|value| value.as_bytes()), Some(ATOM_CONTENT_TYPE.as_bytes()));
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.expect("Should read response body");
+    let feed = String::from_utf8(body.to_vec())?;
+    assert!(feed.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+
+    let entry_vec = parse_feed_entries(&feed)?;
+    let expected_entry_vec: Vec<FeedEntry> = [post_2, post_1].into_iter().map(|post| FeedEntry {
+        title: post.title,
+        link: format!("{}{}", get_app_origin().unwrap_or_default(), get_post_path(&sphere.sphere_name, None, post.post_id)),
+        author: user.username.clone(),
+    }).collect();
+    assert_eq!(entry_vec, expected_entry_vec);
+
+    let response = app.oneshot(
+        Request::get(get_sphere_feed_path("missing")).body(Body::empty()).expect("Should build request")
+    ).await.expect("Should get response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
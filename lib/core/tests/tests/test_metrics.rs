@@ -0,0 +1,59 @@
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+use sphare_core_common::errors::AppError;
+use sphare_core_common::metrics::{COMMENTS_CREATED_METRIC, POSTS_CREATED_METRIC, VOTES_METRIC};
+use sphare_core_content::comment::ssr::create_comment;
+use sphare_core_content::ranking::ssr::vote_on_content;
+use sphare_core_content::ranking::VoteValue;
+
+use crate::common::{create_test_user, create_user, get_db_pool};
+use crate::data_factory::create_sphere_with_post;
+
+mod common;
+mod data_factory;
+
+/// Returns the value of the metric `metric` in the Prometheus text format `rendered_metrics`, 0 if it was not recorded yet
+fn get_metric_value(rendered_metrics: &str, metric: &str) -> u64 {
+    rendered_metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(metric).and_then(|value| value.strip_prefix(' ')))
+        .map(|value| value.parse::<u64>().expect("Should parse metric value"))
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_content_metrics() -> Result<(), AppError> {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let metrics_handle: PrometheusHandle = recorder.handle();
+    metrics::set_global_recorder(recorder).expect("Should set global metrics recorder");
+    let up_votes_metric = format!("{VOTES_METRIC}{{value=\"up\"}}");
+    let down_votes_metric = format!("{VOTES_METRIC}{{value=\"down\"}}");
+
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let voter = create_user("voter", &db_pool).await;
+
+    let (_, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let rendered_metrics = metrics_handle.render();
+    assert_eq!(get_metric_value(&rendered_metrics, POSTS_CREATED_METRIC), 1);
+    assert_eq!(get_metric_value(&rendered_metrics, COMMENTS_CREATED_METRIC), 0);
+
+    let comment = create_comment(post.post_id, None, "comment", None, false, &user, &db_pool).await?;
+    create_comment(post.post_id, Some(comment.comment_id), "reply", None, false, &voter, &db_pool).await?;
+    let rendered_metrics = metrics_handle.render();
+    assert_eq!(get_metric_value(&rendered_metrics, POSTS_CREATED_METRIC), 1);
+    assert_eq!(get_metric_value(&rendered_metrics, COMMENTS_CREATED_METRIC), 2);
+
+    let vote = vote_on_content(VoteValue::Up, post.post_id, None, None, &voter, &db_pool).await?.expect("Vote should be created");
+    vote_on_content(VoteValue::Down, post.post_id, Some(comment.comment_id), None, &voter, &db_pool).await?;
+    assert_eq!(get_metric_value(&metrics_handle.render(), &up_votes_metric), 1);
+    assert_eq!(get_metric_value(&metrics_handle.render(), &down_votes_metric), 1);
+
+    // Repeating a vote does not count as a new vote event
+    vote_on_content(VoteValue::Up, post.post_id, None, Some(vote.vote_id), &voter, &db_pool).await?;
+    assert_eq!(get_metric_value(&metrics_handle.render(), &up_votes_metric), 1);
+    vote_on_content(VoteValue::Down, post.post_id, None, Some(vote.vote_id), &voter, &db_pool).await?;
+    assert_eq!(get_metric_value(&metrics_handle.render(), &down_votes_metric), 2);
+
+    Ok(())
+}
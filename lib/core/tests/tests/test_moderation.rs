@@ -1,22 +1,31 @@
 use std::ops::Add;
+use std::time::Duration;
 use chrono::Days;
 
+use sphare_core_common::constants::{MAX_BAN_DURATION_HOURS, MAX_REPORT_REASON_LENGTH};
 use sphare_core_common::errors::AppError;
-use sphare_core_content::comment::ssr::{create_comment, get_comment_by_id};
+use sphare_core_content::comment::ssr::{create_comment, get_comment_by_id, PENDING_POST_COMMENT_STR};
 use sphare_core_content::embed::Link;
-use sphare_core_content::moderation::{Content};
-use sphare_core_content::moderation::ssr::{ban_user_from_sphere, get_moderation_info, moderate_comment, moderate_comment_and_ban_user, moderate_post, moderate_post_and_ban_user};
-use sphare_core_content::post::PostTags;
-use sphare_core_content::post::ssr::{create_post, get_post_by_id};
+use sphare_core_content::moderation::{Content, ContentReport, REPORT_FETCH_LIMIT};
+use sphare_core_content::moderation::ssr::{approve_post, ban_user_from_sphere, ban_user_temporarily, create_mod_reason_template, delete_mod_reason_template, get_pending_post_vec, get_controversial_content, get_global_reports, get_mod_reason_template_vec, get_moderation_info, get_removed_comment, get_removed_post, get_reports_for_sphere, moderate_comment, moderate_reported_content, moderate_comment_and_ban_user, moderate_post, moderate_post_and_ban_user, moderate_post_with_template, moderate_posts, move_post_to_satellite, mute_user, report_comment, report_post, unmoderate_comment, unmoderate_post, unmute_user, update_mod_reason_template};
+use sphare_core_content::post::{PostDataInputs, PostLocation, PostTags};
+use sphare_core_content::filter::SphereCategoryFilter;
+use sphare_core_content::post::ssr::{create_post, create_post_and_vote, delete_post, get_post_by_id, get_post_vec_by_sphere_name, get_sorted_post_vec};
+use sphare_core_content::ranking::{PostSortType, SortType};
+use sphare_core_sphere::satellite::ssr::create_satellite;
+use sphare_core_sphere::sphere::ssr::set_sphere_require_post_approval;
 use sphare_core_sphere::rule::BaseRule;
 use sphare_core_sphere::rule::ssr::add_rule;
+use sphare_core_user::mod_log::ssr::get_mod_log;
+use sphare_core_user::mod_log::{ModAction, MOD_LOG_FETCH_LIMIT};
 use sphare_core_user::notification::NotificationType;
-use sphare_core_user::role::AdminRole;
-use sphare_core_user::role::ssr::set_user_admin_role;
+use sphare_core_user::notification::ssr::get_notifications;
+use sphare_core_user::role::{AdminRole, PermissionLevel};
+use sphare_core_user::role::ssr::{set_user_admin_role, set_user_sphere_role};
 use sphare_core_user::user::User;
 use crate::common::{create_test_user, create_user, get_db_pool};
-use crate::data_factory::{add_base_rule, create_sphere_with_post, create_sphere_with_post_and_comment};
-use crate::utils::get_notification;
+use crate::data_factory::{add_base_rule, create_simple_post, create_sphere_with_post, create_sphere_with_post_and_comment, create_sphere_with_satellite, set_comment_timestamp, set_comment_vote_counts, set_post_timestamp, set_post_vote_counts};
+use crate::utils::{get_notification, set_user_ban_until_timestamp};
 
 mod common;
 mod data_factory;
@@ -48,6 +57,480 @@ async fn test_get_moderation_info() {
     assert_eq!(comment_moderation_info.content, Content::Comment(moderated_comment));
 }
 
+#[tokio::test]
+async fn test_get_removed_post() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_user("test", &db_pool).await;
+    let mut global_moderator = create_user("mod", &db_pool).await;
+    global_moderator.admin_role = AdminRole::Moderator;
+    let regular_user = create_user("user", &db_pool).await;
+
+    let (sphere, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &user, &db_pool).await.expect("Rule should be added.");
+
+    assert_eq!(get_removed_post(post.post_id, &user, &db_pool).await, Err(AppError::NotFound));
+
+    moderate_post(post.post_id, rule.rule_id, "test", &user, &db_pool).await?;
+
+    let removed_post = get_removed_post(post.post_id, &user, &db_pool).await?;
+    assert_eq!(removed_post.title, post.title);
+    assert_eq!(removed_post.body, post.body);
+    assert_eq!(removed_post.moderator_id, Some(user.user_id));
+    assert_eq!(get_removed_post(post.post_id, &global_moderator, &db_pool).await?, removed_post);
+
+    assert_eq!(get_removed_post(post.post_id, &regular_user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_removed_comment() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_user("test", &db_pool).await;
+    let mut global_moderator = create_user("mod", &db_pool).await;
+    global_moderator.admin_role = AdminRole::Moderator;
+    let regular_user = create_user("user", &db_pool).await;
+
+    let (sphere, _post, comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &user, &db_pool).await.expect("Rule should be added.");
+
+    assert_eq!(get_removed_comment(comment.comment_id, &user, &db_pool).await, Err(AppError::NotFound));
+
+    moderate_comment(comment.comment_id, rule.rule_id, "test", &user, &db_pool).await?;
+
+    let removed_comment = get_removed_comment(comment.comment_id, &user, &db_pool).await?;
+    assert_eq!(removed_comment.body, comment.body);
+    assert_eq!(removed_comment.moderator_id, Some(user.user_id));
+    assert_eq!(get_removed_comment(comment.comment_id, &global_moderator, &db_pool).await?, removed_comment);
+
+    assert_eq!(get_removed_comment(comment.comment_id, &regular_user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_post_approval_queue() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+
+    let (sphere, approved_post) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    assert!(approved_post.is_approved);
+
+    assert_eq!(
+        set_sphere_require_post_approval(&sphere.sphere_name, true, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    let sphere = set_sphere_require_post_approval(&sphere.sphere_name, true, &lead, &db_pool).await?;
+    assert!(sphere.require_post_approval);
+
+    let pending_post = create_post(
+        &sphere.sphere_name,
+        None,
+        "pending",
+        "body",
+        None,
+        Link::default(),
+        PostTags::default(),
+        &user,
+        &db_pool,
+    ).await?;
+    assert!(!pending_post.is_approved);
+
+    // posts of moderators are approved directly
+    let moderator_post = create_post(
+        &sphere.sphere_name,
+        None,
+        "moderator",
+        "body",
+        None,
+        Link::default(),
+        PostTags::default(),
+        &lead,
+        &db_pool,
+    ).await?;
+    assert!(moderator_post.is_approved);
+
+    let get_visible_post_ids = async || -> Result<(Vec<i64>, Vec<i64>), AppError> {
+        let sphere_post_id_vec = get_post_vec_by_sphere_name(
+            &sphere.sphere_name,
+            SphereCategoryFilter::All,
+            SortType::Post(PostSortType::Recent),
+            10,
+            0,
+            None,
+            &db_pool,
+        ).await?.into_iter().map(|post| post.post_id).collect();
+        let sorted_post_id_vec = get_sorted_post_vec(
+            SortType::Post(PostSortType::Recent),
+            10,
+            0,
+            None,
+            &db_pool,
+        ).await?.into_iter().map(|post| post.post.post_id).collect();
+        Ok((sphere_post_id_vec, sorted_post_id_vec))
+    };
+
+    let (sphere_post_id_vec, sorted_post_id_vec) = get_visible_post_ids().await?;
+    assert_eq!(sphere_post_id_vec, vec![moderator_post.post_id, approved_post.post_id]);
+    assert_eq!(sorted_post_id_vec, vec![moderator_post.post_id, approved_post.post_id]);
+
+    assert_eq!(get_pending_post_vec(&sphere.sphere_name, &user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(get_pending_post_vec(&sphere.sphere_name, &lead, &db_pool).await?, vec![pending_post.clone()]);
+
+    assert_eq!(approve_post(pending_post.post_id, &user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    let approved_pending_post = approve_post(pending_post.post_id, &lead, &db_pool).await?;
+    assert!(approved_pending_post.is_approved);
+
+    assert!(get_pending_post_vec(&sphere.sphere_name, &lead, &db_pool).await?.is_empty());
+    let (sphere_post_id_vec, sorted_post_id_vec) = get_visible_post_ids().await?;
+    let expected_post_id_vec = vec![moderator_post.post_id, pending_post.post_id, approved_post.post_id];
+    assert_eq!(sphere_post_id_vec, expected_post_id_vec);
+    assert_eq!(sorted_post_id_vec, expected_post_id_vec);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pending_post_mentions_and_comments() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let mentioned_user = create_user("mentioned", &db_pool).await;
+
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    set_sphere_require_post_approval(&sphere.sphere_name, true, &lead, &db_pool).await?;
+
+    let (pending_post, _, _) = create_post_and_vote(
+        PostLocation {
+            sphere: sphere.sphere_name.clone(),
+            satellite_id: None,
+        },
+        PostDataInputs {
+            title: String::from("pending"),
+            body: format!("hello @{}", mentioned_user.username),
+            is_markdown: false,
+            embed_type: Default::default(),
+            link: None,
+            alt_text: None,
+            post_tags: Default::default(),
+        },
+        None,
+        &user,
+        &db_pool,
+    ).await?;
+    assert!(!pending_post.is_approved);
+
+    // mentions are only notified once the post is approved
+    assert!(get_notifications(mentioned_user.user_id, &db_pool).await?.is_empty());
+    // pending posts cannot be commented
+    assert_eq!(
+        create_comment(pending_post.post_id, None, "comment", None, false, &lead, &db_pool).await,
+        Err(AppError::new(PENDING_POST_COMMENT_STR)),
+    );
+
+    approve_post(pending_post.post_id, &lead, &db_pool).await?;
+    let notif_vec = get_notifications(mentioned_user.user_id, &db_pool).await?;
+    assert_eq!(notif_vec.len(), 1);
+    assert_eq!(notif_vec[0].post_id, pending_post.post_id);
+    assert_eq!(notif_vec[0].trigger_user_id, user.user_id);
+    assert_eq!(notif_vec[0].notification_type, NotificationType::Mention);
+
+    // approving again does not notify again
+    approve_post(pending_post.post_id, &lead, &db_pool).await?;
+    assert_eq!(get_notifications(mentioned_user.user_id, &db_pool).await?.len(), 1);
+
+    create_comment(pending_post.post_id, None, "comment", None, false, &user, &db_pool).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_approve_removed_post() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    set_sphere_require_post_approval(&sphere.sphere_name, true, &lead, &db_pool).await?;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &lead, &db_pool).await?;
+
+    let moderated_post = create_simple_post(&sphere.sphere_name, None, "a", "b", None, &user, &db_pool).await.post;
+    moderate_post(moderated_post.post_id, rule.rule_id, "test", &lead, &db_pool).await?;
+    let deleted_post = create_simple_post(&sphere.sphere_name, None, "a", "b", None, &user, &db_pool).await.post;
+    delete_post(deleted_post.post_id, &user, &db_pool).await?;
+
+    for post_id in [moderated_post.post_id, deleted_post.post_id] {
+        assert_eq!(approve_post(post_id, &lead, &db_pool).await, Err(AppError::NotFound));
+        assert!(!get_post_by_id(post_id, &db_pool).await?.is_approved);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_post_to_satellite() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+
+    let (sphere, post) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let satellite = create_satellite(&sphere.sphere_name, "a", "a", false, false, false, &lead, &db_pool).await?;
+    let nsfw_spoiler_satellite = create_satellite(&sphere.sphere_name, "b", "b", false, true, true, &lead, &db_pool).await?;
+    let (_, other_sphere_satellite) = create_sphere_with_satellite("other", "c", false, false, &mut lead, &db_pool).await?;
+
+    assert_eq!(move_post_to_satellite(post.post_id, Some(satellite.satellite_id), &user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+
+    let moved_post = move_post_to_satellite(post.post_id, Some(nsfw_spoiler_satellite.satellite_id), &lead, &db_pool).await?;
+    assert_eq!(moved_post.satellite_id, Some(nsfw_spoiler_satellite.satellite_id));
+    assert_eq!(moved_post.sphere_id, sphere.sphere_id);
+    assert_eq!(moved_post.creator_name, lead.username);
+    assert!(moved_post.is_nsfw);
+    assert!(moved_post.is_spoiler);
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?, moved_post);
+
+    // Tags inherited from the previous satellite are removed
+    let moved_post = move_post_to_satellite(post.post_id, Some(satellite.satellite_id), &lead, &db_pool).await?;
+    assert_eq!(moved_post.satellite_id, Some(satellite.satellite_id));
+    assert!(!moved_post.is_nsfw);
+    assert!(!moved_post.is_spoiler);
+
+    let moved_post = move_post_to_satellite(post.post_id, None, &lead, &db_pool).await?;
+    assert_eq!(moved_post.satellite_id, None);
+    assert!(!moved_post.is_nsfw);
+    assert!(!moved_post.is_spoiler);
+
+    // Tags set by the author are kept
+    let tagged_post = create_post(
+        &sphere.sphere_name,
+        None,
+        "tagged",
+        "body",
+        None,
+        Link::default(),
+        PostTags::new(true, true, false, None),
+        &lead,
+        &db_pool,
+    ).await?;
+    let moved_post = move_post_to_satellite(tagged_post.post_id, Some(satellite.satellite_id), &lead, &db_pool).await?;
+    assert_eq!(moved_post.satellite_id, Some(satellite.satellite_id));
+    assert!(moved_post.is_nsfw);
+    assert!(moved_post.is_spoiler);
+
+    // Satellites of other spheres are rejected
+    assert!(move_post_to_satellite(post.post_id, Some(other_sphere_satellite.satellite_id), &lead, &db_pool).await.is_err());
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.satellite_id, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_report_content() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    let (sphere, post, comment) = create_sphere_with_post_and_comment("a", &mut lead, &db_pool).await;
+    let (other_sphere, _) = create_sphere_with_post("b", &mut lead, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "rule", "description", false, &lead, &db_pool).await?;
+    let other_rule = add_rule(&other_sphere.sphere_name, 0, "other", "description", false, &lead, &db_pool).await?;
+
+    // a reason is required when no rule is given, rules must belong to the sphere of the content
+    assert!(report_post(post.post_id, None, "", &user, &db_pool).await.is_err());
+    assert!(report_post(post.post_id, Some(other_rule.rule_id), "reason", &user, &db_pool).await.is_err());
+    assert!(report_post(post.post_id, None, &"a".repeat(MAX_REPORT_REASON_LENGTH + 1), &user, &db_pool).await.is_err());
+
+    let post_report = report_post(post.post_id, Some(rule.rule_id), "", &user, &db_pool).await?;
+    assert_eq!(post_report.sphere_id, sphere.sphere_id);
+    assert_eq!(post_report.sphere_name, sphere.sphere_name);
+    assert_eq!(post_report.post_id, post.post_id);
+    assert_eq!(post_report.comment_id, None);
+    assert_eq!(post_report.rule_id, Some(rule.rule_id));
+    assert_eq!(post_report.rule_title, Some(rule.title.clone()));
+    assert_eq!(post_report.reason, "");
+    assert_eq!(post_report.reporter_id, user.user_id);
+    assert_eq!(post_report.reporter_name, user.username);
+
+    let comment_report = report_comment(comment.comment_id, None, "spam", &user, &db_pool).await?;
+    assert_eq!(comment_report.sphere_id, sphere.sphere_id);
+    assert_eq!(comment_report.post_id, post.post_id);
+    assert_eq!(comment_report.comment_id, Some(comment.comment_id));
+    assert_eq!(comment_report.rule_id, None);
+    assert_eq!(comment_report.rule_title, None);
+    assert_eq!(comment_report.reason, "spam");
+
+    // duplicate reports are rejected, other users can still report the same content
+    assert!(report_post(post.post_id, None, "again", &user, &db_pool).await.is_err());
+    assert!(report_comment(comment.comment_id, Some(rule.rule_id), "again", &user, &db_pool).await.is_err());
+    let other_post_report = report_post(post.post_id, None, "other", &other_user, &db_pool).await?;
+
+    // only moderators can list reports
+    assert_eq!(get_reports_for_sphere(&sphere.sphere_name, &user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    let lead = User::get(lead.user_id, &db_pool).await.expect("Should reload user");
+    let report_vec = get_reports_for_sphere(&sphere.sphere_name, &lead, &db_pool).await?;
+    let expected_report_vec: Vec<ContentReport> = vec![other_post_report.clone(), comment_report.clone(), post_report];
+    assert_eq!(
+        report_vec.iter().map(|report| report.report_id).collect::<Vec<i64>>(),
+        expected_report_vec.iter().map(|report| report.report_id).collect::<Vec<i64>>(),
+    );
+    assert_eq!(report_vec[0].reporter_name, other_user.username);
+    assert!(get_reports_for_sphere(&other_sphere.sphere_name, &lead, &db_pool).await?.is_empty());
+
+    // reports of moderated content are resolved and moderated content cannot be reported
+    moderate_post(post.post_id, rule.rule_id, "moderated", &lead, &db_pool).await?;
+    let report_vec = get_reports_for_sphere(&sphere.sphere_name, &lead, &db_pool).await?;
+    assert_eq!(
+        report_vec.iter().map(|report| report.report_id).collect::<Vec<i64>>(),
+        vec![comment_report.report_id],
+    );
+    let new_user = create_user("new", &db_pool).await;
+    assert!(report_post(post.post_id, None, "reason", &new_user, &db_pool).await.is_err());
+
+    moderate_comment(comment.comment_id, rule.rule_id, "moderated", &lead, &db_pool).await?;
+    assert!(get_reports_for_sphere(&sphere.sphere_name, &lead, &db_pool).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_global_reports() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let mut admin = create_user("site", &db_pool).await;
+
+    let (sphere, post, comment) = create_sphere_with_post_and_comment("a", &mut lead, &db_pool).await;
+    let (other_sphere, other_post) = create_sphere_with_post("b", &mut lead, &db_pool).await;
+
+    let post_report = report_post(post.post_id, None, "spam", &user, &db_pool).await?;
+    let comment_report = report_comment(comment.comment_id, None, "insult", &user, &db_pool).await?;
+    let other_post_report = report_post(other_post.post_id, None, "off-topic", &user, &db_pool).await?;
+
+    // only admins can list reports of all spheres, sphere leaders and global moderators are rejected
+    assert_eq!(get_global_reports(REPORT_FETCH_LIMIT, 0, &user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    let lead = User::get(lead.user_id, &db_pool).await.expect("Should reload user");
+    assert_eq!(get_global_reports(REPORT_FETCH_LIMIT, 0, &lead, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    admin.admin_role = AdminRole::Moderator;
+    assert_eq!(get_global_reports(REPORT_FETCH_LIMIT, 0, &admin, &db_pool).await, Err(AppError::InsufficientPrivileges));
+
+    admin.admin_role = AdminRole::Admin;
+    let report_vec = get_global_reports(REPORT_FETCH_LIMIT, 0, &admin, &db_pool).await?;
+    let expected_report_vec: Vec<ContentReport> = vec![other_post_report.clone(), comment_report.clone(), post_report];
+    assert_eq!(report_vec, expected_report_vec);
+    assert_eq!(report_vec[0].sphere_name, other_sphere.sphere_name);
+    assert_eq!(report_vec[1].sphere_name, sphere.sphere_name);
+
+    let report_vec = get_global_reports(1, 1, &admin, &db_pool).await?;
+    assert_eq!(report_vec, vec![expected_report_vec[1].clone()]);
+
+    // negative limits and offsets are clamped
+    assert!(get_global_reports(-1, 0, &admin, &db_pool).await?.is_empty());
+    assert_eq!(get_global_reports(REPORT_FETCH_LIMIT, -1, &admin, &db_pool).await?, expected_report_vec);
+
+    // admins can moderate reported content of any sphere for infringing a site-wide rule or a rule of its sphere
+    let global_rule = add_base_rule(0, BaseRule::NoIllegalContent.into(), "test", None, &admin, &db_pool).await?;
+    let sphere_rule = add_rule(&sphere.sphere_name, 0, "rule", "description", false, &lead, &db_pool).await?;
+    assert_eq!(
+        moderate_reported_content(other_post_report.report_id, global_rule.rule_id, "moderated", &lead, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(moderate_reported_content(other_post_report.report_id, sphere_rule.rule_id, "moderated", &admin, &db_pool).await.is_err());
+    assert!(moderate_reported_content(other_post_report.report_id, global_rule.rule_id, "", &admin, &db_pool).await.is_err());
+
+    let moderated_post = match moderate_reported_content(other_post_report.report_id, global_rule.rule_id, "moderated", &admin, &db_pool).await? {
+        Content::Post(post) => post,
+        Content::Comment(_) => panic!("Reported post should be moderated."),
+    };
+    assert_eq!(moderated_post.post_id, other_post.post_id);
+    assert_eq!(moderated_post.moderator_id, Some(admin.user_id));
+    assert_eq!(moderated_post.infringed_rule_id, Some(global_rule.rule_id));
+    assert!(!moderated_post.is_sphere_rule);
+
+    let moderated_comment = match moderate_reported_content(comment_report.report_id, sphere_rule.rule_id, "moderated", &admin, &db_pool).await? {
+        Content::Comment(comment) => comment,
+        Content::Post(_) => panic!("Reported comment should be moderated."),
+    };
+    assert_eq!(moderated_comment.comment_id, comment.comment_id);
+    assert_eq!(moderated_comment.moderator_id, Some(admin.user_id));
+    assert_eq!(moderated_comment.infringed_rule_id, Some(sphere_rule.rule_id));
+
+    // reports of moderated content are resolved
+    assert_eq!(get_global_reports(REPORT_FETCH_LIMIT, 0, &admin, &db_pool).await?, vec![expected_report_vec[2].clone()]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mod_log() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let moderator = create_user("moderator", &db_pool).await;
+
+    let (sphere, _post, comment) = create_sphere_with_post_and_comment("a", &mut lead, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "rule", "description", false, &lead, &db_pool).await?;
+    let user_post = create_post(&sphere.sphere_name, None, "a", "b", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+    assert!(get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &lead, &db_pool).await?.is_empty());
+
+    let (moderated_post, user_ban, _) = moderate_post_and_ban_user(user_post.post_id, rule.rule_id, "moderated", Some(7), &lead, &db_pool).await?;
+    let user_ban = user_ban.expect("User should be banned");
+    set_user_sphere_role(&moderator.username, &sphere.sphere_name, PermissionLevel::Moderate, &lead, &db_pool).await?;
+    let moderator = User::get(moderator.user_id, &db_pool).await.expect("Should reload moderator");
+    moderate_comment(comment.comment_id, rule.rule_id, "moderated", &moderator, &db_pool).await?;
+
+    let mod_log_vec = get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &lead, &db_pool).await?;
+    assert_eq!(mod_log_vec.len(), 4);
+    assert!(mod_log_vec.iter().all(|mod_log_entry| mod_log_entry.sphere_id == sphere.sphere_id));
+
+    let comment_log = &mod_log_vec[0];
+    assert_eq!(comment_log.action, ModAction::ModerateComment);
+    assert_eq!(comment_log.actor_id, moderator.user_id);
+    assert_eq!(comment_log.actor_name, moderator.username);
+    assert_eq!(comment_log.target_user_id, Some(lead.user_id));
+    assert_eq!(comment_log.post_id, Some(comment.post_id));
+    assert_eq!(comment_log.comment_id, Some(comment.comment_id));
+
+    let role_log = &mod_log_vec[1];
+    assert_eq!(role_log.action, ModAction::SetRole);
+    assert_eq!(role_log.actor_id, lead.user_id);
+    assert_eq!(role_log.target_user_id, Some(moderator.user_id));
+    assert_eq!(role_log.target_username, Some(moderator.username.clone()));
+    assert_eq!(role_log.post_id, None);
+
+    let ban_log = &mod_log_vec[2];
+    assert_eq!(ban_log.action, ModAction::BanUser);
+    assert_eq!(ban_log.actor_id, lead.user_id);
+    assert_eq!(ban_log.target_user_id, Some(user.user_id));
+    assert_eq!(ban_log.target_username, Some(user.username.clone()));
+    assert_eq!(ban_log.post_id, user_ban.post_id);
+    assert_eq!(ban_log.comment_id, None);
+
+    let post_log = &mod_log_vec[3];
+    assert_eq!(post_log.action, ModAction::ModeratePost);
+    assert_eq!(post_log.actor_id, lead.user_id);
+    assert_eq!(post_log.actor_name, lead.username);
+    assert_eq!(post_log.target_user_id, Some(user.user_id));
+    assert_eq!(post_log.post_id, Some(moderated_post.post_id));
+    assert_eq!(post_log.comment_id, None);
+
+    // pagination
+    let paginated_log_vec = get_mod_log(&sphere.sphere_name, 2, 1, &lead, &db_pool).await?;
+    assert_eq!(paginated_log_vec, mod_log_vec[1..3].to_vec());
+    // out of range pagination values are clamped
+    assert_eq!(get_mod_log(&sphere.sphere_name, 0, 0, &lead, &db_pool).await?, mod_log_vec[..1].to_vec());
+    assert_eq!(get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT + 1, -1, &lead, &db_pool).await?, mod_log_vec);
+
+    // only moderators can read the log
+    assert_eq!(
+        get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges)
+    );
+    assert_eq!(get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &moderator, &db_pool).await?, mod_log_vec);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_moderate_post_and_ban_user() {
     let db_pool = get_db_pool().await;
@@ -128,9 +611,9 @@ async fn test_moderate_post_and_ban_user() {
     let user_ban = user_ban.expect("User should be banned");
     assert_eq!(user_ban.user_id, base_user.user_id);
     assert_eq!(user_ban.username, base_user.username);
-    assert_eq!(user_ban.post_id, post_2.post_id);
+    assert_eq!(user_ban.post_id, Some(post_2.post_id));
     assert_eq!(user_ban.comment_id, None);
-    assert_eq!(user_ban.infringed_rule_id, rule.rule_id);
+    assert_eq!(user_ban.infringed_rule_id, Some(rule.rule_id));
     assert_eq!(user_ban.moderator_id, user.user_id);
     assert!(user_ban.until_timestamp.is_some_and(|until| until > current_timestamp + chrono::Duration::days(1)));
 
@@ -181,6 +664,208 @@ async fn test_moderate_post() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_moderate_posts() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let mut other_lead = create_user("other", &db_pool).await;
+    let base_user = create_user("user", &db_pool).await;
+
+    let (sphere, post_1) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let (other_sphere, other_post) = create_sphere_with_post("other", &mut other_lead, &db_pool).await;
+    let post_2 = create_simple_post(&sphere.sphere_name, None, "spam 2", "body", None, &base_user, &db_pool).await.post;
+    let post_3 = create_simple_post(&sphere.sphere_name, None, "spam 3", "body", None, &base_user, &db_pool).await.post;
+    let rule = add_rule(&sphere.sphere_name, 0, "spam", "no spam", false, &lead, &db_pool).await?;
+
+    assert_eq!(moderate_posts(Vec::new(), rule.rule_id, "spam", &lead, &db_pool).await?, Vec::new());
+    assert_eq!(
+        moderate_posts(vec![post_2.post_id, post_3.post_id], rule.rule_id, "spam", &base_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+
+    // no post is moderated when one of them is in a sphere the user cannot moderate
+    assert_eq!(
+        moderate_posts(vec![post_2.post_id, other_post.post_id, post_3.post_id], rule.rule_id, "spam", &lead, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    // or when one of them doesn't exist
+    assert_eq!(
+        moderate_posts(vec![post_2.post_id, post_3.post_id + 1000], rule.rule_id, "spam", &lead, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+    for post_id in [post_2.post_id, post_3.post_id, other_post.post_id] {
+        assert_eq!(get_post_by_id(post_id, &db_pool).await?.moderator_id, None);
+    }
+    assert!(get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &lead, &db_pool).await?.is_empty());
+
+    let moderated_post_vec = moderate_posts(
+        vec![post_3.post_id, post_1.post_id, post_2.post_id, post_3.post_id],
+        rule.rule_id,
+        "spam",
+        &lead,
+        &db_pool,
+    ).await?;
+    assert_eq!(
+        moderated_post_vec.iter().map(|post| post.post_id).collect::<Vec<i64>>(),
+        vec![post_1.post_id, post_2.post_id, post_3.post_id],
+    );
+    for moderated_post in &moderated_post_vec {
+        assert_eq!(moderated_post.moderator_id, Some(lead.user_id));
+        assert_eq!(moderated_post.moderator_name, Some(lead.username.clone()));
+        assert_eq!(moderated_post.moderator_message, Some(String::from("spam")));
+        assert_eq!(moderated_post.infringed_rule_id, Some(rule.rule_id));
+        assert_eq!(moderated_post.infringed_rule_title, Some(rule.title.clone()));
+
+        let post = get_post_by_id(moderated_post.post_id, &db_pool).await?;
+        assert_eq!(post.moderator_id, Some(lead.user_id));
+        assert_eq!(post.infringed_rule_id, Some(rule.rule_id));
+    }
+    assert_eq!(get_post_by_id(other_post.post_id, &db_pool).await?.moderator_id, None);
+
+    let mod_log_vec = get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &lead, &db_pool).await?;
+    assert_eq!(mod_log_vec.len(), 3);
+    for mod_log_entry in &mod_log_vec {
+        assert_eq!(mod_log_entry.action, ModAction::ModeratePost);
+        assert_eq!(mod_log_entry.actor_id, lead.user_id);
+    }
+    let mut logged_post_id_vec: Vec<(Option<i64>, Option<i64>)> = mod_log_vec.iter()
+        .map(|mod_log_entry| (mod_log_entry.post_id, mod_log_entry.target_user_id))
+        .collect();
+    logged_post_id_vec.sort();
+    assert_eq!(
+        logged_post_id_vec,
+        vec![
+            (Some(post_1.post_id), Some(lead.user_id)),
+            (Some(post_2.post_id), Some(base_user.user_id)),
+            (Some(post_3.post_id), Some(base_user.user_id)),
+        ],
+    );
+
+    // a global moderator can moderate posts of several spheres at once
+    let mut global_moderator = create_user("mod", &db_pool).await;
+    global_moderator.admin_role = AdminRole::Moderator;
+    let moderated_post_vec = moderate_posts(vec![post_2.post_id, other_post.post_id], rule.rule_id, "global", &global_moderator, &db_pool).await?;
+    assert_eq!(moderated_post_vec.len(), 2);
+    assert!(moderated_post_vec.iter().all(|post| post.moderator_id == Some(global_moderator.user_id)));
+    assert_eq!(get_mod_log(&other_sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &other_lead, &db_pool).await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unmoderate_post() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let base_user = create_user("user", &db_pool).await;
+
+    let (sphere, post) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &lead, &db_pool).await?;
+
+    let get_sphere_post_id_vec = async || -> Result<Vec<i64>, AppError> {
+        Ok(get_post_vec_by_sphere_name(
+            &sphere.sphere_name,
+            SphereCategoryFilter::All,
+            SortType::Post(PostSortType::Recent),
+            10,
+            0,
+            None,
+            &db_pool,
+        ).await?.into_iter().map(|post| post.post_id).collect())
+    };
+
+    assert!(unmoderate_post(post.post_id, &lead, &db_pool).await.is_err());
+
+    let moderated_post = moderate_post(post.post_id, rule.rule_id, "moderated", &lead, &db_pool).await?;
+    assert!(get_sphere_post_id_vec().await?.is_empty());
+
+    assert_eq!(unmoderate_post(post.post_id, &base_user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.moderator_id, Some(lead.user_id));
+
+    let unmoderated_post = unmoderate_post(post.post_id, &lead, &db_pool).await?;
+    assert_eq!(unmoderated_post.post_id, post.post_id);
+    assert_eq!(unmoderated_post.moderator_id, None);
+    assert_eq!(unmoderated_post.moderator_name, None);
+    assert_eq!(unmoderated_post.moderator_message, None);
+    assert_eq!(unmoderated_post.infringed_rule_id, None);
+    assert_eq!(unmoderated_post.infringed_rule_title, None);
+    assert!(unmoderated_post.edit_timestamp > moderated_post.edit_timestamp);
+    assert!(unmoderated_post.is_active());
+    assert_eq!(get_sphere_post_id_vec().await?, vec![post.post_id]);
+
+    let mod_log_vec = get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &lead, &db_pool).await?;
+    assert_eq!(mod_log_vec.len(), 2);
+    assert_eq!(mod_log_vec[0].action, ModAction::UnmoderatePost);
+    assert_eq!(mod_log_vec[0].actor_id, lead.user_id);
+    assert_eq!(mod_log_vec[0].target_user_id, Some(post.creator_id));
+    assert_eq!(mod_log_vec[0].post_id, Some(post.post_id));
+    assert_eq!(mod_log_vec[0].comment_id, None);
+    assert_eq!(mod_log_vec[1].action, ModAction::ModeratePost);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unmoderate_comment() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let base_user = create_user("user", &db_pool).await;
+
+    let (sphere, post, comment) = create_sphere_with_post_and_comment("sphere", &mut lead, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &lead, &db_pool).await?;
+
+    assert!(unmoderate_comment(comment.comment_id, &lead, &db_pool).await.is_err());
+
+    let moderated_comment = moderate_comment(comment.comment_id, rule.rule_id, "moderated", &lead, &db_pool).await?;
+    assert!(!moderated_comment.is_active());
+
+    assert_eq!(unmoderate_comment(comment.comment_id, &base_user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(get_comment_by_id(comment.comment_id, &db_pool).await?.moderator_id, Some(lead.user_id));
+
+    let unmoderated_comment = unmoderate_comment(comment.comment_id, &lead, &db_pool).await?;
+    assert_eq!(unmoderated_comment.comment_id, comment.comment_id);
+    assert_eq!(unmoderated_comment.moderator_id, None);
+    assert_eq!(unmoderated_comment.moderator_name, None);
+    assert_eq!(unmoderated_comment.moderator_message, None);
+    assert_eq!(unmoderated_comment.infringed_rule_id, None);
+    assert_eq!(unmoderated_comment.body, comment.body);
+    assert!(unmoderated_comment.edit_timestamp > moderated_comment.edit_timestamp);
+    assert!(unmoderated_comment.is_active());
+
+    let mod_log_vec = get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &lead, &db_pool).await?;
+    assert_eq!(mod_log_vec.len(), 2);
+    assert_eq!(mod_log_vec[0].action, ModAction::UnmoderateComment);
+    assert_eq!(mod_log_vec[0].actor_id, lead.user_id);
+    assert_eq!(mod_log_vec[0].target_user_id, Some(comment.creator_id));
+    assert_eq!(mod_log_vec[0].post_id, Some(post.post_id));
+    assert_eq!(mod_log_vec[0].comment_id, Some(comment.comment_id));
+    assert_eq!(mod_log_vec[1].action, ModAction::ModerateComment);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_moderate_comment_num_comments() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+
+    let (sphere, post, comment) = create_sphere_with_post_and_comment("sphere", &mut lead, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &lead, &db_pool).await?;
+    create_comment(post.post_id, None, "other", None, false, &lead, &db_pool).await?;
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.num_comments, 2);
+
+    moderate_comment(comment.comment_id, rule.rule_id, "moderated", &lead, &db_pool).await?;
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.num_comments, 1);
+
+    // moderating an already moderated comment doesn't change the count
+    moderate_comment(comment.comment_id, rule.rule_id, "moderated again", &lead, &db_pool).await?;
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.num_comments, 1);
+
+    unmoderate_comment(comment.comment_id, &lead, &db_pool).await?;
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.num_comments, 2);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_moderate_comment_and_ban_user() {
     let db_pool = get_db_pool().await;
@@ -261,9 +946,9 @@ async fn test_moderate_comment_and_ban_user() {
     let user_ban = user_ban.expect("User should be banned");
     assert_eq!(user_ban.user_id, base_user.user_id);
     assert_eq!(user_ban.username, base_user.username);
-    assert_eq!(user_ban.post_id, comment_2.post_id);
+    assert_eq!(user_ban.post_id, Some(comment_2.post_id));
     assert_eq!(user_ban.comment_id, Some(comment_2.comment_id));
-    assert_eq!(user_ban.infringed_rule_id, rule.rule_id);
+    assert_eq!(user_ban.infringed_rule_id, Some(rule.rule_id));
     assert_eq!(user_ban.moderator_id, user.user_id);
     assert!(user_ban.until_timestamp.is_some_and(|until| until > current_timestamp + chrono::Duration::days(1)));
 
@@ -314,12 +999,131 @@ async fn test_moderate_comment() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_ban_user_temporarily() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let moderator = create_user("moderator", &db_pool).await;
+
+    let (sphere, _) = create_sphere_with_post("a", &mut lead, &db_pool).await;
+    set_user_sphere_role(&moderator.username, &sphere.sphere_name, PermissionLevel::Moderate, &lead, &db_pool).await?;
+    let moderator = User::get(moderator.user_id, &db_pool).await.expect("Should reload moderator");
+    let lead = User::get(lead.user_id, &db_pool).await.expect("Should reload lead");
+    let ban_duration = chrono::Duration::days(1);
+
+    // the ban permission is required, moderators and the user themselves cannot be banned
+    assert_eq!(
+        ban_user_temporarily(user.user_id, &sphere.sphere_name, ban_duration, "reason", &moderator, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(ban_user_temporarily(moderator.user_id, &sphere.sphere_name, ban_duration, "reason", &lead, &db_pool).await.is_err());
+    assert!(ban_user_temporarily(lead.user_id, &sphere.sphere_name, ban_duration, "reason", &lead, &db_pool).await.is_err());
+    // the duration must be positive and a reason must be given
+    assert!(ban_user_temporarily(user.user_id, &sphere.sphere_name, chrono::Duration::zero(), "reason", &lead, &db_pool).await.is_err());
+    assert!(ban_user_temporarily(user.user_id, &sphere.sphere_name, ban_duration, "", &lead, &db_pool).await.is_err());
+
+    let current_timestamp = chrono::Utc::now();
+    let user_ban = ban_user_temporarily(user.user_id, &sphere.sphere_name, ban_duration, "reason", &lead, &db_pool).await?;
+    assert_eq!(user_ban.user_id, user.user_id);
+    assert_eq!(user_ban.sphere_id, Some(sphere.sphere_id));
+    assert_eq!(user_ban.sphere_name, Some(sphere.sphere_name.clone()));
+    assert_eq!(user_ban.post_id, None);
+    assert_eq!(user_ban.comment_id, None);
+    assert_eq!(user_ban.infringed_rule_id, None);
+    assert_eq!(user_ban.reason.as_deref(), Some("reason"));
+    assert_eq!(user_ban.moderator_id, lead.user_id);
+    assert!(user_ban.until_timestamp.is_some_and(|until| until > current_timestamp && until <= chrono::Utc::now() + ban_duration));
+
+    let mod_log_vec = get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &lead, &db_pool).await?;
+    assert_eq!(mod_log_vec[0].action, ModAction::BanUser);
+    assert_eq!(mod_log_vec[0].target_user_id, Some(user.user_id));
+    assert_eq!(mod_log_vec[0].post_id, None);
+
+    // active ban blocks publishing
+    let banned_user = User::get(user.user_id, &db_pool).await.expect("Should reload user");
+    assert!(matches!(banned_user.check_can_publish_on_sphere(&sphere.sphere_name), Err(AppError::SphereBanUntil(_))));
+    assert!(matches!(
+        create_post(&sphere.sphere_name, None, "a", "b", None, Link::default(), PostTags::default(), &banned_user, &db_pool).await,
+        Err(AppError::SphereBanUntil(_))
+    ));
+
+    // expired ban is ignored
+    set_user_ban_until_timestamp(user_ban.ban_id, current_timestamp - chrono::Duration::hours(1), &db_pool).await?;
+    let unbanned_user = User::get(user.user_id, &db_pool).await.expect("Should reload user");
+    unbanned_user.check_can_publish_on_sphere(&sphere.sphere_name)?;
+    create_post(&sphere.sphere_name, None, "a", "b", None, Link::default(), PostTags::default(), &unbanned_user, &db_pool).await?;
+
+    // overly long bans are clamped to the maximum ban duration
+    let other_user = create_user("other", &db_pool).await;
+    let clamped_ban = ban_user_temporarily(other_user.user_id, &sphere.sphere_name, chrono::TimeDelta::MAX, "reason", &lead, &db_pool).await?;
+    assert!(clamped_ban.until_timestamp.is_some_and(|until| until <= chrono::Utc::now() + chrono::TimeDelta::hours(MAX_BAN_DURATION_HOURS as i64)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mute_user() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    let (sphere, post) = create_sphere_with_post("a", &mut lead, &db_pool).await;
+    let (other_sphere, other_post) = create_sphere_with_post("b", &mut lead, &db_pool).await;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("Should reload lead");
+
+    // only moderators can mute, moderators and the user themselves cannot be muted
+    assert_eq!(mute_user(other_user.user_id, &sphere.sphere_name, &user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert!(mute_user(lead.user_id, &sphere.sphere_name, &lead, &db_pool).await.is_err());
+
+    let user_mute = mute_user(user.user_id, &sphere.sphere_name, &lead, &db_pool).await?;
+    assert_eq!(user_mute.user_id, user.user_id);
+    assert_eq!(user_mute.username, user.username);
+    assert_eq!(user_mute.sphere_id, sphere.sphere_id);
+    assert_eq!(user_mute.sphere_name, sphere.sphere_name);
+    assert_eq!(user_mute.moderator_id, lead.user_id);
+    assert_eq!(user_mute.delete_timestamp, None);
+    assert!(mute_user(user.user_id, &sphere.sphere_name, &lead, &db_pool).await.is_err());
+
+    let mod_log_vec = get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &lead, &db_pool).await?;
+    assert_eq!(mod_log_vec[0].action, ModAction::MuteUser);
+    assert_eq!(mod_log_vec[0].target_user_id, Some(user.user_id));
+
+    // muted users cannot comment but can still post
+    assert_eq!(
+        create_comment(post.post_id, None, "a", None, false, &user, &db_pool).await,
+        Err(AppError::SphereMute),
+    );
+    create_post(&sphere.sphere_name, None, "a", "b", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+    // mutes are limited to their sphere
+    create_comment(other_post.post_id, None, "a", None, false, &user, &db_pool).await?;
+    create_comment(post.post_id, None, "a", None, false, &other_user, &db_pool).await?;
+
+    assert_eq!(unmute_user(user.user_id, &sphere.sphere_name, &user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(unmute_user(user.user_id, &other_sphere.sphere_name, &lead, &db_pool).await, Err(AppError::NotFound));
+    unmute_user(user.user_id, &sphere.sphere_name, &lead, &db_pool).await?;
+    assert_eq!(unmute_user(user.user_id, &sphere.sphere_name, &lead, &db_pool).await, Err(AppError::NotFound));
+
+    let mod_log_vec = get_mod_log(&sphere.sphere_name, MOD_LOG_FETCH_LIMIT, 0, &lead, &db_pool).await?;
+    assert_eq!(mod_log_vec[0].action, ModAction::UnmuteUser);
+    assert_eq!(mod_log_vec[0].target_user_id, Some(user.user_id));
+
+    create_comment(post.post_id, None, "a", None, false, &user, &db_pool).await?;
+    create_post(&sphere.sphere_name, None, "c", "d", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+
+    // user can be muted again after being unmuted
+    mute_user(user.user_id, &sphere.sphere_name, &lead, &db_pool).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_ban_user_from_sphere() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
     let mut user = create_user("test", &db_pool).await;
     let mut global_moderator = create_user("mod", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
     // set user role in the DB, needed to test that global Moderators/Admin cannot be banned
     global_moderator.admin_role = AdminRole::Moderator;
     admin.admin_role = AdminRole::Admin;
@@ -400,4 +1204,189 @@ async fn test_ban_user_from_sphere() -> Result<(), AppError> {
     );
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_get_controversial_content() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let (sphere, post_1) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let (_, other_sphere_post) = create_sphere_with_post("other", &mut lead, &db_pool).await;
+
+    let post_2 = create_simple_post(&sphere.sphere_name, None, "post 2", "body", None, &lead, &db_pool).await.post;
+    let old_post = create_simple_post(&sphere.sphere_name, None, "old post", "body", None, &lead, &db_pool).await.post;
+    let comment_1 = create_comment(post_1.post_id, None, "comment 1", None, false, &lead, &db_pool).await?;
+    let comment_2 = create_comment(post_1.post_id, Some(comment_1.comment_id), "comment 2", None, false, &lead, &db_pool).await?;
+    let old_comment = create_comment(post_2.post_id, None, "old comment", None, false, &lead, &db_pool).await?;
+
+    set_post_vote_counts(post_1.post_id, 1, 3, &db_pool).await?;
+    set_post_vote_counts(post_2.post_id, 5, 0, &db_pool).await?;
+    set_post_vote_counts(old_post.post_id, 0, 10, &db_pool).await?;
+    set_post_timestamp(old_post.post_id, -10, &db_pool).await?;
+    set_post_vote_counts(other_sphere_post.post_id, 0, 20, &db_pool).await?;
+    set_comment_vote_counts(comment_1.comment_id, 2, 5, &db_pool).await?;
+    set_comment_vote_counts(comment_2.comment_id, 0, 1, &db_pool).await?;
+    set_comment_vote_counts(old_comment.comment_id, 0, 8, &db_pool).await?;
+    set_comment_timestamp(old_comment.comment_id, -10, &db_pool).await?;
+
+    let get_content_ids = |content_vec: Vec<Content>| content_vec.into_iter().map(|content| match content {
+        Content::Post(post) => (post.post_id, None),
+        Content::Comment(comment) => (comment.post_id, Some(comment.comment_id)),
+    }).collect::<Vec<(i64, Option<i64>)>>();
+
+    let week = Duration::from_hours(24 * 7);
+    assert_eq!(
+        get_controversial_content(&sphere.sphere_name, week, 10, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+
+    let content_vec = get_controversial_content(&sphere.sphere_name, week, 10, &lead, &db_pool).await?;
+    assert_eq!(content_vec.iter().map(Content::downvotes).collect::<Vec<i32>>(), vec![5, 3, 1]);
+    assert_eq!(
+        get_content_ids(content_vec),
+        vec![
+            (post_1.post_id, Some(comment_1.comment_id)),
+            (post_1.post_id, None),
+            (post_1.post_id, Some(comment_2.comment_id)),
+        ],
+    );
+
+    let content_vec = get_controversial_content(&sphere.sphere_name, week, 2, &lead, &db_pool).await?;
+    assert_eq!(
+        get_content_ids(content_vec),
+        vec![(post_1.post_id, Some(comment_1.comment_id)), (post_1.post_id, None)],
+    );
+
+    let content_vec = get_controversial_content(&sphere.sphere_name, Duration::from_hours(24 * 30), 10, &lead, &db_pool).await?;
+    assert_eq!(
+        get_content_ids(content_vec),
+        vec![
+            (old_post.post_id, None),
+            (post_2.post_id, Some(old_comment.comment_id)),
+            (post_1.post_id, Some(comment_1.comment_id)),
+            (post_1.post_id, None),
+            (post_1.post_id, Some(comment_2.comment_id)),
+        ],
+    );
+
+    // moderated content is not returned anymore
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &lead, &db_pool).await?;
+    moderate_comment(comment_1.comment_id, rule.rule_id, "moderated", &lead, &db_pool).await?;
+    let content_vec = get_controversial_content(&sphere.sphere_name, week, 10, &lead, &db_pool).await?;
+    assert_eq!(
+        get_content_ids(content_vec),
+        vec![(post_1.post_id, None), (post_1.post_id, Some(comment_2.comment_id))],
+    );
+
+    // the time window and the limit are clamped
+    let content_vec = get_controversial_content(&sphere.sphere_name, Duration::MAX, i64::MAX, &lead, &db_pool).await?;
+    assert_eq!(content_vec.len(), 4);
+    assert!(get_controversial_content(&sphere.sphere_name, week, -1, &lead, &db_pool).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mod_reason_template_crud() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let mut other_lead = create_user("other", &db_pool).await;
+    let base_user = create_user("user", &db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let (other_sphere, _) = create_sphere_with_post("other", &mut other_lead, &db_pool).await;
+
+    assert_eq!(
+        create_mod_reason_template(&sphere.sphere_name, "Spam", "Spam is not allowed.", &base_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(create_mod_reason_template(&sphere.sphere_name, "", "Spam is not allowed.", &lead, &db_pool).await.is_err());
+    assert!(create_mod_reason_template(&sphere.sphere_name, "Spam", "", &lead, &db_pool).await.is_err());
+
+    let spam_template = create_mod_reason_template(&sphere.sphere_name, "Spam", "Spam is not allowed.", &lead, &db_pool).await?;
+    assert_eq!(spam_template.sphere_id, sphere.sphere_id);
+    assert_eq!(spam_template.title, "Spam");
+    assert_eq!(spam_template.reason, "Spam is not allowed.");
+    assert_eq!(spam_template.creator_id, lead.user_id);
+    assert_eq!(spam_template.edit_timestamp, None);
+    assert!(create_mod_reason_template(&sphere.sphere_name, "Spam", "Duplicate title.", &lead, &db_pool).await.is_err());
+    let abuse_template = create_mod_reason_template(&sphere.sphere_name, "Abuse", "Be kind.", &lead, &db_pool).await?;
+    let other_template = create_mod_reason_template(&other_sphere.sphere_name, "Spam", "No spam here.", &other_lead, &db_pool).await?;
+
+    assert_eq!(get_mod_reason_template_vec(&sphere.sphere_name, &base_user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(get_mod_reason_template_vec(&sphere.sphere_name, &lead, &db_pool).await?, vec![abuse_template.clone(), spam_template.clone()]);
+    assert_eq!(get_mod_reason_template_vec(&other_sphere.sphere_name, &other_lead, &db_pool).await?, vec![other_template.clone()]);
+
+    assert_eq!(
+        update_mod_reason_template(spam_template.template_id, "Spam", "Updated", &base_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        update_mod_reason_template(other_template.template_id, "Spam", "Updated", &lead, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    let updated_template = update_mod_reason_template(spam_template.template_id, "Spam and ads", "No spam or ads.", &lead, &db_pool).await?;
+    assert_eq!(updated_template.template_id, spam_template.template_id);
+    assert_eq!(updated_template.title, "Spam and ads");
+    assert_eq!(updated_template.reason, "No spam or ads.");
+    assert_eq!(updated_template.create_timestamp, spam_template.create_timestamp);
+    assert!(updated_template.edit_timestamp.is_some());
+
+    assert_eq!(delete_mod_reason_template(abuse_template.template_id, &base_user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(delete_mod_reason_template(other_template.template_id, &lead, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    delete_mod_reason_template(abuse_template.template_id, &lead, &db_pool).await?;
+    assert_eq!(get_mod_reason_template_vec(&sphere.sphere_name, &lead, &db_pool).await?, vec![updated_template]);
+    assert_eq!(delete_mod_reason_template(abuse_template.template_id, &lead, &db_pool).await, Err(AppError::NotFound));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_moderate_post_with_template() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("lead", &db_pool).await;
+    let mut other_lead = create_user("other", &db_pool).await;
+    let base_user = create_user("user", &db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let post = create_simple_post(&sphere.sphere_name, None, "spam", "body", None, &base_user, &db_pool).await.post;
+    let (other_sphere, _) = create_sphere_with_post("other", &mut other_lead, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &lead, &db_pool).await?;
+
+    let template = create_mod_reason_template(&sphere.sphere_name, "Spam", "Spam is not allowed.", &lead, &db_pool).await?;
+    let other_template = create_mod_reason_template(&other_sphere.sphere_name, "Spam", "No spam here.", &other_lead, &db_pool).await?;
+
+    assert_eq!(
+        moderate_post_with_template(post.post_id, rule.rule_id, template.template_id, Some(0), &base_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    // permissions are checked before the template, to avoid leaking which templates exist
+    assert_eq!(
+        moderate_post_with_template(post.post_id, rule.rule_id, other_template.template_id, Some(0), &base_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        moderate_post_with_template(post.post_id, rule.rule_id, -1, Some(0), &base_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        moderate_post_with_template(post.post_id, rule.rule_id, other_template.template_id, Some(0), &lead, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.moderator_id, None);
+
+    let (moderated_post, user_ban, _) = moderate_post_with_template(
+        post.post_id,
+        rule.rule_id,
+        template.template_id,
+        Some(0),
+        &lead,
+        &db_pool,
+    ).await?;
+    assert_eq!(moderated_post.moderator_id, Some(lead.user_id));
+    assert_eq!(moderated_post.moderator_message, Some(template.reason.clone()));
+    assert_eq!(moderated_post.infringed_rule_id, Some(rule.rule_id));
+    assert_eq!(user_ban, None);
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.moderator_message, Some(template.reason));
+
+    Ok(())
+}
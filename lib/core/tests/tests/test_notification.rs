@@ -1,5 +1,5 @@
-use sphare_core_content::comment::ssr::create_comment;
-use sphare_core_user::notification::ssr::{create_notification, delete_stale_notifications, get_notifications, set_all_notifications_read, set_notification_read};
+use sphare_core_content::comment::ssr::{create_comment, create_comment_with_notif};
+use sphare_core_user::notification::ssr::{create_mention_notifications, create_notification, create_reply_notification, delete_stale_notifications, get_notifications, set_all_notifications_read, set_notification_read};
 use sphare_core_user::notification::{NotificationType, NOTIF_RETENTION_DAYS};
 
 use crate::common::*;
@@ -160,6 +160,177 @@ async fn test_create_notification() {
     assert_eq!(user_2_notif_vec.len(), 2);
 }
 
+#[tokio::test]
+async fn test_create_reply_notification() {
+    let db_pool = get_db_pool().await;
+    let mut user_1 = create_test_user(&db_pool).await;
+    let user_2 = create_user("trigger", &db_pool).await;
+
+    let (_, post) = create_sphere_with_post("sphere", &mut user_1, &db_pool).await;
+
+    let comment = create_comment(
+        post.post_id,
+        None,
+        "a",
+        None,
+        false,
+        &user_2,
+        &db_pool
+    ).await.expect("Should create comment");
+
+    let post_reply_notif = create_reply_notification(
+        post.post_id,
+        comment.parent_id,
+        comment.comment_id,
+        user_2.user_id,
+        &db_pool
+    )
+        .await
+        .expect("Should create post reply notification")
+        .expect("Should have notification");
+
+    assert_eq!(post_reply_notif.post_id, post.post_id);
+    assert_eq!(post_reply_notif.comment_id, Some(comment.comment_id));
+    assert_eq!(post_reply_notif.user_id, user_1.user_id);
+    assert_eq!(post_reply_notif.trigger_user_id, user_2.user_id);
+    assert_eq!(post_reply_notif.notification_type, NotificationType::PostReply);
+
+    let nested_comment = create_comment(
+        post.post_id,
+        Some(comment.comment_id),
+        "b",
+        None,
+        false,
+        &user_1,
+        &db_pool
+    ).await.expect("Should create nested comment");
+
+    let comment_reply_notif = create_reply_notification(
+        post.post_id,
+        nested_comment.parent_id,
+        nested_comment.comment_id,
+        user_1.user_id,
+        &db_pool
+    )
+        .await
+        .expect("Should create comment reply notification")
+        .expect("Should have notification");
+
+    assert_eq!(comment_reply_notif.post_id, post.post_id);
+    assert_eq!(comment_reply_notif.comment_id, Some(nested_comment.comment_id));
+    assert_eq!(comment_reply_notif.user_id, user_2.user_id);
+    assert_eq!(comment_reply_notif.trigger_user_id, user_1.user_id);
+    assert_eq!(comment_reply_notif.notification_type, NotificationType::CommentReply);
+
+    // Self-replies on post and comment don't create notifications
+    let self_post_reply = create_comment(
+        post.post_id,
+        None,
+        "c",
+        None,
+        false,
+        &user_1,
+        &db_pool
+    ).await.expect("Should create self post reply");
+    let self_post_reply_notif = create_reply_notification(
+        post.post_id,
+        self_post_reply.parent_id,
+        self_post_reply.comment_id,
+        user_1.user_id,
+        &db_pool
+    ).await.expect("Should not create notification and return Ok(None)");
+    assert_eq!(self_post_reply_notif, None);
+
+    let self_comment_reply = create_comment(
+        post.post_id,
+        Some(comment.comment_id),
+        "d",
+        None,
+        false,
+        &user_2,
+        &db_pool
+    ).await.expect("Should create self comment reply");
+    let self_comment_reply_notif = create_reply_notification(
+        post.post_id,
+        self_comment_reply.parent_id,
+        self_comment_reply.comment_id,
+        user_2.user_id,
+        &db_pool
+    ).await.expect("Should not create notification and return Ok(None)");
+    assert_eq!(self_comment_reply_notif, None);
+
+    let user_1_notif_vec = get_notifications(user_1.user_id, &db_pool).await.expect("Should get user 1 notification vec");
+    let user_2_notif_vec = get_notifications(user_2.user_id, &db_pool).await.expect("Should get user 2 notification vec");
+    assert_eq!(user_1_notif_vec, vec![post_reply_notif]);
+    assert_eq!(user_2_notif_vec, vec![comment_reply_notif]);
+}
+
+#[tokio::test]
+async fn test_create_mention_notifications() {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let user_1 = create_user("user_1", &db_pool).await;
+    let user_2 = create_user("user_2", &db_pool).await;
+
+    let (sphere, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+
+    let empty_notif_vec = create_mention_notifications(
+        post.post_id,
+        None,
+        &[],
+        user.user_id,
+        &db_pool
+    ).await.expect("Should handle empty mention vec");
+    assert!(empty_notif_vec.is_empty());
+
+    let username_vec = vec![
+        user_1.username.clone(),
+        String::from("unknown"),
+        user.username.clone(),
+        user_2.username.clone(),
+    ];
+    let post_mention_notif_vec = create_mention_notifications(
+        post.post_id,
+        None,
+        &username_vec,
+        user.user_id,
+        &db_pool
+    ).await.expect("Should create post mention notifications");
+
+    assert_eq!(post_mention_notif_vec.len(), 2);
+    for (notif, mentioned_user) in post_mention_notif_vec.iter().zip([&user_1, &user_2]) {
+        assert_eq!(notif.sphere_id, sphere.sphere_id);
+        assert_eq!(notif.post_id, post.post_id);
+        assert_eq!(notif.comment_id, None);
+        assert_eq!(notif.user_id, mentioned_user.user_id);
+        assert_eq!(notif.trigger_user_id, user.user_id);
+        assert_eq!(notif.trigger_username, user.username);
+        assert_eq!(notif.notification_type, NotificationType::Mention);
+    }
+
+    // Mentions in comments are deduplicated and the comment author is not notified
+    let comment = create_comment_with_notif(
+        post.post_id,
+        None,
+        &format!("@{} @{} hello @{} @unknown", user_2.username, user_1.username, user_2.username),
+        true,
+        false,
+        &user_1,
+        &db_pool
+    ).await.expect("Should create comment with mentions").comment;
+
+    let user_1_notif_vec = get_notifications(user_1.user_id, &db_pool).await.expect("Should get user 1 notification vec");
+    let user_2_notif_vec = get_notifications(user_2.user_id, &db_pool).await.expect("Should get user 2 notification vec");
+    assert_eq!(user_1_notif_vec.len(), 1);
+    assert_eq!(user_2_notif_vec.len(), 2);
+
+    let comment_mention_notif = user_2_notif_vec.first().expect("Should have comment mention notification");
+    assert_eq!(comment_mention_notif.post_id, post.post_id);
+    assert_eq!(comment_mention_notif.comment_id, Some(comment.comment_id));
+    assert_eq!(comment_mention_notif.trigger_user_id, user_1.user_id);
+    assert_eq!(comment_mention_notif.notification_type, NotificationType::Mention);
+}
+
 #[tokio::test]
 async fn test_get_notifications() {
     let db_pool = get_db_pool().await;
@@ -298,11 +469,35 @@ async fn test_delete_stale_notifications() {
         .expect("Should create comment comment notification")
         .expect("Should have notification");
 
+    let notif_3 = create_notification(
+        comment.post_id,
+        Some(comment.comment_id),
+        Some(comment.comment_id),
+        trigger_user.user_id,
+        NotificationType::Moderation,
+        &db_pool
+    )
+        .await
+        .expect("Should create comment moderation notification")
+        .expect("Should have notification");
+
     update_notification_timestamp(notif_2.notification_id, (NOTIF_RETENTION_DAYS + 1) as f64, &db_pool).await.expect("Should update notification timestamp");
+    update_notification_timestamp(notif_3.notification_id, 5.5, &db_pool).await.expect("Should update notification timestamp");
 
-    delete_stale_notifications(&db_pool).await.expect("Should delete stale notifications");
+    delete_stale_notifications(NOTIF_RETENTION_DAYS, &db_pool).await.expect("Should delete stale notifications");
 
     let notif_vec = get_notifications(user.user_id, &db_pool).await.expect("Should get notification vec");
+    assert_eq!(notif_vec.len(), 2);
     assert_eq!(notif_vec.contains(&notif_1), true);
     assert_eq!(notif_vec.contains(&notif_2), false);
-}
\ No newline at end of file
+    assert!(notif_vec.iter().any(|notif| notif.notification_id == notif_3.notification_id));
+
+    // Custom retention threshold
+    delete_stale_notifications(6, &db_pool).await.expect("Should delete stale notifications");
+    let notif_vec = get_notifications(user.user_id, &db_pool).await.expect("Should get notification vec");
+    assert_eq!(notif_vec.len(), 2);
+
+    delete_stale_notifications(5, &db_pool).await.expect("Should delete stale notifications");
+    let notif_vec = get_notifications(user.user_id, &db_pool).await.expect("Should get notification vec");
+    assert_eq!(notif_vec, vec![notif_1]);
+}
@@ -0,0 +1,182 @@
+use sphare_core_common::constants::MAX_POLL_OPTIONS;
+use sphare_core_common::errors::AppError;
+use sphare_core_content::poll::ssr::{create_poll, get_poll_by_post_id, vote_poll, POLL_ALREADY_VOTED_STR, POLL_CLOSED_STR};
+use sphare_core_content::post::ssr::get_post_with_info_by_id;
+use sphare_core_content::ranking::VoteValue;
+use sphare_core_sphere::sphere::ssr::create_sphere;
+use sphare_core_user::user::{BanStatus, User};
+
+use crate::common::{create_user, get_db_pool};
+
+mod common;
+mod data_factory;
+
+fn get_option_vec(options: &[&str]) -> Vec<String> {
+    options.iter().map(|option| option.to_string()).collect()
+}
+
+#[tokio::test]
+async fn test_create_poll() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("test", &db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user.");
+    let ends_at = chrono::Utc::now() + chrono::TimeDelta::days(1);
+
+    let (post, poll) = create_poll(&sphere.sphere_name, "poll", &get_option_vec(&["a", " b "]), ends_at, &user, &db_pool).await?;
+    assert_eq!(post.title, "poll");
+    assert_eq!(post.sphere_id, sphere.sphere_id);
+    assert_eq!(poll.post_id, post.post_id);
+    assert!(!poll.is_closed());
+    // the creator upvotes the poll post, as for other posts
+    let post_with_info = get_post_with_info_by_id(post.post_id, Some(&user), &db_pool).await?;
+    assert_eq!(post_with_info.vote.map(|vote| vote.value), Some(VoteValue::Up));
+    assert_eq!(post_with_info.post.score, 1);
+
+    let poll_with_results = get_poll_by_post_id(post.post_id, Some(user.user_id), &db_pool).await?.expect("Poll should exist.");
+    assert_eq!(poll_with_results.poll, poll);
+    assert_eq!(
+        poll_with_results.option_vec.iter().map(|option| (option.option_text.as_str(), option.position, option.num_votes)).collect::<Vec<_>>(),
+        vec![("a", 0, 0), ("b", 1, 0)],
+    );
+    assert_eq!(poll_with_results.user_option_id, None);
+    assert!(!poll_with_results.show_results());
+
+    let too_many_options: Vec<String> = (0..=MAX_POLL_OPTIONS).map(|i| i.to_string()).collect();
+    assert!(create_poll(&sphere.sphere_name, "poll", &get_option_vec(&["a"]), ends_at, &user, &db_pool).await.is_err());
+    assert!(create_poll(&sphere.sphere_name, "poll", &too_many_options, ends_at, &user, &db_pool).await.is_err());
+    assert!(create_poll(&sphere.sphere_name, "poll", &get_option_vec(&["a", "a"]), ends_at, &user, &db_pool).await.is_err());
+    assert!(create_poll(&sphere.sphere_name, "poll", &get_option_vec(&["a", ""]), ends_at, &user, &db_pool).await.is_err());
+    assert!(create_poll(
+        &sphere.sphere_name,
+        "poll",
+        &get_option_vec(&["a", "b"]),
+        chrono::Utc::now() - chrono::TimeDelta::hours(1),
+        &user,
+        &db_pool,
+    ).await.is_err());
+    assert!(create_poll("missing", "poll", &get_option_vec(&["a", "b"]), ends_at, &user, &db_pool).await.is_err());
+
+    // failed polls do not leave a post behind
+    let post_count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM posts WHERE sphere_id = $1")
+        .bind(sphere.sphere_id)
+        .fetch_one(&db_pool)
+        .await?;
+    assert_eq!(post_count, 1);
+
+    let (_, text_post) = data_factory::create_sphere_with_post("other", &mut user.clone(), &db_pool).await;
+    assert_eq!(get_poll_by_post_id(text_post.post_id, Some(user.user_id), &db_pool).await?, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vote_poll_single_vote() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("test", &db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user.");
+    let ends_at = chrono::Utc::now() + chrono::TimeDelta::days(1);
+
+    let (post, poll) = create_poll(&sphere.sphere_name, "poll", &get_option_vec(&["a", "b"]), ends_at, &user, &db_pool).await?;
+    let (_, other_poll) = create_poll(&sphere.sphere_name, "other", &get_option_vec(&["c", "d"]), ends_at, &user, &db_pool).await?;
+    let option_vec = get_poll_by_post_id(post.post_id, None, &db_pool).await?.expect("Poll should exist.").option_vec;
+    let other_option_vec = get_poll_by_post_id(other_poll.post_id, None, &db_pool).await?.expect("Poll should exist.").option_vec;
+
+    assert_eq!(
+        vote_poll(poll.poll_id, other_option_vec[0].option_id, &user, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+
+    let poll_with_results = vote_poll(poll.poll_id, option_vec[0].option_id, &user, &db_pool).await?;
+    assert_eq!(poll_with_results.user_option_id, Some(option_vec[0].option_id));
+    assert!(poll_with_results.show_results());
+
+    assert_eq!(
+        vote_poll(poll.poll_id, option_vec[0].option_id, &user, &db_pool).await,
+        Err(AppError::new(POLL_ALREADY_VOTED_STR)),
+    );
+    assert_eq!(
+        vote_poll(poll.poll_id, option_vec[1].option_id, &user, &db_pool).await,
+        Err(AppError::new(POLL_ALREADY_VOTED_STR)),
+    );
+
+    let poll_with_results = get_poll_by_post_id(post.post_id, Some(user.user_id), &db_pool).await?.expect("Poll should exist.");
+    assert_eq!(poll_with_results.user_option_id, Some(option_vec[0].option_id));
+    assert_eq!(poll_with_results.total_votes(), 1);
+
+    // voting in a poll does not count as a vote in another one
+    vote_poll(other_poll.poll_id, other_option_vec[1].option_id, &user, &db_pool).await?;
+
+    let mut banned_user = create_user("banned", &db_pool).await;
+    banned_user.ban_status = BanStatus::Permanent;
+    assert!(vote_poll(poll.poll_id, option_vec[1].option_id, &banned_user, &db_pool).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vote_poll_closed() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("test", &db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user.");
+    let ends_at = chrono::Utc::now() + chrono::TimeDelta::days(1);
+
+    let (post, poll) = create_poll(&sphere.sphere_name, "poll", &get_option_vec(&["a", "b"]), ends_at, &user, &db_pool).await?;
+    let option_vec = get_poll_by_post_id(post.post_id, None, &db_pool).await?.expect("Poll should exist.").option_vec;
+
+    vote_poll(poll.poll_id, option_vec[0].option_id, &user, &db_pool).await?;
+
+    sqlx::query("UPDATE polls SET ends_at = NOW() - INTERVAL '1 minute' WHERE poll_id = $1")
+        .bind(poll.poll_id)
+        .execute(&db_pool)
+        .await?;
+
+    assert_eq!(
+        vote_poll(poll.poll_id, option_vec[1].option_id, &other_user, &db_pool).await,
+        Err(AppError::new(POLL_CLOSED_STR)),
+    );
+
+    let poll_with_results = get_poll_by_post_id(post.post_id, Some(other_user.user_id), &db_pool).await?.expect("Poll should exist.");
+    assert!(poll_with_results.poll.is_closed());
+    assert_eq!(poll_with_results.user_option_id, None);
+    assert!(poll_with_results.show_results());
+    assert_eq!(poll_with_results.total_votes(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_poll_results() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user_1 = create_user("1", &db_pool).await;
+    let user_2 = create_user("2", &db_pool).await;
+    let user_3 = create_user("3", &db_pool).await;
+    let user_4 = create_user("4", &db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &user_1, &db_pool).await?;
+    let user_1 = User::get(user_1.user_id, &db_pool).await.expect("Should reload user.");
+    let ends_at = chrono::Utc::now() + chrono::TimeDelta::days(1);
+
+    let (post, poll) = create_poll(&sphere.sphere_name, "poll", &get_option_vec(&["a", "b", "c"]), ends_at, &user_1, &db_pool).await?;
+    let option_vec = get_poll_by_post_id(post.post_id, None, &db_pool).await?.expect("Poll should exist.").option_vec;
+
+    vote_poll(poll.poll_id, option_vec[1].option_id, &user_1, &db_pool).await?;
+    vote_poll(poll.poll_id, option_vec[1].option_id, &user_2, &db_pool).await?;
+    vote_poll(poll.poll_id, option_vec[0].option_id, &user_3, &db_pool).await?;
+    let poll_with_results = vote_poll(poll.poll_id, option_vec[1].option_id, &user_4, &db_pool).await?;
+
+    assert_eq!(
+        poll_with_results.option_vec.iter().map(|option| (option.option_id, option.num_votes)).collect::<Vec<_>>(),
+        vec![(option_vec[0].option_id, 1), (option_vec[1].option_id, 3), (option_vec[2].option_id, 0)],
+    );
+    assert_eq!(poll_with_results.total_votes(), 4);
+    assert_eq!(poll_with_results.user_option_id, Some(option_vec[1].option_id));
+
+    let poll_with_results = get_poll_by_post_id(post.post_id, Some(user_3.user_id), &db_pool).await?.expect("Poll should exist.");
+    assert_eq!(poll_with_results.user_option_id, Some(option_vec[0].option_id));
+    assert_eq!(poll_with_results.total_votes(), 4);
+
+    Ok(())
+}
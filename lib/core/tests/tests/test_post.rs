@@ -1,33 +1,41 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
+use futures_util::StreamExt;
 use rand::RngExt;
 use sqlx::PgPool;
 
 use sphare_core_common::colors::Color;
+use sphare_core_common::constants::{BEST_SORT_MIN_VOTE_COUNT, COMMENT_BATCH_SIZE, IMAGE_FILE_PARAM, MAX_ALT_TEXT_LENGTH, MAX_IDEMPOTENCY_KEY_LENGTH, MAX_MIN_ACCOUNT_AGE_DAYS, MAX_POST_IMAGES, MAX_TITLE_LENGTH, POST_ID_PARAM, POST_SCORE_UPDATE_BATCH_SIZE, TRENDING_SPHERE_DAYS};
 use sphare_core_common::editor::get_styled_html_from_markdown;
 use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
 use sphare_core_common::errors::AppError;
 use sphare_core_common::routes::get_post_path;
-use sphare_core_content::comment::ssr::create_comment;
-use sphare_core_content::embed::{Link, LinkType};
+use sphare_core_content::comment::ssr::{create_comment, delete_comment, get_post_comment_tree};
+use sphare_core_content::embed::{EmbedType, Link, LinkType};
+use object_store::memory::InMemory;
+use object_store::{ObjectStore, ObjectStoreExt};
 use sphare_core_content::filter::{CategorySetFilter, SphereCategoryFilter};
-use sphare_core_content::moderation::ssr::moderate_post;
-use sphare_core_content::post::ssr::{create_post, create_post_and_vote, delete_post, edit_post, get_homepage_post_vec, get_post_by_id, get_post_inherited_attributes, get_post_vec_by_satellite_id, get_post_vec_by_sphere_name, get_post_with_info_by_id, get_sorted_post_vec, get_subscribed_post_vec, update_post, update_post_scores};
-use sphare_core_content::post::{PostDataInputs, PostLocation, PostTags, PostWithSphereInfo};
+use sphare_core_content::moderation::ssr::{ban_user_from_sphere, moderate_comment, moderate_post};
+use sphare_core_content::post::ssr::{add_post_images, create_post, create_post_and_vote, create_scheduled_post, create_scheduled_post_and_vote, crosspost, delete_draft, delete_post, get_duplicate_link_post_id, load_draft, save_draft, find_recent_post_with_link, DUPLICATE_LINK_WINDOW, PENDING_POST_IDEMPOTENCY_WINDOW, POST_IMAGE_FILE_INFER_ERROR_STR, POST_IDEMPOTENCY_WINDOW, get_post_image_vec, insert_post_images, edit_post, NewPost, PostImageInput, get_home_feed, get_homepage_post_vec, get_post_by_id, get_post_inherited_attributes, get_post_vec_by_satellite_id, get_post_vec_by_sphere_name, get_post_with_info_by_id, get_similar_posts, get_sorted_post_vec, get_subscribed_post_vec, publish_scheduled_posts, recompute_post_comment_counts, set_accepted_answer, set_pin_order, update_post, update_post_scores};
+use sphare_core_content::post::{upvote_ratio, CrosspostHeader, PostDataInputs, PostLocation, PostTags, PostWithSphereInfo};
 use sphare_core_content::ranking::ssr::vote_on_content;
-use sphare_core_content::ranking::{PostSortType, SortType, VoteValue};
-use sphare_core_sphere::rule::ssr::add_rule;
-use sphare_core_sphere::satellite::ssr::create_satellite;
+use sphare_core_content::ranking::{CommentSortType, PostSortType, SortType, VoteValue};
+use sphare_core_sphere::rule::ssr::{accept_rules, add_rule};
+use sphare_core_sphere::satellite::ssr::{create_satellite, deactivate_satellite, subscribe_satellite};
 use sphare_core_sphere::satellite::Satellite;
-use sphare_core_sphere::sphere::ssr::{create_sphere, get_post_sphere, subscribe};
-use sphare_core_sphere::sphere::Sphere;
+use sphare_core_sphere::sphere::ssr::{confirm_nsfw, create_sphere, get_post_sphere, set_sphere_allowed_post_types, set_sphere_min_account_age_days, set_sphere_min_karma, set_sphere_reject_duplicate_links, set_sphere_require_category, set_sphere_require_post_approval, set_sphere_require_rule_acceptance, set_sphere_title_rules, subscribe};
+use sphare_core_sphere::sphere::{AllowedPostTypes, Sphere};
 use sphare_core_sphere::sphere_category::ssr::set_sphere_category;
-use sphare_core_user::user::User;
+use sphare_core_sphere::sphere_management::ssr::{add_trusted_user, remove_trusted_user};
+use sphare_core_user::notification::NotificationType;
+use sphare_core_user::notification::ssr::get_notifications;
+use sphare_core_user::user::{KarmaType, NsfwMode, User};
+use sphare_core_user::user::ssr::set_user_settings;
 
 use crate::common::*;
 use crate::data_factory::*;
-use crate::utils::{get_user_post_vote, sort_post_vec, test_post_score, POST_SORT_TYPE_ARRAY};
+use crate::utils::{get_invalid_multipart_image_with_string, get_multipart_images_with_string, get_user_post_vote, set_user_timestamp, sort_post_vec, test_post_score, POST_SORT_TYPE_ARRAY};
 
 mod common;
 mod data_factory;
@@ -484,6 +492,96 @@ async fn test_get_subscribed_post_vec() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_subscribed_post_vec_with_satellites() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let subscriber = create_user("subscriber", &db_pool).await;
+
+    let num_post = 3usize;
+    let (sphere, satellite) = create_sphere_with_satellite(
+        "1",
+        "a",
+        false,
+        false,
+        &mut user,
+        &db_pool,
+    ).await?;
+    let other_satellite = create_satellite(
+        &sphere.sphere_name,
+        "b",
+        "satellite",
+        false,
+        false,
+        false,
+        &user,
+        &db_pool,
+    ).await?;
+
+    let mut sphere_post_id_vec = Vec::new();
+    let mut satellite_post_id_vec = Vec::new();
+    for i in 0..num_post {
+        for (satellite_id, post_id_vec) in [(None, &mut sphere_post_id_vec), (Some(satellite.satellite_id), &mut satellite_post_id_vec)] {
+            let post = create_post(
+                &sphere.sphere_name,
+                satellite_id,
+                &format!("post {i}"),
+                "body",
+                None,
+                Link::default(),
+                PostTags::default(),
+                &user,
+                &db_pool,
+            ).await?;
+            post_id_vec.push(post.post_id);
+        }
+        create_post(
+            &sphere.sphere_name,
+            Some(other_satellite.satellite_id),
+            &format!("other satellite post {i}"),
+            "body",
+            None,
+            Link::default(),
+            PostTags::default(),
+            &user,
+            &db_pool,
+        ).await?;
+    }
+    sphere_post_id_vec.reverse();
+    satellite_post_id_vec.reverse();
+
+    let get_post_id_vec = |post_vec: Vec<PostWithSphereInfo>| post_vec.into_iter().map(|post| post.post.post_id).collect::<Vec<i64>>();
+
+    // Without satellite subscription, satellite posts are not included in the feed
+    subscribe(sphere.sphere_id, subscriber.user_id, &db_pool).await?;
+    let post_vec = get_subscribed_post_vec(SortType::Post(PostSortType::Recent), 100, 0, &subscriber, &db_pool).await?;
+    assert_eq!(get_post_id_vec(post_vec), sphere_post_id_vec);
+
+    // Posts of the followed satellite are included once, even though the parent sphere is also subscribed
+    subscribe_satellite(satellite.satellite_id, subscriber.user_id, &db_pool).await?;
+    let post_vec = get_subscribed_post_vec(SortType::Post(PostSortType::Recent), 100, 0, &subscriber, &db_pool).await?;
+    let satellite_post_vec: Vec<PostWithSphereInfo> = post_vec.iter().filter(|post| post.post.satellite_id.is_some()).cloned().collect();
+    assert!(satellite_post_vec.iter().all(|post| post.post.satellite_id == Some(satellite.satellite_id) && post.sphere_name == sphere.sphere_name));
+    assert_eq!(get_post_id_vec(satellite_post_vec), satellite_post_id_vec);
+
+    let post_id_vec = get_post_id_vec(post_vec);
+    assert_eq!(post_id_vec.len(), 2 * num_post);
+    assert_eq!(post_id_vec.iter().collect::<HashSet<_>>().len(), 2 * num_post);
+    assert!(sphere_post_id_vec.iter().chain(satellite_post_id_vec.iter()).all(|post_id| post_id_vec.contains(post_id)));
+
+    // Pagination over sphere and satellite posts does not repeat posts
+    let first_page = get_post_id_vec(get_subscribed_post_vec(SortType::Post(PostSortType::Recent), num_post as i64, 0, &subscriber, &db_pool).await?);
+    let second_page = get_post_id_vec(get_subscribed_post_vec(SortType::Post(PostSortType::Recent), num_post as i64, num_post as i64, &subscriber, &db_pool).await?);
+    assert_eq!([first_page, second_page].concat(), post_id_vec);
+
+    // Posts of deactivated satellites are not included
+    deactivate_satellite(satellite.satellite_id, &user, &db_pool).await?;
+    let post_vec = get_subscribed_post_vec(SortType::Post(PostSortType::Recent), 100, 0, &subscriber, &db_pool).await?;
+    assert_eq!(get_post_id_vec(post_vec), sphere_post_id_vec);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_subscribed_post_vec_with_filters() {
     let db_pool = get_db_pool().await;
@@ -541,7 +639,7 @@ async fn test_get_subscribed_post_vec_with_filters() {
     }
 
     user.days_hide_spoiler = Some(1);
-    user.show_nsfw = false;
+    user.nsfw_mode = NsfwMode::Hide;
     let mut one_day_spoiler_filter_expected_post = post_vec.clone();
     one_day_spoiler_filter_expected_post.push(old_spoiler_post.clone());
 
@@ -563,7 +661,7 @@ async fn test_get_subscribed_post_vec_with_filters() {
     }
 
     user.days_hide_spoiler = Some(3);
-    user.show_nsfw = true;
+    user.nsfw_mode = NsfwMode::Show;
     let mut three_day_spoiler_filter_expected_post = post_vec.clone();
     three_day_spoiler_filter_expected_post.push(nsfw_post.clone());
 
@@ -716,7 +814,7 @@ async fn test_get_sorted_post_vec_with_filters() {
     }
 
     user.days_hide_spoiler = Some(1);
-    user.show_nsfw = false;
+    user.nsfw_mode = NsfwMode::Hide;
     let mut one_day_spoiler_filter_expected_post = post_vec.clone();
     one_day_spoiler_filter_expected_post.push(old_spoiler_post.clone());
 
@@ -733,7 +831,7 @@ async fn test_get_sorted_post_vec_with_filters() {
     }
 
     user.days_hide_spoiler = Some(3);
-    user.show_nsfw = true;
+    user.nsfw_mode = NsfwMode::Show;
     let mut three_day_spoiler_filter_expected_post = post_vec.clone();
     three_day_spoiler_filter_expected_post.push(nsfw_post.clone());
 
@@ -750,6 +848,129 @@ async fn test_get_sorted_post_vec_with_filters() {
     }
 }
 
+#[tokio::test]
+async fn test_get_post_vec_nsfw_mode() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let num_post = 5;
+    let (sphere, _, _, _, _, nsfw_post) = create_sphere_with_filter_posts(
+        "sphere",
+        num_post,
+        false,
+        &mut user,
+        &db_pool,
+    ).await;
+    subscribe(sphere.sphere_id, user.user_id, &db_pool).await?;
+    let sort_type = SortType::Post(PostSortType::Hot);
+    let load_count = (2*num_post) as i64;
+
+    let get_nsfw_posts = |post_vec: Vec<PostWithSphereInfo>| post_vec.into_iter().filter(|post| post.post.is_nsfw).collect::<Vec<PostWithSphereInfo>>();
+
+    // Hide mode omits NSFW posts
+    user.nsfw_mode = NsfwMode::Hide;
+    assert!(get_nsfw_posts(get_sorted_post_vec(sort_type, load_count, 0, Some(&user), &db_pool).await?).is_empty());
+    assert!(get_nsfw_posts(get_subscribed_post_vec(sort_type, load_count, 0, &user, &db_pool).await?).is_empty());
+    let sphere_post_vec = get_post_vec_by_sphere_name(&sphere.sphere_name, SphereCategoryFilter::All, sort_type, load_count, 0, Some(&user), &db_pool).await?;
+    assert!(sphere_post_vec.iter().all(|post| !post.is_nsfw && !post.is_blurred));
+
+    // Blur mode includes NSFW posts flagged as blurred
+    user.nsfw_mode = NsfwMode::Blur;
+    let mut blurred_nsfw_post = nsfw_post.clone();
+    blurred_nsfw_post.post.is_blurred = true;
+    assert_eq!(get_nsfw_posts(get_sorted_post_vec(sort_type, load_count, 0, Some(&user), &db_pool).await?), vec![blurred_nsfw_post.clone()]);
+    assert_eq!(get_nsfw_posts(get_subscribed_post_vec(sort_type, load_count, 0, &user, &db_pool).await?), vec![blurred_nsfw_post.clone()]);
+    let sphere_post_vec = get_post_vec_by_sphere_name(&sphere.sphere_name, SphereCategoryFilter::All, sort_type, load_count, 0, Some(&user), &db_pool).await?;
+    assert!(sphere_post_vec.contains(&blurred_nsfw_post.post));
+    assert!(sphere_post_vec.iter().all(|post| post.is_blurred == post.is_nsfw));
+    assert!(get_post_with_info_by_id(nsfw_post.post.post_id, Some(&user), &db_pool).await?.post.is_blurred);
+
+    // Show mode reveals NSFW posts
+    user.nsfw_mode = NsfwMode::Show;
+    assert_eq!(get_nsfw_posts(get_sorted_post_vec(sort_type, load_count, 0, Some(&user), &db_pool).await?), vec![nsfw_post.clone()]);
+    assert_eq!(get_nsfw_posts(get_subscribed_post_vec(sort_type, load_count, 0, &user, &db_pool).await?), vec![nsfw_post.clone()]);
+    let sphere_post_vec = get_post_vec_by_sphere_name(&sphere.sphere_name, SphereCategoryFilter::All, sort_type, load_count, 0, Some(&user), &db_pool).await?;
+    assert!(sphere_post_vec.contains(&nsfw_post.post));
+    assert!(sphere_post_vec.iter().all(|post| !post.is_blurred));
+    assert!(!get_post_with_info_by_id(nsfw_post.post.post_id, Some(&user), &db_pool).await?.post.is_blurred);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_post_vec_by_sphere_name_nsfw_confirmation() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    user.nsfw_mode = NsfwMode::Show;
+    let lead = create_user("lead", &db_pool).await;
+
+    let nsfw_sphere = create_sphere("nsfw", "sphere", true, &lead, &db_pool).await?;
+    let sphere = create_sphere("sfw", "sphere", false, &lead, &db_pool).await?;
+    let nsfw_post = create_post(&nsfw_sphere.sphere_name, None, "a", "b", None, Link::default(), PostTags::default(), &lead, &db_pool).await?;
+    let post = create_post(&sphere.sphere_name, None, "a", "b", None, Link::default(), PostTags::default(), &lead, &db_pool).await?;
+    let sort_type = SortType::Post(PostSortType::Hot);
+
+    // listing of NSFW spheres is gated until confirmation, anonymous users cannot confirm
+    assert_eq!(
+        get_post_vec_by_sphere_name(&nsfw_sphere.sphere_name, SphereCategoryFilter::All, sort_type, 10, 0, Some(&user), &db_pool).await,
+        Err(AppError::NsfwNotConfirmed),
+    );
+    assert_eq!(
+        get_post_vec_by_sphere_name(&nsfw_sphere.sphere_name, SphereCategoryFilter::All, sort_type, 10, 0, None, &db_pool).await,
+        Err(AppError::NsfwNotConfirmed),
+    );
+    assert_eq!(
+        get_post_vec_by_sphere_name(&sphere.sphere_name, SphereCategoryFilter::All, sort_type, 10, 0, None, &db_pool).await?,
+        vec![post.clone()],
+    );
+
+    confirm_nsfw(&nsfw_sphere.sphere_name, &user, &db_pool).await?;
+    assert_eq!(
+        get_post_vec_by_sphere_name(&nsfw_sphere.sphere_name, SphereCategoryFilter::All, sort_type, 10, 0, Some(&user), &db_pool).await?,
+        vec![nsfw_post],
+    );
+    assert_eq!(
+        get_post_vec_by_sphere_name(&nsfw_sphere.sphere_name, SphereCategoryFilter::All, sort_type, 10, 0, Some(&lead), &db_pool).await,
+        Err(AppError::NsfwNotConfirmed),
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_post_vec_by_satellite_id_nsfw_confirmation() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    user.nsfw_mode = NsfwMode::Show;
+    let lead = create_user("lead", &db_pool).await;
+
+    let nsfw_sphere = create_sphere("nsfw", "sphere", true, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("Should reload user.");
+    let satellite = create_satellite(&nsfw_sphere.sphere_name, "satellite", "body", false, false, false, &lead, &db_pool).await?;
+    let post = create_post(&nsfw_sphere.sphere_name, Some(satellite.satellite_id), "a", "b", None, Link::default(), PostTags::default(), &lead, &db_pool).await?;
+    let sort_type = SortType::Post(PostSortType::Hot);
+
+    // satellites of NSFW spheres are gated like their sphere
+    for user in [Some(&user), None] {
+        assert_eq!(
+            get_post_vec_by_satellite_id(satellite.satellite_id, None, sort_type, 10, 0, user, &db_pool).await,
+            Err(AppError::NsfwNotConfirmed),
+        );
+    }
+
+    confirm_nsfw(&nsfw_sphere.sphere_name, &user, &db_pool).await?;
+    assert_eq!(
+        get_post_vec_by_satellite_id(satellite.satellite_id, None, sort_type, 10, 0, Some(&user), &db_pool).await?,
+        vec![post],
+    );
+    assert_eq!(
+        get_post_vec_by_satellite_id(i64::MAX, None, sort_type, 10, 0, Some(&user), &db_pool).await,
+        Err(AppError::NotFound),
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_post_vec_by_sphere_name() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -934,6 +1155,46 @@ async fn test_get_post_vec_by_sphere_name_with_pinned_post() -> Result<(), AppEr
         assert_eq!(post_vec.first(), Some(&pinned_post));
     }
 
+    let second_pinned_post = create_post(
+        sphere_name,
+        None,
+        "pinned 2",
+        "b",
+        None,
+        Link::default(),
+        PostTags::new(false, false, true, None),
+        &user,
+        &db_pool
+    ).await.expect("Second pinned post should be created");
+    let second_pinned_post = set_post_score(second_pinned_post.post_id, -10, &db_pool).await?;
+    let pinned_post = set_post_score(pinned_post.post_id, 100, &db_pool).await?;
+
+    let unauthorized_user = create_user("user", &db_pool).await;
+    assert_eq!(
+        set_pin_order(second_pinned_post.post_id, 0, &unauthorized_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    let pinned_post = set_pin_order(pinned_post.post_id, 2, &user, &db_pool).await?;
+    let second_pinned_post = set_pin_order(second_pinned_post.post_id, 1, &user, &db_pool).await?;
+    assert_eq!(pinned_post.pin_order, 2);
+    assert_eq!(second_pinned_post.pin_order, 1);
+
+    for sort_type in POST_SORT_TYPE_ARRAY {
+        let post_vec = get_post_vec_by_sphere_name(
+            sphere_name,
+            SphereCategoryFilter::All,
+            SortType::Post(sort_type),
+            partial_load_num_post as i64,
+            0,
+            None,
+            &db_pool,
+        ).await?;
+
+        assert_eq!(post_vec.len(), partial_load_num_post);
+        assert_eq!(post_vec[0].post_id, second_pinned_post.post_id);
+        assert_eq!(post_vec[1].post_id, pinned_post.post_id);
+    }
+
     Ok(())
 }
 
@@ -1120,7 +1381,7 @@ async fn test_get_post_vec_by_sphere_name_with_filters() {
     }
 
     user.days_hide_spoiler = Some(1);
-    user.show_nsfw = false;
+    user.nsfw_mode = NsfwMode::Hide;
     let mut one_day_spoiler_filter_expected_post = post_vec.clone();
     one_day_spoiler_filter_expected_post.push(old_spoiler_post.clone());
 
@@ -1143,7 +1404,7 @@ async fn test_get_post_vec_by_sphere_name_with_filters() {
     }
 
     user.days_hide_spoiler = Some(3);
-    user.show_nsfw = true;
+    user.nsfw_mode = NsfwMode::Show;
     let mut three_day_spoiler_filter_expected_post = post_vec.clone();
     three_day_spoiler_filter_expected_post.push(nsfw_post.clone());
 
@@ -1166,6 +1427,133 @@ async fn test_get_post_vec_by_sphere_name_with_filters() {
     }
 }
 
+#[tokio::test]
+async fn test_get_post_vec_best_sort_min_vote_count() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let sphere_name = "sphere";
+    let (_, _, post_vec) = create_sphere_with_posts(
+        sphere_name,
+        None,
+        3,
+        None,
+        vec![false; 3],
+        &mut user,
+        &db_pool,
+    ).await?;
+
+    // Posts below the vote count floor rank after well-voted posts, even with a higher score
+    let well_voted_post = set_post_vote_counts(post_vec[0].post.post_id, 6, 4, &db_pool).await?;
+    let single_vote_post = set_post_vote_counts(post_vec[1].post.post_id, 1, 0, &db_pool).await?;
+    let few_votes_post = set_post_vote_counts(post_vec[2].post.post_id, BEST_SORT_MIN_VOTE_COUNT - 1, 0, &db_pool).await?;
+    assert!(single_vote_post.score < well_voted_post.score);
+    assert!(few_votes_post.score > well_voted_post.score);
+
+    let expected_post_id_vec = vec![well_voted_post.post_id, few_votes_post.post_id, single_vote_post.post_id];
+
+    let sphere_post_vec = get_post_vec_by_sphere_name(
+        sphere_name,
+        SphereCategoryFilter::All,
+        SortType::Post(PostSortType::Best),
+        10,
+        0,
+        None,
+        &db_pool,
+    ).await?;
+    assert_eq!(sphere_post_vec.into_iter().map(|post| post.post_id).collect::<Vec<i64>>(), expected_post_id_vec);
+
+    let sorted_post_vec = get_sorted_post_vec(
+        SortType::Post(PostSortType::Best),
+        10,
+        0,
+        None,
+        &db_pool,
+    ).await?;
+    assert_eq!(sorted_post_vec.into_iter().map(|post| post.post.post_id).collect::<Vec<i64>>(), expected_post_id_vec);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_best_post_index_vote_floor() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+
+    // the indexes of the best sort are created by a migration and must use the same vote floor as BEST_POST_ORDER_BY_COLUMN
+    let index_def_vec = sqlx::query_scalar::<_, String>(
+        "SELECT indexdef FROM pg_indexes WHERE indexname IN ('idx_posts_best', 'idx_posts_sphere_best', 'idx_posts_satellite_best')"
+    )
+        .fetch_all(&db_pool)
+        .await?;
+    assert_eq!(index_def_vec.len(), 3);
+    for index_def in index_def_vec {
+        assert!(index_def.contains(&format!(">= {BEST_SORT_MIN_VOTE_COUNT})")), "Unexpected vote floor in index: {index_def}");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_post_vec_best_ratio_sort() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let sphere_name = "sphere";
+    let (_, _, post_vec) = create_sphere_with_posts(
+        sphere_name,
+        None,
+        3,
+        None,
+        vec![false; 3],
+        &mut user,
+        &db_pool,
+    ).await?;
+
+    // The high score post has more net votes, but a worse upvote ratio than the high ratio post
+    let high_score_post = set_post_vote_counts(post_vec[0].post.post_id, 60, 40, &db_pool).await?;
+    let high_ratio_post = set_post_vote_counts(post_vec[1].post.post_id, 10, 0, &db_pool).await?;
+    let no_vote_post = set_post_vote_counts(post_vec[2].post.post_id, 0, 0, &db_pool).await?;
+    assert!(high_score_post.score > high_ratio_post.score);
+    assert!(upvote_ratio(&high_ratio_post) > upvote_ratio(&high_score_post));
+
+    let best_post_vec = get_post_vec_by_sphere_name(
+        sphere_name,
+        SphereCategoryFilter::All,
+        SortType::Post(PostSortType::Best),
+        10,
+        0,
+        None,
+        &db_pool,
+    ).await?;
+    assert_eq!(
+        best_post_vec.into_iter().map(|post| post.post_id).collect::<Vec<i64>>(),
+        vec![high_score_post.post_id, high_ratio_post.post_id, no_vote_post.post_id],
+    );
+
+    let expected_post_id_vec = vec![high_ratio_post.post_id, high_score_post.post_id, no_vote_post.post_id];
+    let best_ratio_post_vec = get_post_vec_by_sphere_name(
+        sphere_name,
+        SphereCategoryFilter::All,
+        SortType::Post(PostSortType::BestRatio),
+        10,
+        0,
+        None,
+        &db_pool,
+    ).await?;
+    assert_eq!(best_ratio_post_vec.into_iter().map(|post| post.post_id).collect::<Vec<i64>>(), expected_post_id_vec);
+
+    let sorted_post_vec = get_sorted_post_vec(
+        SortType::Post(PostSortType::BestRatio),
+        10,
+        0,
+        None,
+        &db_pool,
+    ).await?;
+    assert_eq!(sorted_post_vec.into_iter().map(|post| post.post.post_id).collect::<Vec<i64>>(), expected_post_id_vec);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_post_vec_by_satellite_id() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -1344,6 +1732,37 @@ async fn test_get_post_vec_by_satellite_id_with_pinned_post() -> Result<(), AppE
         assert_eq!(post_vec.first(), Some(&pinned_post));
     }
 
+    let second_pinned_post = create_post(
+        sphere_name,
+        Some(satellite.satellite_id),
+        "pinned 2",
+        "b",
+        None,
+        Link::default(),
+        PostTags::new(false, false, true, None),
+        &user,
+        &db_pool
+    ).await.expect("Second pinned post should be created");
+    let second_pinned_post = set_post_score(second_pinned_post.post_id, -10, &db_pool).await?;
+    let pinned_post = set_post_score(pinned_post.post_id, 100, &db_pool).await?;
+    let pinned_post = set_pin_order(pinned_post.post_id, 1, &user, &db_pool).await?;
+
+    for sort_type in POST_SORT_TYPE_ARRAY {
+        let post_vec = get_post_vec_by_satellite_id(
+            satellite.satellite_id,
+            None,
+            SortType::Post(sort_type),
+            load_count as i64,
+            0,
+            None,
+            &db_pool,
+        ).await.expect("Post vec should be loaded");
+
+        assert_eq!(post_vec.len(), load_count);
+        assert_eq!(post_vec[0].post_id, second_pinned_post.post_id);
+        assert_eq!(post_vec[1].post_id, pinned_post.post_id);
+    }
+
     Ok(())
 }
 
@@ -1534,7 +1953,7 @@ async fn test_get_post_vec_by_satellite_id_with_filters() {
     }
 
     user.days_hide_spoiler = Some(1);
-    user.show_nsfw = false;
+    user.nsfw_mode = NsfwMode::Hide;
     let mut one_day_spoiler_filter_expected_post = post_vec.clone();
     one_day_spoiler_filter_expected_post.push(old_spoiler_post.clone());
 
@@ -1557,7 +1976,7 @@ async fn test_get_post_vec_by_satellite_id_with_filters() {
     }
 
     user.days_hide_spoiler = Some(3);
-    user.show_nsfw = true;
+    user.nsfw_mode = NsfwMode::Show;
     let mut three_day_spoiler_filter_expected_post = post_vec.clone();
     three_day_spoiler_filter_expected_post.push(nsfw_post.clone());
 
@@ -1640,6 +2059,72 @@ async fn test_get_homepage_post_vec() {
     }
 }
 
+#[tokio::test]
+async fn test_get_home_feed() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let mut other_user = create_user("other", &db_pool).await;
+
+    let num_posts = 6;
+    let mut create_sphere_with_scored_posts = async |sphere_name: &str, score_offset: i32| {
+        create_sphere_with_posts(
+            sphere_name,
+            None,
+            num_posts,
+            Some((0..num_posts).map(|i| score_offset + i as i32).collect()),
+            (0..num_posts).map(|i| (i % 2) == 0).collect(),
+            &mut other_user,
+            &db_pool,
+        ).await.expect("Sphere with posts should be created")
+    };
+    let (subscribed_sphere, _, mut subscribed_post_vec) = create_sphere_with_scored_posts("subscribed", 0).await;
+    let (trending_sphere, _, mut trending_post_vec) = create_sphere_with_scored_posts("trending", 10).await;
+    let (stale_sphere, _, stale_post_vec) = create_sphere_with_scored_posts("stale", 20).await;
+
+    subscribe(subscribed_sphere.sphere_id, user.user_id, &db_pool).await?;
+    subscribe(trending_sphere.sphere_id, other_user.user_id, &db_pool).await?;
+    subscribe(stale_sphere.sphere_id, other_user.user_id, &db_pool).await?;
+    set_subscription_timestamp(stale_sphere.sphere_id, other_user.user_id, -(TRENDING_SPHERE_DAYS + 1), &db_pool).await?;
+    user = User::get(user.user_id, &db_pool).await.expect("Should reload user");
+
+    let sort_type = PostSortType::Hot;
+    sort_post_vec(&mut subscribed_post_vec, sort_type, false);
+    sort_post_vec(&mut trending_post_vec, sort_type, false);
+
+    // A discovery post every 4 slots, then only discovery posts once subscribed ones are exhausted
+    let mut expected_feed = Vec::new();
+    let mut subscribed_iter = subscribed_post_vec.iter().cloned();
+    let mut trending_iter = trending_post_vec.iter().cloned();
+    for is_discovery in [false, false, false, true, false, false, false, true, true, true, true, true] {
+        expected_feed.push(match is_discovery {
+            true => trending_iter.next().unwrap(),
+            false => subscribed_iter.next().unwrap(),
+        });
+    }
+
+    let home_feed = get_home_feed(&user, SortType::Post(sort_type), 20, 0, 0.25, &db_pool).await?;
+    assert_eq!(home_feed, expected_feed);
+
+    let home_feed_post_ids = home_feed.iter().map(|post| post.post.post_id).collect::<HashSet<i64>>();
+    assert_eq!(home_feed_post_ids.len(), home_feed.len());
+    assert!(stale_post_vec.iter().all(|post| !home_feed_post_ids.contains(&post.post.post_id)));
+
+    // Pages are consistent with the full feed
+    let mut paged_feed = Vec::new();
+    for offset in (0..15).step_by(5) {
+        paged_feed.append(&mut get_home_feed(&user, SortType::Post(sort_type), 5, offset, 0.25, &db_pool).await?);
+    }
+    assert_eq!(paged_feed, expected_feed);
+
+    // Without discovery, discovery posts only come after all subscribed posts
+    assert_eq!(
+        get_home_feed(&user, SortType::Post(sort_type), 20, 0, 0.0, &db_pool).await?,
+        [subscribed_post_vec, trending_post_vec].concat(),
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_create_post_and_vote() {
     let db_pool = get_db_pool().await;
@@ -1658,12 +2143,14 @@ async fn test_create_post_and_vote() {
         is_markdown: false,
         embed_type: Default::default(),
         link: None,
+        alt_text: None,
         post_tags: Default::default(),
     };
 
     let (post_1, vote_1, post_1_path) = create_post_and_vote(
         post_1_location,
         post_1_inputs,
+        None,
         &user,
         &db_pool,
     ).await.expect("Should create post 1 and vote");
@@ -1706,12 +2193,14 @@ async fn test_create_post_and_vote() {
         is_markdown: true,
         embed_type: Default::default(),
         link: None,
+        alt_text: None,
         post_tags: Default::default(),
     };
 
     let (post_2, vote_2, post_path) = create_post_and_vote(
         post_2_location,
         post_2_inputs,
+        None,
         &user,
         &db_pool,
     ).await.expect("Should create post 2 and vote");
@@ -1743,18 +2232,438 @@ async fn test_create_post_and_vote() {
 }
 
 #[tokio::test]
-async fn test_create_post() -> Result<(), AppError> {
+async fn test_create_post_and_vote_with_idempotency_key() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
-    let user = create_test_user(&db_pool).await;
+    let mut user = create_test_user(&db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let post_location = PostLocation {
+        sphere: sphere.sphere_name.clone(),
+        satellite_id: None,
+    };
+    let post_inputs = PostDataInputs {
+        title: String::from("title"),
+        body: String::from("body"),
+        ..Default::default()
+    };
+    let get_post_count = async || sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM posts WHERE sphere_id = $1")
+        .bind(sphere.sphere_id)
+        .fetch_one(&db_pool)
+        .await
+        .expect("Should count posts");
+    let initial_post_count = get_post_count().await;
+
+    // creating a post twice with the same key returns the first post
+    let (post_1, vote_1, post_1_path) = create_post_and_vote(post_location.clone(), post_inputs.clone(), Some("a"), &user, &db_pool).await?;
+    let (replayed_post, replayed_vote, replayed_path) = create_post_and_vote(post_location.clone(), post_inputs.clone(), Some("a"), &user, &db_pool).await?;
+    assert_eq!(replayed_post.post_id, post_1.post_id);
+    assert_eq!(replayed_vote, vote_1);
+    assert_eq!(replayed_path, post_1_path);
+    assert_eq!(get_post_count().await, initial_post_count + 1);
+
+    // different keys and users create different posts
+    let (post_2, _, _) = create_post_and_vote(post_location.clone(), post_inputs.clone(), Some("b"), &user, &db_pool).await?;
+    assert_ne!(post_2.post_id, post_1.post_id);
+    let (other_user_post, _, _) = create_post_and_vote(post_location.clone(), post_inputs.clone(), Some("a"), &other_user, &db_pool).await?;
+    assert_ne!(other_user_post.post_id, post_1.post_id);
+    assert_eq!(other_user_post.creator_id, other_user.user_id);
+    assert_eq!(get_post_count().await, initial_post_count + 3);
+
+    // the key is released when the post could not be created
+    let missing_sphere_location = PostLocation {
+        sphere: String::from("missing"),
+        satellite_id: None,
+    };
+    assert!(create_post_and_vote(missing_sphere_location, post_inputs.clone(), Some("c"), &user, &db_pool).await.is_err());
+    let (post_3, _, _) = create_post_and_vote(post_location.clone(), post_inputs.clone(), Some("c"), &user, &db_pool).await?;
+    assert_ne!(post_3.post_id, post_2.post_id);
+
+    // a key reserved by a request still in progress cannot be used
+    sqlx::query("INSERT INTO post_idempotency_keys (user_id, idempotency_key) VALUES ($1, 'd')")
+        .bind(user.user_id)
+        .execute(&db_pool)
+        .await?;
+    assert_eq!(
+        create_post_and_vote(post_location.clone(), post_inputs.clone(), Some("d"), &user, &db_pool).await.map(|(post, _, _)| post.post_id),
+        Err(AppError::Conflict),
+    );
+    // unless the reservation is older than the pending window, in which case its request is considered interrupted
+    sqlx::query("UPDATE post_idempotency_keys SET create_timestamp = create_timestamp - $1 WHERE user_id = $2 AND idempotency_key = 'd'")
+        .bind(PENDING_POST_IDEMPOTENCY_WINDOW)
+        .bind(user.user_id)
+        .execute(&db_pool)
+        .await?;
+    let (post_d, _, _) = create_post_and_vote(post_location.clone(), post_inputs.clone(), Some("d"), &user, &db_pool).await?;
+    assert_eq!(post_d.creator_id, user.user_id);
+
+    // the path of a replayed post is the one of the stored post, regardless of the location of the retried request
+    let other_location = PostLocation {
+        sphere: String::from("other"),
+        satellite_id: Some(1),
+    };
+    let (_, _, replayed_path) = create_post_and_vote(other_location, post_inputs.clone(), Some("d"), &user, &db_pool).await?;
+    assert_eq!(replayed_path, get_post_path(&sphere.sphere_name, None, post_d.post_id));
+
+    assert!(create_post_and_vote(post_location.clone(), post_inputs.clone(), Some(""), &user, &db_pool).await.is_err());
+    let too_long_key = "a".repeat(MAX_IDEMPOTENCY_KEY_LENGTH + 1);
+    assert!(create_post_and_vote(post_location.clone(), post_inputs.clone(), Some(&too_long_key), &user, &db_pool).await.is_err());
+
+    // keys expire after the idempotency window
+    sqlx::query("UPDATE post_idempotency_keys SET create_timestamp = create_timestamp - $1 WHERE user_id = $2 AND idempotency_key = 'a'")
+        .bind(POST_IDEMPOTENCY_WINDOW)
+        .bind(user.user_id)
+        .execute(&db_pool)
+        .await?;
+    let (post_4, _, _) = create_post_and_vote(post_location, post_inputs, Some("a"), &user, &db_pool).await?;
+    assert_ne!(post_4.post_id, post_1.post_id);
+    assert_eq!(get_post_count().await, initial_post_count + 6);
 
-    let sphere_1 = create_sphere("a", "sphere", false, &user, &db_pool).await?;
-    let sphere_2 = create_sphere("b", "sphere", true, &user, &db_pool).await?;
+    Ok(())
+}
 
-    let post_1_title = "1";
-    let post_1_body = "test";
-    let post_1 = create_post(
-        &sphere_1.sphere_name,
-        None,
+#[tokio::test]
+async fn test_create_post_and_vote_rollback() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let (sphere, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "1", "2", false, &user, &db_pool).await?;
+    let post_location = PostLocation {
+        sphere: sphere.sphere_name.clone(),
+        satellite_id: None,
+    };
+    let post_inputs = PostDataInputs {
+        title: String::from("title"),
+        body: String::from("body"),
+        ..Default::default()
+    };
+    let get_post_count = async || sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM posts WHERE sphere_id = $1")
+        .bind(sphere.sphere_id)
+        .fetch_one(&db_pool)
+        .await
+        .expect("Should count posts");
+    let initial_post_count = get_post_count().await;
+
+    // the banned user is not reloaded, so that the post checks pass and only the vote fails after the post is inserted
+    let banned_user = create_user("banned", &db_pool).await;
+    ban_user_from_sphere(banned_user.user_id, sphere.sphere_id, post.post_id, None, rule.rule_id, Some(1), &user, &db_pool).await?;
+
+    // the post is not kept when its vote fails and retries with the same key do not create duplicates
+    for _ in 0..2 {
+        assert!(create_post_and_vote(post_location.clone(), post_inputs.clone(), Some("a"), &banned_user, &db_pool).await.is_err());
+        assert_eq!(get_post_count().await, initial_post_count);
+    }
+    assert!(create_post_and_vote(post_location, post_inputs, None, &banned_user, &db_pool).await.is_err());
+    assert_eq!(get_post_count().await, initial_post_count);
+
+    // the key is released with the rolled back post
+    let key_count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM post_idempotency_keys WHERE user_id = $1")
+        .bind(banned_user.user_id)
+        .fetch_one(&db_pool)
+        .await?;
+    assert_eq!(key_count, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_similar_posts() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let (sphere, uncategorized_post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let (other_sphere, _) = create_sphere_with_post("other", &mut user, &db_pool).await;
+    let category = set_sphere_category(&sphere.sphere_name, "a", Color::Green, "a", true, &user, &db_pool).await?;
+    let other_category = set_sphere_category(&sphere.sphere_name, "b", Color::Blue, "b", true, &user, &db_pool).await?;
+    let other_sphere_category = set_sphere_category(&other_sphere.sphere_name, "a", Color::Green, "a", true, &user, &db_pool).await?;
+    let category_tags = PostTags::new(false, false, false, Some(category.category_id));
+
+    let post = create_post(&sphere.sphere_name, None, "1", "1", None, Link::default(), category_tags.clone(), &user, &db_pool).await?;
+    let similar_post_1 = create_post(&sphere.sphere_name, None, "2", "2", None, Link::default(), category_tags.clone(), &user, &db_pool).await?;
+    let similar_post_2 = create_post(&sphere.sphere_name, None, "3", "3", None, Link::default(), category_tags.clone(), &user, &db_pool).await?;
+    let other_category_post = create_post(
+        &sphere.sphere_name, None, "4", "4", None, Link::default(), PostTags::new(false, false, false, Some(other_category.category_id)), &user, &db_pool
+    ).await?;
+    create_post(
+        &other_sphere.sphere_name, None, "5", "5", None, Link::default(), PostTags::new(false, false, false, Some(other_sphere_category.category_id)), &user, &db_pool
+    ).await?;
+    let nsfw_post = create_post(
+        &sphere.sphere_name, None, "6", "6", None, Link::default(), PostTags::new(false, true, false, Some(category.category_id)), &user, &db_pool
+    ).await?;
+    let deleted_post = create_post(&sphere.sphere_name, None, "7", "7", None, Link::default(), category_tags.clone(), &user, &db_pool).await?;
+    delete_post(deleted_post.post_id, &user, &db_pool).await?;
+    let moderated_post = create_post(&sphere.sphere_name, None, "8", "8", None, Link::default(), category_tags.clone(), &user, &db_pool).await?;
+    let rule = add_rule(&sphere.sphere_name, 0, "1", "2", false, &user, &db_pool).await?;
+    moderate_post(moderated_post.post_id, rule.rule_id, "reason", &user, &db_pool).await?;
+    let satellite = create_satellite(&sphere.sphere_name, "satellite", "body", false, false, false, &user, &db_pool).await?;
+    create_post(&sphere.sphere_name, Some(satellite.satellite_id), "9", "9", None, Link::default(), category_tags.clone(), &user, &db_pool).await?;
+    let spoiler_post = create_post(
+        &sphere.sphere_name, None, "10", "10", None, Link::default(), PostTags::new(true, false, false, Some(category.category_id)), &user, &db_pool
+    ).await?;
+    // give a higher score to the second post to check ordering
+    vote_on_content(VoteValue::Up, similar_post_2.post_id, None, None, &user, &db_pool).await?;
+    update_post_scores(POST_SCORE_UPDATE_BATCH_SIZE, &db_pool).await?;
+
+    let get_similar_post_ids = async |post_id: i64, limit: i64| -> Result<Vec<i64>, AppError> {
+        Ok(get_similar_posts(post_id, limit, None, &db_pool).await?.into_iter().map(|post| post.post.post_id).collect())
+    };
+
+    assert_eq!(get_similar_post_ids(post.post_id, 10).await?, vec![similar_post_2.post_id, spoiler_post.post_id, similar_post_1.post_id]);
+    assert_eq!(get_similar_post_ids(post.post_id, 1).await?, vec![similar_post_2.post_id]);
+    assert_eq!(
+        get_similar_post_ids(similar_post_1.post_id, 10).await?.into_iter().collect::<HashSet<i64>>(),
+        HashSet::from([post.post_id, similar_post_2.post_id, spoiler_post.post_id]),
+    );
+    // posts without category are similar to other posts without category
+    assert!(get_similar_post_ids(uncategorized_post.post_id, 10).await?.is_empty());
+    assert!(get_similar_post_ids(other_category_post.post_id, 10).await?.is_empty());
+    // nsfw posts are only included for nsfw posts
+    assert_eq!(get_similar_post_ids(nsfw_post.post_id, 10).await?.len(), 4);
+
+    // recent spoilers are excluded for users hiding them
+    set_user_settings(false, NsfwMode::Hide, 7, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user");
+    let similar_post_id_vec: Vec<i64> = get_similar_posts(post.post_id, 10, Some(&user), &db_pool).await?
+        .into_iter()
+        .map(|post| post.post.post_id)
+        .collect();
+    assert_eq!(similar_post_id_vec, vec![similar_post_2.post_id, similar_post_1.post_id]);
+    // nsfw posts are excluded when the user hides them and blurred when the user blurs them
+    let other_nsfw_post = create_post(
+        &sphere.sphere_name, None, "11", "11", None, Link::default(), PostTags::new(false, true, false, Some(category.category_id)), &user, &db_pool
+    ).await?;
+    assert!(get_similar_posts(nsfw_post.post_id, 10, Some(&user), &db_pool).await?.iter().all(|post| !post.post.is_nsfw));
+    set_user_settings(false, NsfwMode::Blur, 7, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user");
+    let similar_post_vec = get_similar_posts(nsfw_post.post_id, 10, Some(&user), &db_pool).await?;
+    assert!(similar_post_vec.iter().any(|post| post.post.post_id == other_nsfw_post.post_id));
+    assert!(similar_post_vec.iter().all(|post| post.post.is_blurred == post.post.is_nsfw));
+
+    let similar_post_vec = get_similar_posts(post.post_id, 10, None, &db_pool).await?;
+    assert!(similar_post_vec.iter().all(|post_info|
+        post_info.sphere_name == sphere.sphere_name &&
+            post_info.sphere_category.as_ref().is_some_and(|sphere_category| sphere_category.category_name == category.category_name)
+    ));
+
+    assert!(get_similar_posts(-1, 10, None, &db_pool).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_scheduled_post() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user.");
+    let sort_type = SortType::Post(PostSortType::Recent);
+
+    assert!(
+        create_scheduled_post(
+            NewPost { sphere_name: &sphere.sphere_name, title: "a", body: "b", ..Default::default() },
+            chrono::Utc::now() - chrono::Duration::hours(1),
+            &user,
+            &db_pool,
+        ).await.is_err()
+    );
+
+    let publish_at = chrono::Utc::now() + chrono::Duration::hours(1);
+    let scheduled_post = create_scheduled_post(
+        NewPost { sphere_name: &sphere.sphere_name, title: "a", body: "b", ..Default::default() },
+        publish_at,
+        &user,
+        &db_pool,
+    ).await?;
+    assert_eq!(scheduled_post.publish_at.map(|t| t.timestamp_micros()), Some(publish_at.timestamp_micros()));
+
+    // future scheduled posts are excluded from feeds, even after the scheduler runs
+    assert_eq!(publish_scheduled_posts(&db_pool).await?, 0);
+    assert!(get_post_vec_by_sphere_name(&sphere.sphere_name, SphereCategoryFilter::All, sort_type, 10, 0, Some(&user), &db_pool).await?.is_empty());
+    assert!(get_sorted_post_vec(sort_type, 10, 0, Some(&user), &db_pool).await?.is_empty());
+    assert_eq!(get_post_by_id(scheduled_post.post_id, &db_pool).await?.publish_at, scheduled_post.publish_at);
+
+    // once publish_at has passed, the scheduler publishes the post
+    let past_publish_at = chrono::Utc::now() - chrono::Duration::minutes(1);
+    set_post_publish_at(scheduled_post.post_id, past_publish_at, &db_pool).await?;
+    assert!(get_post_vec_by_sphere_name(&sphere.sphere_name, SphereCategoryFilter::All, sort_type, 10, 0, Some(&user), &db_pool).await?.is_empty());
+    assert_eq!(publish_scheduled_posts(&db_pool).await?, 1);
+
+    let published_post = get_post_by_id(scheduled_post.post_id, &db_pool).await?;
+    assert_eq!(published_post.publish_at, None);
+    assert_eq!(published_post.create_timestamp.timestamp_micros(), past_publish_at.timestamp_micros());
+    let post_vec = get_post_vec_by_sphere_name(&sphere.sphere_name, SphereCategoryFilter::All, sort_type, 10, 0, Some(&user), &db_pool).await?;
+    assert_eq!(post_vec.iter().map(|post| post.post_id).collect::<Vec<i64>>(), vec![scheduled_post.post_id]);
+    assert_eq!(publish_scheduled_posts(&db_pool).await?, 0);
+
+    // only moderators and trusted users can schedule posts
+    let post_location = PostLocation {
+        sphere: sphere.sphere_name.clone(),
+        satellite_id: None,
+    };
+    let post_inputs = PostDataInputs {
+        title: String::from("scheduled"),
+        body: String::from("body"),
+        is_markdown: false,
+        embed_type: EmbedType::None,
+        link: None,
+        alt_text: None,
+        post_tags: PostTags::default(),
+    };
+    assert_eq!(
+        create_scheduled_post_and_vote(post_location.clone(), post_inputs.clone(), publish_at, &other_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    add_trusted_user(&other_user.username, &sphere.sphere_name, &user, &db_pool).await?;
+    let (trusted_post, trusted_vote, trusted_post_path) = create_scheduled_post_and_vote(
+        post_location, post_inputs, publish_at, &other_user, &db_pool,
+    ).await?;
+    assert_eq!(trusted_post.publish_at.map(|t| t.timestamp_micros()), Some(publish_at.timestamp_micros()));
+    assert_eq!(trusted_post.creator_id, other_user.user_id);
+    assert_eq!(trusted_vote.map(|vote| vote.value), Some(VoteValue::Up));
+    assert_eq!(trusted_post_path, get_post_path(&sphere.sphere_name, None, trusted_post.post_id));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scheduled_post_mention_notifications() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let mentioned_user = create_user("mentioned", &db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user.");
+
+    let post_location = PostLocation {
+        sphere: sphere.sphere_name.clone(),
+        satellite_id: None,
+    };
+    let post_inputs = PostDataInputs {
+        title: String::from("scheduled"),
+        body: format!("hello @{}", mentioned_user.username),
+        is_markdown: false,
+        embed_type: EmbedType::None,
+        link: None,
+        alt_text: None,
+        post_tags: PostTags::default(),
+    };
+    let (scheduled_post, _, _) = create_scheduled_post_and_vote(
+        post_location, post_inputs, chrono::Utc::now() + chrono::Duration::hours(1), &user, &db_pool,
+    ).await?;
+
+    // mentions are only notified once the post is published
+    assert!(get_notifications(mentioned_user.user_id, &db_pool).await?.is_empty());
+    assert_eq!(publish_scheduled_posts(&db_pool).await?, 0);
+    assert!(get_notifications(mentioned_user.user_id, &db_pool).await?.is_empty());
+
+    set_post_publish_at(scheduled_post.post_id, chrono::Utc::now() - chrono::Duration::minutes(1), &db_pool).await?;
+    assert_eq!(publish_scheduled_posts(&db_pool).await?, 1);
+    let notif_vec = get_notifications(mentioned_user.user_id, &db_pool).await?;
+    assert_eq!(notif_vec.len(), 1);
+    assert_eq!(notif_vec[0].post_id, scheduled_post.post_id);
+    assert_eq!(notif_vec[0].trigger_user_id, user.user_id);
+    assert_eq!(notif_vec[0].notification_type, NotificationType::Mention);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_save_and_load_draft() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    assert_eq!(load_draft(&user, &db_pool).await?, None);
+
+    let draft = save_draft("title", "body", Some("https://example.com"), &user, &db_pool).await?;
+    assert_eq!(draft.user_id, user.user_id);
+    assert_eq!(draft.title, "title");
+    assert_eq!(draft.body, "body");
+    assert_eq!(draft.link, Some(String::from("https://example.com")));
+    assert_eq!(load_draft(&user, &db_pool).await?, Some(draft.clone()));
+    assert_eq!(load_draft(&other_user, &db_pool).await?, None);
+
+    // saving again overwrites the previous draft
+    let overwritten_draft = save_draft("new title", "", None, &user, &db_pool).await?;
+    assert_eq!(overwritten_draft.title, "new title");
+    assert_eq!(overwritten_draft.body, "");
+    assert_eq!(overwritten_draft.link, None);
+    assert!(overwritten_draft.edit_timestamp >= draft.edit_timestamp);
+    assert_eq!(load_draft(&user, &db_pool).await?, Some(overwritten_draft));
+
+    let too_long_title = "a".repeat(MAX_TITLE_LENGTH as usize + 1);
+    assert!(save_draft(&too_long_title, "", None, &user, &db_pool).await.is_err());
+
+    delete_draft(&user, &db_pool).await?;
+    assert_eq!(load_draft(&user, &db_pool).await?, None);
+    // deleting a missing draft has no effect
+    delete_draft(&user, &db_pool).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_post_draft_deletion() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &user, &db_pool).await?;
+    let other_sphere = create_sphere("other", "other", false, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user.");
+
+    let draft = save_draft("title", "body", None, &user, &db_pool).await?;
+
+    // only posts created from the create post form delete the draft
+    let post = create_post(&sphere.sphere_name, None, "other title", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+    crosspost(post.post_id, &other_sphere.sphere_name, &user, &db_pool).await?;
+    create_scheduled_post(
+        NewPost { sphere_name: &sphere.sphere_name, title: "scheduled", body: "body", ..Default::default() },
+        chrono::Utc::now() + chrono::Duration::hours(1),
+        &user,
+        &db_pool,
+    ).await?;
+
+    assert_eq!(load_draft(&user, &db_pool).await?, Some(draft));
+
+    let post_location = PostLocation {
+        sphere: sphere.sphere_name.clone(),
+        satellite_id: None,
+    };
+    let post_inputs = PostDataInputs {
+        title: String::from("title"),
+        body: String::from("body"),
+        is_markdown: false,
+        embed_type: EmbedType::None,
+        link: None,
+        alt_text: None,
+        post_tags: PostTags::default(),
+    };
+    // a failed submit keeps the draft
+    assert!(create_post_and_vote(
+        PostLocation { sphere: String::from("missing"), satellite_id: None }, post_inputs.clone(), None, &user, &db_pool,
+    ).await.is_err());
+    assert!(load_draft(&user, &db_pool).await?.is_some());
+
+    create_post_and_vote(post_location.clone(), post_inputs.clone(), None, &user, &db_pool).await?;
+    assert_eq!(load_draft(&user, &db_pool).await?, None);
+
+    // publishing without draft succeeds
+    create_post_and_vote(post_location, post_inputs, None, &user, &db_pool).await?;
+    assert_eq!(load_draft(&user, &db_pool).await?, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_post() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+
+    let sphere_1 = create_sphere("a", "sphere", false, &user, &db_pool).await?;
+    let sphere_2 = create_sphere("b", "sphere", true, &user, &db_pool).await?;
+
+    let post_1_title = "1";
+    let post_1_body = "test";
+    let post_1 = create_post(
+        &sphere_1.sphere_name,
+        None,
         post_1_title,
         post_1_body,
         None,
@@ -2035,6 +2944,7 @@ async fn test_edit_post() {
             is_markdown: false,
             embed_type: Default::default(),
             link: None,
+            alt_text: None,
             post_tags: Default::default(),
         },
         PostDataInputs {
@@ -2043,6 +2953,7 @@ async fn test_edit_post() {
             is_markdown: true,
             embed_type: Default::default(),
             link: None,
+            alt_text: None,
             post_tags: PostTags {
                 is_spoiler: true,
                 is_nsfw: true,
@@ -2076,6 +2987,392 @@ async fn test_edit_post() {
     }
 }
 
+#[tokio::test]
+async fn test_post_link_alt_text() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let sphere = create_sphere("sphere", "sphere", false, &user, &db_pool).await?;
+    user = User::get(user.user_id, &db_pool).await.expect("Should reload user.");
+    let link_url = String::from("https://www.test.com/example");
+    let alt_text = String::from("Description of the link");
+    let post_location = PostLocation {
+        sphere: sphere.sphere_name.clone(),
+        satellite_id: None,
+    };
+    let mut post_inputs = PostDataInputs {
+        title: String::from("link"),
+        body: String::from("body"),
+        is_markdown: false,
+        embed_type: EmbedType::Link,
+        link: Some(link_url.clone()),
+        alt_text: Some(alt_text.clone()),
+        post_tags: Default::default(),
+    };
+
+    let (post, _, _) = create_post_and_vote(post_location, post_inputs.clone(), None, &user, &db_pool).await?;
+    assert_eq!(post.link.link_url, Some(link_url.clone()));
+    assert_eq!(post.link.alt_text, Some(alt_text));
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.link, post.link);
+
+    let edited_alt_text = String::from("Edited description");
+    post_inputs.alt_text = Some(edited_alt_text.clone());
+    let post = edit_post(post.post_id, post_inputs.clone(), &user, &db_pool).await?;
+    assert_eq!(post.link.alt_text, Some(edited_alt_text));
+
+    post_inputs.alt_text = Some(String::new());
+    let post = edit_post(post.post_id, post_inputs.clone(), &user, &db_pool).await?;
+    assert_eq!(post.link.alt_text, None);
+
+    post_inputs.alt_text = Some("a".repeat(MAX_ALT_TEXT_LENGTH as usize + 1));
+    assert!(edit_post(post.post_id, post_inputs, &user, &db_pool).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_find_recent_post_with_link() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (sphere, _) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let (other_sphere, _) = create_sphere_with_post("other", &mut user, &db_pool).await;
+    let link_url = "https://www.example.com/article/?utm_source=test";
+    let post = create_post(
+        &sphere.sphere_name, None, "link", "body", None, Link::new(LinkType::Link, Some(String::from(link_url)), None, None), PostTags::default(), &user, &db_pool
+    ).await?;
+
+    assert_eq!(find_recent_post_with_link(sphere.sphere_id, link_url, DUPLICATE_LINK_WINDOW, &db_pool).await?, Some(post.post_id));
+    assert_eq!(find_recent_post_with_link(sphere.sphere_id, "https://example.com/article", DUPLICATE_LINK_WINDOW, &db_pool).await?, Some(post.post_id));
+    assert_eq!(find_recent_post_with_link(sphere.sphere_id, "http://example.com/article#top", DUPLICATE_LINK_WINDOW, &db_pool).await?, Some(post.post_id));
+    assert_eq!(find_recent_post_with_link(sphere.sphere_id, "https://example.com/other_article", DUPLICATE_LINK_WINDOW, &db_pool).await?, None);
+    assert_eq!(find_recent_post_with_link(sphere.sphere_id, "https://example.com/article?id=1", DUPLICATE_LINK_WINDOW, &db_pool).await?, None);
+    assert_eq!(find_recent_post_with_link(other_sphere.sphere_id, link_url, DUPLICATE_LINK_WINDOW, &db_pool).await?, None);
+    assert_eq!(find_recent_post_with_link(sphere.sphere_id, link_url, Duration::ZERO, &db_pool).await?, None);
+
+    assert_eq!(get_duplicate_link_post_id(&sphere.sphere_name, link_url, &db_pool).await?, Some(post.post_id));
+    assert_eq!(get_duplicate_link_post_id(&sphere.sphere_name, " https://example.com/article ", &db_pool).await?, Some(post.post_id));
+    assert_eq!(get_duplicate_link_post_id(&other_sphere.sphere_name, link_url, &db_pool).await?, None);
+    assert_eq!(get_duplicate_link_post_id("missing", link_url, &db_pool).await, Err(AppError::NotFound));
+
+    let edited_link_url = "https://example.com/edited_article";
+    update_post(
+        post.post_id, "link", "body", None, Link::new(LinkType::Link, Some(String::from(edited_link_url)), None, None), PostTags::default(), &user, &db_pool
+    ).await?;
+    assert_eq!(find_recent_post_with_link(sphere.sphere_id, edited_link_url, DUPLICATE_LINK_WINDOW, &db_pool).await?, Some(post.post_id));
+    assert_eq!(find_recent_post_with_link(sphere.sphere_id, link_url, DUPLICATE_LINK_WINDOW, &db_pool).await?, None);
+
+    delete_post(post.post_id, &user, &db_pool).await?;
+    assert_eq!(find_recent_post_with_link(sphere.sphere_id, edited_link_url, DUPLICATE_LINK_WINDOW, &db_pool).await?, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_post_duplicate_link() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (sphere, _) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let link = Link::new(LinkType::Link, Some(String::from("https://example.com/article")), None, None);
+    let normalized_duplicate_link = Link::new(LinkType::Link, Some(String::from("https://www.example.com/article/?fbclid=123")), None, None);
+
+    let post = create_post(
+        &sphere.sphere_name, None, "1", "body", None, link.clone(), PostTags::default(), &user, &db_pool
+    ).await?;
+    // duplicate links are allowed by default
+    let duplicate_post = create_post(
+        &sphere.sphere_name, None, "2", "body", None, link.clone(), PostTags::default(), &user, &db_pool
+    ).await?;
+
+    let other_user = create_user("other", &db_pool).await;
+    assert_eq!(
+        set_sphere_reject_duplicate_links(&sphere.sphere_name, true, &other_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    let sphere = set_sphere_reject_duplicate_links(&sphere.sphere_name, true, &user, &db_pool).await?;
+    assert!(sphere.reject_duplicate_links);
+
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "3", "body", None, link, PostTags::default(), &user, &db_pool).await,
+        Err(AppError::DuplicateLink(duplicate_post.post_id)),
+    );
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "4", "body", None, normalized_duplicate_link, PostTags::default(), &user, &db_pool).await,
+        Err(AppError::DuplicateLink(duplicate_post.post_id)),
+    );
+    assert!(post.post_id < duplicate_post.post_id);
+
+    let other_link = Link::new(LinkType::Link, Some(String::from("https://example.com/other_article")), None, None);
+    create_post(&sphere.sphere_name, None, "5", "body", None, other_link, PostTags::default(), &user, &db_pool).await?;
+    create_post(&sphere.sphere_name, None, "6", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_post_min_account_age() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let new_user = create_user("new", &db_pool).await;
+    let old_user = create_user("old", &db_pool).await;
+    set_user_timestamp(old_user.user_id, chrono::Utc::now() - chrono::Days::new(8), &db_pool).await?;
+    let old_user = User::get(old_user.user_id, &db_pool).await.expect("Should load old user.");
+
+    // no minimum account age by default
+    create_post(&sphere.sphere_name, None, "1", "body", None, Link::default(), PostTags::default(), &new_user, &db_pool).await?;
+
+    assert_eq!(
+        set_sphere_min_account_age_days(&sphere.sphere_name, 7, &new_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(set_sphere_min_account_age_days(&sphere.sphere_name, -1, &lead, &db_pool).await.is_err());
+    assert!(set_sphere_min_account_age_days(&sphere.sphere_name, MAX_MIN_ACCOUNT_AGE_DAYS + 1, &lead, &db_pool).await.is_err());
+    let sphere = set_sphere_min_account_age_days(&sphere.sphere_name, 7, &lead, &db_pool).await?;
+    assert_eq!(sphere.min_account_age_days, 7);
+
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "2", "body", None, Link::default(), PostTags::default(), &new_user, &db_pool).await,
+        Err(AppError::AccountTooNew(7)),
+    );
+    let post = create_post(&sphere.sphere_name, None, "3", "body", None, Link::default(), PostTags::default(), &old_user, &db_pool).await?;
+    assert_eq!(post.creator_id, old_user.user_id);
+    // moderators are not restricted
+    create_post(&sphere.sphere_name, None, "4", "body", None, Link::default(), PostTags::default(), &lead, &db_pool).await?;
+
+    let sphere = set_sphere_min_account_age_days(&sphere.sphere_name, 0, &lead, &db_pool).await?;
+    create_post(&sphere.sphere_name, None, "5", "body", None, Link::default(), PostTags::default(), &new_user, &db_pool).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_post_trusted_user() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    set_sphere_min_account_age_days(&sphere.sphere_name, 7, &lead, &db_pool).await?;
+    set_sphere_min_karma(&sphere.sphere_name, 5, KarmaType::Total, &lead, &db_pool).await?;
+
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "1", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::AccountTooNew(7)),
+    );
+
+    // trusted users are exempted from the account age and karma requirements
+    let trusted_user = add_trusted_user(&user.username, &sphere.sphere_name, &lead, &db_pool).await?;
+    let post = create_post(&sphere.sphere_name, None, "2", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+    assert_eq!(post.creator_id, user.user_id);
+
+    remove_trusted_user(trusted_user.trusted_user_id, &lead, &db_pool).await?;
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "3", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::AccountTooNew(7)),
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_post_min_karma() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+
+    // no minimum karma by default
+    let post = create_post(&sphere.sphere_name, None, "1", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+    let comment = create_comment(post.post_id, None, "a", None, false, &user, &db_pool).await?;
+
+    assert_eq!(
+        set_sphere_min_karma(&sphere.sphere_name, 5, KarmaType::Total, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(set_sphere_min_karma(&sphere.sphere_name, -1, KarmaType::Total, &lead, &db_pool).await.is_err());
+    let sphere = set_sphere_min_karma(&sphere.sphere_name, 5, KarmaType::Total, &lead, &db_pool).await?;
+    assert_eq!(sphere.min_karma, 5);
+    assert_eq!(sphere.min_karma_type, KarmaType::Total);
+
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "2", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::InsufficientKarma(5)),
+    );
+    // moderators are not restricted
+    create_post(&sphere.sphere_name, None, "3", "body", None, Link::default(), PostTags::default(), &lead, &db_pool).await?;
+
+    set_post_score(post.post_id, 3, &db_pool).await?;
+    set_comment_score(comment.comment_id, 3, &db_pool).await?;
+    create_post(&sphere.sphere_name, None, "4", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+
+    set_sphere_min_karma(&sphere.sphere_name, 5, KarmaType::Post, &lead, &db_pool).await?;
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "5", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::InsufficientKarma(5)),
+    );
+    set_sphere_min_karma(&sphere.sphere_name, 3, KarmaType::Post, &lead, &db_pool).await?;
+    create_post(&sphere.sphere_name, None, "6", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+
+    set_sphere_min_karma(&sphere.sphere_name, 5, KarmaType::Comment, &lead, &db_pool).await?;
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "7", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::InsufficientKarma(5)),
+    );
+    set_comment_score(comment.comment_id, 5, &db_pool).await?;
+    create_post(&sphere.sphere_name, None, "8", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_post_allowed_post_types() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let link = Link::new(LinkType::Link, Some(String::from("https://example.com/article")), None, None);
+    let image = Link::new(LinkType::Image, Some(String::from("https://example.com/image.png")), None, None);
+    assert_eq!(sphere.allowed_post_types, AllowedPostTypes::Any);
+
+    assert_eq!(
+        set_sphere_allowed_post_types(&sphere.sphere_name, AllowedPostTypes::Link, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    let sphere = set_sphere_allowed_post_types(&sphere.sphere_name, AllowedPostTypes::Link, &lead, &db_pool).await?;
+    assert_eq!(sphere.allowed_post_types, AllowedPostTypes::Link);
+
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "1", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::PostTypeNotAllowed),
+    );
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "2", "body", None, image.clone(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::PostTypeNotAllowed),
+    );
+    let link_post = create_post(&sphere.sphere_name, None, "3", "body", None, link.clone(), PostTags::default(), &user, &db_pool).await?;
+    assert_eq!(link_post.link.link_type, LinkType::Link);
+
+    let sphere = set_sphere_allowed_post_types(&sphere.sphere_name, AllowedPostTypes::Text, &lead, &db_pool).await?;
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "4", "body", None, link, PostTags::default(), &user, &db_pool).await,
+        Err(AppError::PostTypeNotAllowed),
+    );
+    create_post(&sphere.sphere_name, None, "5", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+
+    let sphere = set_sphere_allowed_post_types(&sphere.sphere_name, AllowedPostTypes::Image, &lead, &db_pool).await?;
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "6", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::PostTypeNotAllowed),
+    );
+    let image_post = create_post(&sphere.sphere_name, None, "7", "body", None, image.clone(), PostTags::default(), &user, &db_pool).await?;
+
+    // editing a post cannot change it to a type that is not allowed
+    assert_eq!(
+        update_post(image_post.post_id, "7", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::PostTypeNotAllowed),
+    );
+    let updated_post = update_post(image_post.post_id, "8", "body", None, image, PostTags::default(), &user, &db_pool).await?;
+    assert_eq!(updated_post.link.link_type, LinkType::Image);
+    assert_eq!(updated_post.title, "8");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_post_title_rules() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let (sphere, post) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let title_regex = r"^\[(News|Question)\] ";
+
+    assert_eq!(
+        set_sphere_title_rules(&sphere.sphere_name, 10, Some(30), Some(title_regex), &create_user("user", &db_pool).await, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(set_sphere_title_rules(&sphere.sphere_name, -1, None, None, &lead, &db_pool).await.is_err());
+    assert!(set_sphere_title_rules(&sphere.sphere_name, 10, Some(5), None, &lead, &db_pool).await.is_err());
+    assert!(set_sphere_title_rules(&sphere.sphere_name, 0, Some(MAX_TITLE_LENGTH as i32 + 1), None, &lead, &db_pool).await.is_err());
+    assert!(set_sphere_title_rules(&sphere.sphere_name, 0, None, Some("[unclosed"), &lead, &db_pool).await.is_err());
+
+    let sphere = set_sphere_title_rules(&sphere.sphere_name, 10, Some(30), Some(title_regex), &lead, &db_pool).await?;
+    assert_eq!(sphere.min_title_length, 10);
+    assert_eq!(sphere.max_title_length, Some(30));
+    assert_eq!(sphere.title_regex.as_deref(), Some(title_regex));
+
+    let create_post_with_title = async |title: &str| create_post(
+        &sphere.sphere_name, None, title, "body", None, Link::default(), PostTags::default(), &lead, &db_pool
+    ).await;
+    assert_eq!(create_post_with_title("[News] a").await, Err(AppError::TitleTooShort(10)));
+    assert_eq!(create_post_with_title("[News] a title that is way too long").await, Err(AppError::TitleTooLong(30)));
+    assert_eq!(create_post_with_title("A title without tag").await, Err(AppError::TitleFormatMismatch(title_regex.to_string())));
+    assert_eq!(create_post_with_title("[Other] a title").await, Err(AppError::TitleFormatMismatch(title_regex.to_string())));
+    let valid_post = create_post_with_title("[Question] a title").await?;
+    assert_eq!(valid_post.title, "[Question] a title");
+
+    // title rules also apply when editing posts
+    assert_eq!(
+        update_post(post.post_id, "Untagged title", "body", None, Link::default(), PostTags::default(), &lead, &db_pool).await,
+        Err(AppError::TitleFormatMismatch(title_regex.to_string())),
+    );
+    let updated_post = update_post(post.post_id, "[News] tagged title", "body", None, Link::default(), PostTags::default(), &lead, &db_pool).await?;
+    assert_eq!(updated_post.title, "[News] tagged title");
+
+    let sphere = set_sphere_title_rules(&sphere.sphere_name, 0, None, Some(""), &lead, &db_pool).await?;
+    assert_eq!(sphere.title_regex, None);
+    create_post(&sphere.sphere_name, None, "a", "body", None, Link::default(), PostTags::default(), &lead, &db_pool).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_post_require_category() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_test_user(&db_pool).await;
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let (other_sphere, _) = create_sphere_with_post("other", &mut lead, &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+    let category = set_sphere_category(&sphere.sphere_name, "a", Color::Orange, "a", true, &lead, &db_pool).await?;
+    let inactive_category = set_sphere_category(&sphere.sphere_name, "b", Color::Blue, "b", false, &lead, &db_pool).await?;
+    let other_category = set_sphere_category(&other_sphere.sphere_name, "a", Color::Orange, "a", true, &lead, &db_pool).await?;
+
+    // categories are optional by default
+    create_post(&sphere.sphere_name, None, "1", "body", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+
+    assert_eq!(
+        set_sphere_require_category(&sphere.sphere_name, true, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    let sphere = set_sphere_require_category(&sphere.sphere_name, true, &lead, &db_pool).await?;
+    assert!(sphere.require_category);
+
+    for category_id in [None, Some(inactive_category.category_id), Some(other_category.category_id), Some(i64::MAX)] {
+        assert_eq!(
+            create_post(&sphere.sphere_name, None, "2", "body", None, Link::default(), PostTags::new(false, false, false, category_id), &user, &db_pool).await,
+            Err(AppError::CategoryRequired),
+        );
+    }
+    let post = create_post(
+        &sphere.sphere_name, None, "3", "body", None, Link::default(), PostTags::new(false, false, false, Some(category.category_id)), &user, &db_pool
+    ).await?;
+    assert_eq!(post.category_id, Some(category.category_id));
+
+    // the category is also required when editing a post
+    for category_id in [None, Some(inactive_category.category_id), Some(other_category.category_id)] {
+        assert_eq!(
+            update_post(post.post_id, "3", "body", None, Link::default(), PostTags::new(false, false, false, category_id), &user, &db_pool).await,
+            Err(AppError::CategoryRequired),
+        );
+    }
+    let updated_post = update_post(
+        post.post_id, "4", "body", None, Link::default(), PostTags::new(false, false, false, Some(category.category_id)), &user, &db_pool
+    ).await?;
+    assert_eq!(updated_post.category_id, Some(category.category_id));
+    assert_eq!(updated_post.title, "4");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_update_post() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -2203,6 +3500,186 @@ async fn test_update_post() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_crosspost() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let lead = create_user("lead", &db_pool).await;
+
+    let (sphere_1, _) = create_sphere_with_post("1", &mut user, &db_pool).await;
+    let sphere_2 = create_sphere("2", "b", false, &lead, &db_pool).await?;
+    let sphere_3 = create_sphere("3", "c", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    let link = Link::new(
+        LinkType::Link,
+        Some(String::from("https://example.com/article")),
+        None,
+        Some(String::from("thumbnail"))
+    );
+    let original_post = create_post(
+        &sphere_1.sphere_name,
+        None,
+        "original",
+        "body",
+        None,
+        link.clone(),
+        PostTags::new(true, false, false, None),
+        &user,
+        &db_pool,
+    ).await?;
+
+    // cannot crosspost in the same sphere or in a missing sphere
+    assert!(crosspost(original_post.post_id, &sphere_1.sphere_name, &user, &db_pool).await.is_err());
+    assert!(crosspost(original_post.post_id, "missing", &user, &db_pool).await.is_err());
+
+    // crossposts respect the publish permissions of the target sphere
+    set_sphere_require_rule_acceptance(&sphere_2.sphere_name, true, &lead, &db_pool).await?;
+    assert_eq!(
+        crosspost(original_post.post_id, &sphere_2.sphere_name, &user, &db_pool).await,
+        Err(AppError::RulesNotAccepted),
+    );
+    accept_rules(&sphere_2.sphere_name, &user, &db_pool).await?;
+
+    let crosspost_1 = crosspost(original_post.post_id, &sphere_2.sphere_name, &user, &db_pool).await?;
+    assert_eq!(crosspost_1.crosspost_of, Some(original_post.post_id));
+    assert_eq!(crosspost_1.title, original_post.title);
+    assert_eq!(crosspost_1.link, link);
+    assert!(crosspost_1.is_spoiler);
+    assert_eq!(crosspost_1.sphere_id, sphere_2.sphere_id);
+    assert_eq!(crosspost_1.creator_id, user.user_id);
+    assert_eq!(crosspost_1.creator_name, user.username);
+
+    let crosspost_1_with_info = get_post_with_info_by_id(crosspost_1.post_id, Some(&user), &db_pool).await?;
+    assert_eq!(
+        crosspost_1_with_info.crosspost_header,
+        Some(CrosspostHeader {
+            post_id: original_post.post_id,
+            sphere_name: sphere_1.sphere_name.clone(),
+            satellite_id: None,
+            is_active: true,
+        })
+    );
+    assert_eq!(crosspost_1_with_info.vote.map(|vote| vote.value), Some(VoteValue::Up));
+    assert_eq!(get_post_with_info_by_id(original_post.post_id, None, &db_pool).await?.crosspost_header, None);
+
+    // crossposting a crosspost links to the original post
+    let crosspost_2 = crosspost(crosspost_1.post_id, &sphere_3.sphere_name, &user, &db_pool).await?;
+    assert_eq!(crosspost_2.crosspost_of, Some(original_post.post_id));
+
+    // deleting the original does not break crossposts
+    delete_post(original_post.post_id, &user, &db_pool).await?;
+    let crosspost_1_with_info = get_post_with_info_by_id(crosspost_1.post_id, None, &db_pool).await?;
+    assert_eq!(crosspost_1_with_info.post.title, original_post.title);
+    assert_eq!(crosspost_1_with_info.post.link, link);
+    assert_eq!(
+        crosspost_1_with_info.crosspost_header,
+        Some(CrosspostHeader {
+            post_id: original_post.post_id,
+            sphere_name: sphere_1.sphere_name.clone(),
+            satellite_id: None,
+            is_active: false,
+        })
+    );
+    // deleted posts cannot be crossposted
+    assert!(crosspost(original_post.post_id, &sphere_3.sphere_name, &user, &db_pool).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_crosspost_unpublished_and_gallery_post() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let lead = create_user("lead", &db_pool).await;
+
+    let (sphere_1, gallery_post) = create_sphere_with_post("1", &mut user, &db_pool).await;
+    let sphere_2 = create_sphere("2", "b", false, &lead, &db_pool).await?;
+
+    // scheduled and unapproved posts cannot be crossposted
+    let scheduled_post = create_scheduled_post(
+        NewPost { sphere_name: &sphere_1.sphere_name, title: "scheduled", body: "body", ..Default::default() },
+        chrono::Utc::now() + chrono::TimeDelta::hours(1),
+        &user,
+        &db_pool,
+    ).await?;
+    assert!(crosspost(scheduled_post.post_id, &sphere_2.sphere_name, &user, &db_pool).await.is_err());
+
+    set_sphere_require_post_approval(&sphere_1.sphere_name, true, &user, &db_pool).await?;
+    let other_user = create_user("other", &db_pool).await;
+    let unapproved_post = create_post(
+        &sphere_1.sphere_name,
+        None,
+        "unapproved",
+        "body",
+        None,
+        Link::default(),
+        PostTags::default(),
+        &other_user,
+        &db_pool,
+    ).await?;
+    assert!(!unapproved_post.is_approved);
+    assert!(crosspost(unapproved_post.post_id, &sphere_2.sphere_name, &other_user, &db_pool).await.is_err());
+
+    // the images of a gallery are carried over to the crosspost
+    let image_vec = insert_post_images(
+        gallery_post.post_id,
+        vec![
+            PostImageInput {
+                url: String::from("https://a.com/1.webp"),
+                thumbnail_url: Some(String::from("https://a.com/1_thumbnail.webp")),
+                alt_text: Some(String::from("first image")),
+            },
+            PostImageInput {
+                url: String::from("https://a.com/2.webp"),
+                ..Default::default()
+            },
+        ],
+        &user,
+        &db_pool,
+    ).await?;
+    let gallery_crosspost = crosspost(gallery_post.post_id, &sphere_2.sphere_name, &user, &db_pool).await?;
+    assert_eq!(gallery_crosspost.link.link_type, LinkType::Gallery);
+    let crosspost_image_vec = get_post_image_vec(gallery_crosspost.post_id, &db_pool).await?;
+    assert_eq!(crosspost_image_vec.len(), image_vec.len());
+    for (crosspost_image, image) in crosspost_image_vec.iter().zip(image_vec) {
+        assert_eq!(crosspost_image.post_id, gallery_crosspost.post_id);
+        assert_eq!(crosspost_image.position, image.position);
+        assert_eq!(crosspost_image.url, image.url);
+        assert_eq!(crosspost_image.thumbnail_url, image.thumbnail_url);
+        assert_eq!(crosspost_image.alt_text, image.alt_text);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_post_edit_count() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    assert_eq!(post.edit_count, 0);
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.edit_count, 0);
+
+    for expected_edit_count in 1..=3 {
+        let updated_post = update_post(
+            post.post_id,
+            &format!("title {expected_edit_count}"),
+            "body",
+            None,
+            Link::default(),
+            PostTags::default(),
+            &user,
+            &db_pool
+        ).await?;
+        assert_eq!(updated_post.edit_count, expected_edit_count);
+    }
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.edit_count, 3);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_update_post_in_satellite() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -2386,6 +3863,156 @@ async fn test_delete_post() {
     );
 }
 
+#[tokio::test]
+async fn test_insert_post_images() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_user("test", &db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    let (_, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    assert!(get_post_image_vec(post.post_id, &db_pool).await?.is_empty());
+
+    let first_image_vec = vec![
+        PostImageInput {
+            url: String::from("https://a.com/1.webp"),
+            thumbnail_url: Some(String::from("https://a.com/1_thumbnail.webp")),
+            alt_text: Some(String::from("first image")),
+        },
+        PostImageInput {
+            url: String::from("https://a.com/2.webp"),
+            thumbnail_url: None,
+            alt_text: None,
+        },
+    ];
+    let second_image_vec = vec![
+        PostImageInput {
+            url: String::from("https://a.com/3.webp"),
+            thumbnail_url: Some(String::from("https://a.com/3_thumbnail.webp")),
+            alt_text: Some(String::from("third image")),
+        },
+    ];
+
+    assert_eq!(
+        insert_post_images(post.post_id, first_image_vec.clone(), &other_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    insert_post_images(post.post_id, first_image_vec.clone(), &user, &db_pool).await?;
+    let image_vec = insert_post_images(post.post_id, second_image_vec.clone(), &user, &db_pool).await?;
+
+    let expected_image_vec: Vec<PostImageInput> = first_image_vec.into_iter().chain(second_image_vec).collect();
+    assert_eq!(image_vec.len(), expected_image_vec.len());
+    for (position, (image, expected_image)) in image_vec.iter().zip(expected_image_vec).enumerate() {
+        assert_eq!(image.post_id, post.post_id);
+        assert_eq!(image.position, position as i32);
+        assert_eq!(image.url, expected_image.url);
+        assert_eq!(image.thumbnail_url, expected_image.thumbnail_url);
+        assert_eq!(image.alt_text, expected_image.alt_text);
+    }
+    assert_eq!(get_post_image_vec(post.post_id, &db_pool).await?, image_vec);
+
+    let gallery_post = get_post_by_id(post.post_id, &db_pool).await?;
+    assert_eq!(gallery_post.link.link_type, LinkType::Gallery);
+    assert_eq!(gallery_post.link.link_url, Some(image_vec[0].url.clone()));
+    assert_eq!(gallery_post.link.link_thumbnail_url, image_vec[0].thumbnail_url.clone());
+    assert_eq!(gallery_post.link.alt_text, image_vec[0].alt_text.clone());
+
+    let too_long_alt_text_vec = vec![
+        PostImageInput {
+            url: String::from("https://a.com/4.webp"),
+            thumbnail_url: None,
+            alt_text: Some("a".repeat(MAX_ALT_TEXT_LENGTH as usize + 1)),
+        },
+    ];
+    assert!(insert_post_images(post.post_id, too_long_alt_text_vec, &user, &db_pool).await.is_err());
+
+    let too_many_url_vec = (0..MAX_POST_IMAGES).map(|i| PostImageInput {
+        url: format!("https://a.com/{i}.webp"),
+        ..Default::default()
+    }).collect();
+    assert!(insert_post_images(post.post_id, too_many_url_vec, &user, &db_pool).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_post_images() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_user("test", &db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+    let object_store = InMemory::new();
+    let container_url = "https://container.com";
+    let bucket_name = "post_images";
+
+    let (_, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+
+    assert_eq!(
+        add_post_images(
+            get_multipart_images_with_string(IMAGE_FILE_PARAM, 2, POST_ID_PARAM, &post.post_id.to_string()).await,
+            &object_store,
+            container_url,
+            bucket_name,
+            &other_user,
+            &db_pool,
+        ).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+
+    let image_vec = add_post_images(
+        get_multipart_images_with_string(IMAGE_FILE_PARAM, 3, POST_ID_PARAM, &post.post_id.to_string()).await,
+        &object_store,
+        container_url,
+        bucket_name,
+        &user,
+        &db_pool,
+    ).await?;
+
+    assert_eq!(image_vec.len(), 3);
+    for (position, image) in image_vec.iter().enumerate() {
+        assert_eq!(image.position, position as i32);
+        assert!(image.url.starts_with(&format!("{container_url}/{bucket_name}/post_{}_", post.post_id)));
+        let file_name = image.url.rsplit('/').next().expect("Image url should have a file name");
+        assert!(object_store.head(&object_store::path::Path::from(file_name)).await.is_ok());
+        let thumbnail_url = image.thumbnail_url.clone().expect("Image should have a thumbnail");
+        let thumbnail_file_name = thumbnail_url.rsplit('/').next().expect("Thumbnail url should have a file name");
+        assert!(object_store.head(&object_store::path::Path::from(thumbnail_file_name)).await.is_ok());
+    }
+    assert_eq!(get_post_image_vec(post.post_id, &db_pool).await?, image_vec);
+
+    let too_many_url_vec = (image_vec.len()..MAX_POST_IMAGES).map(|i| PostImageInput {
+        url: format!("https://a.com/{i}.webp"),
+        ..Default::default()
+    }).collect();
+    insert_post_images(post.post_id, too_many_url_vec, &user, &db_pool).await?;
+    let num_stored_objects = object_store.list(None).count().await;
+    assert_eq!(
+        add_post_images(
+            get_multipart_images_with_string(IMAGE_FILE_PARAM, 1, POST_ID_PARAM, &post.post_id.to_string()).await,
+            &object_store,
+            container_url,
+            bucket_name,
+            &user,
+            &db_pool,
+        ).await,
+        Err(AppError::ValidationError(format!("A post cannot have more than {MAX_POST_IMAGES} images."))),
+    );
+    assert_eq!(object_store.list(None).count().await, num_stored_objects);
+
+    let (_, other_post) = create_sphere_with_post("other_sphere", &mut user, &db_pool).await;
+    assert_eq!(
+        add_post_images(
+            get_invalid_multipart_image_with_string(IMAGE_FILE_PARAM, POST_ID_PARAM, &other_post.post_id.to_string()).await,
+            &object_store,
+            container_url,
+            bucket_name,
+            &user,
+            &db_pool,
+        ).await,
+        Err(AppError::new(POST_IMAGE_FILE_INFER_ERROR_STR)),
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn increment_post_comment_count() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -2402,6 +4029,106 @@ async fn increment_post_comment_count() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_recompute_post_comment_counts() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (sphere, post_1) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let post_2 = create_simple_post(&sphere.sphere_name, None, "post 2", "body", None, &user, &db_pool).await.post;
+    let rule = add_rule(&sphere.sphere_name, 0, "1", "2", false, &user, &db_pool).await.expect("Should add rule");
+
+    let mut comment_vec = Vec::new();
+    for i in 0..4 {
+        comment_vec.push(create_comment(post_1.post_id, None, &format!("comment {i}"), None, false, &user, &db_pool).await?);
+    }
+    create_comment(post_2.post_id, None, "comment", None, false, &user, &db_pool).await?;
+    delete_comment(comment_vec[0].comment_id, &user, &db_pool).await?;
+    moderate_comment(comment_vec[1].comment_id, rule.rule_id, "moderated", &user, &db_pool).await?;
+
+    // counters in sync with the number of active comments are left untouched
+    sqlx::query("UPDATE posts SET num_comments = 2 WHERE post_id = $1")
+        .bind(post_1.post_id)
+        .execute(&db_pool)
+        .await?;
+    assert_eq!(recompute_post_comment_counts(&db_pool).await?, 0);
+
+    // desynchronize the counters
+    sqlx::query("UPDATE posts SET num_comments = 42 WHERE post_id = $1")
+        .bind(post_1.post_id)
+        .execute(&db_pool)
+        .await?;
+    sqlx::query("UPDATE posts SET num_comments = 0 WHERE post_id = $1")
+        .bind(post_2.post_id)
+        .execute(&db_pool)
+        .await?;
+
+    assert_eq!(recompute_post_comment_counts(&db_pool).await?, 2);
+    assert_eq!(get_post_by_id(post_1.post_id, &db_pool).await?.num_comments, 2);
+    assert_eq!(get_post_by_id(post_2.post_id, &db_pool).await?.num_comments, 1);
+    assert_eq!(recompute_post_comment_counts(&db_pool).await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_accepted_answer() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    let (sphere, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let other_post = create_simple_post(&sphere.sphere_name, None, "other", "body", None, &user, &db_pool).await.post;
+    assert_eq!(post.accepted_comment_id, None);
+
+    let pinned_comment = create_comment(post.post_id, None, "pinned", None, true, &user, &db_pool).await?;
+    let comment = create_comment(post.post_id, None, "answer", None, false, &other_user, &db_pool).await?;
+    let reply = create_comment(post.post_id, Some(comment.comment_id), "reply", None, false, &other_user, &db_pool).await?;
+    let other_post_comment = create_comment(other_post.post_id, None, "other", None, false, &other_user, &db_pool).await?;
+    let deleted_comment = create_comment(post.post_id, None, "deleted", None, false, &other_user, &db_pool).await?;
+    delete_comment(deleted_comment.comment_id, &other_user, &db_pool).await?;
+
+    // only the author of the post can accept an answer
+    assert_eq!(
+        set_accepted_answer(post.post_id, Some(comment.comment_id), &other_user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.accepted_comment_id, None);
+
+    // only active top-level comments of the post can be accepted
+    assert!(set_accepted_answer(post.post_id, Some(reply.comment_id), &user, &db_pool).await.is_err());
+    assert!(set_accepted_answer(post.post_id, Some(other_post_comment.comment_id), &user, &db_pool).await.is_err());
+    assert!(set_accepted_answer(post.post_id, Some(deleted_comment.comment_id), &user, &db_pool).await.is_err());
+    assert_eq!(set_accepted_answer(post.post_id, Some(i64::MAX), &user, &db_pool).await, Err(AppError::NotFound));
+
+    let updated_post = set_accepted_answer(post.post_id, Some(comment.comment_id), &user, &db_pool).await?;
+    assert_eq!(updated_post.accepted_comment_id, Some(comment.comment_id));
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.accepted_comment_id, Some(comment.comment_id));
+
+    // the accepted answer comes before the pinned comments
+    let comment_tree = get_post_comment_tree(
+        post.post_id, SortType::Comment(CommentSortType::Recent), None, None, COMMENT_BATCH_SIZE, 0, &db_pool
+    ).await?;
+    assert_eq!(
+        comment_tree.iter().map(|comment| comment.comment.comment_id).collect::<Vec<_>>(),
+        vec![comment.comment_id, pinned_comment.comment_id, deleted_comment.comment_id],
+    );
+    assert_eq!(comment_tree[0].child_comments.len(), 1);
+    assert_eq!(comment_tree[0].child_comments[0].comment.comment_id, reply.comment_id);
+
+    let updated_post = set_accepted_answer(post.post_id, None, &user, &db_pool).await?;
+    assert_eq!(updated_post.accepted_comment_id, None);
+    let comment_tree = get_post_comment_tree(
+        post.post_id, SortType::Comment(CommentSortType::Recent), None, None, COMMENT_BATCH_SIZE, 0, &db_pool
+    ).await?;
+    assert_eq!(
+        comment_tree.iter().map(|comment| comment.comment.comment_id).collect::<Vec<_>>(),
+        vec![pinned_comment.comment_id, deleted_comment.comment_id, comment.comment_id],
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_update_post_scores() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -2413,7 +4140,8 @@ async fn test_update_post_scores() -> Result<(), AppError> {
     // wait to have a meaningful difference in scores after update
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    update_post_scores(&db_pool).await.expect("Post scores should be updatable.");
+    let num_updated_posts = update_post_scores(POST_SCORE_UPDATE_BATCH_SIZE, &db_pool).await.expect("Post scores should be updatable.");
+    assert_eq!(num_updated_posts, 1);
 
     let updated_post = get_post_with_info_by_id(post.post_id, None, &db_pool).await.expect("Should be able to get updated post.");
 
@@ -2428,6 +4156,115 @@ async fn test_update_post_scores() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_update_post_scores_in_batches() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let num_posts = 23;
+    let num_old_posts = 3;
+    let batch_size = 5;
+    let (_, _, post_vec) = create_sphere_with_posts(
+        "sphere",
+        None,
+        num_posts,
+        Some(vec![10; num_posts]),
+        vec![false; num_posts],
+        &mut user,
+        &db_pool,
+    ).await?;
+
+    let mut old_post_id_set = HashSet::new();
+    for post in post_vec.iter().step_by(num_posts / num_old_posts).take(num_old_posts) {
+        set_post_timestamp(post.post.post_id, -3, &db_pool).await?;
+        old_post_id_set.insert(post.post.post_id);
+    }
+
+    let mut initial_post_map = HashMap::new();
+    for post in &post_vec {
+        let post = get_post_by_id(post.post.post_id, &db_pool).await?;
+        initial_post_map.insert(post.post_id, post);
+    }
+
+    // wait to have a meaningful difference in scores after update
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let num_updated_posts = update_post_scores(batch_size, &db_pool).await?;
+    assert_eq!(num_updated_posts, (num_posts - num_old_posts) as u64);
+
+    for (post_id, initial_post) in initial_post_map {
+        let updated_post = get_post_by_id(post_id, &db_pool).await?;
+        if old_post_id_set.contains(&post_id) {
+            assert_eq!(updated_post.scoring_timestamp, initial_post.scoring_timestamp);
+            assert_eq!(updated_post.recommended_score, initial_post.recommended_score);
+        } else {
+            assert!(updated_post.scoring_timestamp > initial_post.scoring_timestamp);
+            assert!(updated_post.recommended_score < initial_post.recommended_score);
+            assert!(updated_post.trending_score < initial_post.trending_score);
+            test_post_score(&updated_post);
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_post_scores_materializes_scores() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let num_posts = 50;
+    let score_vec: Vec<i32> = (0..num_posts as i32).map(|i| (i - 10) * 3 + 1).collect();
+    let (sphere, _, _) = create_sphere_with_posts(
+        "sphere",
+        None,
+        num_posts,
+        Some(score_vec),
+        vec![false; num_posts],
+        &mut user,
+        &db_pool,
+    ).await?;
+
+    // wait to have a meaningful difference in scores after update
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let num_updated_posts = update_post_scores(POST_SCORE_UPDATE_BATCH_SIZE, &db_pool).await?;
+    assert_eq!(num_updated_posts, num_posts as u64);
+
+    // The stored scores of every updated row must already match the formulas, without recomputing them at read time
+    let num_materialized_posts = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM posts
+        WHERE
+            sphere_id = $1 AND
+            recommended_score = (
+                LOG10(score_mapping(score)) - 3 * EXTRACT(EPOCH FROM (scoring_timestamp - create_timestamp))/(3600 * 24 * 2)
+            )::REAL AND
+            trending_score = (
+                LOG10(score_mapping(score)) - EXTRACT(EPOCH FROM (scoring_timestamp - create_timestamp))/(3600 * 2)
+            )::REAL"
+    )
+        .bind(sphere.sphere_id)
+        .fetch_one(&db_pool)
+        .await?;
+    assert_eq!(num_materialized_posts as u64, num_updated_posts);
+
+    let post_vec = get_post_vec_by_sphere_name(
+        &sphere.sphere_name,
+        SphereCategoryFilter::All,
+        SortType::Post(PostSortType::Hot),
+        num_posts as i64,
+        0,
+        None,
+        &db_pool,
+    ).await?;
+    assert_eq!(post_vec.len(), num_posts);
+    for post in &post_vec {
+        test_post_score(post);
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_post_scores() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
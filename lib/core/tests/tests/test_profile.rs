@@ -1,16 +1,23 @@
+use object_store::memory::InMemory;
+use object_store::ObjectStoreExt;
+
+use sphare_core_common::constants::IMAGE_FILE_PARAM;
 use sphare_core_common::errors::AppError;
-use sphare_core_content::comment::ssr::create_comment;
+use sphare_core_content::comment::ssr::{create_comment, get_comment_by_id};
 use sphare_core_content::comment::CommentWithContext;
 use sphare_core_content::embed::Link;
-use sphare_core_content::post::ssr::create_post;
+use sphare_core_content::post::ssr::{create_post, get_post_by_id};
 use sphare_core_content::post::{PostTags, PostWithSphereInfo};
-use sphare_core_content::profile::ssr::{get_user_comment_vec, get_user_post_vec};
+use sphare_core_content::profile::ssr::{get_user_comment_vec, get_user_post_vec, is_profile_hidden, set_user_avatar};
+use sphare_core_content::profile::ProfileContent;
 use sphare_core_content::ranking::{CommentSortType, PostSortType, SortType, VoteValue};
 use sphare_core_sphere::satellite::ssr::create_satellite;
+use sphare_core_user::user::ssr::set_public_profile_visible;
+use sphare_core_user::user::User;
 
 use crate::common::{create_user, get_db_pool};
 use crate::data_factory::{create_post_with_comments, create_sphere_with_post_and_comment, create_sphere_with_posts, get_moderated_and_deleted_comments, get_moderated_and_deleted_posts, set_comment_score, set_post_score};
-use crate::utils::{sort_comment_vec, sort_post_vec, COMMENT_SORT_TYPE_ARRAY, POST_SORT_TYPE_ARRAY};
+use crate::utils::{get_invalid_multipart_image_with_string, get_multipart_image, get_multipart_string, sort_comment_vec, sort_post_vec, COMMENT_SORT_TYPE_ARRAY, POST_SORT_TYPE_ARRAY};
 
 mod common;
 mod data_factory;
@@ -79,20 +86,22 @@ async fn test_get_user_post_vec() -> Result<(), AppError> {
         let user_1_post_vec = get_user_post_vec(
             &user_1.username,
             SortType::Post(sort_type),
+            None,
             (num_post + 2) as i64,
             0,
             &db_pool,
-        ).await?;
+        ).await?.content_vec;
         sort_post_vec(&mut user_1_expected_post_vec, sort_type, false);
         assert_eq!(user_1_post_vec, user_1_expected_post_vec);
 
         let user_2_post_vec = get_user_post_vec(
             &user_2.username,
             SortType::Post(sort_type),
+            None,
             num_post as i64,
             0,
             &db_pool,
-        ).await?;
+        ).await?.content_vec;
         sort_post_vec(&mut user_2_expected_post_vec, sort_type, false);
         assert_eq!(user_2_post_vec, user_2_expected_post_vec);
     }
@@ -101,10 +110,11 @@ async fn test_get_user_post_vec() -> Result<(), AppError> {
     let post_vec = get_user_post_vec(
         &user_1.username,
         SortType::Post(PostSortType::Recent),
+        None,
         num_post as i64,
         0,
         &db_pool,
-    ).await.expect("Should get user_post vec");
+    ).await.expect("Should get user_post vec").content_vec;
     assert!(!post_vec.contains(&moderated_post));
     assert!(!post_vec.contains(&deleted_post));
     
@@ -177,17 +187,19 @@ async fn test_get_user_comment_vec() {
         let user_1_comment_vec_1 = get_user_comment_vec(
             &user_1.username,
             SortType::Comment(sort_type),
+            None,
             num_comments as i64,
             0,
             &db_pool
-        ).await.expect("First comment vec should be loaded");
+        ).await.expect("First comment vec should be loaded").content_vec;
         let user_1_comment_vec_2 = get_user_comment_vec(
             &user_1.username,
             SortType::Comment(sort_type),
+            None,
             num_comments as i64,
             num_comments as i64,
             &db_pool
-        ).await.expect("Second post vec should be loaded");
+        ).await.expect("Second post vec should be loaded").content_vec;
         sort_comment_vec(&mut user_1_expected_comment_vec, sort_type, false);
         assert_eq!(user_1_comment_vec_1, user_1_expected_comment_vec[..num_comments]);
         assert_eq!(user_1_comment_vec_2, user_1_expected_comment_vec[num_comments..user_1_expected_comment_vec.len()]);
@@ -196,10 +208,11 @@ async fn test_get_user_comment_vec() {
     let user_2_comment_vec = get_user_comment_vec(
         &user_2.username,
         SortType::Comment(CommentSortType::Best),
+        None,
         num_comments as i64,
         0,
         &db_pool,
-    ).await.expect("Should get user 2 comments");
+    ).await.expect("Should get user 2 comments").content_vec;
 
     assert_eq!(user_2_comment_vec.len(), 1);
     assert_eq!(
@@ -215,10 +228,11 @@ async fn test_get_user_comment_vec() {
     let comment_vec = get_user_comment_vec(
         &user_1.username,
         SortType::Comment(CommentSortType::Recent),
+        None,
         num_comments as i64,
         0,
         &db_pool,
-    ).await.expect("Should get user 1 comments");
+    ).await.expect("Should get user 1 comments").content_vec;
     assert!(!comment_vec.contains(&CommentWithContext::from_comment(
         moderated_comment,
         (&sphere_1).into(),
@@ -229,4 +243,144 @@ async fn test_get_user_comment_vec() {
         (&sphere_1).into(),
         &user_1_post,
     )));
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_hidden_profile() {
+    let db_pool = get_db_pool().await;
+    let mut user = create_user("1", &db_pool).await;
+    let viewer = create_user("2", &db_pool).await;
+
+    let (_, post, comment) = create_sphere_with_post_and_comment("1", &mut user, &db_pool).await;
+
+    assert_eq!(is_profile_hidden(&user.username, None, &db_pool).await, Ok(false));
+    assert_eq!(is_profile_hidden("unknown", None, &db_pool).await, Ok(false));
+
+    set_public_profile_visible(false, &user, &db_pool).await.expect("Should hide profile");
+    let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
+    assert!(!user.public_profile_visible);
+
+    assert_eq!(is_profile_hidden(&user.username, None, &db_pool).await, Ok(true));
+    assert_eq!(is_profile_hidden(&user.username, Some(viewer.user_id), &db_pool).await, Ok(true));
+    assert_eq!(is_profile_hidden(&user.username, Some(user.user_id), &db_pool).await, Ok(false));
+
+    for viewer_id in [None, Some(viewer.user_id)] {
+        let post_content = get_user_post_vec(
+            &user.username,
+            SortType::Post(PostSortType::Recent),
+            viewer_id,
+            10,
+            0,
+            &db_pool,
+        ).await.expect("Should get hidden user posts");
+        assert_eq!(post_content, ProfileContent::hidden());
+
+        let comment_content = get_user_comment_vec(
+            &user.username,
+            SortType::Comment(CommentSortType::Recent),
+            viewer_id,
+            10,
+            0,
+            &db_pool,
+        ).await.expect("Should get hidden user comments");
+        assert_eq!(comment_content, ProfileContent::hidden());
+    }
+
+    let post_content = get_user_post_vec(
+        &user.username,
+        SortType::Post(PostSortType::Recent),
+        Some(user.user_id),
+        10,
+        0,
+        &db_pool,
+    ).await.expect("Should get own posts");
+    assert!(!post_content.is_hidden);
+    assert_eq!(post_content.content_vec.len(), 1);
+    assert_eq!(post_content.content_vec.first().map(|post| post.post.post_id), Some(post.post_id));
+
+    let comment_content = get_user_comment_vec(
+        &user.username,
+        SortType::Comment(CommentSortType::Recent),
+        Some(user.user_id),
+        10,
+        0,
+        &db_pool,
+    ).await.expect("Should get own comments");
+    assert!(!comment_content.is_hidden);
+    assert_eq!(comment_content.content_vec.len(), 1);
+    assert_eq!(comment_content.content_vec.first().map(|comment| comment.comment.comment_id), Some(comment.comment_id));
+
+    set_public_profile_visible(true, &user, &db_pool).await.expect("Should show profile");
+    let post_content = get_user_post_vec(
+        &user.username,
+        SortType::Post(PostSortType::Recent),
+        Some(viewer.user_id),
+        10,
+        0,
+        &db_pool,
+    ).await.expect("Should get visible user posts");
+    assert!(!post_content.is_hidden);
+    assert_eq!(post_content.content_vec.len(), 1);
+}
+
+#[tokio::test]
+async fn test_set_user_avatar() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_user("user", &db_pool).await;
+    let (_, post, comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+    let object_store = InMemory::new();
+    let container_url = "https://objectstorage.com";
+    let bucket_name = "avatar_bucket";
+
+    let avatar_url = set_user_avatar(
+        get_multipart_image(IMAGE_FILE_PARAM).await,
+        &object_store,
+        container_url,
+        bucket_name,
+        &user,
+        &db_pool,
+    ).await?.expect("Avatar url should be set");
+
+    let avatar_filename = avatar_url.split('/').next_back().expect("Should get avatar filename");
+    assert_eq!(avatar_url, format!("{container_url}/{bucket_name}/{avatar_filename}"));
+    assert!(object_store.get(&object_store::path::Path::from(avatar_filename)).await.is_ok());
+
+    let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
+    assert_eq!(user.avatar_url.as_ref(), Some(&avatar_url));
+    assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.creator_avatar_url.as_ref(), Some(&avatar_url));
+    assert_eq!(get_comment_by_id(comment.comment_id, &db_pool).await?.creator_avatar_url.as_ref(), Some(&avatar_url));
+
+    let updated_avatar_url = set_user_avatar(
+        get_multipart_image(IMAGE_FILE_PARAM).await,
+        &object_store,
+        container_url,
+        bucket_name,
+        &user,
+        &db_pool,
+    ).await?.expect("Updated avatar url should be set");
+    assert_ne!(updated_avatar_url, avatar_url);
+    assert!(object_store.get(&object_store::path::Path::from(avatar_filename)).await.is_err());
+
+    let invalid_result = set_user_avatar(
+        get_invalid_multipart_image_with_string(IMAGE_FILE_PARAM, "other", "value").await,
+        &object_store,
+        container_url,
+        bucket_name,
+        &user,
+        &db_pool,
+    ).await;
+    assert!(invalid_result.is_err());
+    let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
+    assert_eq!(user.avatar_url, Some(updated_avatar_url.clone()));
+
+    let missing_file_result = set_user_avatar(
+        get_multipart_string("other", "value").await,
+        &object_store,
+        container_url,
+        bucket_name,
+        &user,
+        &db_pool,
+    ).await;
+    assert!(missing_file_result.is_err());
+
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use sphare_core_common::errors::AppError;
-use sphare_core_content::comment::ssr::get_comment_by_id;
+use sphare_core_content::comment::ssr::{get_comment_by_id, get_comment_tree_by_id, get_post_comment_tree};
 use sphare_core_content::moderation::ssr::ban_user_from_sphere;
-use sphare_core_content::ranking::VoteValue;
+use sphare_core_common::constants::ALLOWED_REACTIONS;
+use sphare_core_content::ranking::{CommentSortType, ReactionCount, SortType, VoteValue};
 use sphare_core_content::{post, ranking};
 use sphare_core_sphere::rule::ssr::add_rule;
 use sphare_core_user::role::AdminRole;
@@ -9,7 +10,7 @@ use sphare_core_user::user::{BanStatus, User};
 
 use crate::common::*;
 use crate::data_factory::{create_sphere_with_post, create_sphere_with_post_and_comment};
-use crate::utils::get_user_comment_vote;
+use crate::utils::{get_user_comment_vote, set_user_ban_until_timestamp};
 
 mod common;
 mod data_factory;
@@ -42,6 +43,8 @@ async fn test_vote_on_content_post() -> Result<(), AppError> {
     assert_eq!(vote.post_id, post.post_id);
     assert_eq!(vote.comment_id, None);
     assert_eq!(post.score + 1, post_with_vote.post.score);
+    assert_eq!(post_with_vote.post.upvotes(), post.upvotes() + 1);
+    assert_eq!(post_with_vote.post.downvotes(), post.downvotes());
 
     // repeating vote just returns same result
     let repeat_vote = ranking::ssr::vote_on_content(
@@ -82,6 +85,8 @@ async fn test_vote_on_content_post() -> Result<(), AppError> {
     assert_eq!(vote.post_id, post.post_id);
     assert_eq!(vote.comment_id, None);
     assert_eq!(post.score - 1, post_with_vote.post.score);
+    assert_eq!(post_with_vote.post.upvotes(), post.upvotes());
+    assert_eq!(post_with_vote.post.downvotes(), post.downvotes() + 1);
 
     ranking::ssr::vote_on_content(
         VoteValue::None,
@@ -95,6 +100,8 @@ async fn test_vote_on_content_post() -> Result<(), AppError> {
     let post_with_vote = post::ssr::get_post_with_info_by_id(post.post_id, Some(&user), &db_pool).await?;
     assert_eq!(post_with_vote.vote, None);
     assert_eq!(post.score, post_with_vote.post.score);
+    assert_eq!(post_with_vote.post.upvotes(), post.upvotes());
+    assert_eq!(post_with_vote.post.downvotes(), post.downvotes());
 
     Ok(())
 }
@@ -180,6 +187,76 @@ async fn test_vote_on_content_comment() {
     assert_eq!(init_comment.score, comment.score);
 }
 
+#[tokio::test]
+async fn test_vote_on_content_comment_score() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let user_1 = create_user("1", &db_pool).await;
+    let user_2 = create_user("2", &db_pool).await;
+
+    let (_, _, init_comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+    let init_comment = get_comment_by_id(init_comment.comment_id, &db_pool).await?;
+
+    let upvote = ranking::ssr::vote_on_content(
+        VoteValue::Up,
+        init_comment.post_id,
+        Some(init_comment.comment_id),
+        None,
+        &user_1,
+        &db_pool,
+    ).await?.expect("Upvote should be returned.");
+    assert_eq!(upvote.value, VoteValue::Up);
+    assert_eq!(upvote.user_id, user_1.user_id);
+    assert_eq!(upvote.post_id, init_comment.post_id);
+    assert_eq!(upvote.comment_id, Some(init_comment.comment_id));
+
+    let downvote = ranking::ssr::vote_on_content(
+        VoteValue::Down,
+        init_comment.post_id,
+        Some(init_comment.comment_id),
+        None,
+        &user_2,
+        &db_pool,
+    ).await?.expect("Downvote should be returned.");
+    assert_eq!(downvote.value, VoteValue::Down);
+    assert_eq!(downvote.user_id, user_2.user_id);
+    assert_eq!(downvote.comment_id, Some(init_comment.comment_id));
+
+    let comment = get_comment_by_id(init_comment.comment_id, &db_pool).await?;
+    assert_eq!(comment.score, init_comment.score);
+    assert_eq!(comment.upvotes(), init_comment.upvotes() + 1);
+    assert_eq!(comment.downvotes(), init_comment.downvotes() + 1);
+
+    // the comment tree contains the vote of the user loading it, used to initialize the vote panel
+    let sort_type = SortType::Comment(CommentSortType::Best);
+    let comment_tree = get_comment_tree_by_id(comment.comment_id, sort_type, None, Some(user_1.user_id), &db_pool).await?;
+    assert_eq!(comment_tree.comment.score, comment.score);
+    assert_eq!(comment_tree.comment.score_minus, comment.score_minus);
+    assert_eq!(comment_tree.vote, Some(upvote.clone()));
+    let comment_tree = get_comment_tree_by_id(comment.comment_id, sort_type, None, Some(user_2.user_id), &db_pool).await?;
+    assert_eq!(comment_tree.vote, Some(downvote.clone()));
+    let comment_tree = get_comment_tree_by_id(comment.comment_id, sort_type, None, Some(user.user_id), &db_pool).await?;
+    assert_eq!(comment_tree.vote, None);
+
+    assert_eq!(
+        ranking::ssr::vote_on_content(
+            VoteValue::None,
+            init_comment.post_id,
+            Some(init_comment.comment_id),
+            Some(upvote.vote_id),
+            &user_1,
+            &db_pool,
+        ).await?,
+        None,
+    );
+    let comment = get_comment_by_id(init_comment.comment_id, &db_pool).await?;
+    assert_eq!(comment.score, init_comment.score - 1);
+    assert_eq!(comment.upvotes(), init_comment.upvotes());
+    assert_eq!(comment.downvotes(), init_comment.downvotes() + 1);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_vote_on_content_with_ban() {
     let db_pool = get_db_pool().await;
@@ -273,4 +350,206 @@ async fn test_vote_on_content_with_ban() {
         &user_2,
         &db_pool,
     ).await.expect_err("User 2 cannot vote anywhere.");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_vote_on_content_with_temporary_ban() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (sphere, post, comment) = create_sphere_with_post_and_comment("a", &mut user, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &user, &db_pool).await?;
+
+    // the banned user is not reloaded, so that only the ban check of the queries applies
+    let banned_user = create_user("banned", &db_pool).await;
+    let vote = ranking::ssr::vote_on_content(VoteValue::Up, post.post_id, None, None, &banned_user, &db_pool).await?.expect("Vote should be created.");
+    ban_user_from_sphere(banned_user.user_id, sphere.sphere_id, post.post_id, None, rule.rule_id, Some(1), &user, &db_pool).await?;
+    assert!(ranking::ssr::vote_on_content(VoteValue::Down, post.post_id, None, Some(vote.vote_id), &banned_user, &db_pool).await.is_err());
+    assert!(ranking::ssr::vote_on_content(VoteValue::Up, post.post_id, Some(comment.comment_id), None, &banned_user, &db_pool).await.is_err());
+
+    // expired bans do not prevent votes
+    let unbanned_user = create_user("unbanned", &db_pool).await;
+    let expired_ban = ban_user_from_sphere(
+        unbanned_user.user_id, sphere.sphere_id, post.post_id, None, rule.rule_id, Some(1), &user, &db_pool
+    ).await?.expect("User should be banned.");
+    set_user_ban_until_timestamp(expired_ban.ban_id, chrono::Utc::now() - chrono::Duration::hours(1), &db_pool).await?;
+    let vote = ranking::ssr::vote_on_content(VoteValue::Up, post.post_id, None, None, &unbanned_user, &db_pool).await?.expect("Vote should be created.");
+    ranking::ssr::vote_on_content(VoteValue::Down, post.post_id, None, Some(vote.vote_id), &unbanned_user, &db_pool).await?;
+    ranking::ssr::vote_on_content(VoteValue::Up, post.post_id, Some(comment.comment_id), None, &unbanned_user, &db_pool).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_and_remove_reaction() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, post, comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+
+    let post_reaction = ranking::ssr::add_reaction(post.post_id, None, " 🎉 ", &user, &db_pool).await?;
+    assert_eq!(post_reaction.post_id, post.post_id);
+    assert_eq!(post_reaction.comment_id, None);
+    assert_eq!(post_reaction.user_id, user.user_id);
+    assert_eq!(post_reaction.reaction, "🎉");
+
+    // adding the same reaction twice returns the existing one
+    let repeat_reaction = ranking::ssr::add_reaction(post.post_id, None, "🎉", &user, &db_pool).await?;
+    assert_eq!(repeat_reaction.reaction_id, post_reaction.reaction_id);
+
+    let comment_reaction = ranking::ssr::add_reaction(post.post_id, Some(comment.comment_id), "🎉", &user, &db_pool).await?;
+    assert_ne!(comment_reaction.reaction_id, post_reaction.reaction_id);
+    assert_eq!(comment_reaction.comment_id, Some(comment.comment_id));
+
+    assert!(ranking::ssr::add_reaction(post.post_id, None, "", &user, &db_pool).await.is_err());
+
+    // toggle the post reaction off and on again
+    ranking::ssr::remove_reaction(post.post_id, None, "🎉", &user, &db_pool).await?;
+    assert!(ranking::ssr::get_post_reaction_counts(post.post_id, Some(user.user_id), &db_pool).await?.is_empty());
+    assert_eq!(
+        ranking::ssr::remove_reaction(post.post_id, None, "🎉", &user, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+    assert_eq!(
+        ranking::ssr::get_comment_reaction_counts(post.post_id, &[comment.comment_id], Some(user.user_id), &db_pool).await?.len(),
+        1,
+    );
+
+    let post_reaction = ranking::ssr::add_reaction(post.post_id, None, "🎉", &user, &db_pool).await?;
+    assert_eq!(post_reaction.reaction, "🎉");
+    assert_eq!(ranking::ssr::get_post_reaction_counts(post.post_id, Some(user.user_id), &db_pool).await?.len(), 1);
+
+    // reactions are kept separate from votes
+    let post_with_info = post::ssr::get_post_with_info_by_id(post.post_id, Some(&user), &db_pool).await?;
+    assert!(post_with_info.vote.is_none());
+    assert_eq!(post_with_info.post.score, post.score);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_reaction_unknown_reaction() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, post, comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+
+    for reaction in ["a", "🦀", "👍👍", "<b>test</b>"] {
+        assert!(matches!(
+            ranking::ssr::add_reaction(post.post_id, None, reaction, &user, &db_pool).await,
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(matches!(
+            ranking::ssr::add_reaction(post.post_id, Some(comment.comment_id), reaction, &user, &db_pool).await,
+            Err(AppError::ValidationError(_))
+        ));
+    }
+    assert!(ranking::ssr::get_post_reaction_counts(post.post_id, Some(user.user_id), &db_pool).await?.is_empty());
+
+    for reaction in ALLOWED_REACTIONS {
+        ranking::ssr::add_reaction(post.post_id, None, reaction, &user, &db_pool).await?;
+    }
+    assert_eq!(
+        ranking::ssr::get_post_reaction_counts(post.post_id, Some(user.user_id), &db_pool).await?.len(),
+        ALLOWED_REACTIONS.len(),
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_reaction_invalid_content() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (_, post, comment) = create_sphere_with_post_and_comment("a", &mut user, &db_pool).await;
+    let (_, other_post) = create_sphere_with_post("b", &mut user, &db_pool).await;
+
+    assert!(ranking::ssr::add_reaction(other_post.post_id, Some(comment.comment_id), "👍", &user, &db_pool).await.is_err());
+    ranking::ssr::add_reaction(post.post_id, Some(comment.comment_id), "👍", &user, &db_pool).await?;
+
+    let mut banned_user = create_user("banned", &db_pool).await;
+    banned_user.ban_status = BanStatus::Permanent;
+    assert!(ranking::ssr::add_reaction(post.post_id, None, "👍", &banned_user, &db_pool).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_reaction_banned_from_sphere() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+
+    let (sphere, post, comment) = create_sphere_with_post_and_comment("a", &mut user, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &user, &db_pool).await?;
+
+    // the banned user is not reloaded, so that only the ban check of the queries applies
+    let banned_user = create_user("banned", &db_pool).await;
+    ban_user_from_sphere(banned_user.user_id, sphere.sphere_id, post.post_id, None, rule.rule_id, Some(1), &user, &db_pool).await?;
+    assert!(ranking::ssr::add_reaction(post.post_id, None, "👍", &banned_user, &db_pool).await.is_err());
+    assert!(ranking::ssr::add_reaction(post.post_id, Some(comment.comment_id), "👍", &banned_user, &db_pool).await.is_err());
+
+    // expired bans do not prevent reactions
+    let unbanned_user = create_user("unbanned", &db_pool).await;
+    ban_user_from_sphere(unbanned_user.user_id, sphere.sphere_id, post.post_id, None, rule.rule_id, Some(0), &user, &db_pool).await?;
+    ranking::ssr::add_reaction(post.post_id, None, "👍", &unbanned_user, &db_pool).await?;
+    ranking::ssr::add_reaction(post.post_id, Some(comment.comment_id), "👍", &unbanned_user, &db_pool).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_reaction_counts() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user_1 = create_user("1", &db_pool).await;
+    let user_2 = create_user("2", &db_pool).await;
+    let user_3 = create_user("3", &db_pool).await;
+
+    let (_, post, comment) = create_sphere_with_post_and_comment("sphere", &mut user_1, &db_pool).await;
+
+    ranking::ssr::add_reaction(post.post_id, None, "👍", &user_1, &db_pool).await?;
+    ranking::ssr::add_reaction(post.post_id, None, "🎉", &user_1, &db_pool).await?;
+    ranking::ssr::add_reaction(post.post_id, None, "🎉", &user_2, &db_pool).await?;
+    ranking::ssr::add_reaction(post.post_id, None, "🎉", &user_3, &db_pool).await?;
+    ranking::ssr::add_reaction(post.post_id, Some(comment.comment_id), "👀", &user_2, &db_pool).await?;
+    ranking::ssr::add_reaction(post.post_id, Some(comment.comment_id), "👀", &user_3, &db_pool).await?;
+
+    let post_reaction_vec = ranking::ssr::get_post_reaction_counts(post.post_id, Some(user_2.user_id), &db_pool).await?;
+    assert_eq!(
+        post_reaction_vec,
+        vec![
+            ReactionCount { post_id: post.post_id, comment_id: None, reaction: String::from("🎉"), count: 3, has_reacted: true },
+            ReactionCount { post_id: post.post_id, comment_id: None, reaction: String::from("👍"), count: 1, has_reacted: false },
+        ]
+    );
+
+    let post_with_info = post::ssr::get_post_with_info_by_id(post.post_id, Some(&user_1), &db_pool).await?;
+    assert_eq!(post_with_info.reactions.len(), 2);
+    assert!(post_with_info.reactions.iter().all(|reaction_count| reaction_count.has_reacted));
+
+    let post_with_info = post::ssr::get_post_with_info_by_id(post.post_id, None, &db_pool).await?;
+    assert_eq!(post_with_info.reactions.len(), 2);
+    assert!(post_with_info.reactions.iter().all(|reaction_count| !reaction_count.has_reacted));
+
+    let comment_reaction_vec = ranking::ssr::get_comment_reaction_counts(post.post_id, &[comment.comment_id], Some(user_1.user_id), &db_pool).await?;
+    let expected_comment_reaction_vec = vec![
+        ReactionCount { post_id: post.post_id, comment_id: Some(comment.comment_id), reaction: String::from("👀"), count: 2, has_reacted: false },
+    ];
+    assert_eq!(comment_reaction_vec, expected_comment_reaction_vec);
+    assert!(ranking::ssr::get_comment_reaction_counts(post.post_id, &[], Some(user_1.user_id), &db_pool).await?.is_empty());
+
+    // comment reactions are loaded with the comments
+    let comment_tree = get_comment_tree_by_id(comment.comment_id, SortType::Comment(CommentSortType::Best), None, Some(user_1.user_id), &db_pool).await?;
+    assert_eq!(comment_tree.reactions, expected_comment_reaction_vec);
+    let comment_tree = get_post_comment_tree(post.post_id, SortType::Comment(CommentSortType::Best), None, Some(user_2.user_id), 10, 0, &db_pool).await?;
+    assert_eq!(comment_tree.len(), 1);
+    assert_eq!(comment_tree[0].reactions.len(), 1);
+    assert!(comment_tree[0].reactions[0].has_reacted);
+
+    ranking::ssr::remove_reaction(post.post_id, None, "🎉", &user_3, &db_pool).await?;
+    let post_reaction_vec = ranking::ssr::get_post_reaction_counts(post.post_id, None, &db_pool).await?;
+    assert_eq!(post_reaction_vec[0].reaction, "🎉");
+    assert_eq!(post_reaction_vec[0].count, 2);
+
+    Ok(())
+}
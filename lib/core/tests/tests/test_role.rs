@@ -324,7 +324,7 @@ async fn test_set_user_admin_role() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
     let ordinary_user = create_user("user", &db_pool).await;
     let moderator = create_user("mod", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
 
     // ordinary user cannot set admin role
     assert_eq!(set_user_admin_role(ordinary_user.user_id, AdminRole::Admin, &ordinary_user, &db_pool).await, Err(AppError::InsufficientPrivileges));
@@ -352,3 +352,73 @@ async fn test_set_user_admin_role() -> Result<(), AppError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_set_user_sphere_role_prevents_privilege_escalation() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let lead = create_user("lead", &db_pool).await;
+    let manager = create_user("manager", &db_pool).await;
+    let other_manager = create_user("other_manager", &db_pool).await;
+    let moderator = create_user("moderator", &db_pool).await;
+    let user = create_user("user", &db_pool).await;
+
+    let sphere = sphere::ssr::create_sphere("sphere", "sphere", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("Should be able to reload user.");
+    set_user_sphere_role(&manager.username, &sphere.sphere_name, PermissionLevel::Manage, &lead, &db_pool).await?;
+    set_user_sphere_role(&other_manager.username, &sphere.sphere_name, PermissionLevel::Manage, &lead, &db_pool).await?;
+    set_user_sphere_role(&moderator.username, &sphere.sphere_name, PermissionLevel::Ban, &lead, &db_pool).await?;
+    let manager = User::get(manager.user_id, &db_pool).await.expect("Should be able to reload user.");
+    let moderator = User::get(moderator.user_id, &db_pool).await.expect("Should be able to reload user.");
+
+    // a manager cannot promote someone to leader or to its own level
+    assert_eq!(
+        set_user_sphere_role(&user.username, &sphere.sphere_name, PermissionLevel::Lead, &manager, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        set_user_sphere_role(&user.username, &sphere.sphere_name, PermissionLevel::Manage, &manager, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        set_user_sphere_role(&moderator.username, &sphere.sphere_name, PermissionLevel::Manage, &manager, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    // a manager cannot modify users with an equal or higher level, including themselves
+    assert_eq!(
+        set_user_sphere_role(&other_manager.username, &sphere.sphere_name, PermissionLevel::None, &manager, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        set_user_sphere_role(&lead.username, &sphere.sphere_name, PermissionLevel::Moderate, &manager, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        set_user_sphere_role(&manager.username, &sphere.sphere_name, PermissionLevel::Lead, &manager, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    // sphere roles do not allow setting admin roles
+    assert_eq!(
+        set_user_admin_role(user.user_id, AdminRole::Admin, &lead, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    // moderators without the manage permission cannot grant any role
+    assert_eq!(
+        set_user_sphere_role(&user.username, &sphere.sphere_name, PermissionLevel::Moderate, &moderator, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+
+    // roles are unchanged after rejected attempts
+    assert_eq!(get_user_sphere_role(user.user_id, &sphere.sphere_name, &db_pool).await, Err(AppError::NotFound));
+    assert_eq!(get_user_sphere_role(other_manager.user_id, &sphere.sphere_name, &db_pool).await?.permission_level, PermissionLevel::Manage);
+    assert_eq!(get_user_sphere_role(lead.user_id, &sphere.sphere_name, &db_pool).await?.permission_level, PermissionLevel::Lead);
+
+    // a manager can grant lower levels to users with a lower level
+    let (user_role, _) = set_user_sphere_role(&user.username, &sphere.sphere_name, PermissionLevel::Ban, &manager, &db_pool).await?;
+    assert_eq!(user_role.user_id, user.user_id);
+    assert_eq!(user_role.permission_level, PermissionLevel::Ban);
+    assert_eq!(user_role.grantor_id, manager.user_id);
+    let (moderator_role, _) = set_user_sphere_role(&moderator.username, &sphere.sphere_name, PermissionLevel::Moderate, &manager, &db_pool).await?;
+    assert_eq!(moderator_role.permission_level, PermissionLevel::Moderate);
+
+    Ok(())
+}
@@ -2,14 +2,17 @@ use leptos::prelude::*;
 use leptos_fluent::tr;
 use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
 use sphare_core_common::errors::AppError;
-use sphare_core_sphere::rule::ssr::{add_rule, remove_rule, update_rule};
+use sphare_core_content::embed::Link;
+use sphare_core_content::post::PostTags;
+use sphare_core_content::post::ssr::create_post;
+use sphare_core_sphere::rule::ssr::{accept_rules, add_rule, check_rules_accepted, has_accepted_rules, remove_rule, update_rule};
 use sphare_core_sphere::rule::ssr::{get_rule_vec, load_rule_by_id};
 use sphare_core_sphere::rule::{get_rule_description, get_rule_title, BaseRule};
-use sphare_core_sphere::sphere::ssr::create_sphere;
+use sphare_core_sphere::sphere::ssr::{create_sphere, set_sphere_require_rule_acceptance};
 use sphare_core_user::role::AdminRole;
 use sphare_core_user::user::User;
 
-use crate::common::{create_user, get_db_pool, get_i18n};
+use crate::common::{create_test_user, create_user, get_db_pool, get_i18n};
 use crate::data_factory::{add_base_rule, remove_base_rule, update_base_rule};
 
 mod common;
@@ -19,7 +22,7 @@ mod data_factory;
 async fn test_load_rule_by_id() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
     let user = create_user("test", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
     admin.admin_role = AdminRole::Admin;
     let sphere_1 = create_sphere("1", "a", false, &user, &db_pool).await?;
     let user = User::get(user.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
@@ -56,7 +59,7 @@ async fn test_load_rule_by_id() -> Result<(), AppError> {
 async fn test_get_rule_vec() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
     let user = create_user("test", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
     admin.admin_role = AdminRole::Admin;
     let sphere_1 = create_sphere("1", "a", false, &user, &db_pool).await?;
     let sphere_2 = create_sphere("2", "b", false, &user, &db_pool).await?;
@@ -100,7 +103,7 @@ async fn test_add_rule() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
     let user = create_user("user", &db_pool).await;
     let lead = create_user("lead", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
     admin.admin_role = AdminRole::Admin;
     let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
     let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
@@ -146,7 +149,7 @@ async fn test_update_rule() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
     let user = create_user("user", &db_pool).await;
     let lead = create_user("lead", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
     admin.admin_role = AdminRole::Admin;
     let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
     let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
@@ -208,7 +211,7 @@ async fn test_remove_rule() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
     let user = create_user("user", &db_pool).await;
     let lead = create_user("lead", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
     admin.admin_role = AdminRole::Admin;
     let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
     let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
@@ -270,4 +273,68 @@ fn test_get_rule_description() {
     assert_eq!(get_rule_description(BaseRule::PlatformIntegrity.into(), "", false).get_untracked(), tr!("rule-platform-integrity-description"));
     assert_eq!(get_rule_description("test-non-base-rule", "", false).get_untracked(), tr!("rule-respectful-description"));
     assert_eq!(get_rule_description("", "test-non-base-rule", true).get_untracked(), "test-non-base-rule");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_accept_rules() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let sphere_1 = create_sphere("1", "a", false, &user, &db_pool).await?;
+    let sphere_2 = create_sphere("2", "b", false, &user, &db_pool).await?;
+
+    assert!(!has_accepted_rules(&sphere_1.sphere_name, user.user_id, &db_pool).await?);
+    assert!(!has_accepted_rules(&sphere_2.sphere_name, user.user_id, &db_pool).await?);
+
+    accept_rules(&sphere_1.sphere_name, &user, &db_pool).await?;
+    assert!(has_accepted_rules(&sphere_1.sphere_name, user.user_id, &db_pool).await?);
+    assert!(!has_accepted_rules(&sphere_2.sphere_name, user.user_id, &db_pool).await?);
+
+    // accepting twice has no effect
+    accept_rules(&sphere_1.sphere_name, &user, &db_pool).await?;
+    assert!(has_accepted_rules(&sphere_1.sphere_name, user.user_id, &db_pool).await?);
+
+    assert!(accept_rules("missing", &user, &db_pool).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_post_with_rule_acceptance() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let lead = create_user("lead", &db_pool).await;
+    let user = create_test_user(&db_pool).await;
+    let sphere = create_sphere("1", "a", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    assert_eq!(
+        set_sphere_require_rule_acceptance(&sphere.sphere_name, true, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    // acceptance not required by default
+    check_rules_accepted(&sphere.sphere_name, &user, &db_pool).await?;
+
+    let sphere = set_sphere_require_rule_acceptance(&sphere.sphere_name, true, &lead, &db_pool).await?;
+    assert!(sphere.require_rule_acceptance);
+
+    assert_eq!(check_rules_accepted(&sphere.sphere_name, &user, &db_pool).await, Err(AppError::RulesNotAccepted));
+    assert_eq!(
+        create_post(&sphere.sphere_name, None, "a", "b", None, Link::default(), PostTags::default(), &user, &db_pool).await,
+        Err(AppError::RulesNotAccepted),
+    );
+    // moderators do not need to accept the rules
+    create_post(&sphere.sphere_name, None, "a", "b", None, Link::default(), PostTags::default(), &lead, &db_pool).await?;
+
+    accept_rules(&sphere.sphere_name, &user, &db_pool).await?;
+    let post_1 = create_post(&sphere.sphere_name, None, "a", "b", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+    // acceptance persists across posts
+    let post_2 = create_post(&sphere.sphere_name, None, "c", "d", None, Link::default(), PostTags::default(), &user, &db_pool).await?;
+    assert_ne!(post_1.post_id, post_2.post_id);
+
+    // users can post again without acceptance if the sphere does not require it anymore
+    let other_user = create_user("other", &db_pool).await;
+    let sphere = set_sphere_require_rule_acceptance(&sphere.sphere_name, false, &lead, &db_pool).await?;
+    assert!(!sphere.require_rule_acceptance);
+    create_post(&sphere.sphere_name, None, "e", "f", None, Link::default(), PostTags::default(), &other_user, &db_pool).await?;
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use sphare_core_common::errors::AppError;
 use sphare_core_user::user::User;
 
 use sphare_core_sphere::satellite::ssr::{activate_satellite, create_satellite, deactivate_satellite, get_satellite_sphere, update_satellite};
-use sphare_core_sphere::satellite::ssr::{get_satellite_vec_by_sphere_name, get_satellite_by_id};
+use sphare_core_sphere::satellite::ssr::{get_satellite_vec_by_sphere_name, get_satellite_by_id, is_subscribed_to_satellite, subscribe_satellite, unsubscribe_satellite};
 use sphare_core_sphere::sphere::ssr::create_sphere;
 
 pub use crate::common::*;
@@ -366,4 +366,33 @@ async fn test_deactivate_satellite() -> Result<(), AppError> {
     assert!(satellite_vec.is_empty());
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_subscribe_satellite() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_test_user(&db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    let (_, satellite) = create_sphere_with_satellite(
+        "1",
+        "1",
+        false,
+        false,
+        &mut user,
+        &db_pool,
+    ).await.expect("Sphere with satellite should be created");
+
+    assert!(!is_subscribed_to_satellite(satellite.satellite_id, other_user.user_id, &db_pool).await?);
+
+    subscribe_satellite(satellite.satellite_id, other_user.user_id, &db_pool).await?;
+    assert!(is_subscribed_to_satellite(satellite.satellite_id, other_user.user_id, &db_pool).await?);
+    assert!(!is_subscribed_to_satellite(satellite.satellite_id, user.user_id, &db_pool).await?);
+    assert!(subscribe_satellite(satellite.satellite_id, other_user.user_id, &db_pool).await.is_err());
+
+    unsubscribe_satellite(satellite.satellite_id, other_user.user_id, &db_pool).await?;
+    assert!(!is_subscribed_to_satellite(satellite.satellite_id, other_user.user_id, &db_pool).await?);
+    assert!(unsubscribe_satellite(satellite.satellite_id, other_user.user_id, &db_pool).await.is_err());
+
+    Ok(())
+}
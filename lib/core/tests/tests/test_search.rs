@@ -1,20 +1,25 @@
 use std::collections::BTreeSet;
 
 use sphare_core_common::common::SphereHeader;
+use sphare_core_common::constants::SAVED_SEARCH_NOTIF_LIMIT;
 use sphare_core_common::errors::AppError;
 use sphare_core_content::comment::ssr::create_comment;
 use sphare_core_content::comment::CommentWithContext;
 use sphare_core_content::embed::Link;
-use sphare_core_content::post::ssr::create_post;
+use sphare_core_content::moderation::ssr::approve_post;
+use sphare_core_content::post::ssr::{create_post, create_scheduled_post, publish_scheduled_posts, NewPost};
 use sphare_core_content::post::{PostTags, PostWithSphereInfo};
-use sphare_core_content::search::ssr::{get_matching_sphere_header_vec, search_comments, search_posts, search_spheres};
-use sphare_core_sphere::sphere::ssr::create_sphere;
+use sphare_core_content::search::ssr::{create_saved_search, delete_saved_search, get_matching_sphere_header_vec, get_saved_search_vec, run_saved_searches, search_comments, search_posts, search_spheres};
+use sphare_core_sphere::sphere::ssr::{create_sphere, set_sphere_require_post_approval};
 use sphare_core_sphere::sphere_management::ssr::set_sphere_icon_url;
-use sphare_core_user::user::ssr::{get_matching_user_header_vec, set_user_settings};
-use sphare_core_user::user::{User, UserHeader};
+use sphare_core_user::notification::ssr::get_notifications;
+use sphare_core_user::notification::NotificationType;
+use sphare_core_sphere::rule::ssr::add_rule;
+use sphare_core_user::user::ssr::{get_matching_user_header_vec, get_matching_username_vec, set_user_settings};
+use sphare_core_user::user::{NsfwMode, User, UserHeader};
 
 use crate::common::{create_test_user, create_user, get_db_pool};
-use crate::data_factory::{create_simple_post, set_sphere_num_members};
+use crate::data_factory::{create_simple_post, create_sphere_with_post, set_post_publish_at, set_sphere_num_members};
 
 mod common;
 mod data_factory;
@@ -58,7 +63,7 @@ async fn test_get_matching_user_header_vec() {
     get_matching_user_header_vec("", false, num_users as i64, &db_pool).await.expect_err("Should get error for empty username prefix");
 
     let nsfw_user = create_user("nsfw", &db_pool).await;
-    set_user_settings(true, false, 0, &nsfw_user, &db_pool).await.expect("Should set user settings");
+    set_user_settings(true, NsfwMode::Hide, 0, &nsfw_user, &db_pool).await.expect("Should set user settings");
     let nsfw_header_vec = UserHeader {
         username: nsfw_user.username,
         is_nsfw: true,
@@ -71,6 +76,62 @@ async fn test_get_matching_user_header_vec() {
     assert_eq!(user_header_vec.first(), Some(&nsfw_header_vec));
 }
 
+#[tokio::test]
+async fn test_get_matching_username_vec() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+
+    let mut user = create_user("mention", &db_pool).await;
+    let mut user_vec = Vec::new();
+    for i in 0..10 {
+        user_vec.push(create_user(&format!("Mention{i}"), &db_pool).await);
+    }
+    create_user("other", &db_pool).await;
+
+    let username_vec = get_matching_username_vec("MENT", 20, &db_pool).await?;
+    assert_eq!(username_vec.len(), 11);
+    assert_eq!(username_vec.first(), Some(&user.username));
+    assert!(username_vec.iter().all(|username| username.to_lowercase().starts_with("ment")));
+    assert!(username_vec.iter().skip(1).is_sorted());
+
+    let username_vec = get_matching_username_vec("mention", 3, &db_pool).await?;
+    assert_eq!(username_vec, vec![user.username.clone(), String::from("Mention0"), String::from("Mention1")]);
+
+    get_matching_username_vec("", 5, &db_pool).await.expect_err("Should get error for empty username prefix");
+
+    sqlx::query("UPDATE users SET delete_timestamp = NOW() WHERE user_id = $1")
+        .bind(user_vec[0].user_id)
+        .execute(&db_pool)
+        .await?;
+
+    let (sphere, post) = create_sphere_with_post("sphere", &mut user, &db_pool).await;
+    let rule = add_rule(&sphere.sphere_name, 0, "test", "test", false, &user, &db_pool).await?;
+    sqlx::query(
+        "INSERT INTO user_bans (user_id, sphere_id, post_id, infringed_rule_id, moderator_id, until_timestamp)
+        VALUES ($1, NULL, $2, $3, $4, NULL)"
+    )
+        .bind(user_vec[1].user_id)
+        .bind(post.post_id)
+        .bind(rule.rule_id)
+        .bind(user.user_id)
+        .execute(&db_pool)
+        .await?;
+    sqlx::query(
+        "INSERT INTO user_bans (user_id, sphere_id, post_id, infringed_rule_id, moderator_id, until_timestamp)
+        VALUES ($1, NULL, $2, $3, $4, NOW() - INTERVAL '1 day')"
+    )
+        .bind(user_vec[2].user_id)
+        .bind(post.post_id)
+        .bind(rule.rule_id)
+        .bind(user.user_id)
+        .execute(&db_pool)
+        .await?;
+
+    let username_vec = get_matching_username_vec("mention", 3, &db_pool).await?;
+    assert_eq!(username_vec, vec![user.username.clone(), String::from("Mention2"), String::from("Mention3")]);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_matching_sphere_header_vec() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -129,6 +190,39 @@ async fn test_get_matching_sphere_header_vec() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_matching_sphere_header_vec_fuzzy() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+
+    for sphere_name in ["music", "musical_art", "classical_music", "programming", "sports"] {
+        create_sphere(sphere_name, "sphere", false, &user, &db_pool).await?;
+    }
+
+    let get_sphere_name_vec = |sphere_header_vec: Vec<SphereHeader>| sphere_header_vec.into_iter().map(|sphere_header| sphere_header.sphere_name).collect::<Vec<String>>();
+
+    // mistyped prefixes still match the intended sphere
+    let sphere_name_vec = get_sphere_name_vec(get_matching_sphere_header_vec("musci", 10, &db_pool).await?);
+    assert!(sphere_name_vec.contains(&String::from("music")));
+    assert!(!sphere_name_vec.contains(&String::from("sports")));
+    let sphere_name_vec = get_sphere_name_vec(get_matching_sphere_header_vec("prgramming", 10, &db_pool).await?);
+    assert_eq!(sphere_name_vec.first().map(String::as_str), Some("programming"));
+
+    // exact prefixes rank first, ordered by name, followed by similar names
+    let sphere_name_vec = get_sphere_name_vec(get_matching_sphere_header_vec("Music", 10, &db_pool).await?);
+    assert_eq!(sphere_name_vec, vec!["music", "musical_art", "classical_music"]);
+
+    let sphere_name_vec = get_sphere_name_vec(get_matching_sphere_header_vec("music", 1, &db_pool).await?);
+    assert_eq!(sphere_name_vec, vec!["music"]);
+    // underscores in the prefix are matched literally
+    let sphere_name_vec = get_sphere_name_vec(get_matching_sphere_header_vec("musical_", 10, &db_pool).await?);
+    assert_eq!(sphere_name_vec.first().map(String::as_str), Some("musical_art"));
+
+    assert!(get_matching_sphere_header_vec("xyz", 10, &db_pool).await?.is_empty());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_search_spheres() {
     let db_pool = get_db_pool().await;
@@ -386,4 +480,153 @@ async fn test_search_comments() {
     let falle_comment_vec = search_comments("Falle", None, 10, 0, &db_pool).await.expect("Falle search should run");
     assert_eq!(falle_comment_vec.len(), 1);
     assert_eq!(falle_comment_vec.first(), Some(&comment_5));
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_create_and_delete_saved_search() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &user, &db_pool).await?;
+
+    let saved_search = create_saved_search(" rust ", None, &user, &db_pool).await?;
+    assert_eq!(saved_search.user_id, user.user_id);
+    assert_eq!(saved_search.search_query, "rust");
+    assert_eq!(saved_search.sphere_id, None);
+    assert_eq!(saved_search.sphere_name, None);
+
+    let sphere_saved_search = create_saved_search("rust", Some(&sphere.sphere_name), &user, &db_pool).await?;
+    assert_eq!(sphere_saved_search.sphere_id, Some(sphere.sphere_id));
+    assert_eq!(sphere_saved_search.sphere_name, Some(sphere.sphere_name.clone()));
+
+    // saving the same search twice returns the existing one
+    let repeat_saved_search = create_saved_search("rust", None, &user, &db_pool).await?;
+    assert_eq!(repeat_saved_search.saved_search_id, saved_search.saved_search_id);
+
+    assert!(create_saved_search("", None, &user, &db_pool).await.is_err());
+    assert!(create_saved_search("rust", Some("missing"), &user, &db_pool).await.is_err());
+
+    assert_eq!(get_saved_search_vec(&user, &db_pool).await?, vec![saved_search.clone(), sphere_saved_search.clone()]);
+    assert!(get_saved_search_vec(&other_user, &db_pool).await?.is_empty());
+
+    assert_eq!(delete_saved_search(saved_search.saved_search_id, &other_user, &db_pool).await, Err(AppError::NotFound));
+    delete_saved_search(saved_search.saved_search_id, &user, &db_pool).await?;
+    assert_eq!(delete_saved_search(saved_search.saved_search_id, &user, &db_pool).await, Err(AppError::NotFound));
+    assert_eq!(get_saved_search_vec(&user, &db_pool).await?, vec![sphere_saved_search]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_saved_searches() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let author = create_user("author", &db_pool).await;
+    let sphere_1 = create_sphere("a", "sphere", false, &author, &db_pool).await?;
+    let sphere_2 = create_sphere("b", "sphere", false, &author, &db_pool).await?;
+    let author = User::get(author.user_id, &db_pool).await.expect("Should reload user.");
+
+    let old_post = create_simple_post(&sphere_1.sphere_name, None, "rust before", "body", None, &author, &db_pool).await;
+
+    let saved_search = create_saved_search("rust", None, &user, &db_pool).await?;
+    create_saved_search("rust", Some(&sphere_2.sphere_name), &user, &db_pool).await?;
+    assert!(saved_search.last_run_timestamp > old_post.post.create_timestamp);
+
+    assert_eq!(run_saved_searches(&db_pool).await?, 0);
+    assert!(get_notifications(user.user_id, &db_pool).await?.is_empty());
+
+    let new_post_1 = create_simple_post(&sphere_1.sphere_name, None, "rust after", "body", None, &author, &db_pool).await;
+    let new_post_2 = create_simple_post(&sphere_2.sphere_name, None, "rust again", "body", None, &author, &db_pool).await;
+    create_simple_post(&sphere_1.sphere_name, None, "unrelated", "body", None, &author, &db_pool).await;
+    create_simple_post(&sphere_1.sphere_name, None, "own rust post", "body", None, &user, &db_pool).await;
+
+    // new_post_2 matches both saved searches
+    assert_eq!(run_saved_searches(&db_pool).await?, 3);
+    let notification_vec = get_notifications(user.user_id, &db_pool).await?;
+    assert_eq!(notification_vec.len(), 3);
+    assert!(notification_vec.iter().all(|notification| {
+        notification.notification_type == NotificationType::SavedSearch &&
+            notification.trigger_user_id == author.user_id &&
+            notification.comment_id.is_none()
+    }));
+    let notified_post_id_set = notification_vec.iter().map(|notification| notification.post_id).collect::<BTreeSet<i64>>();
+    assert_eq!(notified_post_id_set, BTreeSet::from([new_post_1.post.post_id, new_post_2.post.post_id]));
+    assert!(!notified_post_id_set.contains(&old_post.post.post_id));
+
+    // posts are only notified once, the last run timestamp having moved past them
+    let saved_search = get_saved_search_vec(&user, &db_pool).await?.into_iter().next().expect("Saved search should exist.");
+    assert!(saved_search.last_run_timestamp >= new_post_2.post.create_timestamp);
+    assert_eq!(run_saved_searches(&db_pool).await?, 0);
+    assert_eq!(get_notifications(user.user_id, &db_pool).await?.len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_saved_searches_over_limit() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let author = create_user("author", &db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &author, &db_pool).await?;
+    let author = User::get(author.user_id, &db_pool).await.expect("Should reload user.");
+
+    create_saved_search("rust", None, &user, &db_pool).await?;
+
+    let num_posts = SAVED_SEARCH_NOTIF_LIMIT as usize + 3;
+    let mut post_id_set = BTreeSet::new();
+    for i in 0..num_posts {
+        let post = create_simple_post(&sphere.sphere_name, None, &format!("rust {i}"), "body", None, &author, &db_pool).await;
+        post_id_set.insert(post.post.post_id);
+    }
+
+    // posts over the limit are notified on the next run instead of being skipped
+    assert_eq!(run_saved_searches(&db_pool).await?, SAVED_SEARCH_NOTIF_LIMIT as usize);
+    assert_eq!(run_saved_searches(&db_pool).await?, 3);
+    assert_eq!(run_saved_searches(&db_pool).await?, 0);
+
+    let notified_post_id_set = get_notifications(user.user_id, &db_pool).await?.into_iter()
+        .map(|notification| notification.post_id)
+        .collect::<BTreeSet<i64>>();
+    assert_eq!(notified_post_id_set, post_id_set);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_saved_searches_pending_and_scheduled_posts() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let lead = create_user("lead", &db_pool).await;
+    let author = create_user("author", &db_pool).await;
+    let sphere = create_sphere("sphere", "sphere", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("Should reload user.");
+    set_sphere_require_post_approval(&sphere.sphere_name, true, &lead, &db_pool).await?;
+
+    create_saved_search("rust", None, &user, &db_pool).await?;
+
+    let pending_post = create_simple_post(&sphere.sphere_name, None, "rust pending", "body", None, &author, &db_pool).await;
+    assert!(!pending_post.post.is_approved);
+    let scheduled_post = create_scheduled_post(
+        NewPost { sphere_name: &sphere.sphere_name, title: "rust scheduled", body: "body", ..Default::default() },
+        chrono::Utc::now() + chrono::Duration::hours(1),
+        &lead,
+        &db_pool,
+    ).await?;
+
+    // unpublished posts are not notified
+    assert_eq!(run_saved_searches(&db_pool).await?, 0);
+
+    // posts are notified once they are approved or published, even though they were created before the last run
+    approve_post(pending_post.post.post_id, &lead, &db_pool).await?;
+    set_post_publish_at(scheduled_post.post_id, scheduled_post.create_timestamp, &db_pool).await?;
+    assert_eq!(publish_scheduled_posts(&db_pool).await?, 1);
+
+    assert_eq!(run_saved_searches(&db_pool).await?, 2);
+    let notified_post_id_set = get_notifications(user.user_id, &db_pool).await?.into_iter()
+        .map(|notification| notification.post_id)
+        .collect::<BTreeSet<i64>>();
+    assert_eq!(notified_post_id_set, BTreeSet::from([pending_post.post.post_id, scheduled_post.post_id]));
+
+    assert_eq!(run_saved_searches(&db_pool).await?, 0);
+
+    Ok(())
+}
@@ -1,15 +1,16 @@
 use rand::RngExt;
 
 use sphare_core_common::common::SphereHeader;
+use sphare_core_common::constants::TRENDING_SPHERE_DAYS;
 use sphare_core_common::db_utils::ssr::create_db_pool;
 use sphare_core_common::errors::AppError;
 use sphare_core_common::errors::AppError::InsufficientPrivileges;
 use sphare_core_sphere::sphere;
-use sphare_core_sphere::sphere::ssr::{create_sphere_and_subscribe, get_sphere_by_name, get_subscribed_sphere_headers, update_sphere_description};
-use sphare_core_sphere::sphere::ssr::{create_sphere, subscribe, unsubscribe};
+use sphare_core_sphere::sphere::ssr::{create_sphere_and_subscribe, get_recommended_spheres, get_sphere_by_name, get_subscribed_sphere_headers, get_trending_sphere_vec, update_sphere_description};
+use sphare_core_sphere::sphere::ssr::{check_nsfw_confirmed, confirm_nsfw, create_sphere, has_confirmed_nsfw, subscribe, subscribe_many, unsubscribe};
 use sphare_core_user::role::PermissionLevel;
 use sphare_core_user::user::ssr::set_user_settings;
-use sphare_core_user::user::User;
+use sphare_core_user::user::{NsfwMode, User};
 
 pub use crate::common::*;
 pub use crate::data_factory::*;
@@ -126,6 +127,139 @@ async fn test_get_popular_sphere_headers() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_recommended_spheres() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let test_user = create_test_user(&db_pool).await;
+
+    let mut user_vec = Vec::new();
+    for i in 0..5 {
+        user_vec.push(create_user(&i.to_string(), &db_pool).await);
+    }
+
+    let common_sphere = create_sphere("common", "sphere", false, &test_user, &db_pool).await?;
+    let related_sphere = create_sphere("related", "sphere", false, &test_user, &db_pool).await?;
+    let less_related_sphere = create_sphere("less_related", "sphere", false, &test_user, &db_pool).await?;
+    let nsfw_sphere = create_sphere("nsfw", "sphere", true, &test_user, &db_pool).await?;
+    let unrelated_sphere = create_sphere("unrelated", "sphere", false, &test_user, &db_pool).await?;
+
+    // user 0 shares `common` with users 1 and 2, who are also subscribed to `related`, `less_related` and `nsfw`
+    for user in user_vec.iter().take(3) {
+        subscribe(common_sphere.sphere_id, user.user_id, &db_pool).await?;
+    }
+    for user in &user_vec[1..3] {
+        subscribe(related_sphere.sphere_id, user.user_id, &db_pool).await?;
+        subscribe(nsfw_sphere.sphere_id, user.user_id, &db_pool).await?;
+    }
+    subscribe(less_related_sphere.sphere_id, user_vec[1].user_id, &db_pool).await?;
+    // `unrelated` is more popular but only among users without common subscriptions
+    for user in &user_vec[3..] {
+        subscribe(unrelated_sphere.sphere_id, user.user_id, &db_pool).await?;
+    }
+
+    let related_sphere = get_sphere_by_name(&related_sphere.sphere_name, &db_pool).await?;
+    let less_related_sphere = get_sphere_by_name(&less_related_sphere.sphere_name, &db_pool).await?;
+    let nsfw_sphere = get_sphere_by_name(&nsfw_sphere.sphere_name, &db_pool).await?;
+
+    let user = &mut user_vec[0];
+    assert_eq!(
+        get_recommended_spheres(Some(user), 10, &db_pool).await?,
+        vec![SphereHeader::from(&related_sphere), SphereHeader::from(&less_related_sphere)]
+    );
+    assert_eq!(
+        get_recommended_spheres(Some(user), 1, &db_pool).await?,
+        vec![SphereHeader::from(&related_sphere)]
+    );
+
+    user.nsfw_mode = NsfwMode::Show;
+    assert_eq!(
+        get_recommended_spheres(Some(user), 10, &db_pool).await?,
+        vec![SphereHeader::from(&nsfw_sphere), SphereHeader::from(&related_sphere), SphereHeader::from(&less_related_sphere)]
+    );
+
+    // Subscribed spheres are not recommended
+    subscribe(related_sphere.sphere_id, user.user_id, &db_pool).await?;
+    assert_eq!(
+        get_recommended_spheres(Some(user), 10, &db_pool).await?,
+        vec![SphereHeader::from(&nsfw_sphere), SphereHeader::from(&less_related_sphere)]
+    );
+
+    // Anonymous users and users without subscriptions get trending spheres
+    let trending_sphere_vec = get_trending_sphere_vec(10, 0, &db_pool).await?;
+    assert!(!trending_sphere_vec.is_empty());
+    assert_eq!(get_recommended_spheres(None, 10, &db_pool).await?, trending_sphere_vec);
+    assert_eq!(get_recommended_spheres(Some(&test_user), 10, &db_pool).await?, trending_sphere_vec);
+
+    // The trending fallback excludes the spheres the user is already subscribed to
+    let unrelated_sphere_header = SphereHeader::from(&get_sphere_by_name(&unrelated_sphere.sphere_name, &db_pool).await?);
+    assert!(trending_sphere_vec.contains(&unrelated_sphere_header));
+    assert_eq!(
+        get_recommended_spheres(Some(&user_vec[3]), 10, &db_pool).await?,
+        trending_sphere_vec.into_iter().filter(|sphere_header| *sphere_header != unrelated_sphere_header).collect::<Vec<SphereHeader>>()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_trending_sphere_vec() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let test_user = create_test_user(&db_pool).await;
+
+    let mut user_vec = Vec::new();
+    for i in 0..6 {
+        user_vec.push(create_user(&i.to_string(), &db_pool).await);
+    }
+
+    let large_sphere = create_sphere("large", "sphere", false, &test_user, &db_pool).await?;
+    let growing_sphere = create_sphere("growing", "sphere", false, &test_user, &db_pool).await?;
+    let small_sphere = create_sphere("small", "sphere", false, &test_user, &db_pool).await?;
+    let nsfw_sphere = create_sphere("nsfw", "sphere", true, &test_user, &db_pool).await?;
+    create_sphere("empty", "sphere", false, &test_user, &db_pool).await?;
+
+    // The large sphere has the most members, but only 1 recent subscription
+    for user in &user_vec {
+        subscribe(large_sphere.sphere_id, user.user_id, &db_pool).await?;
+    }
+    for user in user_vec.iter().skip(1) {
+        set_subscription_timestamp(large_sphere.sphere_id, user.user_id, -(TRENDING_SPHERE_DAYS + 1), &db_pool).await?;
+    }
+    for user in user_vec.iter().take(3) {
+        subscribe(growing_sphere.sphere_id, user.user_id, &db_pool).await?;
+        subscribe(nsfw_sphere.sphere_id, user.user_id, &db_pool).await?;
+    }
+    for user in user_vec.iter().take(2) {
+        subscribe(small_sphere.sphere_id, user.user_id, &db_pool).await?;
+    }
+
+    let large_sphere = get_sphere_by_name(&large_sphere.sphere_name, &db_pool).await?;
+    let growing_sphere = get_sphere_by_name(&growing_sphere.sphere_name, &db_pool).await?;
+    assert!(large_sphere.num_members > growing_sphere.num_members);
+
+    let trending_sphere_vec = get_trending_sphere_vec(10, 0, &db_pool).await?;
+    assert_eq!(
+        trending_sphere_vec,
+        vec![
+            SphereHeader::from(&growing_sphere),
+            SphereHeader::from(&small_sphere),
+            SphereHeader::from(&large_sphere),
+        ]
+    );
+
+    let trending_sphere_vec = get_trending_sphere_vec(1, 1, &db_pool).await?;
+    assert_eq!(trending_sphere_vec, vec![SphereHeader::from(&small_sphere)]);
+
+    // Once all recent subscriptions are old, the sphere is not trending anymore
+    set_subscription_timestamp(large_sphere.sphere_id, user_vec[0].user_id, -(TRENDING_SPHERE_DAYS + 1), &db_pool).await?;
+    let trending_sphere_vec = get_trending_sphere_vec(10, 0, &db_pool).await?;
+    assert_eq!(
+        trending_sphere_vec,
+        vec![SphereHeader::from(&growing_sphere), SphereHeader::from(&small_sphere)]
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_subscribed_sphere_headers() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -330,6 +464,56 @@ async fn test_create_sphere() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_create_sphere_normalized_name() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let test_user = create_test_user(&db_pool).await;
+
+    let sphere = create_sphere("Foo", "a", false, &test_user, &db_pool).await?;
+    assert_eq!(sphere.sphere_name, "Foo");
+    assert_eq!(sphere.normalized_sphere_name, "foo");
+
+    // Names that only differ in case or by '-' and '_' are taken
+    assert_eq!(create_sphere("foo", "a", false, &test_user, &db_pool).await, Err(AppError::SphereNameTaken));
+    assert_eq!(create_sphere("FOO", "a", false, &test_user, &db_pool).await, Err(AppError::SphereNameTaken));
+    let sphere = create_sphere("foo-bar", "a", false, &test_user, &db_pool).await?;
+    assert_eq!(sphere.normalized_sphere_name, "foo_bar");
+    assert_eq!(create_sphere("Foo_Bar", "a", false, &test_user, &db_pool).await, Err(AppError::SphereNameTaken));
+    assert_eq!(
+        create_sphere_and_subscribe("foo", "a", false, &test_user, &db_pool).await.map(|(sphere, _)| sphere.sphere_id),
+        Err(AppError::SphereNameTaken)
+    );
+
+    // Surrounding whitespace is trimmed
+    let sphere = create_sphere(" Baz ", "a", false, &test_user, &db_pool).await?;
+    assert_eq!(sphere.sphere_name, "Baz");
+    assert_eq!(create_sphere("baz ", "a", false, &test_user, &db_pool).await, Err(AppError::SphereNameTaken));
+
+    // Invalid characters are rejected
+    for invalid_name in ["foo bar", "foo%", "föo", "foo.bar", "foo/bar"] {
+        let result = create_sphere(invalid_name, "a", false, &test_user, &db_pool).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))), "{invalid_name} should be rejected, got {result:?}");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_sphere_reserved_name() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let test_user = create_test_user(&db_pool).await;
+
+    for reserved_name in ["admin", "ADMIN", "api", "Posts", "spheres", "users"] {
+        assert_eq!(create_sphere(reserved_name, "a", false, &test_user, &db_pool).await, Err(AppError::ReservedName));
+        assert!(!sphere::ssr::is_sphere_available(reserved_name, &db_pool).await?);
+    }
+
+    assert!(sphere::ssr::is_sphere_available("admins", &db_pool).await?);
+    create_sphere("admins", "a", false, &test_user, &db_pool).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_update_sphere_description() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -397,6 +581,50 @@ async fn test_subscribe() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_subscribe_many() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let test_user = create_test_user(&db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    let mut sphere_vec = Vec::new();
+    for i in 0..4 {
+        sphere_vec.push(create_sphere(&i.to_string(), "sphere", false, &test_user, &db_pool).await?);
+    }
+    let sphere_id_vec: Vec<i64> = sphere_vec.iter().map(|sphere| sphere.sphere_id).collect();
+
+    subscribe(sphere_id_vec[0], test_user.user_id, &db_pool).await?;
+    subscribe(sphere_id_vec[1], other_user.user_id, &db_pool).await?;
+
+    // Existing subscriptions, duplicated and unknown ids are skipped
+    let num_added_subscriptions = subscribe_many(
+        vec![sphere_id_vec[0], sphere_id_vec[1], sphere_id_vec[2], sphere_id_vec[2], -1],
+        test_user.user_id,
+        &db_pool,
+    ).await?;
+    assert_eq!(num_added_subscriptions, 2);
+
+    let expected_num_members = [1, 2, 1, 0];
+    for (sphere, expected_num_members) in sphere_vec.iter().zip(expected_num_members) {
+        let sphere = get_sphere_by_name(&sphere.sphere_name, &db_pool).await?;
+        assert_eq!(sphere.num_members, expected_num_members);
+    }
+
+    let subscribed_sphere_vec = get_subscribed_sphere_headers(test_user.user_id, &db_pool).await?;
+    assert_eq!(
+        subscribed_sphere_vec,
+        sphere_vec.iter().take(3).map(SphereHeader::from).collect::<Vec<SphereHeader>>()
+    );
+
+    assert_eq!(subscribe_many(sphere_id_vec.clone(), test_user.user_id, &db_pool).await?, 1);
+    assert_eq!(subscribe_many(sphere_id_vec.clone(), test_user.user_id, &db_pool).await?, 0);
+    assert_eq!(subscribe_many(Vec::new(), test_user.user_id, &db_pool).await?, 0);
+    // Subscribe with non-existent user fails
+    assert!(subscribe_many(sphere_id_vec, other_user.user_id + 1, &db_pool).await.is_err());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_unsubscribe() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
@@ -429,7 +657,7 @@ async fn populate_dev_db() -> Result<(), AppError> {
     let mut test_user = create_test_user(&db_pool).await;
 
     let nsfw_user = create_user("nsfw", &db_pool).await;
-    set_user_settings(true, false, 0, &nsfw_user, &db_pool).await?;
+    set_user_settings(true, NsfwMode::Hide, 0, &nsfw_user, &db_pool).await?;
 
     let num_users = 50;
 
@@ -483,3 +711,30 @@ async fn populate_dev_db() -> Result<(), AppError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_confirm_nsfw() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    let nsfw_sphere = create_sphere("nsfw", "a", true, &user, &db_pool).await?;
+    let sphere = create_sphere("sfw", "b", false, &user, &db_pool).await?;
+
+    assert!(!has_confirmed_nsfw(&nsfw_sphere.sphere_name, user.user_id, &db_pool).await?);
+    assert!(!has_confirmed_nsfw(&sphere.sphere_name, user.user_id, &db_pool).await?);
+    assert_eq!(check_nsfw_confirmed(&nsfw_sphere.sphere_name, Some(&user), &db_pool).await, Err(AppError::NsfwNotConfirmed));
+    assert_eq!(check_nsfw_confirmed(&nsfw_sphere.sphere_name, None, &db_pool).await, Err(AppError::NsfwNotConfirmed));
+    assert_eq!(check_nsfw_confirmed(&sphere.sphere_name, None, &db_pool).await, Ok(()));
+
+    confirm_nsfw(&nsfw_sphere.sphere_name, &user, &db_pool).await?;
+    assert!(has_confirmed_nsfw(&nsfw_sphere.sphere_name, user.user_id, &db_pool).await?);
+    assert_eq!(check_nsfw_confirmed(&nsfw_sphere.sphere_name, Some(&user), &db_pool).await, Ok(()));
+    assert_eq!(check_nsfw_confirmed(&nsfw_sphere.sphere_name, None, &db_pool).await, Err(AppError::NsfwNotConfirmed));
+
+    // confirming twice has no effect
+    confirm_nsfw(&nsfw_sphere.sphere_name, &user, &db_pool).await?;
+    assert!(has_confirmed_nsfw(&nsfw_sphere.sphere_name, user.user_id, &db_pool).await?);
+
+    assert_eq!(confirm_nsfw("missing", &user, &db_pool).await, Err(AppError::NotFound));
+
+    Ok(())
+}
@@ -1,11 +1,11 @@
 use sphare_core_common::colors::Color;
 use sphare_core_common::errors::AppError;
 use sphare_core_content::embed::Link;
-use sphare_core_content::post::ssr::create_post;
+use sphare_core_content::post::ssr::{create_post, get_post_by_id};
 use sphare_core_content::post::PostTags;
 use sphare_core_sphere::sphere::ssr::create_sphere;
 use sphare_core_sphere::sphere_category::ssr::get_sphere_category_vec;
-use sphare_core_sphere::sphere_category::ssr::{delete_sphere_category, set_sphere_category, CATEGORY_NOT_DELETED_STR};
+use sphare_core_sphere::sphere_category::ssr::{delete_sphere_category, reorder_categories, set_category_active, set_sphere_category};
 use sphare_core_user::user::User;
 
 use crate::common::{create_user, get_db_pool};
@@ -73,10 +73,17 @@ async fn test_get_sphere_category_vec() -> Result<(), AppError> {
 
     let sphere_1_category_vec = get_sphere_category_vec(
         &sphere_1.sphere_name,
+        true,
         &db_pool
     ).await.expect("Should load sphere categories");
+    let sphere_1_active_category_vec = get_sphere_category_vec(
+        &sphere_1.sphere_name,
+        false,
+        &db_pool
+    ).await.expect("Should load active sphere categories");
     let sphere_2_category_vec = get_sphere_category_vec(
         &sphere_2.sphere_name,
+        true,
         &db_pool
     ).await?;
 
@@ -84,6 +91,7 @@ async fn test_get_sphere_category_vec() -> Result<(), AppError> {
     assert_eq!(sphere_1_category_vec.first(), Some(&sphere_1_category_1_updated));
     assert_eq!(sphere_1_category_vec.get(1), Some(&sphere_1_category_2));
     assert_eq!(sphere_1_category_vec.get(2), Some(&sphere_1_category_off));
+    assert_eq!(sphere_1_active_category_vec, vec![sphere_1_category_1_updated.clone(), sphere_1_category_2.clone()]);
     assert_eq!(sphere_2_category_vec.len(), 1);
     assert_eq!(sphere_2_category_vec.first(), Some(&sphere_2_category_1));
 
@@ -172,12 +180,97 @@ async fn test_set_sphere_category() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_reorder_categories() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("user", &db_pool).await;
+    let lead = create_user("lead", &db_pool).await;
+    let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    let category_a = set_sphere_category(&sphere.sphere_name, "a", Color::Black, "a", true, &lead, &db_pool).await?;
+    let category_b = set_sphere_category(&sphere.sphere_name, "b", Color::Black, "b", false, &lead, &db_pool).await?;
+    let category_c = set_sphere_category(&sphere.sphere_name, "c", Color::Black, "c", true, &lead, &db_pool).await?;
+    assert_eq!(category_a.display_order, 0);
+    assert_eq!(category_b.display_order, 1);
+    assert_eq!(category_c.display_order, 2);
+
+    // Updating a category keeps its display order
+    let category_a = set_sphere_category(&sphere.sphere_name, "a", Color::Blue, "a2", true, &lead, &db_pool).await?;
+    assert_eq!(category_a.display_order, 0);
+
+    let new_order = vec![String::from("c"), String::from("a"), String::from("b")];
+    assert_eq!(
+        reorder_categories(&sphere.sphere_name, &new_order, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(reorder_categories(&sphere.sphere_name, &new_order[..2], &lead, &db_pool).await.is_err());
+    assert!(reorder_categories(&sphere.sphere_name, &[String::from("c"), String::from("a"), String::from("a")], &lead, &db_pool).await.is_err());
+    assert!(reorder_categories(&sphere.sphere_name, &[String::from("c"), String::from("a"), String::from("d")], &lead, &db_pool).await.is_err());
+    assert_eq!(
+        get_sphere_category_vec(&sphere.sphere_name, true, &db_pool).await?.into_iter().map(|category| category.category_name).collect::<Vec<_>>(),
+        vec![String::from("a"), String::from("b"), String::from("c")],
+    );
+
+    let category_vec = reorder_categories(&sphere.sphere_name, &new_order, &lead, &db_pool).await?;
+    assert_eq!(category_vec.iter().map(|category| category.category_name.clone()).collect::<Vec<_>>(), new_order);
+    assert_eq!(category_vec.iter().map(|category| category.display_order).collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(get_sphere_category_vec(&sphere.sphere_name, true, &db_pool).await?, category_vec);
+    assert_eq!(
+        get_sphere_category_vec(&sphere.sphere_name, false, &db_pool).await?.into_iter().map(|category| category.category_name).collect::<Vec<_>>(),
+        vec![String::from("c"), String::from("a")],
+    );
+
+    // New categories are added at the end
+    let category_d = set_sphere_category(&sphere.sphere_name, "d", Color::Black, "d", true, &lead, &db_pool).await?;
+    assert_eq!(category_d.display_order, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_category_active() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("user", &db_pool).await;
+    let lead = create_user("lead", &db_pool).await;
+    let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    let category_a = set_sphere_category(&sphere.sphere_name, "a", Color::Black, "a", true, &lead, &db_pool).await?;
+    let category_b = set_sphere_category(&sphere.sphere_name, "b", Color::Black, "b", true, &lead, &db_pool).await?;
+
+    assert_eq!(
+        set_category_active(&sphere.sphere_name, "a", false, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        set_category_active(&sphere.sphere_name, "missing", false, &lead, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+
+    let inactive_category_a = set_category_active(&sphere.sphere_name, "a", false, &lead, &db_pool).await?;
+    assert_eq!(inactive_category_a.category_id, category_a.category_id);
+    assert_eq!(inactive_category_a.display_order, category_a.display_order);
+    assert_eq!(inactive_category_a.description, category_a.description);
+    assert!(!inactive_category_a.is_active);
+
+    assert_eq!(get_sphere_category_vec(&sphere.sphere_name, false, &db_pool).await?, vec![category_b.clone()]);
+    assert_eq!(get_sphere_category_vec(&sphere.sphere_name, true, &db_pool).await?, vec![inactive_category_a, category_b.clone()]);
+
+    let active_category_a = set_category_active(&sphere.sphere_name, "a", true, &lead, &db_pool).await?;
+    assert!(active_category_a.is_active);
+    assert_eq!(get_sphere_category_vec(&sphere.sphere_name, false, &db_pool).await?, vec![active_category_a, category_b]);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delete_sphere_category() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
-    let user = create_user("test", &db_pool).await;
-    let sphere = create_sphere("sphere", "a", false, &user, &db_pool).await?;
-    let user = User::get(user.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+    let user = create_user("user", &db_pool).await;
+    let lead = create_user("lead", &db_pool).await;
+    let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
 
     let category_name = "a";
     let sphere_category = set_sphere_category(
@@ -186,40 +279,75 @@ async fn test_delete_sphere_category() -> Result<(), AppError> {
         Color::Purple,
         "b",
         true,
-        &user,
+        &lead,
         &db_pool
     ).await.expect("Category should be added.");
 
-    delete_sphere_category(&sphere.sphere_name, &sphere_category.category_name, &user, &db_pool).await.expect("Sphere category should be deleted.");
+    assert_eq!(
+        delete_sphere_category(&sphere.sphere_name, &sphere_category.category_name, false, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(
+        delete_sphere_category(&sphere.sphere_name, "missing", false, &lead, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+
+    delete_sphere_category(&sphere.sphere_name, &sphere_category.category_name, false, &lead, &db_pool).await.expect("Sphere category should be deleted.");
 
-    assert!(get_sphere_category_vec(&sphere.sphere_name, &db_pool).await.expect("Sphere category should be deleted.").is_empty());
+    assert!(get_sphere_category_vec(&sphere.sphere_name, true, &db_pool).await.expect("Sphere category should be deleted.").is_empty());
 
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_sphere_category_with_posts() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let lead = create_user("lead", &db_pool).await;
+    let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    let category_name = "a";
     let sphere_category = set_sphere_category(
         &sphere.sphere_name,
         category_name,
         Color::Cyan,
         "b",
         true,
-        &user,
+        &lead,
         &db_pool
     ).await.expect("Category should be added.");
 
-    create_post(
-        &sphere.sphere_name,
-        None,
-        "a",
-        "b",
-        None,
-        Link::default(),
-        PostTags::new(false, false, false, Some(sphere_category.category_id)),
-        &user,
-        &db_pool
-    ).await.expect("Post should be created.");
+    let mut post_vec = Vec::new();
+    for i in 0..2 {
+        post_vec.push(create_post(
+            &sphere.sphere_name,
+            None,
+            &format!("post {i}"),
+            "b",
+            None,
+            Link::default(),
+            PostTags::new(false, false, false, Some(sphere_category.category_id)),
+            &lead,
+            &db_pool
+        ).await.expect("Post should be created."));
+    }
 
+    // Deletion is blocked by default when posts use the category
     assert_eq!(
-        delete_sphere_category(&sphere.sphere_name, &sphere_category.category_name, &user, &db_pool).await,
-        Err(AppError::InternalServerError(String::from(CATEGORY_NOT_DELETED_STR))),
+        delete_sphere_category(&sphere.sphere_name, category_name, false, &lead, &db_pool).await,
+        Err(AppError::CategoryInUse),
     );
+    assert_eq!(get_sphere_category_vec(&sphere.sphere_name, true, &db_pool).await?, vec![sphere_category.clone()]);
+    for post in &post_vec {
+        assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.category_id, Some(sphere_category.category_id));
+    }
+
+    // Posts lose their category when reassigning
+    delete_sphere_category(&sphere.sphere_name, category_name, true, &lead, &db_pool).await?;
+    assert!(get_sphere_category_vec(&sphere.sphere_name, true, &db_pool).await?.is_empty());
+    for post in &post_vec {
+        assert_eq!(get_post_by_id(post.post_id, &db_pool).await?.category_id, None);
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -1,6 +1,8 @@
 use object_store::memory::InMemory;
 use object_store::ObjectStoreExt;
-use sphare_core_common::constants::{IMAGE_FILE_PARAM, SPHERE_NAME_PARAM};
+use sphare_core_common::colors::Color;
+use sphare_core_common::constants::{IMAGE_FILE_PARAM, MAX_SPHERE_SIDEBAR_LENGTH, SPHERE_NAME_PARAM};
+use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
 use sphare_core_common::errors::AppError;
 use sphare_core_content::comment::ssr::create_comment_with_notif;
 use sphare_core_content::moderation::ssr::{ban_user_from_sphere};
@@ -8,10 +10,10 @@ use sphare_core_content::post::{PostDataInputs, PostLocation};
 use sphare_core_content::post::ssr::create_post_and_vote;
 use sphare_core_sphere::rule::ssr::add_rule;
 use sphare_core_sphere::sphere::ssr::{create_sphere, get_sphere_by_name};
-use sphare_core_sphere::sphere_management::ssr::{delete_sphere_image, get_sphere_ban_vec, remove_user_ban, set_sphere_banner_url, set_sphere_icon_url, set_sphere_image, store_sphere_image, SphereImageType, MAX_ICON_SIZE};
+use sphare_core_sphere::sphere_management::ssr::{add_trusted_user, delete_sphere_image, get_sphere_ban_vec, get_sphere_trusted_user_vec, is_trusted_user, remove_trusted_user, remove_user_ban, set_sphere_archived, set_sphere_banner_url, set_sphere_color, set_sphere_sidebar, set_sphere_icon_url, set_sphere_image, store_sphere_image, SphereImageType, MAX_ICON_SIZE};
 use sphare_core_sphere::sphere_management::ssr::{BANNER_FILE_INFER_ERROR_STR, INCORRECT_BANNER_FILE_TYPE_STR, MISSING_BANNER_FILE_STR, MISSING_SPHERE_STR};
-use sphare_core_user::role::ssr::{is_user_sphere_moderator, set_user_admin_role};
-use sphare_core_user::role::AdminRole;
+use sphare_core_user::role::ssr::{is_user_sphere_moderator, set_user_admin_role, set_user_sphere_role};
+use sphare_core_user::role::{AdminRole, PermissionLevel};
 use sphare_core_user::user::User;
 
 use crate::common::*;
@@ -116,8 +118,10 @@ async fn test_remove_user_ban() -> Result<(), AppError> {
             is_markdown: false,
             embed_type: Default::default(),
             link: None,
+            alt_text: None,
             post_tags: Default::default(),
         },
+        None,
         &banned_user_1,
         &db_pool,
     ).await.expect("Should create post and vote");
@@ -161,12 +165,216 @@ async fn test_remove_user_ban() -> Result<(), AppError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_add_trusted_user() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("test", &db_pool).await;
+    let user = create_user("1", &db_pool).await;
+
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let (other_sphere, _) = create_sphere_with_post("other", &mut lead, &db_pool).await;
+
+    assert!(!is_trusted_user(user.user_id, sphere.sphere_id, &db_pool).await?);
+    assert_eq!(
+        add_trusted_user(&user.username, &sphere.sphere_name, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges)
+    );
+
+    let trusted_user = add_trusted_user(&user.username, &sphere.sphere_name, &lead, &db_pool).await?;
+    assert_eq!(trusted_user.user_id, user.user_id);
+    assert_eq!(trusted_user.username, user.username);
+    assert_eq!(trusted_user.sphere_id, sphere.sphere_id);
+    assert_eq!(trusted_user.sphere_name, sphere.sphere_name);
+    assert_eq!(trusted_user.grantor_id, lead.user_id);
+    assert_eq!(trusted_user.delete_timestamp, None);
+
+    assert!(is_trusted_user(user.user_id, sphere.sphere_id, &db_pool).await?);
+    assert!(!is_trusted_user(user.user_id, other_sphere.sphere_id, &db_pool).await?);
+    assert!(!is_trusted_user(lead.user_id, sphere.sphere_id, &db_pool).await?);
+
+    // Trusting a user twice keeps a single trusted user entry
+    let trusted_user_2 = add_trusted_user(&user.username, &sphere.sphere_name, &lead, &db_pool).await?;
+    assert_eq!(trusted_user_2.trusted_user_id, trusted_user.trusted_user_id);
+    assert_eq!(get_sphere_trusted_user_vec(&sphere.sphere_name, &db_pool).await?, vec![trusted_user]);
+    assert!(get_sphere_trusted_user_vec(&other_sphere.sphere_name, &db_pool).await?.is_empty());
+
+    assert_eq!(
+        add_trusted_user("missing", &sphere.sphere_name, &lead, &db_pool).await,
+        Err(AppError::NotFound)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remove_trusted_user() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("test", &db_pool).await;
+    let mut global_mod = create_user("global", &db_pool).await;
+    global_mod.admin_role = AdminRole::Moderator;
+    let user = create_user("1", &db_pool).await;
+
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+
+    let trusted_user = add_trusted_user(&user.username, &sphere.sphere_name, &lead, &db_pool).await?;
+
+    assert_eq!(remove_trusted_user(trusted_user.trusted_user_id, &user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(remove_trusted_user(trusted_user.trusted_user_id, &lead, &db_pool).await, Ok(trusted_user.clone()));
+    assert!(!is_trusted_user(user.user_id, sphere.sphere_id, &db_pool).await?);
+    assert!(get_sphere_trusted_user_vec(&sphere.sphere_name, &db_pool).await?.is_empty());
+    assert_eq!(remove_trusted_user(trusted_user.trusted_user_id, &lead, &db_pool).await, Err(AppError::NotFound));
+
+    // User can be trusted again after being removed
+    let trusted_user = add_trusted_user(&user.username, &sphere.sphere_name, &lead, &db_pool).await?;
+    assert!(is_trusted_user(user.user_id, sphere.sphere_id, &db_pool).await?);
+    assert_eq!(remove_trusted_user(trusted_user.trusted_user_id, &global_mod, &db_pool).await, Ok(trusted_user));
+    assert!(!is_trusted_user(user.user_id, sphere.sphere_id, &db_pool).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_sphere_archived() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("test", &db_pool).await;
+    let user = create_user("1", &db_pool).await;
+
+    let (sphere, post) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    let post_location = PostLocation {
+        sphere: sphere.sphere_name.clone(),
+        satellite_id: None,
+    };
+    let post_inputs = PostDataInputs {
+        title: "a".to_string(),
+        body: "b".to_string(),
+        is_markdown: false,
+        embed_type: Default::default(),
+        link: None,
+        alt_text: None,
+        post_tags: Default::default(),
+    };
+    assert!(!sphere.is_archived);
+
+    assert_eq!(
+        set_sphere_archived(&sphere.sphere_name, true, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges)
+    );
+    let archived_sphere = set_sphere_archived(&sphere.sphere_name, true, &lead, &db_pool).await?;
+    assert!(archived_sphere.is_archived);
+    assert_eq!(archived_sphere.sphere_id, sphere.sphere_id);
+
+    // Archived spheres can still be read but reject new content, including from moderators
+    let sphere = get_sphere_by_name(&sphere.sphere_name, &db_pool).await?;
+    assert!(sphere.is_archived);
+    assert_eq!(
+        create_post_and_vote(post_location.clone(), post_inputs.clone(), None, &user, &db_pool).await.map(|(post, _, _)| post.post_id),
+        Err(AppError::SphereArchived)
+    );
+    assert_eq!(
+        create_post_and_vote(post_location.clone(), post_inputs.clone(), None, &lead, &db_pool).await.map(|(post, _, _)| post.post_id),
+        Err(AppError::SphereArchived)
+    );
+    assert_eq!(
+        create_comment_with_notif(post.post_id, None, "c", false, false, &user, &db_pool).await.map(|comment| comment.comment.comment_id),
+        Err(AppError::SphereArchived)
+    );
+
+    // Posting and commenting resume after unarchiving
+    let sphere = set_sphere_archived(&sphere.sphere_name, false, &lead, &db_pool).await?;
+    assert!(!sphere.is_archived);
+    let (post, _, _) = create_post_and_vote(post_location, post_inputs, None, &user, &db_pool).await?;
+    create_comment_with_notif(post.post_id, None, "c", false, false, &user, &db_pool).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_sphere_color() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("test", &db_pool).await;
+    let manager = create_user("1", &db_pool).await;
+    let user = create_user("2", &db_pool).await;
+
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    set_user_sphere_role(&manager.username, &sphere.sphere_name, PermissionLevel::Manage, &lead, &db_pool).await?;
+    let manager = User::get(manager.user_id, &db_pool).await.expect("Should reload manager.");
+    assert_eq!(sphere.accent_color, Color::None);
+
+    assert_eq!(
+        set_sphere_color(&sphere.sphere_name, Color::Green, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges)
+    );
+    assert_eq!(
+        set_sphere_color(&sphere.sphere_name, Color::Green, &manager, &db_pool).await,
+        Err(AppError::InsufficientPrivileges)
+    );
+    assert_eq!(get_sphere_by_name(&sphere.sphere_name, &db_pool).await?.accent_color, Color::None);
+
+    let updated_sphere = set_sphere_color(&sphere.sphere_name, Color::Green, &lead, &db_pool).await?;
+    assert_eq!(updated_sphere.sphere_id, sphere.sphere_id);
+    assert_eq!(updated_sphere.accent_color, Color::Green);
+    assert_eq!(get_sphere_by_name(&sphere.sphere_name, &db_pool).await?, updated_sphere);
+
+    let reset_sphere = set_sphere_color(&sphere.sphere_name, Color::None, &lead, &db_pool).await?;
+    assert_eq!(reset_sphere.accent_color, Color::None);
+    assert_eq!(get_sphere_by_name(&sphere.sphere_name, &db_pool).await?.accent_color, Color::None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_sphere_sidebar() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut lead = create_user("test", &db_pool).await;
+    let moderator = create_user("1", &db_pool).await;
+    let user = create_user("2", &db_pool).await;
+
+    let (sphere, _) = create_sphere_with_post("sphere", &mut lead, &db_pool).await;
+    set_user_sphere_role(&moderator.username, &sphere.sphere_name, PermissionLevel::Moderate, &lead, &db_pool).await?;
+    let moderator = User::get(moderator.user_id, &db_pool).await.expect("Should reload moderator.");
+    assert_eq!(sphere.sidebar_markdown, None);
+    assert_eq!(sphere.sidebar_html, None);
+
+    let sidebar_markdown = "# Links\n\n[wiki](/spheres/sphere/wiki/faq)";
+    assert_eq!(
+        set_sphere_sidebar(&sphere.sphere_name, sidebar_markdown, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges)
+    );
+    assert!(
+        set_sphere_sidebar(&sphere.sphere_name, &"a".repeat(MAX_SPHERE_SIDEBAR_LENGTH + 1), &moderator, &db_pool).await.is_err()
+    );
+    assert_eq!(get_sphere_by_name(&sphere.sphere_name, &db_pool).await?.sidebar_markdown, None);
+
+    let updated_sphere = set_sphere_sidebar(&sphere.sphere_name, sidebar_markdown, &moderator, &db_pool).await?;
+    let (expected_html, _) = get_html_and_markdown_strings(sidebar_markdown, true)?;
+    assert_eq!(updated_sphere.sphere_id, sphere.sphere_id);
+    assert_eq!(updated_sphere.sidebar_markdown.as_deref(), Some(sidebar_markdown));
+    assert_eq!(updated_sphere.sidebar_html, Some(expected_html));
+    assert_eq!(get_sphere_by_name(&sphere.sphere_name, &db_pool).await?, updated_sphere);
+
+    // The sidebar content is sanitized
+    let malicious_markdown = "<script>alert('x')</script>[link](javascript:alert('x'))<img src=x onerror=alert(1)>";
+    let sanitized_sphere = set_sphere_sidebar(&sphere.sphere_name, malicious_markdown, &lead, &db_pool).await?;
+    assert_eq!(sanitized_sphere.sidebar_markdown.as_deref(), Some(malicious_markdown));
+    let sidebar_html = sanitized_sphere.sidebar_html.expect("Sidebar html should be set.");
+    assert!(!sidebar_html.contains("<script"));
+    assert!(!sidebar_html.contains("href=\"javascript:"));
+    assert!(!sidebar_html.contains("<img"));
+
+    // An empty sidebar removes the content
+    let cleared_sphere = set_sphere_sidebar(&sphere.sphere_name, "", &moderator, &db_pool).await?;
+    assert_eq!(cleared_sphere.sidebar_markdown, None);
+    assert_eq!(cleared_sphere.sidebar_html, None);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_is_user_sphere_moderator() -> Result<(), AppError> {
     let db_pool = get_db_pool().await;
     let user = create_user("test", &db_pool).await;
     let mut global_moderator = create_user("mod", &db_pool).await;
-    let mut admin = create_user("admin", &db_pool).await;
+    let mut admin = create_user("global_admin", &db_pool).await;
     // set user role in the DB, needed to test that global Moderators/Admin cannot be banned
     global_moderator.admin_role = AdminRole::Moderator;
     admin.admin_role = AdminRole::Admin;
@@ -1,18 +1,23 @@
 use sphare_core_common::errors::AppError;
 use sphare_core_content::moderation::ssr::ban_user_from_sphere;
+use sphare_core_content::comment::ssr::get_comment_by_id;
+use sphare_core_content::post::ssr::get_post_by_id;
 use sphare_core_content::profile::ssr::{get_user_comment_vec, get_user_post_vec};
 use sphare_core_content::ranking::{CommentSortType, PostSortType, SortType};
 use sphare_core_sphere::rule::ssr::add_rule;
 use sphare_core_user::role::ssr::get_user_sphere_role;
 use sphare_core_user::role::AdminRole;
-use sphare_core_user::user::ssr::{create_or_update_user, delete_user, set_user_settings};
-use sphare_core_user::user::User;
+use sphare_core_common::constants::{MAX_BIO_LENGTH, USERNAME_CHANGE_COOLDOWN_DAYS};
+use sphare_core_user::user::ssr::{change_username, create_or_update_user, delete_user, get_user_karma, get_user_profile_header, get_username_history, set_default_post_sort, set_user_bio, set_user_settings};
+use sphare_core_user::user::{NsfwMode, User, UserKarma};
 
 use crate::common::{create_test_user, create_user, get_db_pool};
-use crate::data_factory::{create_simple_post, create_sphere_with_post_and_comment};
+use crate::data_factory::{create_simple_post, create_sphere_with_post_and_comment, get_moderated_and_deleted_comments, get_moderated_and_deleted_posts, set_comment_score, set_post_score};
+use crate::utils::set_username_change_timestamp;
 
 mod common;
 mod data_factory;
+mod utils;
 
 #[tokio::test]
 async fn test_create_or_update_user() -> Result<(), AppError> {
@@ -28,7 +33,7 @@ async fn test_create_or_update_user() -> Result<(), AppError> {
     assert_eq!(user.username, username);
     assert_eq!(user.email, email);
     assert_eq!(user.admin_role, AdminRole::None);
-    assert_eq!(user.show_nsfw, false);
+    assert_eq!(user.nsfw_mode, NsfwMode::Hide);
     assert_eq!(user.days_hide_spoiler, None);
     assert_eq!(user.delete_timestamp, None);
 
@@ -38,13 +43,219 @@ async fn test_create_or_update_user() -> Result<(), AppError> {
     assert_eq!(loaded_user.username, user.username);
     assert_eq!(loaded_user.email, user.email);
     assert_eq!(loaded_user.admin_role, user.admin_role);
-    assert_eq!(loaded_user.show_nsfw, user.show_nsfw);
+    assert_eq!(loaded_user.nsfw_mode, user.nsfw_mode);
     assert_eq!(loaded_user.days_hide_spoiler, user.days_hide_spoiler);
     assert_eq!(loaded_user.delete_timestamp, None);
 
     Ok(())
 }
 
+#[tokio::test]
+async fn test_change_username() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("user", &db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+    let (_, post, _) = create_sphere_with_post_and_comment("sphere", &mut user.clone(), &db_pool).await;
+
+    assert!(get_username_history(user.user_id, &db_pool).await?.is_empty());
+
+    // Invalid, reserved, unchanged or taken usernames are rejected
+    assert!(change_username("new name", &user, &db_pool).await.is_err());
+    assert!(change_username("", &user, &db_pool).await.is_err());
+    assert_eq!(change_username("admin", &user, &db_pool).await, Err(AppError::ReservedName));
+    assert!(change_username(&user.username, &user, &db_pool).await.is_err());
+    assert_eq!(change_username(&other_user.username, &user, &db_pool).await, Err(AppError::UsernameTaken));
+    assert_eq!(change_username("OTHER", &user, &db_pool).await, Err(AppError::UsernameTaken));
+    assert!(get_username_history(user.user_id, &db_pool).await?.is_empty());
+
+    let username_change = change_username("renamed", &user, &db_pool).await?;
+    assert_eq!(username_change.user_id, user.user_id);
+    assert_eq!(username_change.old_username, user.username);
+    assert_eq!(username_change.new_username, "renamed");
+    assert_eq!(get_username_history(user.user_id, &db_pool).await?, vec![username_change.clone()]);
+
+    let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
+    assert_eq!(user.username, "renamed");
+    // creator names are loaded from the users table so existing content shows the new username
+    let post = get_post_by_id(post.post_id, &db_pool).await?;
+    assert_eq!(post.creator_name, "renamed");
+    // logging in again keeps the changed username
+    let sql_user = create_or_update_user(&user.oidc_id, "user", &user.email, &db_pool).await?;
+    assert_eq!(sql_user.username, "renamed");
+
+    // Username can only be changed once per cooldown period
+    let cooldown_result = change_username("renamed_again", &user, &db_pool).await;
+    assert!(matches!(cooldown_result, Err(AppError::RateLimited(Some(timestamp))) if timestamp > chrono::Utc::now()));
+    assert_eq!(get_username_history(user.user_id, &db_pool).await?.len(), 1);
+
+    set_username_change_timestamp(
+        username_change.change_id,
+        chrono::Utc::now() - chrono::Duration::days(USERNAME_CHANGE_COOLDOWN_DAYS + 1),
+        &db_pool,
+    ).await?;
+    let username_change_2 = change_username("Renamed_Again", &user, &db_pool).await?;
+    assert_eq!(username_change_2.old_username, "renamed");
+    assert_eq!(username_change_2.new_username, "Renamed_Again");
+
+    let username_history = get_username_history(user.user_id, &db_pool).await?;
+    assert_eq!(username_history.len(), 2);
+    assert_eq!(username_history.first(), Some(&username_change_2));
+    assert_eq!(username_history.last().map(|change| change.change_id), Some(username_change.change_id));
+
+    // The previous username can be used by other users
+    let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
+    let username_change_3 = change_username("user", &other_user, &db_pool).await?;
+    assert_eq!(username_change_3.old_username, other_user.username);
+    assert_eq!(user.username, "Renamed_Again");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_change_username_concurrent() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("user", &db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    // concurrent changes of the same user respect the cooldown
+    let (result_1, result_2) = tokio::join!(
+        change_username("renamed_1", &user, &db_pool),
+        change_username("renamed_2", &user, &db_pool),
+    );
+    assert_eq!(result_1.is_ok() as u8 + result_2.is_ok() as u8, 1);
+    assert!([result_1, result_2].into_iter().any(|result| matches!(result, Err(AppError::RateLimited(_)))));
+    assert_eq!(get_username_history(user.user_id, &db_pool).await?.len(), 1);
+
+    // concurrent changes to the same username by different users only succeed once, regardless of case
+    let third_user = create_user("third", &db_pool).await;
+    let (result_1, result_2) = tokio::join!(
+        change_username("taken", &other_user, &db_pool),
+        change_username("TAKEN", &third_user, &db_pool),
+    );
+    assert_eq!(result_1.is_ok() as u8 + result_2.is_ok() as u8, 1);
+    assert!([result_1, result_2].into_iter().any(|result| result == Err(AppError::UsernameTaken)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_change_username_creator_name() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_user("user", &db_pool).await;
+    let (_, post, comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+    assert_eq!(post.creator_name, user.username);
+    assert_eq!(comment.creator_name, user.username);
+
+    change_username("renamed", &user, &db_pool).await?;
+
+    let post = get_post_by_id(post.post_id, &db_pool).await?;
+    assert_eq!(post.creator_name, "renamed");
+    assert_eq!(post.creator_id, user.user_id);
+    let comment = get_comment_by_id(comment.comment_id, &db_pool).await?;
+    assert_eq!(comment.creator_name, "renamed");
+    assert_eq!(comment.creator_id, user.user_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_or_update_user_reserved_name() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+
+    for reserved_name in ["admin", "Admin", "API", "posts", "Spheres"] {
+        assert_eq!(
+            create_or_update_user(reserved_name, reserved_name, reserved_name, &db_pool).await.map(|user| user.user_id),
+            Err(AppError::ReservedName)
+        );
+    }
+
+    // Existing users keep their username when their identity provider username is reserved
+    let user = create_or_update_user("a", "b", "c", &db_pool).await?;
+    let updated_user = create_or_update_user("a", "admin", "d", &db_pool).await?;
+    assert_eq!(updated_user.user_id, user.user_id);
+    assert_eq!(updated_user.username, "b");
+    assert_eq!(updated_user.email, "d");
+
+    for reserved_name in ["Administrator", "API", "posts", "Spheres", "wiki", "about-sphare"] {
+        assert_eq!(
+            change_username(reserved_name, &User::get(user.user_id, &db_pool).await.expect("Should get user"), &db_pool).await,
+            Err(AppError::ReservedName)
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_user_karma() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let mut user = create_user("user", &db_pool).await;
+    let other_user = create_user("other", &db_pool).await;
+
+    assert_eq!(get_user_karma(user.user_id, &db_pool).await?, UserKarma::default());
+
+    let (sphere, post, comment) = create_sphere_with_post_and_comment("sphere", &mut user, &db_pool).await;
+    let other_post = create_simple_post(&sphere.sphere_name, None, "a", "b", None, &user, &db_pool).await;
+    set_post_score(post.post_id, 10, &db_pool).await?;
+    set_post_score(other_post.post.post_id, -3, &db_pool).await?;
+    set_comment_score(comment.comment_id, 4, &db_pool).await?;
+
+    // moderated and deleted content does not count
+    let user = User::get(user.user_id, &db_pool).await.expect("Should reload user");
+    let (moderated_post, deleted_post) = get_moderated_and_deleted_posts(&sphere.sphere_name, &user, &db_pool).await;
+    let (moderated_comment, deleted_comment) = get_moderated_and_deleted_comments(&post, &sphere.sphere_name, &user, &db_pool).await;
+    set_post_score(moderated_post.post.post_id, 100, &db_pool).await?;
+    set_post_score(deleted_post.post.post_id, 100, &db_pool).await?;
+    set_comment_score(moderated_comment.comment_id, 100, &db_pool).await?;
+    set_comment_score(deleted_comment.comment_id, 100, &db_pool).await?;
+
+    let expected_karma = UserKarma {
+        post_karma: 7,
+        comment_karma: 4,
+    };
+    assert_eq!(get_user_karma(user.user_id, &db_pool).await?, expected_karma);
+    assert_eq!(get_user_karma(other_user.user_id, &db_pool).await?, UserKarma::default());
+    assert_eq!(get_user_profile_header(&user.username, &db_pool).await?.karma, expected_karma);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_user_bio() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("user", &db_pool).await;
+
+    let profile_header = get_user_profile_header(&user.username, &db_pool).await?;
+    assert_eq!(profile_header.username, user.username);
+    assert_eq!(profile_header.bio, None);
+    assert_eq!(profile_header.timestamp, user.timestamp);
+
+    let markdown_bio = "Hello **world**\n\n<script>alert('bio')</script>\n\n[link](javascript:alert('bio'))";
+    let bio = set_user_bio(markdown_bio, &user, &db_pool).await?.expect("Bio should be set");
+    assert!(bio.contains("<strong"));
+    assert!(!bio.contains("<script"));
+    assert!(bio.contains("link"));
+    assert!(!bio.contains("href=\"javascript:"));
+    assert_eq!(get_user_profile_header(&user.username, &db_pool).await?.bio, Some(bio));
+
+    let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
+    assert_eq!(user.markdown_bio.as_deref(), Some(markdown_bio));
+
+    // Too long bios are rejected and leave the current one untouched
+    assert!(set_user_bio(&"a".repeat(MAX_BIO_LENGTH + 1), &user, &db_pool).await.is_err());
+    assert!(set_user_bio(&"a".repeat(MAX_BIO_LENGTH), &user, &db_pool).await.is_ok());
+    assert_eq!(User::get(user.user_id, &db_pool).await.expect("Should get user").markdown_bio, Some("a".repeat(MAX_BIO_LENGTH)));
+
+    // An empty bio clears it
+    assert_eq!(set_user_bio("  ", &user, &db_pool).await?, None);
+    let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
+    assert_eq!(user.bio, None);
+    assert_eq!(user.markdown_bio, None);
+    assert_eq!(get_user_profile_header(&user.username, &db_pool).await?.bio, None);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delete_user() {
     let db_pool = get_db_pool().await;
@@ -61,13 +272,13 @@ async fn test_delete_user() {
     let deleted_ban_user = User::get(banned_user.user_id, &db_pool).await.expect("Should get user");
     assert_eq!(deleted_ban_user.user_id, banned_user.user_id);
     assert!(deleted_ban_user.ban_status_by_sphere_map.is_empty());
-    let deleted_ban_user_post_vec = get_user_post_vec(&banned_user.username, SortType::Post(PostSortType::Hot), 1, 0, &db_pool).await.expect("Should get user posts");
+    let deleted_ban_user_post_vec = get_user_post_vec(&banned_user.username, SortType::Post(PostSortType::Hot), None, 1, 0, &db_pool).await.expect("Should get user posts").content_vec;
     assert!(deleted_ban_user_post_vec.is_empty());
 
     delete_user(&user, &db_pool).await.expect("Should delete user");
 
-    let deleted_user_post_vec = get_user_post_vec(&user.username, SortType::Post(PostSortType::Hot), 1, 0, &db_pool).await.expect("Should get user posts");
-    let deleted_user_comment_vec = get_user_comment_vec(&user.username, SortType::Comment(CommentSortType::Recent), 1, 0, &db_pool).await.expect("Should get user comments");
+    let deleted_user_post_vec = get_user_post_vec(&user.username, SortType::Post(PostSortType::Hot), None, 1, 0, &db_pool).await.expect("Should get user posts").content_vec;
+    let deleted_user_comment_vec = get_user_comment_vec(&user.username, SortType::Comment(CommentSortType::Recent), None, 1, 0, &db_pool).await.expect("Should get user comments").content_vec;
     assert!(deleted_user_post_vec.is_empty());
     assert!(deleted_user_comment_vec.is_empty());
     assert_eq!(get_user_sphere_role(user.user_id, &sphere.sphere_name, &db_pool).await, Err(AppError::NotFound));
@@ -83,27 +294,44 @@ async fn test_set_user_settings() {
     let db_pool = get_db_pool().await;
     let user = create_test_user(&db_pool).await;
     
-    set_user_settings(true, true, 0, &user, &db_pool).await.expect("Should set user settings");
+    set_user_settings(true, NsfwMode::Show, 0, &user, &db_pool).await.expect("Should set user settings");
     let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
     assert_eq!(user.is_nsfw, true);
-    assert_eq!(user.show_nsfw, true);
+    assert_eq!(user.nsfw_mode, NsfwMode::Show);
     assert_eq!(user.days_hide_spoiler, None);
 
-    set_user_settings(true, false, 1, &user, &db_pool).await.expect("Should set user settings");
+    set_user_settings(true, NsfwMode::Hide, 1, &user, &db_pool).await.expect("Should set user settings");
     let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
     assert_eq!(user.is_nsfw, true);
-    assert_eq!(user.show_nsfw, false);
+    assert_eq!(user.nsfw_mode, NsfwMode::Hide);
     assert_eq!(user.days_hide_spoiler, Some(1));
 
-    set_user_settings(false, true, 10, &user, &db_pool).await.expect("Should set user settings");
+    set_user_settings(false, NsfwMode::Blur, 10, &user, &db_pool).await.expect("Should set user settings");
     let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
     assert_eq!(user.is_nsfw, false);
-    assert_eq!(user.show_nsfw, true);
+    assert_eq!(user.nsfw_mode, NsfwMode::Blur);
     assert_eq!(user.days_hide_spoiler, Some(10));
 
-    set_user_settings(false, false, 0, &user, &db_pool).await.expect("Should set user preferences");
+    set_user_settings(false, NsfwMode::Hide, 0, &user, &db_pool).await.expect("Should set user preferences");
     let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
     assert_eq!(user.is_nsfw, false);
-    assert_eq!(user.show_nsfw, false);
+    assert_eq!(user.nsfw_mode, NsfwMode::Hide);
     assert_eq!(user.days_hide_spoiler, None);
 }
+
+#[tokio::test]
+async fn test_set_default_post_sort() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_test_user(&db_pool).await;
+    assert_eq!(user.default_post_sort, PostSortType::Hot);
+
+    set_default_post_sort(PostSortType::Best, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
+    assert_eq!(user.default_post_sort, PostSortType::Best);
+
+    set_default_post_sort(PostSortType::Recent, &user, &db_pool).await?;
+    let user = User::get(user.user_id, &db_pool).await.expect("Should get user");
+    assert_eq!(user.default_post_sort, PostSortType::Recent);
+
+    Ok(())
+}
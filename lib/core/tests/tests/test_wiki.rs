@@ -0,0 +1,162 @@
+use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
+use sphare_core_common::errors::AppError;
+use sphare_core_sphere::sphere::ssr::create_sphere;
+use sphare_core_sphere::wiki::ssr::{create_sphere_page, delete_sphere_page, get_sphere_page, get_sphere_page_vec, update_sphere_page};
+use sphare_core_user::user::User;
+
+use crate::common::{create_user, get_db_pool};
+
+mod common;
+
+#[tokio::test]
+async fn test_create_sphere_page() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("user", &db_pool).await;
+    let lead = create_user("lead", &db_pool).await;
+    let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    let markdown_body = "# Welcome\n\n<script>alert('x')</script>**bold**";
+    let (expected_html_body, _) = get_html_and_markdown_strings(markdown_body, true)?;
+
+    assert_eq!(
+        create_sphere_page(&sphere.sphere_name, "faq", "FAQ", markdown_body, true, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert!(create_sphere_page(&sphere.sphere_name, "Invalid_Slug", "FAQ", markdown_body, true, &lead, &db_pool).await.is_err());
+    assert!(create_sphere_page(&sphere.sphere_name, "faq", "", markdown_body, true, &lead, &db_pool).await.is_err());
+    assert!(create_sphere_page(&sphere.sphere_name, "faq", "FAQ", "", true, &lead, &db_pool).await.is_err());
+
+    let page = create_sphere_page(&sphere.sphere_name, "faq", "FAQ", markdown_body, true, &lead, &db_pool).await?;
+    assert_eq!(page.sphere_id, sphere.sphere_id);
+    assert_eq!(page.slug, "faq");
+    assert_eq!(page.title, "FAQ");
+    assert_eq!(page.markdown_body, markdown_body);
+    assert_eq!(page.html_body, expected_html_body);
+    assert!(!page.html_body.contains("<script>"));
+    assert!(page.is_markdown);
+    assert_eq!(page.creator_id, lead.user_id);
+    assert_eq!(page.editor_id, lead.user_id);
+    assert_eq!(page.edit_timestamp, None);
+
+    // slugs are unique within a sphere
+    assert!(create_sphere_page(&sphere.sphere_name, "faq", "FAQ 2", markdown_body, true, &lead, &db_pool).await.is_err());
+
+    // plain text bodies are not converted from markdown
+    let plain_body = "# Not a title";
+    let plain_page = create_sphere_page(&sphere.sphere_name, "plain", "Plain", plain_body, false, &lead, &db_pool).await?;
+    assert_eq!(plain_page.markdown_body, plain_body);
+    assert_eq!(plain_page.html_body, plain_body);
+    assert!(!plain_page.is_markdown);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_sphere_page() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let lead = create_user("lead", &db_pool).await;
+    let sphere_1 = create_sphere("sphere1", "a", false, &lead, &db_pool).await?;
+    let sphere_2 = create_sphere("sphere2", "b", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    let faq_page = create_sphere_page(&sphere_1.sphere_name, "faq", "FAQ", "faq", true, &lead, &db_pool).await?;
+    let about_page = create_sphere_page(&sphere_1.sphere_name, "about", "About", "about", true, &lead, &db_pool).await?;
+    let sphere_2_faq_page = create_sphere_page(&sphere_2.sphere_name, "faq", "FAQ", "other faq", true, &lead, &db_pool).await?;
+
+    assert_eq!(get_sphere_page(&sphere_1.sphere_name, "faq", &db_pool).await?, faq_page);
+    assert_eq!(get_sphere_page(&sphere_2.sphere_name, "faq", &db_pool).await?, sphere_2_faq_page);
+    assert_eq!(get_sphere_page(&sphere_2.sphere_name, "about", &db_pool).await, Err(AppError::NotFound));
+    assert!(get_sphere_page(&sphere_1.sphere_name, "Invalid_Slug", &db_pool).await.is_err());
+
+    assert_eq!(get_sphere_page_vec(&sphere_1.sphere_name, &db_pool).await?, vec![about_page, faq_page]);
+    assert_eq!(get_sphere_page_vec(&sphere_2.sphere_name, &db_pool).await?, vec![sphere_2_faq_page]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_sphere_page() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("user", &db_pool).await;
+    let lead = create_user("lead", &db_pool).await;
+    let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    let page = create_sphere_page(&sphere.sphere_name, "faq", "FAQ", "faq", true, &lead, &db_pool).await?;
+
+    let updated_body = "## Updated\n\n[link](javascript:alert('x'))";
+    let (expected_html_body, _) = get_html_and_markdown_strings(updated_body, true)?;
+
+    assert_eq!(
+        update_sphere_page(&sphere.sphere_name, "faq", "Updated FAQ", updated_body, true, &user, &db_pool).await,
+        Err(AppError::InsufficientPrivileges),
+    );
+    assert_eq!(get_sphere_page(&sphere.sphere_name, "faq", &db_pool).await?, page);
+    assert_eq!(
+        update_sphere_page(&sphere.sphere_name, "missing", "Updated FAQ", updated_body, true, &lead, &db_pool).await,
+        Err(AppError::NotFound),
+    );
+
+    let updated_page = update_sphere_page(&sphere.sphere_name, "faq", "Updated FAQ", updated_body, true, &lead, &db_pool).await?;
+    assert_eq!(updated_page.page_id, page.page_id);
+    assert_eq!(updated_page.slug, page.slug);
+    assert_eq!(updated_page.title, "Updated FAQ");
+    assert_eq!(updated_page.markdown_body, updated_body);
+    assert_eq!(updated_page.html_body, expected_html_body);
+    assert!(!updated_page.html_body.contains("javascript:"));
+    assert_eq!(updated_page.creator_id, lead.user_id);
+    assert_eq!(updated_page.editor_id, lead.user_id);
+    assert_eq!(updated_page.create_timestamp, page.create_timestamp);
+    assert!(updated_page.edit_timestamp.is_some());
+    assert!(updated_page.is_markdown);
+    assert_eq!(get_sphere_page(&sphere.sphere_name, "faq", &db_pool).await?, updated_page);
+
+    let plain_page = update_sphere_page(&sphere.sphere_name, "faq", "Plain FAQ", updated_body, false, &lead, &db_pool).await?;
+    assert_eq!(plain_page.markdown_body, updated_body);
+    assert_eq!(plain_page.html_body, updated_body);
+    assert!(!plain_page.is_markdown);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sphere_page_plain_text_html_escaped() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let lead = create_user("lead", &db_pool).await;
+    let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    let script_body = "<script>alert('x')</script>";
+    let page = create_sphere_page(&sphere.sphere_name, "faq", "FAQ", script_body, false, &lead, &db_pool).await?;
+    assert_eq!(page.markdown_body, script_body);
+    assert_eq!(page.html_body, "&lt;script&gt;alert('x')&lt;/script&gt;");
+
+    let onerror_body = r#"<img src=x onerror="alert('x')">"#;
+    let updated_page = update_sphere_page(&sphere.sphere_name, "faq", "FAQ", onerror_body, false, &lead, &db_pool).await?;
+    assert_eq!(updated_page.markdown_body, onerror_body);
+    assert_eq!(updated_page.html_body, r#"&lt;img src=x onerror="alert('x')"&gt;"#);
+    assert!(!updated_page.html_body.contains("<img"));
+    assert_eq!(get_sphere_page(&sphere.sphere_name, "faq", &db_pool).await?, updated_page);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_sphere_page() -> Result<(), AppError> {
+    let db_pool = get_db_pool().await;
+    let user = create_user("user", &db_pool).await;
+    let lead = create_user("lead", &db_pool).await;
+    let sphere = create_sphere("sphere", "a", false, &lead, &db_pool).await?;
+    let lead = User::get(lead.user_id, &db_pool).await.expect("User should be loaded after sphere creation");
+
+    let page = create_sphere_page(&sphere.sphere_name, "faq", "FAQ", "faq", true, &lead, &db_pool).await?;
+
+    assert_eq!(delete_sphere_page(&sphere.sphere_name, "faq", &user, &db_pool).await, Err(AppError::InsufficientPrivileges));
+    assert_eq!(get_sphere_page(&sphere.sphere_name, "faq", &db_pool).await?, page);
+
+    delete_sphere_page(&sphere.sphere_name, "faq", &lead, &db_pool).await?;
+    assert_eq!(get_sphere_page(&sphere.sphere_name, "faq", &db_pool).await, Err(AppError::NotFound));
+
+    Ok(())
+}
@@ -10,20 +10,22 @@ use leptos::server_fn::codec::MultipartData;
 use multer::Multipart;
 use sqlx::PgPool;
 
+use sphare_core_common::constants::BEST_SORT_MIN_VOTE_COUNT;
 use sphare_core_common::errors::AppError;
 use sphare_core_content::comment::Comment;
 use sphare_core_content::comment::{CommentWithChildren, CommentWithContext};
-use sphare_core_content::post::Post;
+use sphare_core_content::post::{get_wilson_score_lower_bound, Post};
 use sphare_core_content::post::PostWithSphereInfo;
 use sphare_core_content::ranking::{CommentSortType, PostSortType, Vote, VoteValue};
 use sphare_core_user::notification::Notification;
 use sphare_core_user::role::UserSphereRole;
 use sphare_core_user::user::UserBan;
 
-pub const POST_SORT_TYPE_ARRAY: [PostSortType; 4] = [
+pub const POST_SORT_TYPE_ARRAY: [PostSortType; 5] = [
     PostSortType::Hot,
     PostSortType::Trending,
     PostSortType::Best,
+    PostSortType::BestRatio,
     PostSortType::Recent,
 ];
 
@@ -44,7 +46,14 @@ pub fn sort_post_vec(
             _ => match sort_type {
                 PostSortType::Hot => r.post.recommended_score.partial_cmp(&l.post.recommended_score).unwrap(),
                 PostSortType::Trending => r.post.trending_score.partial_cmp(&l.post.trending_score).unwrap(),
-                PostSortType::Best => r.post.score.partial_cmp(&l.post.score).unwrap(),
+                PostSortType::Best => {
+                    let has_min_votes = |post: &Post| post.score + 2 * post.score_minus >= BEST_SORT_MIN_VOTE_COUNT;
+                    has_min_votes(&r.post).cmp(&has_min_votes(&l.post)).then(r.post.score.cmp(&l.post.score))
+                },
+                PostSortType::BestRatio => {
+                    let wilson_score = |post: &Post| get_wilson_score_lower_bound(post.upvotes(), post.downvotes());
+                    wilson_score(&r.post).partial_cmp(&wilson_score(&l.post)).unwrap().then(r.post.score.cmp(&l.post.score))
+                },
                 PostSortType::Recent => r.post.create_timestamp.partial_cmp(&l.post.create_timestamp).unwrap(),
 
             }
@@ -242,6 +251,34 @@ pub async fn get_user_ban_by_id(
     Ok(user_ban)
 }
 
+pub async fn set_user_ban_until_timestamp(
+    ban_id: i64,
+    until_timestamp: chrono::DateTime<chrono::Utc>,
+    db_pool: &PgPool,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        "UPDATE user_bans SET until_timestamp = $1 WHERE ban_id = $2",
+        until_timestamp,
+        ban_id,
+    ).execute(db_pool).await?;
+
+    Ok(())
+}
+
+pub async fn set_username_change_timestamp(
+    change_id: i64,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    db_pool: &PgPool,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        "UPDATE username_history SET create_timestamp = $1 WHERE change_id = $2",
+        timestamp,
+        change_id,
+    ).execute(db_pool).await?;
+
+    Ok(())
+}
+
 pub async fn get_notification(
     notification_id: i64,
     db_pool: &PgPool,
@@ -432,4 +469,48 @@ pub async fn get_invalid_multipart_image_with_string(
     let stream = once(async move { Result::<Bytes, Infallible>::Ok(Bytes::from(body)) });
     let multipart = Multipart::new(stream, boundary);
     MultipartData::Server(multipart)
-}
\ No newline at end of file
+}
+
+pub async fn get_multipart_images_with_string(
+    image_field_name: &str,
+    num_images: usize,
+    string_field_name: &str,
+    string_value: &str,
+) -> MultipartData {
+    let mut body = Vec::new();
+    let boundary = "boundary-test";
+
+    body.extend_from_slice(format!(
+        "--{boundary}\r\n\
+         Content-Disposition: form-data; name=\"{string_field_name}\"\r\n\r\n\
+         {string_value}\r\n"
+    ).as_bytes());
+    for i in 0..num_images {
+        body.extend_from_slice(format!(
+            "--{boundary}\r\n\
+             Content-Disposition: form-data; name=\"{image_field_name}\"; filename=\"test_{i}.png\"\r\n\
+             Content-Type: image/png\r\n\r\n"
+        ).as_bytes());
+        body.extend_from_slice(get_png_data());
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+
+    let stream = once(async move { Result::<Bytes, Infallible>::Ok(Bytes::from(body)) });
+    let multipart = Multipart::new(stream, boundary);
+    MultipartData::Server(multipart)
+}
+
+pub async fn set_user_timestamp(
+    user_id: i64,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    db_pool: &PgPool,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        "UPDATE users SET timestamp = $1 WHERE user_id = $2",
+        timestamp,
+        user_id,
+    ).execute(db_pool).await?;
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+/// Site-wide announcement managed by admins, displayed during its active window to each user until they dismiss it.
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Announcement {
+    pub announcement_id: i64,
+    pub title: String,
+    pub body: String,
+    pub markdown_body: Option<String>,
+    pub creator_id: i64,
+    pub start_timestamp: chrono::DateTime<chrono::Utc>,
+    pub end_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+    pub delete_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use sqlx::PgPool;
+
+    use sphare_core_common::checks::check_string_length;
+    use sphare_core_common::constants::{MAX_CONTENT_LENGTH, MAX_TITLE_LENGTH};
+    use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
+    use sphare_core_common::errors::AppError;
+
+    use crate::announcement::Announcement;
+    use crate::role::AdminRole;
+    use crate::user::User;
+
+    /// Creates an announcement displayed from `start_timestamp`, or immediately if `None`, until `end_timestamp`, or
+    /// indefinitely if `None`. Only admins can create announcements.
+    pub async fn create_announcement(
+        title: &str,
+        body: &str,
+        is_markdown: bool,
+        start_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+        end_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Announcement, AppError> {
+        user.check_admin_role(AdminRole::Admin)?;
+        check_string_length(title, "Title", MAX_TITLE_LENGTH as usize, false)?;
+        check_string_length(body, "Body", MAX_CONTENT_LENGTH as usize, false)?;
+        let start_timestamp = start_timestamp.unwrap_or_else(chrono::Utc::now);
+        if end_timestamp.is_some_and(|end_timestamp| end_timestamp <= start_timestamp || end_timestamp <= chrono::Utc::now()) {
+            return Err(AppError::new("Announcement must end in the future and after it starts."));
+        }
+        let (body, markdown_body) = get_html_and_markdown_strings(body, is_markdown)?;
+
+        let announcement = sqlx::query_as!(
+            Announcement,
+            "INSERT INTO announcements (title, body, markdown_body, creator_id, start_timestamp, end_timestamp)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *",
+            title,
+            body,
+            markdown_body,
+            user.user_id,
+            start_timestamp,
+            end_timestamp,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(announcement)
+    }
+
+    /// Returns the announcements within their active window, ordered from newest to oldest.
+    pub async fn get_active_announcements(
+        db_pool: &PgPool,
+    ) -> Result<Vec<Announcement>, AppError> {
+        let announcement_vec = sqlx::query_as!(
+            Announcement,
+            "SELECT * FROM announcements
+            WHERE
+                delete_timestamp IS NULL AND
+                start_timestamp <= NOW() AND
+                (end_timestamp IS NULL OR end_timestamp > NOW())
+            ORDER BY create_timestamp DESC, announcement_id DESC",
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(announcement_vec)
+    }
+
+    /// Returns the announcements within their active window that `user` did not dismiss yet, ordered from newest to oldest.
+    pub async fn get_unseen_announcements(
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<Announcement>, AppError> {
+        let announcement_vec = sqlx::query_as!(
+            Announcement,
+            "SELECT a.* FROM announcements a
+            WHERE
+                a.delete_timestamp IS NULL AND
+                a.start_timestamp <= NOW() AND
+                (a.end_timestamp IS NULL OR a.end_timestamp > NOW()) AND
+                NOT EXISTS (
+                    SELECT 1 FROM announcement_dismissals d
+                    WHERE d.announcement_id = a.announcement_id AND d.user_id = $1
+                )
+            ORDER BY a.create_timestamp DESC, a.announcement_id DESC",
+            user.user_id,
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(announcement_vec)
+    }
+
+    /// Hides the announcement `announcement_id` for `user`. Returns [AppError::NotFound] if the announcement does not exist.
+    pub async fn dismiss_announcement(
+        announcement_id: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "WITH announcement AS (
+                SELECT announcement_id FROM announcements WHERE announcement_id = $1
+            ), dismissal AS (
+                INSERT INTO announcement_dismissals (announcement_id, user_id)
+                SELECT announcement_id, $2 FROM announcement
+                ON CONFLICT (announcement_id, user_id) DO NOTHING
+            )
+            SELECT announcement_id FROM announcement",
+            announcement_id,
+            user.user_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+pub mod announcement;
 pub mod auth;
+pub mod mod_log;
 pub mod notification;
 pub mod role;
 pub mod session;
@@ -0,0 +1,131 @@
+use leptos::prelude::Signal;
+use leptos_fluent::move_tr;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, IntoStaticStr};
+
+use sphare_core_common::traits::ToLocalizedStr;
+
+pub const MOD_LOG_FETCH_LIMIT: i64 = 50;
+
+#[repr(i16)]
+#[derive(Clone, Copy, Debug, Display, EnumString, Eq, IntoStaticStr, Hash, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+pub enum ModAction {
+    ModeratePost = 0,
+    ModerateComment = 1,
+    ApprovePost = 2,
+    MovePost = 3,
+    BanUser = 4,
+    UnbanUser = 5,
+    SetRole = 6,
+    MuteUser = 7,
+    UnmuteUser = 8,
+    UnmoderatePost = 9,
+    UnmoderateComment = 10,
+}
+
+/// Entry of the moderation log of a sphere, recording which moderator performed which action on which target
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModLogEntry {
+    pub log_id: i64,
+    pub sphere_id: i64,
+    pub action: ModAction,
+    pub actor_id: i64,
+    pub actor_name: String,
+    pub target_user_id: Option<i64>,
+    pub target_username: Option<String>,
+    pub post_id: Option<i64>,
+    pub comment_id: Option<i64>,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl ToLocalizedStr for ModAction {
+    fn to_localized_str(&self) -> Signal<String> {
+        match self {
+            ModAction::ModeratePost => move_tr!("mod-action-moderate-post"),
+            ModAction::ModerateComment => move_tr!("mod-action-moderate-comment"),
+            ModAction::ApprovePost => move_tr!("mod-action-approve-post"),
+            ModAction::MovePost => move_tr!("mod-action-move-post"),
+            ModAction::BanUser => move_tr!("mod-action-ban-user"),
+            ModAction::UnbanUser => move_tr!("mod-action-unban-user"),
+            ModAction::SetRole => move_tr!("mod-action-set-role"),
+            ModAction::MuteUser => move_tr!("mod-action-mute-user"),
+            ModAction::UnmuteUser => move_tr!("mod-action-unmute-user"),
+            ModAction::UnmoderatePost => move_tr!("mod-action-unmoderate-post"),
+            ModAction::UnmoderateComment => move_tr!("mod-action-unmoderate-comment"),
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use std::cmp::max;
+
+    use sqlx::{PgExecutor, PgPool};
+
+    use sphare_core_common::checks::check_sphere_name;
+    use sphare_core_common::errors::AppError;
+
+    use crate::mod_log::{ModAction, ModLogEntry, MOD_LOG_FETCH_LIMIT};
+    use crate::role::PermissionLevel;
+    use crate::user::User;
+
+    /// Records that the moderator `actor_id` performed `action` in the sphere `sphere_id`,
+    /// using `executor` so that it can be part of a larger transaction
+    pub async fn insert_mod_log<'e, E: PgExecutor<'e>>(
+        sphere_id: i64,
+        action: ModAction,
+        actor_id: i64,
+        target_user_id: Option<i64>,
+        post_id: Option<i64>,
+        comment_id: Option<i64>,
+        executor: E,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO mod_log (sphere_id, action, actor_id, target_user_id, post_id, comment_id)
+            VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+            .bind(sphere_id)
+            .bind(action)
+            .bind(actor_id)
+            .bind(target_user_id)
+            .bind(post_id)
+            .bind(comment_id)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the moderation log of the sphere `sphere_name`, most recent first, only to its moderators.
+    pub async fn get_mod_log(
+        sphere_name: &str,
+        limit: i64,
+        offset: i64,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Vec<ModLogEntry>, AppError> {
+        check_sphere_name(sphere_name)?;
+        user.check_sphere_permissions_by_name(sphere_name, PermissionLevel::Moderate)?;
+
+        let mod_log_vec = sqlx::query_as::<_, ModLogEntry>(
+            "SELECT l.*, a.username as actor_name, t.username as target_username
+            FROM mod_log l
+            JOIN spheres s ON s.sphere_id = l.sphere_id
+            JOIN users a ON a.user_id = l.actor_id
+            LEFT JOIN users t ON t.user_id = l.target_user_id
+            WHERE s.sphere_name = $1
+            ORDER BY l.create_timestamp DESC, l.log_id DESC
+            LIMIT $2
+            OFFSET $3",
+        )
+            .bind(sphere_name)
+            .bind(limit.clamp(1, MOD_LOG_FETCH_LIMIT))
+            .bind(max(offset, 0))
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(mod_log_vec)
+    }
+}
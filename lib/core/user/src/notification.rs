@@ -23,6 +23,8 @@ pub enum NotificationType {
     PostReply = 0,
     CommentReply = 1,
     Moderation = 2,
+    Mention = 3,
+    SavedSearch = 4,
 }
 
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
@@ -119,11 +121,11 @@ impl NotifHandler {
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
-    use sqlx::PgPool;
+    use sqlx::{PgExecutor, PgPool};
 
     use sphare_core_common::errors::AppError;
 
-    use crate::notification::{Notification, NotificationType, NOTIF_RETENTION_DAYS};
+    use crate::notification::{Notification, NotificationType};
 
     pub async fn create_notification(
         post_id: i64,
@@ -173,6 +175,119 @@ pub mod ssr {
         Ok(notification)
     }
 
+    /// Notifies each existing user in `username_vec` that they were mentioned in a post or comment.
+    /// The trigger user is never notified and unknown usernames are ignored.
+    pub async fn create_mention_notifications(
+        post_id: i64,
+        comment_id: Option<i64>,
+        username_vec: &[String],
+        trigger_user_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<Notification>, AppError> {
+        if username_vec.is_empty() {
+            return Ok(Vec::new());
+        }
+        let notification_vec = sqlx::query_as::<_, Notification>(
+            "WITH trigger_user AS (
+                SELECT username FROM users WHERE user_id = $4
+            ), post_info AS (
+                SELECT sphere_id, satellite_id FROM posts WHERE post_id = $1
+            ), new_notification AS (
+                INSERT INTO notifications (sphere_id, satellite_id, post_id, comment_id, user_id, trigger_user_id, notification_type)
+                SELECT
+                    p.sphere_id,
+                    p.satellite_id,
+                    $1, $2,
+                    u.user_id,
+                    $4, $5
+                FROM post_info p, users u
+                WHERE
+                    u.username = ANY($3) AND
+                    u.user_id != $4 AND
+                    u.delete_timestamp IS NULL
+                RETURNING *
+            )
+            SELECT n.*, u.username AS trigger_username, s.sphere_name, s.icon_url, s.is_nsfw
+            FROM new_notification n, trigger_user u, spheres s
+            WHERE s.sphere_id = n.sphere_id
+            ORDER BY n.notification_id",
+        )
+            .bind(post_id)
+            .bind(comment_id)
+            .bind(username_vec)
+            .bind(trigger_user_id)
+            .bind(NotificationType::Mention as i16)
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(notification_vec)
+    }
+
+    /// Notifies the user `user_id` that each post of `post_id_vec` matches one of their saved searches.
+    /// The posts' authors are the notifications' trigger users, posts of `user_id` are never notified.
+    pub async fn create_saved_search_notifications<'e, E: PgExecutor<'e>>(
+        user_id: i64,
+        post_id_vec: &[i64],
+        executor: E,
+    ) -> Result<Vec<Notification>, AppError> {
+        if post_id_vec.is_empty() {
+            return Ok(Vec::new());
+        }
+        let notification_vec = sqlx::query_as::<_, Notification>(
+            "WITH new_notification AS (
+                INSERT INTO notifications (sphere_id, satellite_id, post_id, comment_id, user_id, trigger_user_id, notification_type)
+                SELECT
+                    p.sphere_id,
+                    p.satellite_id,
+                    p.post_id,
+                    NULL,
+                    $1,
+                    p.creator_id,
+                    $3
+                FROM posts p
+                WHERE
+                    p.post_id = ANY($2) AND
+                    p.creator_id != $1
+                RETURNING *
+            )
+            SELECT n.*, u.username AS trigger_username, s.sphere_name, s.icon_url, s.is_nsfw
+            FROM new_notification n
+            JOIN users u ON u.user_id = n.trigger_user_id
+            JOIN spheres s ON s.sphere_id = n.sphere_id
+            ORDER BY n.notification_id",
+        )
+            .bind(user_id)
+            .bind(post_id_vec)
+            .bind(NotificationType::SavedSearch as i16)
+            .fetch_all(executor)
+            .await?;
+
+        Ok(notification_vec)
+    }
+
+    /// Notifies the author of the replied-to content: the parent comment's author for nested replies,
+    /// the post's author for top-level comments. Returns `None` for self-replies.
+    pub async fn create_reply_notification(
+        post_id: i64,
+        parent_comment_id: Option<i64>,
+        comment_id: i64,
+        trigger_user_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<Option<Notification>, AppError> {
+        let notification_type = match parent_comment_id {
+            Some(_) => NotificationType::CommentReply,
+            None => NotificationType::PostReply,
+        };
+        create_notification(
+            post_id,
+            parent_comment_id,
+            Some(comment_id),
+            trigger_user_id,
+            notification_type,
+            db_pool,
+        ).await
+    }
+
     pub async fn get_notifications(
         user_id: i64,
         db_pool: &PgPool,
@@ -224,13 +339,15 @@ pub mod ssr {
         Ok(())
     }
 
+    /// Deletes notifications older than `retention_days` days.
     pub async fn delete_stale_notifications(
+        retention_days: i64,
         db_pool: &PgPool,
     ) -> Result<(), AppError> {
         sqlx::query!(
             "DELETE FROM notifications
             WHERE create_timestamp < NOW() - (INTERVAL '1 day' * $1)",
-            NOTIF_RETENTION_DAYS as f64,
+            retention_days as f64,
         )
             .execute(db_pool)
             .await?;
@@ -274,6 +391,8 @@ pub fn get_notification_text(notification: &Notification) -> Signal<String> {
         (NotificationType::CommentReply, _) => move_tr!("notification-comment-reply"),
         (NotificationType::Moderation, Some(_)) => move_tr!("notification-moderate-comment"),
         (NotificationType::Moderation, None) => move_tr!("notification-moderate-post"),
+        (NotificationType::Mention, _) => move_tr!("notification-mention"),
+        (NotificationType::SavedSearch, _) => move_tr!("notification-saved-search"),
     }
 }
 
@@ -293,6 +412,12 @@ pub fn get_web_notif_text(notification: &Notification) -> String {
         (NotificationType::Moderation, None) => tr!(
             "web-notif-moderate-post", {"username" => username, "sphere_name" => sphere_name}
         ),
+        (NotificationType::Mention, _) => tr!(
+            "web-notif-mention", {"username" => username, "sphere_name" => sphere_name}
+        ),
+        (NotificationType::SavedSearch, _) => tr!(
+            "web-notif-saved-search", {"username" => username, "sphere_name" => sphere_name}
+        ),
     }
 }
 
@@ -604,6 +729,26 @@ mod tests {
             *notif_text.read(),
             tr!("notification-moderate-comment"),
         );
+
+        let notif_mention = Notification {
+            notification_type: NotificationType::Mention,
+            ..Default::default()
+        };
+        let notif_text = get_notification_text(&notif_mention);
+        assert_eq!(
+            *notif_text.read(),
+            tr!("notification-mention"),
+        );
+
+        let notif_saved_search = Notification {
+            notification_type: NotificationType::SavedSearch,
+            ..Default::default()
+        };
+        let notif_text = get_notification_text(&notif_saved_search);
+        assert_eq!(
+            *notif_text.read(),
+            tr!("notification-saved-search"),
+        );
     }
 
     #[test]
@@ -686,5 +831,42 @@ mod tests {
                 }
             ),
         );
+
+        let notif_mention = Notification {
+            notification_type: NotificationType::Mention,
+            comment_id: Some(1),
+            trigger_username: String::from("e"),
+            sphere_header: SphereHeader::new(String::from("m"), None, false),
+            ..Default::default()
+        };
+        let notif_text = get_web_notif_text(&notif_mention);
+        assert_eq!(
+            notif_text,
+            tr!(
+                "web-notif-mention",
+                {
+                    "username" => notif_mention.trigger_username,
+                    "sphere_name" => notif_mention.sphere_header.sphere_name
+                }
+            ),
+        );
+
+        let notif_saved_search = Notification {
+            notification_type: NotificationType::SavedSearch,
+            trigger_username: String::from("f"),
+            sphere_header: SphereHeader::new(String::from("n"), None, false),
+            ..Default::default()
+        };
+        let notif_text = get_web_notif_text(&notif_saved_search);
+        assert_eq!(
+            notif_text,
+            tr!(
+                "web-notif-saved-search",
+                {
+                    "username" => notif_saved_search.trigger_username,
+                    "sphere_name" => notif_saved_search.sphere_header.sphere_name
+                }
+            ),
+        );
     }
 }
\ No newline at end of file
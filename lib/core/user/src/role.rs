@@ -47,6 +47,17 @@ impl AdminRole {
     }
 }
 
+impl PermissionLevel {
+    /// Returns whether a user with this permission level can change the role of a user with `target_level` to `granted_level`.
+    /// Users can only manage users with a strictly lower level and grant levels strictly lower than their own,
+    /// except leaders who can designate another leader.
+    pub fn can_set_user_role(self, target_level: PermissionLevel, granted_level: PermissionLevel) -> bool {
+        self >= PermissionLevel::Manage &&
+            target_level < self &&
+            (granted_level < self || self == PermissionLevel::Lead)
+    }
+}
+
 impl From<String> for AdminRole {
     fn from(value: String) -> AdminRole {
         AdminRole::from_str(&value).unwrap_or(AdminRole::None)
@@ -78,6 +89,9 @@ pub mod ssr {
     use crate::user::{ssr::SqlUser, User};
     use sphare_core_common::errors::AppError;
 
+    use crate::mod_log::ModAction;
+    use crate::mod_log::ssr::insert_mod_log;
+
     use super::*;
 
     pub async fn get_user_sphere_role(
@@ -146,8 +160,8 @@ pub mod ssr {
         check_username(username, false)?;
         check_sphere_name(sphere_name)?;
         let assigned_user = SqlUser::get_by_username(username, db_pool).await?;
-        if permission_level == PermissionLevel::Lead {
-            set_sphere_leader(assigned_user.user_id, sphere_name, grantor, db_pool).await
+        let (user_sphere_role, prev_leader_id) = if permission_level == PermissionLevel::Lead {
+            set_sphere_leader(assigned_user.user_id, sphere_name, grantor, db_pool).await?
         } else {
             let user_sphere_role = insert_user_sphere_role(
                 assigned_user.user_id,
@@ -156,8 +170,20 @@ pub mod ssr {
                 grantor,
                 db_pool,
             ).await?;
-            Ok((user_sphere_role, None))
-        }
+            (user_sphere_role, None)
+        };
+
+        insert_mod_log(
+            user_sphere_role.sphere_id,
+            ModAction::SetRole,
+            grantor.user_id,
+            Some(user_sphere_role.user_id),
+            None,
+            None,
+            db_pool,
+        ).await?;
+
+        Ok((user_sphere_role, prev_leader_id))
     }
 
     async fn set_sphere_leader(
@@ -340,4 +366,25 @@ mod tests {
         assert_eq!(AdminRole::from(String::from("Admin")), AdminRole::Admin);
         assert_eq!(AdminRole::from(String::from("invalid")), AdminRole::None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_permission_level_can_set_user_role() {
+        // levels below manage cannot set any role
+        for grantor_level in [PermissionLevel::None, PermissionLevel::Moderate, PermissionLevel::Ban] {
+            assert!(!grantor_level.can_set_user_role(PermissionLevel::None, PermissionLevel::Moderate));
+        }
+
+        // manage can only grant lower levels to users with a lower level
+        assert!(PermissionLevel::Manage.can_set_user_role(PermissionLevel::None, PermissionLevel::Ban));
+        assert!(PermissionLevel::Manage.can_set_user_role(PermissionLevel::Ban, PermissionLevel::None));
+        assert!(!PermissionLevel::Manage.can_set_user_role(PermissionLevel::None, PermissionLevel::Manage));
+        assert!(!PermissionLevel::Manage.can_set_user_role(PermissionLevel::Moderate, PermissionLevel::Lead));
+        assert!(!PermissionLevel::Manage.can_set_user_role(PermissionLevel::Manage, PermissionLevel::Moderate));
+        assert!(!PermissionLevel::Manage.can_set_user_role(PermissionLevel::Lead, PermissionLevel::None));
+
+        // leaders can grant any level to users other than leaders
+        assert!(PermissionLevel::Lead.can_set_user_role(PermissionLevel::Manage, PermissionLevel::None));
+        assert!(PermissionLevel::Lead.can_set_user_role(PermissionLevel::None, PermissionLevel::Lead));
+        assert!(!PermissionLevel::Lead.can_set_user_role(PermissionLevel::Lead, PermissionLevel::Manage));
+    }
+}
@@ -1,10 +1,16 @@
 use std::cmp::max;
 use std::collections::HashMap;
 use std::default::Default;
+use std::str::FromStr;
 
+use leptos::prelude::Signal;
+use leptos_fluent::move_tr;
 use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
 
+use sphare_core_common::common::PostSortType;
 use sphare_core_common::errors::AppError;
+use sphare_core_common::traits::ToLocalizedStr;
 
 use crate::role::{AdminRole, PermissionLevel};
 
@@ -15,6 +21,16 @@ pub enum BanStatus {
     Permanent,
 }
 
+/// How NSFW posts are displayed to a user: hidden, blurred until clicked or shown
+#[derive(Clone, Copy, Debug, Default, Display, EnumIter, EnumString, Eq, Hash, IntoStaticStr, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+pub enum NsfwMode {
+    #[default]
+    Hide,
+    Blur,
+    Show,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct User {
     pub user_id: i64,
@@ -24,7 +40,12 @@ pub struct User {
     pub is_nsfw: bool,
     pub admin_role: AdminRole,
     pub days_hide_spoiler: Option<i32>,
-    pub show_nsfw: bool,
+    pub nsfw_mode: NsfwMode,
+    pub public_profile_visible: bool,
+    pub default_post_sort: PostSortType,
+    pub avatar_url: Option<String>,
+    pub bio: Option<String>,
+    pub markdown_bio: Option<String>,
     pub permission_by_sphere_name_map: HashMap<String, PermissionLevel>,
     pub permission_by_sphere_id_map: HashMap<i64, PermissionLevel>,
     pub ban_status: BanStatus,
@@ -39,10 +60,49 @@ pub struct UserHeader {
     pub is_nsfw: bool,
 }
 
+/// Sum of the scores of a user's posts and comments, moderated and deleted content excluded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserKarma {
+    pub post_karma: i64,
+    pub comment_karma: i64,
+}
+
+/// Karma of a user taken into account by a sphere's minimum karma requirement
+#[repr(i16)]
+#[derive(Clone, Copy, Debug, Default, Display, EnumIter, EnumString, Eq, Hash, IntoStaticStr, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::Type))]
+pub enum KarmaType {
+    #[default]
+    Total = 0,
+    Post = 1,
+    Comment = 2,
+}
+
+impl UserKarma {
+    /// Returns the karma of the given `karma_type`
+    pub fn get(&self, karma_type: KarmaType) -> i64 {
+        match karma_type {
+            KarmaType::Total => self.post_karma + self.comment_karma,
+            KarmaType::Post => self.post_karma,
+            KarmaType::Comment => self.comment_karma,
+        }
+    }
+}
+
+/// Public information displayed at the top of a user's profile
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UserProfileHeader {
+    pub username: String,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub karma: UserKarma,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct UserPostFilters {
     pub days_hide_spoiler: Option<i32>,
-    pub show_nsfw: bool,
+    pub nsfw_mode: NsfwMode,
 }
 
 #[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
@@ -53,15 +113,55 @@ pub struct UserBan {
     pub username: String,
     pub sphere_id: Option<i64>,
     pub sphere_name: Option<String>,
-    pub post_id: i64,
+    pub post_id: Option<i64>,
     pub comment_id: Option<i64>,
-    pub infringed_rule_id: i64,
+    pub infringed_rule_id: Option<i64>,
+    pub reason: Option<String>,
     pub moderator_id: i64,
     pub until_timestamp: Option<chrono::DateTime<chrono::Utc>>,
     pub create_timestamp: chrono::DateTime<chrono::Utc>,
     pub delete_timestamp: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Change of a user's username, kept to relate past content and mentions to the user's current name.
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+#[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct UsernameChange {
+    pub change_id: i64,
+    pub user_id: i64,
+    pub old_username: String,
+    pub new_username: String,
+    pub create_timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl NsfwMode {
+    /// Returns whether NSFW posts are included in feeds, either blurred or shown
+    pub fn includes_nsfw(self) -> bool {
+        self != NsfwMode::Hide
+    }
+
+    /// Returns whether a post with the given `is_nsfw` flag is blurred until clicked
+    pub fn is_blurred(self, is_nsfw: bool) -> bool {
+        is_nsfw && self == NsfwMode::Blur
+    }
+}
+
+impl From<String> for NsfwMode {
+    fn from(value: String) -> NsfwMode {
+        NsfwMode::from_str(&value).unwrap_or_default()
+    }
+}
+
+impl ToLocalizedStr for NsfwMode {
+    fn to_localized_str(&self) -> Signal<String> {
+        match self {
+            NsfwMode::Hide => move_tr!("nsfw-mode-hide"),
+            NsfwMode::Blur => move_tr!("nsfw-mode-blur"),
+            NsfwMode::Show => move_tr!("nsfw-mode-show"),
+        }
+    }
+}
+
 impl BanStatus {
     pub fn is_permanent(&self) -> bool {
         *self == BanStatus::Permanent
@@ -83,8 +183,13 @@ impl Default for User {
             email: String::default(),
             is_nsfw: false,
             admin_role: AdminRole::None,
-            show_nsfw: true,
+            nsfw_mode: NsfwMode::Show,
             days_hide_spoiler: None,
+            public_profile_visible: true,
+            default_post_sort: PostSortType::default(),
+            avatar_url: None,
+            bio: None,
+            markdown_bio: None,
             permission_by_sphere_name_map: HashMap::new(),
             permission_by_sphere_id_map: HashMap::new(),
             ban_status: BanStatus::None,
@@ -156,19 +261,29 @@ impl User {
         }
     }
 
+    /// Returns the time elapsed since the user's account was created
+    pub fn account_age(&self) -> chrono::TimeDelta {
+        chrono::Utc::now().signed_duration_since(self.timestamp)
+    }
+
     pub fn get_posts_filter(&self) -> UserPostFilters {
         UserPostFilters {
             days_hide_spoiler: self.days_hide_spoiler,
-            show_nsfw: self.show_nsfw,
+            nsfw_mode: self.nsfw_mode,
         }
     }
 }
 
+/// Returns the default post sort of `user`, anonymous users get the default `PostSortType`
+pub fn get_default_post_sort(user: Option<&User>) -> PostSortType {
+    user.map(|user| user.default_post_sort).unwrap_or_default()
+}
+
 impl Default for UserPostFilters {
     fn default() -> Self {
         UserPostFilters {
             days_hide_spoiler: None,
-            show_nsfw: true,
+            nsfw_mode: NsfwMode::Show,
         }
     }
 }
@@ -185,8 +300,9 @@ pub mod ssr {
     use sqlx::PgPool;
     use tokio::sync::Mutex;
 
-    use sphare_core_common::checks::check_username;
-    use sphare_core_common::constants::USER_FETCH_LIMIT;
+    use sphare_core_common::checks::{check_reserved_name, check_string_length, check_username};
+    use sphare_core_common::constants::{MAX_BIO_LENGTH, USERNAME_CHANGE_COOLDOWN_DAYS, USER_FETCH_LIMIT};
+    use sphare_core_common::editor::ssr::get_html_and_markdown_strings;
     use sphare_core_common::errors::AppError;
 
     use crate::role::ssr::get_user_sphere_role;
@@ -202,8 +318,13 @@ pub mod ssr {
         pub email: String,
         pub is_nsfw: bool,
         pub admin_role: AdminRole,
-        pub show_nsfw: bool,
+        pub nsfw_mode: NsfwMode,
         pub days_hide_spoiler: Option<i32>,
+        pub public_profile_visible: bool,
+        pub default_post_sort: PostSortType,
+        pub avatar_url: Option<String>,
+        pub bio: Option<String>,
+        pub markdown_bio: Option<String>,
         pub timestamp: chrono::DateTime<chrono::Utc>,
         pub delete_timestamp: Option<chrono::DateTime<chrono::Utc>>,
     }
@@ -280,8 +401,13 @@ pub mod ssr {
                 email: self.email,
                 is_nsfw: self.is_nsfw,
                 admin_role: self.admin_role,
-                show_nsfw: self.show_nsfw,
+                nsfw_mode: self.nsfw_mode,
                 days_hide_spoiler: self.days_hide_spoiler,
+                public_profile_visible: self.public_profile_visible,
+                default_post_sort: self.default_post_sort,
+                avatar_url: self.avatar_url,
+                bio: self.bio,
+                markdown_bio: self.markdown_bio,
                 permission_by_sphere_name_map,
                 permission_by_sphere_id_map,
                 ban_status: global_ban_status,
@@ -352,16 +478,15 @@ pub mod ssr {
             sphere_name: &str,
             db_pool: &PgPool,
         ) -> Result<(), AppError> {
-            let user_role = get_user_sphere_role(user_id, sphere_name, db_pool).await;
-            match (self.admin_role, self.permission_by_sphere_name_map.get(sphere_name), user_role) {
-                (AdminRole::Admin, _, Ok(user_role)) |
-                (_, Some(PermissionLevel::Lead), Ok(user_role)) if user_role.permission_level < PermissionLevel::Lead => Ok(()),
-                (AdminRole::Admin, _, Err(AppError::NotFound)) |
-                (_, Some(PermissionLevel::Lead), Err(AppError::NotFound)) => Ok(()),
-                (_, Some(PermissionLevel::Manage), Ok(user_role))
-                if permission_level < PermissionLevel::Manage && user_role.permission_level < PermissionLevel::Manage  => Ok(()),
-                (_, Some(own_level), Err(AppError::NotFound)) if *own_level >= PermissionLevel::Manage && *own_level > permission_level => Ok(()),
-                _ => Err(AppError::InsufficientPrivileges),
+            let grantor_level = self.get_sphere_permission_level(sphere_name);
+            let target_level = match get_user_sphere_role(user_id, sphere_name, db_pool).await {
+                Ok(user_role) => user_role.permission_level,
+                Err(AppError::NotFound) => PermissionLevel::None,
+                Err(e) => return Err(e),
+            };
+            match grantor_level.can_set_user_role(target_level, permission_level) {
+                true => Ok(()),
+                false => Err(AppError::InsufficientPrivileges),
             }
         }
     }
@@ -396,12 +521,28 @@ pub mod ssr {
         db_pool: &PgPool,
     ) -> Result<SqlUser, AppError> {
         log::debug!("Create or update user {username} with oidc id = {oidc_id}");
+        if let Err(reserved_name_error) = check_reserved_name(username) {
+            // new users cannot be created with a reserved name, existing users keep their current username
+            let sql_user = sqlx::query_as!(
+                SqlUser,
+                "UPDATE users SET email = $2 WHERE oidc_id = $1 RETURNING *",
+                oidc_id,
+                email,
+            )
+                .fetch_optional(db_pool)
+                .await?;
+            return sql_user.ok_or(reserved_name_error);
+        }
         let sql_user = sqlx::query_as!(
             SqlUser,
             "INSERT INTO users (oidc_id, username, email)
             VALUES ($1, $2, $3)
             ON CONFLICT (oidc_id) DO UPDATE
-                SET username = EXCLUDED.username,
+                SET username = CASE
+                        -- usernames changed in the app take precedence over the one of the identity provider
+                        WHEN EXISTS (SELECT 1 FROM username_history h WHERE h.user_id = users.user_id) THEN users.username
+                        ELSE EXCLUDED.username
+                    END,
                     email = EXCLUDED.email
             RETURNING *",
             oidc_id,
@@ -477,9 +618,138 @@ pub mod ssr {
         Ok(user_header_vec)
     }
 
+    /// Returns the usernames starting with `username_prefix`, ignoring case, to suggest when mentioning a user.
+    /// Deleted and globally banned users are excluded.
+    pub async fn get_matching_username_vec(
+        username_prefix: &str,
+        limit: i64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<String>, AppError> {
+        check_username(username_prefix, false)?;
+        let username_vec = sqlx::query_scalar!(
+            "SELECT u.username
+            FROM users u
+            WHERE
+                starts_with(LOWER(u.username), LOWER($1)) AND
+                u.delete_timestamp IS NULL AND
+                NOT EXISTS (
+                    SELECT * FROM user_bans b
+                    WHERE
+                        b.user_id = u.user_id AND
+                        b.sphere_id IS NULL AND
+                        b.delete_timestamp IS NULL AND
+                        (b.until_timestamp > NOW() OR b.until_timestamp IS NULL)
+                )
+            ORDER BY LENGTH(u.username), u.username
+            LIMIT $2",
+            username_prefix,
+            min(limit, USER_FETCH_LIMIT),
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(username_vec)
+    }
+
+    /// Changes the username of `user` to `new_username` and records the change in the username history.
+    /// Usernames must be unique regardless of case and can only be changed once every `USERNAME_CHANGE_COOLDOWN_DAYS` days.
+    pub async fn change_username(
+        new_username: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<UsernameChange, AppError> {
+        check_username(new_username, false)?;
+        check_reserved_name(new_username)?;
+        if new_username == user.username {
+            return Err(AppError::new("New username must be different from the current one."));
+        }
+
+        let mut tx = db_pool.begin().await?;
+        // Lock the user so that concurrent changes of the same user are serialized and respect the cooldown
+        sqlx::query!(
+            "SELECT user_id FROM users WHERE user_id = $1 FOR UPDATE",
+            user.user_id,
+        )
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let last_change_timestamp = sqlx::query_scalar!(
+            "SELECT MAX(create_timestamp) FROM username_history WHERE user_id = $1",
+            user.user_id,
+        )
+            .fetch_one(&mut *tx)
+            .await?;
+        if let Some(last_change_timestamp) = last_change_timestamp {
+            let next_change_timestamp = last_change_timestamp + chrono::Duration::days(USERNAME_CHANGE_COOLDOWN_DAYS);
+            if next_change_timestamp > chrono::Utc::now() {
+                return Err(AppError::RateLimited(Some(next_change_timestamp)));
+            }
+        }
+
+        let is_username_taken = sqlx::query_scalar!(
+            "SELECT EXISTS (
+                SELECT 1 FROM users
+                WHERE LOWER(username) = LOWER($1) AND user_id != $2 AND delete_timestamp IS NULL
+            )",
+            new_username,
+            user.user_id,
+        )
+            .fetch_one(&mut *tx)
+            .await?
+            .unwrap_or(true);
+        if is_username_taken {
+            return Err(AppError::UsernameTaken);
+        }
+
+        let username_change = sqlx::query_as!(
+            UsernameChange,
+            "INSERT INTO username_history (user_id, old_username, new_username)
+            SELECT user_id, username, $1 FROM users WHERE user_id = $2
+            RETURNING *",
+            new_username,
+            user.user_id,
+        )
+            .fetch_one(&mut *tx)
+            .await?;
+
+        // The unique index on the lowercase username rejects usernames taken by a concurrent change
+        sqlx::query!(
+            "UPDATE users SET username = $1 WHERE user_id = $2",
+            new_username,
+            user.user_id,
+        )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| match AppError::from(e) {
+                AppError::Conflict => AppError::UsernameTaken,
+                e => e,
+            })?;
+        tx.commit().await?;
+
+        Ok(username_change)
+    }
+
+    /// Returns the username changes of the user with `user_id`, latest first.
+    pub async fn get_username_history(
+        user_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<Vec<UsernameChange>, AppError> {
+        let username_change_vec = sqlx::query_as!(
+            UsernameChange,
+            "SELECT * FROM username_history
+            WHERE user_id = $1
+            ORDER BY create_timestamp DESC, change_id DESC",
+            user_id,
+        )
+            .fetch_all(db_pool)
+            .await?;
+
+        Ok(username_change_vec)
+    }
+
     pub async fn set_user_settings(
         is_nsfw: bool,
-        show_nsfw: bool,
+        nsfw_mode: NsfwMode,
         days_hide_spoilers: u32,
         user: &User,
         db_pool: &PgPool,
@@ -491,11 +761,11 @@ pub mod ssr {
         sqlx::query!(
             "UPDATE users SET
                 is_nsfw = $1,
-                show_nsfw = $2,
+                nsfw_mode = $2,
                 days_hide_spoiler = $3
             WHERE user_id = $4",
             is_nsfw,
-            show_nsfw,
+            nsfw_mode.to_string(),
             days_hide_spoilers,
             user.user_id,
         )
@@ -504,6 +774,114 @@ pub mod ssr {
         Ok(())
     }
 
+    pub async fn set_public_profile_visible(
+        is_visible: bool,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET public_profile_visible = $1
+            WHERE user_id = $2",
+            is_visible,
+            user.user_id,
+        )
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_default_post_sort(
+        post_sort_type: PostSortType,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            "UPDATE users SET default_post_sort = $1
+            WHERE user_id = $2",
+            post_sort_type.to_string(),
+            user.user_id,
+        )
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Sets the markdown `bio` of `user`, rendered to sanitized html like post bodies. An empty `bio` clears it.
+    /// Returns the rendered html of the bio.
+    pub async fn set_user_bio(
+        bio: &str,
+        user: &User,
+        db_pool: &PgPool,
+    ) -> Result<Option<String>, AppError> {
+        let bio = bio.trim();
+        check_string_length(bio, "Bio", MAX_BIO_LENGTH, true)?;
+        let (html_bio, markdown_bio) = match bio.is_empty() {
+            true => (None, None),
+            false => {
+                let (html_bio, markdown_bio) = get_html_and_markdown_strings(bio, true)?;
+                (Some(html_bio), markdown_bio)
+            },
+        };
+
+        sqlx::query!(
+            "UPDATE users SET bio = $1, markdown_bio = $2
+            WHERE user_id = $3",
+            html_bio,
+            markdown_bio,
+            user.user_id,
+        )
+            .execute(db_pool)
+            .await?;
+        Ok(html_bio)
+    }
+
+    /// Returns the profile header of the user `username`, with its html bio, avatar and creation timestamp.
+    pub async fn get_user_profile_header(
+        username: &str,
+        db_pool: &PgPool,
+    ) -> Result<UserProfileHeader, AppError> {
+        check_username(username, false)?;
+        let profile_user = sqlx::query!(
+            "SELECT user_id, username, bio, avatar_url, timestamp
+            FROM users
+            WHERE username = $1 AND delete_timestamp IS NULL",
+            username,
+        )
+            .fetch_one(db_pool)
+            .await?;
+        let karma = get_user_karma(profile_user.user_id, db_pool).await?;
+        Ok(UserProfileHeader {
+            username: profile_user.username,
+            bio: profile_user.bio,
+            avatar_url: profile_user.avatar_url,
+            karma,
+            timestamp: profile_user.timestamp,
+        })
+    }
+
+    /// Returns the karma of the user `user_id`, i.e. the summed score of their posts and comments that are neither moderated nor deleted.
+    pub async fn get_user_karma(
+        user_id: i64,
+        db_pool: &PgPool,
+    ) -> Result<UserKarma, AppError> {
+        let karma = sqlx::query_as!(
+            UserKarma,
+            r#"SELECT
+                (
+                    SELECT COALESCE(SUM(p.score), 0) FROM posts p
+                    WHERE p.creator_id = $1 AND p.moderator_id IS NULL AND p.delete_timestamp IS NULL
+                )::BIGINT as "post_karma!",
+                (
+                    SELECT COALESCE(SUM(c.score), 0) FROM comments c
+                    WHERE c.creator_id = $1 AND c.moderator_id IS NULL AND c.delete_timestamp IS NULL
+                )::BIGINT as "comment_karma!""#,
+            user_id,
+        )
+            .fetch_one(db_pool)
+            .await?;
+        Ok(karma)
+    }
+
     pub async fn delete_user(
         user: &User,
         db_pool: &PgPool,
@@ -521,7 +899,10 @@ pub mod ssr {
                  is_nsfw = false,
                  admin_role = 'None',
                  days_hide_spoiler = NULL,
-                 show_nsfw = false,
+                 nsfw_mode = 'Hide',
+                 avatar_url = NULL,
+                 bio = NULL,
+                 markdown_bio = NULL,
                  timestamp = NOW(),
                  delete_timestamp = NOW()
             WHERE user_id = $1",
@@ -546,6 +927,8 @@ pub mod ssr {
                 link_url = NULL,
                 link_embed = NULL,
                 link_thumbnail_url = NULL,
+                link_alt_text = NULL,
+                link_key = NULL,
                 is_nsfw = false,
                 is_spoiler = false,
                 is_pinned = false,
@@ -632,8 +1015,13 @@ pub mod ssr {
                 email: String::from("c"),
                 is_nsfw: false,
                 admin_role: AdminRole::None,
-                show_nsfw: true,
+                nsfw_mode: NsfwMode::Show,
                 days_hide_spoiler: None,
+                public_profile_visible: false,
+                default_post_sort: PostSortType::Recent,
+                avatar_url: None,
+                bio: None,
+                markdown_bio: None,
                 timestamp: chrono::DateTime::from_timestamp_nanos(0),
                 delete_timestamp: None,
             };
@@ -668,9 +1056,10 @@ pub mod ssr {
                     username: String::from("b"),
                     sphere_id: None,
                     sphere_name: None,
-                    post_id: 0,
+                    post_id: Some(0),
                     comment_id: None,
-                    infringed_rule_id: 0,
+                    infringed_rule_id: Some(0),
+                    reason: None,
                     moderator_id: 0,
                     until_timestamp: Some(past_timestamp),
                     create_timestamp: Default::default(),
@@ -682,9 +1071,10 @@ pub mod ssr {
                     username: String::from("b"),
                     sphere_id: Some(0),
                     sphere_name: Some(String::from("a")),
-                    post_id: 0,
+                    post_id: Some(0),
                     comment_id: None,
-                    infringed_rule_id: 0,
+                    infringed_rule_id: Some(0),
+                    reason: None,
                     moderator_id: 0,
                     until_timestamp: Some(past_timestamp),
                     create_timestamp: Default::default(),
@@ -696,9 +1086,10 @@ pub mod ssr {
                     username: String::from("b"),
                     sphere_id: Some(1),
                     sphere_name: Some(String::from("b")),
-                    post_id: 0,
+                    post_id: Some(0),
                     comment_id: None,
-                    infringed_rule_id: 0,
+                    infringed_rule_id: Some(0),
+                    reason: None,
                     moderator_id: 0,
                     until_timestamp: Some(future_timestamp),
                     create_timestamp: Default::default(),
@@ -710,9 +1101,10 @@ pub mod ssr {
                     username: String::from("b"),
                     sphere_id: Some(2),
                     sphere_name: Some(String::from("c")),
-                    post_id: 0,
+                    post_id: Some(0),
                     comment_id: None,
-                    infringed_rule_id: 0,
+                    infringed_rule_id: Some(0),
+                    reason: None,
                     moderator_id: 0,
                     until_timestamp: None,
                     create_timestamp: Default::default(),
@@ -725,6 +1117,8 @@ pub mod ssr {
             assert_eq!(user_1.username, "b");
             assert_eq!(user_1.email, "c");
             assert_eq!(user_1.admin_role, AdminRole::None);
+            assert!(!user_1.public_profile_visible);
+            assert_eq!(user_1.default_post_sort, PostSortType::Recent);
             assert_eq!(user_1.timestamp, chrono::DateTime::from_timestamp_nanos(0));
             assert_eq!(user_1.delete_timestamp, None);
             assert_eq!(user_1.permission_by_sphere_name_map[&String::from("0")], PermissionLevel::Moderate);
@@ -746,9 +1140,10 @@ pub mod ssr {
                 username: String::from("b"),
                 sphere_id: None,
                 sphere_name: None,
-                post_id: 0,
+                post_id: Some(0),
                 comment_id: None,
-                infringed_rule_id: 0,
+                infringed_rule_id: Some(0),
+                reason: None,
                 moderator_id: 0,
                 until_timestamp: Some(future_timestamp),
                 create_timestamp: Default::default(),
@@ -1123,24 +1518,29 @@ mod tests {
     fn test_user_get_posts_filter() {
         let mut user = User::default();
         let user_post_filters = user.get_posts_filter();
-        assert_eq!(user_post_filters.show_nsfw, true);
+        assert_eq!(user_post_filters.nsfw_mode, NsfwMode::Show);
         assert_eq!(user_post_filters.days_hide_spoiler, None);
 
         let days_hide_spoiler = Some(14);
         user.days_hide_spoiler = days_hide_spoiler;
         let user_post_filters = user.get_posts_filter();
-        assert_eq!(user_post_filters.show_nsfw, true);
+        assert_eq!(user_post_filters.nsfw_mode, NsfwMode::Show);
         assert_eq!(user_post_filters.days_hide_spoiler, days_hide_spoiler);
         
-        user.show_nsfw = false;
+        user.nsfw_mode = NsfwMode::Hide;
+        let user_post_filters = user.get_posts_filter();
+        assert_eq!(user_post_filters.nsfw_mode, NsfwMode::Hide);
+        assert_eq!(user_post_filters.days_hide_spoiler, days_hide_spoiler);
+
+        user.nsfw_mode = NsfwMode::Blur;
         let user_post_filters = user.get_posts_filter();
-        assert_eq!(user_post_filters.show_nsfw, false);
+        assert_eq!(user_post_filters.nsfw_mode, NsfwMode::Blur);
         assert_eq!(user_post_filters.days_hide_spoiler, days_hide_spoiler);
 
         user.days_hide_spoiler = None;
-        user.show_nsfw = true;
+        user.nsfw_mode = NsfwMode::Show;
         let user_post_filters = user.get_posts_filter();
-        assert_eq!(user_post_filters.show_nsfw, true);
+        assert_eq!(user_post_filters.nsfw_mode, NsfwMode::Show);
         assert_eq!(user_post_filters.days_hide_spoiler, None);
     }
     
@@ -1148,6 +1548,64 @@ mod tests {
     fn test_user_post_filters_default() {
         let default_post_filters = UserPostFilters::default();
         assert_eq!(default_post_filters.days_hide_spoiler, None);
-        assert_eq!(default_post_filters.show_nsfw, true);
+        assert_eq!(default_post_filters.nsfw_mode, NsfwMode::Show);
+    }
+
+    #[test]
+    fn test_nsfw_mode() {
+        assert!(!NsfwMode::Hide.includes_nsfw());
+        assert!(NsfwMode::Blur.includes_nsfw());
+        assert!(NsfwMode::Show.includes_nsfw());
+
+        assert!(!NsfwMode::Hide.is_blurred(true));
+        assert!(NsfwMode::Blur.is_blurred(true));
+        assert!(!NsfwMode::Blur.is_blurred(false));
+        assert!(!NsfwMode::Show.is_blurred(true));
+
+        assert_eq!(NsfwMode::from(String::from("Blur")), NsfwMode::Blur);
+        assert_eq!(NsfwMode::from(String::from("invalid")), NsfwMode::Hide);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_default_post_sort() {
+        assert_eq!(get_default_post_sort(None), PostSortType::Hot);
+
+        let mut user = User::default();
+        assert_eq!(get_default_post_sort(Some(&user)), PostSortType::Hot);
+
+        user.default_post_sort = PostSortType::Best;
+        assert_eq!(get_default_post_sort(Some(&user)), PostSortType::Best);
+    }
+
+    #[test]
+    fn test_user_account_age() {
+        let mut user = User::default();
+        for age in [
+            chrono::TimeDelta::zero(),
+            chrono::TimeDelta::hours(5),
+            chrono::TimeDelta::days(3),
+            chrono::TimeDelta::days(45),
+            chrono::TimeDelta::days(800),
+        ] {
+            user.timestamp = chrono::Utc::now() - age;
+            let account_age = user.account_age();
+            assert!(account_age >= age);
+            assert!(account_age - age < chrono::TimeDelta::minutes(1));
+        }
+
+        user.timestamp = chrono::DateTime::from_timestamp_nanos(0);
+        assert!(user.account_age().num_days() > 365 * 50);
+    }
+
+    #[test]
+    fn test_user_karma_get() {
+        let karma = UserKarma {
+            post_karma: 7,
+            comment_karma: -3,
+        };
+        assert_eq!(karma.get(KarmaType::Total), 4);
+        assert_eq!(karma.get(KarmaType::Post), 7);
+        assert_eq!(karma.get(KarmaType::Comment), -3);
+        assert_eq!(UserKarma::default().get(KarmaType::Total), 0);
+    }
+}
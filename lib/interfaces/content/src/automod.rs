@@ -0,0 +1,41 @@
+use leptos::prelude::*;
+use sphare_core_common::errors::AppError;
+
+#[cfg(feature = "ssr")]
+use {
+    sphare_core_common::db_utils::ssr::get_db_pool,
+    sphare_core_content::automod::ssr,
+    sphare_core_user::auth::ssr::check_user,
+};
+
+use sphare_core_content::automod::{AutomodAction, AutomodRule};
+
+#[server]
+pub async fn get_automod_rule_vec(
+    sphere_name: String,
+) -> Result<Vec<AutomodRule>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::get_automod_rule_vec(&sphere_name, &user, &db_pool).await
+}
+
+#[server]
+pub async fn create_automod_rule(
+    sphere_name: String,
+    pattern: String,
+    action: AutomodAction,
+    rule_id: Option<i64>,
+) -> Result<AutomodRule, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::create_automod_rule(&sphere_name, &pattern, action, rule_id, &user, &db_pool).await
+}
+
+#[server]
+pub async fn delete_automod_rule(
+    automod_rule_id: i64,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::delete_automod_rule(automod_rule_id, &user, &db_pool).await
+}
@@ -2,14 +2,14 @@ use leptos::prelude::*;
 
 #[cfg(feature = "ssr")]
 use {
-    sphare_core_common::constants::COMMENT_BATCH_SIZE,
+    sphare_core_common::constants::{CHILD_COMMENT_BATCH_SIZE, COMMENT_BATCH_SIZE},
     sphare_core_common::db_utils::ssr::get_db_pool,
     sphare_core_content::comment::*,
     sphare_core_user::auth::ssr::{check_user, get_user},
 };
 
 use sphare_core_common::errors::AppError;
-use sphare_core_content::comment::{Comment, CommentWithChildren};
+use sphare_core_content::comment::{Comment, CommentWithAncestors, CommentWithChildren, CommentWithContext};
 use sphare_core_content::ranking::SortType;
 
 #[server]
@@ -36,17 +36,17 @@ pub async fn get_post_comment_tree(
 }
 
 #[server]
-pub async fn get_comment_tree_by_id(
+pub async fn get_comment_with_ancestors(
     comment_id: i64,
     sort_type: SortType,
     max_depth: Option<usize>,
-) -> Result<CommentWithChildren, AppError> {
+) -> Result<CommentWithAncestors, AppError> {
     let user_id = match get_user().await {
         Ok(Some(user)) => Some(user.user_id),
         _ => None,
     };
     let db_pool = get_db_pool()?;
-    ssr::get_comment_tree_by_id(
+    ssr::get_comment_with_ancestors(
         comment_id,
         sort_type,
         max_depth,
@@ -55,6 +55,57 @@ pub async fn get_comment_tree_by_id(
     ).await
 }
 
+#[server]
+pub async fn get_comment_subtree(
+    comment_id: i64,
+    sort_type: SortType,
+    max_depth: Option<usize>,
+) -> Result<Vec<CommentWithChildren>, AppError> {
+    let user_id = match get_user().await {
+        Ok(Some(user)) => Some(user.user_id),
+        _ => None,
+    };
+    let db_pool = get_db_pool()?;
+    ssr::get_comment_subtree(
+        comment_id,
+        sort_type,
+        max_depth,
+        user_id,
+        &db_pool,
+    ).await
+}
+
+#[server]
+pub async fn get_child_comments(
+    parent_id: i64,
+    sort_type: SortType,
+    max_depth: Option<usize>,
+    num_already_loaded: usize,
+) -> Result<Vec<CommentWithChildren>, AppError> {
+    let user_id = match get_user().await {
+        Ok(Some(user)) => Some(user.user_id),
+        _ => None,
+    };
+    let db_pool = get_db_pool()?;
+    ssr::get_child_comments(
+        parent_id,
+        sort_type,
+        max_depth,
+        user_id,
+        CHILD_COMMENT_BATCH_SIZE,
+        num_already_loaded as i64,
+        &db_pool,
+    ).await
+}
+
+#[server]
+pub async fn get_parent_comment(
+    comment_id: i64,
+) -> Result<Option<CommentWithContext>, AppError> {
+    let db_pool = get_db_pool()?;
+    ssr::get_parent_comment(comment_id, &db_pool).await
+}
+
 #[server]
 pub async fn create_comment(
     post_id: i64,
@@ -97,4 +148,19 @@ pub async fn delete_comment(
     ).await?;
 
     Ok(())
+}
+
+#[server]
+pub async fn restore_comment(
+    comment_id: i64,
+) -> Result<Comment, AppError> {
+    log::trace!("Restore comment {comment_id}");
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::restore_comment(
+        comment_id,
+        &user,
+        &db_pool,
+    ).await
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
+pub mod automod;
 pub mod comment;
 pub mod moderation;
+pub mod poll;
 pub mod post;
 pub mod profile;
 pub mod ranking;
@@ -3,14 +3,17 @@ use sphare_core_common::errors::AppError;
 
 #[cfg(feature = "ssr")]
 use {
+    std::cmp::min,
+    sphare_core_common::constants::MAX_BAN_DURATION_HOURS,
     sphare_core_common::db_utils::ssr::get_db_pool,
     sphare_core_content::moderation::*,
     sphare_core_user::auth::ssr::{check_user, reload_user},
 };
 
 use sphare_core_content::comment::Comment;
-use sphare_core_content::moderation::ModerationInfo;
+use sphare_core_content::moderation::{Content, ContentReport, ModReasonTemplate, ModerationInfo, UserMute};
 use sphare_core_content::post::Post;
+use sphare_core_user::user::UserBan;
 
 #[server]
 pub async fn get_moderation_info(
@@ -21,6 +24,227 @@ pub async fn get_moderation_info(
     ssr::get_moderation_info(post_id, comment_id, &db_pool).await
 }
 
+#[server]
+pub async fn get_removed_post(
+    post_id: i64,
+) -> Result<Post, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::get_removed_post(post_id, &user, &db_pool).await
+}
+
+#[server]
+pub async fn get_removed_comment(
+    comment_id: i64,
+) -> Result<Comment, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::get_removed_comment(comment_id, &user, &db_pool).await
+}
+
+#[server]
+pub async fn get_pending_post_vec(
+    sphere_name: String,
+) -> Result<Vec<Post>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::get_pending_post_vec(&sphere_name, &user, &db_pool).await
+}
+
+#[server]
+pub async fn approve_post(
+    post_id: i64,
+) -> Result<Post, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::approve_post(post_id, &user, &db_pool).await
+}
+
+#[server]
+pub async fn move_post_to_satellite(
+    post_id: i64,
+    satellite_id: Option<i64>,
+) -> Result<Post, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::move_post_to_satellite(post_id, satellite_id, &user, &db_pool).await
+}
+
+#[server]
+pub async fn report_post(
+    post_id: i64,
+    rule_id: Option<i64>,
+    reason: String,
+) -> Result<ContentReport, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::report_post(post_id, rule_id, &reason, &user, &db_pool).await
+}
+
+#[server]
+pub async fn report_comment(
+    comment_id: i64,
+    rule_id: Option<i64>,
+    reason: String,
+) -> Result<ContentReport, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::report_comment(comment_id, rule_id, &reason, &user, &db_pool).await
+}
+
+#[server]
+pub async fn get_reports_for_sphere(
+    sphere_name: String,
+) -> Result<Vec<ContentReport>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::get_reports_for_sphere(&sphere_name, &user, &db_pool).await
+}
+
+/// Function to get the reports of all spheres, only available to admins
+#[server]
+pub async fn get_global_reports(
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<ContentReport>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::get_global_reports(limit, offset, &user, &db_pool).await
+}
+
+/// Function to moderate the content of a report for a site-wide rule or a rule of its sphere, only available to admins
+#[server]
+pub async fn moderate_reported_content(
+    report_id: i64,
+    rule_id: i64,
+    moderator_message: String,
+) -> Result<Content, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::moderate_reported_content(report_id, rule_id, &moderator_message, &user, &db_pool).await
+}
+
+/// Function to get the most downvoted content of a sphere created within the last `since_hours`, clamped to `MAX_CONTROVERSIAL_SINCE_HOURS`
+#[server]
+pub async fn get_controversial_content(
+    sphere_name: String,
+    since_hours: u64,
+    limit: i64,
+) -> Result<Vec<Content>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    let since = std::time::Duration::from_hours(std::cmp::min(since_hours, MAX_CONTROVERSIAL_SINCE_HOURS));
+    ssr::get_controversial_content(&sphere_name, since, limit, &user, &db_pool).await
+}
+
+/// Function to revert the moderation of a post
+#[server]
+pub async fn unmoderate_post(
+    post_id: i64,
+) -> Result<Post, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::unmoderate_post(post_id, &user, &db_pool).await
+}
+
+/// Function to revert the moderation of a comment
+#[server]
+pub async fn unmoderate_comment(
+    comment_id: i64,
+) -> Result<Comment, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::unmoderate_comment(comment_id, &user, &db_pool).await
+}
+
+#[server]
+pub async fn get_mod_reason_template_vec(
+    sphere_name: String,
+) -> Result<Vec<ModReasonTemplate>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::get_mod_reason_template_vec(&sphere_name, &user, &db_pool).await
+}
+
+#[server]
+pub async fn create_mod_reason_template(
+    sphere_name: String,
+    title: String,
+    reason: String,
+) -> Result<ModReasonTemplate, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::create_mod_reason_template(&sphere_name, &title, &reason, &user, &db_pool).await
+}
+
+#[server]
+pub async fn update_mod_reason_template(
+    template_id: i64,
+    title: String,
+    reason: String,
+) -> Result<ModReasonTemplate, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::update_mod_reason_template(template_id, &title, &reason, &user, &db_pool).await
+}
+
+#[server]
+pub async fn delete_mod_reason_template(
+    template_id: i64,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::delete_mod_reason_template(template_id, &user, &db_pool).await
+}
+
+/// Function to ban a user from a sphere for `ban_duration_hours`, without moderating any content
+#[server]
+pub async fn ban_user_temporarily(
+    user_id: i64,
+    sphere_name: String,
+    ban_duration_hours: i64,
+    reason: String,
+) -> Result<UserBan, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    let duration = chrono::TimeDelta::try_hours(min(ban_duration_hours, MAX_BAN_DURATION_HOURS as i64))
+        .ok_or(AppError::new("Invalid ban duration."))?;
+
+    let user_ban = ssr::ban_user_temporarily(
+        user_id,
+        &sphere_name,
+        duration,
+        &reason,
+        &user,
+        &db_pool,
+    ).await?;
+
+    reload_user(user_id)?;
+
+    Ok(user_ban)
+}
+
+#[server]
+pub async fn mute_user(
+    user_id: i64,
+    sphere_name: String,
+) -> Result<UserMute, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::mute_user(user_id, &sphere_name, &user, &db_pool).await
+}
+
+#[server]
+pub async fn unmute_user(
+    user_id: i64,
+    sphere_name: String,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::unmute_user(user_id, &sphere_name, &user, &db_pool).await
+}
+
 /// Function to moderate a post and optionally ban its author
 ///
 /// The ban is performed for the sphere of the given post and the duration is given by `ban_num_days`.
@@ -29,19 +253,43 @@ pub async fn get_moderation_info(
 pub async fn moderate_post(
     post_id: i64,
     rule_id: i64,
-    moderator_message: String,
+    moderator_message: Option<String>,
     ban_duration_days: Option<usize>,
+    template_id: Option<i64>,
 ) -> Result<Post, AppError> {
     let user = check_user().await?;
     let db_pool = get_db_pool()?;
 
-    let (post, _, _) = ssr::moderate_post_and_ban_user(post_id, rule_id, &moderator_message, ban_duration_days, &user, &db_pool).await?;
+    // the message of the template replaces the moderator message
+    let (post, _, _) = match template_id {
+        Some(template_id) => ssr::moderate_post_with_template(post_id, rule_id, template_id, ban_duration_days, &user, &db_pool).await?,
+        None => ssr::moderate_post_and_ban_user(
+            post_id,
+            rule_id,
+            moderator_message.as_deref().unwrap_or_default(),
+            ban_duration_days,
+            &user,
+            &db_pool,
+        ).await?,
+    };
 
     reload_user(post.creator_id)?;
 
     Ok(post)
 }
 
+/// Function to moderate several posts at once, either all of them are moderated or none
+#[server]
+pub async fn moderate_posts(
+    post_id_vec: Vec<i64>,
+    rule_id: i64,
+    moderator_message: String,
+) -> Result<Vec<Post>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::moderate_posts(post_id_vec, rule_id, &moderator_message, &user, &db_pool).await
+}
+
 /// Function to moderate a comment and optionally ban its author
 ///
 /// The ban is performed for the sphere of the given comment and the duration is given by `ban_num_days`.
@@ -0,0 +1,53 @@
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use {
+    std::cmp::min,
+    sphare_core_common::constants::MAX_POLL_DURATION_HOURS,
+    sphare_core_common::db_utils::ssr::get_db_pool,
+    sphare_core_common::routes::get_post_path,
+    sphare_core_content::poll::*,
+    sphare_core_user::auth::{ssr::check_user, ssr::get_user},
+};
+
+use sphare_core_common::errors::AppError;
+use sphare_core_content::poll::PollWithResults;
+
+#[server]
+pub async fn create_poll(
+    sphere_name: String,
+    title: String,
+    option_vec: Vec<String>,
+    duration_hours: u32,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    let duration = chrono::TimeDelta::hours(min(duration_hours, MAX_POLL_DURATION_HOURS) as i64);
+    let ends_at = chrono::Utc::now().checked_add_signed(duration).ok_or(AppError::new("Invalid poll duration."))?;
+    let (post, _) = ssr::create_poll(&sphere_name, &title, &option_vec, ends_at, &user, &db_pool).await?;
+
+    leptos_axum::redirect(get_post_path(&sphere_name, None, post.post_id).as_str());
+    Ok(())
+}
+
+#[server]
+pub async fn get_poll_by_post_id(
+    post_id: i64,
+) -> Result<Option<PollWithResults>, AppError> {
+    let user = get_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::get_poll_by_post_id(post_id, user.map(|user| user.user_id), &db_pool).await
+}
+
+#[server]
+pub async fn vote_poll(
+    poll_id: i64,
+    option_id: i64,
+) -> Result<PollWithResults, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::vote_poll(poll_id, option_id, &user, &db_pool).await
+}
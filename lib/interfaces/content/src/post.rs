@@ -1,22 +1,29 @@
 use leptos::prelude::*;
+use leptos::server_fn::codec::{MultipartData, MultipartFormData};
 
 #[cfg(feature = "ssr")]
 use {
-    sphare_core_common::constants::POST_BATCH_SIZE,
+    std::cmp::min,
+    sphare_core_common::constants::{MAX_POST_SCHEDULE_HOURS, POST_BATCH_SIZE, SIMILAR_POST_LIMIT},
+    sphare_core_sphere::sphere::ssr::{check_nsfw_confirmed, get_post_sphere},
+    sphare_core_sphere::sphere_management::ssr::OBJECT_CONTAINER_URL_ENV,
     sphare_core_common::db_utils::ssr::get_db_pool,
+    sphare_core_common::routes::get_post_path,
     sphare_core_content::post::*,
     sphare_core_user::auth::{ssr::check_user, ssr::get_user},
 };
 
 use sphare_core_common::errors::AppError;
 use sphare_core_content::filter::SphereCategoryFilter;
-use sphare_core_content::post::{Post, PostDataInputs, PostInheritedAttributes, PostLocation, PostWithInfo, PostWithSphereInfo};
+use sphare_core_content::post::{Post, PostDataInputs, PostDraft, PostImage, PostInheritedAttributes, PostLocation, PostWithInfo, PostWithSphereInfo};
 use sphare_core_content::ranking::SortType;
 
 #[server]
 pub async fn get_post_with_info_by_id(post_id: i64) -> Result<PostWithInfo, AppError> {
     let db_pool = get_db_pool()?;
     let user = get_user().await?;
+    let sphere = get_post_sphere(post_id, &db_pool).await?;
+    check_nsfw_confirmed(&sphere.sphere_name, user.as_ref(), &db_pool).await?;
     Ok(ssr::get_post_with_info_by_id(post_id, user.as_ref(), &db_pool).await?)
 }
 
@@ -26,6 +33,15 @@ pub async fn get_post_inherited_attributes(post_id: i64) -> Result<PostInherited
     Ok(ssr::get_post_inherited_attributes(post_id, &db_pool).await?)
 }
 
+#[server]
+pub async fn get_similar_posts(post_id: i64) -> Result<Vec<PostWithSphereInfo>, AppError> {
+    let user = get_user().await.unwrap_or(None);
+    let db_pool = get_db_pool()?;
+    let sphere = get_post_sphere(post_id, &db_pool).await?;
+    check_nsfw_confirmed(&sphere.sphere_name, user.as_ref(), &db_pool).await?;
+    ssr::get_similar_posts(post_id, SIMILAR_POST_LIMIT, user.as_ref(), &db_pool).await
+}
+
 #[server]
 pub async fn get_sorted_post_vec(
     sort_type: SortType,
@@ -50,8 +66,19 @@ pub async fn get_homepage_post_vec(
 ) -> Result<Vec<PostWithSphereInfo>, AppError> {
     let user = get_user().await?;
     let db_pool = get_db_pool()?;
+    let discovery_ratio = std::env::var(ssr::HOME_FEED_DISCOVERY_RATIO_ENV).ok().and_then(|ratio| ratio.parse::<f64>().ok());
 
-    ssr::get_homepage_post_vec(sort_type, num_already_loaded, user.as_ref(), &db_pool).await
+    match (user, discovery_ratio) {
+        (Some(user), Some(discovery_ratio)) if discovery_ratio > 0.0 => ssr::get_home_feed(
+            &user,
+            sort_type,
+            POST_BATCH_SIZE,
+            num_already_loaded as i64,
+            discovery_ratio,
+            &db_pool,
+        ).await,
+        (user, _) => ssr::get_homepage_post_vec(sort_type, num_already_loaded, user.as_ref(), &db_pool).await,
+    }
 }
 
 #[server]
@@ -97,17 +124,80 @@ pub async fn get_post_vec_by_satellite_id(
 #[server]
 pub async fn create_post(
     post_location: PostLocation,
-    post_inputs: PostDataInputs
+    post_inputs: PostDataInputs,
+    idempotency_key: Option<String>,
+    publish_delay_hours: Option<u32>,
 ) -> Result<(), AppError> {
     let user = check_user().await?;
     let db_pool = get_db_pool()?;
 
-    let (_, _, new_post_path) = ssr::create_post_and_vote(post_location, post_inputs, &user, &db_pool).await?;
+    let (_, _, new_post_path) = match publish_delay_hours.filter(|delay_hours| *delay_hours > 0) {
+        Some(delay_hours) => {
+            let delay = chrono::TimeDelta::hours(min(delay_hours, MAX_POST_SCHEDULE_HOURS) as i64);
+            let publish_at = chrono::Utc::now().checked_add_signed(delay).ok_or(AppError::new("Invalid publication delay."))?;
+            ssr::create_scheduled_post_and_vote(post_location, post_inputs, publish_at, &user, &db_pool).await?
+        },
+        None => {
+            let idempotency_key = idempotency_key.as_deref().filter(|key| !key.is_empty());
+            ssr::create_post_and_vote(post_location, post_inputs, idempotency_key, &user, &db_pool).await?
+        },
+    };
 
     leptos_axum::redirect(new_post_path.as_str());
     Ok(())
 }
 
+#[server]
+pub async fn get_duplicate_link_post_id(
+    sphere_name: String,
+    link: String,
+) -> Result<Option<i64>, AppError> {
+    let db_pool = get_db_pool()?;
+    ssr::get_duplicate_link_post_id(&sphere_name, &link, &db_pool).await
+}
+
+#[server]
+pub async fn save_draft(
+    title: String,
+    body: String,
+    link: Option<String>,
+) -> Result<PostDraft, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::save_draft(&title, &body, link.as_deref(), &user, &db_pool).await
+}
+
+#[server]
+pub async fn load_draft() -> Result<Option<PostDraft>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::load_draft(&user, &db_pool).await
+}
+
+#[server]
+pub async fn delete_draft() -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::delete_draft(&user, &db_pool).await
+}
+
+#[server]
+pub async fn crosspost(
+    post_id: i64,
+    sphere_name: String,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    let crosspost = ssr::crosspost(post_id, &sphere_name, &user, &db_pool).await?;
+
+    leptos_axum::redirect(get_post_path(&sphere_name, None, crosspost.post_id).as_str());
+    Ok(())
+}
+
 #[server]
 pub async fn edit_post(
     post_id: i64,
@@ -119,6 +209,56 @@ pub async fn edit_post(
     ssr::edit_post(post_id, post_inputs, &user, &db_pool).await
 }
 
+#[server]
+pub async fn set_pin_order(
+    post_id: i64,
+    pin_order: i32,
+) -> Result<Post, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_pin_order(post_id, pin_order, &user, &db_pool).await
+}
+
+#[server]
+pub async fn set_accepted_answer(
+    post_id: i64,
+    comment_id: Option<i64>,
+) -> Result<Post, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_accepted_answer(post_id, comment_id, &user, &db_pool).await
+}
+
+#[server]
+pub async fn get_post_image_vec(
+    post_id: i64,
+) -> Result<Vec<PostImage>, AppError> {
+    let db_pool = get_db_pool()?;
+    ssr::get_post_image_vec(post_id, &db_pool).await
+}
+
+#[server(input = MultipartFormData)]
+pub async fn add_post_images(
+    data: MultipartData,
+) -> Result<Vec<PostImage>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    let object_container_url = std::env::var(OBJECT_CONTAINER_URL_ENV)?;
+    let bucket_name = std::env::var(ssr::POST_IMAGE_BUCKET_ENV)?;
+    let object_store = ssr::get_post_image_object_store()?;
+    ssr::add_post_images(
+        data,
+        &object_store,
+        &object_container_url,
+        &bucket_name,
+        &user,
+        &db_pool,
+    ).await
+}
+
 #[server]
 pub async fn delete_post(
     post_id: i64,
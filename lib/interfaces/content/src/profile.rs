@@ -1,15 +1,19 @@
 use leptos::prelude::*;
+use leptos::server_fn::codec::{MultipartData, MultipartFormData};
 
 #[cfg(feature = "ssr")]
 use {
     sphare_core_common::constants::{COMMENT_BATCH_SIZE, POST_BATCH_SIZE},
     sphare_core_common::db_utils::ssr::get_db_pool,
     sphare_core_content::profile::*,
+    sphare_core_sphere::sphere_management::ssr::OBJECT_CONTAINER_URL_ENV,
+    sphare_core_user::auth::ssr::{check_user, get_user, reload_user},
 };
 
 use sphare_core_common::errors::AppError;
 use sphare_core_content::comment::CommentWithContext;
 use sphare_core_content::post::PostWithSphereInfo;
+use sphare_core_content::profile::ProfileContent;
 use sphare_core_content::ranking::SortType;
 
 #[server]
@@ -17,12 +21,14 @@ pub async fn get_user_post_vec(
     username: String,
     sort_type: SortType,
     num_already_loaded: usize,
-) -> Result<Vec<PostWithSphereInfo>, AppError> {
+) -> Result<ProfileContent<PostWithSphereInfo>, AppError> {
+    let viewer_id = get_user().await.unwrap_or(None).map(|user| user.user_id);
     let db_pool = get_db_pool()?;
 
     ssr::get_user_post_vec(
         &username,
         sort_type,
+        viewer_id,
         POST_BATCH_SIZE,
         num_already_loaded as i64,
         &db_pool,
@@ -34,14 +40,39 @@ pub async fn get_user_comment_vec(
     username: String,
     sort_type: SortType,
     num_already_loaded: usize,
-) -> Result<Vec<CommentWithContext>, AppError> {
+) -> Result<ProfileContent<CommentWithContext>, AppError> {
+    let viewer_id = get_user().await.unwrap_or(None).map(|user| user.user_id);
     let db_pool = get_db_pool()?;
 
     ssr::get_user_comment_vec(
         &username,
         sort_type,
+        viewer_id,
         COMMENT_BATCH_SIZE,
         num_already_loaded as i64,
         &db_pool,
     ).await
-}
\ No newline at end of file
+}
+
+#[server(input = MultipartFormData)]
+pub async fn set_user_avatar(
+    data: MultipartData,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    let object_container_url = std::env::var(OBJECT_CONTAINER_URL_ENV)?;
+    let bucket_name = std::env::var(ssr::AVATAR_BUCKET_ENV)?;
+    let object_store = ssr::get_avatar_object_store()?;
+    ssr::set_user_avatar(
+        data,
+        &object_store,
+        &object_container_url,
+        &bucket_name,
+        &user,
+        &db_pool,
+    ).await?;
+    reload_user(user.user_id)?;
+
+    Ok(())
+}
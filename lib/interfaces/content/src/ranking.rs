@@ -8,7 +8,7 @@ use {
 };
 
 use sphare_core_common::errors::AppError;
-use sphare_core_content::ranking::{Vote, VoteValue};
+use sphare_core_content::ranking::{ContentReaction, Vote, VoteValue};
 
 #[server]
 pub async fn vote_on_content(
@@ -28,4 +28,28 @@ pub async fn vote_on_content(
         &user,
         &db_pool,
     ).await
-}
\ No newline at end of file
+}
+
+#[server]
+pub async fn add_reaction(
+    post_id: i64,
+    comment_id: Option<i64>,
+    reaction: String,
+) -> Result<ContentReaction, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::add_reaction(post_id, comment_id, &reaction, &user, &db_pool).await
+}
+
+#[server]
+pub async fn remove_reaction(
+    post_id: i64,
+    comment_id: Option<i64>,
+    reaction: String,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::remove_reaction(post_id, comment_id, &reaction, &user, &db_pool).await
+}
@@ -5,13 +5,14 @@ use {
     sphare_core_common::constants::{COMMENT_BATCH_SIZE, POST_BATCH_SIZE, SPHERE_HEADER_FETCH_LIMIT},
     sphare_core_common::db_utils::ssr::get_db_pool,
     sphare_core_content::search::*,
-    sphare_core_user::auth::ssr::{get_user},
+    sphare_core_user::auth::ssr::{check_user, get_user},
 };
 
 use sphare_core_common::common::SphereHeader;
 use sphare_core_common::errors::AppError;
 use sphare_core_content::comment::CommentWithContext;
 use sphare_core_content::post::PostWithSphereInfo;
+use sphare_core_content::search::SavedSearch;
 
 #[server]
 pub async fn get_matching_sphere_header_vec(
@@ -32,7 +33,7 @@ pub async fn search_spheres(
     num_already_loaded: usize,
 ) -> Result<Vec<SphereHeader>, AppError> {
     let db_pool = get_db_pool()?;
-    let show_nsfw = get_user().await.unwrap_or(None).map(|user| user.show_nsfw).unwrap_or_default();
+    let show_nsfw = get_user().await.unwrap_or(None).map(|user| user.nsfw_mode.includes_nsfw()).unwrap_or_default();
     ssr::search_spheres(&search_query, show_nsfw, load_count as i64, num_already_loaded as i64, &db_pool).await
 }
 
@@ -44,7 +45,7 @@ pub async fn search_posts(
     num_already_loaded: usize,
 ) -> Result<Vec<PostWithSphereInfo>, AppError> {
     let db_pool = get_db_pool()?;
-    let show_nsfw = get_user().await.unwrap_or(None).map(|user| user.show_nsfw).unwrap_or_default();
+    let show_nsfw = get_user().await.unwrap_or(None).map(|user| user.nsfw_mode.includes_nsfw()).unwrap_or_default();
     ssr::search_posts(
         &search_query,
         sphere_name.as_deref(),
@@ -70,4 +71,33 @@ pub async fn search_comments(
         num_already_loaded as i64,
         &db_pool
     ).await
-}
\ No newline at end of file
+}
+
+#[server]
+pub async fn get_saved_search_vec() -> Result<Vec<SavedSearch>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::get_saved_search_vec(&user, &db_pool).await
+}
+
+#[server]
+pub async fn create_saved_search(
+    search_query: String,
+    sphere_name: Option<String>,
+) -> Result<SavedSearch, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::create_saved_search(&search_query, sphere_name.as_deref(), &user, &db_pool).await
+}
+
+#[server]
+pub async fn delete_saved_search(
+    saved_search_id: i64,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::delete_saved_search(saved_search_id, &user, &db_pool).await
+}
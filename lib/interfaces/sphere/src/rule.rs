@@ -4,7 +4,7 @@ use leptos::prelude::*;
 use {
     sphare_core_common::db_utils::ssr::get_db_pool,
     sphare_core_sphere::rule::*,
-    sphare_core_user::auth::ssr::check_user,
+    sphare_core_user::auth::ssr::{check_user, get_user},
 };
 
 use sphare_core_common::common::Rule;
@@ -80,4 +80,25 @@ pub async fn remove_rule(
     let db_pool = get_db_pool()?;
     let user = check_user().await?;
     ssr::remove_rule(sphere_name.as_ref(), priority, &user, &db_pool).await
-}
\ No newline at end of file
+}
+
+#[server]
+pub async fn has_accepted_rules(
+    sphere_name: String,
+) -> Result<bool, AppError> {
+    let db_pool = get_db_pool()?;
+    match get_user().await {
+        Ok(Some(user)) => ssr::has_accepted_rules(&sphere_name, user.user_id, &db_pool).await,
+        _ => Ok(false),
+    }
+}
+
+#[server]
+pub async fn accept_rules(
+    sphere_name: String,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::accept_rules(&sphere_name, &user, &db_pool).await
+}
@@ -4,7 +4,7 @@ use leptos::prelude::*;
 use {
     sphare_core_common::db_utils::ssr::get_db_pool,
     sphare_core_sphere::satellite::*,
-    sphare_core_user::auth::ssr::check_user,
+    sphare_core_user::auth::ssr::{check_user, get_user},
 };
 
 use sphare_core_common::errors::AppError;
@@ -100,4 +100,33 @@ pub async fn deactivate_satellite(
         &user,
         &db_pool
     ).await
-}
\ No newline at end of file
+}
+
+#[server]
+pub async fn is_subscribed_to_satellite(
+    satellite_id: i64,
+) -> Result<bool, AppError> {
+    match get_user().await {
+        Ok(Some(user)) => {
+            let db_pool = get_db_pool()?;
+            ssr::is_subscribed_to_satellite(satellite_id, user.user_id, &db_pool).await
+        }
+        _ => Ok(false),
+    }
+}
+
+#[server]
+pub async fn subscribe_satellite(satellite_id: i64) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::subscribe_satellite(satellite_id, user.user_id, &db_pool).await
+}
+
+#[server]
+pub async fn unsubscribe_satellite(satellite_id: i64) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::unsubscribe_satellite(satellite_id, user.user_id, &db_pool).await
+}
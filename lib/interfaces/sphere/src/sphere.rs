@@ -2,6 +2,7 @@ use leptos::prelude::*;
 
 #[cfg(feature = "ssr")]
 use {
+    sphare_core_common::constants::SPHERE_FETCH_LIMIT,
     sphare_core_common::db_utils::ssr::get_db_pool,
     sphare_core_sphere::sphere::*,
     sphare_core_user::auth::ssr::{check_user, get_user, reload_user},
@@ -9,7 +10,8 @@ use {
 
 use sphare_core_common::common::SphereHeader;
 use sphare_core_common::errors::AppError;
-use sphare_core_sphere::sphere::{Sphere, SphereWithUserInfo};
+use sphare_core_sphere::sphere::{AllowedPostTypes, Sphere, SphereWithUserInfo};
+use sphare_core_user::user::KarmaType;
 
 #[server]
 pub async fn is_sphere_available(sphere_name: String) -> Result<bool, AppError> {
@@ -35,12 +37,27 @@ pub async fn get_subscribed_sphere_headers() -> Result<Vec<SphereHeader>, AppErr
     }
 }
 
+#[server]
+pub async fn get_recommended_spheres() -> Result<Vec<SphereHeader>, AppError> {
+    let user = get_user().await.unwrap_or(None);
+    let db_pool = get_db_pool()?;
+    ssr::get_recommended_spheres(user.as_ref(), SPHERE_FETCH_LIMIT as i64, &db_pool).await
+}
+
 #[server]
 pub async fn get_popular_sphere_headers() -> Result<Vec<SphereHeader>, AppError> {
     let db_pool = get_db_pool()?;
     ssr::get_popular_sphere_headers(20, &db_pool).await
 }
 
+#[server]
+pub async fn get_trending_sphere_vec(
+    num_already_loaded: usize,
+) -> Result<Vec<SphereHeader>, AppError> {
+    let db_pool = get_db_pool()?;
+    ssr::get_trending_sphere_vec(SPHERE_FETCH_LIMIT as i64, num_already_loaded as i64, &db_pool).await
+}
+
 #[server]
 pub async fn get_sphere_with_user_info(
     sphere_name: String,
@@ -91,6 +108,105 @@ pub async fn update_sphere_description(
 
 }
 
+#[server]
+pub async fn set_sphere_require_rule_acceptance(
+    sphere_name: String,
+    require_rule_acceptance: bool,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_sphere_require_rule_acceptance(&sphere_name, require_rule_acceptance, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_require_post_approval(
+    sphere_name: String,
+    require_post_approval: bool,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_sphere_require_post_approval(&sphere_name, require_post_approval, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_reject_duplicate_links(
+    sphere_name: String,
+    reject_duplicate_links: bool,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_sphere_reject_duplicate_links(&sphere_name, reject_duplicate_links, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_require_category(
+    sphere_name: String,
+    require_category: bool,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_sphere_require_category(&sphere_name, require_category, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_min_account_age_days(
+    sphere_name: String,
+    min_account_age_days: i32,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_sphere_min_account_age_days(&sphere_name, min_account_age_days, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_min_karma(
+    sphere_name: String,
+    min_karma: i32,
+    min_karma_type: KarmaType,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_sphere_min_karma(&sphere_name, min_karma, min_karma_type, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_allowed_post_types(
+    sphere_name: String,
+    allowed_post_types: AllowedPostTypes,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_sphere_allowed_post_types(&sphere_name, allowed_post_types, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_title_rules(
+    sphere_name: String,
+    min_title_length: i32,
+    max_title_length: Option<i32>,
+    title_regex: Option<String>,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::set_sphere_title_rules(&sphere_name, min_title_length, max_title_length, title_regex.as_deref(), &user, &db_pool).await?;
+    Ok(())
+}
+
 #[server]
 pub async fn subscribe(sphere_id: i64) -> Result<(), AppError> {
     let user = check_user().await?;
@@ -99,6 +215,14 @@ pub async fn subscribe(sphere_id: i64) -> Result<(), AppError> {
     ssr::subscribe(sphere_id, user.user_id, &db_pool).await
 }
 
+#[server]
+pub async fn subscribe_many(sphere_id_vec: Vec<i64>) -> Result<u64, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::subscribe_many(sphere_id_vec, user.user_id, &db_pool).await
+}
+
 #[server]
 pub async fn unsubscribe(sphere_id: i64) -> Result<(), AppError> {
     let user = check_user().await?;
@@ -106,3 +230,24 @@ pub async fn unsubscribe(sphere_id: i64) -> Result<(), AppError> {
 
     ssr::unsubscribe(sphere_id, user.user_id, &db_pool).await
 }
+
+#[server]
+pub async fn has_confirmed_nsfw(
+    sphere_name: String,
+) -> Result<bool, AppError> {
+    let db_pool = get_db_pool()?;
+    match get_user().await {
+        Ok(Some(user)) => ssr::has_confirmed_nsfw(&sphere_name, user.user_id, &db_pool).await,
+        _ => Ok(false),
+    }
+}
+
+#[server]
+pub async fn confirm_nsfw(
+    sphere_name: String,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::confirm_nsfw(&sphere_name, &user, &db_pool).await
+}
@@ -14,9 +14,10 @@ use sphare_core_sphere::sphere_category::SphereCategory;
 #[server]
 pub async fn get_sphere_category_vec(
     sphere_name: String,
+    include_inactive: bool,
 ) -> Result<Vec<SphereCategory>, AppError> {
     let db_pool = get_db_pool()?;
-    ssr::get_sphere_category_vec(&sphere_name, &db_pool).await
+    ssr::get_sphere_category_vec(&sphere_name, include_inactive, &db_pool).await
 }
 
 #[server]
@@ -32,12 +33,34 @@ pub async fn set_sphere_category(
     ssr::set_sphere_category(&sphere_name, &category_name, category_color, &description, is_active, &user, &db_pool).await
 }
 
+#[server]
+pub async fn reorder_categories(
+    sphere_name: String,
+    category_name_vec: Vec<String>,
+) -> Result<Vec<SphereCategory>, AppError> {
+    let db_pool = get_db_pool()?;
+    let user = check_user().await?;
+    ssr::reorder_categories(&sphere_name, &category_name_vec, &user, &db_pool).await
+}
+
+#[server]
+pub async fn set_category_active(
+    sphere_name: String,
+    category_name: String,
+    is_active: bool,
+) -> Result<SphereCategory, AppError> {
+    let db_pool = get_db_pool()?;
+    let user = check_user().await?;
+    ssr::set_category_active(&sphere_name, &category_name, is_active, &user, &db_pool).await
+}
+
 #[server]
 pub async fn delete_sphere_category(
     sphere_name: String,
     category_name: String,
+    reassign_posts: bool,
 ) -> Result<(), AppError> {
     let db_pool = get_db_pool()?;
     let user = check_user().await?;
-    ssr::delete_sphere_category(&sphere_name, &category_name, &user, &db_pool).await
+    ssr::delete_sphere_category(&sphere_name, &category_name, reassign_posts, &user, &db_pool).await
 }
\ No newline at end of file
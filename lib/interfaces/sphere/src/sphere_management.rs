@@ -1,5 +1,6 @@
 use leptos::prelude::*;
 use leptos::server_fn::codec::{MultipartData, MultipartFormData};
+use sphare_core_common::colors::Color;
 use sphare_core_common::errors::AppError;
 
 #[cfg(feature = "ssr")]
@@ -12,6 +13,7 @@ use {
     sphare_core_user::auth::ssr::{check_user, reload_user},
 };
 
+use sphare_core_sphere::sphere_management::TrustedUser;
 use sphare_core_user::user::UserBan;
 
 #[server]
@@ -34,6 +36,67 @@ pub async fn remove_user_ban(
     Ok(())
 }
 
+#[server]
+pub async fn get_sphere_trusted_user_vec(
+    sphere_name: String,
+) -> Result<Vec<TrustedUser>, AppError> {
+    let db_pool = get_db_pool()?;
+    ssr::get_sphere_trusted_user_vec(&sphere_name, &db_pool).await
+}
+
+#[server]
+pub async fn add_trusted_user(
+    username: String,
+    sphere_name: String,
+) -> Result<TrustedUser, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::add_trusted_user(&username, &sphere_name, &user, &db_pool).await
+}
+
+#[server]
+pub async fn remove_trusted_user(
+    trusted_user_id: i64
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::remove_trusted_user(trusted_user_id, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_archived(
+    sphere_name: String,
+    is_archived: bool,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::set_sphere_archived(&sphere_name, is_archived, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_color(
+    sphere_name: String,
+    color: Color,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::set_sphere_color(&sphere_name, color, &user, &db_pool).await?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_sphere_sidebar(
+    sphere_name: String,
+    markdown: String,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::set_sphere_sidebar(&sphere_name, &markdown, &user, &db_pool).await?;
+    Ok(())
+}
+
 #[server(input = MultipartFormData)]
 pub async fn set_sphere_icon(
     data: MultipartData,
@@ -0,0 +1,67 @@
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use {
+    sphare_core_common::db_utils::ssr::get_db_pool,
+    sphare_core_sphere::wiki::ssr,
+    sphare_core_user::auth::ssr::check_user,
+};
+
+use sphare_core_common::errors::AppError;
+use sphare_core_sphere::wiki::SpherePage;
+
+#[server]
+pub async fn get_sphere_page(
+    sphere_name: String,
+    slug: String,
+) -> Result<SpherePage, AppError> {
+    let db_pool = get_db_pool()?;
+    ssr::get_sphere_page(&sphere_name, &slug, &db_pool).await
+}
+
+#[server]
+pub async fn get_sphere_page_vec(
+    sphere_name: String,
+) -> Result<Vec<SpherePage>, AppError> {
+    let db_pool = get_db_pool()?;
+    ssr::get_sphere_page_vec(&sphere_name, &db_pool).await
+}
+
+#[server]
+pub async fn create_sphere_page(
+    sphere_name: String,
+    slug: String,
+    title: String,
+    markdown_body: String,
+    is_markdown: bool,
+) -> Result<SpherePage, AppError> {
+    let db_pool = get_db_pool()?;
+    let user = check_user().await?;
+
+    ssr::create_sphere_page(&sphere_name, &slug, &title, &markdown_body, is_markdown, &user, &db_pool).await
+}
+
+#[server]
+pub async fn update_sphere_page(
+    sphere_name: String,
+    slug: String,
+    title: String,
+    markdown_body: String,
+    is_markdown: bool,
+) -> Result<SpherePage, AppError> {
+    let db_pool = get_db_pool()?;
+    let user = check_user().await?;
+
+    ssr::update_sphere_page(&sphere_name, &slug, &title, &markdown_body, is_markdown, &user, &db_pool).await
+}
+
+#[server]
+pub async fn delete_sphere_page(
+    sphere_name: String,
+    slug: String,
+) -> Result<(), AppError> {
+    let db_pool = get_db_pool()?;
+    let user = check_user().await?;
+
+    ssr::delete_sphere_page(&sphere_name, &slug, &user, &db_pool).await
+}
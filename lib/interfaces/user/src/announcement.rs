@@ -0,0 +1,64 @@
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use {
+    std::cmp::min,
+    sphare_core_common::constants::MAX_ANNOUNCEMENT_HOURS,
+    sphare_core_common::db_utils::ssr::get_db_pool,
+    sphare_core_user::auth::ssr::{check_user, get_user},
+};
+
+use sphare_core_common::errors::AppError;
+use sphare_core_user::announcement::*;
+
+/// Returns the active announcements, excluding those dismissed by the current user if logged in
+#[server]
+pub async fn get_active_announcements() -> Result<Vec<Announcement>, AppError> {
+    let db_pool = get_db_pool()?;
+
+    match get_user().await {
+        Ok(Some(user)) => ssr::get_unseen_announcements(&user, &db_pool).await,
+        _ => ssr::get_active_announcements(&db_pool).await,
+    }
+}
+
+#[server]
+pub async fn get_unseen_announcements() -> Result<Vec<Announcement>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::get_unseen_announcements(&user, &db_pool).await
+}
+
+#[server]
+pub async fn create_announcement(
+    title: String,
+    body: String,
+    is_markdown: bool,
+    start_in_hours: Option<u32>,
+    duration_hours: Option<u32>,
+) -> Result<Announcement, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    let start_delay = chrono::TimeDelta::hours(min(start_in_hours.unwrap_or_default(), MAX_ANNOUNCEMENT_HOURS) as i64);
+    let start_timestamp = chrono::Utc::now().checked_add_signed(start_delay).ok_or(AppError::new("Invalid announcement start."))?;
+    let end_timestamp = match duration_hours {
+        Some(duration_hours) => {
+            let duration = chrono::TimeDelta::hours(min(duration_hours, MAX_ANNOUNCEMENT_HOURS) as i64);
+            Some(start_timestamp.checked_add_signed(duration).ok_or(AppError::new("Invalid announcement duration."))?)
+        },
+        None => None,
+    };
+    ssr::create_announcement(&title, &body, is_markdown, Some(start_timestamp), end_timestamp, &user, &db_pool).await
+}
+
+#[server]
+pub async fn dismiss_announcement(
+    announcement_id: i64,
+) -> Result<(), AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+
+    ssr::dismiss_announcement(announcement_id, &user, &db_pool).await
+}
@@ -0,0 +1,22 @@
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use {
+    sphare_core_common::db_utils::ssr::get_db_pool,
+    sphare_core_user::auth::ssr::check_user,
+    sphare_core_user::mod_log::*,
+};
+
+use sphare_core_common::errors::AppError;
+use sphare_core_user::mod_log::ModLogEntry;
+
+#[server]
+pub async fn get_mod_log(
+    sphere_name: String,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<ModLogEntry>, AppError> {
+    let user = check_user().await?;
+    let db_pool = get_db_pool()?;
+    ssr::get_mod_log(&sphere_name, limit, offset, &user, &db_pool).await
+}
@@ -2,13 +2,15 @@ use leptos::prelude::*;
 
 #[cfg(feature = "ssr")]
 use {
+    sphare_core_common::constants::MENTION_SUGGESTION_LIMIT,
     sphare_core_common::db_utils::ssr::get_db_pool,
     sphare_core_user::auth::ssr::{check_user, delete_user_in_oidc_provider, get_user, reload_user},
     sphare_core_user::user::*,
 };
 
+use sphare_core_common::common::PostSortType;
 use sphare_core_common::errors::AppError;
-use sphare_core_user::user::UserHeader;
+use sphare_core_user::user::{NsfwMode, UserHeader, UserProfileHeader};
 
 #[server]
 pub async fn get_matching_user_header_vec(
@@ -18,11 +20,19 @@ pub async fn get_matching_user_header_vec(
 ) -> Result<Vec<UserHeader>, AppError> {
     let db_pool = get_db_pool()?;
     // TODO check if show_nsfw can be simplified
-    let show_nsfw = show_nsfw.unwrap_or_default() || get_user().await.unwrap_or(None).map(|user| user.show_nsfw).unwrap_or_default();
+    let show_nsfw = show_nsfw.unwrap_or_default() || get_user().await.unwrap_or(None).map(|user| user.nsfw_mode.includes_nsfw()).unwrap_or_default();
     let user_header_vec = ssr::get_matching_user_header_vec(&username_prefix, show_nsfw, load_count as i64, &db_pool).await?;
     Ok(user_header_vec)
 }
 
+#[server]
+pub async fn get_matching_username_vec(
+    username_prefix: String,
+) -> Result<Vec<String>, AppError> {
+    let db_pool = get_db_pool()?;
+    ssr::get_matching_username_vec(&username_prefix, MENTION_SUGGESTION_LIMIT, &db_pool).await
+}
+
 #[server]
 pub async fn delete_user() -> Result<(), AppError> {
     let db_pool = get_db_pool()?;
@@ -40,13 +50,69 @@ pub async fn delete_user() -> Result<(), AppError> {
 #[server]
 pub async fn set_user_settings(
     is_nsfw: bool,
-    show_nsfw: bool,
+    nsfw_mode: NsfwMode,
     days_hide_spoilers: u32,
 ) -> Result<(), AppError> {
     let db_pool = get_db_pool()?;
     let user = check_user().await?;
 
-    ssr::set_user_settings(is_nsfw, show_nsfw, days_hide_spoilers, &user, &db_pool).await?;
+    ssr::set_user_settings(is_nsfw, nsfw_mode, days_hide_spoilers, &user, &db_pool).await?;
+    reload_user(user.user_id)?;
+    Ok(())
+}
+
+#[server]
+pub async fn change_username(
+    new_username: String,
+) -> Result<(), AppError> {
+    let db_pool = get_db_pool()?;
+    let user = check_user().await?;
+
+    ssr::change_username(&new_username, &user, &db_pool).await?;
+    reload_user(user.user_id)?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_default_post_sort(
+    post_sort_type: PostSortType,
+) -> Result<(), AppError> {
+    let db_pool = get_db_pool()?;
+    let user = check_user().await?;
+
+    ssr::set_default_post_sort(post_sort_type, &user, &db_pool).await?;
     reload_user(user.user_id)?;
     Ok(())
-}
\ No newline at end of file
+}
+
+#[server]
+pub async fn set_public_profile_visible(
+    is_visible: bool,
+) -> Result<(), AppError> {
+    let db_pool = get_db_pool()?;
+    let user = check_user().await?;
+
+    ssr::set_public_profile_visible(is_visible, &user, &db_pool).await?;
+    reload_user(user.user_id)?;
+    Ok(())
+}
+
+#[server]
+pub async fn set_user_bio(
+    bio: String,
+) -> Result<(), AppError> {
+    let db_pool = get_db_pool()?;
+    let user = check_user().await?;
+
+    ssr::set_user_bio(&bio, &user, &db_pool).await?;
+    reload_user(user.user_id)?;
+    Ok(())
+}
+
+#[server]
+pub async fn get_user_profile_header(
+    username: String,
+) -> Result<UserProfileHeader, AppError> {
+    let db_pool = get_db_pool()?;
+    ssr::get_user_profile_header(&username, &db_pool).await
+}
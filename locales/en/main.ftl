@@ -3,6 +3,8 @@ cancel = Cancel
 submit = Submit
 create = Create
 publish = Publish
+publish-delay = Publication
+publish-now = Now
 assign = Assign
 save = Save
 login = Login
@@ -10,6 +12,7 @@ search = Search
 manage = Manage
 new = New
 join = Join
+follow-satellite = Follow satellite
 close = Close
 
 users = Users
@@ -23,6 +26,8 @@ sphere = Sphere
 sphere-banner = Sphere banner
 sphere-icon = Sphere icon
 sphere-description = Sphere description
+sphere-sidebar = Sphere sidebar
+sphere-accent-color = Sphere accent color
 satellites = Satellites
 sphere-categories = Sphere categories
 categories = Categories
@@ -36,15 +41,26 @@ notification-post-reply = replied to your post
 notification-comment-reply = replied to your comment
 notification-moderate-post = moderated your post
 notification-moderate-comment = moderated your comment
+notification-mention = mentioned you
+notification-saved-search = posted a post matching your saved search
 web-notif-post-reply = {$username} replied to your post on {$sphere_name}.
 web-notif-comment-reply = {$username} replied to your comment on {$sphere_name}.
 web-notif-moderate-post = {$username} moderated your post on {$sphere_name}.
 web-notif-moderate-comment = {$username} moderated your comment on {$sphere_name}.
+web-notif-mention = {$username} mentioned you on {$sphere_name}.
+web-notif-saved-search = {$username} posted a post matching your saved search on {$sphere_name}.
 multi-web-notif = You have {$new_notif_count} new notifications.
 multi-web-notif-with-unread = You have {$new_notif_count} new notifications, {$unread_notif_count} unread in total.
 web-notif-unread-addon = {" "}You have {$unread_notif_count} unread notifications.
 read-all-notifs = Read all
 read-notif = Set read
+reading-time = {$count} min read
+edited-times = {$count ->
+    [one] Edited once
+   *[other] Edited {$count} times
+}
+vote-breakdown = {$upvotes} upvotes / {$downvotes} downvotes
+add-reaction = + React
 notif-not-supported = Browser notifications are not supported.
 notif-permission-granted = Notifications permission granted.
 notif-permission-unknown = Notifications permission unknown, trying to request...
@@ -74,12 +90,29 @@ infringed-rule = Infringed rule
 moderator-message = Moderator message
 banned-users = Banner users
 ban-duration = Ban duration (days)
+mod-log = Moderation log
+date = Date
+moderator = Moderator
+action = Action
+target = Target
+mod-action-moderate-post = Moderated post
+mod-action-moderate-comment = Moderated comment
+mod-action-approve-post = Approved post
+mod-action-move-post = Moved post
+mod-action-ban-user = Banned user
+mod-action-unban-user = Unbanned user
+mod-action-set-role = Changed role
+mod-action-mute-user = Muted user
+mod-action-unmute-user = Unmuted user
+mod-action-unmoderate-post = Restored moderated post
+mod-action-unmoderate-comment = Restored moderated comment
 
 link = Link
 link-none = None
 link-link = Link
 link-embed = Embed
 link-url = Url
+alt-text = Image description (alt text)
 link-help = When you input a link, Sphare will first try to embed it if its provider implements the OEmbed API or if the link is a file with a recognised format. If not, it will default to display it as a simple link.
 
 home = Home
@@ -92,24 +125,68 @@ github-repo = GitHub Repository
 hot = Hot
 trending = Trending
 best = Best
+best-ratio = Best ratio
 recent = Recent
 
 spheres = Spheres
 create-sphere = Settle a Sphere!
 create-satellite = Create a Satellite
 edit-satellite = Edit Satellite
+wiki = Wiki
+no-wiki-page = No wiki page yet.
+create-wiki-page = Create a Wiki Page
+edit-wiki-page = Edit Wiki Page
+wiki-slug = Page identifier
+wiki-page-not-found = This wiki page does not exist yet.
 posts = Posts
 share-post = Share a post!
 edit-post = Edit your post
 delete-post = Delete post
-load-replies = Load replies
+crosspost = Crosspost
+similar-posts = Similar posts
+crossposted-from = Crossposted from {$sphere}
+crossposted-from-deleted = Crossposted from a deleted post of {$sphere}
+continue-thread = Continue this thread
+load-more-replies = Load more replies
+loading-more-content = Loading more content
+content-loaded = {$count ->
+    [one] One item loaded
+   *[other] {$count} items loaded
+}
 comments = Comments
 share-comment = Share a comment
 edit-comment = Edit your comment
 your-comment = Your comment...
 delete-comment = Delete comment
+accepted-answer = Accepted answer
+accept-answer = Accept as answer
+remove-accepted-answer = Remove accepted answer
+create-poll = Create a poll
+poll-option = Option {$index}
+add-poll-option = Add an option
+poll-duration = Duration
+poll-duration-days = {$count ->
+    [one] 1 day
+   *[other] {$count} days
+}
+poll-ends = Poll ends on {$date}
+poll-votes = {$count ->
+    [one] 1 vote
+   *[other] {$count} votes
+}
+poll-closed = {$count ->
+    [one] Poll closed, 1 vote
+   *[other] Poll closed, {$count} votes
+}
 moderate-post = Moderate post
 moderate-comment = Moderate comment
+report-post = Report post
+report-comment = Report comment
+report-reason = Reason (optional)
+report-inbox = Report inbox
+reported-post = Reported post
+reported-comment = Reported comment
+moderate = Moderate
 add-rule = Add a rule
 
 role-none = None
@@ -122,11 +199,18 @@ not-found-message = There's nothing here.
 search-no-user-found = No matching user.
 search-no-sphere-found = No matching Sphere.
 search-no-post-found = No matching post.
+save-search = Save search
+search-saved = Search saved, you will be notified of new matching posts.
 search-no-comment-found = No matching comment.
 
 welcome-to-sphare = Welcome to Sphare!
 about-sphare = About Sphare
 sphere-cockpit = Sphere Cockpit
+sphere-settings = Sphere settings
+require-rule-acceptance = Users must accept the rules before posting
+reject-duplicate-links = Reject links already posted recently
+accept-rules = I accept the rules
+rules-accepted = You accepted the rules of this sphere.
 single-comment-tree = Single comment view. Back to post.
 
 until = Until
@@ -135,8 +219,25 @@ deleted = Deleted
 
 account = User account
 nsfw-profile = NSFW profile
-show-nsfw = Show NSFW content
+nsfw-mode = NSFW content
+nsfw-mode-hide = Hide
+nsfw-mode-blur = Blur
+nsfw-mode-show = Show
+reveal-nsfw = Click to reveal NSFW content
+nsfw-sphere-warning = This sphere contains NSFW content.
+confirm-nsfw = I understand, show me the posts
+public-profile-visible = Show my posts and comments on my public profile
+profile-hidden = This user chose not to show their posts and comments.
+default-post-sort = Default sort of post feeds
 hide-spoiler-duration = Hide spoilers duration (days)
+change-username = Change username
+saved-searches = Saved searches
+no-saved-search = No saved search.
+avatar = Avatar
+joined = Joined
+post-karma = {$count} post karma
+comment-karma = {$count} comment karma
+bio = Bio
 delete-account = Delete your account
 
 time-seconds-short = {$count} s
@@ -398,6 +499,9 @@ faq-image-answer =
 invalid-link = Invalid link
 invalid-domain-name = Invalid domain name
 invalid-video-format = Your browser doesn't support this video's format.
+add-images = Add images to the gallery
+next-image = Next image
+previous-image = Previous image
 copy-link-to-clipboard-message = Copied link to clipboard.
 clipboard-error-message = Clipboard API not supported in your browser.
 
@@ -412,6 +516,26 @@ sphere-ban-until-message = You are banned from this sphere until {$timestamp}
 permanent-sphere-ban-message = You are permanently banned from this sphere.
 global-ban-until-message = You are banned from Sphare until {$timestamp}
 permanent-global-ban-message = You are permanently banned from Sphare.
+rules-not-accepted-message = You must accept the rules of this sphere before posting.
+sphere-mute-message = You are muted in this sphere and cannot comment.
+sphere-archived-message = This sphere is archived, new posts and comments are disabled.
+account-too-new-message = Your account must be at least {$days} days old to post in this sphere.
+insufficient-karma-message = You need at least {$karma} karma to post in this sphere.
+post-type-not-allowed-message = This type of post is not allowed in this sphere.
+category-required-message = Posts in this sphere must have an active category.
+title-too-short-message = Post titles in this sphere must have at least {$count} characters.
+title-too-long-message = Post titles in this sphere cannot exceed {$count} characters.
+title-format-mismatch-message = Post titles in this sphere must match the format {$regex}
+nsfw-not-confirmed-message = You must confirm you are willing to see NSFW content to browse this sphere.
+invalid-input-message = Some of the provided values are invalid.
 bad-request-message = Sorry, we didn't understand your request.
 unavailable-message = Sorry, we've got noise on the line.
-payload-too-large-message = Payload exceeds the {$mb_limit} MB limit.
\ No newline at end of file
+payload-too-large-message = Payload exceeds the {$mb_limit} MB limit.
+duplicate-link-message = This link was already posted recently in this sphere.
+sphere-name-taken-message = This sphere name is already taken.
+category-in-use-message = This category is used by posts and cannot be deleted.
+reserved-name-message = This name is reserved.
+username-taken-message = This username is already taken.
+conflict-message = This resource already exists.
+rate-limited-message = Slow down! Please try again in a moment.
+rate-limited-until-message = Slow down! You can try again after {$timestamp}
@@ -3,6 +3,8 @@ cancel = Annuler
 submit = Soumettre
 create = Créer
 publish = Publier
+publish-delay = Publication
+publish-now = Maintenant
 assign = Assigner
 save = Sauvegarder
 login = Se connecter
@@ -10,6 +12,7 @@ search = Rechercher
 manage = Gérer
 new = Nouveau
 join = Rejoindre
+follow-satellite = Suivre le satellite
 close = Fermer
 
 users = Utilisateurs
@@ -23,6 +26,8 @@ sphere = Sphère
 sphere-banner = Bannière de la Sphère
 sphere-icon = Icone de la Sphère
 sphere-description = Description de la Sphère
+sphere-sidebar = Barre latérale de la Sphère
+sphere-accent-color = Couleur d'accent de la Sphère
 satellites = Satellites
 sphere-categories = Catégories de Sphères
 categories = Catégories
@@ -36,15 +41,26 @@ notification-post-reply = a répondu à ton poste
 notification-comment-reply = a répondu à ton commentaire
 notification-moderate-post = a modéré ton poste
 notification-moderate-comment = a modéré ton commentaire
+notification-mention = t'a mentionné
+notification-saved-search = a publié un poste correspondant à ta recherche enregistrée
 web-notif-post-reply = {$username} a répondu à ton poste sur {$sphere_name}.
 web-notif-comment-reply = {$username} a répondu à ton commentaire sur {$sphere_name}.
 web-notif-moderate-post = {$username} a modéré ton poste sur {$sphere_name}.
 web-notif-moderate-comment = {$username} a modéré ton commentaire sur {$sphere_name}.
+web-notif-mention = {$username} t'a mentionné sur {$sphere_name}.
+web-notif-saved-search = {$username} a publié un poste correspondant à ta recherche enregistrée sur {$sphere_name}.
 multi-web-notif = Tu as {$new_notif_count} nouvelles notifications.
 multi-web-notif-with-unread = Tu as {$new_notif_count} nouvelles notifications, {$unread_notif_count} non-lues en tout.
 web-notif-unread-addon = {" "}Tu as {$unread_notif_count} notifications non-lues.
 read-all-notifs = Lire tout
 read-notif = Marqué lu
+reading-time = {$count} min de lecture
+edited-times = {$count ->
+    [one] Modifié une fois
+   *[other] Modifié {$count} fois
+}
+vote-breakdown = {$upvotes} votes positifs / {$downvotes} votes négatifs
+add-reaction = + Réagir
 notif-not-supported = Les notifications ne sont pas prises en charge par le navigateur.
 notif-permission-granted = Autorisation d'envoi des notifications accordée.
 notif-permission-unknown = Statut d'autorisation des notifications inconnu, tentative de requête...
@@ -74,12 +90,29 @@ infringed-rule = Règle enfreinte
 moderator-message = Message du modérateur
 banned-users = Utilisateurs bannis
 ban-duration = Durée du bannissement (jours)
+mod-log = Journal de modération
+date = Date
+moderator = Modérateur
+action = Action
+target = Cible
+mod-action-moderate-post = Poste modéré
+mod-action-moderate-comment = Commentaire modéré
+mod-action-approve-post = Poste approuvé
+mod-action-move-post = Poste déplacé
+mod-action-ban-user = Utilisateur banni
+mod-action-unban-user = Utilisateur débanni
+mod-action-set-role = Rôle modifié
+mod-action-mute-user = Utilisateur réduit au silence
+mod-action-unmute-user = Utilisateur rétabli
+mod-action-unmoderate-post = Modération du poste annulée
+mod-action-unmoderate-comment = Modération du commentaire annulée
 
 link = Lien
 link-none = Aucun
 link-link = Lien
 link-embed = Intégré
 link-url = Url
+alt-text = Description de l'image (texte alternatif)
 link-help = Lorsque tu entres un lien, Sphare va d'abord essayer de l'inscruster si son fournisseur implémente l'API OEmbed ou si le lien est un fichier avec un format reconnu. Si ce n'est pas le cas, un simple lien sera affiché à la place.
 
 home = Accueil
@@ -92,24 +125,68 @@ github-repo = Répertoire GitHub
 hot = Populaire
 trending = Tendance
 best = Top
+best-ratio = Meilleur ratio
 recent = Récent
 
 spheres = Sphères
 create-sphere = Coloniser une Sphère !
 create-satellite = Créer un Satellite
 edit-satellite = Modifier le Satellite
+wiki = Wiki
+no-wiki-page = Aucune page wiki pour l'instant.
+create-wiki-page = Créer une page wiki
+edit-wiki-page = Modifier la page wiki
+wiki-slug = Identifiant de la page
+wiki-page-not-found = Cette page wiki n'existe pas encore.
 posts = Postes
 share-post = Partage un poste !
 edit-post = Modifie ton poste
 delete-post = Supprimer le poste
-load-replies = Charger les réponses
+crosspost = Partager dans une autre sphère
+similar-posts = Posts similaires
+crossposted-from = Partagé depuis {$sphere}
+crossposted-from-deleted = Partagé depuis un poste supprimé de {$sphere}
+continue-thread = Continuer ce fil
+load-more-replies = Charger plus de réponses
+loading-more-content = Chargement de plus de contenu
+content-loaded = {$count ->
+    [one] Un élément chargé
+   *[other] {$count} éléments chargés
+}
 comments = Commentaires
 share-comment = Partager un commentaire
 edit-comment = Modifie ton commentaire
 your-comment = Ton commentaire...
 delete-comment = Supprimer le commentaire
+accepted-answer = Réponse acceptée
+accept-answer = Accepter comme réponse
+remove-accepted-answer = Retirer la réponse acceptée
+create-poll = Créer un sondage
+poll-option = Option {$index}
+add-poll-option = Ajouter une option
+poll-duration = Durée
+poll-duration-days = {$count ->
+    [one] 1 jour
+   *[other] {$count} jours
+}
+poll-ends = Le sondage se termine le {$date}
+poll-votes = {$count ->
+    [one] 1 vote
+   *[other] {$count} votes
+}
+poll-closed = {$count ->
+    [one] Sondage terminé, 1 vote
+   *[other] Sondage terminé, {$count} votes
+}
 moderate-post = Modérer le poste
 moderate-comment = Modérer le commentaire
+report-post = Signaler le poste
+report-comment = Signaler le commentaire
+report-reason = Raison (facultatif)
+report-inbox = Boîte des signalements
+reported-post = Poste signalé
+reported-comment = Commentaire signalé
+moderate = Modérer
 add-rule = Ajouter une règle
 
 role-none = Aucun
@@ -122,11 +199,18 @@ not-found-message = Il n'y a rien ici.
 search-no-user-found = Pas d'utilisateur correspondant.
 search-no-sphere-found = Pas de Sphère correspondante.
 search-no-post-found = Pas de poste correspondant.
+save-search = Enregistrer la recherche
+search-saved = Recherche enregistrée, tu seras notifié des nouveaux postes correspondants.
 search-no-comment-found = Pas de commentaire correspondant.
 
 welcome-to-sphare = Bienvenue sur Sphare !
 about-sphare = À propos de Sphare
 sphere-cockpit = Cockpit de la Sphère
+sphere-settings = Paramètres de la Sphère
+require-rule-acceptance = Les utilisateurs doivent accepter les règles avant de publier
+reject-duplicate-links = Refuser les liens déjà publiés récemment
+accept-rules = J'accepte les règles
+rules-accepted = Vous avez accepté les règles de cette sphère.
 single-comment-tree = Vue de commentaire individuel. Retour au poste.
 
 until = Jusqu'à
@@ -135,8 +219,25 @@ deleted = Supprimé
 
 account = Compte utilisateur
 nsfw-profile = Profile NSFW
-show-nsfw = Afficher le contenu NSFW
+nsfw-mode = Contenu NSFW
+nsfw-mode-hide = Masquer
+nsfw-mode-blur = Flouter
+nsfw-mode-show = Afficher
+reveal-nsfw = Cliquer pour afficher le contenu NSFW
+nsfw-sphere-warning = Cette sphère contient du contenu NSFW.
+confirm-nsfw = Je comprends, afficher les postes
+public-profile-visible = Afficher mes postes et commentaires sur mon profil public
+profile-hidden = Cet utilisateur a choisi de ne pas afficher ses postes et commentaires.
+default-post-sort = Tri par défaut des postes
 hide-spoiler-duration = Durée de bloquage des spoilers (jours)
+change-username = Changer de nom d'utilisateur
+saved-searches = Recherches enregistrées
+no-saved-search = Aucune recherche enregistrée.
+avatar = Avatar
+joined = Inscrit le
+post-karma = {$count} de karma de posts
+comment-karma = {$count} de karma de commentaires
+bio = Biographie
 delete-account = Supprimer votre compte
 
 time-seconds-short = {$count} s
@@ -402,6 +503,9 @@ faq-image-answer =
 invalid-link = Lien invalide
 invalid-domain-name = Nom de domaine invalide
 invalid-video-format = Votre navigateur ne prend pas en charge ce format vidéo.
+add-images = Ajouter des images à la galerie
+next-image = Image suivante
+previous-image = Image précédente
 copy-link-to-clipboard-message = Lien copié dans le presse-papiers.
 clipboard-error-message = API presse-papiers non-supporté par votre navigateur.
 
@@ -416,6 +520,26 @@ sphere-ban-until-message = Vous êtes banni de cette Sphère jusqu'au {$timestam
 permanent-sphere-ban-message = Vous êtes définitivement banni de cette sphere.
 global-ban-until-message = Vous êtes banni de Sphare jusqu'au {$timestamp}
 permanent-global-ban-message = Vous êtes définitivement banni de Sphare.
+rules-not-accepted-message = Vous devez accepter les règles de cette sphère avant de publier.
+sphere-mute-message = Vous êtes réduit au silence dans cette sphère et ne pouvez pas commenter.
+sphere-archived-message = Cette sphère est archivée, les nouveaux posts et commentaires sont désactivés.
+account-too-new-message = Votre compte doit avoir au moins {$days} jours pour publier dans cette sphère.
+insufficient-karma-message = Vous devez avoir au moins {$karma} de karma pour publier dans cette sphère.
+post-type-not-allowed-message = Ce type de post n'est pas autorisé dans cette sphère.
+category-required-message = Les posts de cette sphère doivent avoir une catégorie active.
+title-too-short-message = Les titres des posts de cette sphère doivent contenir au moins {$count} caractères.
+title-too-long-message = Les titres des posts de cette sphère ne peuvent pas dépasser {$count} caractères.
+title-format-mismatch-message = Les titres des posts de cette sphère doivent respecter le format {$regex}
+nsfw-not-confirmed-message = Vous devez confirmer vouloir voir du contenu NSFW pour parcourir cette sphère.
+invalid-input-message = Certaines des valeurs fournies sont invalides.
 bad-request-message = Désolé, nous n'avons pas compris votre requête.
 unavailable-message = Désolé, il y a du bruit sur la ligne.
-payload-too-large-message = Le fichier dépasse la limite de {$mb_limit} MB.
\ No newline at end of file
+payload-too-large-message = Le fichier dépasse la limite de {$mb_limit} MB.
+duplicate-link-message = Ce lien a déjà été publié récemment dans cette sphère.
+sphere-name-taken-message = Ce nom de sphère est déjà pris.
+category-in-use-message = Cette catégorie est utilisée par des posts et ne peut pas être supprimée.
+reserved-name-message = Ce nom est réservé.
+username-taken-message = Ce nom d'utilisateur est déjà pris.
+conflict-message = Cette ressource existe déjà.
+rate-limited-message = Doucement ! Veuillez réessayer dans un instant.
+rate-limited-until-message = Doucement ! Vous pourrez réessayer après le {$timestamp}
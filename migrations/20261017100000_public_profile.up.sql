@@ -0,0 +1 @@
+ALTER TABLE users ADD COLUMN public_profile_visible BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,12 @@
+CREATE TABLE trusted_users (
+    trusted_user_id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    sphere_id BIGINT NOT NULL REFERENCES spheres (sphere_id),
+    grantor_id BIGINT NOT NULL REFERENCES users (user_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delete_timestamp TIMESTAMPTZ
+);
+
+-- index to guarantee a user is trusted at most once per sphere
+CREATE UNIQUE INDEX idx_unique_trusted_user ON trusted_users (sphere_id, user_id)
+    WHERE trusted_users.delete_timestamp IS NULL;
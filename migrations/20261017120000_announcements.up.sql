@@ -0,0 +1,16 @@
+CREATE TABLE announcements (
+    announcement_id BIGSERIAL PRIMARY KEY,
+    title TEXT NOT NULL CHECK (LENGTH(title) <= 250),
+    body TEXT NOT NULL CHECK (markdown_body IS NOT NULL OR LENGTH(body) <= 20000),
+    markdown_body TEXT CHECK (LENGTH(markdown_body) <= 20000),
+    creator_id BIGINT NOT NULL REFERENCES users (user_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delete_timestamp TIMESTAMPTZ
+);
+
+CREATE TABLE announcement_dismissals (
+    announcement_id BIGINT NOT NULL REFERENCES announcements (announcement_id),
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (announcement_id, user_id)
+);
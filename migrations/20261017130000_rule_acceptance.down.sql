@@ -0,0 +1,2 @@
+DROP TABLE sphere_rule_acceptances;
+ALTER TABLE spheres DROP COLUMN require_rule_acceptance;
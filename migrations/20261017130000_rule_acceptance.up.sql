@@ -0,0 +1,8 @@
+ALTER TABLE spheres ADD COLUMN require_rule_acceptance BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE sphere_rule_acceptances (
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    sphere_id BIGINT NOT NULL REFERENCES spheres (sphere_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (sphere_id, user_id)
+);
@@ -0,0 +1,15 @@
+-- Best sort ranks posts with at least 5 votes (score + 2 * score_minus) first
+DROP INDEX idx_posts_best;
+DROP INDEX idx_posts_sphere_best;
+DROP INDEX idx_posts_satellite_best;
+
+CREATE INDEX idx_posts_best ON posts ((score + 2 * score_minus >= 5) DESC, score DESC)
+    WHERE moderator_id IS NULL AND delete_timestamp IS NULL AND satellite_id IS NULL;
+
+CREATE INDEX idx_posts_sphere_best
+    ON posts (sphere_id, (score + 2 * score_minus >= 5) DESC, score DESC)
+    WHERE is_pinned = FALSE AND moderator_id IS NULL AND delete_timestamp IS NULL AND satellite_id IS NULL;
+
+CREATE INDEX idx_posts_satellite_best
+    ON posts (satellite_id, (score + 2 * score_minus >= 5) DESC, score DESC)
+    WHERE is_pinned = FALSE AND moderator_id IS NULL AND delete_timestamp IS NULL AND satellite_id IS NOT NULL;
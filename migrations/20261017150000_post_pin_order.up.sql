@@ -0,0 +1,2 @@
+-- Explicit ordering of pinned posts, lower values are displayed first
+ALTER TABLE posts ADD COLUMN pin_order INTEGER NOT NULL DEFAULT 0;
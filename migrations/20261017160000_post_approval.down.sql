@@ -0,0 +1,3 @@
+DROP INDEX idx_posts_sphere_pending;
+ALTER TABLE posts DROP COLUMN is_approved;
+ALTER TABLE spheres DROP COLUMN require_post_approval;
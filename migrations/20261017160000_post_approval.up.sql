@@ -0,0 +1,7 @@
+ALTER TABLE spheres ADD COLUMN require_post_approval BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE posts ADD COLUMN is_approved BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- Approval queue of each sphere
+CREATE INDEX idx_posts_sphere_pending
+    ON posts (sphere_id, create_timestamp)
+    WHERE is_approved = FALSE AND moderator_id IS NULL AND delete_timestamp IS NULL;
@@ -0,0 +1,5 @@
+DROP TABLE post_images;
+
+UPDATE posts SET link_type = 1 WHERE link_type = 4;
+ALTER TABLE posts DROP CONSTRAINT posts_link_type_check;
+ALTER TABLE posts ADD CONSTRAINT posts_link_type_check CHECK (link_type IN (-1, 0, 1, 2, 3));
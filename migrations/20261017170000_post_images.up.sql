@@ -0,0 +1,12 @@
+ALTER TABLE posts DROP CONSTRAINT posts_link_type_check;
+ALTER TABLE posts ADD CONSTRAINT posts_link_type_check CHECK (link_type IN (-1, 0, 1, 2, 3, 4));
+
+CREATE TABLE post_images (
+    image_id BIGSERIAL PRIMARY KEY,
+    post_id BIGINT NOT NULL REFERENCES posts (post_id),
+    position INTEGER NOT NULL,
+    url TEXT NOT NULL,
+    thumbnail_url TEXT,
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (post_id, position)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE post_images DROP COLUMN alt_text;
+ALTER TABLE posts DROP COLUMN link_alt_text;
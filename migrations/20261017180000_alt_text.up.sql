@@ -0,0 +1,2 @@
+ALTER TABLE posts ADD COLUMN link_alt_text TEXT;
+ALTER TABLE post_images ADD COLUMN alt_text TEXT;
@@ -0,0 +1,2 @@
+DROP INDEX idx_posts_sphere_link;
+ALTER TABLE spheres DROP COLUMN reject_duplicate_links;
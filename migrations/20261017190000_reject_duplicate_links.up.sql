@@ -0,0 +1,6 @@
+ALTER TABLE spheres ADD COLUMN reject_duplicate_links BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Recent links of each sphere, used to detect duplicate links
+CREATE INDEX idx_posts_sphere_link
+    ON posts (sphere_id, create_timestamp)
+    WHERE link_url IS NOT NULL AND moderator_id IS NULL AND delete_timestamp IS NULL;
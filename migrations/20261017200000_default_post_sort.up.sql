@@ -0,0 +1 @@
+ALTER TABLE users ADD COLUMN default_post_sort TEXT NOT NULL DEFAULT 'Hot' CHECK (default_post_sort IN ('Hot', 'Trending', 'Best', 'Recent'));
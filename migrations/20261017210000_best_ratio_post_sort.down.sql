@@ -0,0 +1,4 @@
+UPDATE users SET default_post_sort = 'Best' WHERE default_post_sort = 'BestRatio';
+ALTER TABLE users DROP CONSTRAINT users_default_post_sort_check;
+ALTER TABLE users ADD CONSTRAINT users_default_post_sort_check
+    CHECK (default_post_sort IN ('Hot', 'Trending', 'Best', 'Recent'));
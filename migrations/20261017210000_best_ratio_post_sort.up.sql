@@ -0,0 +1,3 @@
+ALTER TABLE users DROP CONSTRAINT users_default_post_sort_check;
+ALTER TABLE users ADD CONSTRAINT users_default_post_sort_check
+    CHECK (default_post_sort IN ('Hot', 'Trending', 'Best', 'BestRatio', 'Recent'));
@@ -0,0 +1 @@
+DROP TABLE satellite_subscriptions;
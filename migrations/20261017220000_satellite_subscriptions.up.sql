@@ -0,0 +1,7 @@
+CREATE TABLE satellite_subscriptions (
+    subscription_id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    satellite_id BIGINT NOT NULL REFERENCES satellites (satellite_id),
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_satellite_subscription UNIQUE (user_id, satellite_id)
+);
@@ -0,0 +1 @@
+ALTER TABLE posts ADD COLUMN crosspost_of BIGINT REFERENCES posts (post_id);
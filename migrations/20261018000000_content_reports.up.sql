@@ -0,0 +1,18 @@
+CREATE TABLE content_reports (
+    report_id BIGSERIAL PRIMARY KEY,
+    sphere_id BIGINT NOT NULL REFERENCES spheres (sphere_id),
+    post_id BIGINT NOT NULL REFERENCES posts (post_id),
+    comment_id BIGINT REFERENCES comments (comment_id),
+    rule_id BIGINT REFERENCES rules (rule_id),
+    reason TEXT NOT NULL,
+    reporter_id BIGINT NOT NULL REFERENCES users (user_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_content_report_sphere ON content_reports (sphere_id, create_timestamp);
+
+-- indexes to guarantee a user reports a given post or comment at most once
+CREATE UNIQUE INDEX idx_unique_post_report ON content_reports (post_id, reporter_id)
+    WHERE content_reports.comment_id IS NULL;
+CREATE UNIQUE INDEX idx_unique_comment_report ON content_reports (comment_id, reporter_id)
+    WHERE content_reports.comment_id IS NOT NULL;
@@ -0,0 +1,12 @@
+CREATE TABLE mod_log (
+    log_id BIGSERIAL PRIMARY KEY,
+    sphere_id BIGINT NOT NULL REFERENCES spheres (sphere_id),
+    action SMALLINT NOT NULL CHECK (action IN (0, 1, 2, 3, 4, 5, 6)),
+    actor_id BIGINT NOT NULL REFERENCES users (user_id),
+    target_user_id BIGINT REFERENCES users (user_id),
+    post_id BIGINT REFERENCES posts (post_id),
+    comment_id BIGINT REFERENCES comments (comment_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_mod_log_sphere ON mod_log (sphere_id, create_timestamp);
@@ -0,0 +1,5 @@
+DELETE FROM user_bans WHERE post_id IS NULL OR infringed_rule_id IS NULL;
+ALTER TABLE user_bans DROP CONSTRAINT user_bans_post_or_reason_check;
+ALTER TABLE user_bans DROP COLUMN reason;
+ALTER TABLE user_bans ALTER COLUMN infringed_rule_id SET NOT NULL;
+ALTER TABLE user_bans ALTER COLUMN post_id SET NOT NULL;
@@ -0,0 +1,5 @@
+-- allow bans which are not tied to a moderated post or comment, the moderator then gives a reason
+ALTER TABLE user_bans ALTER COLUMN post_id DROP NOT NULL;
+ALTER TABLE user_bans ALTER COLUMN infringed_rule_id DROP NOT NULL;
+ALTER TABLE user_bans ADD COLUMN reason TEXT;
+ALTER TABLE user_bans ADD CONSTRAINT user_bans_post_or_reason_check CHECK (post_id IS NOT NULL OR reason IS NOT NULL);
@@ -0,0 +1,5 @@
+DELETE FROM mod_log WHERE action IN (7, 8);
+ALTER TABLE mod_log DROP CONSTRAINT mod_log_action_check;
+ALTER TABLE mod_log ADD CONSTRAINT mod_log_action_check CHECK (action IN (0, 1, 2, 3, 4, 5, 6));
+
+DROP TABLE user_mutes;
@@ -0,0 +1,15 @@
+CREATE TABLE user_mutes (
+    mute_id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    sphere_id BIGINT NOT NULL REFERENCES spheres (sphere_id),
+    moderator_id BIGINT NOT NULL REFERENCES users (user_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delete_timestamp TIMESTAMPTZ
+);
+
+-- index to guarantee a user is muted at most once per sphere
+CREATE UNIQUE INDEX idx_unique_user_mute ON user_mutes (sphere_id, user_id)
+    WHERE user_mutes.delete_timestamp IS NULL;
+
+ALTER TABLE mod_log DROP CONSTRAINT mod_log_action_check;
+ALTER TABLE mod_log ADD CONSTRAINT mod_log_action_check CHECK (action IN (0, 1, 2, 3, 4, 5, 6, 7, 8));
@@ -0,0 +1 @@
+ALTER TABLE spheres DROP COLUMN is_archived;
@@ -0,0 +1 @@
+ALTER TABLE spheres ADD COLUMN is_archived BOOLEAN NOT NULL DEFAULT FALSE;
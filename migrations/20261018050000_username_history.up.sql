@@ -0,0 +1,9 @@
+CREATE TABLE username_history (
+    change_id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    old_username TEXT NOT NULL,
+    new_username TEXT NOT NULL,
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_username_history_user ON username_history (user_id, create_timestamp DESC);
@@ -0,0 +1,3 @@
+ALTER TABLE users ADD COLUMN show_nsfw BOOLEAN NOT NULL DEFAULT FALSE;
+UPDATE users SET show_nsfw = nsfw_mode != 'Hide';
+ALTER TABLE users DROP COLUMN nsfw_mode;
@@ -0,0 +1,3 @@
+ALTER TABLE users ADD COLUMN nsfw_mode TEXT NOT NULL DEFAULT 'Hide' CHECK (nsfw_mode IN ('Hide', 'Blur', 'Show'));
+UPDATE users SET nsfw_mode = 'Show' WHERE show_nsfw;
+ALTER TABLE users DROP COLUMN show_nsfw;
@@ -0,0 +1 @@
+DROP TABLE nsfw_confirmations;
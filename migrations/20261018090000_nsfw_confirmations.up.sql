@@ -0,0 +1,6 @@
+CREATE TABLE nsfw_confirmations (
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    sphere_id BIGINT NOT NULL REFERENCES spheres (sphere_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (sphere_id, user_id)
+);
@@ -0,0 +1,7 @@
+CREATE TABLE post_drafts (
+    user_id BIGINT PRIMARY KEY REFERENCES users (user_id),
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    link TEXT,
+    edit_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
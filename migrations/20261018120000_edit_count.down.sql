@@ -0,0 +1,2 @@
+ALTER TABLE comments DROP COLUMN edit_count;
+ALTER TABLE posts DROP COLUMN edit_count;
@@ -0,0 +1,5 @@
+ALTER TABLE posts ADD COLUMN edit_count INT NOT NULL DEFAULT 0;
+ALTER TABLE comments ADD COLUMN edit_count INT NOT NULL DEFAULT 0;
+
+UPDATE posts SET edit_count = 1 WHERE edit_timestamp IS NOT NULL AND delete_timestamp IS NULL AND moderator_id IS NULL;
+UPDATE comments SET edit_count = 1 WHERE edit_timestamp IS NOT NULL AND delete_timestamp IS NULL AND moderator_id IS NULL;
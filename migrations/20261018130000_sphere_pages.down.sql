@@ -0,0 +1 @@
+DROP TABLE sphere_pages;
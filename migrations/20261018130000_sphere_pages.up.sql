@@ -0,0 +1,13 @@
+CREATE TABLE sphere_pages (
+    page_id BIGSERIAL PRIMARY KEY,
+    sphere_id BIGINT NOT NULL REFERENCES spheres (sphere_id),
+    slug TEXT NOT NULL CHECK (LENGTH(slug) <= 50),
+    title TEXT NOT NULL CHECK (LENGTH(title) <= 250),
+    markdown_body TEXT NOT NULL CHECK (LENGTH(markdown_body) <= 20000),
+    html_body TEXT NOT NULL,
+    creator_id BIGINT NOT NULL REFERENCES users (user_id),
+    editor_id BIGINT NOT NULL REFERENCES users (user_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    edit_timestamp TIMESTAMPTZ,
+    UNIQUE (sphere_id, slug)
+);
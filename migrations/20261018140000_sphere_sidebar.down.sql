@@ -0,0 +1,2 @@
+ALTER TABLE spheres DROP COLUMN sidebar_html;
+ALTER TABLE spheres DROP COLUMN sidebar_markdown;
@@ -0,0 +1,2 @@
+ALTER TABLE spheres ADD COLUMN sidebar_markdown TEXT CHECK (LENGTH(sidebar_markdown) <= 5000);
+ALTER TABLE spheres ADD COLUMN sidebar_html TEXT;
@@ -0,0 +1 @@
+ALTER TABLE spheres ADD COLUMN accent_color SMALLINT NOT NULL DEFAULT -1;
@@ -0,0 +1,3 @@
+DELETE FROM mod_log WHERE action IN (9, 10);
+ALTER TABLE mod_log DROP CONSTRAINT mod_log_action_check;
+ALTER TABLE mod_log ADD CONSTRAINT mod_log_action_check CHECK (action IN (0, 1, 2, 3, 4, 5, 6, 7, 8));
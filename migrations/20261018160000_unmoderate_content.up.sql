@@ -0,0 +1,2 @@
+ALTER TABLE mod_log DROP CONSTRAINT mod_log_action_check;
+ALTER TABLE mod_log ADD CONSTRAINT mod_log_action_check CHECK (action IN (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10));
@@ -0,0 +1,10 @@
+CREATE TABLE mod_reason_templates (
+    template_id BIGSERIAL PRIMARY KEY,
+    sphere_id BIGINT NOT NULL REFERENCES spheres (sphere_id),
+    title TEXT NOT NULL CHECK (LENGTH(title) <= 100),
+    reason TEXT NOT NULL CHECK (LENGTH(reason) <= 500),
+    creator_id BIGINT NOT NULL REFERENCES users (user_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    edit_timestamp TIMESTAMPTZ,
+    UNIQUE (sphere_id, title)
+);
@@ -0,0 +1 @@
+DROP TABLE automod_rules;
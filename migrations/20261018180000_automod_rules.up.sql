@@ -0,0 +1,9 @@
+CREATE TABLE automod_rules (
+    automod_rule_id BIGSERIAL PRIMARY KEY,
+    sphere_id BIGINT NOT NULL REFERENCES spheres (sphere_id),
+    pattern TEXT NOT NULL CHECK (LENGTH(pattern) BETWEEN 1 AND 100),
+    action SMALLINT NOT NULL CHECK (action BETWEEN 0 AND 1),
+    creator_id BIGINT NOT NULL REFERENCES users (user_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (sphere_id, pattern)
+);
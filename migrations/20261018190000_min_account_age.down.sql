@@ -0,0 +1 @@
+ALTER TABLE spheres DROP COLUMN min_account_age_days;
@@ -0,0 +1 @@
+ALTER TABLE spheres ADD COLUMN min_account_age_days INT NOT NULL DEFAULT 0 CHECK (min_account_age_days >= 0);
@@ -0,0 +1,2 @@
+ALTER TABLE spheres DROP COLUMN min_karma_type;
+ALTER TABLE spheres DROP COLUMN min_karma;
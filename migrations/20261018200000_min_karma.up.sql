@@ -0,0 +1,2 @@
+ALTER TABLE spheres ADD COLUMN min_karma INT NOT NULL DEFAULT 0 CHECK (min_karma >= 0);
+ALTER TABLE spheres ADD COLUMN min_karma_type SMALLINT NOT NULL DEFAULT 0 CHECK (min_karma_type BETWEEN 0 AND 2);
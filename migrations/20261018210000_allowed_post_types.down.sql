@@ -0,0 +1 @@
+ALTER TABLE spheres DROP COLUMN allowed_post_types;
@@ -0,0 +1 @@
+ALTER TABLE spheres ADD COLUMN allowed_post_types SMALLINT NOT NULL DEFAULT 0 CHECK (allowed_post_types BETWEEN 0 AND 3);
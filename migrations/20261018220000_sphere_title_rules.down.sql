@@ -0,0 +1,3 @@
+ALTER TABLE spheres DROP COLUMN title_regex;
+ALTER TABLE spheres DROP COLUMN max_title_length;
+ALTER TABLE spheres DROP COLUMN min_title_length;
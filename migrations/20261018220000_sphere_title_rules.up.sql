@@ -0,0 +1,3 @@
+ALTER TABLE spheres ADD COLUMN min_title_length INT NOT NULL DEFAULT 0 CHECK (min_title_length >= 0);
+ALTER TABLE spheres ADD COLUMN max_title_length INT CHECK (max_title_length > 0);
+ALTER TABLE spheres ADD COLUMN title_regex TEXT CHECK (LENGTH(title_regex) <= 200);
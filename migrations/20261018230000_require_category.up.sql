@@ -0,0 +1 @@
+ALTER TABLE spheres ADD COLUMN require_category BOOLEAN NOT NULL DEFAULT FALSE;
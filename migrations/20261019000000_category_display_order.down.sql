@@ -0,0 +1,3 @@
+DROP INDEX idx_category_order;
+CREATE INDEX idx_category_order ON sphere_categories (sphere_id, is_active, category_name);
+ALTER TABLE sphere_categories DROP COLUMN display_order;
@@ -0,0 +1,13 @@
+ALTER TABLE sphere_categories ADD COLUMN display_order INT NOT NULL DEFAULT 0;
+
+UPDATE sphere_categories c SET display_order = o.display_order
+FROM (
+    SELECT
+        category_id,
+        ROW_NUMBER() OVER (PARTITION BY sphere_id ORDER BY is_active DESC, category_name) - 1 AS display_order
+    FROM sphere_categories
+) o
+WHERE o.category_id = c.category_id;
+
+DROP INDEX idx_category_order;
+CREATE INDEX idx_category_order ON sphere_categories (sphere_id, display_order, category_name);
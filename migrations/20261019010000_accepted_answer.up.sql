@@ -0,0 +1 @@
+ALTER TABLE posts ADD COLUMN accepted_comment_id BIGINT REFERENCES comments (comment_id) ON DELETE SET NULL;
@@ -0,0 +1,11 @@
+CREATE TABLE content_reactions (
+    reaction_id BIGSERIAL PRIMARY KEY,
+    post_id BIGINT NOT NULL REFERENCES posts (post_id),
+    comment_id BIGINT REFERENCES comments (comment_id),
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    reaction TEXT NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_content_reaction UNIQUE NULLS NOT DISTINCT (post_id, comment_id, user_id, reaction)
+);
+
+CREATE INDEX idx_content_reactions_content ON content_reactions (post_id, comment_id);
@@ -0,0 +1,25 @@
+CREATE TABLE polls (
+    poll_id BIGSERIAL PRIMARY KEY,
+    post_id BIGINT UNIQUE NOT NULL REFERENCES posts (post_id),
+    ends_at TIMESTAMPTZ NOT NULL,
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE poll_options (
+    option_id BIGSERIAL PRIMARY KEY,
+    poll_id BIGINT NOT NULL REFERENCES polls (poll_id) ON DELETE CASCADE,
+    option_text TEXT NOT NULL,
+    position INT NOT NULL,
+    CONSTRAINT unique_poll_option_position UNIQUE (poll_id, position)
+);
+
+CREATE TABLE poll_votes (
+    poll_vote_id BIGSERIAL PRIMARY KEY,
+    poll_id BIGINT NOT NULL REFERENCES polls (poll_id) ON DELETE CASCADE,
+    option_id BIGINT NOT NULL REFERENCES poll_options (option_id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_poll_vote UNIQUE (poll_id, user_id)
+);
+
+CREATE INDEX idx_poll_votes_option ON poll_votes (option_id);
@@ -0,0 +1,4 @@
+DELETE FROM notifications WHERE notification_type = 4;
+ALTER TABLE notifications DROP CONSTRAINT notifications_notification_type_check;
+ALTER TABLE notifications ADD CONSTRAINT notifications_notification_type_check CHECK (notification_type IN (0, 1, 2, 3));
+DROP TABLE saved_searches;
@@ -0,0 +1,12 @@
+CREATE TABLE saved_searches (
+    saved_search_id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    search_query TEXT NOT NULL,
+    sphere_id BIGINT REFERENCES spheres (sphere_id),
+    last_run_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT unique_saved_search UNIQUE NULLS NOT DISTINCT (user_id, search_query, sphere_id)
+);
+
+ALTER TABLE notifications DROP CONSTRAINT notifications_notification_type_check;
+ALTER TABLE notifications ADD CONSTRAINT notifications_notification_type_check CHECK (notification_type IN (0, 1, 2, 3, 4));
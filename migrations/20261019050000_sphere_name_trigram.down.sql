@@ -0,0 +1 @@
+DROP INDEX idx_normalized_sphere_name_trigram;
@@ -0,0 +1 @@
+CREATE INDEX idx_normalized_sphere_name_trigram ON spheres USING GIN (normalized_sphere_name gin_trgm_ops);
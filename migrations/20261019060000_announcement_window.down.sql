@@ -0,0 +1,5 @@
+DROP INDEX idx_announcement_window;
+
+ALTER TABLE announcements
+    DROP COLUMN end_timestamp,
+    DROP COLUMN start_timestamp;
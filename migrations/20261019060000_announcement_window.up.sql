@@ -0,0 +1,6 @@
+ALTER TABLE announcements
+    ADD COLUMN start_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN end_timestamp TIMESTAMPTZ CHECK (end_timestamp > start_timestamp);
+
+CREATE INDEX idx_announcement_window ON announcements (start_timestamp, end_timestamp)
+    WHERE announcements.delete_timestamp IS NULL;
@@ -0,0 +1,7 @@
+CREATE TABLE post_idempotency_keys (
+    user_id BIGINT NOT NULL REFERENCES users (user_id),
+    idempotency_key TEXT NOT NULL,
+    post_id BIGINT REFERENCES posts (post_id),
+    create_timestamp TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, idempotency_key)
+);
@@ -0,0 +1,3 @@
+DROP INDEX idx_post_publish_timestamp;
+
+ALTER TABLE posts DROP COLUMN publish_timestamp;
@@ -0,0 +1,7 @@
+ALTER TABLE posts ADD COLUMN publish_timestamp TIMESTAMPTZ;
+
+UPDATE posts SET publish_timestamp = create_timestamp
+WHERE is_approved AND publish_at IS NULL;
+
+CREATE INDEX idx_post_publish_timestamp ON posts (publish_timestamp)
+    WHERE publish_timestamp IS NOT NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE comments
+    DROP COLUMN deleted_markdown_body,
+    DROP COLUMN deleted_body;
@@ -0,0 +1,3 @@
+ALTER TABLE comments
+    ADD COLUMN deleted_body TEXT,
+    ADD COLUMN deleted_markdown_body TEXT;
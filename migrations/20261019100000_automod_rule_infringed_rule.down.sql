@@ -0,0 +1 @@
+ALTER TABLE automod_rules DROP COLUMN rule_id;
@@ -0,0 +1 @@
+ALTER TABLE automod_rules ADD COLUMN rule_id BIGINT REFERENCES rules (rule_id);
@@ -0,0 +1,4 @@
+DROP INDEX idx_unique_username;
+
+CREATE UNIQUE INDEX idx_unique_username ON users (username)
+    WHERE users.delete_timestamp IS NULL;
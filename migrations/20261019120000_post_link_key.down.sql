@@ -0,0 +1,6 @@
+DROP INDEX idx_posts_sphere_link;
+ALTER TABLE posts DROP COLUMN link_key;
+
+CREATE INDEX idx_posts_sphere_link
+    ON posts (sphere_id, create_timestamp)
+    WHERE link_url IS NOT NULL AND moderator_id IS NULL AND delete_timestamp IS NULL;
@@ -0,0 +1,11 @@
+-- Comparison key of the link of a post, computed by the application to detect duplicate links
+ALTER TABLE posts ADD COLUMN link_key TEXT;
+
+-- Approximate the key of existing links: scheme, `www.` prefix, fragment and trailing slash are ignored
+UPDATE posts SET link_key = rtrim(regexp_replace(split_part(lower(link_url), '#', 1), '^https?://(www\.)?', ''), '/')
+WHERE link_url IS NOT NULL;
+
+DROP INDEX idx_posts_sphere_link;
+CREATE INDEX idx_posts_sphere_link
+    ON posts (sphere_id, link_key, create_timestamp DESC)
+    WHERE link_key IS NOT NULL AND moderator_id IS NULL AND delete_timestamp IS NULL;
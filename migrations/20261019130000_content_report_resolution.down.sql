@@ -0,0 +1 @@
+ALTER TABLE content_reports DROP COLUMN resolve_timestamp;
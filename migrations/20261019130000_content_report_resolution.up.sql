@@ -0,0 +1,10 @@
+-- Reports are resolved once their content is moderated and are then no longer listed
+ALTER TABLE content_reports ADD COLUMN resolve_timestamp TIMESTAMPTZ;
+
+UPDATE content_reports r SET resolve_timestamp = NOW()
+FROM posts p
+WHERE p.post_id = r.post_id AND r.comment_id IS NULL AND p.moderator_id IS NOT NULL;
+
+UPDATE content_reports r SET resolve_timestamp = NOW()
+FROM comments c
+WHERE c.comment_id = r.comment_id AND c.moderator_id IS NOT NULL;
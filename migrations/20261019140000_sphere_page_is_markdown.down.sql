@@ -0,0 +1 @@
+ALTER TABLE sphere_pages DROP COLUMN is_markdown;
@@ -0,0 +1,2 @@
+-- Whether the body of a wiki page is written in markdown or plain text
+ALTER TABLE sphere_pages ADD COLUMN is_markdown BOOLEAN NOT NULL DEFAULT TRUE;